@@ -71,6 +71,15 @@ pub enum ttfp_glyph_class {
 #[repr(C)]
 pub enum ttfp_raster_image_format {
     PNG = 0,
+    BITMAP_MONO,
+    BITMAP_MONO_PACKED,
+    BITMAP_GRAY_2,
+    BITMAP_GRAY_2_PACKED,
+    BITMAP_GRAY_4,
+    BITMAP_GRAY_4_PACKED,
+    BITMAP_GRAY_8,
+    JPEG,
+    TIFF,
 }
 
 /// @brief A glyph image.
@@ -286,9 +295,9 @@ pub extern "C" fn ttfp_is_oblique(face: *const ttfp_face) -> bool {
     face_from_ptr(face).is_oblique()
 }
 
-/// @brief Checks that face is marked as *Monospaced*.
+/// @brief Checks that face is monospaced.
 ///
-/// @return `false` when `post` table is not present.
+/// Combines the `post` table flag, the PANOSE proportion and horizontal advances.
 #[no_mangle]
 pub extern "C" fn ttfp_is_monospaced(face: *const ttfp_face) -> bool {
     face_from_ptr(face).is_monospaced()
@@ -304,7 +313,9 @@ pub extern "C" fn ttfp_is_variable(face: *const ttfp_face) -> bool {
 
 /// @brief Returns face's weight.
 ///
-/// @return Face's weight or `400` when OS/2 table is not present.
+/// Variable fonts use the current `wght` axis value.
+///
+/// @return Face's weight or `400` when not found.
 #[no_mangle]
 pub extern "C" fn ttfp_get_weight(face: *const ttfp_face) -> u16 {
     face_from_ptr(face).weight().to_number()
@@ -312,7 +323,9 @@ pub extern "C" fn ttfp_get_weight(face: *const ttfp_face) -> u16 {
 
 /// @brief Returns face's width.
 ///
-/// @return Face's width in a 1..9 range or `5` when OS/2 table is not present
+/// Variable fonts use the current `wdth` axis value.
+///
+/// @return Face's width in a 1..9 range or `5` when not found
 ///         or when value is invalid.
 #[no_mangle]
 pub extern "C" fn ttfp_get_width(face: *const ttfp_face) -> u16 {
@@ -636,6 +649,17 @@ pub extern "C" fn ttfp_get_glyph_y_origin(face: *const ttfp_face, glyph_id: Glyp
     face_from_ptr(face).glyph_y_origin(glyph_id).unwrap_or(0)
 }
 
+/// @brief Returns glyph's vertical origin Y coordinate.
+///
+/// Uses `VORG` when present. Otherwise, combines the `vmtx` top side bearing
+/// with the glyph's bounding box, falling back to the face ascender.
+///
+/// This function is affected by variation axes.
+#[no_mangle]
+pub extern "C" fn ttfp_get_glyph_ver_origin(face: *const ttfp_face, glyph_id: GlyphId) -> i16 {
+    face_from_ptr(face).glyph_ver_origin(glyph_id)
+}
+
 /// @brief Returns glyph's name.
 ///
 /// Uses the `post` and `CFF` tables as sources.
@@ -666,6 +690,21 @@ pub extern "C" fn ttfp_get_glyph_name(
     }
 }
 
+/// @brief Resolves a Glyph ID by its name.
+///
+/// Uses the `post` and `CFF` tables as sources.
+///
+/// @param name A null-terminated UTF-8 string.
+/// @return Returns 0 when glyph is not present or the name is not a valid UTF-8 string.
+#[no_mangle]
+pub extern "C" fn ttfp_get_glyph_index_by_name(face: *const ttfp_face, name: *const c_char) -> u16 {
+    let name = unsafe { std::ffi::CStr::from_ptr(name) };
+    match name.to_str() {
+        Ok(name) => face_from_ptr(face).glyph_index_by_name(name).map(|gid| gid.0).unwrap_or(0),
+        Err(_) => 0,
+    }
+}
+
 /// @brief Returns glyph's class according to Glyph Class Definition Table.
 ///
 /// @return A glyph class or TTFP_GLYPH_CLASS_UNKNOWN otherwise.
@@ -808,6 +847,15 @@ pub extern "C" fn ttfp_get_glyph_raster_image(
                     pixels_per_em: image.pixels_per_em,
                     format: match image.format {
                         ttf_parser::RasterImageFormat::PNG => ttfp_raster_image_format::PNG,
+                        ttf_parser::RasterImageFormat::BitmapMono => ttfp_raster_image_format::BITMAP_MONO,
+                        ttf_parser::RasterImageFormat::BitmapMonoPacked => ttfp_raster_image_format::BITMAP_MONO_PACKED,
+                        ttf_parser::RasterImageFormat::BitmapGray2 => ttfp_raster_image_format::BITMAP_GRAY_2,
+                        ttf_parser::RasterImageFormat::BitmapGray2Packed => ttfp_raster_image_format::BITMAP_GRAY_2_PACKED,
+                        ttf_parser::RasterImageFormat::BitmapGray4 => ttfp_raster_image_format::BITMAP_GRAY_4,
+                        ttf_parser::RasterImageFormat::BitmapGray4Packed => ttfp_raster_image_format::BITMAP_GRAY_4_PACKED,
+                        ttf_parser::RasterImageFormat::BitmapGray8 => ttfp_raster_image_format::BITMAP_GRAY_8,
+                        ttf_parser::RasterImageFormat::JPEG => ttfp_raster_image_format::JPEG,
+                        ttf_parser::RasterImageFormat::TIFF => ttfp_raster_image_format::TIFF,
                     },
                     data: image.data.as_ptr() as _,
                     len: image.data.len() as u32,
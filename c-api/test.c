@@ -153,6 +153,7 @@ int main() {
     char glyph_name[256];
     assert(ttfp_get_glyph_name(face, a_gid, glyph_name));
     assert(strcmp(glyph_name, "A") == 0);
+    assert(ttfp_get_glyph_index_by_name(face, "A") == a_gid);
 
     free(font_data);
 
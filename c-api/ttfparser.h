@@ -64,6 +64,15 @@ typedef enum {
  */
 typedef enum {
     TTFP_RASTER_IMAGE_FORMAT_PNG = 0,
+    TTFP_RASTER_IMAGE_FORMAT_BITMAP_MONO,
+    TTFP_RASTER_IMAGE_FORMAT_BITMAP_MONO_PACKED,
+    TTFP_RASTER_IMAGE_FORMAT_BITMAP_GRAY_2,
+    TTFP_RASTER_IMAGE_FORMAT_BITMAP_GRAY_2_PACKED,
+    TTFP_RASTER_IMAGE_FORMAT_BITMAP_GRAY_4,
+    TTFP_RASTER_IMAGE_FORMAT_BITMAP_GRAY_4_PACKED,
+    TTFP_RASTER_IMAGE_FORMAT_BITMAP_GRAY_8,
+    TTFP_RASTER_IMAGE_FORMAT_JPEG,
+    TTFP_RASTER_IMAGE_FORMAT_TIFF,
 } ttfp_raster_image_format;
 
 /**
@@ -294,9 +303,9 @@ bool ttfp_is_bold(const ttfp_face *face);
 bool ttfp_is_oblique(const ttfp_face *face);
 
 /**
- * @brief Checks that face is marked as *Monospaced*.
+ * @brief Checks that face is monospaced.
  *
- * @return `false` when `post` table is not present.
+ * Combines the `post` table flag, the PANOSE proportion and horizontal advances.
  */
 bool ttfp_is_monospaced(const ttfp_face *face);
 
@@ -310,14 +319,18 @@ bool ttfp_is_variable(const ttfp_face *face);
 /**
  * @brief Returns face's weight.
  *
- * @return Face's weight or `400` when OS/2 table is not present.
+ * Variable fonts use the current `wght` axis value.
+ *
+ * @return Face's weight or `400` when not found.
  */
 uint16_t ttfp_get_weight(const ttfp_face *face);
 
 /**
  * @brief Returns face's width.
  *
- * @return Face's width in a 1..9 range or `5` when OS/2 table is not present
+ * Variable fonts use the current `wdth` axis value.
+ *
+ * @return Face's width in a 1..9 range or `5` when not found
  *         or when value is invalid.
  */
 uint16_t ttfp_get_width(const ttfp_face *face);
@@ -555,6 +568,16 @@ int16_t ttfp_get_glyph_ver_side_bearing(const ttfp_face *face, uint16_t glyph_id
  */
 int16_t ttfp_get_glyph_y_origin(const ttfp_face *face, uint16_t glyph_id);
 
+/**
+ * @brief Returns glyph's vertical origin Y coordinate.
+ *
+ * Uses `VORG` when present. Otherwise, combines the `vmtx` top side bearing
+ * with the glyph's bounding box, falling back to the face ascender.
+ *
+ * This function is affected by variation axes.
+ */
+int16_t ttfp_get_glyph_ver_origin(const ttfp_face *face, uint16_t glyph_id);
+
 /**
  * @brief Returns glyph's name.
  *
@@ -567,6 +590,16 @@ int16_t ttfp_get_glyph_y_origin(const ttfp_face *face, uint16_t glyph_id);
  */
 bool ttfp_get_glyph_name(const ttfp_face *face, uint16_t glyph_id, char *name);
 
+/**
+ * @brief Resolves a Glyph ID by its name.
+ *
+ * Uses the `post` and `CFF` tables as sources.
+ *
+ * @param name A null-terminated UTF-8 string.
+ * @return Returns 0 when glyph is not present or the name is not a valid UTF-8 string.
+ */
+uint16_t ttfp_get_glyph_index_by_name(const ttfp_face *face, const char *name);
+
 /**
  * @brief Returns glyph's class according to Glyph Class Definition Table.
  *
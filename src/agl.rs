@@ -0,0 +1,160 @@
+// https://github.com/adobe-type-tools/agl-specification
+
+use std::string::String;
+
+/// Converts a glyph name to a Unicode string using the Adobe Glyph List rules.
+///
+/// Only a subset of the Adobe Glyph List is included, see `AGL_SUBSET`.
+/// `uniXXXX` and `uXXXX[XX]` names are fully supported.
+///
+/// Returns `None` when nothing can be mapped.
+pub(crate) fn glyph_name_to_unicode(name: &str) -> Option<String> {
+    // Everything after the first period is a suffix, like in `a.alt`.
+    let name = name.split('.').next().unwrap_or_default();
+
+    let mut text = String::new();
+    for component in name.split('_') {
+        if let Ok(i) = AGL_SUBSET.binary_search_by(|(n, _)| n.cmp(&component)) {
+            text.extend(core::char::from_u32(u32::from(AGL_SUBSET[i].1)));
+        } else if component.get(..3) == Some("uni") {
+            let digits = &component[3..];
+            if digits.is_empty() || digits.len() % 4 != 0 || !digits.is_ascii() {
+                continue;
+            }
+
+            let code_points: Option<String> = (0..digits.len() / 4)
+                .map(|i| parse_hex(&digits[i * 4..i * 4 + 4]))
+                .collect();
+            // The whole component is ignored when any of the code points is invalid.
+            if let Some(code_points) = code_points {
+                text.push_str(&code_points);
+            }
+        } else if component.get(..1) == Some("u") && (5..=7).contains(&component.len()) {
+            text.extend(parse_hex(&component[1..]));
+        }
+    }
+
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+fn parse_hex(s: &str) -> Option<char> {
+    // Only uppercase digits are allowed.
+    if !s.bytes().all(|c| c.is_ascii_digit() || (b'A'..=b'F').contains(&c)) {
+        return None;
+    }
+
+    // Surrogates are rejected by `from_u32`.
+    core::char::from_u32(u32::from_str_radix(s, 16).ok()?)
+}
+
+/// A subset of the [Adobe Glyph List](https://github.com/adobe-type-tools/agl-aglfn).
+///
+/// The full list has about 4500 names. Only the standard Macintosh glyph names
+/// and a few common ligatures and symbols are included, since fonts that need
+/// other names usually provide a Unicode `cmap` or use `uniXXXX` names.
+///
+/// Sorted by name.
+const AGL_SUBSET: &[(&str, u16)] = &[
+    ("A", 0x0041), ("AE", 0x00C6), ("Aacute", 0x00C1), ("Acircumflex", 0x00C2),
+    ("Adieresis", 0x00C4), ("Agrave", 0x00C0), ("Aring", 0x00C5), ("Atilde", 0x00C3),
+    ("B", 0x0042), ("C", 0x0043), ("Cacute", 0x0106), ("Ccaron", 0x010C), ("Ccedilla", 0x00C7),
+    ("D", 0x0044), ("Delta", 0x2206), ("E", 0x0045), ("Eacute", 0x00C9), ("Ecircumflex", 0x00CA),
+    ("Edieresis", 0x00CB), ("Egrave", 0x00C8), ("Eth", 0x00D0), ("Euro", 0x20AC), ("F", 0x0046),
+    ("G", 0x0047), ("Gbreve", 0x011E), ("H", 0x0048), ("I", 0x0049), ("Iacute", 0x00CD),
+    ("Icircumflex", 0x00CE), ("Idieresis", 0x00CF), ("Idotaccent", 0x0130), ("Igrave", 0x00CC),
+    ("J", 0x004A), ("K", 0x004B), ("L", 0x004C), ("Lslash", 0x0141), ("M", 0x004D), ("N", 0x004E),
+    ("Ntilde", 0x00D1), ("O", 0x004F), ("OE", 0x0152), ("Oacute", 0x00D3), ("Ocircumflex", 0x00D4),
+    ("Odieresis", 0x00D6), ("Ograve", 0x00D2), ("Omega", 0x03A9), ("Oslash", 0x00D8),
+    ("Otilde", 0x00D5), ("P", 0x0050), ("Q", 0x0051), ("R", 0x0052), ("S", 0x0053),
+    ("Scaron", 0x0160), ("Scedilla", 0x015E), ("T", 0x0054), ("Thorn", 0x00DE), ("U", 0x0055),
+    ("Uacute", 0x00DA), ("Ucircumflex", 0x00DB), ("Udieresis", 0x00DC), ("Ugrave", 0x00D9),
+    ("V", 0x0056), ("W", 0x0057), ("X", 0x0058), ("Y", 0x0059), ("Yacute", 0x00DD),
+    ("Ydieresis", 0x0178), ("Z", 0x005A), ("Zcaron", 0x017D), ("a", 0x0061), ("aacute", 0x00E1),
+    ("acircumflex", 0x00E2), ("acute", 0x00B4), ("adieresis", 0x00E4), ("ae", 0x00E6),
+    ("agrave", 0x00E0), ("ampersand", 0x0026), ("apple", 0xF8FF), ("approxequal", 0x2248),
+    ("aring", 0x00E5), ("asciicircum", 0x005E), ("asciitilde", 0x007E), ("asterisk", 0x002A),
+    ("at", 0x0040), ("atilde", 0x00E3), ("b", 0x0062), ("backslash", 0x005C), ("bar", 0x007C),
+    ("braceleft", 0x007B), ("braceright", 0x007D), ("bracketleft", 0x005B),
+    ("bracketright", 0x005D), ("breve", 0x02D8), ("brokenbar", 0x00A6), ("bullet", 0x2022),
+    ("c", 0x0063), ("cacute", 0x0107), ("caron", 0x02C7), ("ccaron", 0x010D), ("ccedilla", 0x00E7),
+    ("cedilla", 0x00B8), ("cent", 0x00A2), ("circumflex", 0x02C6), ("colon", 0x003A),
+    ("comma", 0x002C), ("copyright", 0x00A9), ("currency", 0x00A4), ("d", 0x0064),
+    ("dagger", 0x2020), ("daggerdbl", 0x2021), ("dcroat", 0x0111), ("degree", 0x00B0),
+    ("dieresis", 0x00A8), ("divide", 0x00F7), ("dollar", 0x0024), ("dotaccent", 0x02D9),
+    ("dotlessi", 0x0131), ("dotlessj", 0x0237), ("e", 0x0065), ("eacute", 0x00E9),
+    ("ecircumflex", 0x00EA), ("edieresis", 0x00EB), ("egrave", 0x00E8), ("eight", 0x0038),
+    ("ellipsis", 0x2026), ("emdash", 0x2014), ("endash", 0x2013), ("equal", 0x003D),
+    ("eth", 0x00F0), ("exclam", 0x0021), ("exclamdown", 0x00A1), ("f", 0x0066), ("ff", 0xFB00),
+    ("ffi", 0xFB03), ("ffl", 0xFB04), ("fi", 0xFB01), ("five", 0x0035), ("fl", 0xFB02),
+    ("florin", 0x0192), ("four", 0x0034), ("fraction", 0x2044), ("franc", 0x20A3), ("g", 0x0067),
+    ("gbreve", 0x011F), ("germandbls", 0x00DF), ("grave", 0x0060), ("greater", 0x003E),
+    ("greaterequal", 0x2265), ("guillemotleft", 0x00AB), ("guillemotright", 0x00BB),
+    ("guilsinglleft", 0x2039), ("guilsinglright", 0x203A), ("h", 0x0068), ("hungarumlaut", 0x02DD),
+    ("hyphen", 0x002D), ("i", 0x0069), ("iacute", 0x00ED), ("icircumflex", 0x00EE),
+    ("idieresis", 0x00EF), ("igrave", 0x00EC), ("infinity", 0x221E), ("integral", 0x222B),
+    ("j", 0x006A), ("k", 0x006B), ("l", 0x006C), ("less", 0x003C), ("lessequal", 0x2264),
+    ("logicalnot", 0x00AC), ("lozenge", 0x25CA), ("lslash", 0x0142), ("m", 0x006D),
+    ("macron", 0x00AF), ("minus", 0x2212), ("mu", 0x00B5), ("multiply", 0x00D7), ("n", 0x006E),
+    ("nbspace", 0x00A0), ("nine", 0x0039), ("nonbreakingspace", 0x00A0), ("notequal", 0x2260),
+    ("ntilde", 0x00F1), ("numbersign", 0x0023), ("o", 0x006F), ("oacute", 0x00F3),
+    ("ocircumflex", 0x00F4), ("odieresis", 0x00F6), ("oe", 0x0153), ("ogonek", 0x02DB),
+    ("ograve", 0x00F2), ("one", 0x0031), ("onehalf", 0x00BD), ("onequarter", 0x00BC),
+    ("onesuperior", 0x00B9), ("ordfeminine", 0x00AA), ("ordmasculine", 0x00BA), ("oslash", 0x00F8),
+    ("otilde", 0x00F5), ("p", 0x0070), ("paragraph", 0x00B6), ("parenleft", 0x0028),
+    ("parenright", 0x0029), ("partialdiff", 0x2202), ("percent", 0x0025), ("period", 0x002E),
+    ("periodcentered", 0x00B7), ("perthousand", 0x2030), ("pi", 0x03C0), ("plus", 0x002B),
+    ("plusminus", 0x00B1), ("product", 0x220F), ("q", 0x0071), ("question", 0x003F),
+    ("questiondown", 0x00BF), ("quotedbl", 0x0022), ("quotedblbase", 0x201E),
+    ("quotedblleft", 0x201C), ("quotedblright", 0x201D), ("quoteleft", 0x2018),
+    ("quoteright", 0x2019), ("quotesinglbase", 0x201A), ("quotesingle", 0x0027), ("r", 0x0072),
+    ("radical", 0x221A), ("registered", 0x00AE), ("ring", 0x02DA), ("s", 0x0073),
+    ("scaron", 0x0161), ("scedilla", 0x015F), ("section", 0x00A7), ("semicolon", 0x003B),
+    ("seven", 0x0037), ("sfthyphen", 0x00AD), ("six", 0x0036), ("slash", 0x002F),
+    ("space", 0x0020), ("sterling", 0x00A3), ("summation", 0x2211), ("t", 0x0074),
+    ("thorn", 0x00FE), ("three", 0x0033), ("threequarters", 0x00BE), ("threesuperior", 0x00B3),
+    ("tilde", 0x02DC), ("trademark", 0x2122), ("two", 0x0032), ("twosuperior", 0x00B2),
+    ("u", 0x0075), ("uacute", 0x00FA), ("ucircumflex", 0x00FB), ("udieresis", 0x00FC),
+    ("ugrave", 0x00F9), ("underscore", 0x005F), ("v", 0x0076), ("w", 0x0077), ("x", 0x0078),
+    ("y", 0x0079), ("yacute", 0x00FD), ("ydieresis", 0x00FF), ("yen", 0x00A5), ("z", 0x007A),
+    ("zcaron", 0x017E), ("zero", 0x0030),
+];
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_sorted() {
+        assert!(AGL_SUBSET.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn list_names() {
+        assert_eq!(glyph_name_to_unicode("A"), Some(String::from("A")));
+        assert_eq!(glyph_name_to_unicode("Adieresis"), Some(String::from("\u{C4}")));
+        assert_eq!(glyph_name_to_unicode("fi"), Some(String::from("\u{FB01}")));
+        assert_eq!(glyph_name_to_unicode("a.alt"), Some(String::from("a")));
+        assert_eq!(glyph_name_to_unicode("f_f_i.liga"), Some(String::from("ffi")));
+        assert_eq!(glyph_name_to_unicode(".notdef"), None);
+        assert_eq!(glyph_name_to_unicode("foo"), None);
+    }
+
+    #[test]
+    fn code_point_names() {
+        assert_eq!(glyph_name_to_unicode("uni0041"), Some(String::from("A")));
+        assert_eq!(glyph_name_to_unicode("uni00410042"), Some(String::from("AB")));
+        assert_eq!(glyph_name_to_unicode("uni004"), None);
+        assert_eq!(glyph_name_to_unicode("uni00e9"), None);
+        assert_eq!(glyph_name_to_unicode("uniD800"), None);
+        assert_eq!(glyph_name_to_unicode("uni\u{E9}\u{E9}"), None);
+        assert_eq!(glyph_name_to_unicode("u1F600"), Some(String::from("\u{1F600}")));
+        assert_eq!(glyph_name_to_unicode("u110000"), None);
+        assert_eq!(glyph_name_to_unicode("u41"), None);
+        assert_eq!(glyph_name_to_unicode("uni0041_u0042_c"), Some(String::from("ABc")));
+    }
+}
@@ -0,0 +1,103 @@
+use kurbo::{Affine, BezPath, Point};
+
+use crate::colr;
+use crate::OutlineBuilder;
+
+/// An `OutlineBuilder` that writes a `kurbo::BezPath`.
+///
+/// # Example
+///
+/// ```
+/// use ttf_parser::{BezPathBuilder, GlyphId};
+///
+/// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+/// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+/// let mut builder = BezPathBuilder::new();
+/// face.outline_glyph(GlyphId(1), &mut builder).unwrap();
+/// assert_eq!(builder.into_path().elements().len(), 15);
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct BezPathBuilder {
+    path: BezPath,
+}
+
+impl BezPathBuilder {
+    /// Creates a new builder.
+    #[inline]
+    pub fn new() -> Self {
+        BezPathBuilder::default()
+    }
+
+    /// Returns the path.
+    #[inline]
+    pub fn path(&self) -> &BezPath {
+        &self.path
+    }
+
+    /// Returns the path, consuming the builder.
+    #[inline]
+    pub fn into_path(self) -> BezPath {
+        self.path
+    }
+}
+
+#[inline]
+fn point(x: f32, y: f32) -> Point {
+    Point::new(f64::from(x), f64::from(y))
+}
+
+impl OutlineBuilder for BezPathBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.path.move_to(point(x, y));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.path.line_to(point(x, y));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.path.quad_to(point(x1, y1), point(x, y));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.path.curve_to(point(x1, y1), point(x2, y2), point(x, y));
+    }
+
+    fn close(&mut self) {
+        self.path.close_path();
+    }
+}
+
+impl From<colr::Transform> for Affine {
+    #[inline]
+    fn from(ts: colr::Transform) -> Self {
+        Affine::new([
+            f64::from(ts.xx), f64::from(ts.yx),
+            f64::from(ts.xy), f64::from(ts.yy),
+            f64::from(ts.dx), f64::from(ts.dy),
+        ])
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transform() {
+        let ts = colr::Transform { xx: 2.0, yx: 0.5, xy: 0.25, yy: 3.0, dx: 10.0, dy: 20.0 };
+        let p = Affine::from(ts) * Point::new(1.0, 1.0);
+        assert_eq!(p, Point::new(2.0 + 0.25 + 10.0, 0.5 + 3.0 + 20.0));
+    }
+
+    #[test]
+    fn builder() {
+        let mut builder = BezPathBuilder::new();
+        builder.move_to(1.0, 2.0);
+        builder.quad_to(3.0, 4.0, 5.0, 6.0);
+        builder.curve_to(1.0, 1.0, 2.0, 2.0, 3.0, 3.0);
+        builder.close();
+        assert_eq!(builder.path().to_svg(), "M1,2 Q3,4 5,6 C1,1 2,2 3,3 Z");
+    }
+}
@@ -0,0 +1,250 @@
+use std::vec::Vec;
+
+use crate::OutlineBuilder;
+
+/// A point of a glyph outline.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(missing_docs)]
+pub struct OutlinePoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl OutlinePoint {
+    #[inline]
+    fn new(x: f32, y: f32) -> Self {
+        OutlinePoint { x, y }
+    }
+}
+
+/// A segment of a glyph outline contour.
+///
+/// Unlike `OutlineBuilder` callbacks, each segment includes its start point.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutlineSegment {
+    /// A line from the first point to the second one.
+    Line(OutlinePoint, OutlinePoint),
+    /// A quadratic curve with a start point, a control point and an end point.
+    Quad(OutlinePoint, OutlinePoint, OutlinePoint),
+    /// A cubic curve with a start point, two control points and an end point.
+    Cubic(OutlinePoint, OutlinePoint, OutlinePoint, OutlinePoint),
+}
+
+impl OutlineSegment {
+    /// Returns the segment start point.
+    #[inline]
+    pub fn start(&self) -> OutlinePoint {
+        match *self {
+            OutlineSegment::Line(p, _) => p,
+            OutlineSegment::Quad(p, _, _) => p,
+            OutlineSegment::Cubic(p, _, _, _) => p,
+        }
+    }
+
+    /// Returns the segment end point.
+    #[inline]
+    pub fn end(&self) -> OutlinePoint {
+        match *self {
+            OutlineSegment::Line(_, p) => p,
+            OutlineSegment::Quad(_, _, p) => p,
+            OutlineSegment::Cubic(_, _, _, p) => p,
+        }
+    }
+}
+
+/// A glyph outline contour.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Contour {
+    start: OutlinePoint,
+    segments: Vec<OutlineSegment>,
+    closed: bool,
+}
+
+impl Contour {
+    /// Returns the contour start point.
+    #[inline]
+    pub fn start(&self) -> OutlinePoint {
+        self.start
+    }
+
+    /// Returns contour segments.
+    ///
+    /// A closed contour always ends at its start point,
+    /// so an implicit closing line is included.
+    #[inline]
+    pub fn segments(&self) -> &[OutlineSegment] {
+        &self.segments
+    }
+
+    /// Checks that the contour was closed.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+}
+
+/// An `OutlineBuilder` that splits an outline into contours.
+///
+/// # Example
+///
+/// ```
+/// use ttf_parser::{ContoursBuilder, GlyphId};
+///
+/// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+/// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+/// let mut builder = ContoursBuilder::new();
+/// face.outline_glyph(GlyphId(1), &mut builder).unwrap();
+/// let contours = builder.into_contours();
+/// assert_eq!(contours.len(), 2);
+/// assert_eq!(contours[1].segments().len(), 8);
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct ContoursBuilder {
+    contours: Vec<Contour>,
+}
+
+impl ContoursBuilder {
+    /// Creates a new builder.
+    #[inline]
+    pub fn new() -> Self {
+        ContoursBuilder::default()
+    }
+
+    /// Returns contours.
+    #[inline]
+    pub fn contours(&self) -> &[Contour] {
+        &self.contours
+    }
+
+    /// Returns contours, consuming the builder.
+    #[inline]
+    pub fn into_contours(self) -> Vec<Contour> {
+        self.contours
+    }
+
+    fn push(&mut self, f: impl FnOnce(OutlinePoint) -> OutlineSegment) {
+        // Segments without a preceding MoveTo start at the origin.
+        if self.contours.last().map(|c| c.closed).unwrap_or(true) {
+            self.move_to(0.0, 0.0);
+        }
+
+        if let Some(contour) = self.contours.last_mut() {
+            let current = contour.segments.last().map(|s| s.end()).unwrap_or(contour.start);
+            contour.segments.push(f(current));
+        }
+    }
+}
+
+impl OutlineBuilder for ContoursBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.contours.push(Contour {
+            start: OutlinePoint::new(x, y),
+            segments: Vec::new(),
+            closed: false,
+        });
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.push(|p0| OutlineSegment::Line(p0, OutlinePoint::new(x, y)));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.push(|p0| OutlineSegment::Quad(p0, OutlinePoint::new(x1, y1), OutlinePoint::new(x, y)));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.push(|p0| OutlineSegment::Cubic(
+            p0,
+            OutlinePoint::new(x1, y1),
+            OutlinePoint::new(x2, y2),
+            OutlinePoint::new(x, y),
+        ));
+    }
+
+    fn close(&mut self) {
+        if let Some(contour) = self.contours.last_mut() {
+            if contour.closed {
+                return;
+            }
+
+            if let Some(end) = contour.segments.last().map(|s| s.end()) {
+                if end != contour.start {
+                    contour.segments.push(OutlineSegment::Line(end, contour.start));
+                }
+            }
+
+            contour.closed = true;
+        }
+    }
+}
+
+/// An iterator over glyph outline contours.
+///
+/// Can be created via `Face::glyph_contours`.
+#[derive(Clone, Debug)]
+pub struct Contours {
+    pub(crate) contours: std::vec::IntoIter<Contour>,
+}
+
+impl Iterator for Contours {
+    type Item = Contour;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.contours.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.contours.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Contours {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f32, y: f32) -> OutlinePoint {
+        OutlinePoint::new(x, y)
+    }
+
+    #[test]
+    fn implicit_close() {
+        let mut builder = ContoursBuilder::new();
+        builder.move_to(0.0, 0.0);
+        builder.quad_to(5.0, 10.0, 10.0, 0.0);
+        builder.close();
+        builder.move_to(20.0, 0.0);
+        builder.curve_to(20.0, 10.0, 30.0, 10.0, 30.0, 0.0);
+        builder.line_to(20.0, 0.0);
+        builder.close();
+
+        let contours = builder.into_contours();
+        assert_eq!(contours.len(), 2);
+        assert!(contours[0].is_closed());
+        assert_eq!(contours[0].segments(), &[
+            OutlineSegment::Quad(point(0.0, 0.0), point(5.0, 10.0), point(10.0, 0.0)),
+            OutlineSegment::Line(point(10.0, 0.0), point(0.0, 0.0)),
+        ]);
+        assert_eq!(contours[1].start(), point(20.0, 0.0));
+        assert_eq!(contours[1].segments().len(), 2);
+        assert_eq!(contours[1].segments()[1].start(), point(30.0, 0.0));
+    }
+
+    #[test]
+    fn open_contour() {
+        let mut builder = ContoursBuilder::new();
+        builder.line_to(1.0, 2.0);
+        builder.move_to(5.0, 5.0);
+        let contours = builder.into_contours();
+        assert_eq!(contours.len(), 2);
+        assert!(!contours[0].is_closed());
+        assert_eq!(contours[0].segments(), &[OutlineSegment::Line(point(0.0, 0.0), point(1.0, 2.0))]);
+        assert!(contours[1].segments().is_empty());
+    }
+}
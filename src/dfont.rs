@@ -0,0 +1,222 @@
+// https://developer.apple.com/library/archive/documentation/mac/pdf/MoreMacintoshToolbox.pdf
+// Chapter 1, "Resource Manager", "Resource File Format".
+
+use crate::Tag;
+use crate::parser::{Stream, FromData, LazyArray16, NumFrom, Offset, Offset16, U24};
+
+const SFNT_RESOURCE_TYPE: Tag = Tag::from_bytes(b"sfnt");
+
+#[derive(Clone, Copy)]
+struct TypeListEntry {
+    resource_type: Tag,
+    // Stored as count - 1.
+    resources_count: u16,
+    reference_list_offset: Offset16,
+}
+
+impl FromData for TypeListEntry {
+    const SIZE: usize = 8;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(TypeListEntry {
+            resource_type: s.read()?,
+            resources_count: s.read()?,
+            reference_list_offset: s.read()?,
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ReferenceListEntry {
+    id: u16,
+    // 0xFFFF means no name.
+    name_offset: u16,
+    data_offset: u32,
+}
+
+impl FromData for ReferenceListEntry {
+    const SIZE: usize = 12;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let id: u16 = s.read()?;
+        let name_offset: u16 = s.read()?;
+        s.skip::<u8>(); // attributes
+        let data_offset = s.read::<U24>()?.0;
+        // Handle is ignored.
+        Some(ReferenceListEntry { id, name_offset, data_offset })
+    }
+}
+
+
+/// An `sfnt` resource of a Mac OS `.dfont` file.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SfntResource<'a> {
+    /// Resource ID.
+    pub id: u16,
+
+    /// Resource name in the Mac OS Roman encoding.
+    pub name: Option<&'a [u8]>,
+
+    /// Font data, which can be passed to `Face::from_slice`.
+    pub data: &'a [u8],
+}
+
+
+/// A Mac OS `.dfont` file.
+///
+/// It's a resource fork stored in a data fork, where each face is an `sfnt` resource.
+#[derive(Clone, Copy)]
+pub struct Dfont<'a> {
+    resources_data: &'a [u8],
+    names_data: &'a [u8],
+    references: LazyArray16<'a, ReferenceListEntry>,
+}
+
+impl<'a> Dfont<'a> {
+    /// Parses a resource map.
+    ///
+    /// Returns `None` when the data is not a resource fork or it's malformed.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let data_offset: u32 = s.read()?;
+        let map_offset: u32 = s.read()?;
+        let data_len: u32 = s.read()?;
+        let map_len: u32 = s.read()?;
+
+        let resources_data = Stream::new_at(data, usize::num_from(data_offset))?
+            .read_bytes(usize::num_from(data_len))?;
+        let map = Stream::new_at(data, usize::num_from(map_offset))?
+            .read_bytes(usize::num_from(map_len))?;
+
+        // Skip header copy, next map handle, file reference number and attributes.
+        let mut s = Stream::new_at(map, 24)?;
+        let type_list_offset: Offset16 = s.read()?;
+        let name_list_offset: Offset16 = s.read()?;
+
+        let type_list = map.get(type_list_offset.to_usize()..)?;
+        let mut s = Stream::new(type_list);
+        // Stored as count - 1, so 0xFFFF means no types.
+        let types_count = s.read::<u16>()?.wrapping_add(1);
+        let types = s.read_array16::<TypeListEntry>(types_count)?;
+        let sfnt = types.into_iter().find(|t| t.resource_type == SFNT_RESOURCE_TYPE)?;
+
+        let count = sfnt.resources_count.checked_add(1)?;
+        let references = Stream::new_at(type_list, sfnt.reference_list_offset.to_usize())?
+            .read_array16::<ReferenceListEntry>(count)?;
+
+        Some(Dfont {
+            resources_data,
+            names_data: map.get(name_list_offset.to_usize()..).unwrap_or_default(),
+            references,
+        })
+    }
+
+    /// Returns the number of `sfnt` resources.
+    #[inline]
+    pub fn len(&self) -> u16 {
+        self.references.len()
+    }
+
+    /// Checks that the file has no `sfnt` resources.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.references.is_empty()
+    }
+
+    /// Returns an `sfnt` resource by index.
+    pub fn get(&self, index: u16) -> Option<SfntResource<'a>> {
+        let reference = self.references.get(index)?;
+        let mut s = Stream::new_at(self.resources_data, usize::num_from(reference.data_offset))?;
+        let len: u32 = s.read()?;
+        let data = s.read_bytes(usize::num_from(len))?;
+
+        let name = if reference.name_offset != 0xFFFF {
+            // A Pascal string.
+            let mut s = Stream::new_at(self.names_data, usize::from(reference.name_offset))?;
+            let len: u8 = s.read()?;
+            s.read_bytes(usize::from(len))
+        } else {
+            None
+        };
+
+        Some(SfntResource { id: reference.id, name, data })
+    }
+
+    /// Returns an iterator over `sfnt` resources.
+    pub fn resources(&self) -> impl Iterator<Item = SfntResource<'a>> + 'a {
+        let dfont = *self;
+        (0..self.len()).filter_map(move |i| dfont.get(i))
+    }
+}
+
+impl core::fmt::Debug for Dfont<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Dfont()")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn resources() {
+        let data = writer::convert(&[
+            // Resource Header
+            UInt32(16), // data offset
+            UInt32(30), // map offset
+            UInt32(14), // data length
+            UInt32(74), // map length
+            // Resource Data
+            UInt32(3), // length [0]
+            UInt8(1), UInt8(2), UInt8(3), // data [0]
+            UInt32(3), // length [1]
+            UInt8(4), UInt8(5), UInt8(6), // data [1]
+            // Resource Map
+            Raw(&[0; 16]), // header copy
+            UInt32(0), // next map handle
+            UInt16(0), // file reference number
+            UInt16(0), // attributes
+            UInt16(28), // type list offset
+            UInt16(70), // name list offset
+            // Type List
+            UInt16(1), // number of types - 1
+            // Type List Entry [0]
+            Raw(b"FOND"), // type
+            UInt16(0), // number of resources - 1
+            UInt16(42), // reference list offset
+            // Type List Entry [1]
+            Raw(b"sfnt"), // type
+            UInt16(1), // number of resources - 1
+            UInt16(18), // reference list offset
+            // Reference List Entry [0]
+            UInt16(256), // id
+            UInt16(0), // name offset
+            UInt8(0), // attributes
+            UInt24(0), // data offset
+            UInt32(0), // handle
+            // Reference List Entry [1]
+            UInt16(257), // id
+            UInt16(0xFFFF), // name offset
+            UInt8(0), // attributes
+            UInt24(7), // data offset
+            UInt32(0), // handle
+            // Name List
+            UInt8(3), // length
+            Raw(b"Foo"), // name
+        ]);
+
+        let dfont = Dfont::parse(&data).unwrap();
+        assert_eq!(dfont.len(), 2);
+        assert_eq!(dfont.get(0), Some(SfntResource { id: 256, name: Some(b"Foo"), data: &[1, 2, 3] }));
+        assert_eq!(dfont.get(1), Some(SfntResource { id: 257, name: None, data: &[4, 5, 6] }));
+        assert_eq!(dfont.resources().count(), 2);
+    }
+}
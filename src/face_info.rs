@@ -0,0 +1,205 @@
+use std::string::String;
+#[cfg(feature = "variable-fonts")]
+use std::vec::Vec;
+
+use crate::{name_id, ColorGlyphFormats, Face, Style, Weight, Width};
+#[cfg(feature = "variable-fonts")]
+use crate::VariationAxis;
+
+/// A summary of face's metadata.
+///
+/// Returned by `Face::info`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FaceInfo {
+    /// *Family* name.
+    pub family_name: Option<String>,
+
+    /// *Subfamily* name, like `Bold Italic`.
+    pub subfamily_name: Option<String>,
+
+    /// *Full* name.
+    pub full_name: Option<String>,
+
+    /// *PostScript* name.
+    pub post_script_name: Option<String>,
+
+    /// Units per EM.
+    pub units_per_em: Option<u16>,
+
+    /// The number of glyphs.
+    pub number_of_glyphs: u16,
+
+    /// A horizontal ascender.
+    pub ascender: i16,
+
+    /// A horizontal descender.
+    pub descender: i16,
+
+    /// A horizontal line gap.
+    pub line_gap: i16,
+
+    /// An x height.
+    pub x_height: Option<i16>,
+
+    /// A capital height.
+    pub capital_height: Option<i16>,
+
+    /// A weight.
+    pub weight: Weight,
+
+    /// A width.
+    pub width: Width,
+
+    /// A style.
+    pub style: Style,
+
+    /// Indicates that the face is monospaced.
+    pub is_monospaced: bool,
+
+    /// Indicates that the face is variable.
+    pub is_variable: bool,
+
+    /// Variation axes.
+    #[cfg(feature = "variable-fonts")]
+    pub variation_axes: Vec<VariationAxis>,
+
+    /// Color glyph formats present in the face.
+    pub color_glyph_formats: ColorGlyphFormats,
+}
+
+impl<'a> Face<'a> {
+    /// Returns a summary of face's metadata.
+    ///
+    /// Names are in English when available.
+    /// Metrics and style are affected by variation axes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+    /// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+    /// let info = face.info();
+    /// assert_eq!(info.units_per_em, Some(1000));
+    /// assert_eq!(info.number_of_glyphs, 2);
+    /// assert!(!info.is_variable);
+    /// ```
+    pub fn info(&self) -> FaceInfo {
+        FaceInfo {
+            family_name: self.first_name(&[name_id::FAMILY]),
+            subfamily_name: self.first_name(&[name_id::SUBFAMILY]),
+            full_name: self.first_name(&[name_id::FULL_NAME]),
+            post_script_name: self.first_name(&[name_id::POST_SCRIPT_NAME]),
+            units_per_em: self.units_per_em(),
+            number_of_glyphs: self.number_of_glyphs(),
+            ascender: self.ascender(),
+            descender: self.descender(),
+            line_gap: self.line_gap(),
+            x_height: self.x_height(),
+            capital_height: self.capital_height(),
+            weight: self.weight(),
+            width: self.width(),
+            style: self.style(),
+            is_monospaced: self.is_monospaced(),
+            is_variable: self.is_variable(),
+            #[cfg(feature = "variable-fonts")]
+            variation_axes: self.variation_axes().collect(),
+            color_glyph_formats: self.color_glyph_formats(),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FontWriter, Tag};
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn names_and_metrics() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let mut writer = FontWriter::from_face(&face);
+        writer.set_table(Tag::from_bytes(b"name"), writer::convert(&[
+            UInt16(0), // format
+            UInt16(5), // count
+            UInt16(66), // storage offset
+            // Name record 0
+            UInt16(1), // platform ID: Macintosh
+            UInt16(0), // encoding ID: Roman
+            UInt16(0), // language ID: English
+            UInt16(1), // name ID: family
+            UInt16(4), // length
+            UInt16(0), // offset
+            // Name record 1
+            UInt16(1), // platform ID: Macintosh
+            UInt16(0), // encoding ID: Roman
+            UInt16(0), // language ID: English
+            UInt16(2), // name ID: subfamily
+            UInt16(7), // length
+            UInt16(4), // offset
+            // Name record 2
+            UInt16(1), // platform ID: Macintosh
+            UInt16(0), // encoding ID: Roman
+            UInt16(0), // language ID: English
+            UInt16(4), // name ID: full name
+            UInt16(12), // length
+            UInt16(11), // offset
+            // Name record 3
+            UInt16(1), // platform ID: Macintosh
+            UInt16(0), // encoding ID: Roman
+            UInt16(0), // language ID: English
+            UInt16(6), // name ID: PostScript name
+            UInt16(12), // length
+            UInt16(23), // offset
+            // Name record 4
+            UInt16(1), // platform ID: Macintosh
+            UInt16(0), // encoding ID: Roman
+            UInt16(1), // language ID: French
+            UInt16(1), // name ID: family
+            UInt16(7), // length
+            UInt16(35), // offset
+            // Storage
+            Raw(b"Demo"),
+            Raw(b"Regular"),
+            Raw(b"Demo Regular"),
+            Raw(b"Demo-Regular"),
+            Raw(b"Demo FR"),
+        ]));
+        let font = writer.finish();
+        let face = Face::from_slice(&font, 0).unwrap();
+
+        assert_eq!(face.info(), FaceInfo {
+            family_name: Some("Demo".into()),
+            subfamily_name: Some("Regular".into()),
+            full_name: Some("Demo Regular".into()),
+            post_script_name: Some("Demo-Regular".into()),
+            units_per_em: Some(1000),
+            number_of_glyphs: 2,
+            ascender: 1024,
+            descender: -400,
+            line_gap: 0,
+            x_height: None,
+            capital_height: None,
+            weight: Weight::Normal,
+            width: Width::Normal,
+            style: Style::Normal,
+            is_monospaced: false,
+            is_variable: false,
+            #[cfg(feature = "variable-fonts")]
+            variation_axes: Vec::new(),
+            color_glyph_formats: ColorGlyphFormats::default(),
+        });
+    }
+
+    #[test]
+    fn no_names() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let info = face.info();
+        assert_eq!(info.family_name, None);
+        assert_eq!(info.post_script_name, None);
+        assert_eq!(info.number_of_glyphs, face.number_of_glyphs());
+    }
+}
@@ -0,0 +1,203 @@
+use std::vec::Vec;
+
+use crate::sfnt::{
+    self, padded_len, search_params, CHECKSUM_MAGIC, HEAD_CHECKSUM_ADJUSTMENT_OFFSET, SFNT_HEADER_SIZE,
+    TABLE_RECORD_SIZE,
+};
+use crate::{Face, Tag};
+
+/// An sfnt font data writer.
+///
+/// Assembles tables into a font: builds the table directory,
+/// calculates checksums, pads tables and updates the `head` table `checkSumAdjustment`.
+/// Tables are not validated.
+///
+/// # Example
+///
+/// ```
+/// use ttf_parser::{FontWriter, Tag};
+///
+/// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+/// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+/// let mut writer = FontWriter::from_face(&face);
+/// writer.set_table(Tag::from_bytes(b"test"), vec![1, 2, 3]);
+/// let data = writer.finish();
+///
+/// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+/// assert_eq!(face.table_data(Tag::from_bytes(b"test")), Some(&[1, 2, 3][..]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct FontWriter {
+    sfnt_version: u32,
+    // Sorted by tag.
+    tables: Vec<(Tag, Vec<u8>)>,
+}
+
+impl FontWriter {
+    /// Creates a new writer without tables.
+    ///
+    /// `sfnt_version` must be `0x00010000` for TrueType outlines and `OTTO` for CFF ones.
+    #[inline]
+    pub fn new(sfnt_version: u32) -> Self {
+        FontWriter { sfnt_version, tables: Vec::new() }
+    }
+
+    /// Creates a new writer with all tables of a face.
+    ///
+    /// The sfnt version is chosen depending on `CFF ` and `CFF2` tables presence.
+    pub fn from_face(face: &Face) -> Self {
+        let is_cff = face.table_data(Tag::from_bytes(b"CFF ")).is_some()
+            || face.table_data(Tag::from_bytes(b"CFF2")).is_some();
+        let mut writer = FontWriter::new(if is_cff { 0x4F54544F } else { 0x00010000 });
+        for record in face.table_records() {
+            if let Some(data) = face.table_data(record.tag) {
+                writer.set_table(record.tag, data.to_vec());
+            }
+        }
+
+        writer
+    }
+
+    /// Returns the sfnt version.
+    #[inline]
+    pub fn sfnt_version(&self) -> u32 {
+        self.sfnt_version
+    }
+
+    /// Returns table data.
+    pub fn table(&self, tag: Tag) -> Option<&[u8]> {
+        let index = self.tables.binary_search_by_key(&tag, |t| t.0).ok()?;
+        Some(&self.tables[index].1)
+    }
+
+    /// Adds a table or replaces an existing one.
+    pub fn set_table(&mut self, tag: Tag, data: Vec<u8>) {
+        match self.tables.binary_search_by_key(&tag, |t| t.0) {
+            Ok(index) => self.tables[index].1 = data,
+            Err(index) => self.tables.insert(index, (tag, data)),
+        }
+    }
+
+    /// Removes a table.
+    ///
+    /// Returns its data if it was present.
+    pub fn remove_table(&mut self, tag: Tag) -> Option<Vec<u8>> {
+        let index = self.tables.binary_search_by_key(&tag, |t| t.0).ok()?;
+        Some(self.tables.remove(index).1)
+    }
+
+    /// Returns an iterator over table tags in ascending order.
+    pub fn tags(&self) -> impl Iterator<Item = Tag> + '_ {
+        self.tables.iter().map(|t| t.0)
+    }
+
+    /// Writes the font data.
+    pub fn finish(&self) -> Vec<u8> {
+        let num_tables = self.tables.len() as u16;
+        let mut offset = SFNT_HEADER_SIZE + TABLE_RECORD_SIZE * self.tables.len();
+        let len = offset + self.tables.iter().map(|t| padded_len(t.1.len())).sum::<usize>();
+
+        let mut data = Vec::with_capacity(len);
+        data.extend_from_slice(&self.sfnt_version.to_be_bytes());
+        let (search_range, entry_selector, range_shift) = search_params(num_tables, TABLE_RECORD_SIZE as u16);
+        for n in &[num_tables, search_range, entry_selector, range_shift] {
+            data.extend_from_slice(&n.to_be_bytes());
+        }
+
+        let mut head_offset = None;
+        for (tag, table) in &self.tables {
+            let checksum = if *tag == Tag::from_bytes(b"head") {
+                head_offset = Some(offset);
+                head_checksum(table)
+            } else {
+                table_checksum(table)
+            };
+
+            data.extend_from_slice(&tag.0.to_be_bytes());
+            data.extend_from_slice(&checksum.to_be_bytes());
+            data.extend_from_slice(&(offset as u32).to_be_bytes());
+            data.extend_from_slice(&(table.len() as u32).to_be_bytes());
+            offset += padded_len(table.len());
+        }
+
+        for (_, table) in &self.tables {
+            data.extend_from_slice(table);
+            data.resize(padded_len(data.len()), 0);
+        }
+
+        if let Some(offset) = head_offset {
+            let offset = offset + HEAD_CHECKSUM_ADJUSTMENT_OFFSET;
+            if let Some(bytes) = data.get_mut(offset..offset + 4) {
+                bytes.copy_from_slice(&[0; 4]);
+                let adjustment = CHECKSUM_MAGIC.wrapping_sub(table_checksum(&data));
+                data[offset..offset + 4].copy_from_slice(&adjustment.to_be_bytes());
+            }
+        }
+
+        data
+    }
+}
+
+/// Calculates a table checksum.
+///
+/// Data is summed as big-endian `u32` values, with the last value padded with zeros.
+#[inline]
+pub fn table_checksum(data: &[u8]) -> u32 {
+    sfnt::checksum(data, None)
+}
+
+// The `head` table checksum is calculated with a zero `checkSumAdjustment`.
+fn head_checksum(data: &[u8]) -> u32 {
+    sfnt::checksum(data, Some(HEAD_CHECKSUM_ADJUSTMENT_OFFSET))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn tables() {
+        let mut writer = FontWriter::new(0x00010000);
+        writer.set_table(Tag::from_bytes(b"efgh"), vec![1]);
+        writer.set_table(Tag::from_bytes(b"abcd"), vec![0]);
+        writer.set_table(Tag::from_bytes(b"abcd"), vec![1, 2, 3, 4, 5]);
+        writer.set_table(Tag::from_bytes(b"xxxx"), vec![]);
+        assert_eq!(writer.remove_table(Tag::from_bytes(b"xxxx")), Some(vec![]));
+        assert_eq!(writer.table(Tag::from_bytes(b"efgh")), Some(&[1][..]));
+
+        assert_eq!(writer.finish(), writer::convert(&[
+            TrueTypeMagic, // magic
+            UInt16(2), // numTables
+            UInt16(32), // searchRange
+            UInt16(1), // entrySelector
+            UInt16(0), // rangeShift
+            // Table Record [0]
+            Raw(b"abcd"), // tag
+            UInt32(0x06020304), // checksum
+            UInt32(44), // offset
+            UInt32(5), // length
+            // Table Record [1]
+            Raw(b"efgh"), // tag
+            UInt32(0x01000000), // checksum
+            UInt32(52), // offset
+            UInt32(1), // length
+            // Tables
+            UInt8(1), UInt8(2), UInt8(3), UInt8(4), UInt8(5), Raw(&[0; 3]), // table [0]
+            UInt8(1), Raw(&[0; 3]), // table [1]
+        ]));
+    }
+
+    #[test]
+    fn head_checksum_adjustment() {
+        let mut head = vec![0; 54];
+        head[8..12].copy_from_slice(&[1, 2, 3, 4]);
+        let mut writer = FontWriter::new(0x00010000);
+        writer.set_table(Tag::from_bytes(b"head"), head);
+        let data = writer.finish();
+        assert_eq!(table_checksum(&data), CHECKSUM_MAGIC);
+        // The record checksum ignores `checkSumAdjustment`.
+        assert_eq!(&data[16..20], &[0; 4]);
+    }
+}
@@ -1,10 +1,17 @@
 //! Common types for GDEF, GPOS and GSUB tables.
 
+use core::convert::TryFrom;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
 use crate::GlyphId;
-use crate::{parser::*, Tag};
+#[cfg(feature = "std")]
+use crate::GlyphSet;
+use crate::{parser::*, Limits, Tag};
 
 #[derive(Clone, Copy)]
-struct RangeRecord {
+pub(crate) struct RangeRecord {
     start_glyph_id: GlyphId,
     end_glyph_id: GlyphId,
     value: u16,
@@ -61,6 +68,106 @@ impl<'a> CoverageTable<'a> {
             _ => false,
         }
     }
+
+    /// Returns a coverage index of the glyph.
+    pub fn index(&self, glyph_id: GlyphId) -> Option<u16> {
+        let mut s = Stream::new(self.data);
+        let format: u16 = s.read()?;
+        match format {
+            1 => {
+                let count: u16 = s.read()?;
+                s.read_array16::<GlyphId>(count)?.binary_search(&glyph_id).map(|(i, _)| i)
+            }
+            2 => {
+                let count: u16 = s.read()?;
+                let records = s.read_array16::<RangeRecord>(count)?;
+                let record = records.into_iter().find(|r| r.range().contains(&glyph_id))?;
+                record.value.checked_add(glyph_id.0 - record.start_glyph_id.0)
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks that glyphs or ranges are sorted and do not overlap,
+    /// which is required for a binary search.
+    pub fn is_sorted(&self) -> Option<bool> {
+        let mut s = Stream::new(self.data);
+        let format: u16 = s.read()?;
+        match format {
+            1 => {
+                let count: u16 = s.read()?;
+                let glyphs = s.read_array16::<GlyphId>(count)?;
+                Some(glyphs.into_iter().zip(glyphs.into_iter().skip(1)).all(|(a, b)| a < b))
+            }
+            2 => {
+                let count: u16 = s.read()?;
+                let records = s.read_array16::<RangeRecord>(count)?;
+                let is_valid = records.into_iter().all(|r| r.start_glyph_id <= r.end_glyph_id);
+                let is_sorted = records.into_iter().zip(records.into_iter().skip(1))
+                    .all(|(a, b)| a.end_glyph_id < b.start_glyph_id);
+                Some(is_valid && is_sorted)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the number of glyphs or ranges stored in the table.
+    pub fn entries_count(&self) -> Option<u16> {
+        let mut s = Stream::new(self.data);
+        match s.read::<u16>()? {
+            1 | 2 => s.read(),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of covered glyphs.
+    pub fn len(&self) -> Option<u32> {
+        let mut s = Stream::new(self.data);
+        let format: u16 = s.read()?;
+        match format {
+            1 => s.read::<u16>().map(u32::from),
+            2 => {
+                let count: u16 = s.read()?;
+                let records = s.read_array16::<RangeRecord>(count)?;
+                let mut len = 0u32;
+                for r in records {
+                    let n = r.end_glyph_id.0.checked_sub(r.start_glyph_id.0)?;
+                    len += u32::from(n) + 1;
+                }
+
+                Some(len)
+            }
+            _ => None,
+        }
+    }
+
+    /// Calls `f` for each range of covered glyphs.
+    ///
+    /// Invalid ranges are ignored.
+    #[cfg(feature = "std")]
+    pub fn for_each_range(&self, mut f: impl FnMut(GlyphId, GlyphId)) -> Option<()> {
+        let mut s = Stream::new(self.data);
+        let format: u16 = s.read()?;
+        match format {
+            1 => {
+                let count: u16 = s.read()?;
+                for glyph_id in s.read_array16::<GlyphId>(count)? {
+                    f(glyph_id, glyph_id);
+                }
+            }
+            2 => {
+                let count: u16 = s.read()?;
+                for r in s.read_array16::<RangeRecord>(count)? {
+                    if r.start_glyph_id <= r.end_glyph_id {
+                        f(r.start_glyph_id, r.end_glyph_id);
+                    }
+                }
+            }
+            _ => return None,
+        }
+
+        Some(())
+    }
 }
 
 /// A value of [Class Definition Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#class-definition-table).
@@ -78,14 +185,43 @@ impl FromData for Class {
 }
 
 /// A [Class Definition Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#class-definition-table).
+///
+/// The table header is parsed once on creation.
 #[derive(Clone, Copy)]
-pub(crate) struct ClassDefinitionTable<'a> {
-    data: &'a [u8],
+pub(crate) enum ClassDefinitionTable<'a> {
+    Format1 {
+        start_glyph_id: GlyphId,
+        classes: LazyArray16<'a, Class>,
+    },
+    Format2 {
+        records: LazyArray16<'a, RangeRecord>,
+    },
+    /// A malformed or unsupported table. All glyphs belong to Class 0.
+    Empty,
 }
 
 impl<'a> ClassDefinitionTable<'a> {
     pub fn new(data: &'a [u8]) -> Self {
-        ClassDefinitionTable { data }
+        Self::parse(data).unwrap_or(ClassDefinitionTable::Empty)
+    }
+
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let format: u16 = s.read()?;
+        match format {
+            1 => {
+                let start_glyph_id: GlyphId = s.read()?;
+                let count: u16 = s.read()?;
+                let classes = s.read_array16::<Class>(count)?;
+                Some(ClassDefinitionTable::Format1 { start_glyph_id, classes })
+            }
+            2 => {
+                let count: u16 = s.read()?;
+                let records = s.read_array16::<RangeRecord>(count)?;
+                Some(ClassDefinitionTable::Format2 { records })
+            }
+            _ => None,
+        }
     }
 
     /// Any glyph not included in the range of covered glyph IDs automatically belongs to Class 0.
@@ -94,34 +230,182 @@ impl<'a> ClassDefinitionTable<'a> {
     }
 
     fn get_impl(&self, glyph_id: GlyphId) -> Option<Class> {
-        let mut s = Stream::new(self.data);
-        let format: u16 = s.read()?;
-        match format {
-            1 => {
-                let start_glyph_id: GlyphId = s.read()?;
-
+        match *self {
+            ClassDefinitionTable::Format1 { start_glyph_id, classes } => {
                 // Prevent overflow.
                 if glyph_id < start_glyph_id {
                     return None;
                 }
 
-                let count: u16 = s.read()?;
-                let classes = s.read_array16::<Class>(count)?;
                 classes.get(glyph_id.0 - start_glyph_id.0)
             }
-            2 => {
-                let count: u16 = s.read()?;
-                let records = s.read_array16::<RangeRecord>(count)?;
+            ClassDefinitionTable::Format2 { records } => {
+                // 'ClassRangeRecords must be sorted by startGlyphID in ascending order.'
                 records
-                    .into_iter()
-                    .find(|r| r.range().contains(&glyph_id))
-                    .map(|record| Class(record.value))
+                    .binary_search_by(|r| {
+                        if r.end_glyph_id < glyph_id {
+                            core::cmp::Ordering::Less
+                        } else if r.start_glyph_id > glyph_id {
+                            core::cmp::Ordering::Greater
+                        } else {
+                            core::cmp::Ordering::Equal
+                        }
+                    })
+                    .map(|(_, record)| Class(record.value))
+            }
+            ClassDefinitionTable::Empty => None,
+        }
+    }
+}
+
+/// A [Device Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#device-and-variationindex-tables).
+///
+/// Stores either hinting adjustments for specific sizes or a reference
+/// into the Item Variation Store.
+#[derive(Clone, Copy, Debug)]
+pub enum Device<'a> {
+    /// Per-size pixel adjustments.
+    Hinting(HintingDevice<'a>),
+    /// An index into the Item Variation Store.
+    Variation(VariationDevice),
+}
+
+impl<'a> Device<'a> {
+    /// Parses a Device or a VariationIndex table.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let first: u16 = s.read()?;
+        let second: u16 = s.read()?;
+        let format: u16 = s.read()?;
+        match format {
+            1..=3 => {
+                let start_size = first;
+                let end_size = second;
+                if start_size > end_size {
+                    return None;
+                }
+
+                // Each format packs 8/4/2 values into a single u16.
+                let values_per_word = 16 >> format;
+                let count = (end_size - start_size) / values_per_word + 1;
+                let delta_values = s.read_array16::<u16>(count)?;
+                Some(Device::Hinting(HintingDevice {
+                    start_size,
+                    end_size,
+                    delta_format: format,
+                    delta_values,
+                }))
+            }
+            0x8000 => {
+                Some(Device::Variation(VariationDevice {
+                    outer_index: first,
+                    inner_index: second,
+                }))
             }
             _ => None,
         }
     }
 }
 
+
+/// A hinting [Device Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#device-tables).
+#[derive(Clone, Copy, Debug)]
+pub struct HintingDevice<'a> {
+    start_size: u16,
+    end_size: u16,
+    delta_format: u16,
+    delta_values: LazyArray16<'a, u16>,
+}
+
+impl<'a> HintingDevice<'a> {
+    /// Returns the smallest size to correct, in ppem.
+    #[inline]
+    pub fn start_size(&self) -> u16 {
+        self.start_size
+    }
+
+    /// Returns the largest size to correct, in ppem.
+    #[inline]
+    pub fn end_size(&self) -> u16 {
+        self.end_size
+    }
+
+    /// Returns an adjustment in pixels for the specified size.
+    ///
+    /// Sizes outside the `start_size..=end_size` range have no adjustment.
+    pub fn delta(&self, pixels_per_em: u16) -> i8 {
+        self.delta_impl(pixels_per_em).unwrap_or(0)
+    }
+
+    /// Returns an adjustment in pixels for a fractional size.
+    ///
+    /// Unlike `delta`, which is defined only for integer sizes,
+    /// this method linearly interpolates between the two nearest sizes.
+    /// Useful when text is scaled linearly and a sudden jump at an integer
+    /// size boundary is undesirable.
+    ///
+    /// Returns `0.0` for a negative, NaN or infinite size.
+    pub fn delta_interpolated(&self, pixels_per_em: f32) -> f32 {
+        if !pixels_per_em.is_finite() || pixels_per_em < 0.0 {
+            return 0.0;
+        }
+
+        // Sizes above `u16::MAX` are outside of any range anyway.
+        if pixels_per_em > f32::from(self.end_size) + 1.0 {
+            return 0.0;
+        }
+
+        // `f32::floor` is not available in `no_std`, but the value is non-negative.
+        let lower = pixels_per_em as u16;
+        let t = pixels_per_em - f32::from(lower);
+        let lower_delta = f32::from(self.delta(lower));
+        if t == 0.0 {
+            return lower_delta;
+        }
+
+        let upper_delta = match lower.checked_add(1) {
+            Some(upper) => f32::from(self.delta(upper)),
+            None => 0.0,
+        };
+
+        lower_delta + (upper_delta - lower_delta) * t
+    }
+
+    fn delta_impl(&self, pixels_per_em: u16) -> Option<i8> {
+        if pixels_per_em < self.start_size || pixels_per_em > self.end_size {
+            return None;
+        }
+
+        let bits = 1 << self.delta_format; // 2, 4 or 8
+        let values_per_word = 16 / bits;
+        let index = pixels_per_em - self.start_size;
+        let word = self.delta_values.get(index / values_per_word)?;
+        let shift = 16 - bits * (index % values_per_word + 1);
+        let mask = (1u16 << bits) - 1;
+        let value = (word >> shift) & mask;
+
+        // Sign-extend a value.
+        let sign_bit = 1 << (bits - 1);
+        let value = if value & sign_bit != 0 {
+            i16::try_from(value).ok()? - (1 << bits)
+        } else {
+            i16::try_from(value).ok()?
+        };
+
+        i8::try_from(value).ok()
+    }
+}
+
+
+/// A [VariationIndex Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#variationindex-table).
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug)]
+pub struct VariationDevice {
+    pub outer_index: u16,
+    pub inner_index: u16,
+}
+
+
 /// A [Script List Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#script-list-table-and-script-record).
 #[derive(Clone, Copy)]
 pub(crate) struct ScriptListTable<'a> {
@@ -138,6 +422,28 @@ impl<'a> ScriptListTable<'a> {
             script_records: s.read_array16(count)?,
         })
     }
+
+    /// Returns a script with a specified tag.
+    pub(crate) fn script(&self, tag: Tag) -> Option<Script<'a>> {
+        let record = self.script_records.into_iter().find(|r| r.script_tag == tag)?;
+        Script::parse(self.data.get(record.script_offset.to_usize()..)?)
+    }
+
+    /// Resolves a language system.
+    ///
+    /// Scripts are tried in order, followed by `DFLT` and the first script in the list.
+    /// When a script doesn't have the requested language, its default language system is used.
+    pub(crate) fn lang_sys(&self, scripts: &[Tag], language: Option<Tag>) -> Option<LangSysTable<'a>> {
+        let script = scripts.iter()
+            .chain(core::iter::once(&Tag::from_bytes(b"DFLT")))
+            .find_map(|tag| self.script(*tag))
+            .or_else(|| {
+                let record = self.script_records.get(0)?;
+                Script::parse(self.data.get(record.script_offset.to_usize()..)?)
+            })?;
+
+        script.lang_sys(language)
+    }
 }
 
 /// A [Script Record](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#script-list-table-and-script-record).
@@ -161,6 +467,7 @@ impl FromData for ScriptRecord {
 /// A [Script](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#script-table-and-language-system-record).
 #[derive(Clone, Copy)]
 pub(crate) struct Script<'a> {
+    data: &'a [u8],
     default_lang_sys_offset: Option<Offset16>,
     lang_sys_records: LazyArray16<'a, LangSysRecord>,
 }
@@ -171,10 +478,21 @@ impl<'a> Script<'a> {
         let default_lang_sys_offset = s.read()?;
         let count = s.read()?;
         Some(Self {
+            data,
             default_lang_sys_offset,
             lang_sys_records: s.read_array16(count)?,
         })
     }
+
+    /// Returns a language system with a specified tag,
+    /// falling back to the default one.
+    pub(crate) fn lang_sys(&self, language: Option<Tag>) -> Option<LangSysTable<'a>> {
+        let offset = language
+            .and_then(|tag| self.lang_sys_records.into_iter().find(|r| r.lang_sys_tag == tag))
+            .map(|record| record.lang_sys_offset)
+            .or(self.default_lang_sys_offset)?;
+        LangSysTable::parse(self.data.get(offset.to_usize()..)?)
+    }
 }
 
 /// A [Language System Record](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#script-table-and-language-system-record).
@@ -197,7 +515,7 @@ impl FromData for LangSysRecord {
 
 /// A [Language System Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#language-system-table).
 #[derive(Clone, Copy)]
-pub(crate) struct LangSysTable<'a> {
+pub struct LangSysTable<'a> {
     required_feature_index: Option<u16>,
     feature_indices: LazyArray16<'a, u16>,
 }
@@ -220,11 +538,34 @@ impl<'a> LangSysTable<'a> {
             feature_indices: s.read_array16(count)?,
         })
     }
+
+    /// Returns indices of features in the feature list.
+    ///
+    /// Doesn't include the required feature.
+    #[inline]
+    pub fn feature_indices(&self) -> LazyArray16<'a, u16> {
+        self.feature_indices
+    }
+
+    /// Returns an index of a feature required by this language system.
+    ///
+    /// Such feature must always be applied, regardless of user settings.
+    #[inline]
+    pub fn required_feature_index(&self) -> Option<u16> {
+        self.required_feature_index
+    }
+}
+
+impl core::fmt::Debug for LangSysTable<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "LangSysTable()")
+    }
 }
 
 /// A [Feature List Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#feature-list-table).
 #[derive(Clone, Copy)]
 pub(crate) struct FeatureListTable<'a> {
+    data: &'a [u8],
     feature_records: LazyArray16<'a, FeatureRecord>,
 }
 
@@ -233,9 +574,24 @@ impl<'a> FeatureListTable<'a> {
         let mut s = Stream::new(data);
         let count = s.read()?;
         Some(Self {
+            data,
             feature_records: s.read_array16(count)?,
         })
     }
+
+    /// Returns a tag of a feature at a specified index.
+    pub(crate) fn feature_tag(&self, index: u16) -> Option<Tag> {
+        self.feature_records.get(index).map(|record| record.feature_tag)
+    }
+
+    /// Returns an iterator over feature tables with a specified tag.
+    pub(crate) fn features(&self, tag: Tag) -> impl Iterator<Item = FeatureTable<'a>> + 'a {
+        let data = self.data;
+        self.feature_records
+            .into_iter()
+            .filter(move |record| record.feature_tag == tag)
+            .filter_map(move |record| FeatureTable::parse(data.get(record.feature_offset.to_usize()..)?))
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -272,11 +628,17 @@ impl<'a> FeatureTable<'a> {
             lookup_list_indices: s.read_array16(count)?,
         })
     }
+
+    #[inline]
+    pub(crate) fn lookup_indices(&self) -> LazyArray16<'a, u16> {
+        self.lookup_list_indices
+    }
 }
 
 /// A [Lookup List Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#lookup-list-table).
 #[derive(Clone, Copy)]
 pub(crate) struct LookupListTable<'a> {
+    data: &'a [u8],
     lookup_offsets: LazyArray16<'a, Offset16>,
 }
 
@@ -285,14 +647,26 @@ impl<'a> LookupListTable<'a> {
         let mut s = Stream::new(data);
         let count = s.read()?;
         Some(Self {
+            data,
             lookup_offsets: s.read_array16(count)?,
         })
     }
+
+    #[inline]
+    pub(crate) fn len(&self) -> u16 {
+        self.lookup_offsets.len()
+    }
+
+    pub(crate) fn get(&self, index: u16) -> Option<LookupTable<'a>> {
+        let offset = self.lookup_offsets.get(index)?;
+        LookupTable::parse(self.data.get(offset.to_usize()..)?)
+    }
 }
 
 /// A [Lookup Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#lookup-table).
 #[derive(Clone, Copy)]
 pub(crate) struct LookupTable<'a> {
+    data: &'a [u8],
     lookup_type: u16,
     lookup_flag: u16,
     subtable_offsets: LazyArray16<'a, Offset16>,
@@ -303,23 +677,660 @@ impl<'a> LookupTable<'a> {
     pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
         let mut s = Stream::new(data);
         let lookup_type = s.read()?;
-        let lookup_flag = s.read()?;
+        let lookup_flag: u16 = s.read()?;
         let count = s.read()?;
         let subtable_offsets = s.read_array16(count)?;
-        let mark_filtering_set = s.read()?;
+        // Present only when the `useMarkFilteringSet` flag is set.
+        let mark_filtering_set = if lookup_flag & 0x0010 != 0 { s.read()? } else { 0 };
         Some(Self {
+            data,
             lookup_type,
             lookup_flag,
             subtable_offsets,
             mark_filtering_set,
         })
     }
+
+    #[inline]
+    pub(crate) fn lookup_type(&self) -> u16 {
+        self.lookup_type
+    }
+
+    pub(crate) fn subtables(&self) -> impl Iterator<Item = &'a [u8]> + 'a {
+        let data = self.data;
+        self.subtable_offsets
+            .into_iter()
+            .filter_map(move |offset| data.get(offset.to_usize()..))
+    }
+}
+
+/// A kind of a lookup list.
+///
+/// Lookup types are table-specific, so we have to know
+/// which table a lookup belongs to.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum LookupListKind {
+    Substitution,
+    Positioning,
+}
+
+impl LookupListKind {
+    fn context_type(self) -> u16 {
+        match self {
+            LookupListKind::Substitution => 5,
+            LookupListKind::Positioning => 7,
+        }
+    }
+
+    fn chained_context_type(self) -> u16 {
+        match self {
+            LookupListKind::Substitution => 6,
+            LookupListKind::Positioning => 8,
+        }
+    }
+
+    fn extension_type(self) -> u16 {
+        match self {
+            LookupListKind::Substitution => 7,
+            LookupListKind::Positioning => 9,
+        }
+    }
+}
+
+/// A [Sequence Lookup Record](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#sequence-lookup-record).
+#[cfg(feature = "std")]
+#[derive(Clone, Copy)]
+struct SequenceLookupRecord {
+    #[allow(dead_code)]
+    sequence_index: u16,
+    lookup_list_index: u16,
+}
+
+#[cfg(feature = "std")]
+impl FromData for SequenceLookupRecord {
+    const SIZE: usize = 4;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(Self {
+            sequence_index: s.read()?,
+            lookup_list_index: s.read()?,
+        })
+    }
+}
+
+/// Calls `f` for each lookup index referenced by a lookup subtable.
+///
+/// Only contextual, chained contextual and extension subtables can reference other lookups.
+/// The same index can be reported multiple times.
+///
+/// Only the first `max_rules` sequence rules of each rule set are checked.
+#[cfg(feature = "std")]
+pub(crate) fn nested_lookups(
+    kind: LookupListKind,
+    lookup_type: u16,
+    data: &[u8],
+    max_rules: u16,
+    f: &mut dyn FnMut(u16),
+) -> Option<()> {
+    let mut s = Stream::new(data);
+    if lookup_type == kind.extension_type() {
+        let format: u16 = s.read()?;
+        if format != 1 {
+            return None;
+        }
+
+        let extension_type: u16 = s.read()?;
+        let offset: Offset32 = s.read()?;
+        // An extension subtable cannot reference another extension subtable.
+        if extension_type == kind.extension_type() {
+            return None;
+        }
+
+        return nested_lookups(kind, extension_type, data.get(offset.to_usize()..)?, max_rules, f);
+    }
+
+    let chained = if lookup_type == kind.context_type() {
+        false
+    } else if lookup_type == kind.chained_context_type() {
+        true
+    } else {
+        return Some(());
+    };
+
+    let format: u16 = s.read()?;
+    match format {
+        1 | 2 => {
+            s.skip::<Offset16>(); // coverage
+            if format == 2 {
+                if chained {
+                    s.skip::<Offset16>(); // backtrack class definition
+                    s.skip::<Offset16>(); // input class definition
+                    s.skip::<Offset16>(); // lookahead class definition
+                } else {
+                    s.skip::<Offset16>(); // class definition
+                }
+            }
+
+            let count: u16 = s.read()?;
+            let rule_sets = s.read_array16::<Option<Offset16>>(count)?;
+            for rule_set_offset in rule_sets.into_iter().flatten() {
+                let rule_set_data = data.get(rule_set_offset.to_usize()..)?;
+                let mut s = Stream::new(rule_set_data);
+                let count: u16 = s.read()?;
+                let rules = s.read_array16::<Offset16>(count)?;
+                for rule_offset in rules.into_iter().take(usize::from(max_rules)) {
+                    let mut s = Stream::new_at(rule_set_data, rule_offset.to_usize())?;
+                    let records = if chained {
+                        parse_chained_sequence_rule(&mut s)
+                    } else {
+                        parse_sequence_rule(&mut s)
+                    };
+
+                    for record in records? {
+                        f(record.lookup_list_index);
+                    }
+                }
+            }
+        }
+        3 => {
+            let records = if chained {
+                let backtrack_count: u16 = s.read()?;
+                s.advance(usize::from(backtrack_count) * Offset16::SIZE); // coverages
+                let input_count: u16 = s.read()?;
+                s.advance(usize::from(input_count) * Offset16::SIZE); // coverages
+                let lookahead_count: u16 = s.read()?;
+                s.advance(usize::from(lookahead_count) * Offset16::SIZE); // coverages
+                let lookup_count: u16 = s.read()?;
+                s.read_array16::<SequenceLookupRecord>(lookup_count)?
+            } else {
+                let glyph_count: u16 = s.read()?;
+                let lookup_count: u16 = s.read()?;
+                s.advance(usize::from(glyph_count) * Offset16::SIZE); // coverages
+                s.read_array16::<SequenceLookupRecord>(lookup_count)?
+            };
+
+            for record in records {
+                f(record.lookup_list_index);
+            }
+        }
+        _ => return None,
+    }
+
+    Some(())
+}
+
+#[cfg(feature = "std")]
+fn parse_sequence_rule<'a>(s: &mut Stream<'a>) -> Option<LazyArray16<'a, SequenceLookupRecord>> {
+    let glyph_count: u16 = s.read()?;
+    let lookup_count: u16 = s.read()?;
+    // The first glyph is matched by the coverage table.
+    s.advance(usize::from(glyph_count.checked_sub(1)?) * u16::SIZE);
+    s.read_array16::<SequenceLookupRecord>(lookup_count)
+}
+
+#[cfg(feature = "std")]
+fn parse_chained_sequence_rule<'a>(
+    s: &mut Stream<'a>,
+) -> Option<LazyArray16<'a, SequenceLookupRecord>> {
+    let backtrack_count: u16 = s.read()?;
+    s.advance(usize::from(backtrack_count) * u16::SIZE);
+    let input_count: u16 = s.read()?;
+    s.advance(usize::from(input_count.checked_sub(1)?) * u16::SIZE);
+    let lookahead_count: u16 = s.read()?;
+    s.advance(usize::from(lookahead_count) * u16::SIZE);
+    let lookup_count: u16 = s.read()?;
+    s.read_array16::<SequenceLookupRecord>(lookup_count)
+}
+
+/// Returns an offset to the coverage table that matches the first glyph of a subtable.
+///
+/// For format 3 contextual subtables, the first input coverage is used.
+/// For mark attachment subtables, the mark coverage is used.
+///
+/// The stream must be positioned right after the subtable format.
+fn primary_coverage_offset(
+    kind: LookupListKind,
+    lookup_type: u16,
+    format: u16,
+    s: &mut Stream,
+) -> Option<Offset16> {
+    let is_context = lookup_type == kind.context_type()
+        || lookup_type == kind.chained_context_type();
+    if is_context && format == 3 {
+        if lookup_type == kind.chained_context_type() {
+            let backtrack_count: u16 = s.read()?;
+            s.advance(usize::from(backtrack_count) * Offset16::SIZE);
+            s.skip::<u16>(); // input glyph count
+        } else {
+            s.skip::<u16>(); // glyph count
+            s.skip::<u16>(); // sequence lookup count
+        }
+
+        s.read()
+    } else if format == 3 {
+        // Only contextual subtables have format 3.
+        None
+    } else {
+        s.read()
+    }
+}
+
+/// A graph of lookups referenced by other lookups.
+///
+/// Contextual and chained contextual lookups can apply other lookups
+/// via sequence lookup records. This graph stores such references,
+/// which allows finding unreachable lookups and detecting cycles in malformed fonts.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct LookupGraph {
+    edges: Vec<Vec<u16>>,
+    max_nesting: u16,
+}
+
+#[cfg(feature = "std")]
+impl LookupGraph {
+    pub(crate) fn new(list: LookupListTable, kind: LookupListKind, limits: Limits) -> Self {
+        let mut edges = Vec::with_capacity(usize::from(list.len()));
+        for index in 0..list.len() {
+            let mut nested = Vec::new();
+            if let Some(lookup) = list.get(index) {
+                for data in lookup.subtables() {
+                    // Malformed subtables are ignored.
+                    let _ = nested_lookups(
+                        kind, lookup.lookup_type, data, limits.max_contexts_per_glyph,
+                        &mut |i| nested.push(i),
+                    );
+                }
+            }
+
+            nested.sort_unstable();
+            nested.dedup();
+            edges.push(nested);
+        }
+
+        LookupGraph { edges, max_nesting: limits.max_lookup_nesting }
+    }
+
+    /// Returns the number of lookups in the graph.
+    #[inline]
+    pub fn len(&self) -> u16 {
+        self.edges.len() as u16
+    }
+
+    /// Checks that the graph has no lookups.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Returns sorted, unique indices of lookups directly referenced by a specified lookup.
+    ///
+    /// Indices are not validated, so they can be out of bounds in malformed fonts.
+    pub fn dependencies(&self, lookup_index: u16) -> &[u16] {
+        self.edges.get(usize::from(lookup_index)).map(|v| v.as_slice()).unwrap_or(&[])
+    }
+
+    /// Returns a per-lookup flag indicating that a lookup is reachable
+    /// from any of the `roots`, directly or via other lookups.
+    ///
+    /// Roots are usually lookups referenced by features.
+    /// Lookups nested deeper than `Limits::max_lookup_nesting` are not reported.
+    pub fn reachable(&self, roots: &[u16]) -> Vec<bool> {
+        let mut visited = vec![false; self.edges.len()];
+        // Lookups are visited level by level, so each one is reached via the shortest path.
+        let mut level: Vec<u16> = roots.to_vec();
+        let mut depth = 0;
+        while !level.is_empty() {
+            let mut next = Vec::new();
+            for index in level {
+                match visited.get_mut(usize::from(index)) {
+                    Some(v) if !*v => *v = true,
+                    _ => continue,
+                }
+
+                if depth < self.max_nesting {
+                    next.extend_from_slice(self.dependencies(index));
+                }
+            }
+
+            level = next;
+            depth += 1;
+        }
+
+        visited
+    }
+
+    /// Checks that the graph has a cycle.
+    ///
+    /// A valid font must not have one.
+    #[inline]
+    pub fn has_cycle(&self) -> bool {
+        self.find_cycle().is_some()
+    }
+
+    /// Returns lookup indices forming a cycle, if any.
+    ///
+    /// The first lookup in the returned list references the second one and so on,
+    /// while the last one references the first one.
+    pub fn find_cycle(&self) -> Option<Vec<u16>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            New,
+            InProgress,
+            Done,
+        }
+
+        let mut states = vec![State::New; self.edges.len()];
+        for root in 0..self.edges.len() {
+            if states[root] != State::New {
+                continue;
+            }
+
+            // A stack of (lookup index, next dependency index) pairs.
+            // Also acts as the current path.
+            let mut stack = vec![(root as u16, 0)];
+            states[root] = State::InProgress;
+            while let Some(&(index, next)) = stack.last() {
+                if let Some(&dep) = self.dependencies(index).get(next) {
+                    if let Some(last) = stack.last_mut() {
+                        last.1 += 1;
+                    }
+
+                    match states.get(usize::from(dep)) {
+                        Some(State::New) => {
+                            states[usize::from(dep)] = State::InProgress;
+                            stack.push((dep, 0));
+                        }
+                        Some(State::InProgress) => {
+                            let start = stack.iter().position(|(i, _)| *i == dep)?;
+                            return Some(stack[start..].iter().map(|(i, _)| *i).collect());
+                        }
+                        _ => {}
+                    }
+                } else {
+                    states[usize::from(index)] = State::Done;
+                    stack.pop();
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Per-lookup sets of glyphs a lookup can start matching at.
+///
+/// Allows skipping lookups that cannot apply to the current glyph
+/// without checking coverage tables of each subtable.
+///
+/// Lookups with malformed subtables skip those subtables.
+/// When `Limits::max_coverage_entries` is reached, remaining lookups are treated
+/// as applicable to any glyph.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct LookupCoverages {
+    // `None` matches any glyph.
+    lookups: Vec<Option<GlyphSet>>,
+}
+
+#[cfg(feature = "std")]
+impl LookupCoverages {
+    pub(crate) fn new(list: LookupListTable, kind: LookupListKind, limits: Limits) -> Self {
+        let mut entries_left = limits.max_coverage_entries;
+        let mut lookups = Vec::with_capacity(usize::from(list.len()));
+        for index in 0..list.len() {
+            let mut glyphs = Some(GlyphSet::new());
+            if let Some(lookup) = list.get(index) {
+                for data in lookup.subtables() {
+                    let coverage = match subtable_coverage(kind, lookup.lookup_type, data) {
+                        Some(v) => v,
+                        None => continue,
+                    };
+
+                    let entries_count = u32::from(coverage.entries_count().unwrap_or(0));
+                    if entries_count > entries_left {
+                        glyphs = None;
+                        break;
+                    }
+
+                    entries_left -= entries_count;
+                    if let Some(ref mut glyphs) = glyphs {
+                        coverage.for_each_range(|start, end| glyphs.insert_range(start..=end));
+                    }
+                }
+            }
+
+            lookups.push(glyphs);
+        }
+
+        LookupCoverages { lookups }
+    }
+
+    /// Returns the number of lookups.
+    #[inline]
+    pub fn len(&self) -> u16 {
+        self.lookups.len() as u16
+    }
+
+    /// Checks that there are no lookups.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.lookups.is_empty()
+    }
+
+    /// Checks that a lookup can start matching at a specified glyph.
+    ///
+    /// Returns `false` for an out of bounds lookup index.
+    #[inline]
+    pub fn may_apply(&self, lookup_index: u16, glyph_id: GlyphId) -> bool {
+        match self.lookups.get(usize::from(lookup_index)) {
+            Some(Some(glyphs)) => glyphs.contains(glyph_id),
+            Some(None) => true,
+            None => false,
+        }
+    }
+
+    /// Returns glyphs a lookup can start matching at.
+    ///
+    /// Returns `None` for an out of bounds lookup index
+    /// or when the lookup was not checked because of `Limits::max_coverage_entries`.
+    #[inline]
+    pub fn glyphs(&self, lookup_index: u16) -> Option<&GlyphSet> {
+        self.lookups.get(usize::from(lookup_index))?.as_ref()
+    }
+}
+
+#[cfg(feature = "std")]
+fn subtable_coverage<'a>(
+    kind: LookupListKind,
+    lookup_type: u16,
+    data: &'a [u8],
+) -> Option<CoverageTable<'a>> {
+    let mut s = Stream::new(data);
+    let format: u16 = s.read()?;
+    if lookup_type == kind.extension_type() {
+        if format != 1 {
+            return None;
+        }
+
+        let extension_type: u16 = s.read()?;
+        let offset: Offset32 = s.read()?;
+        // An extension subtable cannot reference another extension subtable.
+        if extension_type == kind.extension_type() {
+            return None;
+        }
+
+        return subtable_coverage(kind, extension_type, data.get(offset.to_usize()..)?);
+    }
+
+    if lookup_type == 0 || lookup_type > MAX_LOOKUP_TYPE {
+        return None;
+    }
+
+    if format == 0 || format > MAX_SUBTABLE_FORMAT {
+        return None;
+    }
+
+    let offset = primary_coverage_offset(kind, lookup_type, format, &mut s)?;
+    Some(CoverageTable::new(data.get(offset.to_usize()..)?))
+}
+
+/// Maximum lookup type. GSUB has 8 and GPOS has 9 lookup types.
+const MAX_LOOKUP_TYPE: u16 = 9;
+
+/// Maximum subtable format. Only contextual subtables have 3 formats.
+const MAX_SUBTABLE_FORMAT: u16 = 3;
+
+/// A summary of lookups in a
+/// [Lookup List Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#lookup-list-table).
+///
+/// Mainly useful for diagnostics.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct LookupStatistics {
+    lookups: [u16; MAX_LOOKUP_TYPE as usize],
+    subtables: [[u32; MAX_SUBTABLE_FORMAT as usize]; MAX_LOOKUP_TYPE as usize],
+
+    /// The total number of lookups.
+    pub lookups_count: u16,
+
+    /// The total number of subtables.
+    ///
+    /// Extension subtables are counted once.
+    pub subtables_count: u32,
+
+    /// The number of subtables wrapped into an extension subtable.
+    pub extension_subtables_count: u32,
+
+    /// The number of subtables that cannot be parsed
+    /// or have an unknown lookup type or format.
+    pub malformed_subtables_count: u32,
+
+    /// The total number of glyphs in the primary coverage tables of all subtables.
+    ///
+    /// For format 3 contextual subtables, the first input coverage is used.
+    /// For mark attachment subtables, the mark coverage is used.
+    pub coverage_glyphs_count: u32,
+
+    /// The number of primary coverage tables that are not sorted.
+    ///
+    /// Glyphs from such tables may not be found.
+    pub unsorted_coverages_count: u32,
+
+    /// The number of primary coverage tables that were not checked,
+    /// because `Limits::max_coverage_entries` was reached.
+    pub skipped_coverages_count: u32,
+}
+
+impl LookupStatistics {
+    pub(crate) fn new(list: LookupListTable, kind: LookupListKind, limits: Limits) -> Self {
+        let mut stats = LookupStatistics::default();
+        let mut entries_left = limits.max_coverage_entries;
+        for index in 0..list.len() {
+            stats.lookups_count += 1;
+
+            let lookup = match list.get(index) {
+                Some(v) => v,
+                None => continue,
+            };
+
+            if let Some(n) = stats.lookups.get_mut(usize::from(lookup.lookup_type).wrapping_sub(1)) {
+                *n += 1;
+            }
+
+            for data in lookup.subtables() {
+                stats.subtables_count += 1;
+                if stats.add_subtable(kind, lookup.lookup_type, data, &mut entries_left).is_none() {
+                    stats.malformed_subtables_count += 1;
+                }
+            }
+        }
+
+        stats
+    }
+
+    fn add_subtable(
+        &mut self,
+        kind: LookupListKind,
+        lookup_type: u16,
+        data: &[u8],
+        entries_left: &mut u32,
+    ) -> Option<()> {
+        let mut s = Stream::new(data);
+        let format: u16 = s.read()?;
+        if lookup_type == kind.extension_type() {
+            if format != 1 {
+                return None;
+            }
+
+            let extension_type: u16 = s.read()?;
+            let offset: Offset32 = s.read()?;
+            // An extension subtable cannot reference another extension subtable.
+            if extension_type == kind.extension_type() {
+                return None;
+            }
+
+            self.extension_subtables_count += 1;
+            return self.add_subtable(kind, extension_type, data.get(offset.to_usize()..)?, entries_left);
+        }
+
+        if lookup_type == 0 || lookup_type > MAX_LOOKUP_TYPE {
+            return None;
+        }
+
+        if format == 0 || format > MAX_SUBTABLE_FORMAT {
+            return None;
+        }
+
+        let coverage_offset = primary_coverage_offset(kind, lookup_type, format, &mut s)?;
+        let coverage = CoverageTable::new(data.get(coverage_offset.to_usize()..)?);
+        let entries_count = u32::from(coverage.entries_count()?);
+        if entries_count > *entries_left {
+            self.subtables[usize::from(lookup_type - 1)][usize::from(format - 1)] += 1;
+            self.skipped_coverages_count += 1;
+            return Some(());
+        }
+
+        *entries_left -= entries_count;
+        let coverage_len = coverage.len()?;
+        if coverage.is_sorted() == Some(false) {
+            self.unsorted_coverages_count += 1;
+        }
+
+        self.subtables[usize::from(lookup_type - 1)][usize::from(format - 1)] += 1;
+        self.coverage_glyphs_count = self.coverage_glyphs_count.saturating_add(coverage_len);
+        Some(())
+    }
+
+    /// Returns the number of lookups with a specified type.
+    ///
+    /// Extension lookups are counted using the extension lookup type.
+    #[inline]
+    pub fn lookups_with_type(&self, lookup_type: u16) -> u16 {
+        let index = usize::from(lookup_type).wrapping_sub(1);
+        self.lookups.get(index).cloned().unwrap_or(0)
+    }
+
+    /// Returns the number of valid subtables with a specified lookup type and format.
+    ///
+    /// Subtables inside extension subtables are counted using the wrapped lookup type.
+    #[inline]
+    pub fn subtables_with_format(&self, lookup_type: u16, format: u16) -> u32 {
+        let type_index = usize::from(lookup_type).wrapping_sub(1);
+        let format_index = usize::from(format).wrapping_sub(1);
+        self.subtables.get(type_index)
+            .and_then(|formats| formats.get(format_index))
+            .cloned()
+            .unwrap_or(0)
+    }
 }
 
 #[cfg(feature = "variable-fonts")]
 /// A [Feature Variations Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#featurevariations-table).
 #[derive(Clone, Copy)]
 pub(crate) struct FeatureVariations<'a> {
+    data: &'a [u8],
     feature_variation_records: LazyArray32<'a, FeatureVariationRecord>,
 }
 
@@ -333,9 +1344,56 @@ impl<'a> FeatureVariations<'a> {
         }
         let count = s.read()?;
         Some(Self {
+            data,
             feature_variation_records: s.read_array32(count)?,
         })
     }
+
+    /// Returns an index of the first feature variation record
+    /// which condition set matches the provided coordinates.
+    pub(crate) fn find_index(&self, coordinates: &[crate::NormalizedCoordinate]) -> Option<u32> {
+        for i in 0..self.feature_variation_records.len() {
+            let record = self.feature_variation_records.get(i)?;
+            let data = self.data.get(record.condition_set_offset.to_usize()..)?;
+            if condition_set_matches(data, coordinates) == Some(true) {
+                return Some(i);
+            }
+        }
+
+        None
+    }
+}
+
+// https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#conditionset-table
+#[cfg(feature = "variable-fonts")]
+fn condition_set_matches(data: &[u8], coordinates: &[crate::NormalizedCoordinate]) -> Option<bool> {
+    let mut s = Stream::new(data);
+    let count: u16 = s.read()?;
+    let offsets = s.read_array16::<Offset32>(count)?;
+    for offset in offsets {
+        let mut s = Stream::new_at(data, offset.to_usize())?;
+        let format: u16 = s.read()?;
+        // Unknown formats cannot be satisfied.
+        if format != 1 {
+            return Some(false);
+        }
+
+        let axis_index: u16 = s.read()?;
+        let min: i16 = s.read()?;
+        let max: i16 = s.read()?;
+        // 'If the axis index is not valid, the condition is not satisfied.'
+        let value = match coordinates.get(usize::from(axis_index)) {
+            Some(v) => v.get(),
+            None => return Some(false),
+        };
+
+        if value < min || value > max {
+            return Some(false);
+        }
+    }
+
+    // 'A condition set with no conditions matches any set of coordinates.'
+    Some(true)
 }
 
 #[cfg(feature = "variable-fonts")]
@@ -348,7 +1406,7 @@ pub(crate) struct FeatureVariationRecord {
 
 #[cfg(feature = "variable-fonts")]
 impl FromData for FeatureVariationRecord {
-    const SIZE: usize = 6;
+    const SIZE: usize = 8;
     fn parse(data: &[u8]) -> Option<Self> {
         let mut s = Stream::new(data);
         Some(Self {
@@ -357,3 +1415,555 @@ impl FromData for FeatureVariationRecord {
         })
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn class_definition_format_2() {
+        let data = writer::convert(&[
+            UInt16(2), // format
+            UInt16(3), // classRangeCount
+            // Class Range Record [0]
+            UInt16(5), // startGlyphID
+            UInt16(7), // endGlyphID
+            UInt16(1), // class
+            // Class Range Record [1]
+            UInt16(10), // startGlyphID
+            UInt16(10), // endGlyphID
+            UInt16(2), // class
+            // Class Range Record [2]
+            UInt16(20), // startGlyphID
+            UInt16(30), // endGlyphID
+            UInt16(3), // class
+        ]);
+
+        let table = ClassDefinitionTable::new(&data);
+        assert_eq!(table.get(GlyphId(0)), Class(0));
+        assert_eq!(table.get(GlyphId(5)), Class(1));
+        assert_eq!(table.get(GlyphId(7)), Class(1));
+        assert_eq!(table.get(GlyphId(8)), Class(0));
+        assert_eq!(table.get(GlyphId(10)), Class(2));
+        assert_eq!(table.get(GlyphId(25)), Class(3));
+        assert_eq!(table.get(GlyphId(30)), Class(3));
+        assert_eq!(table.get(GlyphId(31)), Class(0));
+    }
+
+    #[test]
+    fn lang_sys_fallback() {
+        let data = writer::convert(&[
+            UInt16(2), // scriptCount
+            // Script Record [0]
+            Raw(b"cyrl"), // scriptTag
+            UInt16(14), // scriptOffset
+            // Script Record [1]
+            Raw(b"latn"), // scriptTag
+            UInt16(26), // scriptOffset
+            // Script [0]
+            UInt16(4), // defaultLangSysOffset
+            UInt16(0), // langSysCount
+            // Default Language System
+            UInt16(0), // lookupOrderOffset
+            UInt16(0xFFFF), // requiredFeatureIndex
+            UInt16(1), // featureIndexCount
+            UInt16(5), // featureIndex [0]
+            // Script [1]
+            UInt16(0), // defaultLangSysOffset
+            UInt16(1), // langSysCount
+            // Language System Record [0]
+            Raw(b"NLD "), // langSysTag
+            UInt16(10), // langSysOffset
+            // Language System
+            UInt16(0), // lookupOrderOffset
+            UInt16(0xFFFF), // requiredFeatureIndex
+            UInt16(2), // featureIndexCount
+            UInt16(1), // featureIndex [0]
+            UInt16(2), // featureIndex [1]
+        ]);
+
+        let table = ScriptListTable::parse(&data).unwrap();
+        let indices = |scripts: &[&[u8; 4]], language: Option<&[u8; 4]>| {
+            let scripts: std::vec::Vec<_> = scripts.iter().map(|t| Tag::from_bytes(t)).collect();
+            let lang_sys = table.lang_sys(&scripts, language.map(Tag::from_bytes))?;
+            Some(lang_sys.feature_indices().into_iter().collect::<std::vec::Vec<_>>())
+        };
+
+        assert_eq!(indices(&[b"latn"], Some(b"NLD ")), Some(vec![1, 2]));
+        // No default language system.
+        assert_eq!(indices(&[b"latn"], Some(b"DEU ")), None);
+        assert_eq!(indices(&[b"dev2", b"cyrl"], Some(b"NLD ")), Some(vec![5]));
+        // Neither requested nor `DFLT` scripts are present.
+        assert_eq!(indices(&[b"arab"], None), Some(vec![5]));
+    }
+
+    #[test]
+    fn required_feature() {
+        let lang_sys_data = writer::convert(&[
+            UInt16(0), // lookupOrderOffset
+            UInt16(1), // requiredFeatureIndex
+            UInt16(1), // featureIndexCount
+            UInt16(0), // featureIndex [0]
+        ]);
+
+        let feature_list_data = writer::convert(&[
+            UInt16(2), // featureCount
+            // Feature Record [0]
+            Raw(b"ccmp"), // featureTag
+            UInt16(14), // featureOffset
+            // Feature Record [1]
+            Raw(b"rlig"), // featureTag
+            UInt16(14), // featureOffset
+            // Feature
+            UInt16(0), // featureParamsOffset
+            UInt16(0), // lookupIndexCount
+        ]);
+
+        let lang_sys = LangSysTable::parse(&lang_sys_data).unwrap();
+        let feature_list = FeatureListTable::parse(&feature_list_data).unwrap();
+        let index = lang_sys.required_feature_index().unwrap();
+        assert_eq!(feature_list.feature_tag(index), Some(Tag::from_bytes(b"rlig")));
+        assert_eq!(feature_list.feature_tag(2), None);
+    }
+
+    #[test]
+    fn device_format_1() {
+        let data = &[
+            0x00, 0x0B, // start size: 11
+            0x00, 0x0C, // end size: 12
+            0x00, 0x01, // delta format: 1
+            0x7F, 0x80, // deltas: [1, -1, -1, -1, -2, 0, 0, 0]
+        ];
+
+        let device = match Device::parse(data).unwrap() {
+            Device::Hinting(device) => device,
+            _ => panic!("must be a hinting device"),
+        };
+
+        assert_eq!(device.delta(10), 0);
+        assert_eq!(device.delta(11), 1);
+        assert_eq!(device.delta(12), -1);
+        assert_eq!(device.delta(13), 0);
+    }
+
+    #[test]
+    fn device_format_2() {
+        let data = &[
+            0x00, 0x0C, // start size: 12
+            0x00, 0x11, // end size: 17
+            0x00, 0x02, // delta format: 2
+            0x1F, 0x80, // deltas: [1, -1, -8, 0]
+            0x7F, 0x00, // deltas: [7, -1, 0, 0]
+        ];
+
+        let device = match Device::parse(data).unwrap() {
+            Device::Hinting(device) => device,
+            _ => panic!("must be a hinting device"),
+        };
+
+        assert_eq!(device.delta(12), 1);
+        assert_eq!(device.delta(13), -1);
+        assert_eq!(device.delta(14), -8);
+        assert_eq!(device.delta(15), 0);
+        assert_eq!(device.delta(16), 7);
+        assert_eq!(device.delta(17), -1);
+    }
+
+    #[test]
+    fn device_format_3() {
+        let data = &[
+            0x00, 0x0C, // start size: 12
+            0x00, 0x0D, // end size: 13
+            0x00, 0x03, // delta format: 3
+            0x05, 0xFE, // deltas: [5, -2]
+        ];
+
+        let device = match Device::parse(data).unwrap() {
+            Device::Hinting(device) => device,
+            _ => panic!("must be a hinting device"),
+        };
+
+        assert_eq!(device.delta(12), 5);
+        assert_eq!(device.delta(13), -2);
+    }
+
+    #[test]
+    fn device_interpolated() {
+        let data = &[
+            0x00, 0x0C, // start size: 12
+            0x00, 0x0D, // end size: 13
+            0x00, 0x03, // delta format: 3
+            0x04, 0xFE, // deltas: [4, -2]
+        ];
+
+        let device = match Device::parse(data).unwrap() {
+            Device::Hinting(device) => device,
+            _ => panic!("must be a hinting device"),
+        };
+
+        assert_eq!(device.delta_interpolated(11.0), 0.0);
+        assert_eq!(device.delta_interpolated(11.5), 2.0);
+        assert_eq!(device.delta_interpolated(12.0), 4.0);
+        assert_eq!(device.delta_interpolated(12.25), 2.5);
+        assert_eq!(device.delta_interpolated(13.0), -2.0);
+        assert_eq!(device.delta_interpolated(13.5), -1.0);
+        assert_eq!(device.delta_interpolated(14.0), 0.0);
+        assert_eq!(device.delta_interpolated(-1.0), 0.0);
+        assert_eq!(device.delta_interpolated(core::f32::NAN), 0.0);
+    }
+
+    #[test]
+    fn variation_index() {
+        let data = &[
+            0x00, 0x01, // outer index: 1
+            0x00, 0x02, // inner index: 2
+            0x80, 0x00, // delta format: VariationIndex
+        ];
+
+        match Device::parse(data).unwrap() {
+            Device::Variation(device) => {
+                assert_eq!(device.outer_index, 1);
+                assert_eq!(device.inner_index, 2);
+            }
+            _ => panic!("must be a variation device"),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn lookup_graph() {
+        let data = &[
+            // Lookup List Table
+            0x00, 0x03, // lookup count: 3
+            0x00, 0x08, // offset [0]: 8
+            0x00, 0x1C, // offset [1]: 28
+            0x00, 0x22, // offset [2]: 34
+
+            // Lookup 0
+            0x00, 0x05, // lookup type: context
+            0x00, 0x00, // lookup flag: 0
+            0x00, 0x01, // subtable count: 1
+            0x00, 0x08, // offset [0]: 8
+            // Sequence Context Format 3
+            0x00, 0x03, // format: 3
+            0x00, 0x01, // glyph count: 1
+            0x00, 0x01, // sequence lookup count: 1
+            0x00, 0x00, // coverage offset [0]: 0
+            0x00, 0x00, // sequence index: 0
+            0x00, 0x01, // lookup index: 1
+
+            // Lookup 1
+            0x00, 0x01, // lookup type: single
+            0x00, 0x00, // lookup flag: 0
+            0x00, 0x00, // subtable count: 0
+
+            // Lookup 2
+            0x00, 0x07, // lookup type: extension
+            0x00, 0x00, // lookup flag: 0
+            0x00, 0x01, // subtable count: 1
+            0x00, 0x08, // offset [0]: 8
+            // Extension Substitution Format 1
+            0x00, 0x01, // format: 1
+            0x00, 0x06, // extension lookup type: chained context
+            0x00, 0x00, 0x00, 0x08, // offset: 8
+            // Chained Sequence Context Format 3
+            0x00, 0x03, // format: 3
+            0x00, 0x00, // backtrack glyph count: 0
+            0x00, 0x01, // input glyph count: 1
+            0x00, 0x00, // input coverage offset [0]: 0
+            0x00, 0x00, // lookahead glyph count: 0
+            0x00, 0x02, // sequence lookup count: 2
+            0x00, 0x00, // sequence index: 0
+            0x00, 0x02, // lookup index: 2
+            0x00, 0x00, // sequence index: 0
+            0x00, 0x00, // lookup index: 0
+        ];
+
+        let list = LookupListTable::parse(data).unwrap();
+        let graph = LookupGraph::new(list, LookupListKind::Substitution, Limits::default());
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.dependencies(0), &[1]);
+        assert_eq!(graph.dependencies(1), &[]);
+        assert_eq!(graph.dependencies(2), &[0, 2]);
+        assert_eq!(graph.dependencies(3), &[]);
+        assert_eq!(graph.reachable(&[0]), vec![true, true, false]);
+        assert_eq!(graph.reachable(&[2]), vec![true, true, true]);
+        assert_eq!(graph.find_cycle(), Some(vec![2]));
+
+        let limits = Limits { max_lookup_nesting: 1, ..Limits::default() };
+        let graph = LookupGraph::new(list, LookupListKind::Substitution, limits);
+        assert_eq!(graph.reachable(&[2]), vec![true, false, true]);
+
+        // The same lookup types have a different meaning in GPOS.
+        let graph = LookupGraph::new(list, LookupListKind::Positioning, Limits::default());
+        assert_eq!(graph.dependencies(0), &[]);
+        assert!(!graph.has_cycle());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn lookup_graph_indirect_cycle() {
+        let data = &[
+            // Lookup List Table
+            0x00, 0x02, // lookup count: 2
+            0x00, 0x06, // offset [0]: 6
+            0x00, 0x24, // offset [1]: 36
+
+            // Lookup 0
+            0x00, 0x05, // lookup type: context
+            0x00, 0x00, // lookup flag: 0
+            0x00, 0x01, // subtable count: 1
+            0x00, 0x08, // offset [0]: 8
+            // Sequence Context Format 1
+            0x00, 0x01, // format: 1
+            0x00, 0x00, // coverage offset: 0
+            0x00, 0x01, // rule set count: 1
+            0x00, 0x08, // rule set offset [0]: 8
+            // Sequence Rule Set
+            0x00, 0x01, // rule count: 1
+            0x00, 0x04, // rule offset [0]: 4
+            // Sequence Rule
+            0x00, 0x02, // glyph count: 2
+            0x00, 0x01, // sequence lookup count: 1
+            0x00, 0x05, // input sequence [0]: 5
+            0x00, 0x01, // sequence index: 1
+            0x00, 0x01, // lookup index: 1
+
+            // Lookup 1
+            0x00, 0x06, // lookup type: chained context
+            0x00, 0x00, // lookup flag: 0
+            0x00, 0x01, // subtable count: 1
+            0x00, 0x08, // offset [0]: 8
+            // Chained Sequence Context Format 3
+            0x00, 0x03, // format: 3
+            0x00, 0x00, // backtrack glyph count: 0
+            0x00, 0x01, // input glyph count: 1
+            0x00, 0x00, // input coverage offset [0]: 0
+            0x00, 0x00, // lookahead glyph count: 0
+            0x00, 0x01, // sequence lookup count: 1
+            0x00, 0x00, // sequence index: 0
+            0x00, 0x00, // lookup index: 0
+        ];
+
+        let list = LookupListTable::parse(data).unwrap();
+        let graph = LookupGraph::new(list, LookupListKind::Substitution, Limits::default());
+        assert_eq!(graph.dependencies(0), &[1]);
+        assert_eq!(graph.dependencies(1), &[0]);
+        assert_eq!(graph.find_cycle(), Some(vec![0, 1]));
+    }
+
+    #[test]
+    fn device_invalid_format() {
+        let data = &[
+            0x00, 0x0C, // start size: 12
+            0x00, 0x0D, // end size: 13
+            0x00, 0x04, // delta format: 4
+        ];
+
+        assert!(Device::parse(data).is_none());
+    }
+
+    #[test]
+    fn coverage_sorting() {
+        let data = &[
+            0x00, 0x01, // format: 1
+            0x00, 0x03, // glyph count: 3
+            0x00, 0x01, // glyph [0]: 1
+            0x00, 0x05, // glyph [1]: 5
+            0x00, 0x05, // glyph [2]: 5
+        ];
+        assert_eq!(CoverageTable::new(data).is_sorted(), Some(false));
+
+        let data = &[
+            0x00, 0x02, // format: 2
+            0x00, 0x02, // range count: 2
+            0x00, 0x0A, // start glyph ID [0]: 10
+            0x00, 0x0E, // end glyph ID [0]: 14
+            0x00, 0x00, // start coverage index [0]: 0
+            0x00, 0x02, // start glyph ID [1]: 2
+            0x00, 0x04, // end glyph ID [1]: 4
+            0x00, 0x05, // start coverage index [1]: 5
+        ];
+        assert_eq!(CoverageTable::new(data).is_sorted(), Some(false));
+    }
+
+    #[test]
+    fn lookup_statistics() {
+        let data = &[
+            // Lookup List Table
+            0x00, 0x02, // lookup count: 2
+            0x00, 0x06, // offset [0]: 6
+            0x00, 0x20, // offset [1]: 32
+
+            // Lookup 0
+            0x00, 0x01, // lookup type: single
+            0x00, 0x00, // lookup flag: 0
+            0x00, 0x02, // subtable count: 2
+            0x00, 0x0A, // offset [0]: 10
+            0x00, 0x10, // offset [1]: 16
+            // Single Substitution Format 1
+            0x00, 0x01, // format: 1
+            0x00, 0x08, // coverage offset: 8
+            0x00, 0x01, // delta glyph ID: 1
+            // Single Substitution Format 4
+            0x00, 0x04, // format: 4 (invalid)
+            // Coverage Format 1
+            0x00, 0x01, // format: 1
+            0x00, 0x02, // glyph count: 2
+            0x00, 0x01, // glyph [0]: 1
+            0x00, 0x02, // glyph [1]: 2
+
+            // Lookup 1
+            0x00, 0x07, // lookup type: extension
+            0x00, 0x00, // lookup flag: 0
+            0x00, 0x01, // subtable count: 1
+            0x00, 0x08, // offset [0]: 8
+            // Extension Substitution Format 1
+            0x00, 0x01, // format: 1
+            0x00, 0x01, // extension lookup type: single
+            0x00, 0x00, 0x00, 0x08, // offset: 8
+            // Single Substitution Format 2
+            0x00, 0x02, // format: 2
+            0x00, 0x06, // coverage offset: 6
+            0x00, 0x00, // glyph count: 0
+            // Coverage Format 2
+            0x00, 0x02, // format: 2
+            0x00, 0x01, // range count: 1
+            0x00, 0x0A, // start glyph ID: 10
+            0x00, 0x0E, // end glyph ID: 14
+            0x00, 0x00, // start coverage index: 0
+        ];
+
+        let list = LookupListTable::parse(data).unwrap();
+        let stats = LookupStatistics::new(list, LookupListKind::Substitution, Limits::default());
+        assert_eq!(stats.lookups_count, 2);
+        assert_eq!(stats.subtables_count, 3);
+        assert_eq!(stats.extension_subtables_count, 1);
+        assert_eq!(stats.malformed_subtables_count, 1);
+        assert_eq!(stats.coverage_glyphs_count, 7);
+        assert_eq!(stats.unsorted_coverages_count, 0);
+        assert_eq!(stats.lookups_with_type(1), 1);
+        assert_eq!(stats.lookups_with_type(7), 1);
+        assert_eq!(stats.lookups_with_type(0), 0);
+        assert_eq!(stats.lookups_with_type(10), 0);
+        assert_eq!(stats.subtables_with_format(1, 1), 1);
+        assert_eq!(stats.subtables_with_format(1, 2), 1);
+        assert_eq!(stats.subtables_with_format(1, 4), 0);
+        assert_eq!(stats.subtables_with_format(7, 1), 0);
+        assert_eq!(stats.skipped_coverages_count, 0);
+
+        let limits = Limits { max_coverage_entries: 2, ..Limits::default() };
+        let stats = LookupStatistics::new(list, LookupListKind::Substitution, limits);
+        assert_eq!(stats.subtables_with_format(1, 2), 1);
+        assert_eq!(stats.coverage_glyphs_count, 2);
+        assert_eq!(stats.skipped_coverages_count, 1);
+    }
+
+    #[test]
+    fn lookup_coverages() {
+        let data = &[
+            // Lookup List Table
+            0x00, 0x03, // lookup count: 3
+            0x00, 0x08, // offset [0]: 8
+            0x00, 0x22, // offset [1]: 34
+            0x00, 0x42, // offset [2]: 66
+
+            // Lookup 0
+            0x00, 0x01, // lookup type: single
+            0x00, 0x00, // lookup flag: 0
+            0x00, 0x02, // subtable count: 2
+            0x00, 0x0A, // offset [0]: 10
+            0x00, 0x10, // offset [1]: 16
+            // Single Substitution Format 1
+            0x00, 0x01, // format: 1
+            0x00, 0x08, // coverage offset: 8
+            0x00, 0x01, // delta glyph ID: 1
+            // Single Substitution Format 4
+            0x00, 0x04, // format: 4 (invalid)
+            // Coverage Format 1
+            0x00, 0x01, // format: 1
+            0x00, 0x02, // glyph count: 2
+            0x00, 0x01, // glyph [0]: 1
+            0x00, 0x64, // glyph [1]: 100
+
+            // Lookup 1
+            0x00, 0x07, // lookup type: extension
+            0x00, 0x00, // lookup flag: 0
+            0x00, 0x01, // subtable count: 1
+            0x00, 0x08, // offset [0]: 8
+            // Extension Substitution Format 1
+            0x00, 0x01, // format: 1
+            0x00, 0x01, // extension lookup type: single
+            0x00, 0x00, 0x00, 0x08, // offset: 8
+            // Single Substitution Format 2
+            0x00, 0x02, // format: 2
+            0x00, 0x06, // coverage offset: 6
+            0x00, 0x00, // glyph count: 0
+            // Coverage Format 2
+            0x00, 0x02, // format: 2
+            0x00, 0x01, // range count: 1
+            0x00, 0x0A, // start glyph ID: 10
+            0x00, 0x0E, // end glyph ID: 14
+            0x00, 0x00, // start coverage index: 0
+
+            // Lookup 2
+            0x00, 0x01, // lookup type: single
+            0x00, 0x00, // lookup flag: 0
+            0x00, 0x00, // subtable count: 0
+        ];
+
+        let list = LookupListTable::parse(data).unwrap();
+        let coverages = LookupCoverages::new(list, LookupListKind::Substitution, Limits::default());
+        assert_eq!(coverages.len(), 3);
+        assert!(coverages.may_apply(0, GlyphId(1)));
+        assert!(coverages.may_apply(0, GlyphId(100)));
+        assert!(!coverages.may_apply(0, GlyphId(2)));
+        assert!(!coverages.may_apply(0, GlyphId(1000)));
+        assert!(coverages.may_apply(1, GlyphId(10)));
+        assert!(coverages.may_apply(1, GlyphId(14)));
+        assert!(!coverages.may_apply(1, GlyphId(15)));
+        assert!(!coverages.may_apply(2, GlyphId(1)));
+        assert!(!coverages.may_apply(3, GlyphId(1)));
+        assert_eq!(coverages.glyphs(1).map(|glyphs| glyphs.len()), Some(5));
+
+        let limits = Limits { max_coverage_entries: 2, ..Limits::default() };
+        let coverages = LookupCoverages::new(list, LookupListKind::Substitution, limits);
+        assert!(!coverages.may_apply(0, GlyphId(2)));
+        assert!(coverages.may_apply(1, GlyphId(2)));
+        assert!(coverages.glyphs(1).is_none());
+    }
+
+    #[cfg(feature = "variable-fonts")]
+    #[test]
+    fn feature_variations() {
+        use crate::NormalizedCoordinate;
+
+        let data = &[
+            0x00, 0x01, 0x00, 0x00, // version: 1.0
+            0x00, 0x00, 0x00, 0x02, // count: 2
+            // Record 0
+            0x00, 0x00, 0x00, 0x18, // condition set offset: 24
+            0x00, 0x00, 0x00, 0x00, // feature table substitution offset: 0
+            // Record 1
+            0x00, 0x00, 0x00, 0x26, // condition set offset: 38
+            0x00, 0x00, 0x00, 0x00, // feature table substitution offset: 0
+            // Condition set 0
+            0x00, 0x01, // count: 1
+            0x00, 0x00, 0x00, 0x06, // condition offset: 6
+            // Condition 0
+            0x00, 0x01, // format: 1
+            0x00, 0x00, // axis index: 0
+            0x20, 0x00, // min value: 0.5
+            0x40, 0x00, // max value: 1.0
+            // Condition set 1
+            0x00, 0x00, // count: 0
+        ];
+
+        let table = FeatureVariations::parse(data).unwrap();
+        assert_eq!(table.find_index(&[NormalizedCoordinate::from(0.75)]), Some(0));
+        assert_eq!(table.find_index(&[NormalizedCoordinate::from(0.25)]), Some(1));
+    }
+}
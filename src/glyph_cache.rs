@@ -0,0 +1,92 @@
+use std::boxed::Box;
+use std::vec::Vec;
+
+use crate::{Face, GlyphId};
+
+const PAGE_SIZE: usize = 256;
+
+/// A character to glyph mapping cache.
+///
+/// `Face::glyph_index` performs a binary search in `cmap` on each call,
+/// which can be noticeable in tight text measurement loops.
+/// This cache is a two-level page table, where each page of 256 code points
+/// is resolved on first use.
+///
+/// The cache doesn't borrow a face, therefore it's up to the caller
+/// to use it only with the face it was filled from.
+///
+/// # Example
+///
+/// ```
+/// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+/// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+/// let mut cache = ttf_parser::GlyphIndexCache::new();
+/// assert_eq!(cache.glyph_index(&face, 'A'), face.glyph_index('A'));
+/// ```
+#[derive(Clone, Default)]
+pub struct GlyphIndexCache {
+    pages: Vec<Option<Box<[Option<GlyphId>; PAGE_SIZE]>>>,
+}
+
+impl GlyphIndexCache {
+    /// Creates an empty cache.
+    #[inline]
+    pub fn new() -> Self {
+        GlyphIndexCache::default()
+    }
+
+    /// Resolves a Glyph ID for a code point.
+    ///
+    /// Same as `Face::glyph_index`, but resolves the whole page on first use.
+    pub fn glyph_index(&mut self, face: &Face, c: char) -> Option<GlyphId> {
+        let code_point = c as usize;
+        let page_index = code_point / PAGE_SIZE;
+        if page_index >= self.pages.len() {
+            self.pages.resize(page_index + 1, None);
+        }
+
+        let page = self.pages[page_index].get_or_insert_with(|| {
+            let mut page = Box::new([None; PAGE_SIZE]);
+            let first = page_index * PAGE_SIZE;
+            for (i, id) in page.iter_mut().enumerate() {
+                if let Some(c) = core::char::from_u32((first + i) as u32) {
+                    *id = face.glyph_index(c);
+                }
+            }
+
+            page
+        });
+
+        page[code_point % PAGE_SIZE]
+    }
+
+    /// Removes all resolved pages.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.pages.clear();
+    }
+}
+
+impl core::fmt::Debug for GlyphIndexCache {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "GlyphIndexCache()")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_as_face() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let mut cache = GlyphIndexCache::new();
+        for c in &['A', 'B', '0', '\u{0}', '\u{FFFF}', '\u{10FFFF}'] {
+            assert_eq!(cache.glyph_index(&face, *c), face.glyph_index(*c));
+        }
+
+        assert_eq!(cache.pages.iter().filter(|p| p.is_some()).count(), 3);
+    }
+}
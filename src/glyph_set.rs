@@ -0,0 +1,351 @@
+use std::vec::Vec;
+
+use crate::GlyphId;
+
+const PAGE_BITS: u16 = 512;
+const PAGE_WORDS: usize = PAGE_BITS as usize / 64;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Page {
+    index: u16,
+    words: [u64; PAGE_WORDS],
+}
+
+impl Page {
+    #[inline]
+    fn new(index: u16) -> Self {
+        Page { index, words: [0; PAGE_WORDS] }
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+}
+
+#[inline]
+fn split(glyph_id: GlyphId) -> (u16, usize, u64) {
+    let bit = glyph_id.0 % PAGE_BITS;
+    (glyph_id.0 / PAGE_BITS, usize::from(bit / 64), 1 << (bit % 64))
+}
+
+/// A set of glyph IDs.
+///
+/// Glyphs are stored in a bitset split into 512 glyph pages.
+/// Only non-empty pages are allocated, so both dense ranges and sparse sets are compact.
+///
+/// # Example
+///
+/// ```
+/// use ttf_parser::{GlyphId, GlyphSet};
+///
+/// let mut set = GlyphSet::new();
+/// set.insert_range(GlyphId(10)..=GlyphId(20));
+/// set.remove(GlyphId(15));
+///
+/// let other: GlyphSet = [GlyphId(1), GlyphId(12), GlyphId(15)].iter().cloned().collect();
+/// set.intersect(&other);
+/// assert_eq!(set.iter().collect::<Vec<_>>(), vec![GlyphId(12)]);
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct GlyphSet {
+    // Sorted by index. Empty pages are removed.
+    pages: Vec<Page>,
+}
+
+impl GlyphSet {
+    /// Creates an empty set.
+    #[inline]
+    pub fn new() -> Self {
+        GlyphSet::default()
+    }
+
+    /// Returns the number of glyphs in the set.
+    pub fn len(&self) -> u32 {
+        self.pages.iter().flat_map(|p| p.words.iter()).map(|w| w.count_ones()).sum()
+    }
+
+    /// Checks that the set has no glyphs.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+
+    /// Removes all glyphs.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.pages.clear();
+    }
+
+    /// Checks that the set contains a glyph.
+    pub fn contains(&self, glyph_id: GlyphId) -> bool {
+        let (index, word, mask) = split(glyph_id);
+        match self.pages.binary_search_by_key(&index, |p| p.index) {
+            Ok(i) => self.pages[i].words[word] & mask != 0,
+            Err(_) => false,
+        }
+    }
+
+    /// Adds a glyph to the set.
+    ///
+    /// Returns `false` when the glyph was already present.
+    pub fn insert(&mut self, glyph_id: GlyphId) -> bool {
+        let (index, word, mask) = split(glyph_id);
+        let page = self.page_mut(index);
+        let is_new = page.words[word] & mask == 0;
+        page.words[word] |= mask;
+        is_new
+    }
+
+    /// Adds a range of glyphs to the set.
+    pub fn insert_range(&mut self, range: core::ops::RangeInclusive<GlyphId>) {
+        let (start, end) = (range.start().0, range.end().0);
+        if start > end {
+            return;
+        }
+
+        for index in start / PAGE_BITS..=end / PAGE_BITS {
+            let first = core::cmp::max(start, index * PAGE_BITS) % PAGE_BITS;
+            let last = core::cmp::min(end, index * PAGE_BITS + (PAGE_BITS - 1)) % PAGE_BITS;
+            let page = self.page_mut(index);
+            for (i, word) in page.words.iter_mut().enumerate() {
+                let word_first = i as u16 * 64;
+                let word_last = word_first + 63;
+                if word_last < first || word_first > last {
+                    continue;
+                }
+
+                let lo = core::cmp::max(first, word_first) - word_first;
+                let hi = core::cmp::min(last, word_last) - word_first;
+                // Bits `lo..=hi`.
+                *word |= (u64::MAX >> (63 - hi)) & (u64::MAX << lo);
+            }
+        }
+    }
+
+    /// Removes a glyph from the set.
+    ///
+    /// Returns `false` when the glyph was not present.
+    pub fn remove(&mut self, glyph_id: GlyphId) -> bool {
+        let (index, word, mask) = split(glyph_id);
+        let i = match self.pages.binary_search_by_key(&index, |p| p.index) {
+            Ok(i) => i,
+            Err(_) => return false,
+        };
+
+        let was_present = self.pages[i].words[word] & mask != 0;
+        self.pages[i].words[word] &= !mask;
+        if self.pages[i].is_empty() {
+            self.pages.remove(i);
+        }
+
+        was_present
+    }
+
+    /// Adds all glyphs from `other`.
+    pub fn union(&mut self, other: &GlyphSet) {
+        for other_page in &other.pages {
+            let page = self.page_mut(other_page.index);
+            for (a, b) in page.words.iter_mut().zip(other_page.words.iter()) {
+                *a |= *b;
+            }
+        }
+    }
+
+    /// Keeps only glyphs that are also present in `other`.
+    pub fn intersect(&mut self, other: &GlyphSet) {
+        for page in &mut self.pages {
+            match other.pages.binary_search_by_key(&page.index, |p| p.index) {
+                Ok(i) => {
+                    for (a, b) in page.words.iter_mut().zip(other.pages[i].words.iter()) {
+                        *a &= *b;
+                    }
+                }
+                Err(_) => page.words = [0; PAGE_WORDS],
+            }
+        }
+
+        self.pages.retain(|p| !p.is_empty());
+    }
+
+    /// Removes all glyphs that are present in `other`.
+    pub fn subtract(&mut self, other: &GlyphSet) {
+        for page in &mut self.pages {
+            if let Ok(i) = other.pages.binary_search_by_key(&page.index, |p| p.index) {
+                for (a, b) in page.words.iter_mut().zip(other.pages[i].words.iter()) {
+                    *a &= !*b;
+                }
+            }
+        }
+
+        self.pages.retain(|p| !p.is_empty());
+    }
+
+    /// Returns an iterator over glyphs in ascending order.
+    #[inline]
+    pub fn iter(&self) -> GlyphSetIter<'_> {
+        GlyphSetIter {
+            pages: &self.pages,
+            page: 0,
+            word: 0,
+            bits: self.pages.first().map(|p| p.words[0]).unwrap_or(0),
+        }
+    }
+
+    fn page_mut(&mut self, index: u16) -> &mut Page {
+        let i = match self.pages.binary_search_by_key(&index, |p| p.index) {
+            Ok(i) => i,
+            Err(i) => {
+                self.pages.insert(i, Page::new(index));
+                i
+            }
+        };
+
+        &mut self.pages[i]
+    }
+}
+
+impl core::fmt::Debug for GlyphSet {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl core::iter::FromIterator<GlyphId> for GlyphSet {
+    fn from_iter<I: IntoIterator<Item = GlyphId>>(iter: I) -> Self {
+        let mut set = GlyphSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<GlyphId> for GlyphSet {
+    fn extend<I: IntoIterator<Item = GlyphId>>(&mut self, iter: I) {
+        for glyph_id in iter {
+            self.insert(glyph_id);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a GlyphSet {
+    type Item = GlyphId;
+    type IntoIter = GlyphSetIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over `GlyphSet`.
+#[derive(Clone)]
+pub struct GlyphSetIter<'a> {
+    pages: &'a [Page],
+    page: usize,
+    word: usize,
+    // Remaining bits of the current word.
+    bits: u64,
+}
+
+impl core::fmt::Debug for GlyphSetIter<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "GlyphSetIter()")
+    }
+}
+
+impl<'a> Iterator for GlyphSetIter<'a> {
+    type Item = GlyphId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let page = self.pages.get(self.page)?;
+            if self.bits != 0 {
+                let bit = self.bits.trailing_zeros() as u16;
+                self.bits &= self.bits - 1;
+                return Some(GlyphId(page.index * PAGE_BITS + self.word as u16 * 64 + bit));
+            }
+
+            self.word += 1;
+            if self.word == PAGE_WORDS {
+                self.word = 0;
+                self.page += 1;
+            }
+
+            self.bits = self.pages.get(self.page).map(|p| p.words[self.word]).unwrap_or(0);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    fn glyphs(set: &GlyphSet) -> Vec<u16> {
+        set.iter().map(|g| g.0).collect()
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut set = GlyphSet::new();
+        assert!(set.is_empty());
+        assert!(set.insert(GlyphId(600)));
+        assert!(set.insert(GlyphId(3)));
+        assert!(!set.insert(GlyphId(3)));
+        assert!(set.insert(GlyphId(65535)));
+        assert_eq!(glyphs(&set), vec![3, 600, 65535]);
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(GlyphId(600)));
+        assert!(!set.contains(GlyphId(601)));
+
+        assert!(set.remove(GlyphId(600)));
+        assert!(!set.remove(GlyphId(600)));
+        assert!(!set.remove(GlyphId(1000)));
+        assert_eq!(glyphs(&set), vec![3, 65535]);
+        assert_eq!(set.pages.len(), 2);
+    }
+
+    #[test]
+    fn ranges() {
+        let mut set = GlyphSet::new();
+        set.insert_range(GlyphId(60)..=GlyphId(1100));
+        assert_eq!(set.len(), 1041);
+        assert!(!set.contains(GlyphId(59)));
+        assert!(set.contains(GlyphId(60)));
+        assert!(set.contains(GlyphId(511)));
+        assert!(set.contains(GlyphId(512)));
+        assert!(set.contains(GlyphId(1100)));
+        assert!(!set.contains(GlyphId(1101)));
+
+        set.insert_range(GlyphId(0)..=GlyphId(65535));
+        assert_eq!(set.len(), 65536);
+
+        let mut set = GlyphSet::new();
+        set.insert_range(GlyphId(5)..=GlyphId(4));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: GlyphSet = [1, 2, 3, 700].iter().map(|n| GlyphId(*n)).collect();
+        let b: GlyphSet = [2, 3, 4, 1000].iter().map(|n| GlyphId(*n)).collect();
+
+        let mut set = a.clone();
+        set.union(&b);
+        assert_eq!(glyphs(&set), vec![1, 2, 3, 4, 700, 1000]);
+
+        let mut set = a.clone();
+        set.intersect(&b);
+        assert_eq!(glyphs(&set), vec![2, 3]);
+        assert_eq!(set.pages.len(), 1);
+
+        let mut set = a.clone();
+        set.subtract(&b);
+        assert_eq!(glyphs(&set), vec![1, 700]);
+
+        let mut set = a.clone();
+        set.subtract(&a);
+        assert_eq!(set, GlyphSet::new());
+    }
+}
@@ -0,0 +1,1746 @@
+// https://docs.microsoft.com/en-us/typography/opentype/spec/tt_instructions
+// https://docs.microsoft.com/en-us/typography/opentype/spec/tt_graphics_state
+
+use core::convert::TryFrom;
+use std::vec::Vec;
+
+/// Limits the number of executed instructions per program,
+/// so malformed fonts cannot loop forever.
+const MAX_INSTRUCTIONS: u32 = 1_000_000;
+
+/// Function calls nesting limit.
+const MAX_CALL_DEPTH: u8 = 64;
+
+pub(crate) const ON_CURVE: u8 = 0x01;
+const TOUCHED_X: u8 = 0x02;
+const TOUCHED_Y: u8 = 0x04;
+
+pub(crate) const TWILIGHT_ZONE: usize = 0;
+pub(crate) const GLYPH_ZONE: usize = 1;
+
+/// A point in 26.6 fixed-point pixels.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub(crate) struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    #[inline]
+    pub fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+
+    #[inline]
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x.wrapping_sub(other.x), self.y.wrapping_sub(other.y))
+    }
+
+    #[inline]
+    fn get(self, is_x: bool) -> i32 {
+        if is_x { self.x } else { self.y }
+    }
+
+    #[inline]
+    fn set(&mut self, is_x: bool, n: i32) {
+        if is_x { self.x = n } else { self.y = n }
+    }
+}
+
+
+/// A unit vector in 2.14 fixed-point.
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Vector {
+    x: i32,
+    y: i32,
+}
+
+const X_AXIS: Vector = Vector { x: 0x4000, y: 0 };
+const Y_AXIS: Vector = Vector { x: 0, y: 0x4000 };
+
+impl Vector {
+    /// Creates a unit vector with the specified direction.
+    fn normalize(x: i32, y: i32) -> Vector {
+        let (fx, fy) = (f64::from(x), f64::from(y));
+        let len = (fx * fx + fy * fy).sqrt();
+        if len == 0.0 {
+            return X_AXIS;
+        }
+
+        Vector {
+            x: (fx / len * 16384.0).round() as i32,
+            y: (fy / len * 16384.0).round() as i32,
+        }
+    }
+
+    /// Projects a vector on this one.
+    #[inline]
+    fn project(self, p: Point) -> i32 {
+        let n = i64::from(p.x) * i64::from(self.x) + i64::from(p.y) * i64::from(self.y);
+        ((n + 0x2000) >> 14) as i32
+    }
+}
+
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum RoundState {
+    ToHalfGrid,
+    ToGrid,
+    ToDoubleGrid,
+    DownToGrid,
+    UpToGrid,
+    Off,
+    Super,
+    Super45,
+}
+
+
+/// A graphics state.
+///
+/// All distances are in 26.6 fixed-point pixels.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct GraphicsState {
+    auto_flip: bool,
+    control_value_cutin: i32,
+    delta_base: i32,
+    delta_shift: i32,
+    dual_vector: Vector,
+    freedom_vector: Vector,
+    projection_vector: Vector,
+    pub instruct_control: u8,
+    loop_count: i32,
+    minimum_distance: i32,
+    round_state: RoundState,
+    period: i32,
+    phase: i32,
+    threshold: i32,
+    rp0: usize,
+    rp1: usize,
+    rp2: usize,
+    single_width_cutin: i32,
+    single_width_value: i32,
+    zp0: usize,
+    zp1: usize,
+    zp2: usize,
+}
+
+impl Default for GraphicsState {
+    #[inline]
+    fn default() -> Self {
+        GraphicsState {
+            auto_flip: true,
+            control_value_cutin: 68, // 17/16 pixels
+            delta_base: 9,
+            delta_shift: 3,
+            dual_vector: X_AXIS,
+            freedom_vector: X_AXIS,
+            projection_vector: X_AXIS,
+            instruct_control: 0,
+            loop_count: 1,
+            minimum_distance: 64,
+            round_state: RoundState::ToGrid,
+            period: 64,
+            phase: 0,
+            threshold: 32,
+            rp0: 0,
+            rp1: 0,
+            rp2: 0,
+            single_width_cutin: 0,
+            single_width_value: 0,
+            zp0: GLYPH_ZONE,
+            zp1: GLYPH_ZONE,
+            zp2: GLYPH_ZONE,
+        }
+    }
+}
+
+impl GraphicsState {
+    /// Resets values that are not retained between programs.
+    pub fn reset_pointers(&mut self) {
+        self.loop_count = 1;
+        self.rp0 = 0;
+        self.rp1 = 0;
+        self.rp2 = 0;
+        self.zp0 = GLYPH_ZONE;
+        self.zp1 = GLYPH_ZONE;
+        self.zp2 = GLYPH_ZONE;
+    }
+}
+
+
+/// A set of points instructions operate on.
+#[derive(Clone, Default, Debug)]
+pub(crate) struct Zone {
+    /// Scaled points before hinting.
+    pub original: Vec<Point>,
+    /// Hinted points.
+    pub current: Vec<Point>,
+    pub flags: Vec<u8>,
+    /// Contour end points.
+    ///
+    /// Phantom points do not belong to any contour.
+    pub end_points: Vec<usize>,
+}
+
+impl Zone {
+    /// Creates a zone with the specified number of zero points.
+    pub fn new(len: usize) -> Self {
+        Zone {
+            original: vec![Point::default(); len],
+            current: vec![Point::default(); len],
+            flags: vec![0; len],
+            end_points: Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn touch(&mut self, point: usize, freedom_vector: Vector) {
+        if freedom_vector.x != 0 {
+            self.flags[point] |= TOUCHED_X;
+        }
+
+        if freedom_vector.y != 0 {
+            self.flags[point] |= TOUCHED_Y;
+        }
+    }
+
+    /// Returns the range of the specified contour.
+    fn contour(&self, index: usize) -> Option<(usize, usize)> {
+        let end = *self.end_points.get(index)?;
+        let start = match index.checked_sub(1) {
+            Some(prev) => self.end_points[prev] + 1,
+            None => 0,
+        };
+
+        if start > end || end >= self.current.len() {
+            return None;
+        }
+
+        Some((start, end))
+    }
+
+    /// Interpolates untouched points of all contours.
+    fn interpolate_untouched(&mut self, is_x: bool) {
+        let touched = if is_x { TOUCHED_X } else { TOUCHED_Y };
+        for index in 0..self.end_points.len() {
+            let (start, end) = match self.contour(index) {
+                Some(range) => range,
+                None => break,
+            };
+
+            let first = match (start..=end).find(|i| self.flags[*i] & touched != 0) {
+                Some(first) => first,
+                None => continue,
+            };
+
+            let next = |i: usize| if i == end { start } else { i + 1 };
+            let mut prev = first;
+            let mut i = next(first);
+            while i != first {
+                if self.flags[i] & touched != 0 {
+                    self.interpolate_range(is_x, prev, i, next);
+                    prev = i;
+                }
+
+                i = next(i);
+            }
+
+            // A single touched point shifts the whole contour.
+            self.interpolate_range(is_x, prev, first, next);
+        }
+    }
+
+    /// Interpolates points between two touched points, exclusive.
+    fn interpolate_range(&mut self, is_x: bool, ref1: usize, ref2: usize, next: impl Fn(usize) -> usize) {
+        let mut org1 = self.original[ref1].get(is_x);
+        let mut org2 = self.original[ref2].get(is_x);
+        let mut cur1 = self.current[ref1].get(is_x);
+        let mut cur2 = self.current[ref2].get(is_x);
+        if org1 > org2 {
+            core::mem::swap(&mut org1, &mut org2);
+            core::mem::swap(&mut cur1, &mut cur2);
+        }
+
+        let mut i = next(ref1);
+        while i != ref2 {
+            let x = self.original[i].get(is_x);
+            let n = if x <= org1 {
+                x + cur1 - org1
+            } else if x >= org2 {
+                x + cur2 - org2
+            } else {
+                cur1 + mul_div(x - org1, cur2 - cur1, org2 - org1)
+            };
+
+            self.current[i].set(is_x, n);
+            i = next(i);
+        }
+    }
+}
+
+
+/// A TrueType instructions interpreter.
+#[derive(Clone)]
+pub(crate) struct Engine<'a> {
+    pub zones: [Zone; 2],
+    pub graphics_state: GraphicsState,
+    /// Scaled control values.
+    pub cvt: Vec<i32>,
+    pub storage: Vec<i32>,
+    functions: Vec<Option<&'a [u8]>>,
+    instruction_defs: Vec<Option<&'a [u8]>>,
+    stack: Vec<i32>,
+    max_stack: usize,
+    /// Scale from font units to 26.6 pixels.
+    pub scale: f32,
+    pub ppem: i32,
+    /// Normalized variation coordinates in 2.14.
+    pub coordinates: Vec<i32>,
+    pub is_prep: bool,
+    instructions_left: u32,
+}
+
+impl<'a> Engine<'a> {
+    pub fn new(max_stack: u16, max_functions: u16, max_storage: u16, max_twilight_points: u16) -> Self {
+        Engine {
+            zones: [Zone::new(usize::from(max_twilight_points)), Zone::default()],
+            graphics_state: GraphicsState::default(),
+            cvt: Vec::new(),
+            storage: vec![0; usize::from(max_storage)],
+            functions: vec![None; usize::from(max_functions)],
+            instruction_defs: vec![None; 256],
+            // Some fonts have a slightly underestimated stack size.
+            stack: Vec::new(),
+            max_stack: usize::from(max_stack) + 32,
+            scale: 1.0,
+            ppem: 0,
+            coordinates: Vec::new(),
+            is_prep: false,
+            instructions_left: 0,
+        }
+    }
+
+    /// Executes a program.
+    pub fn execute(&mut self, code: &'a [u8]) -> Option<()> {
+        self.stack.clear();
+        self.instructions_left = MAX_INSTRUCTIONS;
+        self.run(code, 0)
+    }
+
+    #[inline]
+    fn pop(&mut self) -> Option<i32> {
+        self.stack.pop()
+    }
+
+    #[inline]
+    fn pop_index(&mut self) -> Option<usize> {
+        usize::try_from(self.pop()?).ok()
+    }
+
+    #[inline]
+    fn push(&mut self, n: i32) -> Option<()> {
+        if self.stack.len() >= self.max_stack {
+            return None;
+        }
+
+        self.stack.push(n);
+        Some(())
+    }
+
+    fn run(&mut self, code: &'a [u8], depth: u8) -> Option<()> {
+        if depth > MAX_CALL_DEPTH {
+            return None;
+        }
+
+        let mut ip = 0;
+        while let Some(&opcode) = code.get(ip) {
+            self.instructions_left = self.instructions_left.checked_sub(1)?;
+            let mut next = ip + 1;
+            match opcode {
+                // SVTCA, SPVTCA, SFVTCA
+                0x00..=0x05 => {
+                    let vector = if opcode & 1 != 0 { X_AXIS } else { Y_AXIS };
+                    let gs = &mut self.graphics_state;
+                    if opcode < 0x04 {
+                        gs.projection_vector = vector;
+                        gs.dual_vector = vector;
+                    }
+
+                    if !(0x02..0x04).contains(&opcode) {
+                        gs.freedom_vector = vector;
+                    }
+                }
+                // SPVTL, SFVTL
+                0x06..=0x09 => {
+                    let p2 = self.pop_index()?;
+                    let p1 = self.pop_index()?;
+                    let gs = self.graphics_state;
+                    let a = *self.zones[gs.zp1].current.get(p1)?;
+                    let b = *self.zones[gs.zp2].current.get(p2)?;
+                    let vector = line_vector(a.sub(b), opcode & 1 != 0);
+                    if opcode < 0x08 {
+                        self.graphics_state.projection_vector = vector;
+                        self.graphics_state.dual_vector = vector;
+                    } else {
+                        self.graphics_state.freedom_vector = vector;
+                    }
+                }
+                // SPVFS, SFVFS
+                0x0A | 0x0B => {
+                    let y = self.pop()? as i16;
+                    let x = self.pop()? as i16;
+                    let vector = Vector::normalize(i32::from(x), i32::from(y));
+                    if opcode == 0x0A {
+                        self.graphics_state.projection_vector = vector;
+                        self.graphics_state.dual_vector = vector;
+                    } else {
+                        self.graphics_state.freedom_vector = vector;
+                    }
+                }
+                // GPV, GFV
+                0x0C | 0x0D => {
+                    let gs = self.graphics_state;
+                    let vector = if opcode == 0x0C { gs.projection_vector } else { gs.freedom_vector };
+                    self.push(vector.x)?;
+                    self.push(vector.y)?;
+                }
+                // SFVTPV
+                0x0E => {
+                    self.graphics_state.freedom_vector = self.graphics_state.projection_vector;
+                }
+                // ISECT
+                0x0F => self.intersect()?,
+                // SRP0, SRP1, SRP2
+                0x10 => self.graphics_state.rp0 = self.pop_index()?,
+                0x11 => self.graphics_state.rp1 = self.pop_index()?,
+                0x12 => self.graphics_state.rp2 = self.pop_index()?,
+                // SZP0, SZP1, SZP2, SZPS
+                0x13..=0x16 => {
+                    let zone = self.pop_index()?;
+                    if zone > GLYPH_ZONE {
+                        return None;
+                    }
+
+                    let gs = &mut self.graphics_state;
+                    match opcode {
+                        0x13 => gs.zp0 = zone,
+                        0x14 => gs.zp1 = zone,
+                        0x15 => gs.zp2 = zone,
+                        _ => {
+                            gs.zp0 = zone;
+                            gs.zp1 = zone;
+                            gs.zp2 = zone;
+                        }
+                    }
+                }
+                // SLOOP
+                0x17 => {
+                    let n = self.pop()?;
+                    if n < 0 {
+                        return None;
+                    }
+
+                    self.graphics_state.loop_count = n;
+                }
+                // RTG
+                0x18 => self.graphics_state.round_state = RoundState::ToGrid,
+                // RTHG
+                0x19 => self.graphics_state.round_state = RoundState::ToHalfGrid,
+                // SMD
+                0x1A => self.graphics_state.minimum_distance = self.pop()?,
+                // ELSE
+                0x1B => next = skip_branch(code, next, false)?,
+                // JMPR
+                0x1C => {
+                    let offset = self.pop()?;
+                    next = jump(ip, offset)?;
+                }
+                // SCVTCI
+                0x1D => self.graphics_state.control_value_cutin = self.pop()?,
+                // SSWCI
+                0x1E => self.graphics_state.single_width_cutin = self.pop()?,
+                // SSW
+                0x1F => {
+                    let n = self.pop()?;
+                    self.graphics_state.single_width_value = self.scale(n);
+                }
+                // DUP
+                0x20 => {
+                    let n = *self.stack.last()?;
+                    self.push(n)?;
+                }
+                // POP
+                0x21 => {
+                    self.pop()?;
+                }
+                // CLEAR
+                0x22 => self.stack.clear(),
+                // SWAP
+                0x23 => {
+                    let a = self.pop()?;
+                    let b = self.pop()?;
+                    self.push(a)?;
+                    self.push(b)?;
+                }
+                // DEPTH
+                0x24 => {
+                    let n = self.stack.len() as i32;
+                    self.push(n)?;
+                }
+                // CINDEX, MINDEX
+                0x25 | 0x26 => {
+                    let k = self.pop_index()?;
+                    let index = self.stack.len().checked_sub(k)?;
+                    if k == 0 {
+                        return None;
+                    }
+
+                    if opcode == 0x25 {
+                        let n = self.stack[index];
+                        self.push(n)?;
+                    } else {
+                        let n = self.stack.remove(index);
+                        self.push(n)?;
+                    }
+                }
+                // ALIGNPTS
+                0x27 => {
+                    let p2 = self.pop_index()?;
+                    let p1 = self.pop_index()?;
+                    let gs = self.graphics_state;
+                    let a = *self.zones[gs.zp1].current.get(p1)?;
+                    let b = *self.zones[gs.zp0].current.get(p2)?;
+                    let distance = self.project(b.sub(a)) / 2;
+                    self.move_point(gs.zp1, p1, distance, true)?;
+                    self.move_point(gs.zp0, p2, distance.wrapping_neg(), true)?;
+                }
+                // UTP
+                0x29 => {
+                    let point = self.pop_index()?;
+                    let gs = self.graphics_state;
+                    let flags = self.zones[gs.zp0].flags.get_mut(point)?;
+                    if gs.freedom_vector.x != 0 {
+                        *flags &= !TOUCHED_X;
+                    }
+
+                    if gs.freedom_vector.y != 0 {
+                        *flags &= !TOUCHED_Y;
+                    }
+                }
+                // LOOPCALL
+                0x2A => {
+                    let index = self.pop_index()?;
+                    let count = self.pop()?;
+                    let function = (*self.functions.get(index)?)?;
+                    for _ in 0..count.max(0) {
+                        self.run(function, depth + 1)?;
+                    }
+                }
+                // CALL
+                0x2B => {
+                    let index = self.pop_index()?;
+                    let function = (*self.functions.get(index)?)?;
+                    self.run(function, depth + 1)?;
+                }
+                // FDEF
+                0x2C => {
+                    let index = self.pop_index()?;
+                    let end = find_endf(code, next)?;
+                    *self.functions.get_mut(index)? = Some(&code[next..end]);
+                    next = end + 1;
+                }
+                // MDAP
+                0x2E | 0x2F => {
+                    let point = self.pop_index()?;
+                    let zp0 = self.graphics_state.zp0;
+                    let distance = if opcode == 0x2F {
+                        let current = self.project(*self.zones[zp0].current.get(point)?);
+                        self.round(current).wrapping_sub(current)
+                    } else {
+                        0
+                    };
+
+                    self.move_point(zp0, point, distance, true)?;
+                    self.graphics_state.rp0 = point;
+                    self.graphics_state.rp1 = point;
+                }
+                // IUP
+                0x30 | 0x31 => {
+                    // Instructions can operate only on the glyph zone.
+                    self.zones[GLYPH_ZONE].interpolate_untouched(opcode == 0x31);
+                }
+                // SHP
+                0x32 | 0x33 => {
+                    let (_, _, dx, dy) = self.displacement(opcode == 0x33)?;
+                    let zp2 = self.graphics_state.zp2;
+                    for _ in 0..self.take_loop() {
+                        let point = self.pop_index()?;
+                        self.shift_point(zp2, point, dx, dy, true)?;
+                    }
+                }
+                // SHC
+                0x34 | 0x35 => {
+                    let contour = self.pop_index()?;
+                    let (ref_zone, ref_point, dx, dy) = self.displacement(opcode == 0x35)?;
+                    let zp2 = self.graphics_state.zp2;
+                    let (start, end) = self.zones[zp2].contour(contour)?;
+                    for point in start..=end {
+                        if ref_zone != zp2 || ref_point != point {
+                            self.shift_point(zp2, point, dx, dy, true)?;
+                        }
+                    }
+                }
+                // SHZ
+                0x36 | 0x37 => {
+                    let zone = self.pop_index()?;
+                    if zone > GLYPH_ZONE {
+                        return None;
+                    }
+
+                    let (ref_zone, ref_point, dx, dy) = self.displacement(opcode == 0x37)?;
+                    // Phantom points are not shifted.
+                    let mut len = self.zones[zone].current.len();
+                    if zone == GLYPH_ZONE {
+                        len = len.saturating_sub(4);
+                    }
+
+                    for point in 0..len {
+                        if ref_zone != zone || ref_point != point {
+                            self.shift_point(zone, point, dx, dy, false)?;
+                        }
+                    }
+                }
+                // SHPIX
+                0x38 => {
+                    let amount = self.pop()?;
+                    let fv = self.graphics_state.freedom_vector;
+                    let dx = mul_14(amount, fv.x);
+                    let dy = mul_14(amount, fv.y);
+                    let zp2 = self.graphics_state.zp2;
+                    for _ in 0..self.take_loop() {
+                        let point = self.pop_index()?;
+                        self.shift_point(zp2, point, dx, dy, true)?;
+                    }
+                }
+                // IP
+                0x39 => self.interpolate_points()?,
+                // MSIRP
+                0x3A | 0x3B => {
+                    let distance = self.pop()?;
+                    let point = self.pop_index()?;
+                    let gs = self.graphics_state;
+                    let rp0_original = *self.zones[gs.zp0].original.get(gs.rp0)?;
+                    if gs.zp1 == TWILIGHT_ZONE {
+                        let zone = &mut self.zones[TWILIGHT_ZONE];
+                        *zone.original.get_mut(point)? = rp0_original;
+                        self.move_original(TWILIGHT_ZONE, point, distance)?;
+                        let zone = &mut self.zones[TWILIGHT_ZONE];
+                        zone.current[point] = zone.original[point];
+                    }
+
+                    let current = *self.zones[gs.zp1].current.get(point)?;
+                    let rp0_current = *self.zones[gs.zp0].current.get(gs.rp0)?;
+                    let distance = distance.wrapping_sub(self.project(current.sub(rp0_current)));
+                    self.move_point(gs.zp1, point, distance, true)?;
+                    self.graphics_state.rp1 = gs.rp0;
+                    self.graphics_state.rp2 = point;
+                    if opcode == 0x3B {
+                        self.graphics_state.rp0 = point;
+                    }
+                }
+                // ALIGNRP
+                0x3C => {
+                    let gs = self.graphics_state;
+                    let rp0_current = *self.zones[gs.zp0].current.get(gs.rp0)?;
+                    for _ in 0..self.take_loop() {
+                        let point = self.pop_index()?;
+                        let current = *self.zones[gs.zp1].current.get(point)?;
+                        let distance = self.project(current.sub(rp0_current));
+                        self.move_point(gs.zp1, point, distance.wrapping_neg(), true)?;
+                    }
+                }
+                // RTDG
+                0x3D => self.graphics_state.round_state = RoundState::ToDoubleGrid,
+                // MIAP
+                0x3E | 0x3F => {
+                    let index = self.pop_index()?;
+                    let point = self.pop_index()?;
+                    let mut distance = *self.cvt.get(index)?;
+                    let gs = self.graphics_state;
+                    if gs.zp0 == TWILIGHT_ZONE {
+                        let fv = gs.freedom_vector;
+                        let p = Point::new(mul_14(distance, fv.x), mul_14(distance, fv.y));
+                        let zone = &mut self.zones[TWILIGHT_ZONE];
+                        *zone.original.get_mut(point)? = p;
+                        zone.current[point] = p;
+                    }
+
+                    let current = self.project(*self.zones[gs.zp0].current.get(point)?);
+                    if opcode == 0x3F {
+                        if distance.wrapping_sub(current).wrapping_abs() > gs.control_value_cutin {
+                            distance = current;
+                        }
+
+                        distance = self.round(distance);
+                    }
+
+                    self.move_point(gs.zp0, point, distance.wrapping_sub(current), true)?;
+                    self.graphics_state.rp0 = point;
+                    self.graphics_state.rp1 = point;
+                }
+                // NPUSHB, NPUSHW
+                0x40 | 0x41 => {
+                    let count = usize::from(*code.get(next)?);
+                    next = self.push_data(code, next + 1, count, opcode == 0x41)?;
+                }
+                // WS
+                0x42 => {
+                    let value = self.pop()?;
+                    let index = self.pop_index()?;
+                    // Out of bounds writes are ignored.
+                    if let Some(n) = self.storage.get_mut(index) {
+                        *n = value;
+                    }
+                }
+                // RS
+                0x43 => {
+                    let index = self.pop_index()?;
+                    let value = self.storage.get(index).cloned().unwrap_or(0);
+                    self.push(value)?;
+                }
+                // WCVTP, WCVTF
+                0x44 | 0x70 => {
+                    let mut value = self.pop()?;
+                    let index = self.pop_index()?;
+                    if opcode == 0x70 {
+                        value = self.scale(value);
+                    }
+
+                    if let Some(n) = self.cvt.get_mut(index) {
+                        *n = value;
+                    }
+                }
+                // RCVT
+                0x45 => {
+                    let index = self.pop_index()?;
+                    let value = self.cvt.get(index).cloned().unwrap_or(0);
+                    self.push(value)?;
+                }
+                // GC
+                0x46 | 0x47 => {
+                    let point = self.pop_index()?;
+                    let zone = &self.zones[self.graphics_state.zp2];
+                    let value = if opcode == 0x46 {
+                        self.project(*zone.current.get(point)?)
+                    } else {
+                        self.dual_project(*zone.original.get(point)?)
+                    };
+                    self.push(value)?;
+                }
+                // SCFS
+                0x48 => {
+                    let value = self.pop()?;
+                    let point = self.pop_index()?;
+                    let zp2 = self.graphics_state.zp2;
+                    let current = self.project(*self.zones[zp2].current.get(point)?);
+                    self.move_point(zp2, point, value.wrapping_sub(current), true)?;
+                    if zp2 == TWILIGHT_ZONE {
+                        let zone = &mut self.zones[TWILIGHT_ZONE];
+                        zone.original[point] = zone.current[point];
+                    }
+                }
+                // MD
+                0x49 | 0x4A => {
+                    let p2 = self.pop_index()?;
+                    let p1 = self.pop_index()?;
+                    let gs = self.graphics_state;
+                    let distance = if opcode == 0x49 {
+                        let a = *self.zones[gs.zp0].current.get(p1)?;
+                        let b = *self.zones[gs.zp1].current.get(p2)?;
+                        self.project(a.sub(b))
+                    } else {
+                        let a = *self.zones[gs.zp0].original.get(p1)?;
+                        let b = *self.zones[gs.zp1].original.get(p2)?;
+                        self.dual_project(a.sub(b))
+                    };
+                    self.push(distance)?;
+                }
+                // MPPEM, MPS
+                0x4B | 0x4C => {
+                    let ppem = self.ppem;
+                    self.push(ppem)?;
+                }
+                // FLIPON, FLIPOFF
+                0x4D => self.graphics_state.auto_flip = true,
+                0x4E => self.graphics_state.auto_flip = false,
+                // DEBUG
+                0x4F => {
+                    self.pop()?;
+                }
+                // LT, LTEQ, GT, GTEQ, EQ, NEQ
+                0x50..=0x55 => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    let result = match opcode {
+                        0x50 => a < b,
+                        0x51 => a <= b,
+                        0x52 => a > b,
+                        0x53 => a >= b,
+                        0x54 => a == b,
+                        _ => a != b,
+                    };
+                    self.push(result as i32)?;
+                }
+                // ODD, EVEN
+                0x56 | 0x57 => {
+                    let n = self.pop()?;
+                    let n = self.round(n) & 127;
+                    let result = if opcode == 0x56 { n == 64 } else { n == 0 };
+                    self.push(result as i32)?;
+                }
+                // IF
+                0x58 => {
+                    if self.pop()? == 0 {
+                        next = skip_branch(code, next, true)?;
+                    }
+                }
+                // EIF
+                0x59 => {}
+                // AND, OR
+                0x5A | 0x5B => {
+                    let b = self.pop()? != 0;
+                    let a = self.pop()? != 0;
+                    let result = if opcode == 0x5A { a && b } else { a || b };
+                    self.push(result as i32)?;
+                }
+                // NOT
+                0x5C => {
+                    let n = self.pop()?;
+                    self.push((n == 0) as i32)?;
+                }
+                // DELTAP1, DELTAP2, DELTAP3
+                0x5D | 0x71 | 0x72 => {
+                    let base = match opcode {
+                        0x5D => 0,
+                        0x71 => 16,
+                        _ => 32,
+                    };
+
+                    let count = self.pop()?;
+                    for _ in 0..count.max(0) {
+                        let point = self.pop_index()?;
+                        let arg = self.pop()?;
+                        if let Some(delta) = self.delta(arg, base) {
+                            let zp0 = self.graphics_state.zp0;
+                            // Invalid points are ignored.
+                            if point < self.zones[zp0].current.len() {
+                                self.move_point(zp0, point, delta, true)?;
+                            }
+                        }
+                    }
+                }
+                // SDB, SDS
+                0x5E => self.graphics_state.delta_base = self.pop()?,
+                0x5F => {
+                    let n = self.pop()?;
+                    // Invalid values are ignored.
+                    if (0..=6).contains(&n) {
+                        self.graphics_state.delta_shift = n;
+                    }
+                }
+                // ADD, SUB
+                0x60 | 0x61 => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    let n = if opcode == 0x60 { a.wrapping_add(b) } else { a.wrapping_sub(b) };
+                    self.push(n)?;
+                }
+                // DIV
+                0x62 => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    if b == 0 {
+                        return None;
+                    }
+
+                    self.push((i64::from(a) * 64 / i64::from(b)) as i32)?;
+                }
+                // MUL
+                0x63 => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    self.push(mul_div(a, b, 64))?;
+                }
+                // ABS, NEG, FLOOR, CEILING
+                0x64..=0x67 => {
+                    let n = self.pop()?;
+                    let n = match opcode {
+                        0x64 => n.wrapping_abs(),
+                        0x65 => n.wrapping_neg(),
+                        0x66 => n & !63,
+                        _ => n.wrapping_add(63) & !63,
+                    };
+                    self.push(n)?;
+                }
+                // ROUND
+                0x68..=0x6B => {
+                    let n = self.pop()?;
+                    let n = self.round(n);
+                    self.push(n)?;
+                }
+                // NROUND
+                0x6C..=0x6F => {}
+                // DELTAC1, DELTAC2, DELTAC3
+                0x73..=0x75 => {
+                    let base = (i32::from(opcode) - 0x73) * 16;
+                    let count = self.pop()?;
+                    for _ in 0..count.max(0) {
+                        let index = self.pop_index()?;
+                        let arg = self.pop()?;
+                        if let Some(delta) = self.delta(arg, base) {
+                            if let Some(n) = self.cvt.get_mut(index) {
+                                *n += delta;
+                            }
+                        }
+                    }
+                }
+                // SROUND, S45ROUND
+                0x76 | 0x77 => {
+                    let n = self.pop()?;
+                    self.set_super_round(n, opcode == 0x77);
+                }
+                // JROT, JROF
+                0x78 | 0x79 => {
+                    let condition = self.pop()? != 0;
+                    let offset = self.pop()?;
+                    if condition == (opcode == 0x78) {
+                        next = jump(ip, offset)?;
+                    }
+                }
+                // ROFF
+                0x7A => self.graphics_state.round_state = RoundState::Off,
+                // RUTG
+                0x7C => self.graphics_state.round_state = RoundState::UpToGrid,
+                // RDTG
+                0x7D => self.graphics_state.round_state = RoundState::DownToGrid,
+                // SANGW, AA
+                0x7E | 0x7F => {
+                    self.pop()?;
+                }
+                // FLIPPT
+                0x80 => {
+                    for _ in 0..self.take_loop() {
+                        let point = self.pop_index()?;
+                        *self.zones[GLYPH_ZONE].flags.get_mut(point)? ^= ON_CURVE;
+                    }
+                }
+                // FLIPRGON, FLIPRGOFF
+                0x81 | 0x82 => {
+                    let high = self.pop_index()?;
+                    let low = self.pop_index()?;
+                    let flags = self.zones[GLYPH_ZONE].flags.get_mut(low..=high)?;
+                    for flag in flags {
+                        if opcode == 0x81 {
+                            *flag |= ON_CURVE;
+                        } else {
+                            *flag &= !ON_CURVE;
+                        }
+                    }
+                }
+                // SCANCTRL
+                0x85 => {
+                    // Dropout control is a rasterizer feature.
+                    self.pop()?;
+                }
+                // SDPVTL
+                0x86 | 0x87 => {
+                    let p2 = self.pop_index()?;
+                    let p1 = self.pop_index()?;
+                    let gs = self.graphics_state;
+                    let a = &self.zones[gs.zp1];
+                    let b = &self.zones[gs.zp2];
+                    let original = a.original.get(p1)?.sub(*b.original.get(p2)?);
+                    let current = a.current.get(p1)?.sub(*b.current.get(p2)?);
+                    self.graphics_state.dual_vector = line_vector(original, opcode & 1 != 0);
+                    self.graphics_state.projection_vector = line_vector(current, opcode & 1 != 0);
+                }
+                // GETINFO
+                0x88 => {
+                    let selector = self.pop()?;
+                    let mut result = 0;
+                    // Version 35 matches the classic grayscale rasterizer.
+                    if selector & 0x01 != 0 {
+                        result |= 35;
+                    }
+
+                    if selector & 0x08 != 0 && !self.coordinates.is_empty() {
+                        result |= 1 << 10;
+                    }
+
+                    if selector & 0x20 != 0 {
+                        result |= 1 << 12;
+                    }
+
+                    self.push(result)?;
+                }
+                // IDEF
+                0x89 => {
+                    let index = self.pop_index()?;
+                    let end = find_endf(code, next)?;
+                    *self.instruction_defs.get_mut(index)? = Some(&code[next..end]);
+                    next = end + 1;
+                }
+                // ROLL
+                0x8A => {
+                    let a = self.pop()?;
+                    let b = self.pop()?;
+                    let c = self.pop()?;
+                    self.push(b)?;
+                    self.push(a)?;
+                    self.push(c)?;
+                }
+                // MAX, MIN
+                0x8B | 0x8C => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    self.push(if opcode == 0x8B { a.max(b) } else { a.min(b) })?;
+                }
+                // SCANTYPE
+                0x8D => {
+                    self.pop()?;
+                }
+                // INSTCTRL
+                0x8E => {
+                    let selector = self.pop()?;
+                    let value = self.pop()?;
+                    // Can be used only in the Control Value Program.
+                    if self.is_prep && (1..=3).contains(&selector) {
+                        let flag = 1 << (selector - 1);
+                        let gs = &mut self.graphics_state;
+                        gs.instruct_control &= !flag;
+                        if value != 0 {
+                            gs.instruct_control |= flag;
+                        }
+                    }
+                }
+                // GETVARIATION
+                0x91 if !self.coordinates.is_empty() => {
+                    for i in 0..self.coordinates.len() {
+                        let n = self.coordinates[i];
+                        self.push(n)?;
+                    }
+                }
+                // PUSHB
+                0xB0..=0xB7 => {
+                    let count = usize::from(opcode - 0xB0 + 1);
+                    next = self.push_data(code, next, count, false)?;
+                }
+                // PUSHW
+                0xB8..=0xBF => {
+                    let count = usize::from(opcode - 0xB8 + 1);
+                    next = self.push_data(code, next, count, true)?;
+                }
+                // MDRP
+                0xC0..=0xDF => self.move_direct_relative_point(opcode)?,
+                // MIRP
+                0xE0..=0xFF => self.move_indirect_relative_point(opcode)?,
+                _ => {
+                    // Undefined opcodes can be defined via IDEF.
+                    let instruction = (*self.instruction_defs.get(usize::from(opcode))?)?;
+                    self.run(instruction, depth + 1)?;
+                }
+            }
+
+            ip = next;
+        }
+
+        Some(())
+    }
+
+    fn push_data(&mut self, code: &[u8], offset: usize, count: usize, words: bool) -> Option<usize> {
+        if words {
+            let data = code.get(offset..offset + count * 2)?;
+            for n in data.chunks(2) {
+                self.push(i32::from(i16::from_be_bytes([n[0], n[1]])))?;
+            }
+
+            Some(offset + count * 2)
+        } else {
+            let data = code.get(offset..offset + count)?;
+            for n in data {
+                self.push(i32::from(*n))?;
+            }
+
+            Some(offset + count)
+        }
+    }
+
+    /// Returns the loop counter and resets it.
+    #[inline]
+    fn take_loop(&mut self) -> i32 {
+        let n = self.graphics_state.loop_count;
+        self.graphics_state.loop_count = 1;
+        n
+    }
+
+    /// Scales a value from font units to 26.6 pixels.
+    #[inline]
+    pub fn scale(&self, n: i32) -> i32 {
+        (n as f32 * self.scale).round() as i32
+    }
+
+    #[inline]
+    fn project(&self, p: Point) -> i32 {
+        self.graphics_state.projection_vector.project(p)
+    }
+
+    #[inline]
+    fn dual_project(&self, p: Point) -> i32 {
+        self.graphics_state.dual_vector.project(p)
+    }
+
+    /// Returns the dot product of freedom and projection vectors in 2.14.
+    fn freedom_dot_projection(&self) -> i32 {
+        let gs = &self.graphics_state;
+        let n = (gs.freedom_vector.x * gs.projection_vector.x
+            + gs.freedom_vector.y * gs.projection_vector.y) >> 14;
+
+        // Almost orthogonal vectors would move points too far.
+        if n.abs() < 0x400 { 0x4000 } else { n }
+    }
+
+    /// Moves a point along the freedom vector,
+    /// so its projection changes by `distance`.
+    fn move_point(&mut self, zone: usize, point: usize, distance: i32, touch: bool) -> Option<()> {
+        let fv = self.graphics_state.freedom_vector;
+        let fdotp = self.freedom_dot_projection();
+        let zone = &mut self.zones[zone];
+        let p = zone.current.get_mut(point)?;
+        if fv.x != 0 {
+            p.x = p.x.wrapping_add(mul_div(distance, fv.x, fdotp));
+        }
+
+        if fv.y != 0 {
+            p.y = p.y.wrapping_add(mul_div(distance, fv.y, fdotp));
+        }
+
+        if touch {
+            zone.touch(point, fv);
+        }
+
+        Some(())
+    }
+
+    /// Like `move_point`, but for original points.
+    fn move_original(&mut self, zone: usize, point: usize, distance: i32) -> Option<()> {
+        let fv = self.graphics_state.freedom_vector;
+        let fdotp = self.freedom_dot_projection();
+        let p = self.zones[zone].original.get_mut(point)?;
+        p.x = p.x.wrapping_add(mul_div(distance, fv.x, fdotp));
+        p.y = p.y.wrapping_add(mul_div(distance, fv.y, fdotp));
+        Some(())
+    }
+
+    fn shift_point(&mut self, zone: usize, point: usize, dx: i32, dy: i32, touch: bool) -> Option<()> {
+        let fv = self.graphics_state.freedom_vector;
+        let zone = &mut self.zones[zone];
+        let p = zone.current.get_mut(point)?;
+        if fv.x != 0 {
+            p.x = p.x.wrapping_add(dx);
+        }
+
+        if fv.y != 0 {
+            p.y = p.y.wrapping_add(dy);
+        }
+
+        if touch {
+            zone.touch(point, fv);
+        }
+
+        Some(())
+    }
+
+    /// Returns the displacement of a reference point used by SHP, SHC and SHZ.
+    ///
+    /// Uses `rp1` in `zp0` when `use_rp1` is set and `rp2` in `zp1` otherwise.
+    fn displacement(&self, use_rp1: bool) -> Option<(usize, usize, i32, i32)> {
+        let gs = &self.graphics_state;
+        let (zone, point) = if use_rp1 { (gs.zp0, gs.rp1) } else { (gs.zp1, gs.rp2) };
+        let z = &self.zones[zone];
+        let distance = self.project(z.current.get(point)?.sub(*z.original.get(point)?));
+        let fdotp = self.freedom_dot_projection();
+        let dx = mul_div(distance, gs.freedom_vector.x, fdotp);
+        let dy = mul_div(distance, gs.freedom_vector.y, fdotp);
+        Some((zone, point, dx, dy))
+    }
+
+    fn intersect(&mut self) -> Option<()> {
+        let b1 = self.pop_index()?;
+        let b0 = self.pop_index()?;
+        let a1 = self.pop_index()?;
+        let a0 = self.pop_index()?;
+        let point = self.pop_index()?;
+
+        let gs = self.graphics_state;
+        let pa0 = *self.zones[gs.zp1].current.get(a0)?;
+        let pa1 = *self.zones[gs.zp1].current.get(a1)?;
+        let pb0 = *self.zones[gs.zp0].current.get(b0)?;
+        let pb1 = *self.zones[gs.zp0].current.get(b1)?;
+
+        let (dax, day) = (pa1.x.wrapping_sub(pa0.x), pa1.y.wrapping_sub(pa0.y));
+        let (dbx, dby) = (pb1.x.wrapping_sub(pb0.x), pb1.y.wrapping_sub(pb0.y));
+        let (dx, dy) = (pb0.x.wrapping_sub(pa0.x), pb0.y.wrapping_sub(pa0.y));
+
+        let discriminant = mul_div(dax, dby.wrapping_neg(), 64).wrapping_add(mul_div(day, dbx, 64));
+        let dot_product = mul_div(dax, dbx, 64).wrapping_add(mul_div(day, dby, 64));
+        let p = if 19 * i64::from(discriminant).abs() > i64::from(dot_product).abs() {
+            let n = mul_div(dx, dby.wrapping_neg(), 64).wrapping_add(mul_div(dy, dbx, 64));
+            Point::new(
+                pa0.x.wrapping_add(mul_div(n, dax, discriminant)),
+                pa0.y.wrapping_add(mul_div(n, day, discriminant)),
+            )
+        } else {
+            // Almost parallel lines. Take the middle point.
+            let middle = |a: i32, b: i32, c: i32, d: i32| {
+                ((i64::from(a) + i64::from(b) + i64::from(c) + i64::from(d)) / 4) as i32
+            };
+            Point::new(middle(pa0.x, pa1.x, pb0.x, pb1.x), middle(pa0.y, pa1.y, pb0.y, pb1.y))
+        };
+
+        let zone = &mut self.zones[gs.zp2];
+        *zone.current.get_mut(point)? = p;
+        zone.flags[point] |= TOUCHED_X | TOUCHED_Y;
+        Some(())
+    }
+
+    fn interpolate_points(&mut self) -> Option<()> {
+        let gs = self.graphics_state;
+        let base_original = *self.zones[gs.zp0].original.get(gs.rp1)?;
+        let base_current = *self.zones[gs.zp0].current.get(gs.rp1)?;
+        let original_range = self.dual_project(self.zones[gs.zp1].original.get(gs.rp2)?.sub(base_original));
+        let current_range = self.project(self.zones[gs.zp1].current.get(gs.rp2)?.sub(base_current));
+
+        for _ in 0..self.take_loop() {
+            let point = self.pop_index()?;
+            let zone = &self.zones[gs.zp2];
+            let original = self.dual_project(zone.original.get(point)?.sub(base_original));
+            let current = self.project(zone.current.get(point)?.sub(base_current));
+            let distance = if original != 0 && original_range != 0 {
+                mul_div(original, current_range, original_range)
+            } else {
+                original
+            };
+
+            self.move_point(gs.zp2, point, distance.wrapping_sub(current), true)?;
+        }
+
+        Some(())
+    }
+
+    fn move_direct_relative_point(&mut self, opcode: u8) -> Option<()> {
+        let point = self.pop_index()?;
+        let gs = self.graphics_state;
+
+        let original = self.zones[gs.zp1].original.get(point)?.sub(*self.zones[gs.zp0].original.get(gs.rp0)?);
+        let mut original_distance = self.dual_project(original);
+        if original_distance.wrapping_sub(gs.single_width_value).wrapping_abs() < gs.single_width_cutin {
+            original_distance = with_sign(gs.single_width_value, original_distance);
+        }
+
+        let mut distance = if opcode & 0x04 != 0 {
+            self.round(original_distance)
+        } else {
+            original_distance
+        };
+
+        if opcode & 0x08 != 0 {
+            distance = apply_minimum_distance(distance, original_distance, gs.minimum_distance);
+        }
+
+        let current = self.zones[gs.zp1].current.get(point)?.sub(*self.zones[gs.zp0].current.get(gs.rp0)?);
+        let current_distance = self.project(current);
+        self.move_point(gs.zp1, point, distance.wrapping_sub(current_distance), true)?;
+
+        self.graphics_state.rp1 = gs.rp0;
+        self.graphics_state.rp2 = point;
+        if opcode & 0x10 != 0 {
+            self.graphics_state.rp0 = point;
+        }
+
+        Some(())
+    }
+
+    fn move_indirect_relative_point(&mut self, opcode: u8) -> Option<()> {
+        let index = self.pop_index()?;
+        let point = self.pop_index()?;
+        let gs = self.graphics_state;
+
+        let mut cvt_distance = *self.cvt.get(index)?;
+        if cvt_distance.wrapping_sub(gs.single_width_value).wrapping_abs() < gs.single_width_cutin {
+            cvt_distance = with_sign(gs.single_width_value, cvt_distance);
+        }
+
+        // A twilight point is placed at the CVT distance from `rp0`.
+        if gs.zp1 == TWILIGHT_ZONE {
+            let fv = gs.freedom_vector;
+            let rp0 = *self.zones[gs.zp0].original.get(gs.rp0)?;
+            let p = Point::new(
+                rp0.x.wrapping_add(mul_14(cvt_distance, fv.x)),
+                rp0.y.wrapping_add(mul_14(cvt_distance, fv.y)),
+            );
+            let zone = &mut self.zones[TWILIGHT_ZONE];
+            *zone.original.get_mut(point)? = p;
+            zone.current[point] = p;
+        }
+
+        let original = self.zones[gs.zp1].original.get(point)?.sub(*self.zones[gs.zp0].original.get(gs.rp0)?);
+        let original_distance = self.dual_project(original);
+        let current = self.zones[gs.zp1].current.get(point)?.sub(*self.zones[gs.zp0].current.get(gs.rp0)?);
+        let current_distance = self.project(current);
+
+        if gs.auto_flip && (original_distance ^ cvt_distance) < 0 {
+            cvt_distance = cvt_distance.wrapping_neg();
+        }
+
+        let mut distance = if opcode & 0x04 != 0 {
+            // The cut-in test is performed only for points of the same zone.
+            if gs.zp0 == gs.zp1 && cvt_distance.wrapping_sub(original_distance).wrapping_abs() > gs.control_value_cutin {
+                cvt_distance = original_distance;
+            }
+
+            self.round(cvt_distance)
+        } else {
+            cvt_distance
+        };
+
+        if opcode & 0x08 != 0 {
+            distance = apply_minimum_distance(distance, original_distance, gs.minimum_distance);
+        }
+
+        self.move_point(gs.zp1, point, distance.wrapping_sub(current_distance), true)?;
+
+        self.graphics_state.rp1 = gs.rp0;
+        self.graphics_state.rp2 = point;
+        if opcode & 0x10 != 0 {
+            self.graphics_state.rp0 = point;
+        }
+
+        Some(())
+    }
+
+    /// Returns a DELTA instruction adjustment if it applies to the current ppem.
+    fn delta(&self, arg: i32, base: i32) -> Option<i32> {
+        let gs = &self.graphics_state;
+        let ppem = ((arg & 0xF0) >> 4).wrapping_add(gs.delta_base).wrapping_add(base);
+        if ppem != self.ppem {
+            return None;
+        }
+
+        let mut steps = (arg & 0x0F) - 8;
+        if steps >= 0 {
+            steps += 1;
+        }
+
+        Some(steps.wrapping_mul(64) / (1 << gs.delta_shift))
+    }
+
+    fn set_super_round(&mut self, n: i32, is_45: bool) {
+        let gs = &mut self.graphics_state;
+        // The 45 degrees grid period is sqrt(2)/2 pixels.
+        let grid = if is_45 { 45 } else { 64 };
+        gs.period = match (n >> 6) & 3 {
+            0 => grid / 2,
+            2 => grid * 2,
+            _ => grid,
+        };
+
+        gs.phase = match (n >> 4) & 3 {
+            0 => 0,
+            1 => gs.period / 4,
+            2 => gs.period / 2,
+            _ => gs.period * 3 / 4,
+        };
+
+        gs.threshold = match n & 0x0F {
+            0 => gs.period - 1,
+            t => (t - 4) * gs.period / 8,
+        };
+
+        gs.round_state = if is_45 { RoundState::Super45 } else { RoundState::Super };
+    }
+
+    /// Rounds a distance using the current round state.
+    ///
+    /// The sign of a distance is preserved.
+    fn round(&self, distance: i32) -> i32 {
+        let gs = &self.graphics_state;
+        let round = |f: &dyn Fn(i32) -> i32| {
+            if distance >= 0 {
+                f(distance).max(0)
+            } else {
+                f(distance.wrapping_neg()).wrapping_neg().min(0)
+            }
+        };
+
+        match gs.round_state {
+            RoundState::ToHalfGrid => round(&|d| (d & !63).wrapping_add(32)),
+            RoundState::ToGrid => round(&|d| d.wrapping_add(32) & !63),
+            RoundState::ToDoubleGrid => round(&|d| d.wrapping_add(16) & !31),
+            RoundState::DownToGrid => round(&|d| d & !63),
+            RoundState::UpToGrid => round(&|d| d.wrapping_add(63) & !63),
+            RoundState::Off => distance,
+            RoundState::Super => {
+                let (period, phase, threshold) = (gs.period, gs.phase, gs.threshold);
+                if distance >= 0 {
+                    let n = (distance.wrapping_sub(phase).wrapping_add(threshold) & -period).wrapping_add(phase);
+                    if n < 0 { phase } else { n }
+                } else {
+                    let n = (threshold.wrapping_sub(phase).wrapping_sub(distance) & -period).wrapping_neg().wrapping_sub(phase);
+                    if n > 0 { -phase } else { n }
+                }
+            }
+            RoundState::Super45 => {
+                let (period, phase, threshold) = (gs.period, gs.phase, gs.threshold);
+                if distance >= 0 {
+                    let n = (distance.wrapping_sub(phase).wrapping_add(threshold) / period * period).wrapping_add(phase);
+                    if n < 0 { phase } else { n }
+                } else {
+                    let n = (threshold.wrapping_sub(phase).wrapping_sub(distance) / period * period).wrapping_neg().wrapping_sub(phase);
+                    if n > 0 { -phase } else { n }
+                }
+            }
+        }
+    }
+}
+
+impl core::fmt::Debug for Engine<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Engine()")
+    }
+}
+
+
+/// Returns a unit vector parallel or perpendicular to a line.
+fn line_vector(d: Point, perpendicular: bool) -> Vector {
+    let (mut x, mut y) = (d.x, d.y);
+    if x == 0 && y == 0 {
+        return X_AXIS;
+    }
+
+    if perpendicular {
+        // Rotate 90 degrees counterclockwise.
+        let t = y;
+        y = x;
+        x = t.wrapping_neg();
+    }
+
+    Vector::normalize(x, y)
+}
+
+/// Returns the end of a jump.
+fn jump(ip: usize, offset: i32) -> Option<usize> {
+    // A zero offset would loop forever.
+    if offset == 0 {
+        return None;
+    }
+
+    usize::try_from(ip as i64 + i64::from(offset)).ok()
+}
+
+/// Returns the length of an instruction with its inline data.
+fn instruction_len(code: &[u8], ip: usize) -> Option<usize> {
+    let opcode = *code.get(ip)?;
+    let len = match opcode {
+        0x40 => 2 + usize::from(*code.get(ip + 1)?),
+        0x41 => 2 + usize::from(*code.get(ip + 1)?) * 2,
+        0xB0..=0xB7 => 1 + usize::from(opcode - 0xB0 + 1),
+        0xB8..=0xBF => 1 + usize::from(opcode - 0xB8 + 1) * 2,
+        _ => 1,
+    };
+
+    Some(len)
+}
+
+/// Skips an IF or ELSE branch.
+///
+/// Returns an offset right after the matching EIF or,
+/// when `stop_at_else` is set, ELSE.
+fn skip_branch(code: &[u8], mut ip: usize, stop_at_else: bool) -> Option<usize> {
+    let mut nesting = 0;
+    loop {
+        let opcode = *code.get(ip)?;
+        let len = instruction_len(code, ip)?;
+        match opcode {
+            0x58 => nesting += 1,
+            0x1B if nesting == 0 && stop_at_else => return Some(ip + len),
+            0x59 => {
+                if nesting == 0 {
+                    return Some(ip + len);
+                }
+
+                nesting -= 1;
+            }
+            _ => {}
+        }
+
+        ip += len;
+    }
+}
+
+/// Returns the offset of the ENDF that finishes a function definition.
+fn find_endf(code: &[u8], mut ip: usize) -> Option<usize> {
+    loop {
+        match *code.get(ip)? {
+            0x2D => return Some(ip),
+            // Nested definitions are not allowed.
+            0x2C | 0x89 => return None,
+            _ => ip += instruction_len(code, ip)?,
+        }
+    }
+}
+
+/// Calculates `a * b / c` with rounding.
+#[inline]
+fn mul_div(a: i32, b: i32, c: i32) -> i32 {
+    if c == 0 {
+        return 0;
+    }
+
+    let n = i64::from(a) * i64::from(b);
+    let c = i64::from(c);
+    let half = c.abs() / 2;
+    let n = if (n < 0) == (c < 0) { (n.abs() + half) / c.abs() } else { -((n.abs() + half) / c.abs()) };
+    n as i32
+}
+
+/// Multiplies by a 2.14 value.
+#[inline]
+fn mul_14(a: i32, b: i32) -> i32 {
+    ((i64::from(a) * i64::from(b) + 0x2000) >> 14) as i32
+}
+
+#[inline]
+fn with_sign(n: i32, sign: i32) -> i32 {
+    if sign >= 0 { n } else { n.wrapping_neg() }
+}
+
+#[inline]
+fn apply_minimum_distance(distance: i32, original_distance: i32, minimum_distance: i32) -> i32 {
+    if original_distance >= 0 {
+        distance.max(minimum_distance)
+    } else {
+        distance.min(minimum_distance.wrapping_neg())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine() -> Engine<'static> {
+        let mut engine = Engine::new(32, 4, 4, 2);
+        engine.ppem = 12;
+        engine.cvt = vec![280, 0];
+        let mut zone = Zone::new(4);
+        zone.end_points = vec![3];
+        engine.zones[GLYPH_ZONE] = zone;
+        engine
+    }
+
+    fn set_points(engine: &mut Engine, points: &[(i32, i32)]) {
+        let zone = &mut engine.zones[GLYPH_ZONE];
+        for (i, &(x, y)) in points.iter().enumerate() {
+            zone.original[i] = Point::new(x, y);
+            zone.current[i] = Point::new(x, y);
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut engine = engine();
+        engine.execute(&[
+            0xB1, 0x80, 0x40, // PUSHB[1] 128 64
+            0x63, // MUL: 128
+            0xB0, 0x20, // PUSHB[0] 32
+            0x62, // DIV: 256
+            0xB8, 0xFF, 0x9C, // PUSHW[0] -100
+            0x60, // ADD: 156
+            0x20, // DUP
+            0x68, // ROUND: 128
+            0x61, // SUB: 28
+        ]).unwrap();
+        assert_eq!(engine.stack, [28]);
+
+        assert!(engine.execute(&[0xB1, 1, 0, 0x62]).is_none()); // division by zero
+        assert!(engine.execute(&[0x21]).is_none()); // stack underflow
+    }
+
+    #[test]
+    fn branches() {
+        let mut engine = engine();
+        engine.execute(&[
+            0xB0, 0, // PUSHB[0] 0
+            0x58, // IF
+            0xB0, 1, // PUSHB[0] 1
+            0x58, // IF
+            0xB0, 2, // PUSHB[0] 2
+            0x59, // EIF
+            0x1B, // ELSE
+            0xB0, 3, // PUSHB[0] 3
+            0x59, // EIF
+            0xB1, 7, 1, // PUSHB[1] 7 1
+            0x58, // IF
+            0xB0, 4, // PUSHB[0] 4
+            0x1B, // ELSE
+            0xB0, 5, // PUSHB[0] 5
+            0x59, // EIF
+            0x21, // POP: 4
+        ]).unwrap();
+        assert_eq!(engine.stack, [3, 7]);
+
+        // JROT skips PUSHB[0] 9.
+        engine.execute(&[
+            0xB1, 3, 1, // PUSHB[1] 3 1
+            0x78, // JROT
+            0xB0, 9, // PUSHB[0] 9
+            0xB0, 8, // PUSHB[0] 8
+        ]).unwrap();
+        assert_eq!(engine.stack, [8]);
+    }
+
+    #[test]
+    fn functions() {
+        let mut engine = engine();
+        engine.execute(&[
+            0xB0, 1, // PUSHB[0] 1
+            0x2C, // FDEF
+            0xB0, 10, // PUSHB[0] 10
+            0x60, // ADD
+            0x2D, // ENDF
+        ]).unwrap();
+        engine.execute(&[
+            0xB3, 5, 3, 1, 1, // PUSHB[3] 5 3 1 1
+            0x2B, // CALL: 5 3 11
+            0x23, // SWAP
+            0xB0, 1, // PUSHB[0] 1
+            0x2A, // LOOPCALL: 5 41
+        ]).unwrap();
+        assert_eq!(engine.stack, [5, 41]);
+
+        assert!(engine.execute(&[0xB0, 2, 0x2B]).is_none()); // undefined function
+        assert!(engine.execute(&[0xB0, 0, 0x2C, 0x2C, 0x2D]).is_none()); // nested definition
+    }
+
+    #[test]
+    fn storage_and_cvt() {
+        let mut engine = engine();
+        engine.scale = 0.5;
+        engine.execute(&[
+            0xB1, 2, 42, // PUSHB[1] 2 42
+            0x42, // WS
+            0xB0, 2, // PUSHB[0] 2
+            0x43, // RS
+            0xB1, 1, 100, // PUSHB[1] 1 100
+            0x70, // WCVTF
+            0xB0, 1, // PUSHB[0] 1
+            0x45, // RCVT
+        ]).unwrap();
+        assert_eq!(engine.stack, [42, 50]);
+    }
+
+    #[test]
+    fn move_points() {
+        let mut engine = engine();
+        set_points(&mut engine, &[(0, 0), (100, 10), (150, 20), (300, 30)]);
+        engine.execute(&[
+            0xB0, 0, // PUSHB[0] 0
+            0x2F, // MDAP[rnd]: point 0 stays at 0
+            0xB1, 3, 0, // PUSHB[1] 3 0
+            0xFD, // MIRP[rp0, min, rnd]: point 3 at the rounded CVT 0 distance, i.e. 256
+            0xB0, 1, // PUSHB[0] 1
+            0xC4, // MDRP[rnd]: point 1 at the rounded original distance from point 3, i.e. -192
+            0x31, // IUP[x]
+        ]).unwrap();
+
+        let zone = &engine.zones[GLYPH_ZONE];
+        assert_eq!(zone.current[0], Point::new(0, 0));
+        assert_eq!(zone.current[3], Point::new(256, 30));
+        assert_eq!(zone.current[1], Point::new(64, 10));
+        // Interpolated between points 1 and 3.
+        assert_eq!(zone.current[2], Point::new(112, 20));
+        assert_eq!(engine.graphics_state.rp0, 3);
+        assert_eq!(engine.graphics_state.rp2, 1);
+    }
+
+    #[test]
+    fn deltas() {
+        let mut engine = engine();
+        set_points(&mut engine, &[(0, 0), (0, 0), (0, 0), (0, 0)]);
+        engine.execute(&[
+            0xB4, 0x37, 2, 0x38, 1, 2, // PUSHB[4] 0x37 2 0x38 1 2
+            0x5D, // DELTAP1: point 1 is moved by 1/8 and point 2 by -1/8 at ppem 12
+            0xB4, 0x3F, 0, 0x2F, 0, 2, // PUSHB[4] 0x3F 0 0x2F 0 2
+            0x73, // DELTAC1: cvt 0 is ignored at ppem 11 and increased by 1 at ppem 12
+        ]).unwrap();
+
+        let zone = &engine.zones[GLYPH_ZONE];
+        assert_eq!(zone.current[1], Point::new(8, 0));
+        assert_eq!(zone.current[2], Point::new(-8, 0));
+        assert_eq!(engine.cvt[0], 280 + 64);
+        assert!(engine.stack.is_empty());
+    }
+
+    #[test]
+    fn super_round() {
+        let mut engine = engine();
+        engine.set_super_round(0x68, false); // period 64, phase 32, threshold 32
+        assert_eq!(engine.round(70), 96);
+        assert_eq!(engine.round(-70), -96);
+        engine.graphics_state.round_state = RoundState::ToHalfGrid;
+        assert_eq!(engine.round(70), 96);
+        engine.graphics_state.round_state = RoundState::DownToGrid;
+        assert_eq!(engine.round(-70), -64);
+    }
+
+    #[test]
+    fn overflow() {
+        let mut engine = engine();
+        engine.scale = 8.0;
+        set_points(&mut engine, &[(0, 0), (100, 0)]);
+        // Must not panic.
+        let _ = engine.execute(&[
+            0xB0, 0, // PUSHB[0] 0
+            0xB8, 0x7F, 0xFF, // PUSHW[0] 32767
+            0x20, // DUP
+            0x63, // MUL
+            0xB8, 0x20, 0x00, // PUSHW[0] 8192
+            0x63, // MUL: 2147352576
+            0x44, // WCVTP
+            0xB8, 0x80, 0x00, // PUSHW[0] -32768
+            0x1F, // SSW: -262144
+            0xB1, 1, 0, // PUSHB[1] 1 0
+            0xFC, // MIRP[rp0, min, round]
+            0xB1, 1, 0, // PUSHB[1] 1 0
+            0x3F, // MIAP[round]
+        ]);
+
+        for &state in &[
+            RoundState::ToHalfGrid, RoundState::ToGrid, RoundState::ToDoubleGrid,
+            RoundState::DownToGrid, RoundState::UpToGrid,
+        ] {
+            engine.graphics_state.round_state = state;
+            let _ = engine.round(i32::MAX);
+            let _ = engine.round(i32::MIN);
+        }
+
+        for &is_45 in &[false, true] {
+            engine.set_super_round(0x68, is_45);
+            let _ = engine.round(i32::MAX);
+            let _ = engine.round(i32::MIN);
+        }
+
+        engine.graphics_state.delta_base = i32::MAX;
+        assert_eq!(engine.delta(0xFF, 32), None);
+    }
+}
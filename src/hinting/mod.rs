@@ -0,0 +1,414 @@
+/*!
+A TrueType hinting instructions interpreter.
+
+Hinting is performed by running the Font Program (`fpgm`) once,
+the Control Value Program (`prep`) once per size and glyph programs for each glyph.
+The implementation follows the classic (v35) FreeType interpreter.
+*/
+
+mod engine;
+
+use core::num::NonZeroU16;
+use std::vec::Vec;
+
+use crate::parser::{Stream, F2DOT14};
+use crate::tables::glyf;
+use crate::{Face, GlyphId, OutlineBuilder};
+use engine::{Engine, GraphicsState, Point, Zone, GLYPH_ZONE, ON_CURVE};
+
+const PHANTOM_POINTS_LEN: usize = 4;
+
+/// A face prepared for hinting at a specific size.
+///
+/// Runs the Font Program and the Control Value Program on creation.
+/// The resulting state is shared by all glyph programs,
+/// which makes outlining independent of the glyphs order.
+///
+/// Variation coordinates of the face are applied to outlines and metrics,
+/// but not to control values, since `cvar` is not supported.
+///
+/// # Example
+///
+/// ```
+/// use ttf_parser::HintingInstance;
+///
+/// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+/// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+/// let instance = HintingInstance::new(&face, 16.0).unwrap();
+///
+/// let mut builder = ttf_parser::ContoursBuilder::new();
+/// let advance = instance.outline_glyph(ttf_parser::GlyphId(1), &mut builder).unwrap();
+/// assert_eq!(advance, 9.0);
+/// assert_eq!(builder.into_contours().len(), 2);
+/// ```
+#[derive(Clone)]
+pub struct HintingInstance<'a> {
+    face: Face<'a>,
+    // An interpreter state after the Control Value Program.
+    engine: Engine<'a>,
+    ppem: f32,
+}
+
+impl<'a> HintingInstance<'a> {
+    /// Creates a new hinting instance for the specified pixels per em.
+    ///
+    /// Returns `None` when the face has no TrueType outlines,
+    /// `ppem` is not positive or `fpgm`/`prep` execution failed.
+    pub fn new(face: &Face<'a>, ppem: f32) -> Option<Self> {
+        face.glyf?;
+        if ppem.is_nan() || ppem <= 0.0 {
+            return None;
+        }
+
+        let units_per_em = face.units_per_em()?;
+        let maxp = face.maximum_profile()?;
+        let mut engine = Engine::new(
+            maxp.max_stack_elements,
+            maxp.max_function_defs,
+            maxp.max_storage,
+            maxp.max_twilight_points,
+        );
+        engine.scale = ppem * 64.0 / f32::from(units_per_em);
+        engine.ppem = ppem.round() as i32;
+
+        #[cfg(feature = "variable-fonts")]
+        {
+            if face.is_variable() {
+                engine.coordinates = face.coords().iter().map(|c| i32::from(c.get())).collect();
+            }
+        }
+
+        if let Some(cvt) = face.cvt {
+            let values: Vec<i32> = cvt.values().map(|n| engine.scale(i32::from(n))).collect();
+            engine.cvt = values;
+        }
+
+        if let Some(fpgm) = face.fpgm {
+            engine.execute(fpgm)?;
+        }
+
+        engine.graphics_state = GraphicsState::default();
+        if let Some(prep) = face.prep {
+            engine.is_prep = true;
+            engine.execute(prep)?;
+            engine.is_prep = false;
+        }
+
+        Some(HintingInstance {
+            face: face.clone(),
+            engine,
+            ppem,
+        })
+    }
+
+    /// Returns pixels per em.
+    #[inline]
+    pub fn ppem(&self) -> f32 {
+        self.ppem
+    }
+
+    /// Outlines a hinted glyph.
+    ///
+    /// Coordinates are in pixels, with the origin at the glyph's horizontal origin.
+    ///
+    /// Returns the hinted horizontal advance in pixels.
+    /// Unlike `Face::outline_glyph`, returns it for empty glyphs too.
+    ///
+    /// When a glyph program fails, the glyph is left unhinted.
+    pub fn outline_glyph(&self, glyph_id: GlyphId, builder: &mut dyn OutlineBuilder) -> Option<f32> {
+        let zone = self.load_glyph(glyph_id, 0)?;
+        let len = zone.current.len() - PHANTOM_POINTS_LEN;
+        let origin = zone.current[len].x;
+        let advance = zone.current[len + 1].x - origin;
+
+        let mut b = glyf::Builder::new(glyf::Transform::default(), None, builder);
+        let mut start = 0;
+        for &end in &zone.end_points {
+            for i in start..=end {
+                let p = zone.current[i];
+                b.push_point(
+                    (p.x - origin) as f32 / 64.0,
+                    p.y as f32 / 64.0,
+                    zone.flags[i] & ON_CURVE != 0,
+                    i == end,
+                );
+            }
+
+            start = end + 1;
+        }
+
+        Some(advance as f32 / 64.0)
+    }
+
+    /// Loads a glyph and returns its hinted points followed by phantom points.
+    fn load_glyph(&self, glyph_id: GlyphId, depth: u8) -> Option<Zone> {
+        if depth >= glyf::MAX_COMPONENTS {
+            return None;
+        }
+
+        let range = self.face.loca?.glyph_range(glyph_id);
+        let data = range.and_then(|range| self.face.glyf?.get(range)).unwrap_or(&[]);
+        if data.is_empty() {
+            let mut zone = Zone::default();
+            self.push_phantom_points(glyph_id, 0, 0, &mut zone);
+            return Some(zone);
+        }
+
+        let mut s = Stream::new(data);
+        let number_of_contours: i16 = s.read()?;
+        let x_min: i16 = s.read()?;
+        s.skip::<i16>(); // y_min
+        s.skip::<i16>(); // x_max
+        let y_max: i16 = s.read()?;
+        let deltas = self.glyph_deltas(glyph_id);
+
+        if number_of_contours >= 0 {
+            let mut zone = Zone::default();
+            let mut instructions: &[u8] = &[];
+            if let Some(number_of_contours) = NonZeroU16::new(number_of_contours as u16) {
+                let glyph_data = s.tail()?;
+                let mut s = Stream::new(glyph_data);
+                s.advance(usize::from(number_of_contours.get()) * 2);
+                let instructions_len: u16 = s.read()?;
+                instructions = s.read_bytes(usize::from(instructions_len))?;
+
+                for (i, point) in glyf::parse_simple_outline(glyph_data, number_of_contours)?.enumerate() {
+                    let (dx, dy) = deltas.get(i).cloned().unwrap_or((0.0, 0.0));
+                    let p = self.scale_point(f32::from(point.x) + dx, f32::from(point.y) + dy);
+                    zone.original.push(p);
+                    zone.current.push(p);
+                    zone.flags.push(if point.on_curve_point { ON_CURVE } else { 0 });
+                    if point.last_point {
+                        zone.end_points.push(i);
+                    }
+                }
+            }
+
+            self.push_phantom_points(glyph_id, x_min, y_max, &mut zone);
+            Some(self.hint(zone, instructions))
+        } else {
+            self.load_composite_glyph(glyph_id, s.tail()?, x_min, y_max, &deltas, depth)
+        }
+    }
+
+    fn load_composite_glyph(
+        &self,
+        glyph_id: GlyphId,
+        data: &'a [u8],
+        x_min: i16,
+        y_max: i16,
+        deltas: &[(f32, f32)],
+        depth: u8,
+    ) -> Option<Zone> {
+        const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+        const ARGS_ARE_XY_VALUES: u16 = 0x0002;
+        const ROUND_XY_TO_GRID: u16 = 0x0004;
+        const WE_HAVE_A_SCALE: u16 = 0x0008;
+        const MORE_COMPONENTS: u16 = 0x0020;
+        const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+        const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+        const WE_HAVE_INSTRUCTIONS: u16 = 0x0100;
+        const USE_MY_METRICS: u16 = 0x0200;
+
+        let mut zone = Zone::default();
+        let mut metrics = None;
+        let mut s = Stream::new(data);
+        let mut index = 0;
+        let mut flags = MORE_COMPONENTS;
+        while flags & MORE_COMPONENTS != 0 {
+            flags = s.read()?;
+            let component_id: GlyphId = s.read()?;
+            let (arg1, arg2) = match (flags & ARG_1_AND_2_ARE_WORDS != 0, flags & ARGS_ARE_XY_VALUES != 0) {
+                (true, true) => (i32::from(s.read::<i16>()?), i32::from(s.read::<i16>()?)),
+                (true, false) => (i32::from(s.read::<u16>()?), i32::from(s.read::<u16>()?)),
+                (false, true) => (i32::from(s.read::<i8>()?), i32::from(s.read::<i8>()?)),
+                (false, false) => (i32::from(s.read::<u8>()?), i32::from(s.read::<u8>()?)),
+            };
+
+            let mut ts = glyf::Transform::default();
+            if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+                ts.a = s.read::<F2DOT14>()?.to_f32();
+                ts.b = s.read::<F2DOT14>()?.to_f32();
+                ts.c = s.read::<F2DOT14>()?.to_f32();
+                ts.d = s.read::<F2DOT14>()?.to_f32();
+            } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+                ts.a = s.read::<F2DOT14>()?.to_f32();
+                ts.d = s.read::<F2DOT14>()?.to_f32();
+            } else if flags & WE_HAVE_A_SCALE != 0 {
+                ts.a = s.read::<F2DOT14>()?.to_f32();
+                ts.d = ts.a;
+            }
+
+            let mut component = self.load_glyph(component_id, depth + 1)?;
+            let len = component.current.len() - PHANTOM_POINTS_LEN;
+            if flags & (WE_HAVE_A_TWO_BY_TWO | WE_HAVE_AN_X_AND_Y_SCALE | WE_HAVE_A_SCALE) != 0 {
+                for p in &mut component.current[..len] {
+                    let (x, y) = (p.x as f32, p.y as f32);
+                    *p = Point::new(
+                        (ts.a * x + ts.c * y).round() as i32,
+                        (ts.b * x + ts.d * y).round() as i32,
+                    );
+                }
+            }
+
+            let offset = if flags & ARGS_ARE_XY_VALUES != 0 {
+                let (dx, dy) = deltas.get(index).cloned().unwrap_or((0.0, 0.0));
+                let mut p = self.scale_point(arg1 as f32 + dx, arg2 as f32 + dy);
+                if flags & ROUND_XY_TO_GRID != 0 {
+                    p = Point::new(round_to_grid(p.x), round_to_grid(p.y));
+                }
+
+                p
+            } else {
+                // Point matching.
+                let parent = *zone.current.get(arg1 as usize)?;
+                let child = *component.current[..len].get(arg2 as usize)?;
+                Point::new(parent.x - child.x, parent.y - child.y)
+            };
+
+            if flags & USE_MY_METRICS != 0 {
+                metrics = Some(component.clone());
+            }
+
+            let base = zone.current.len();
+            for i in 0..len {
+                let p = component.current[i];
+                let p = Point::new(p.x + offset.x, p.y + offset.y);
+                // Hinted component points are the original ones for composite instructions.
+                zone.original.push(p);
+                zone.current.push(p);
+                zone.flags.push(component.flags[i] & ON_CURVE);
+            }
+
+            zone.end_points.extend(component.end_points.iter().map(|end| end + base));
+            index += 1;
+        }
+
+        self.push_phantom_points(glyph_id, x_min, y_max, &mut zone);
+        if let Some(component) = metrics {
+            let len = component.current.len() - PHANTOM_POINTS_LEN;
+            let zone_len = zone.current.len() - PHANTOM_POINTS_LEN;
+            zone.original[zone_len..].copy_from_slice(&component.original[len..]);
+            zone.current[zone_len..].copy_from_slice(&component.current[len..]);
+        }
+
+        let mut instructions: &[u8] = &[];
+        if flags & WE_HAVE_INSTRUCTIONS != 0 {
+            let instructions_len: u16 = s.read()?;
+            instructions = s.read_bytes(usize::from(instructions_len))?;
+        }
+
+        Some(self.hint(zone, instructions))
+    }
+
+    /// Appends scaled phantom points.
+    fn push_phantom_points(&self, glyph_id: GlyphId, x_min: i16, y_max: i16, zone: &mut Zone) {
+        let face = &self.face;
+        let advance = face.hmtx.and_then(|hmtx| hmtx.advance(glyph_id)).unwrap_or(0);
+        let lsb = face.hmtx.and_then(|hmtx| hmtx.side_bearing(glyph_id)).unwrap_or(0);
+        let (top, bottom) = match face.vmtx {
+            Some(vmtx) => {
+                let tsb = vmtx.side_bearing(glyph_id).unwrap_or(0);
+                let top = f32::from(y_max) + f32::from(tsb);
+                (top, top - f32::from(vmtx.advance(glyph_id).unwrap_or(0)))
+            }
+            None => (f32::from(face.ascender()), f32::from(face.descender())),
+        };
+
+        let x = f32::from(x_min) - f32::from(lsb);
+        let mut points = [(x, 0.0), (x + f32::from(advance), 0.0), (0.0, top), (0.0, bottom)];
+
+        if let Some(deltas) = self.phantom_points_deltas(glyph_id) {
+            for (p, d) in points.iter_mut().zip(deltas.iter()) {
+                p.0 += d.0;
+                p.1 += d.1;
+            }
+        }
+
+        for (i, &(x, y)) in points.iter().enumerate() {
+            let p = self.scale_point(x, y);
+            zone.original.push(p);
+            zone.current.push(if i < 2 {
+                Point::new(round_to_grid(p.x), p.y)
+            } else {
+                Point::new(p.x, round_to_grid(p.y))
+            });
+            zone.flags.push(0);
+        }
+    }
+
+    /// Runs a glyph program.
+    fn hint(&self, zone: Zone, instructions: &'a [u8]) -> Zone {
+        if instructions.is_empty() || self.engine.graphics_state.instruct_control & 1 != 0 {
+            return zone;
+        }
+
+        let mut engine = self.engine.clone();
+        if engine.graphics_state.instruct_control & 2 != 0 {
+            let instruct_control = engine.graphics_state.instruct_control;
+            engine.graphics_state = GraphicsState::default();
+            engine.graphics_state.instruct_control = instruct_control;
+        }
+
+        engine.graphics_state.reset_pointers();
+        engine.zones[GLYPH_ZONE] = zone;
+        let is_ok = engine.execute(instructions).is_some();
+        let [_, mut zone] = engine.zones;
+        if !is_ok {
+            // Leave the glyph unhinted.
+            let len = zone.current.len() - PHANTOM_POINTS_LEN;
+            zone.current[..len].copy_from_slice(&zone.original[..len]);
+        }
+
+        zone
+    }
+
+    #[inline]
+    fn scale_point(&self, x: f32, y: f32) -> Point {
+        let scale = self.engine.scale;
+        Point::new((x * scale).round() as i32, (y * scale).round() as i32)
+    }
+
+    #[cfg(feature = "variable-fonts")]
+    fn glyph_deltas(&self, glyph_id: GlyphId) -> Vec<(f32, f32)> {
+        let face = &self.face;
+        if !face.is_variable() {
+            return Vec::new();
+        }
+
+        let deltas = || crate::gvar::glyph_deltas(
+            face.loca?, face.glyf?, face.gvar.as_ref()?, face.coords(), glyph_id,
+        );
+        deltas().unwrap_or_default()
+    }
+
+    #[cfg(not(feature = "variable-fonts"))]
+    fn glyph_deltas(&self, _: GlyphId) -> Vec<(f32, f32)> {
+        Vec::new()
+    }
+
+    #[cfg(feature = "variable-fonts")]
+    fn phantom_points_deltas(&self, glyph_id: GlyphId) -> Option<[(f32, f32); PHANTOM_POINTS_LEN]> {
+        if self.face.is_variable() {
+            self.face.phantom_points_deltas(glyph_id)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(not(feature = "variable-fonts"))]
+    fn phantom_points_deltas(&self, _: GlyphId) -> Option<[(f32, f32); PHANTOM_POINTS_LEN]> {
+        None
+    }
+}
+
+impl core::fmt::Debug for HintingInstance<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "HintingInstance()")
+    }
+}
+
+#[inline]
+fn round_to_grid(n: i32) -> i32 {
+    (n + 32) & !63
+}
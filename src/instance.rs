@@ -0,0 +1,555 @@
+//! Variable fonts instancing.
+//!
+//! Produces a static font for a fixed variation location,
+//! like exporting a named instance as a standalone font.
+//!
+//! Only TrueType (`glyf`-based) fonts are supported. All axes are pinned,
+//! so partial instancing is not possible.
+//!
+//! - `glyf` and `loca` are rebuilt with `gvar` deltas applied
+//! - `hmtx` and `vmtx` are rebuilt with `HVAR`, `VVAR` or phantom points deltas applied
+//! - `MVAR` deltas are applied to `hhea`, `vhea`, `OS/2` and `post`
+//! - `head` bounding box and `hhea` extents are updated
+//! - `OS/2` weight and width classes, `OS/2` selection flags and `head` style bits
+//!   are set from the `wght`, `wdth` and `ital` axis values
+//! - `GSUB` and `GPOS` feature variations are removed, so only default features remain
+//! - `fvar`, `avar`, `gvar`, `cvar`, `HVAR`, `VVAR`, `MVAR` and `STAT` are removed
+//! - `DSIG` is removed, since the signature no longer matches
+//! - other tables are copied as is, so `cvt ` and `GDEF` variations are not applied
+
+use core::convert::TryFrom;
+use core::num::NonZeroU16;
+use std::vec::Vec;
+
+use crate::sfnt::{
+    pad, write_u16, write_u32, HEAD_BBOX_OFFSET, HEAD_INDEX_TO_LOC_FORMAT_OFFSET, HHEA_ADVANCE_MAX_OFFSET,
+    HHEA_NUMBER_OF_METRICS_OFFSET,
+};
+use crate::tables::{glyf, gvar, loca};
+use crate::parser::{Stream, f32_bound};
+use crate::{BBox, Face, FontWriter, GlyphId, IndexToLocationFormat, OutlineBuilder, Rect, Tag, Width};
+
+const HEAD_MAC_STYLE_OFFSET: usize = 44;
+const OS2_WEIGHT_CLASS_OFFSET: usize = 4;
+const OS2_WIDTH_CLASS_OFFSET: usize = 6;
+const OS2_FS_SELECTION_OFFSET: usize = 62;
+
+/// Tables that are removed from a static font.
+const REMOVED_TABLES: &[&[u8; 4]] = &[
+    b"DSIG", b"HVAR", b"MVAR", b"STAT", b"VVAR", b"avar", b"cvar", b"fvar", b"gvar",
+];
+
+/// `MVAR` value tags with the corresponding table and field offset.
+const METRICS: &[(&[u8; 4], &[u8; 4], usize)] = &[
+    (b"hcrs", b"hhea", 18),
+    (b"hcrn", b"hhea", 20),
+    (b"hcof", b"hhea", 22),
+    (b"vasc", b"vhea", 4),
+    (b"vdsc", b"vhea", 6),
+    (b"vlgp", b"vhea", 8),
+    (b"vcrs", b"vhea", 18),
+    (b"vcrn", b"vhea", 20),
+    (b"vcof", b"vhea", 22),
+    (b"sbxs", b"OS/2", 10),
+    (b"sbys", b"OS/2", 12),
+    (b"sbxo", b"OS/2", 14),
+    (b"sbyo", b"OS/2", 16),
+    (b"spxs", b"OS/2", 18),
+    (b"spys", b"OS/2", 20),
+    (b"spxo", b"OS/2", 22),
+    (b"spyo", b"OS/2", 24),
+    (b"strs", b"OS/2", 26),
+    (b"stro", b"OS/2", 28),
+    (b"hasc", b"OS/2", 68),
+    (b"hdsc", b"OS/2", 70),
+    (b"hlgp", b"OS/2", 72),
+    (b"hcla", b"OS/2", 74),
+    (b"hcld", b"OS/2", 76),
+    (b"xhgt", b"OS/2", 86),
+    (b"cpht", b"OS/2", 88),
+    (b"undo", b"post", 8),
+    (b"unds", b"post", 10),
+];
+
+/// Creates a static font for the specified variation location.
+///
+/// Axes that are not listed use their default values. Unknown axes are ignored.
+/// A non-variable face is rewritten as is.
+///
+/// Returns `None` when the face is not a TrueType one or on error.
+///
+/// # Example
+///
+/// ```
+/// use ttf_parser::{GlyphId, Tag};
+/// use ttf_parser::instance::instantiate;
+///
+/// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+/// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+/// let data = instantiate(&face, &[(Tag::from_bytes(b"wght"), 700.0)]).unwrap();
+/// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+/// assert!(!face.is_variable());
+/// assert!(face.glyph_bounding_box(GlyphId(1)).is_some());
+/// ```
+pub fn instantiate(face: &Face, variations: &[(Tag, f32)]) -> Option<Vec<u8>> {
+    let loca_table = face.loca?;
+    let glyf_table = face.glyf?;
+    let style = InstanceStyle::new(face, variations);
+    let face = face.pinned(variations);
+    let number_of_glyphs = face.number_of_glyphs();
+
+    // Bounding boxes of composite glyphs depend on components,
+    // so they are calculated after all glyphs are written.
+    let mut glyf_data = Vec::new();
+    let mut loca_data = Vec::new();
+    for id in 0..number_of_glyphs {
+        let glyph_id = GlyphId(id);
+        loca_data.extend_from_slice(&u32::try_from(glyf_data.len()).ok()?.to_be_bytes());
+
+        let range = loca_table.glyph_range(glyph_id);
+        if let Some(data) = range.and_then(|range| glyf_table.get(range)) {
+            if data.is_empty() {
+                continue;
+            }
+
+            let deltas = face.gvar
+                .and_then(|gvar| gvar::glyph_deltas(loca_table, glyf_table, &gvar,
+                                                    face.coords(), glyph_id))
+                .unwrap_or_default();
+            glyf::apply_deltas(data, &deltas, &mut glyf_data)?;
+            pad(&mut glyf_data);
+        }
+    }
+
+    loca_data.extend_from_slice(&u32::try_from(glyf_data.len()).ok()?.to_be_bytes());
+
+    let bboxes = update_bboxes(&mut glyf_data, &loca_data, number_of_glyphs)?;
+
+    let mut writer = FontWriter::from_face(&face);
+    for tag in REMOVED_TABLES {
+        writer.remove_table(Tag::from_bytes(tag));
+    }
+
+    writer.set_table(Tag::from_bytes(b"glyf"), glyf_data);
+    writer.set_table(Tag::from_bytes(b"loca"), loca_data);
+
+    let mut head = face.head.to_vec();
+    let mut face_bbox = BBox::new();
+    for bbox in bboxes.iter().flatten() {
+        face_bbox.extend_by(f32::from(bbox.x_min), f32::from(bbox.y_min));
+        face_bbox.extend_by(f32::from(bbox.x_max), f32::from(bbox.y_max));
+    }
+    if let Some(bbox) = face_bbox.to_rect() {
+        write_rect(&mut head, HEAD_BBOX_OFFSET, bbox)?;
+    }
+    // Long offsets are always used.
+    write_u16(&mut head, HEAD_INDEX_TO_LOC_FORMAT_OFFSET, 1)?;
+    style.apply_to_head(&mut head);
+    writer.set_table(Tag::from_bytes(b"head"), head);
+
+    if let Some(mut os_2) = writer.table(Tag::from_bytes(b"OS/2")).map(|data| data.to_vec()) {
+        style.apply_to_os_2(&mut os_2);
+        writer.set_table(Tag::from_bytes(b"OS/2"), os_2);
+    }
+
+    for table_tag in &[b"GSUB", b"GPOS"] {
+        let table_tag = Tag::from_bytes(table_tag);
+        if let Some(mut data) = writer.table(table_tag).map(|data| data.to_vec()) {
+            remove_feature_variations(&mut data);
+            writer.set_table(table_tag, data);
+        }
+    }
+
+    let mut hhea = face.hhea.to_vec();
+    writer.set_table(Tag::from_bytes(b"hmtx"), write_hmtx(&face, &bboxes, &mut hhea)?);
+    writer.set_table(Tag::from_bytes(b"hhea"), hhea);
+
+    if let (Some(vhea), Some(_)) = (face.vhea, face.vmtx) {
+        let mut vhea = vhea.to_vec();
+        writer.set_table(Tag::from_bytes(b"vmtx"), write_vmtx(&face, &mut vhea)?);
+        writer.set_table(Tag::from_bytes(b"vhea"), vhea);
+    }
+
+    for table_tag in &[b"hhea", b"vhea", b"OS/2", b"post"] {
+        let table_tag = Tag::from_bytes(table_tag);
+        if let Some(mut data) = writer.table(table_tag).map(|data| data.to_vec()) {
+            apply_metrics_variations(&face, table_tag, &mut data);
+            writer.set_table(table_tag, data);
+        }
+    }
+
+    Some(writer.finish())
+}
+
+/// Calculates glyph bounding boxes and writes them into glyph headers.
+fn update_bboxes(
+    glyf_data: &mut [u8],
+    loca_data: &[u8],
+    number_of_glyphs: u16,
+) -> Option<Vec<Option<Rect>>> {
+    let loca_table = loca::Table::parse(loca_data, NonZeroU16::new(number_of_glyphs)?,
+                                        IndexToLocationFormat::Long)?;
+
+    let mut bboxes = Vec::with_capacity(usize::from(number_of_glyphs));
+    for id in 0..number_of_glyphs {
+        let glyph_id = GlyphId(id);
+        let mut builder = BBoxBuilder(BBox::new());
+        let bbox = glyf::outline(loca_table, glyf_data, glyph_id, &mut builder)
+            .and_then(|_| builder.0.to_rect());
+        bboxes.push(bbox);
+    }
+
+    for (id, bbox) in bboxes.iter().enumerate() {
+        if let Some(bbox) = bbox {
+            let range = loca_table.glyph_range(GlyphId(id as u16))?;
+            // Skip number of contours.
+            write_rect(glyf_data, range.start + 2, *bbox)?;
+        }
+    }
+
+    Some(bboxes)
+}
+
+fn write_hmtx(face: &Face, bboxes: &[Option<Rect>], hhea: &mut [u8]) -> Option<Vec<u8>> {
+    let mut advance_max = 0;
+    let mut min_lsb = i16::MAX;
+    let mut min_rsb = i16::MAX;
+    let mut x_max_extent = i16::MIN;
+
+    let mut data = Vec::with_capacity(bboxes.len() * 4);
+    for (id, bbox) in bboxes.iter().enumerate() {
+        let glyph_id = GlyphId(id as u16);
+        let advance = face.glyph_hor_advance(glyph_id).unwrap_or(0);
+        // The left side bearing of a TrueType glyph is its `x_min`.
+        let lsb = match bbox {
+            Some(bbox) => bbox.x_min,
+            None => face.glyph_hor_side_bearing(glyph_id).unwrap_or(0),
+        };
+
+        advance_max = advance_max.max(advance);
+        if let Some(bbox) = bbox {
+            let width = i32::from(bbox.x_max) - i32::from(bbox.x_min);
+            let rsb = i32::from(advance) - i32::from(lsb) - width;
+            min_lsb = min_lsb.min(lsb);
+            min_rsb = min_rsb.min(i16::try_from(rsb).ok()?);
+            x_max_extent = x_max_extent.max(i16::try_from(i32::from(lsb) + width).ok()?);
+        }
+
+        data.extend_from_slice(&advance.to_be_bytes());
+        data.extend_from_slice(&lsb.to_be_bytes());
+    }
+
+    // Every glyph gets a full metrics record.
+    write_u16(hhea, HHEA_ADVANCE_MAX_OFFSET, advance_max)?;
+    if min_lsb != i16::MAX {
+        write_u16(hhea, HHEA_ADVANCE_MAX_OFFSET + 2, min_lsb as u16)?;
+        write_u16(hhea, HHEA_ADVANCE_MAX_OFFSET + 4, min_rsb as u16)?;
+        write_u16(hhea, HHEA_ADVANCE_MAX_OFFSET + 6, x_max_extent as u16)?;
+    }
+    write_u16(hhea, HHEA_NUMBER_OF_METRICS_OFFSET, u16::try_from(bboxes.len()).ok()?)?;
+
+    Some(data)
+}
+
+fn write_vmtx(face: &Face, vhea: &mut [u8]) -> Option<Vec<u8>> {
+    let number_of_glyphs = face.number_of_glyphs();
+    let mut advance_max = 0;
+    let mut data = Vec::with_capacity(usize::from(number_of_glyphs) * 4);
+    for id in 0..number_of_glyphs {
+        let glyph_id = GlyphId(id);
+        let advance = face.glyph_ver_advance(glyph_id).unwrap_or(0);
+        let tsb = face.glyph_ver_side_bearing(glyph_id).unwrap_or(0);
+        advance_max = advance_max.max(advance);
+        data.extend_from_slice(&advance.to_be_bytes());
+        data.extend_from_slice(&tsb.to_be_bytes());
+    }
+
+    // `vhea` has the same layout as `hhea`.
+    write_u16(vhea, HHEA_ADVANCE_MAX_OFFSET, advance_max)?;
+    write_u16(vhea, HHEA_NUMBER_OF_METRICS_OFFSET, number_of_glyphs)?;
+
+    Some(data)
+}
+
+fn apply_metrics_variations(face: &Face, table_tag: Tag, data: &mut [u8]) {
+    for (tag, table, offset) in METRICS {
+        if Tag::from_bytes(table) != table_tag {
+            continue;
+        }
+
+        let delta = match face.metrics_variation(Tag::from_bytes(tag)) {
+            Some(delta) => delta.round() as i32,
+            None => continue,
+        };
+
+        // Windows metrics are unsigned.
+        let is_unsigned = *tag == b"hcla" || *tag == b"hcld";
+        let value = if is_unsigned {
+            Stream::read_at::<u16>(data, *offset).map(i32::from)
+        } else {
+            Stream::read_at::<i16>(data, *offset).map(i32::from)
+        };
+
+        // Fields missing in older table versions are ignored.
+        if let Some(value) = value {
+            let value = value + delta;
+            let n = if is_unsigned {
+                u16::try_from(value).ok()
+            } else {
+                i16::try_from(value).ok().map(|n| n as u16)
+            };
+
+            if let Some(n) = n {
+                let _ = write_u16(data, *offset, n);
+            }
+        }
+    }
+}
+
+/// Style values of an instance.
+///
+/// A missing value means that the corresponding axis is not present
+/// and the original value should be preserved.
+struct InstanceStyle {
+    weight_class: Option<u16>,
+    width: Option<Width>,
+    is_italic: Option<bool>,
+}
+
+impl InstanceStyle {
+    fn new(face: &Face, variations: &[(Tag, f32)]) -> Self {
+        let axis_value = |tag: &[u8; 4]| {
+            let tag = Tag::from_bytes(tag);
+            let axis = face.variation_axis(tag)?;
+            // The last value wins, just like in `Face::pinned`.
+            let value = variations.iter().rev()
+                .find(|v| v.0 == tag)
+                .map(|v| v.1)
+                .unwrap_or(axis.def_value);
+            Some(f32_bound(axis.min_value, value, axis.max_value))
+        };
+
+        InstanceStyle {
+            weight_class: axis_value(b"wght").map(|v| (f32_bound(1.0, v, 1000.0) + 0.5) as u16),
+            width: axis_value(b"wdth").filter(|v| *v > 0.0).map(Width::from_percent),
+            is_italic: axis_value(b"ital").map(|v| v >= 0.5),
+        }
+    }
+
+    fn is_bold(&self, fallback: bool) -> bool {
+        self.weight_class.map(|n| n >= 700).unwrap_or(fallback)
+    }
+
+    fn apply_to_os_2(&self, data: &mut [u8]) {
+        if let Some(n) = self.weight_class {
+            let _ = write_u16(data, OS2_WEIGHT_CLASS_OFFSET, n);
+        }
+
+        if let Some(width) = self.width {
+            let _ = write_u16(data, OS2_WIDTH_CLASS_OFFSET, width.to_number());
+        }
+
+        const ITALIC: u16 = 1 << 0;
+        const BOLD: u16 = 1 << 5;
+        const REGULAR: u16 = 1 << 6;
+        if let Some(mut flags) = Stream::read_at::<u16>(data, OS2_FS_SELECTION_OFFSET) {
+            let is_bold = self.is_bold(flags & BOLD != 0);
+            let is_italic = self.is_italic.unwrap_or(flags & ITALIC != 0);
+            flags &= !(ITALIC | BOLD | REGULAR);
+            if is_bold {
+                flags |= BOLD;
+            }
+            if is_italic {
+                flags |= ITALIC;
+            }
+            if !is_bold && !is_italic {
+                flags |= REGULAR;
+            }
+            let _ = write_u16(data, OS2_FS_SELECTION_OFFSET, flags);
+        }
+    }
+
+    fn apply_to_head(&self, data: &mut [u8]) {
+        const BOLD: u16 = 1 << 0;
+        const ITALIC: u16 = 1 << 1;
+        const CONDENSED: u16 = 1 << 5;
+        const EXTENDED: u16 = 1 << 6;
+        if let Some(mut bits) = Stream::read_at::<u16>(data, HEAD_MAC_STYLE_OFFSET) {
+            if self.is_bold(bits & BOLD != 0) {
+                bits |= BOLD;
+            } else {
+                bits &= !BOLD;
+            }
+
+            match self.is_italic {
+                Some(true) => bits |= ITALIC,
+                Some(false) => bits &= !ITALIC,
+                None => {}
+            }
+
+            if let Some(width) = self.width {
+                bits &= !(CONDENSED | EXTENDED);
+                let n = width.to_number();
+                if n < Width::Normal.to_number() {
+                    bits |= CONDENSED;
+                } else if n > Width::Normal.to_number() {
+                    bits |= EXTENDED;
+                }
+            }
+
+            let _ = write_u16(data, HEAD_MAC_STYLE_OFFSET, bits);
+        }
+    }
+}
+
+/// Removes the `FeatureVariations` table from a `GSUB` or `GPOS` table version 1.1.
+///
+/// Alternate feature tables are not applied, so the default features are used.
+fn remove_feature_variations(data: &mut [u8]) {
+    if Stream::read_at::<u32>(data, 0) == Some(0x00010001) {
+        let _ = write_u16(data, 2, 0); // minor version
+        let _ = write_u32(data, 10, 0); // feature variations offset
+    }
+}
+
+fn write_rect(data: &mut [u8], offset: usize, rect: Rect) -> Option<()> {
+    write_u16(data, offset, rect.x_min as u16)?;
+    write_u16(data, offset + 2, rect.y_min as u16)?;
+    write_u16(data, offset + 4, rect.x_max as u16)?;
+    write_u16(data, offset + 6, rect.y_max as u16)
+}
+
+/// Collects all outline points, including control ones,
+/// just like TrueType bounding boxes do.
+struct BBoxBuilder(BBox);
+
+impl OutlineBuilder for BBoxBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.extend_by(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.extend_by(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.0.extend_by(x1, y1);
+        self.0.extend_by(x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.0.extend_by(x1, y1);
+        self.0.extend_by(x2, y2);
+        self.0.extend_by(x, y);
+    }
+
+    fn close(&mut self) {}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer::TtfType::*;
+
+    #[test]
+    fn static_face() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let data = instantiate(&face, &[]).unwrap();
+        let instance = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(instance.number_of_glyphs(), face.number_of_glyphs());
+        for id in 0..face.number_of_glyphs() {
+            let glyph_id = GlyphId(id);
+            assert_eq!(instance.glyph_outline_hash(glyph_id), face.glyph_outline_hash(glyph_id));
+            assert_eq!(instance.glyph_bounding_box(glyph_id), face.glyph_bounding_box(glyph_id));
+            assert_eq!(instance.glyph_hor_advance(glyph_id), face.glyph_hor_advance(glyph_id));
+        }
+        assert_eq!(instance.global_bounding_box(), face.global_bounding_box());
+    }
+
+    #[test]
+    fn style_and_removed_tables() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let mut os_2 = crate::writer::convert(&[
+            UInt16(4), // version
+            Int16(500), // average char width
+            UInt16(200), // weight class
+            UInt16(5), // width class
+        ]);
+        os_2.resize(62, 0);
+        os_2.extend_from_slice(&crate::writer::convert(&[
+            UInt16(0x0040), // selection flags: regular
+        ]));
+        os_2.resize(96, 0);
+
+        let mut writer = FontWriter::from_face(&face);
+        writer.set_table(Tag::from_bytes(b"OS/2"), os_2);
+        writer.set_table(Tag::from_bytes(b"fvar"), crate::writer::convert(&[
+            UInt32(0x00010000), // version: 1.0
+            UInt16(16), // axes array offset
+            UInt16(2), // reserved
+            UInt16(1), // axis count
+            UInt16(20), // axis size
+            UInt16(0), // instance count
+            UInt16(4), // instance size
+            // Axis 0
+            Raw(b"wght"), // tag
+            Int32(100 << 16), // min value: 100
+            Int32(200 << 16), // default value: 200
+            Int32(900 << 16), // max value: 900
+            UInt16(0), // flags
+            UInt16(256), // name ID
+        ]));
+        writer.set_table(Tag::from_bytes(b"DSIG"), crate::writer::convert(&[
+            UInt32(1), // version
+            UInt16(0), // number of signatures
+            UInt16(0), // flags
+        ]));
+        writer.set_table(Tag::from_bytes(b"STAT"), crate::writer::convert(&[
+            UInt16(1), // major version
+            UInt16(1), // minor version
+            UInt16(8), // design axis size
+            UInt16(0), // design axis count
+            UInt32(0), // design axes offset
+            UInt16(0), // axis value count
+            UInt32(0), // axis value offsets offset
+            UInt16(2), // elided fallback name ID
+        ]));
+        let font = writer.finish();
+        let face = Face::from_slice(&font, 0).unwrap();
+        assert_eq!(face.weight(), crate::Weight::ExtraLight);
+
+        let data = instantiate(&face, &[(Tag::from_bytes(b"wght"), 900.0)]).unwrap();
+        let instance = Face::from_slice(&data, 0).unwrap();
+        assert!(!instance.is_variable());
+        assert_eq!(instance.weight(), crate::Weight::Black);
+        assert!(instance.is_bold());
+        assert!(!instance.is_regular());
+        assert!(instance.table_data(Tag::from_bytes(b"DSIG")).is_none());
+        assert!(instance.table_data(Tag::from_bytes(b"STAT")).is_none());
+        assert!(instance.table_data(Tag::from_bytes(b"fvar")).is_none());
+
+        // The default location keeps the default weight.
+        let data = instantiate(&face, &[]).unwrap();
+        let instance = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(instance.weight(), crate::Weight::ExtraLight);
+        assert!(instance.is_regular());
+    }
+
+    #[test]
+    fn feature_variations() {
+        let mut data = crate::writer::convert(&[
+            UInt16(1), // major version
+            UInt16(1), // minor version
+            UInt16(14), // script list offset
+            UInt16(16), // feature list offset
+            UInt16(18), // lookup list offset
+            UInt32(20), // feature variations offset
+        ]);
+        remove_feature_variations(&mut data);
+        assert_eq!(Stream::read_at::<u32>(&data, 0), Some(0x00010000));
+        assert_eq!(Stream::read_at::<u32>(&data, 10), Some(0));
+    }
+}
@@ -0,0 +1,234 @@
+// https://docs.microsoft.com/en-us/typography/opentype/spec/languagetags
+
+use crate::Tag;
+
+/// Returns an OpenType language system tag for a [BCP 47](https://www.rfc-editor.org/info/bcp47)
+/// language tag.
+///
+/// Matching is case-insensitive and `_` is treated as `-`, so POSIX-like locales work too.
+/// The longest known prefix is used, so `nl-BE` resolves to `NLD ` and `zh-Hant-TW` to `ZHT `.
+/// A plain `zh` resolves to Simplified Chinese.
+///
+/// Only a subset of the registry, covering widely used languages, is supported.
+///
+/// # Example
+///
+/// ```
+/// use ttf_parser::{opentype_language_tag, Tag};
+///
+/// assert_eq!(opentype_language_tag("nl"), Some(Tag::from_bytes(b"NLD ")));
+/// assert_eq!(opentype_language_tag("zh_TW"), Some(Tag::from_bytes(b"ZHT ")));
+/// assert_eq!(opentype_language_tag("tlh"), None);
+/// ```
+pub fn opentype_language_tag(language: &str) -> Option<Tag> {
+    LANGUAGE_TAGS.iter()
+        .filter(|(_, bcp47)| has_prefix(language, bcp47))
+        .max_by_key(|(_, bcp47)| bcp47.len())
+        .map(|(tag, _)| Tag::from_bytes(tag))
+}
+
+/// Returns a [BCP 47](https://www.rfc-editor.org/info/bcp47) language tag
+/// for an OpenType language system tag.
+///
+/// When multiple BCP 47 tags correspond to the same OpenType tag,
+/// the most specific one is returned, like `zh-Hans` for `ZHS `.
+pub fn bcp47_language_tag(tag: Tag) -> Option<&'static str> {
+    LANGUAGE_TAGS.iter()
+        .find(|(t, _)| Tag::from_bytes(t) == tag)
+        .map(|(_, bcp47)| *bcp47)
+}
+
+// Checks that `language` starts with `prefix` followed by a subtag boundary.
+fn has_prefix(language: &str, prefix: &str) -> bool {
+    let language = language.as_bytes();
+    let prefix = prefix.as_bytes();
+    if language.len() < prefix.len() {
+        return false;
+    }
+
+    let is_same = language.iter().zip(prefix).all(|(a, b)| {
+        let a = if *a == b'_' { b'-' } else { *a };
+        a.eq_ignore_ascii_case(b)
+    });
+
+    is_same && match language.get(prefix.len()) {
+        Some(c) => *c == b'-' || *c == b'_',
+        None => true,
+    }
+}
+
+// (OpenType tag, BCP 47 tag). The preferred BCP 47 tag must come first.
+static LANGUAGE_TAGS: &[(&[u8; 4], &str)] = &[
+    (b"ABK ", "ab"),
+    (b"AFK ", "af"),
+    (b"AMH ", "am"),
+    (b"ARA ", "ar"),
+    (b"ASM ", "as"),
+    (b"AZE ", "az"),
+    (b"BSH ", "ba"),
+    (b"BEL ", "be"),
+    (b"BGR ", "bg"),
+    (b"BHO ", "bho"),
+    (b"BEN ", "bn"),
+    (b"TIB ", "bo"),
+    (b"BRE ", "br"),
+    (b"BOS ", "bs"),
+    (b"CAT ", "ca"),
+    (b"CHE ", "ce"),
+    (b"COP ", "cop"),
+    (b"CSY ", "cs"),
+    (b"CHU ", "cv"),
+    (b"WEL ", "cy"),
+    (b"DAN ", "da"),
+    (b"DEU ", "de"),
+    (b"DIV ", "dv"),
+    (b"DZN ", "dz"),
+    (b"ELL ", "el"),
+    (b"ENG ", "en"),
+    (b"NTO ", "eo"),
+    (b"ESP ", "es"),
+    (b"ETI ", "et"),
+    (b"EUQ ", "eu"),
+    (b"FAR ", "fa"),
+    (b"FIN ", "fi"),
+    (b"FJI ", "fj"),
+    (b"FOS ", "fo"),
+    (b"FRA ", "fr"),
+    (b"FRI ", "fy"),
+    (b"IRI ", "ga"),
+    (b"GAE ", "gd"),
+    (b"GEZ ", "gez"),
+    (b"GAL ", "gl"),
+    (b"GUA ", "gn"),
+    (b"GUJ ", "gu"),
+    (b"HAU ", "ha"),
+    (b"HAW ", "haw"),
+    (b"IWR ", "he"),
+    (b"HIN ", "hi"),
+    (b"HRV ", "hr"),
+    (b"HAI ", "ht"),
+    (b"HUN ", "hu"),
+    (b"HYE ", "hy"),
+    (b"IND ", "id"),
+    (b"IBO ", "ig"),
+    (b"ISL ", "is"),
+    (b"ITA ", "it"),
+    (b"INU ", "iu"),
+    (b"JAN ", "ja"),
+    (b"JAV ", "jv"),
+    (b"KAT ", "ka"),
+    (b"KAZ ", "kk"),
+    (b"GRN ", "kl"),
+    (b"KHM ", "km"),
+    (b"KAN ", "kn"),
+    (b"KOR ", "ko"),
+    (b"KOK ", "kok"),
+    (b"KSH ", "ks"),
+    (b"KUR ", "ku"),
+    (b"KIR ", "ky"),
+    (b"LAT ", "la"),
+    (b"LTZ ", "lb"),
+    (b"LAO ", "lo"),
+    (b"LTH ", "lt"),
+    (b"LVI ", "lv"),
+    (b"MTH ", "mai"),
+    (b"MLG ", "mg"),
+    (b"MRI ", "mi"),
+    (b"MKD ", "mk"),
+    (b"MAL ", "ml"),
+    (b"MNG ", "mn"),
+    (b"MAR ", "mr"),
+    (b"MLY ", "ms"),
+    (b"MTS ", "mt"),
+    (b"BRM ", "my"),
+    (b"NEP ", "ne"),
+    (b"NEW ", "new"),
+    (b"NLD ", "nl"),
+    (b"NYN ", "nn"),
+    (b"NOR ", "nb"),
+    (b"NOR ", "no"),
+    (b"OCI ", "oc"),
+    (b"ORI ", "or"),
+    (b"OSS ", "os"),
+    (b"PAN ", "pa"),
+    (b"PLK ", "pl"),
+    (b"PAS ", "ps"),
+    (b"PTG ", "pt"),
+    (b"QUZ ", "qu"),
+    (b"RMS ", "rm"),
+    (b"ROM ", "ro"),
+    (b"RUS ", "ru"),
+    (b"KIN ", "rw"),
+    (b"SAN ", "sa"),
+    (b"SAT ", "sat"),
+    (b"SND ", "sd"),
+    (b"SNH ", "si"),
+    (b"SKY ", "sk"),
+    (b"SLV ", "sl"),
+    (b"SMO ", "sm"),
+    (b"SML ", "so"),
+    (b"SQI ", "sq"),
+    (b"SRB ", "sr"),
+    (b"SVE ", "sv"),
+    (b"SWK ", "sw"),
+    (b"SYR ", "syr"),
+    (b"TAM ", "ta"),
+    (b"TEL ", "te"),
+    (b"TAJ ", "tg"),
+    (b"THA ", "th"),
+    (b"TGY ", "ti"),
+    (b"TKM ", "tk"),
+    (b"TGL ", "tl"),
+    (b"TNA ", "tn"),
+    (b"TGN ", "to"),
+    (b"TRK ", "tr"),
+    (b"TAT ", "tt"),
+    (b"UYG ", "ug"),
+    (b"UKR ", "uk"),
+    (b"URD ", "ur"),
+    (b"UZB ", "uz"),
+    (b"VIT ", "vi"),
+    (b"WLF ", "wo"),
+    (b"XHS ", "xh"),
+    (b"YID ", "yi"),
+    (b"YBA ", "yo"),
+    (b"ZHS ", "zh-Hans"),
+    (b"ZHT ", "zh-Hant"),
+    (b"ZHH ", "zh-HK"),
+    (b"ZHTM", "zh-MO"),
+    (b"ZHT ", "zh-TW"),
+    (b"ZHS ", "zh-CN"),
+    (b"ZHS ", "zh-SG"),
+    (b"ZHS ", "zh"),
+    (b"ZUL ", "zu"),
+];
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_opentype() {
+        let tag = |s| opentype_language_tag(s).map(|t| t.to_bytes());
+        assert_eq!(tag("nl"), Some(*b"NLD "));
+        assert_eq!(tag("NL-be"), Some(*b"NLD "));
+        assert_eq!(tag("nn-NO"), Some(*b"NYN "));
+        assert_eq!(tag("zh"), Some(*b"ZHS "));
+        assert_eq!(tag("zh-Hans"), Some(*b"ZHS "));
+        assert_eq!(tag("zh-Hant-TW"), Some(*b"ZHT "));
+        assert_eq!(tag("zh-HK"), Some(*b"ZHH "));
+        assert_eq!(tag("zh-MO"), Some(*b"ZHTM"));
+        assert_eq!(tag("zhx"), None);
+        assert_eq!(tag("n"), None);
+        assert_eq!(tag(""), None);
+    }
+
+    #[test]
+    fn to_bcp47() {
+        assert_eq!(bcp47_language_tag(Tag::from_bytes(b"NLD ")), Some("nl"));
+        assert_eq!(bcp47_language_tag(Tag::from_bytes(b"ZHS ")), Some("zh-Hans"));
+        assert_eq!(bcp47_language_tag(Tag::from_bytes(b"NOR ")), Some("nb"));
+        assert_eq!(bcp47_language_tag(Tag::from_bytes(b"dflt")), None);
+    }
+}
@@ -35,6 +35,7 @@ A high-level, safe, zero-allocation TrueType font parser.
 #[macro_use]
 extern crate std;
 
+use core::convert::TryFrom;
 use core::fmt;
 use core::num::NonZeroU16;
 
@@ -47,30 +48,115 @@ macro_rules! try_opt_or {
     };
 }
 
+#[cfg(feature = "std")]
+mod agl;
+#[cfg(feature = "kurbo")]
+mod bez_path;
+mod dfont;
 mod ggg;
+mod language_tags;
+mod outline;
 pub mod parser;
+mod scaled;
+mod script_tags;
+mod sfnt;
 mod tables;
+mod unicode_ranges;
 #[cfg(feature = "variable-fonts")]
 mod var_store;
 
+#[cfg(feature = "std")]
+mod contours;
+#[cfg(feature = "std")]
+mod face_info;
+#[cfg(feature = "std")]
+mod font_writer;
+#[cfg(feature = "std")]
+mod glyph_cache;
+#[cfg(feature = "std")]
+mod glyph_set;
+#[cfg(feature = "hinting")]
+mod hinting;
+#[cfg(all(feature = "std", feature = "variable-fonts"))]
+pub mod instance;
+#[cfg(feature = "std")]
+mod owned;
+#[cfg(feature = "std")]
+mod sanitize;
+#[cfg(feature = "std")]
+mod size_report;
+#[cfg(feature = "std")]
+pub mod subset;
+#[cfg(feature = "std")]
+mod svg_path;
+#[cfg(feature = "ttx")]
+mod ttx;
 #[cfg(feature = "std")]
 mod writer;
 
-use head::IndexToLocationFormat;
-use parser::{FromData, LazyArray16, NumFrom, Offset, Offset32, Stream, TryNumFrom};
+#[cfg(feature = "image-decoding")]
+mod png;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "woff")]
+mod woff;
+#[cfg(any(feature = "image-decoding", feature = "woff"))]
+mod zlib;
+
+use parser::{FromData, LazyArray16, LazyArray32, NumFrom, Offset, Offset32, Stream, TryNumFrom};
 use tables::*;
 
+#[cfg(feature = "kurbo")]
+pub use bez_path::BezPathBuilder;
+pub use cff1::{PrivateDict, Ros};
+pub use dfont::{Dfont, SfntResource};
 #[cfg(feature = "variable-fonts")]
-pub use fvar::{VariationAxes, VariationAxis};
+pub use fvar::{VariationAxes, VariationAxis, VariationInstance, VariationInstances};
+#[cfg(feature = "variable-fonts")]
+pub use gvar::{GlyphVariationTuple, GlyphVariationTuples};
 pub use gdef::GlyphClass;
+pub use head::IndexToLocationFormat;
+pub use language_tags::{bcp47_language_tag, opentype_language_tag};
+pub use maxp::MaximumProfile;
 pub use ggg::*;
 pub use name::*;
 pub use os2::*;
-pub use tables::{cmap, kern};
+pub use outline::{CubicBuilder, FillRule, FlattenBuilder, OutlineHasher, QuadBuilder};
+pub use scaled::{ScaledFace, ScaledRect};
+pub use script_tags::{opentype_script_tags, unicode_script};
+pub use tables::{base, cblc, cmap, colr, cpal, cvt, dsig, ebsc, fdsc, fmtx, jstf, kern, math, merg, meta, pclt, sbix, stat, svg, zapf};
+pub use unicode_ranges::{UnicodeBlock, UnicodeBlocks};
+#[cfg(feature = "std")]
+pub use contours::{Contour, Contours, ContoursBuilder, OutlinePoint, OutlineSegment};
+#[cfg(feature = "std")]
+pub use face_info::FaceInfo;
+#[cfg(feature = "std")]
+pub use font_writer::{table_checksum, FontWriter};
+#[cfg(feature = "std")]
+pub use glyph_cache::GlyphIndexCache;
+#[cfg(feature = "std")]
+pub use glyph_set::{GlyphSet, GlyphSetIter};
+#[cfg(feature = "hinting")]
+pub use hinting::HintingInstance;
+#[cfg(feature = "std")]
+pub use owned::{FaceData, OwnedFace};
+#[cfg(feature = "std")]
+pub use sanitize::{sanitize, SanitizeReport, TableReport};
+#[cfg(feature = "std")]
+pub use size_report::{GlyphSizeSummary, SizeReport, TableSize};
+#[cfg(feature = "std")]
+pub use svg_path::{SvgPathBuilder, SvgPathOptions};
+#[cfg(feature = "ttx")]
+pub use ttx::dump_ttx;
+#[cfg(feature = "wasm")]
+pub use wasm::WasmFace;
+#[cfg(feature = "woff")]
+pub use woff::{decompress_woff, is_woff};
 
 /// A type-safe wrapper for glyph ID.
 #[repr(transparent)]
 #[derive(Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GlyphId(pub u16);
 
 impl FromData for GlyphId {
@@ -89,6 +175,7 @@ impl FromData for GlyphId {
 enum Magic {
     TrueType,
     OpenType,
+    PostScript,
     FontCollection,
 }
 
@@ -98,8 +185,12 @@ impl FromData for Magic {
     #[inline]
     fn parse(data: &[u8]) -> Option<Self> {
         match u32::parse(data)? {
+            // `true` is used by Apple TrueType fonts.
             0x00010000 | 0x74727565 => Some(Magic::TrueType),
             0x4F54544F => Some(Magic::OpenType),
+            // `typ1` is used by Apple sfnt-wrapped PostScript Type 1 fonts.
+            // Their outlines are not supported, but other tables are.
+            0x74797031 => Some(Magic::PostScript),
             0x74746366 => Some(Magic::FontCollection),
             _ => None,
         }
@@ -114,6 +205,8 @@ impl FromData for Magic {
 /// The number is stored as f2.16
 #[repr(transparent)]
 #[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "i16"))]
 pub struct NormalizedCoordinate(i16);
 
 impl From<i16> for NormalizedCoordinate {
@@ -129,10 +222,13 @@ impl From<i16> for NormalizedCoordinate {
 impl From<f32> for NormalizedCoordinate {
     /// Creates a new coordinate.
     ///
-    /// The provided number will be clamped to the -1.0..1.0 range.
+    /// The provided number will be clamped to the -1.0..1.0 range
+    /// and rounded to the nearest f2.14 value.
     #[inline]
     fn from(n: f32) -> Self {
-        NormalizedCoordinate((parser::f32_bound(-1.0, n, 1.0) * 16384.0) as i16)
+        let n = parser::f32_bound(-1.0, n, 1.0) * 16384.0;
+        let n = if n >= 0.0 { n + 0.5 } else { n - 0.5 };
+        NormalizedCoordinate(n as i16)
     }
 }
 
@@ -154,6 +250,7 @@ impl NormalizedCoordinate {
 /// Variation { axis: Tag::from_bytes(b"wght"), value: 500.0 };
 /// ```
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Variation {
     /// An axis tag name.
     pub axis: Tag,
@@ -161,9 +258,68 @@ pub struct Variation {
     pub value: f32,
 }
 
+/// A rounding mode for variation deltas.
+///
+/// Different rasterizers round deltas differently, so this can be used
+/// to match a specific one. For example, FreeType rounds deltas to the nearest integer.
+#[cfg(feature = "variable-fonts")]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum VariationRounding {
+    /// The rounding used by previous versions.
+    ///
+    /// Outline deltas are not rounded. Methods that return integers add 0.5
+    /// to the varied value and truncate it toward zero, so positive halves are rounded up,
+    /// but negative values are rounded toward zero, e.g. -1.5 becomes -1 and -1.7 becomes -1.
+    /// `MVAR`-based metrics are truncated without adding 0.5.
+    Legacy,
+    /// Deltas are rounded to the nearest integer, with halves rounded away from zero.
+    Nearest,
+    /// Deltas are truncated toward zero.
+    Truncate,
+}
+
+#[cfg(feature = "variable-fonts")]
+impl Default for VariationRounding {
+    #[inline]
+    fn default() -> Self {
+        VariationRounding::Legacy
+    }
+}
+
+#[cfg(feature = "variable-fonts")]
+impl VariationRounding {
+    /// Rounds a delta that will be added to a float value.
+    #[inline]
+    pub(crate) fn round(self, delta: f32) -> f32 {
+        match self {
+            VariationRounding::Legacy => delta,
+            VariationRounding::Nearest => Self::round_nearest(delta),
+            VariationRounding::Truncate => delta as i32 as f32,
+        }
+    }
+
+    /// Rounds a delta that will be added to an integer value.
+    #[inline]
+    pub(crate) fn round_to_integer(self, delta: f32) -> f32 {
+        match self {
+            // The sum is truncated by the caller afterwards.
+            VariationRounding::Legacy => delta + 0.5,
+            VariationRounding::Nearest => Self::round_nearest(delta),
+            VariationRounding::Truncate => delta as i32 as f32,
+        }
+    }
+
+    #[inline]
+    fn round_nearest(delta: f32) -> f32 {
+        // We can't use `round()` in `no_std`, so this is the next best thing.
+        (if delta >= 0.0 { delta + 0.5 } else { delta - 0.5 }) as i32 as f32
+    }
+}
+
 /// A 4-byte tag.
 #[repr(transparent)]
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tag(pub u32);
 
 impl Tag {
@@ -293,6 +449,7 @@ impl FromData for Tag {
 /// Used for underline and strikeout.
 #[repr(C)]
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineMetrics {
     /// Line position.
     pub position: i16,
@@ -301,9 +458,119 @@ pub struct LineMetrics {
     pub thickness: i16,
 }
 
+/// Line spacing metrics.
+///
+/// All values are in font units.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineSpacing {
+    /// Face's ascender.
+    pub ascender: i16,
+
+    /// Face's descender. Usually negative.
+    pub descender: i16,
+
+    /// Face's line gap.
+    pub line_gap: i16,
+}
+
+impl LineSpacing {
+    /// Returns a distance between two baselines.
+    #[inline]
+    pub fn line_height(&self) -> i32 {
+        i32::from(self.ascender) - i32::from(self.descender) + i32::from(self.line_gap)
+    }
+
+    /// Returns line spacing with Windows clipping metrics and the same line height.
+    ///
+    /// The rest of the line height goes to the line gap.
+    fn to_windows(self, ascender: u16, descender: u16) -> Option<LineSpacing> {
+        let (ascender, descender) = (i32::from(ascender), i32::from(descender));
+        let line_gap = core::cmp::max(0, self.line_height() - (ascender + descender));
+        Some(LineSpacing {
+            ascender: i16::try_from(ascender).ok()?,
+            descender: i16::try_from(-descender).ok()?,
+            line_gap: i16::try_from(line_gap).unwrap_or(i16::MAX),
+        })
+    }
+}
+
+/// A source of line spacing metrics.
+///
+/// Platforms use different tables, so line heights can differ
+/// for the same font.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LineMetricsPolicy {
+    /// Uses `OS/2` typographic metrics when the `USE_TYPO_METRICS` flag is set
+    /// and `hhea` metrics otherwise.
+    ///
+    /// The same as `Face::ascender`, `Face::descender` and `Face::line_gap`.
+    Auto,
+
+    /// Uses `hhea` metrics, like macOS.
+    Hhea,
+
+    /// Uses `OS/2` typographic metrics regardless of the `USE_TYPO_METRICS` flag.
+    Typographic,
+
+    /// Uses `OS/2.usWinAscent` and `OS/2.usWinDescent`, like Windows GDI.
+    ///
+    /// The line gap is the part of the `hhea` line height that doesn't fit
+    /// into the Windows ascender and descender.
+    Windows,
+}
+
+impl Default for LineMetricsPolicy {
+    #[inline]
+    fn default() -> Self {
+        LineMetricsPolicy::Auto
+    }
+}
+
+/// Face metrics scaled to a specific size.
+///
+/// All values are in pixels.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScaledMetrics {
+    /// Pixels per em.
+    pub pixels_per_em: u16,
+
+    /// Face's ascender.
+    ///
+    /// Uses the `VDMX` table value when present.
+    pub ascender: f32,
+
+    /// Face's descender.
+    ///
+    /// Uses the `VDMX` table value when present.
+    pub descender: f32,
+
+    /// Face's line gap.
+    pub line_gap: f32,
+
+    /// Underline position.
+    pub underline_position: Option<f32>,
+
+    /// Underline thickness.
+    pub underline_thickness: Option<f32>,
+
+    /// Face's x height.
+    pub x_height: Option<f32>,
+
+    /// Face's capital height.
+    pub capital_height: Option<f32>,
+
+    /// Maximum advance width.
+    ///
+    /// Uses the `hdmx` table value when present.
+    pub max_advance: f32,
+}
+
 /// A rectangle.
 #[repr(C)]
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub struct Rect {
     pub x_min: i16,
@@ -406,8 +673,43 @@ impl OutlineBuilder for DummyOutline {
 /// A glyph raster image format.
 #[allow(missing_docs)]
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RasterImageFormat {
     PNG,
+
+    /// A monochrome bitmap.
+    ///
+    /// The most significant bit of the first byte corresponds to the top-left pixel.
+    /// Each row is padded to a byte boundary.
+    BitmapMono,
+
+    /// A packed monochrome bitmap.
+    ///
+    /// Just like `BitmapMono`, but rows are not padded.
+    BitmapMonoPacked,
+
+    /// A grayscale bitmap with 2 bits per pixel.
+    ///
+    /// Each row is padded to a byte boundary.
+    BitmapGray2,
+
+    /// A packed grayscale bitmap with 2 bits per pixel.
+    BitmapGray2Packed,
+
+    /// A grayscale bitmap with 4 bits per pixel.
+    ///
+    /// Each row is padded to a byte boundary.
+    BitmapGray4,
+
+    /// A packed grayscale bitmap with 4 bits per pixel.
+    BitmapGray4Packed,
+
+    /// A grayscale bitmap with 8 bits per pixel.
+    BitmapGray8,
+
+    JPEG,
+
+    TIFF,
 }
 
 /// A glyph's raster image.
@@ -439,6 +741,232 @@ pub struct RasterGlyphImage<'a> {
 
     /// A raw image data. It's up to the caller to decode it.
     pub data: &'a [u8],
+
+    /// A scale factor from the strike size to the requested size.
+    ///
+    /// Offsets and size should be multiplied by it to render the image at the requested size.
+    pub scale: f32,
+}
+
+#[cfg(feature = "image-decoding")]
+impl RasterGlyphImage<'_> {
+    /// Decodes the image into premultiplied RGBA pixels.
+    ///
+    /// PNG images and bitmaps are supported. Bitmaps are decoded as black pixels
+    /// with coverage stored in the alpha channel.
+    ///
+    /// Returns `None` for JPEG and TIFF images or when decoding failed.
+    pub fn decode(&self) -> Option<RgbaImage> {
+        let (bit_depth, is_packed) = match self.format {
+            RasterImageFormat::PNG => {
+                return png::decode(self.data);
+            }
+            RasterImageFormat::BitmapMono => (1, false),
+            RasterImageFormat::BitmapMonoPacked => (1, true),
+            RasterImageFormat::BitmapGray2 => (2, false),
+            RasterImageFormat::BitmapGray2Packed => (2, true),
+            RasterImageFormat::BitmapGray4 => (4, false),
+            RasterImageFormat::BitmapGray4Packed => (4, true),
+            RasterImageFormat::BitmapGray8 => (8, false),
+            RasterImageFormat::JPEG | RasterImageFormat::TIFF => return None,
+        };
+
+        let width = usize::from(self.width);
+        let height = usize::from(self.height);
+        let stride_bits = if is_packed {
+            width * bit_depth
+        } else {
+            (width * bit_depth + 7) & !7
+        };
+
+        let max_value = (1u16 << bit_depth) - 1;
+        let mut data = std::vec::Vec::with_capacity(width * height * 4);
+        for y in 0..height {
+            for x in 0..width {
+                let bit = y * stride_bits + x * bit_depth;
+                let byte = *self.data.get(bit / 8)?;
+                let value = (u16::from(byte) >> (8 - bit_depth - bit % 8)) & max_value;
+                // Black color is all zeros when premultiplied.
+                data.extend_from_slice(&[0, 0, 0, (value * 255 / max_value) as u8]);
+            }
+        }
+
+        Some(RgbaImage { width: u32::from(self.width), height: u32::from(self.height), data })
+    }
+}
+
+/// A decoded raster image.
+#[cfg(feature = "image-decoding")]
+#[derive(Clone, PartialEq, Debug)]
+pub struct RgbaImage {
+    /// Image width.
+    pub width: u32,
+
+    /// Image height.
+    pub height: u32,
+
+    /// Premultiplied RGBA pixels, row by row.
+    pub data: std::vec::Vec<u8>,
+}
+
+/// A set of color glyph formats.
+///
+/// Used by `Face::color_glyph_formats` and `Face::glyph_color_formats`.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColorGlyphFormats {
+    /// `COLR` layers or paints.
+    pub colr: bool,
+
+    /// `SVG` documents.
+    pub svg: bool,
+
+    /// `sbix` images.
+    pub sbix: bool,
+
+    /// `CBLC`+`CBDT` images.
+    pub cbdt: bool,
+}
+
+impl ColorGlyphFormats {
+    /// Checks that the set has no formats.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !(self.colr || self.svg || self.sbix || self.cbdt)
+    }
+}
+
+/// Checks that a strike of `ppem` size matches `pixels_per_em` better than a `best_ppem` one.
+///
+/// The smallest strike that is not smaller than `pixels_per_em` is preferred.
+/// Otherwise, the largest one is.
+#[inline]
+pub(crate) fn is_better_strike(pixels_per_em: u16, ppem: u16, best_ppem: u16) -> bool {
+    (pixels_per_em <= ppem && ppem < best_ppem) ||
+        (pixels_per_em > best_ppem && ppem > best_ppem)
+}
+
+/// A numbering system used by `Face::digit_glyphs`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[allow(missing_docs)]
+pub enum NumberingSystem {
+    Latin,
+    ArabicIndic,
+    ExtendedArabicIndic,
+    Nko,
+    Devanagari,
+    Bengali,
+    Gurmukhi,
+    Gujarati,
+    Oriya,
+    Tamil,
+    Telugu,
+    Kannada,
+    Malayalam,
+    Thai,
+    Lao,
+    Tibetan,
+    Myanmar,
+    Khmer,
+    Mongolian,
+    FullWidth,
+}
+
+impl NumberingSystem {
+    /// Returns a code point of the digit zero.
+    ///
+    /// All numbering systems store digits 0-9 consecutively.
+    #[inline]
+    pub fn zero(self) -> char {
+        match self {
+            NumberingSystem::Latin => '\u{0030}',
+            NumberingSystem::ArabicIndic => '\u{0660}',
+            NumberingSystem::ExtendedArabicIndic => '\u{06F0}',
+            NumberingSystem::Nko => '\u{07C0}',
+            NumberingSystem::Devanagari => '\u{0966}',
+            NumberingSystem::Bengali => '\u{09E6}',
+            NumberingSystem::Gurmukhi => '\u{0A66}',
+            NumberingSystem::Gujarati => '\u{0AE6}',
+            NumberingSystem::Oriya => '\u{0B66}',
+            NumberingSystem::Tamil => '\u{0BE6}',
+            NumberingSystem::Telugu => '\u{0C66}',
+            NumberingSystem::Kannada => '\u{0CE6}',
+            NumberingSystem::Malayalam => '\u{0D66}',
+            NumberingSystem::Thai => '\u{0E50}',
+            NumberingSystem::Lao => '\u{0ED0}',
+            NumberingSystem::Tibetan => '\u{0F20}',
+            NumberingSystem::Myanmar => '\u{1040}',
+            NumberingSystem::Khmer => '\u{17E0}',
+            NumberingSystem::Mongolian => '\u{1810}',
+            NumberingSystem::FullWidth => '\u{FF10}',
+        }
+    }
+
+    /// Returns a digit character.
+    ///
+    /// Returns `None` when `digit` is greater than 9.
+    #[inline]
+    pub fn digit(self, digit: u8) -> Option<char> {
+        if digit > 9 {
+            return None;
+        }
+
+        core::char::from_u32(u32::from(self.zero()) + u32::from(digit))
+    }
+
+    #[inline]
+    fn is_arabic(self) -> bool {
+        self == NumberingSystem::ArabicIndic || self == NumberingSystem::ExtendedArabicIndic
+    }
+}
+
+/// Glyphs used to render numbers.
+///
+/// Each glyph is `None` when a face doesn't have it.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub struct DigitGlyphs {
+    /// Digits 0-9.
+    pub digits: [Option<GlyphId>; 10],
+    /// A decimal separator.
+    pub decimal_separator: Option<GlyphId>,
+    /// A group (thousands) separator.
+    pub group_separator: Option<GlyphId>,
+    /// A percent sign.
+    pub percent: Option<GlyphId>,
+    /// A plus sign.
+    pub plus: Option<GlyphId>,
+    /// A minus sign.
+    ///
+    /// Falls back to a hyphen-minus when U+2212 is not available.
+    /// Fullwidth digits use a fullwidth hyphen-minus instead.
+    pub minus: Option<GlyphId>,
+}
+
+impl DigitGlyphs {
+    fn resolve<F>(numbering_system: NumberingSystem, glyph_index: F) -> Self
+        where F: Fn(char) -> Option<GlyphId>
+    {
+        let mut glyphs = DigitGlyphs::default();
+        for (i, glyph) in glyphs.digits.iter_mut().enumerate() {
+            *glyph = numbering_system.digit(i as u8).and_then(&glyph_index);
+        }
+
+        let (decimal_separator, group_separator, percent, plus, minus, hyphen) =
+            if numbering_system.is_arabic() {
+                ('\u{066B}', '\u{066C}', '\u{066A}', '+', '\u{2212}', '-')
+            } else if numbering_system == NumberingSystem::FullWidth {
+                ('\u{FF0E}', '\u{FF0C}', '\u{FF05}', '\u{FF0B}', '\u{2212}', '\u{FF0D}')
+            } else {
+                ('.', ',', '%', '+', '\u{2212}', '-')
+            };
+
+        glyphs.decimal_separator = glyph_index(decimal_separator);
+        glyphs.group_separator = glyph_index(group_separator);
+        glyphs.percent = glyph_index(percent);
+        glyphs.plus = glyph_index(plus);
+        glyphs.minus = glyph_index(minus).or_else(|| glyph_index(hyphen));
+        glyphs
+    }
 }
 
 /// A table name.
@@ -475,13 +1003,52 @@ pub enum TableName {
     WindowsMetrics,
 }
 
-#[derive(Clone, Copy)]
-struct TableRecord {
-    table_tag: Tag,
-    #[allow(dead_code)]
-    check_sum: u32,
-    offset: u32,
-    length: u32,
+/// A table record of a font directory.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableRecord {
+    /// Table's tag.
+    pub tag: Tag,
+
+    /// Table's checksum.
+    pub checksum: u32,
+
+    /// Table's offset from the start of the font data.
+    pub offset: u32,
+
+    /// Table's length.
+    pub length: u32,
+}
+
+impl TableRecord {
+    #[inline]
+    fn data<'a>(&self, font_data: &'a [u8]) -> Option<&'a [u8]> {
+        let offset = usize::num_from(self.offset);
+        let end = offset.checked_add(usize::num_from(self.length))?;
+        font_data.get(offset..end)
+    }
+}
+
+/// A checksum verification result.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Checksum {
+    /// A tag of the verified table.
+    pub tag: Tag,
+
+    /// A checksum stored in the font.
+    pub expected: u32,
+
+    /// A calculated checksum.
+    pub actual: u32,
+}
+
+impl Checksum {
+    /// Checks that the stored checksum matches the calculated one.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.expected == self.actual
+    }
 }
 
 impl FromData for TableRecord {
@@ -491,8 +1058,8 @@ impl FromData for TableRecord {
     fn parse(data: &[u8]) -> Option<Self> {
         let mut s = Stream::new(data);
         Some(TableRecord {
-            table_tag: s.read::<Tag>()?,
-            check_sum: s.read::<u32>()?,
+            tag: s.read::<Tag>()?,
+            checksum: s.read::<u32>()?,
             offset: s.read::<u32>()?,
             length: s.read::<u32>()?,
         })
@@ -503,16 +1070,25 @@ impl FromData for TableRecord {
 const MAX_VAR_COORDS: usize = 32;
 
 #[cfg(feature = "variable-fonts")]
-#[derive(Clone, Default)]
-struct VarCoords {
+#[derive(Clone, Copy, Default)]
+pub(crate) struct VarCoords {
     data: [NormalizedCoordinate; MAX_VAR_COORDS],
     len: u8,
 }
 
 #[cfg(feature = "variable-fonts")]
 impl VarCoords {
+    /// Creates coordinates from a slice. Extra coordinates are ignored.
+    pub(crate) fn from_slice(coordinates: &[NormalizedCoordinate]) -> Self {
+        let mut coords = VarCoords::default();
+        let len = coordinates.len().min(MAX_VAR_COORDS);
+        coords.data[..len].copy_from_slice(&coordinates[..len]);
+        coords.len = len as u8;
+        coords
+    }
+
     #[inline]
-    fn as_slice(&self) -> &[NormalizedCoordinate] {
+    pub(crate) fn as_slice(&self) -> &[NormalizedCoordinate] {
         &self.data[0..usize::from(self.len)]
     }
 
@@ -531,13 +1107,15 @@ pub enum FaceParsingError {
     /// Should occur only on malformed fonts.
     MalformedFont,
 
-    /// Face data must start with `0x00010000`, `0x74727565`, `0x4F54544F` or `0x74746366`.
+    /// Face data must start with `0x00010000`, `0x74727565`, `0x74797031`, `0x4F54544F` or `0x74746366`.
     UnknownMagic,
 
     /// The face index is larger than the number of faces in the font.
     FaceIndexOutOfBounds,
 
     /// The `head` table is missing or malformed.
+    ///
+    /// Apple's `bhed` table is accepted as well.
     NoHeadTable,
 
     /// The `hhea` table is missing or malformed.
@@ -545,6 +1123,11 @@ pub enum FaceParsingError {
 
     /// The `maxp` table is missing or malformed.
     NoMaxpTable,
+
+    /// An optional table is malformed.
+    ///
+    /// Occurs only in the strict parsing mode.
+    MalformedTable(TableError),
 }
 
 impl core::fmt::Display for FaceParsingError {
@@ -556,6 +1139,7 @@ impl core::fmt::Display for FaceParsingError {
             FaceParsingError::NoHeadTable => write!(f, "the head table is missing or malformed"),
             FaceParsingError::NoHheaTable => write!(f, "the hhea table is missing or malformed"),
             FaceParsingError::NoMaxpTable => write!(f, "the maxp table is missing or malformed"),
+            FaceParsingError::MalformedTable(ref e) => write!(f, "{}", e),
         }
     }
 }
@@ -563,67 +1147,489 @@ impl core::fmt::Display for FaceParsingError {
 #[cfg(feature = "std")]
 impl std::error::Error for FaceParsingError {}
 
-/// A font face handle.
-#[derive(Clone)]
-pub struct Face<'a> {
-    font_data: &'a [u8], // The input data. Used by Face::table_data.
-    table_records: LazyArray16<'a, TableRecord>,
-
-    cbdt: Option<&'a [u8]>,
-    cblc: Option<&'a [u8]>,
-    cff1: Option<cff1::Metadata<'a>>,
-    cmap: Option<cmap::Subtables<'a>>,
-    gdef: Option<gdef::Table<'a>>,
-    glyf: Option<&'a [u8]>,
-    gsub: Option<gsub::Table<'a>>,
-    head: &'a [u8],
-    hhea: &'a [u8],
-    hmtx: Option<hmtx::Table<'a>>,
-    kern: Option<kern::Subtables<'a>>,
-    loca: Option<loca::Table<'a>>,
-    name: Option<name::Names<'a>>,
-    os_2: Option<os2::Table<'a>>,
-    post: Option<post::Table<'a>>,
-    vhea: Option<&'a [u8]>,
-    vmtx: Option<hmtx::Table<'a>>,
-    sbix: Option<&'a [u8]>,
-    svg_: Option<&'a [u8]>,
-    vorg: Option<vorg::Table<'a>>,
+/// A face parsing mode.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ParsingMode {
+    /// Malformed optional tables are skipped.
+    ///
+    /// The rest of the face stays usable. Suitable for renderers.
+    Permissive,
 
-    // Variable font tables.
-    #[cfg(feature = "variable-fonts")]
-    avar: Option<avar::Table<'a>>,
-    #[cfg(feature = "variable-fonts")]
-    cff2: Option<cff2::Metadata<'a>>,
-    #[cfg(feature = "variable-fonts")]
-    fvar: Option<fvar::Table<'a>>,
-    #[cfg(feature = "variable-fonts")]
-    gvar: Option<gvar::Table<'a>>,
-    #[cfg(feature = "variable-fonts")]
-    hvar: Option<hvar::Table<'a>>,
-    #[cfg(feature = "variable-fonts")]
-    mvar: Option<mvar::Table<'a>>,
-    #[cfg(feature = "variable-fonts")]
-    vvar: Option<hvar::Table<'a>>,
+    /// Any malformed table is an error.
+    ///
+    /// Suitable for validators.
+    Strict,
+}
 
-    number_of_glyphs: NonZeroU16,
-    #[cfg(feature = "variable-fonts")]
-    coordinates: VarCoords,
+impl Default for ParsingMode {
+    #[inline]
+    fn default() -> Self {
+        ParsingMode::Permissive
+    }
 }
 
-impl<'a> Face<'a> {
-    /// Creates a new `Face` object from a raw data.
+/// Resource limits.
+///
+/// Allow bounding the worst-case CPU and memory usage while processing untrusted fonts.
+/// Default limits are not restrictive.
+///
+/// Lookup limits apply only to lookup analysis, i.e. `LookupGraph`, `LookupStatistics`,
+/// `LookupCoverages` and `Face::warnings`. Lookups applied by the library itself,
+/// like `GPOS` kerning, are not limited.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Limits {
+    /// The maximum table length in bytes.
     ///
-    /// `index` indicates the specific font face in a font collection.
-    /// Use `fonts_in_collection` to get the total number of font faces.
-    /// Set to 0 if unsure.
+    /// Longer tables are ignored, as if they were missing, even by `Face::table_data`.
+    /// They are reported by `Face::table_errors` with `TableErrorKind::LimitExceeded`,
+    /// which is not an error in the strict parsing mode.
+    pub max_table_size: u32,
+
+    /// The maximum depth of lookups applied by contextual lookups.
     ///
-    /// This method will do some parsing and sanitization, so it's a bit expensive.
+    /// Used by `LookupGraph::reachable`.
+    pub max_lookup_nesting: u16,
+
+    /// The maximum number of sequence rules per glyph or class in a contextual subtable.
     ///
-    /// Required tables: `head`, `hhea` and `maxp`.
+    /// Used by `LookupGraph`.
+    pub max_contexts_per_glyph: u16,
+
+    /// The maximum number of coverage table entries iterated per lookup list.
     ///
-    /// If an optional table has an invalid data it will be skipped.
-    pub fn from_slice(data: &'a [u8], index: u32) -> Result<Self, FaceParsingError> {
+    /// Used by `LookupStatistics`, `LookupCoverages` and `Face::warnings`.
+    pub max_coverage_entries: u32,
+}
+
+impl Default for Limits {
+    #[inline]
+    fn default() -> Self {
+        Limits {
+            max_table_size: u32::MAX,
+            max_lookup_nesting: u16::MAX,
+            max_contexts_per_glyph: u16::MAX,
+            max_coverage_entries: u32::MAX,
+        }
+    }
+}
+
+/// Face parsing options.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub struct ParseOptions {
+    /// A parsing mode.
+    pub mode: ParsingMode,
+
+    /// Resource limits.
+    pub limits: Limits,
+}
+
+/// A reason of a table parsing failure.
+///
+/// Except for `OutOfBounds` and `LimitExceeded`, the reason is a best-effort guess
+/// based on table's header, since table parsers don't report failure reasons.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TableErrorKind {
+    /// Table data is out of the font data bounds.
+    OutOfBounds,
+
+    /// Table data is shorter than the table header.
+    Truncated,
+
+    /// Table version is not supported.
+    UnsupportedVersion,
+
+    /// Table data is malformed or not supported.
+    Malformed,
+
+    /// Table length exceeds `Limits::max_table_size`.
+    ///
+    /// The table is ignored, but it's not malformed.
+    LimitExceeded,
+}
+
+/// A table parsing error.
+///
+/// Only identifies the table and the reason. An offset of the failure is not reported,
+/// since table parsers return `Option` and don't track where parsing stopped.
+/// Use `Face::table_records` to find where the table data is located.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TableError {
+    /// A tag of the failed table.
+    pub tag: Tag,
+
+    /// A failure reason.
+    pub kind: TableErrorKind,
+}
+
+impl core::fmt::Display for TableError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let reason = match self.kind {
+            TableErrorKind::OutOfBounds => "is out of bounds",
+            TableErrorKind::Truncated => "is truncated",
+            TableErrorKind::UnsupportedVersion => "has an unsupported version",
+            TableErrorKind::Malformed => "is malformed",
+            TableErrorKind::LimitExceeded => "exceeds the size limit",
+        };
+
+        write!(f, "the {} table {}", self.tag, reason)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TableError {}
+
+/// A non-fatal font anomaly.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Warning {
+    /// Table records are not sorted by tag.
+    ///
+    /// Some tables may not be found by `Face::table_data`.
+    UnsortedTableRecords,
+
+    /// Table length doesn't match the one calculated from other tables.
+    UnexpectedTableLength {
+        /// Table's tag.
+        tag: Tag,
+        /// Calculated length.
+        expected: u32,
+        /// Stored length.
+        actual: u32,
+    },
+
+    /// A glyph ID is larger than the number of glyphs in the face.
+    ///
+    /// Only the first one per table is reported.
+    GlyphIdOutOfBounds {
+        /// Table's tag.
+        tag: Tag,
+        /// Glyph ID.
+        glyph_id: GlyphId,
+    },
+
+    /// Lookups contain unsorted coverage tables.
+    UnsortedCoverage {
+        /// Table's tag.
+        tag: Tag,
+        /// The number of unsorted coverage tables.
+        count: u32,
+    },
+}
+
+impl Warning {
+    /// Returns a tag of the table the anomaly is related to.
+    #[inline]
+    pub fn tag(&self) -> Option<Tag> {
+        match *self {
+            Warning::UnsortedTableRecords => None,
+            Warning::UnexpectedTableLength { tag, .. } => Some(tag),
+            Warning::GlyphIdOutOfBounds { tag, .. } => Some(tag),
+            Warning::UnsortedCoverage { tag, .. } => Some(tag),
+        }
+    }
+}
+
+/// Guesses why the table data cannot be parsed.
+///
+/// Only table's header is checked.
+fn diagnose_table(tag: Tag, data: &[u8]) -> TableErrorKind {
+    // Minimal header sizes and supported major versions of tables with a common layout.
+    let (header_size, versions): (usize, &[u16]) = match &tag.to_bytes() {
+        b"head" | b"bhed" => (54, &[1]),
+        b"hhea" | b"vhea" => (36, &[1]),
+        b"maxp" => (6, &[0, 1]),
+        b"OS/2" => (78, &[0, 1, 2, 3, 4, 5]),
+        b"post" => (32, &[1, 2, 3, 4]),
+        b"cmap" => (4, &[0]),
+        b"name" => (6, &[0, 1]),
+        b"BASE" | b"GDEF" | b"GPOS" | b"GSUB" | b"JSTF" | b"MATH" => (4, &[1]),
+        _ => (0, &[]),
+    };
+
+    if data.len() < header_size {
+        return TableErrorKind::Truncated;
+    }
+
+    match Stream::read_at::<u16>(data, 0) {
+        Some(version) if !versions.is_empty() && !versions.contains(&version) => {
+            TableErrorKind::UnsupportedVersion
+        }
+        _ => TableErrorKind::Malformed,
+    }
+}
+
+/// Returns the smallest Unicode code point for each requested glyph, indexed by glyph ID.
+#[cfg(feature = "std")]
+fn smallest_code_points(
+    subtables: cmap::Subtables,
+    glyphs: &GlyphSet,
+    number_of_glyphs: u16,
+) -> std::vec::Vec<Option<u32>> {
+    let number_of_glyphs = usize::from(number_of_glyphs);
+    let requested = glyphs.iter()
+        .filter(|id| id.0 != 0 && usize::from(id.0) < number_of_glyphs)
+        .count();
+    let mut code_points = vec![None; number_of_glyphs];
+    if requested == 0 {
+        return code_points;
+    }
+
+    for subtable in subtables.filter(|s| s.is_unicode()) {
+        // Code points are visited in ascending order, so the first one is the smallest
+        // in this subtable and we can stop once all requested glyphs were found.
+        let mut found = vec![false; number_of_glyphs];
+        let mut left = requested;
+        subtable.mappings(|c, id| {
+            if !glyphs.contains(id) {
+                return true;
+            }
+
+            if let Some(found) = found.get_mut(usize::from(id.0)).filter(|found| !**found) {
+                *found = true;
+                left -= 1;
+
+                let slot = &mut code_points[usize::from(id.0)];
+                match *slot {
+                    Some(prev) if prev <= c => {}
+                    _ => *slot = Some(c),
+                }
+            }
+
+            left != 0
+        });
+    }
+
+    code_points
+}
+
+/// Tables parsed and validated during `Face` creation.
+///
+/// Tables with a public API are stored parsed. The rest are stored
+/// as raw data, which is present only when the table was parsed successfully.
+///
+/// A missing field means that the table is not present or malformed.
+#[derive(Clone, Copy, Debug)]
+pub struct FaceTables<'a> {
+    /// A `BASE` table.
+    pub base: Option<base::Table<'a>>,
+    /// `CBDT` table data.
+    pub cbdt: Option<&'a [u8]>,
+    /// A `CBLC` table.
+    pub cblc: Option<cblc::Table<'a>>,
+    /// `CFF ` table data.
+    pub cff: Option<&'a [u8]>,
+    /// A `cmap` table.
+    pub cmap: Option<cmap::Subtables<'a>>,
+    /// A `COLR` table.
+    pub colr: Option<colr::Table<'a>>,
+    /// A `CPAL` table.
+    pub cpal: Option<cpal::Table<'a>>,
+    /// A `cvt ` table.
+    pub cvt: Option<cvt::Table<'a>>,
+    /// A `DSIG` table.
+    pub dsig: Option<dsig::Table<'a>>,
+    /// `EBDT` or Apple's `bdat` table data.
+    pub ebdt: Option<&'a [u8]>,
+    /// `EBLC` or Apple's `bloc` table, which have the same structure as `CBLC`.
+    pub eblc: Option<cblc::Table<'a>>,
+    /// An `EBSC` table.
+    pub ebsc: Option<ebsc::Table<'a>>,
+    /// An `fdsc` table.
+    pub fdsc: Option<fdsc::Table<'a>>,
+    /// An `fmtx` table.
+    pub fmtx: Option<fmtx::Table>,
+    /// `fpgm` table data.
+    pub fpgm: Option<&'a [u8]>,
+    /// `GDEF` table data.
+    pub gdef: Option<&'a [u8]>,
+    /// `glyf` table data.
+    pub glyf: Option<&'a [u8]>,
+    /// `GPOS` table data.
+    pub gpos: Option<&'a [u8]>,
+    /// `GSUB` table data.
+    pub gsub: Option<&'a [u8]>,
+    /// `hdmx` table data.
+    pub hdmx: Option<&'a [u8]>,
+    /// `head` or Apple's `bhed` table data.
+    pub head: &'a [u8],
+    /// `hhea` table data.
+    pub hhea: &'a [u8],
+    /// `hmtx` table data.
+    pub hmtx: Option<&'a [u8]>,
+    /// A `JSTF` table.
+    pub jstf: Option<jstf::Table<'a>>,
+    /// A `kern` table.
+    pub kern: Option<kern::Subtables<'a>>,
+    /// `loca` table data.
+    pub loca: Option<&'a [u8]>,
+    /// `LTSH` table data.
+    pub ltsh: Option<&'a [u8]>,
+    /// A `MATH` table.
+    pub math: Option<math::Table<'a>>,
+    /// `maxp` table data.
+    pub maxp: &'a [u8],
+    /// A `MERG` table.
+    pub merg: Option<merg::Table<'a>>,
+    /// A `meta` table.
+    pub meta: Option<meta::Table<'a>>,
+    /// A `name` table.
+    pub name: Option<Names<'a>>,
+    /// `OS/2` table data.
+    pub os_2: Option<&'a [u8]>,
+    /// A `PCLT` table.
+    pub pclt: Option<pclt::Table<'a>>,
+    /// `post` table data.
+    pub post: Option<&'a [u8]>,
+    /// `prep` table data.
+    pub prep: Option<&'a [u8]>,
+    /// An `sbix` table.
+    pub sbix: Option<sbix::Table<'a>>,
+    /// A `STAT` table.
+    pub stat: Option<stat::Table<'a>>,
+    /// An `SVG ` table.
+    pub svg: Option<svg::Table<'a>>,
+    /// `VDMX` table data.
+    pub vdmx: Option<&'a [u8]>,
+    /// `vhea` table data.
+    pub vhea: Option<&'a [u8]>,
+    /// `vmtx` table data.
+    pub vmtx: Option<&'a [u8]>,
+    /// `VORG` table data.
+    pub vorg: Option<&'a [u8]>,
+    /// A `Zapf` table.
+    pub zapf: Option<zapf::Table<'a>>,
+
+    /// `avar` table data.
+    #[cfg(feature = "variable-fonts")]
+    pub avar: Option<&'a [u8]>,
+    /// `CFF2` table data.
+    #[cfg(feature = "variable-fonts")]
+    pub cff2: Option<&'a [u8]>,
+    /// `fvar` table data.
+    #[cfg(feature = "variable-fonts")]
+    pub fvar: Option<&'a [u8]>,
+    /// `gvar` table data.
+    #[cfg(feature = "variable-fonts")]
+    pub gvar: Option<&'a [u8]>,
+    /// `HVAR` table data.
+    #[cfg(feature = "variable-fonts")]
+    pub hvar: Option<&'a [u8]>,
+    /// `MVAR` table data.
+    #[cfg(feature = "variable-fonts")]
+    pub mvar: Option<&'a [u8]>,
+    /// `VVAR` table data.
+    #[cfg(feature = "variable-fonts")]
+    pub vvar: Option<&'a [u8]>,
+}
+
+/// A font face handle.
+#[derive(Clone)]
+pub struct Face<'a> {
+    font_data: &'a [u8], // The input data. Used by Face::table_data.
+    table_records: LazyArray16<'a, TableRecord>,
+    limits: Limits,
+
+    base: Option<base::Table<'a>>,
+    cbdt: Option<&'a [u8]>,
+    cblc: Option<cblc::Table<'a>>,
+    cff1: Option<cff1::Metadata<'a>>,
+    cmap: Option<cmap::Subtables<'a>>,
+    // The first Unicode `cmap` subtable is resolved once during parsing.
+    unicode_cmap: Option<cmap::Subtable<'a>>,
+    has_extra_unicode_cmaps: bool,
+    colr: Option<colr::Table<'a>>,
+    cpal: Option<cpal::Table<'a>>,
+    cvt: Option<cvt::Table<'a>>,
+    dsig: Option<dsig::Table<'a>>,
+    ebdt: Option<&'a [u8]>,
+    eblc: Option<cblc::Table<'a>>,
+    ebsc: Option<ebsc::Table<'a>>,
+    fdsc: Option<fdsc::Table<'a>>,
+    fmtx: Option<fmtx::Table>,
+    gdef: Option<gdef::Table<'a>>,
+    fpgm: Option<&'a [u8]>,
+    glyf: Option<&'a [u8]>,
+    gpos: Option<gpos::Table<'a>>,
+    gsub: Option<gsub::Table<'a>>,
+    hdmx: Option<hdmx::Table<'a>>,
+    head: &'a [u8],
+    hhea: &'a [u8],
+    hmtx: Option<hmtx::Table<'a>>,
+    jstf: Option<jstf::Table<'a>>,
+    kern: Option<kern::Subtables<'a>>,
+    loca: Option<loca::Table<'a>>,
+    math: Option<math::Table<'a>>,
+    maxp: &'a [u8],
+    merg: Option<merg::Table<'a>>,
+    meta: Option<meta::Table<'a>>,
+    ltsh: Option<ltsh::Table<'a>>,
+    name: Option<name::Names<'a>>,
+    os_2: Option<os2::Table<'a>>,
+    pclt: Option<pclt::Table<'a>>,
+    post: Option<post::Table<'a>>,
+    prep: Option<&'a [u8]>,
+    vhea: Option<&'a [u8]>,
+    vmtx: Option<hmtx::Table<'a>>,
+    sbix: Option<sbix::Table<'a>>,
+    stat: Option<stat::Table<'a>>,
+    svg_: Option<svg::Table<'a>>,
+    vdmx: Option<vdmx::Table<'a>>,
+    vorg: Option<vorg::Table<'a>>,
+    zapf: Option<zapf::Table<'a>>,
+
+    // Variable font tables.
+    #[cfg(feature = "variable-fonts")]
+    avar: Option<avar::Table<'a>>,
+    #[cfg(feature = "variable-fonts")]
+    cff2: Option<cff2::Metadata<'a>>,
+    #[cfg(feature = "variable-fonts")]
+    fvar: Option<fvar::Table<'a>>,
+    #[cfg(feature = "variable-fonts")]
+    gvar: Option<gvar::Table<'a>>,
+    #[cfg(feature = "variable-fonts")]
+    hvar: Option<hvar::Table<'a>>,
+    #[cfg(feature = "variable-fonts")]
+    mvar: Option<mvar::Table<'a>>,
+    #[cfg(feature = "variable-fonts")]
+    vvar: Option<hvar::Table<'a>>,
+
+    number_of_glyphs: NonZeroU16,
+    /// Normalized coordinates before the `avar` mapping.
+    #[cfg(feature = "variable-fonts")]
+    unmapped_coordinates: VarCoords,
+    #[cfg(feature = "variable-fonts")]
+    variation_rounding: VariationRounding,
+    #[cfg(feature = "variable-fonts")]
+    coordinates: VarCoords,
+}
+
+impl<'a> Face<'a> {
+    /// Creates a new `Face` object from a raw data.
+    ///
+    /// `index` indicates the specific font face in a font collection.
+    /// Use `fonts_in_collection` to get the total number of font faces.
+    /// Set to 0 if unsure.
+    ///
+    /// This method will do some parsing and sanitization, so it's a bit expensive.
+    ///
+    /// Required tables: `head`, `hhea` and `maxp`.
+    ///
+    /// If an optional table has an invalid data it will be skipped.
+    /// Use `from_slice_with_options` to reject such fonts instead.
+    #[inline]
+    pub fn from_slice(data: &'a [u8], index: u32) -> Result<Self, FaceParsingError> {
+        Self::from_slice_with_options(data, index, ParseOptions::default())
+    }
+
+    /// Creates a new `Face` object from a raw data using the specified options.
+    ///
+    /// In the strict mode, the first table reported by `table_errors`
+    /// is returned as `FaceParsingError::MalformedTable`.
+    /// Tables ignored because of `Limits::max_table_size` are not errors.
+    pub fn from_slice_with_options(
+        data: &'a [u8],
+        index: u32,
+        options: ParseOptions,
+    ) -> Result<Self, FaceParsingError> {
         // https://docs.microsoft.com/en-us/typography/opentype/spec/otff#organization-of-an-opentype-font
 
         let mut s = Stream::new(data);
@@ -667,26 +1673,53 @@ impl<'a> Face<'a> {
         let mut face = Face {
             font_data: data,
             table_records: tables,
+            limits: options.limits,
+            base: None,
             cbdt: None,
             cblc: None,
             cff1: None,
             cmap: None,
+            unicode_cmap: None,
+            has_extra_unicode_cmaps: false,
+            colr: None,
+            cpal: None,
+            cvt: None,
+            dsig: None,
+            ebdt: None,
+            eblc: None,
+            ebsc: None,
+            fdsc: None,
+            fmtx: None,
             gdef: None,
+            gpos: None,
             gsub: None,
+            fpgm: None,
             glyf: None,
+            hdmx: None,
             head: &[],
             hhea: &[],
             hmtx: None,
+            jstf: None,
             kern: None,
             loca: None,
+            maxp: &[],
+            math: None,
+            merg: None,
+            meta: None,
+            ltsh: None,
             name: None,
             os_2: None,
+            pclt: None,
             post: None,
+            prep: None,
             vhea: None,
             vmtx: None,
             sbix: None,
+            stat: None,
             svg_: None,
+            vdmx: None,
             vorg: None,
+            zapf: None,
             #[cfg(feature = "variable-fonts")]
             avar: None,
             #[cfg(feature = "variable-fonts")]
@@ -703,6 +1736,10 @@ impl<'a> Face<'a> {
             vvar: None,
             number_of_glyphs: NonZeroU16::new(1).unwrap(), // dummy
             #[cfg(feature = "variable-fonts")]
+            unmapped_coordinates: VarCoords::default(),
+            #[cfg(feature = "variable-fonts")]
+            variation_rounding: VariationRounding::default(),
+            #[cfg(feature = "variable-fonts")]
             coordinates: VarCoords::default(),
         };
 
@@ -710,8 +1747,14 @@ impl<'a> Face<'a> {
         let mut hmtx = None;
         let mut vmtx = None;
         let mut loca = None;
+        let mut sbix = None;
+        let mut zapf = None;
 
         for table in tables {
+            if table.length > options.limits.max_table_size {
+                continue;
+            }
+
             let offset = usize::num_from(table.offset);
             let length = usize::num_from(table.length);
             let end = offset
@@ -719,37 +1762,66 @@ impl<'a> Face<'a> {
                 .ok_or(FaceParsingError::MalformedFont)?;
             let range = offset..end;
 
-            match &table.table_tag.to_bytes() {
+            match &table.tag.to_bytes() {
+                b"BASE" => face.base = data.get(range).and_then(|data| base::Table::parse(data)),
                 b"CBDT" => face.cbdt = data.get(range),
-                b"CBLC" => face.cblc = data.get(range),
+                b"CBLC" => face.cblc = data.get(range).and_then(|data| cblc::Table::parse(data)),
                 b"CFF " => face.cff1 = data.get(range).and_then(|data| cff1::parse_metadata(data)),
                 #[cfg(feature = "variable-fonts")]
                 b"CFF2" => face.cff2 = data.get(range).and_then(|data| cff2::parse_metadata(data)),
                 b"GDEF" => face.gdef = data.get(range).and_then(|data| gdef::Table::parse(data)),
+                b"GPOS" => face.gpos = data.get(range).and_then(|data| gpos::Table::parse(data)),
                 b"GSUB" => face.gsub = data.get(range).and_then(|data| gsub::Table::parse(data)),
                 #[cfg(feature = "variable-fonts")]
                 b"HVAR" => face.hvar = data.get(range).and_then(|data| hvar::Table::parse(data)),
                 #[cfg(feature = "variable-fonts")]
                 b"MVAR" => face.mvar = data.get(range).and_then(|data| mvar::Table::parse(data)),
                 b"OS/2" => face.os_2 = data.get(range).and_then(|data| os2::Table::parse(data)),
-                b"SVG " => face.svg_ = data.get(range),
+                b"SVG " => face.svg_ = data.get(range).and_then(|data| svg::Table::parse(data)),
                 b"VORG" => face.vorg = data.get(range).and_then(|data| vorg::Table::parse(data)),
                 #[cfg(feature = "variable-fonts")]
                 b"VVAR" => face.vvar = data.get(range).and_then(|data| hvar::Table::parse(data)),
                 #[cfg(feature = "variable-fonts")]
                 b"avar" => face.avar = data.get(range).and_then(|data| avar::Table::parse(data)),
                 b"cmap" => face.cmap = data.get(range).and_then(|data| cmap::parse(data)),
+                b"COLR" => face.colr = data.get(range).and_then(|data| colr::Table::parse(data)),
+                b"CPAL" => face.cpal = data.get(range).and_then(|data| cpal::Table::parse(data)),
+                b"cvt " => face.cvt = data.get(range).and_then(|data| cvt::Table::parse(data)),
+                b"DSIG" => face.dsig = data.get(range).and_then(|data| dsig::Table::parse(data)),
+                b"EBDT" => face.ebdt = data.get(range),
+                // `EBLC` has the same structure as `CBLC`.
+                b"EBLC" => face.eblc = data.get(range).and_then(|data| cblc::Table::parse(data)),
+                // `bdat` and `bloc` are Apple's predecessors of `EBDT` and `EBLC`
+                // with the same structure. `EBDT` and `EBLC` take precedence.
+                b"bdat" => face.ebdt = face.ebdt.or_else(|| data.get(range)),
+                b"bloc" => {
+                    face.eblc = face.eblc
+                        .or_else(|| data.get(range).and_then(|data| cblc::Table::parse(data)))
+                }
+                b"EBSC" => face.ebsc = data.get(range).and_then(|data| ebsc::Table::parse(data)),
+                b"fdsc" => face.fdsc = data.get(range).and_then(|data| fdsc::Table::parse(data)),
+                b"fmtx" => face.fmtx = data.get(range).and_then(|data| fmtx::Table::parse(data)),
                 #[cfg(feature = "variable-fonts")]
                 b"fvar" => face.fvar = data.get(range).and_then(|data| fvar::Table::parse(data)),
+                b"fpgm" => face.fpgm = data.get(range),
                 b"glyf" => face.glyf = data.get(range),
                 #[cfg(feature = "variable-fonts")]
                 b"gvar" => face.gvar = data.get(range).and_then(|data| gvar::Table::parse(data)),
+                b"hdmx" => face.hdmx = data.get(range).and_then(|data| hdmx::Table::parse(data)),
                 b"head" => {
                     face.head = data
                         .get(range)
                         .and_then(|data| head::parse(data))
                         .unwrap_or_default()
                 }
+                // Apple's bitmap-only fonts use `bhed` instead of `head`.
+                // They have the same structure, but `head` takes precedence.
+                b"bhed" if face.head.is_empty() => {
+                    face.head = data
+                        .get(range)
+                        .and_then(|data| head::parse(data))
+                        .unwrap_or_default()
+                }
                 b"hhea" => {
                     face.hhea = data
                         .get(range)
@@ -757,12 +1829,25 @@ impl<'a> Face<'a> {
                         .unwrap_or_default()
                 }
                 b"hmtx" => hmtx = data.get(range),
+                b"JSTF" => face.jstf = data.get(range).and_then(|data| jstf::Table::parse(data)),
                 b"kern" => face.kern = data.get(range).and_then(|data| kern::parse(data)),
+                b"LTSH" => face.ltsh = data.get(range).and_then(|data| ltsh::Table::parse(data)),
+                b"MATH" => face.math = data.get(range).and_then(|data| math::Table::parse(data)),
                 b"loca" => loca = data.get(range),
-                b"maxp" => number_of_glyphs = data.get(range).and_then(|data| maxp::parse(data)),
+                b"maxp" => {
+                    face.maxp = data.get(range).unwrap_or_default();
+                    number_of_glyphs = maxp::parse(face.maxp);
+                }
+                b"MERG" => face.merg = data.get(range).and_then(|data| merg::Table::parse(data)),
+                b"meta" => face.meta = data.get(range).and_then(|data| meta::Table::parse(data)),
                 b"name" => face.name = data.get(range).and_then(|data| name::parse(data)),
+                b"PCLT" => face.pclt = data.get(range).and_then(|data| pclt::Table::parse(data)),
                 b"post" => face.post = data.get(range).and_then(|data| post::Table::parse(data)),
-                b"sbix" => face.sbix = data.get(range),
+                b"prep" => face.prep = data.get(range),
+                b"STAT" => face.stat = data.get(range).and_then(|data| stat::Table::parse(data)),
+                b"sbix" => sbix = data.get(range),
+                b"Zapf" => zapf = data.get(range),
+                b"VDMX" => face.vdmx = data.get(range).and_then(|data| vdmx::Table::parse(data)),
                 b"vhea" => face.vhea = data.get(range).and_then(|data| vhea::parse(data)),
                 b"vmtx" => vmtx = data.get(range),
                 _ => {}
@@ -782,10 +1867,17 @@ impl<'a> Face<'a> {
             None => return Err(FaceParsingError::NoMaxpTable),
         };
 
+        if let Some(subtables) = face.cmap {
+            let mut unicode = subtables.filter(|s| s.is_unicode());
+            face.unicode_cmap = unicode.next();
+            face.has_extra_unicode_cmaps = unicode.next().is_some();
+        }
+
         #[cfg(feature = "variable-fonts")]
         {
             if let Some(ref fvar) = face.fvar {
                 face.coordinates.len = fvar.axes().count().min(MAX_VAR_COORDS) as u8;
+                face.unmapped_coordinates.len = face.coordinates.len;
             }
         }
 
@@ -807,6 +1899,21 @@ impl<'a> Face<'a> {
             }
         }
 
+        if let Some(data) = sbix {
+            face.sbix = sbix::Table::parse(data, face.number_of_glyphs);
+        }
+
+        if let Some(data) = zapf {
+            face.zapf = zapf::Table::parse(data, face.number_of_glyphs.get());
+        }
+
+        if options.mode == ParsingMode::Strict {
+            let mut errors = face.table_errors().filter(|e| e.kind != TableErrorKind::LimitExceeded);
+            if let Some(e) = errors.next() {
+                return Err(FaceParsingError::MalformedTable(e));
+            }
+        }
+
         Ok(face)
     }
 
@@ -887,14 +1994,245 @@ impl<'a> Face<'a> {
     /// Returns the raw data of a selected table.
     ///
     /// Useful if you want to parse the data manually.
+    ///
+    /// Returns `None` for tables longer than `Limits::max_table_size`.
     pub fn table_data(&self, tag: Tag) -> Option<&'a [u8]> {
         let (_, table) = self
             .table_records
-            .binary_search_by(|record| record.table_tag.cmp(&tag))?;
-        let offset = usize::num_from(table.offset);
-        let length = usize::num_from(table.length);
-        let end = offset.checked_add(length)?;
-        self.font_data.get(offset..end)
+            .binary_search_by(|record| record.tag.cmp(&tag))?;
+        if table.length > self.limits.max_table_size {
+            return None;
+        }
+
+        table.data(self.font_data)
+    }
+
+    /// Returns an iterator over table records.
+    ///
+    /// Records are returned in the font directory order, including tables
+    /// that are not supported or malformed.
+    #[inline]
+    pub fn table_records(&self) -> impl Iterator<Item = TableRecord> + 'a {
+        self.table_records.into_iter()
+    }
+
+    /// Returns an iterator over tables that failed to parse.
+    ///
+    /// Since malformed optional tables are silently ignored by `Face::from_slice`,
+    /// this method can be used to find out why a specific table is not available.
+    /// The reason is a best-effort guess and no offset of the failure is reported.
+    /// See `TableErrorKind` and `TableError` for details.
+    ///
+    /// Unsupported tables are checked only against `Limits::max_table_size`.
+    pub fn table_errors(&self) -> impl Iterator<Item = TableError> + 'a {
+        let face = self.clone();
+        self.table_records.into_iter().filter_map(move |record| {
+            if record.length > face.limits.max_table_size {
+                return Some(TableError { tag: record.tag, kind: TableErrorKind::LimitExceeded });
+            }
+
+            let kind = match record.data(face.font_data) {
+                Some(data) => {
+                    if face.is_table_parsed(record.tag)? {
+                        return None;
+                    }
+
+                    diagnose_table(record.tag, data)
+                }
+                None => TableErrorKind::OutOfBounds,
+            };
+
+            Some(TableError { tag: record.tag, kind })
+        })
+    }
+
+    /// Checks that a known table was successfully parsed.
+    ///
+    /// Returns `None` for unsupported tables.
+    fn is_table_parsed(&self, tag: Tag) -> Option<bool> {
+        let is_parsed = match &tag.to_bytes() {
+            b"BASE" => self.base.is_some(),
+            b"CBDT" => self.cbdt.is_some(),
+            b"CBLC" => self.cblc.is_some(),
+            b"CFF " => self.cff1.is_some(),
+            b"COLR" => self.colr.is_some(),
+            b"CPAL" => self.cpal.is_some(),
+            b"cvt " => self.cvt.is_some(),
+            b"DSIG" => self.dsig.is_some(),
+            b"EBDT" | b"bdat" => self.ebdt.is_some(),
+            b"EBLC" | b"bloc" => self.eblc.is_some(),
+            b"EBSC" => self.ebsc.is_some(),
+            b"fdsc" => self.fdsc.is_some(),
+            b"fmtx" => self.fmtx.is_some(),
+            b"GDEF" => self.gdef.is_some(),
+            b"GPOS" => self.gpos.is_some(),
+            b"GSUB" => self.gsub.is_some(),
+            b"JSTF" => self.jstf.is_some(),
+            b"LTSH" => self.ltsh.is_some(),
+            b"MATH" => self.math.is_some(),
+            b"MERG" => self.merg.is_some(),
+            b"OS/2" => self.os_2.is_some(),
+            b"PCLT" => self.pclt.is_some(),
+            b"STAT" => self.stat.is_some(),
+            b"SVG " => self.svg_.is_some(),
+            b"VDMX" => self.vdmx.is_some(),
+            b"VORG" => self.vorg.is_some(),
+            b"Zapf" => self.zapf.is_some(),
+            b"cmap" => self.cmap.is_some(),
+            b"glyf" => self.glyf.is_some(),
+            b"hdmx" => self.hdmx.is_some(),
+            // Required tables are always parsed.
+            b"head" | b"bhed" | b"hhea" | b"maxp" => true,
+            b"hmtx" => self.hmtx.is_some(),
+            b"kern" => self.kern.is_some(),
+            b"loca" => self.loca.is_some(),
+            b"meta" => self.meta.is_some(),
+            b"name" => self.name.is_some(),
+            b"post" => self.post.is_some(),
+            b"sbix" => self.sbix.is_some(),
+            b"vhea" => self.vhea.is_some(),
+            b"vmtx" => self.vmtx.is_some(),
+            #[cfg(feature = "variable-fonts")]
+            b"CFF2" => self.cff2.is_some(),
+            #[cfg(feature = "variable-fonts")]
+            b"HVAR" => self.hvar.is_some(),
+            #[cfg(feature = "variable-fonts")]
+            b"MVAR" => self.mvar.is_some(),
+            #[cfg(feature = "variable-fonts")]
+            b"VVAR" => self.vvar.is_some(),
+            #[cfg(feature = "variable-fonts")]
+            b"avar" => self.avar.is_some(),
+            #[cfg(feature = "variable-fonts")]
+            b"fvar" => self.fvar.is_some(),
+            #[cfg(feature = "variable-fonts")]
+            b"gvar" => self.gvar.is_some(),
+            _ => return None,
+        };
+
+        Some(is_parsed)
+    }
+
+    /// Returns an iterator over non-fatal anomalies.
+    ///
+    /// Checks that table records are sorted, lengths of tables with a known size,
+    /// `cmap` glyph IDs and `GSUB`/`GPOS` primary coverage tables.
+    ///
+    /// This method is expensive, since the whole `cmap` table is traversed.
+    pub fn warnings(&self) -> impl Iterator<Item = Warning> + 'a {
+        let records = self.table_records;
+        let is_sorted = records.into_iter().zip(records.into_iter().skip(1))
+            .all(|(a, b)| a.tag < b.tag);
+        let unsorted_records = if is_sorted { None } else { Some(Warning::UnsortedTableRecords) };
+
+        let face = self.clone();
+        let lengths = records.into_iter().filter_map(move |record| {
+            let expected = face.expected_table_length(record.tag)?;
+            if expected == record.length {
+                return None;
+            }
+
+            Some(Warning::UnexpectedTableLength { tag: record.tag, expected, actual: record.length })
+        });
+
+        let gsub = self.gsub_lookup_statistics().map(|stats| (b"GSUB", stats));
+        let gpos = self.gpos_lookup_statistics().map(|stats| (b"GPOS", stats));
+        let coverages = gsub.into_iter().chain(gpos).filter_map(|(tag, stats)| {
+            if stats.unsorted_coverages_count == 0 {
+                return None;
+            }
+
+            Some(Warning::UnsortedCoverage {
+                tag: Tag::from_bytes(tag),
+                count: stats.unsorted_coverages_count,
+            })
+        });
+
+        unsorted_records.into_iter()
+            .chain(lengths)
+            .chain(self.cmap_glyph_id_out_of_bounds())
+            .chain(coverages)
+    }
+
+    /// Returns a table length calculated from other tables.
+    fn expected_table_length(&self, tag: Tag) -> Option<u32> {
+        let number_of_glyphs = u32::from(self.number_of_glyphs.get());
+        let metrics_len = |number_of_metrics: NonZeroU16| {
+            let number_of_metrics = u32::from(number_of_metrics.get());
+            // Trailing glyphs have only a side bearing.
+            number_of_metrics * 4 + number_of_glyphs.saturating_sub(number_of_metrics) * 2
+        };
+
+        match &tag.to_bytes() {
+            b"head" | b"bhed" => Some(54),
+            b"hhea" | b"vhea" => Some(36),
+            b"maxp" => match Stream::read_at::<u32>(self.maxp, 0)? {
+                0x00005000 => Some(6),
+                0x00010000 => Some(32),
+                _ => None,
+            },
+            b"hmtx" => hhea::number_of_h_metrics(self.hhea).map(metrics_len),
+            b"vmtx" => vhea::num_of_long_ver_metrics(self.vhea?).map(metrics_len),
+            b"loca" => {
+                let offset_size = match head::index_to_loc_format(self.head)? {
+                    IndexToLocationFormat::Short => 2,
+                    IndexToLocationFormat::Long => 4,
+                };
+
+                Some((number_of_glyphs + 1) * offset_size)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the first glyph ID from `cmap`, which is out of bounds.
+    fn cmap_glyph_id_out_of_bounds(&self) -> Option<Warning> {
+        let number_of_glyphs = self.number_of_glyphs.get();
+        let glyph_id = self.cmap?.find_map(|subtable| subtable.glyph_id_out_of_bounds(number_of_glyphs));
+        Some(Warning::GlyphIdOutOfBounds { tag: Tag::from_bytes(b"cmap"), glyph_id: glyph_id? })
+    }
+
+    /// Returns an iterator over table checksums.
+    ///
+    /// Checksums are recalculated, so this method is expensive.
+    /// `head` checksum adjustment is treated as zero, as required by the specification.
+    ///
+    /// Tables with out of bounds data are skipped.
+    pub fn table_checksums(&self) -> impl Iterator<Item = Checksum> + 'a {
+        let font_data = self.font_data;
+        self.table_records.into_iter().filter_map(move |record| {
+            let data = record.data(font_data)?;
+            let is_head = record.tag == Tag::from_bytes(b"head") || record.tag == Tag::from_bytes(b"bhed");
+            Some(Checksum {
+                tag: record.tag,
+                expected: record.checksum,
+                actual: sfnt::checksum(data, if is_head { Some(sfnt::HEAD_CHECKSUM_ADJUSTMENT_OFFSET) } else { None }),
+            })
+        })
+    }
+
+    /// Verifies `head` checksum adjustment, which is a checksum of the whole font.
+    ///
+    /// Checksum adjustment is not defined for faces in a font collection,
+    /// therefore `None` will be returned for them.
+    ///
+    /// Returns `None` when `head` table is not present.
+    pub fn checksum_adjustment(&self) -> Option<Checksum> {
+        if self.font_data.starts_with(b"ttcf") {
+            return None;
+        }
+
+        let record = self.table_records.into_iter().find(|record| {
+            record.tag == Tag::from_bytes(b"head") || record.tag == Tag::from_bytes(b"bhed")
+        })?;
+
+        let offset = sfnt::HEAD_CHECKSUM_ADJUSTMENT_OFFSET;
+        let expected: u32 = Stream::read_at(record.data(self.font_data)?, offset)?;
+        let sum = sfnt::checksum(self.font_data, Some(usize::num_from(record.offset) + offset));
+        Some(Checksum {
+            tag: record.tag,
+            expected,
+            actual: sfnt::CHECKSUM_MAGIC.wrapping_sub(sum),
+        })
     }
 
     /// Returns an iterator over [Name Records].
@@ -907,6 +2245,60 @@ impl<'a> Face<'a> {
         self.name.unwrap_or_default()
     }
 
+    /// Returns face's family name in a specified BCP 47 `language`, like `ja` or `zh-Hant`.
+    ///
+    /// Falls back to a name in the same primary language, then English and then any name.
+    /// See `Names::find_for_language` for details.
+    #[cfg(feature = "std")]
+    pub fn family_name_for_language(&self, language: &str) -> Option<std::string::String> {
+        self.names()
+            .find_for_language(name_id::FAMILY, language)
+            .and_then(|name| name.to_string())
+    }
+
+    /// Returns face's typographic family name.
+    ///
+    /// Uses *Typographic Family* (16) and falls back to *Family* (1).
+    /// Prefers English names.
+    #[cfg(feature = "std")]
+    pub fn typographic_family_name(&self) -> Option<std::string::String> {
+        self.names().typographic_family_name()
+    }
+
+    /// Returns face's typographic subfamily name.
+    ///
+    /// Uses *Typographic Subfamily* (17) and falls back to *Subfamily* (2).
+    /// Prefers English names.
+    #[cfg(feature = "std")]
+    pub fn typographic_subfamily_name(&self) -> Option<std::string::String> {
+        self.names().typographic_subfamily_name()
+    }
+
+    /// Returns face's weight/width/slope family name.
+    ///
+    /// Uses *WWS Family* (21) and falls back to *Typographic Family* (16) and then *Family* (1),
+    /// which is how DirectWrite groups styles.
+    /// Prefers English names.
+    #[cfg(feature = "std")]
+    pub fn wws_family_name(&self) -> Option<std::string::String> {
+        self.names().wws_family_name()
+    }
+
+    /// Returns face's weight/width/slope subfamily name.
+    ///
+    /// Uses *WWS Subfamily* (22) and falls back to *Typographic Subfamily* (17)
+    /// and then *Subfamily* (2).
+    /// Prefers English names.
+    #[cfg(feature = "std")]
+    pub fn wws_subfamily_name(&self) -> Option<std::string::String> {
+        self.names().wws_subfamily_name()
+    }
+
+    #[cfg(feature = "std")]
+    fn first_name(&self, name_ids: &[u16]) -> Option<std::string::String> {
+        self.names().first_name(name_ids)
+    }
+
     /// Checks that face is marked as *Regular*.
     ///
     /// Returns `false` when OS/2 table is not present.
@@ -939,12 +2331,26 @@ impl<'a> Face<'a> {
         try_opt_or!(self.os_2, false).is_oblique()
     }
 
-    /// Checks that face is marked as *Monospaced*.
+    /// Checks that face is monospaced.
     ///
-    /// Returns `false` when `post` table is not present.
-    #[inline]
+    /// Combines the `post` table *isFixedPitch* flag, the PANOSE proportion
+    /// and horizontal advances, since each of them alone is unreliable.
+    ///
+    /// Zero-width glyphs and marks are ignored. A face where all the remaining glyphs
+    /// have the same advance is monospaced even when it's not marked as such.
+    /// A marked face can also have double-width glyphs, like CJK fonts do.
+    /// Otherwise, a face is not monospaced, even if marked.
+    ///
+    /// Only flags are checked when `hmtx` table is not present.
+    ///
+    /// Checks all glyphs, so prefer `Face::is_monospaced_panose` when a flag is enough.
     pub fn is_monospaced(&self) -> bool {
-        try_opt_or!(self.post, false).is_monospaced()
+        let is_marked = self.post.map(|post| post.is_monospaced()).unwrap_or(false)
+            || self.is_monospaced_panose();
+        let hmtx = try_opt_or!(self.hmtx, is_marked);
+        hmtx.is_monospaced(is_marked, self.number_of_glyphs.get(), |glyph_id| {
+            self.glyph_class(glyph_id) == Some(GlyphClass::Mark)
+        })
     }
 
     /// Checks that face is variable.
@@ -966,20 +2372,252 @@ impl<'a> Face<'a> {
 
     /// Returns face's weight.
     ///
-    /// Returns `Weight::Normal` when OS/2 table is not present.
-    #[inline]
+    /// Uses the current `wght` axis value or the matching `STAT` table value for variable fonts.
+    /// Otherwise, falls back to the OS/2 table and then to `head.macStyle`.
+    ///
+    /// Returns `Weight::Normal` when nothing is found.
     pub fn weight(&self) -> Weight {
-        try_opt_or!(self.os_2, Weight::default()).weight()
-    }
+        if let Some(value) = self.style_axis_value(Tag::from_bytes(b"wght")) {
+            if (1.0..=1000.0).contains(&value) {
+                return Weight::from((value + 0.5) as u16);
+            }
+        }
 
-    /// Returns face's width.
-    ///
-    /// Returns `Width::Normal` when OS/2 table is not present or when value is invalid.
-    #[inline]
+        if let Some(table) = self.os_2 {
+            return table.weight();
+        }
+
+        match self.mac_style() {
+            Some(bits) if bits & 0x01 != 0 => Weight::Bold,
+            _ => Weight::default(),
+        }
+    }
+
+    /// Returns face's width.
+    ///
+    /// Uses the current `wdth` axis value or the matching `STAT` table value for variable fonts.
+    /// Otherwise, falls back to the OS/2 table.
+    ///
+    /// Returns `Width::Normal` when nothing is found or when value is invalid.
     pub fn width(&self) -> Width {
+        if let Some(value) = self.style_axis_value(Tag::from_bytes(b"wdth")) {
+            if value > 0.0 {
+                return Width::from_percent(value);
+            }
+        }
+
         try_opt_or!(self.os_2, Width::default()).width()
     }
 
+    /// Returns face's style.
+    ///
+    /// Uses the current `ital` and `slnt` axis values or the matching `STAT` table values
+    /// for variable fonts.
+    /// Otherwise, falls back to the OS/2 table, then to `head.macStyle`
+    /// and then to a non-zero `post.italicAngle`, which is treated as *Oblique*.
+    pub fn style(&self) -> Style {
+        if let Some(value) = self.style_axis_value(Tag::from_bytes(b"ital")) {
+            if value >= 0.5 {
+                return Style::Italic;
+            }
+        }
+
+        if let Some(value) = self.style_axis_value(Tag::from_bytes(b"slnt")) {
+            if value != 0.0 {
+                return Style::Oblique;
+            }
+        }
+
+        if let Some(table) = self.os_2 {
+            if table.is_oblique() {
+                return Style::Oblique;
+            } else if table.is_italic() {
+                return Style::Italic;
+            }
+        }
+
+        if let Some(bits) = self.mac_style() {
+            if bits & 0x02 != 0 {
+                return Style::Italic;
+            }
+        }
+
+        match self.italic_angle() {
+            Some(angle) if angle != 0.0 => Style::Oblique,
+            _ => Style::Normal,
+        }
+    }
+
+    /// Returns a user-space value of a style axis for the current coordinates.
+    ///
+    /// Uses `fvar` axes and then `STAT` values matching the current coordinates.
+    /// Non-variable fonts are described by the OS/2 table, so `None` is returned for them.
+    fn style_axis_value(&self, tag: Tag) -> Option<f32> {
+        if !self.is_variable() {
+            return None;
+        }
+
+        #[cfg(feature = "variable-fonts")]
+        {
+            self.fvar?.style_axis_value(self.stat, self.unmapped_coordinates.as_slice(), tag)
+        }
+
+        #[cfg(not(feature = "variable-fonts"))]
+        {
+            let _ = tag;
+            None
+        }
+    }
+
+    /// Returns face's raw `usWeightClass` value.
+    ///
+    /// Unlike `Face::weight`, preserves values outside the 100..=900 range.
+    ///
+    /// Returns `None` when OS/2 table is not present.
+    #[inline]
+    pub fn weight_class(&self) -> Option<u16> {
+        self.os_2.map(|table| table.weight_class())
+    }
+
+    /// Returns face's raw `usWidthClass` value.
+    ///
+    /// Returns `None` when OS/2 table is not present.
+    #[inline]
+    pub fn width_class(&self) -> Option<u16> {
+        self.os_2.map(|table| table.width_class())
+    }
+
+    /// Returns face's raw embedding licensing flags (`fsType`).
+    ///
+    /// Returns `None` when OS/2 table is not present.
+    #[inline]
+    pub fn fs_type(&self) -> Option<u16> {
+        self.os_2.map(|table| table.fs_type())
+    }
+
+    /// Returns face's embedding permissions.
+    ///
+    /// Returns `None` when OS/2 table is not present.
+    #[inline]
+    pub fn permissions(&self) -> Option<Permissions> {
+        self.os_2.map(|table| table.permissions())
+    }
+
+    /// Checks that face allows subsetting when embedded.
+    ///
+    /// Returns `true` when OS/2 table is not present.
+    #[inline]
+    pub fn is_subsetting_allowed(&self) -> bool {
+        try_opt_or!(self.os_2, true).is_subsetting_allowed()
+    }
+
+    /// Checks that face allows embedding of outlines.
+    ///
+    /// When `false`, only bitmaps can be embedded.
+    ///
+    /// Returns `true` when OS/2 table is not present.
+    #[inline]
+    pub fn is_outline_embedding_allowed(&self) -> bool {
+        try_opt_or!(self.os_2, true).is_outline_embedding_allowed()
+    }
+
+    /// Returns face's raw font selection flags (`fsSelection`).
+    ///
+    /// Prefer `Face::is_italic`, `Face::is_bold` and other predicates
+    /// unless you need the raw value.
+    ///
+    /// Returns `None` when OS/2 table is not present.
+    #[inline]
+    pub fn fs_selection(&self) -> Option<u16> {
+        self.os_2.map(|table| table.fs_selection())
+    }
+
+    /// Returns face's raw [PANOSE](https://docs.microsoft.com/en-us/typography/opentype/spec/os2#panose)
+    /// classification bytes.
+    ///
+    /// Returns `None` when OS/2 table is not present.
+    #[inline]
+    pub fn panose_bytes(&self) -> Option<[u8; 10]> {
+        self.os_2.map(|table| table.panose_bytes())
+    }
+
+    /// Returns face's [PANOSE](https://docs.microsoft.com/en-us/typography/opentype/spec/os2#panose)
+    /// classification.
+    ///
+    /// Returns `None` when OS/2 table is not present.
+    #[inline]
+    pub fn panose(&self) -> Option<Panose> {
+        self.os_2.map(|table| Panose::from_bytes(table.panose_bytes()))
+    }
+
+    /// Checks that face is marked as *Monospaced* by PANOSE.
+    ///
+    /// Unlike `Face::is_monospaced`, doesn't rely on the `post` table and advances.
+    /// Useful for font matching heuristics.
+    ///
+    /// Returns `false` when OS/2 table is not present.
+    #[inline]
+    pub fn is_monospaced_panose(&self) -> bool {
+        self.panose().map(|panose| panose.is_monospaced()).unwrap_or(false)
+    }
+
+    /// Returns face's vendor ID (`achVendID`).
+    ///
+    /// Returns `None` when OS/2 table is not present.
+    #[inline]
+    pub fn vendor_id(&self) -> Option<Tag> {
+        self.os_2.map(|table| table.vendor_id())
+    }
+
+    /// Returns face's raw Unicode ranges bits (`ulUnicodeRange1..4`).
+    ///
+    /// Returns `None` when OS/2 table is not present.
+    #[inline]
+    pub fn unicode_range_bits(&self) -> Option<[u32; 4]> {
+        self.os_2.map(|table| table.unicode_ranges())
+    }
+
+    /// Returns an iterator over Unicode blocks marked as supported
+    /// by the OS/2 `ulUnicodeRange1..4` fields.
+    ///
+    /// Those flags are set by font authors and may not represent
+    /// the actual coverage. Use `Face::unicode_block_coverage` to check it.
+    ///
+    /// The iterator is empty when OS/2 table is not present.
+    #[inline]
+    pub fn unicode_blocks(&self) -> UnicodeBlocks {
+        UnicodeBlocks::new(self.unicode_range_bits().unwrap_or_default())
+    }
+
+    /// Returns the number of code points in a Unicode block that are mapped
+    /// to a glyph by Unicode `cmap` subtables.
+    ///
+    /// Checks each code point of the block, so it can be slow for large blocks,
+    /// like private use planes or the *Non-Plane 0* block, which covers all supplementary planes.
+    pub fn unicode_block_coverage(&self, block: &UnicodeBlock) -> u32 {
+        let mut count = 0;
+        for code_point in block.start..=block.end {
+            let c = match core::char::from_u32(code_point) {
+                Some(c) => c,
+                None => continue,
+            };
+
+            if self.glyph_index(c).is_some() {
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    /// Returns face's raw code page ranges bits (`ulCodePageRange1..2`).
+    ///
+    /// Returns `None` when OS/2 table is not present or when its version is < 1.
+    #[inline]
+    pub fn code_page_range_bits(&self) -> Option<[u32; 2]> {
+        self.os_2.and_then(|table| table.code_page_ranges())
+    }
+
     /// Returns face's italic angle.
     ///
     /// Returns `None` when `post` table is not present.
@@ -1088,10 +2726,117 @@ impl<'a> Face<'a> {
         })
     }
 
+    /// Returns a Windows face ascender.
+    ///
+    /// This is the `OS/2.usWinAscent` value, which is used by Windows for clipping.
+    ///
+    /// This method is affected by variation axes.
+    ///
+    /// Returns `None` when OS/2 table is not present.
+    #[inline]
+    pub fn windows_ascender(&self) -> Option<u16> {
+        let v = i16::try_from(self.os_2?.win_ascent()).ok()?;
+        u16::try_from(self.apply_metrics_variation(Tag::from_bytes(b"hcla"), v)).ok()
+    }
+
+    /// Returns a Windows face descender.
+    ///
+    /// This is the `OS/2.usWinDescent` value, which is a positive number
+    /// used by Windows for clipping.
+    ///
+    /// This method is affected by variation axes.
+    ///
+    /// Returns `None` when OS/2 table is not present.
+    #[inline]
+    pub fn windows_descender(&self) -> Option<u16> {
+        let v = i16::try_from(self.os_2?.win_descent()).ok()?;
+        u16::try_from(self.apply_metrics_variation(Tag::from_bytes(b"hcld"), v)).ok()
+    }
+
+    /// Returns line spacing metrics using a specified policy.
+    ///
+    /// This method is affected by variation axes.
+    ///
+    /// Returns `None` when `OS/2` table is required by the policy but not present.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ttf_parser::LineMetricsPolicy;
+    ///
+    /// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+    /// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+    /// let metrics = face.line_spacing(LineMetricsPolicy::Hhea).unwrap();
+    /// assert_eq!(metrics.ascender, 1024);
+    /// assert_eq!(metrics.descender, -400);
+    /// // This face doesn't have an `OS/2` table.
+    /// assert_eq!(face.line_spacing(LineMetricsPolicy::Windows), None);
+    /// ```
+    pub fn line_spacing(&self, policy: LineMetricsPolicy) -> Option<LineSpacing> {
+        match policy {
+            LineMetricsPolicy::Auto => Some(LineSpacing {
+                ascender: self.ascender(),
+                descender: self.descender(),
+                line_gap: self.line_gap(),
+            }),
+            LineMetricsPolicy::Hhea => Some(LineSpacing {
+                ascender: hhea::ascender(self.hhea),
+                descender: hhea::descender(self.hhea),
+                line_gap: hhea::line_gap(self.hhea),
+            }),
+            LineMetricsPolicy::Typographic => Some(LineSpacing {
+                ascender: self.typographic_ascender()?,
+                descender: self.typographic_descender()?,
+                line_gap: self.typographic_line_gap()?,
+            }),
+            LineMetricsPolicy::Windows => {
+                let hhea = self.line_spacing(LineMetricsPolicy::Hhea)?;
+                hhea.to_windows(self.windows_ascender()?, self.windows_descender()?)
+            }
+        }
+    }
+
+    /// Returns a caret slope rise.
+    ///
+    /// Together with `Face::caret_slope_run`, describes the slope of the cursor.
+    /// A vertical caret has a rise of 1 and a run of 0.
+    ///
+    /// This method is affected by variation axes.
+    #[inline]
+    pub fn caret_slope_rise(&self) -> i16 {
+        let v = hhea::caret_slope_rise(self.hhea);
+        self.apply_metrics_variation(Tag::from_bytes(b"hcrs"), v)
+    }
+
+    /// Returns a caret slope run.
+    ///
+    /// Non-zero for italic and oblique fonts.
+    ///
+    /// This method is affected by variation axes.
+    #[inline]
+    pub fn caret_slope_run(&self) -> i16 {
+        let v = hhea::caret_slope_run(self.hhea);
+        self.apply_metrics_variation(Tag::from_bytes(b"hcrn"), v)
+    }
+
+    /// Returns a horizontal shift of a slanted caret.
+    ///
+    /// Should be 0 for fonts without slanted glyphs.
+    ///
+    /// This method is affected by variation axes.
+    #[inline]
+    pub fn caret_offset(&self) -> i16 {
+        let v = hhea::caret_offset(self.hhea);
+        self.apply_metrics_variation(Tag::from_bytes(b"hcof"), v)
+    }
+
     // TODO: does this affected by USE_TYPO_METRICS?
 
     /// Returns a vertical face ascender.
     ///
+    /// This is the `vhea.vertTypoAscender` value, which is the distance
+    /// from the vertical center baseline to the right of the design space.
+    ///
     /// This method is affected by variation axes.
     #[inline]
     pub fn vertical_ascender(&self) -> Option<i16> {
@@ -1102,6 +2847,9 @@ impl<'a> Face<'a> {
 
     /// Returns a vertical face descender.
     ///
+    /// This is the `vhea.vertTypoDescender` value, which is the distance
+    /// from the vertical center baseline to the left of the design space.
+    ///
     /// This method is affected by variation axes.
     #[inline]
     pub fn vertical_descender(&self) -> Option<i16> {
@@ -1128,6 +2876,38 @@ impl<'a> Face<'a> {
             .map(|v| self.apply_metrics_variation(Tag::from_bytes(b"vlgp"), v))
     }
 
+    /// Returns a vertical caret slope rise.
+    ///
+    /// A horizontal caret has a rise of 0 and a run of 1.
+    ///
+    /// This method is affected by variation axes.
+    #[inline]
+    pub fn vertical_caret_slope_rise(&self) -> Option<i16> {
+        self.vhea
+            .map(vhea::caret_slope_rise)
+            .map(|v| self.apply_metrics_variation(Tag::from_bytes(b"vcrs"), v))
+    }
+
+    /// Returns a vertical caret slope run.
+    ///
+    /// This method is affected by variation axes.
+    #[inline]
+    pub fn vertical_caret_slope_run(&self) -> Option<i16> {
+        self.vhea
+            .map(vhea::caret_slope_run)
+            .map(|v| self.apply_metrics_variation(Tag::from_bytes(b"vcrn"), v))
+    }
+
+    /// Returns a vertical shift of a slanted caret.
+    ///
+    /// This method is affected by variation axes.
+    #[inline]
+    pub fn vertical_caret_offset(&self) -> Option<i16> {
+        self.vhea
+            .map(vhea::caret_offset)
+            .map(|v| self.apply_metrics_variation(Tag::from_bytes(b"vcof"), v))
+    }
+
     /// Returns face's units per EM.
     ///
     /// Returns `None` when value is not in a 16..=16384 range.
@@ -1136,6 +2916,55 @@ impl<'a> Face<'a> {
         head::units_per_em(self.head)
     }
 
+    /// Returns face's revision set by the font manufacturer.
+    #[inline]
+    pub fn font_revision(&self) -> Option<f32> {
+        head::font_revision(self.head)
+    }
+
+    /// Returns face's creation time as a Unix timestamp.
+    ///
+    /// The `head` table stores time since 1904-01-01, which is converted to 1970-01-01.
+    #[inline]
+    pub fn created_timestamp(&self) -> Option<i64> {
+        head::created(self.head)
+    }
+
+    /// Returns face's modification time as a Unix timestamp.
+    ///
+    /// The `head` table stores time since 1904-01-01, which is converted to 1970-01-01.
+    #[inline]
+    pub fn modified_timestamp(&self) -> Option<i64> {
+        head::modified(self.head)
+    }
+
+    /// Returns raw `head` table flags.
+    #[inline]
+    pub fn head_flags(&self) -> Option<u16> {
+        head::flags(self.head)
+    }
+
+    /// Returns raw `head` table `macStyle` bits.
+    ///
+    /// Bit 0 is bold and bit 1 is italic.
+    /// Prefer `Face::is_bold` and `Face::is_italic`, which use the `OS/2` table.
+    #[inline]
+    pub fn mac_style(&self) -> Option<u16> {
+        head::mac_style(self.head)
+    }
+
+    /// Returns the smallest readable size in pixels.
+    #[inline]
+    pub fn lowest_recommended_ppem(&self) -> Option<u16> {
+        head::lowest_rec_ppem(self.head)
+    }
+
+    /// Returns the `loca` table offsets format.
+    #[inline]
+    pub fn index_to_location_format(&self) -> Option<IndexToLocationFormat> {
+        head::index_to_loc_format(self.head)
+    }
+
     /// Returns face's x height.
     ///
     /// This method is affected by variation axes.
@@ -1160,6 +2989,18 @@ impl<'a> Face<'a> {
             .map(|v| self.apply_metrics_variation(Tag::from_bytes(b"cpht"), v))
     }
 
+    /// Returns face's optical point size range as `(lower, upper)` in points.
+    ///
+    /// The face is intended to be used for sizes greater than or equal to the lower value
+    /// and less than the upper one.
+    ///
+    /// Returns `None` when OS/2 table is not present or when its version is < 5.
+    #[inline]
+    pub fn optical_point_size_range(&self) -> Option<(f32, f32)> {
+        let (lower, upper) = self.os_2?.optical_point_sizes()?;
+        Some((f32::from(lower) / 20.0, f32::from(upper) / 20.0))
+    }
+
     /// Returns face's underline metrics.
     ///
     /// This method is affected by variation axes.
@@ -1177,52 +3018,191 @@ impl<'a> Face<'a> {
         Some(metrics)
     }
 
-    /// Returns face's strikeout metrics.
+    /// Returns face's underline position.
+    ///
+    /// A shorthand for `Face::underline_metrics().position`.
     ///
     /// This method is affected by variation axes.
     ///
-    /// Returns `None` when OS/2 table is not present.
+    /// Returns `None` when `post` table is not present.
     #[inline]
-    pub fn strikeout_metrics(&self) -> Option<LineMetrics> {
-        let mut metrics = self.os_2?.strikeout_metrics();
-
-        if self.is_variable() {
-            self.apply_metrics_variation_to(Tag::from_bytes(b"stro"), &mut metrics.position);
-            self.apply_metrics_variation_to(Tag::from_bytes(b"strs"), &mut metrics.thickness);
-        }
-
-        Some(metrics)
+    pub fn underline_position(&self) -> Option<i16> {
+        self.underline_metrics().map(|metrics| metrics.position)
     }
 
-    /// Returns face's subscript metrics.
+    /// Returns face's underline thickness.
+    ///
+    /// A shorthand for `Face::underline_metrics().thickness`.
     ///
     /// This method is affected by variation axes.
     ///
-    /// Returns `None` when OS/2 table is not present.
+    /// Returns `None` when `post` table is not present.
     #[inline]
-    pub fn subscript_metrics(&self) -> Option<ScriptMetrics> {
-        let mut metrics = self.os_2?.subscript_metrics();
-
-        if self.is_variable() {
-            self.apply_metrics_variation_to(Tag::from_bytes(b"sbxs"), &mut metrics.x_size);
-            self.apply_metrics_variation_to(Tag::from_bytes(b"sbys"), &mut metrics.y_size);
-            self.apply_metrics_variation_to(Tag::from_bytes(b"sbxo"), &mut metrics.x_offset);
-            self.apply_metrics_variation_to(Tag::from_bytes(b"sbyo"), &mut metrics.y_offset);
-        }
+    pub fn underline_thickness(&self) -> Option<i16> {
+        self.underline_metrics().map(|metrics| metrics.thickness)
+    }
 
-        Some(metrics)
+    /// Returns a glyph's linear threshold from the
+    /// [Linear Threshold Table](https://docs.microsoft.com/en-us/typography/opentype/spec/ltsh).
+    ///
+    /// Starting from this ppem, the glyph's hinted advance scales linearly.
+    /// `1` means that the glyph always scales linearly.
+    ///
+    /// Returns `None` when `LTSH` table is not present or the glyph is out of range.
+    #[inline]
+    pub fn glyph_linear_threshold(&self, glyph_id: GlyphId) -> Option<u8> {
+        self.ltsh.and_then(|table| table.linear_threshold(glyph_id))
     }
 
-    /// Returns face's superscript metrics.
+    /// Returns `yMax` and `yMin` in pixels for a specified `pixels_per_em` according to
+    /// [Vertical Device Metrics Table](https://docs.microsoft.com/en-us/typography/opentype/spec/vdmx).
     ///
-    /// This method is affected by variation axes.
+    /// Those are the values used by GDI to compute a line height.
+    /// Only the 1:1 aspect ratio is supported.
     ///
-    /// Returns `None` when OS/2 table is not present.
+    /// Returns `None` when `VDMX` table is not present or has no record for this size.
     #[inline]
-    pub fn superscript_metrics(&self) -> Option<ScriptMetrics> {
-        let mut metrics = self.os_2?.superscript_metrics();
+    pub fn vdmx_metrics(&self, pixels_per_em: u16) -> Option<(i16, i16)> {
+        self.vdmx.and_then(|table| table.metrics(pixels_per_em))
+    }
 
-        if self.is_variable() {
+    /// Returns a face scaled to `pixels_per_em`.
+    ///
+    /// Returns `None` when `units_per_em` is invalid.
+    #[inline]
+    pub fn scaled(&self, pixels_per_em: f32) -> Option<ScaledFace<'_, 'a>> {
+        ScaledFace::new(self, pixels_per_em)
+    }
+
+    /// Returns face's metrics scaled to `pixels_per_em` at the specified variation coordinates.
+    ///
+    /// `coords` are normalized coordinates in the `fvar` axes order.
+    /// Missing coordinates are set to default. Ignored for non-variable fonts.
+    ///
+    /// When present, `VDMX` and `hdmx` tables are used for the ascender/descender
+    /// and the maximum advance respectively.
+    ///
+    /// Returns `None` when `units_per_em` is invalid.
+    pub fn metrics_at(
+        &self,
+        pixels_per_em: u16,
+        coords: &[NormalizedCoordinate],
+    ) -> Option<ScaledMetrics> {
+        #[cfg(feature = "variable-fonts")]
+        let face = {
+            let mut face = self.clone();
+            for (i, c) in face.coordinates.as_mut_slice().iter_mut().enumerate() {
+                *c = coords.get(i).cloned().unwrap_or_default();
+            }
+
+            face
+        };
+
+        #[cfg(not(feature = "variable-fonts"))]
+        let face = {
+            let _ = coords;
+            self
+        };
+
+        let units_per_em = face.units_per_em()?;
+        let scale = f32::from(pixels_per_em) / f32::from(units_per_em);
+        let scale_i16 = |v: i16| f32::from(v) * scale;
+
+        let (ascender, descender) = match face.vdmx_metrics(pixels_per_em) {
+            Some((y_max, y_min)) => (f32::from(y_max), f32::from(y_min)),
+            None => (scale_i16(face.ascender()), scale_i16(face.descender())),
+        };
+
+        let max_advance = match face.hdmx.and_then(|t| t.max_advance(pixels_per_em)) {
+            Some(v) => f32::from(v),
+            None => f32::from(hhea::advance_width_max(face.hhea)) * scale,
+        };
+
+        let underline = face.underline_metrics();
+
+        Some(ScaledMetrics {
+            pixels_per_em,
+            ascender,
+            descender,
+            line_gap: scale_i16(face.line_gap()),
+            underline_position: underline.map(|m| scale_i16(m.position)),
+            underline_thickness: underline.map(|m| scale_i16(m.thickness)),
+            x_height: face.x_height().map(scale_i16),
+            capital_height: face.capital_height().map(scale_i16),
+            max_advance,
+        })
+    }
+
+    /// Returns face's strikeout metrics.
+    ///
+    /// This method is affected by variation axes.
+    ///
+    /// Returns `None` when OS/2 table is not present.
+    #[inline]
+    pub fn strikeout_metrics(&self) -> Option<LineMetrics> {
+        let mut metrics = self.os_2?.strikeout_metrics();
+
+        if self.is_variable() {
+            self.apply_metrics_variation_to(Tag::from_bytes(b"stro"), &mut metrics.position);
+            self.apply_metrics_variation_to(Tag::from_bytes(b"strs"), &mut metrics.thickness);
+        }
+
+        Some(metrics)
+    }
+
+    /// Returns face's strikeout position.
+    ///
+    /// A shorthand for `Face::strikeout_metrics().position`.
+    ///
+    /// This method is affected by variation axes.
+    ///
+    /// Returns `None` when OS/2 table is not present.
+    #[inline]
+    pub fn strikeout_position(&self) -> Option<i16> {
+        self.strikeout_metrics().map(|metrics| metrics.position)
+    }
+
+    /// Returns face's strikeout thickness.
+    ///
+    /// A shorthand for `Face::strikeout_metrics().thickness`.
+    ///
+    /// This method is affected by variation axes.
+    ///
+    /// Returns `None` when OS/2 table is not present.
+    #[inline]
+    pub fn strikeout_thickness(&self) -> Option<i16> {
+        self.strikeout_metrics().map(|metrics| metrics.thickness)
+    }
+
+    /// Returns face's subscript metrics.
+    ///
+    /// This method is affected by variation axes.
+    ///
+    /// Returns `None` when OS/2 table is not present.
+    #[inline]
+    pub fn subscript_metrics(&self) -> Option<ScriptMetrics> {
+        let mut metrics = self.os_2?.subscript_metrics();
+
+        if self.is_variable() {
+            self.apply_metrics_variation_to(Tag::from_bytes(b"sbxs"), &mut metrics.x_size);
+            self.apply_metrics_variation_to(Tag::from_bytes(b"sbys"), &mut metrics.y_size);
+            self.apply_metrics_variation_to(Tag::from_bytes(b"sbxo"), &mut metrics.x_offset);
+            self.apply_metrics_variation_to(Tag::from_bytes(b"sbyo"), &mut metrics.y_offset);
+        }
+
+        Some(metrics)
+    }
+
+    /// Returns face's superscript metrics.
+    ///
+    /// This method is affected by variation axes.
+    ///
+    /// Returns `None` when OS/2 table is not present.
+    #[inline]
+    pub fn superscript_metrics(&self) -> Option<ScriptMetrics> {
+        let mut metrics = self.os_2?.superscript_metrics();
+
+        if self.is_variable() {
             self.apply_metrics_variation_to(Tag::from_bytes(b"spxs"), &mut metrics.x_size);
             self.apply_metrics_variation_to(Tag::from_bytes(b"spys"), &mut metrics.y_size);
             self.apply_metrics_variation_to(Tag::from_bytes(b"spxo"), &mut metrics.x_offset);
@@ -1242,6 +3222,15 @@ impl<'a> Face<'a> {
         self.number_of_glyphs.get()
     }
 
+    /// Returns face's [Maximum Profile](https://docs.microsoft.com/en-us/typography/opentype/spec/maxp)
+    /// values.
+    ///
+    /// Returns `None` for `maxp` table version 0.5, which is used by `CFF`-based fonts.
+    #[inline]
+    pub fn maximum_profile(&self) -> Option<MaximumProfile> {
+        maxp::parse_profile(self.maxp)
+    }
+
     /// Returns an iterator over
     /// [character to glyph index mapping](https://docs.microsoft.com/en-us/typography/opentype/spec/cmap).
     ///
@@ -1262,19 +3251,97 @@ impl<'a> Face<'a> {
     /// If you need a more low-level control, prefer `Face::character_mapping_subtables`.
     #[inline]
     pub fn glyph_index(&self, c: char) -> Option<GlyphId> {
-        for encoding in self.character_mapping_subtables() {
-            if !encoding.is_unicode() {
+        if let Some(id) = self.unicode_cmap?.glyph_index(u32::from(c)) {
+            return Some(id);
+        }
+
+        if !self.has_extra_unicode_cmaps {
+            return None;
+        }
+
+        let mut subtables = self.character_mapping_subtables().filter(|s| s.is_unicode()).skip(1);
+        subtables.find_map(|encoding| encoding.glyph_index(u32::from(c)))
+    }
+
+    /// Resolves Glyph IDs for all characters in a string.
+    ///
+    /// Same as calling `Face::glyph_index` for each character,
+    /// but repeated characters are resolved once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ttf_parser::GlyphId;
+    ///
+    /// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+    /// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+    /// let glyphs: Vec<_> = face.glyph_indices("AA\u{D7FF}").collect();
+    /// assert_eq!(glyphs, vec![Some(GlyphId(1)), Some(GlyphId(1)), None]);
+    /// ```
+    pub fn glyph_indices<'b>(&'b self, text: &'b str) -> impl Iterator<Item = Option<GlyphId>> + 'b {
+        let mut last: Option<(char, Option<GlyphId>)> = None;
+        text.chars().map(move |c| {
+            match last {
+                Some((prev, id)) if prev == c => id,
+                _ => {
+                    let id = self.glyph_index(c);
+                    last = Some((c, id));
+                    id
+                }
+            }
+        })
+    }
+
+    /// Checks that face has glyphs for all characters in a string.
+    ///
+    /// See `Face::first_unsupported_char` for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+    /// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+    /// assert!(face.supports_text("AA\u{FE0F}\n"));
+    /// assert!(!face.supports_text("AB"));
+    /// ```
+    #[inline]
+    pub fn supports_text(&self, text: &str) -> bool {
+        self.first_unsupported_char(text).is_none()
+    }
+
+    /// Returns the first character in a string that face has no glyph for.
+    ///
+    /// Control characters, variation selectors and zero-width joiners are skipped,
+    /// since they are not rendered. When a variation sequence is not listed in `cmap`,
+    /// the base character glyph is used instead, so only the base character has to be mapped.
+    ///
+    /// Repeated characters are resolved once.
+    pub fn first_unsupported_char(&self, text: &str) -> Option<char> {
+        let mut last_supported = None;
+        for c in text.chars() {
+            if last_supported == Some(c) || is_ignorable_char(c) {
                 continue;
             }
 
-            if let Some(id) = encoding.glyph_index(u32::from(c)) {
-                return Some(id);
+            if self.glyph_index(c).is_none() {
+                return Some(c);
             }
+
+            last_supported = Some(c);
         }
 
         None
     }
 
+    /// Resolves glyphs for digits and common number punctuation of a specified numbering system.
+    ///
+    /// Arabic-Indic numbering systems use their own separators and percent sign.
+    /// Fullwidth digits use fullwidth punctuation.
+    /// Other numbering systems use ASCII punctuation.
+    pub fn digit_glyphs(&self, numbering_system: NumberingSystem) -> DigitGlyphs {
+        DigitGlyphs::resolve(numbering_system, |c| self.glyph_index(c))
+    }
+
     /// Resolves a variation of a Glyph ID from two code points.
     ///
     /// Implemented according to
@@ -1297,6 +3364,8 @@ impl<'a> Face<'a> {
 
     /// Returns glyph's horizontal advance.
     ///
+    /// Glyphs after the last `hhea.numberOfHMetrics` record share its advance.
+    ///
     /// This method is affected by variation axes.
     #[inline]
     pub fn glyph_hor_advance(&self, glyph_id: GlyphId) -> Option<u16> {
@@ -1305,11 +3374,14 @@ impl<'a> Face<'a> {
             let mut advance = self.hmtx?.advance(glyph_id)? as f32;
 
             if self.is_variable() {
-                // Ignore variation offset when `hvar` is not set.
+                let rounding = self.variation_rounding;
                 if let Some(hvar_data) = self.hvar {
-                    // We can't use `round()` in `no_std`, so this is the next best thing.
-                    advance +=
-                        hvar::glyph_advance_offset(hvar_data, glyph_id, self.coords())? + 0.5;
+                    let offset = hvar::glyph_advance_offset(hvar_data, glyph_id, self.coords())?;
+                    advance += rounding.round_to_integer(offset);
+                } else if let Some(deltas) = self.phantom_points_deltas(glyph_id) {
+                    // Without `hvar`, the advance is the distance
+                    // between the first two phantom points.
+                    advance += rounding.round_to_integer(deltas[1].0 - deltas[0].0);
                 }
             }
 
@@ -1324,6 +3396,8 @@ impl<'a> Face<'a> {
 
     /// Returns glyph's vertical advance.
     ///
+    /// Glyphs after the last `vhea.numOfLongVerMetrics` record share its advance.
+    ///
     /// This method is affected by variation axes.
     #[inline]
     pub fn glyph_ver_advance(&self, glyph_id: GlyphId) -> Option<u16> {
@@ -1332,11 +3406,14 @@ impl<'a> Face<'a> {
             let mut advance = self.vmtx?.advance(glyph_id)? as f32;
 
             if self.is_variable() {
-                // Ignore variation offset when `vvar` is not set.
+                let rounding = self.variation_rounding;
                 if let Some(vvar_data) = self.vvar {
-                    // We can't use `round()` in `no_std`, so this is the next best thing.
-                    advance +=
-                        hvar::glyph_advance_offset(vvar_data, glyph_id, self.coords())? + 0.5;
+                    let offset = hvar::glyph_advance_offset(vvar_data, glyph_id, self.coords())?;
+                    advance += rounding.round_to_integer(offset);
+                } else if let Some(deltas) = self.phantom_points_deltas(glyph_id) {
+                    // Without `vvar`, the advance is the distance
+                    // between the last two phantom points.
+                    advance += rounding.round_to_integer(deltas[2].1 - deltas[3].1);
                 }
             }
 
@@ -1351,6 +3428,9 @@ impl<'a> Face<'a> {
 
     /// Returns glyph's horizontal side bearing.
     ///
+    /// Glyphs after the last `hhea.numberOfHMetrics` record use
+    /// the trailing left side bearings array.
+    ///
     /// This method is affected by variation axes.
     #[inline]
     pub fn glyph_hor_side_bearing(&self, glyph_id: GlyphId) -> Option<i16> {
@@ -1361,9 +3441,9 @@ impl<'a> Face<'a> {
             if self.is_variable() {
                 // Ignore variation offset when `hvar` is not set.
                 if let Some(hvar_data) = self.hvar {
-                    // We can't use `round()` in `no_std`, so this is the next best thing.
-                    bearing +=
-                        hvar::glyph_side_bearing_offset(hvar_data, glyph_id, self.coords())? + 0.5;
+                    let offset =
+                        hvar::glyph_side_bearing_offset(hvar_data, glyph_id, self.coords())?;
+                    bearing += self.variation_rounding.round_to_integer(offset);
                 }
             }
 
@@ -1376,7 +3456,10 @@ impl<'a> Face<'a> {
         }
     }
 
-    /// Returns glyph's vertical side bearing.
+    /// Returns glyph's vertical (top) side bearing.
+    ///
+    /// Glyphs after the last `vhea.numOfLongVerMetrics` record use
+    /// the trailing top side bearings array.
     ///
     /// This method is affected by variation axes.
     #[inline]
@@ -1388,9 +3471,9 @@ impl<'a> Face<'a> {
             if self.is_variable() {
                 // Ignore variation offset when `vvar` is not set.
                 if let Some(vvar_data) = self.vvar {
-                    // We can't use `round()` in `no_std`, so this is the next best thing.
-                    bearing +=
-                        hvar::glyph_side_bearing_offset(vvar_data, glyph_id, self.coords())? + 0.5;
+                    let offset =
+                        hvar::glyph_side_bearing_offset(vvar_data, glyph_id, self.coords())?;
+                    bearing += self.variation_rounding.round_to_integer(offset);
                 }
             }
 
@@ -1405,18 +3488,70 @@ impl<'a> Face<'a> {
 
     /// Returns glyph's vertical origin according to
     /// [Vertical Origin Table](https://docs.microsoft.com/en-us/typography/opentype/spec/vorg).
+    ///
+    /// Glyphs without an explicit origin use the table's default one.
+    ///
+    /// This method is affected by variation axes.
     pub fn glyph_y_origin(&self, glyph_id: GlyphId) -> Option<i16> {
-        self.vorg.map(|vorg| vorg.glyph_y_origin(glyph_id))
+        #[cfg(feature = "variable-fonts")]
+        {
+            let mut origin = self.vorg?.glyph_y_origin(glyph_id) as f32;
+
+            if self.is_variable() {
+                // Ignore variation offset when `vvar` is not set.
+                if let Some(vvar_data) = self.vvar {
+                    // A vertical origin mapping is optional.
+                    if let Some(offset) =
+                        hvar::glyph_y_origin_offset(vvar_data, glyph_id, self.coords())
+                    {
+                        origin += self.variation_rounding.round_to_integer(offset);
+                    }
+                }
+            }
+
+            i16::try_num_from(origin)
+        }
+
+        #[cfg(not(feature = "variable-fonts"))]
+        {
+            self.vorg.map(|vorg| vorg.glyph_y_origin(glyph_id))
+        }
+    }
+
+    /// Returns glyph's vertical origin Y coordinate.
+    ///
+    /// Uses the `VORG` table when present, like `glyph_y_origin()`.
+    /// Otherwise, the origin is the glyph's top side bearing from `vmtx`
+    /// plus its bounding box top, which works for both TrueType and CFF faces.
+    /// Falls back to the face ascender when `vmtx` is not present
+    /// or when a glyph has no outline.
+    ///
+    /// This method is affected by variation axes.
+    pub fn glyph_ver_origin(&self, glyph_id: GlyphId) -> i16 {
+        if let Some(origin) = self.glyph_y_origin(glyph_id) {
+            return origin;
+        }
+
+        if let Some(bearing) = self.glyph_ver_side_bearing(glyph_id) {
+            if let Some(bbox) = self.glyph_bounding_box(glyph_id) {
+                if let Some(origin) = bearing.checked_add(bbox.y_max) {
+                    return origin;
+                }
+            }
+        }
+
+        self.ascender()
     }
 
     /// Returns glyph's name.
     ///
-    /// Uses the `post` and `CFF` tables as sources.
+    /// Uses the `post`, `CFF` and `Zapf` tables as sources.
     ///
     /// Returns `None` when no name is associated with a `glyph`.
     #[inline]
     pub fn glyph_name(&self, glyph_id: GlyphId) -> Option<&str> {
-        if let Some(name) = self.post.and_then(|post| post.glyph_name(glyph_id)) {
+        let number_of_glyphs = self.number_of_glyphs.get();
+        if let Some(name) = self.post.and_then(|post| post.glyph_name(glyph_id, number_of_glyphs)) {
             return Some(name);
         }
 
@@ -1428,7 +3563,162 @@ impl<'a> Face<'a> {
             return Some(name);
         }
 
-        None
+        self.zapf.and_then(|zapf| zapf.glyph_info(glyph_id)?.postscript_name())
+    }
+
+    /// Returns glyph's name or a synthesized `gidN` name.
+    ///
+    /// Same as `Face::glyph_name`, but glyphs without a name are named
+    /// using their ID, like `gid42`, so any valid glyph has a name.
+    ///
+    /// Returns `None` when `glyph_id` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+    /// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+    /// // This face doesn't have glyph names.
+    /// assert_eq!(face.glyph_name_or_gid(ttf_parser::GlyphId(1)).unwrap(), "gid1");
+    /// // And has only two glyphs.
+    /// assert_eq!(face.glyph_name_or_gid(ttf_parser::GlyphId(2)), None);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn glyph_name_or_gid(&self, glyph_id: GlyphId) -> Option<std::borrow::Cow<'_, str>> {
+        if glyph_id.0 >= self.number_of_glyphs.get() {
+            return None;
+        }
+
+        match self.glyph_name(glyph_id) {
+            Some(name) => Some(std::borrow::Cow::Borrowed(name)),
+            None => Some(std::borrow::Cow::Owned(format!("gid{}", glyph_id.0))),
+        }
+    }
+
+    /// Returns Unicode strings for glyphs.
+    ///
+    /// Intended for building a `ToUnicode` CMap when embedding a face into a PDF.
+    ///
+    /// Glyphs are looked up in Unicode `cmap` subtables first.
+    /// When a glyph is mapped from multiple code points, the smallest one is used.
+    /// Other glyphs are resolved using the `Zapf` table Unicode values and then
+    /// using their names and the Adobe Glyph List rules,
+    /// so a ligature named `f_f_i` is mapped to `ffi`.
+    /// Only the standard Macintosh glyph names and a few common ligatures are resolved
+    /// from the list itself, while `uniXXXX` and `uXXXX[XX]` names are always supported.
+    ///
+    /// Returns pairs sorted by glyph ID. Glyphs without a mapping are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ttf_parser::{GlyphId, GlyphSet};
+    ///
+    /// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+    /// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+    /// let glyphs: GlyphSet = [GlyphId(0), GlyphId(1)].iter().cloned().collect();
+    /// assert_eq!(face.glyphs_to_unicode(&glyphs), vec![(GlyphId(1), String::from("A"))]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn glyphs_to_unicode(
+        &self,
+        glyphs: &GlyphSet,
+    ) -> std::vec::Vec<(GlyphId, std::string::String)> {
+        let code_points = smallest_code_points(
+            self.character_mapping_subtables(),
+            glyphs,
+            self.number_of_glyphs.get(),
+        );
+
+        glyphs.iter().filter_map(|id| {
+            let c = code_points.get(usize::from(id.0)).and_then(|c| *c);
+            let text = match c.and_then(core::char::from_u32) {
+                Some(c) => {
+                    let mut text = std::string::String::new();
+                    text.push(c);
+                    text
+                }
+                None => match self.zapf_unicode(id) {
+                    Some(text) => text,
+                    None => agl::glyph_name_to_unicode(self.glyph_name(id)?)?,
+                },
+            };
+
+            Some((id, text))
+        }).collect()
+    }
+
+    /// Returns glyph's text from the `Zapf` table Unicode values.
+    #[cfg(feature = "std")]
+    fn zapf_unicode(&self, glyph_id: GlyphId) -> Option<std::string::String> {
+        self.zapf?.glyph_info(glyph_id)?.text()
+    }
+
+    /// Returns glyph's character code.
+    ///
+    /// Only Apple composite fonts with a `post` table version 4.0 have them.
+    ///
+    /// Returns `None` when no character code is associated with a `glyph`.
+    #[inline]
+    pub fn glyph_char_code(&self, glyph_id: GlyphId) -> Option<u16> {
+        self.post.and_then(|post| post.glyph_char_code(glyph_id))
+    }
+
+    /// Returns glyph's CID.
+    ///
+    /// Only CID-keyed `CFF` fonts have CIDs. Returns `None` otherwise.
+    #[inline]
+    pub fn glyph_cid(&self, glyph_id: GlyphId) -> Option<u16> {
+        self.cff1.as_ref().and_then(|cff1| cff1::glyph_cid(cff1, glyph_id))
+    }
+
+    /// Returns a character collection of a CID-keyed `CFF` font.
+    ///
+    /// Returns `None` when font is not CID-keyed or `ROS` is malformed.
+    #[inline]
+    pub fn ros(&self) -> Option<Ros<'a>> {
+        self.cff1.as_ref().and_then(|cff1| cff1::ros(cff1))
+    }
+
+    /// Returns a glyph ID by a character code using the `CFF` Encoding.
+    ///
+    /// Unlike `glyph_index`, doesn't use the `cmap` table, which makes it useful
+    /// for legacy fonts with a missing or broken `cmap`.
+    ///
+    /// Returns `None` when font is not `CFF`-based or is CID-keyed.
+    #[inline]
+    pub fn glyph_index_by_code(&self, code: u8) -> Option<GlyphId> {
+        self.cff1.as_ref().and_then(|cff1| cff1::glyph_index_by_code(cff1, code))
+    }
+
+    /// Returns glyph's `CFF` Private DICT values.
+    ///
+    /// Contains default and nominal widths, alignment zones and stem widths,
+    /// which are useful for hinting and autohinting.
+    /// In CID-keyed fonts, each glyph can have a different Private DICT.
+    ///
+    /// Returns `None` when font is not `CFF`-based.
+    #[inline]
+    pub fn cff_private_dict(&self, glyph_id: GlyphId) -> Option<PrivateDict> {
+        self.cff1.as_ref().and_then(|cff1| cff1::private_dict(cff1, glyph_id))
+    }
+
+    /// Returns a glyph ID by its name.
+    ///
+    /// Uses the `post`, `CFF` and `Zapf` tables as sources.
+    ///
+    /// Returns `None` when no glyph with such name exists.
+    pub fn glyph_index_by_name(&self, name: &str) -> Option<GlyphId> {
+        let number_of_glyphs = self.number_of_glyphs.get();
+        if let Some(id) = self.post.and_then(|post| post.glyph_index_by_name(name, number_of_glyphs)) {
+            return Some(id);
+        }
+
+        if let Some(id) = self.cff1.as_ref().and_then(|cff1| cff1::glyph_index_by_name(cff1, name)) {
+            return Some(id);
+        }
+
+        self.zapf.and_then(|zapf| zapf.glyph_index_by_name(name))
     }
 
     /// Checks that face has
@@ -1479,30 +3769,579 @@ impl<'a> Face<'a> {
         })
     }
 
-    /// Returns a iterator over kerning subtables.
+    /// Returns a graph of lookups referenced by other lookups in the
+    /// [Glyph Substitution Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gsub).
+    #[cfg(feature = "std")]
+    pub fn gsub_lookup_graph(&self) -> Option<LookupGraph> {
+        self.gsub.map(|gsub| gsub.lookup_graph(LookupListKind::Substitution, self.limits))
+    }
+
+    /// Returns a graph of lookups referenced by other lookups in the
+    /// [Glyph Positioning Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos).
+    #[cfg(feature = "std")]
+    pub fn gpos_lookup_graph(&self) -> Option<LookupGraph> {
+        self.gpos.map(|gpos| gpos.lookup_graph(LookupListKind::Positioning, self.limits))
+    }
+
+    /// Returns per-lookup sets of glyphs that lookups in the
+    /// [Glyph Substitution Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gsub)
+    /// can start matching at.
     ///
-    /// Supports both
-    /// [OpenType](https://docs.microsoft.com/en-us/typography/opentype/spec/kern)
-    /// and
-    /// [Apple Advanced Typography](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6kern.html)
-    /// variants.
-    pub fn kerning_subtables(&self) -> kern::Subtables {
-        self.kern.unwrap_or_default()
+    /// Building is relatively expensive, so the result should be cached by the caller.
+    #[cfg(feature = "std")]
+    pub fn gsub_lookup_coverages(&self) -> Option<LookupCoverages> {
+        self.gsub.map(|gsub| gsub.lookup_coverages(LookupListKind::Substitution, self.limits))
     }
 
-    /// Outlines a glyph and returns its tight bounding box.
+    /// Returns per-lookup sets of glyphs that lookups in the
+    /// [Glyph Positioning Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos)
+    /// can start matching at.
     ///
-    /// **Warning**: since `ttf-parser` is a pull parser,
-    /// `OutlineBuilder` will emit segments even when outline is partially malformed.
-    /// You must check `outline_glyph()` result before using
-    /// `OutlineBuilder`'s output.
+    /// Building is relatively expensive, so the result should be cached by the caller.
+    #[cfg(feature = "std")]
+    pub fn gpos_lookup_coverages(&self) -> Option<LookupCoverages> {
+        self.gpos.map(|gpos| gpos.lookup_coverages(LookupListKind::Positioning, self.limits))
+    }
+
+    /// Returns an index of the `GSUB` feature variation record
+    /// that matches the current variation coordinates.
     ///
-    /// `gvar`, `glyf`, `CFF` and `CFF2` tables are supported.
-    /// And they will be accesses in this specific order.
+    /// Returns `None` when face doesn't have `GSUB` feature variations
+    /// or none of the records matches.
+    #[cfg(feature = "variable-fonts")]
+    #[inline]
+    pub fn gsub_feature_variation_index(&self) -> Option<u32> {
+        self.gsub?.feature_variation_index(self.coords())
+    }
+
+    /// Returns a language system of the
+    /// [Glyph Substitution Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gsub)
+    /// that should be used for the specified scripts and language.
     ///
-    /// This method is affected by variation axes.
+    /// Follows the OpenType fallback rules: `scripts` are tried in order,
+    /// followed by the `DFLT` script and then the first script in the font.
+    /// When the selected script has no `language` system, the default one is used.
     ///
-    /// Returns `None` when glyph has no outline or on error.
+    /// `scripts` are usually tags returned by `opentype_script_tags`.
+    ///
+    /// Returns `None` when there is no `GSUB` table or no suitable language system.
+    #[inline]
+    pub fn gsub_lang_sys(&self, scripts: &[Tag], language: Option<Tag>) -> Option<LangSysTable<'a>> {
+        self.gsub?.lang_sys(scripts, language)
+    }
+
+    /// Returns a language system of the
+    /// [Glyph Positioning Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos)
+    /// that should be used for the specified scripts and language.
+    ///
+    /// Uses the same fallback rules as `gsub_lang_sys`.
+    #[inline]
+    pub fn gpos_lang_sys(&self, scripts: &[Tag], language: Option<Tag>) -> Option<LangSysTable<'a>> {
+        self.gpos?.lang_sys(scripts, language)
+    }
+
+    /// Returns a tag of a feature at a specified index in the
+    /// [Glyph Substitution Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gsub)
+    /// feature list.
+    ///
+    /// Indices are returned by `LangSysTable::feature_indices`
+    /// and `LangSysTable::required_feature_index`.
+    #[inline]
+    pub fn gsub_feature_tag(&self, index: u16) -> Option<Tag> {
+        self.gsub?.feature_tag(index)
+    }
+
+    /// Returns a tag of a feature at a specified index in the
+    /// [Glyph Positioning Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos)
+    /// feature list.
+    #[inline]
+    pub fn gpos_feature_tag(&self, index: u16) -> Option<Tag> {
+        self.gpos?.feature_tag(index)
+    }
+
+    /// Returns a summary of lookups in the
+    /// [Glyph Substitution Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gsub).
+    ///
+    /// Useful for auditing fonts produced by a build pipeline.
+    #[inline]
+    pub fn gsub_lookup_statistics(&self) -> Option<LookupStatistics> {
+        self.gsub.map(|gsub| gsub.lookup_statistics(LookupListKind::Substitution, self.limits))
+    }
+
+    /// Returns a summary of lookups in the
+    /// [Glyph Positioning Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos).
+    ///
+    /// Useful for auditing fonts produced by a build pipeline.
+    #[inline]
+    pub fn gpos_lookup_statistics(&self) -> Option<LookupStatistics> {
+        self.gpos.map(|gpos| gpos.lookup_statistics(LookupListKind::Positioning, self.limits))
+    }
+
+    /// Returns a [Control Value table](https://docs.microsoft.com/en-us/typography/opentype/spec/cvt).
+    ///
+    /// Returns `None` when `cvt ` table is not present.
+    #[inline]
+    pub fn cvt_table(&self) -> Option<cvt::Table<'a>> {
+        self.cvt
+    }
+
+    /// Returns a [Digital Signature table](https://docs.microsoft.com/en-us/typography/opentype/spec/dsig).
+    ///
+    /// Returns `None` when `DSIG` table is not present or malformed.
+    #[inline]
+    pub fn dsig_table(&self) -> Option<dsig::Table<'a>> {
+        self.dsig
+    }
+
+    /// Checks that face has at least one digital signature.
+    ///
+    /// Signatures are not verified.
+    /// Note that many fonts contain a stub `DSIG` table without signatures.
+    #[inline]
+    pub fn is_signed(&self) -> bool {
+        self.dsig.map(|table| !table.is_empty()).unwrap_or(false)
+    }
+
+    /// Returns the [Font Program](https://docs.microsoft.com/en-us/typography/opentype/spec/fpgm)
+    /// TrueType instructions.
+    ///
+    /// The program is executed once, when the font is loaded.
+    ///
+    /// Returns `None` when `fpgm` table is not present.
+    #[inline]
+    pub fn font_program(&self) -> Option<&'a [u8]> {
+        self.fpgm
+    }
+
+    /// Returns the [Control Value Program](https://docs.microsoft.com/en-us/typography/opentype/spec/prep)
+    /// TrueType instructions.
+    ///
+    /// The program is executed on every size or transformation change.
+    ///
+    /// Returns `None` when `prep` table is not present.
+    #[inline]
+    pub fn control_value_program(&self) -> Option<&'a [u8]> {
+        self.prep
+    }
+
+    /// Returns a [Style Attributes table](https://docs.microsoft.com/en-us/typography/opentype/spec/stat).
+    ///
+    /// Returns `None` when `STAT` table is not present or malformed.
+    #[inline]
+    pub fn stat_table(&self) -> Option<stat::Table<'a>> {
+        self.stat
+    }
+
+    /// Returns all tables parsed during `Face` creation at once.
+    ///
+    /// Tables are already parsed by `Face::from_slice`, so this method is cheap.
+    pub fn tables(&self) -> FaceTables<'a> {
+        FaceTables {
+            base: self.base,
+            cbdt: self.cbdt,
+            cblc: self.cblc,
+            cff: self.parsed_table_data(self.cff1.is_some(), b"CFF "),
+            cmap: self.cmap,
+            colr: self.colr,
+            cpal: self.cpal,
+            cvt: self.cvt,
+            dsig: self.dsig,
+            ebdt: self.ebdt,
+            eblc: self.eblc,
+            ebsc: self.ebsc,
+            fdsc: self.fdsc,
+            fmtx: self.fmtx,
+            fpgm: self.fpgm,
+            gdef: self.parsed_table_data(self.gdef.is_some(), b"GDEF"),
+            glyf: self.glyf,
+            gpos: self.parsed_table_data(self.gpos.is_some(), b"GPOS"),
+            gsub: self.parsed_table_data(self.gsub.is_some(), b"GSUB"),
+            hdmx: self.parsed_table_data(self.hdmx.is_some(), b"hdmx"),
+            head: self.head,
+            hhea: self.hhea,
+            hmtx: self.parsed_table_data(self.hmtx.is_some(), b"hmtx"),
+            jstf: self.jstf,
+            kern: self.kern,
+            loca: self.parsed_table_data(self.loca.is_some(), b"loca"),
+            ltsh: self.parsed_table_data(self.ltsh.is_some(), b"LTSH"),
+            math: self.math,
+            maxp: self.maxp,
+            merg: self.merg,
+            meta: self.meta,
+            name: self.name,
+            os_2: self.parsed_table_data(self.os_2.is_some(), b"OS/2"),
+            pclt: self.pclt,
+            post: self.parsed_table_data(self.post.is_some(), b"post"),
+            prep: self.prep,
+            sbix: self.sbix,
+            stat: self.stat,
+            svg: self.svg_,
+            vdmx: self.parsed_table_data(self.vdmx.is_some(), b"VDMX"),
+            vhea: self.vhea,
+            vmtx: self.parsed_table_data(self.vmtx.is_some(), b"vmtx"),
+            vorg: self.parsed_table_data(self.vorg.is_some(), b"VORG"),
+            zapf: self.zapf,
+
+            #[cfg(feature = "variable-fonts")]
+            avar: self.parsed_table_data(self.avar.is_some(), b"avar"),
+            #[cfg(feature = "variable-fonts")]
+            cff2: self.parsed_table_data(self.cff2.is_some(), b"CFF2"),
+            #[cfg(feature = "variable-fonts")]
+            fvar: self.parsed_table_data(self.fvar.is_some(), b"fvar"),
+            #[cfg(feature = "variable-fonts")]
+            gvar: self.parsed_table_data(self.gvar.is_some(), b"gvar"),
+            #[cfg(feature = "variable-fonts")]
+            hvar: self.parsed_table_data(self.hvar.is_some(), b"HVAR"),
+            #[cfg(feature = "variable-fonts")]
+            mvar: self.parsed_table_data(self.mvar.is_some(), b"MVAR"),
+            #[cfg(feature = "variable-fonts")]
+            vvar: self.parsed_table_data(self.vvar.is_some(), b"VVAR"),
+        }
+    }
+
+    /// Returns data of a table that was parsed during `Face` creation.
+    fn parsed_table_data(&self, is_parsed: bool, tag: &[u8; 4]) -> Option<&'a [u8]> {
+        if !is_parsed {
+            return None;
+        }
+
+        // Table records are not required to be sorted, so `Face::table_data` cannot be used.
+        // Like during parsing, the last record wins.
+        let tag = Tag::from_bytes(tag);
+        self.table_records.into_iter().filter(|record| record.tag == tag).last()?.data(self.font_data)
+    }
+
+    /// Returns a style name for the specified variation coordinates composed
+    /// from the `STAT` table axis values, like `Condensed SemiBold Italic`.
+    ///
+    /// Axes without a value in `variations` use their default `fvar` values.
+    /// See `stat::Table::style_name_ids` for details.
+    ///
+    /// Returns `None` when `STAT` table is not present or names are missing.
+    #[cfg(feature = "std")]
+    pub fn style_name(&self, variations: &[Variation]) -> Option<std::string::String> {
+        let stat = self.stat?;
+
+        #[allow(unused_mut)]
+        let mut variations = variations.to_vec();
+        #[cfg(feature = "variable-fonts")]
+        {
+            for axis in self.variation_axes() {
+                if !variations.iter().any(|v| v.axis == axis.tag) {
+                    variations.push(Variation { axis: axis.tag, value: axis.def_value });
+                }
+            }
+        }
+
+        let mut name = std::string::String::new();
+        for id in stat.style_name_ids(&variations) {
+            if !name.is_empty() {
+                name.push(' ');
+            }
+
+            name.push_str(&self.first_name(&[id])?);
+        }
+
+        Some(name)
+    }
+
+    /// Returns a [Baseline table](https://docs.microsoft.com/en-us/typography/opentype/spec/base).
+    ///
+    /// Returns `None` when `BASE` table is not present or malformed.
+    #[inline]
+    pub fn base_table(&self) -> Option<base::Table<'a>> {
+        self.base
+    }
+
+    /// Returns a horizontal baseline position for the specified script.
+    ///
+    /// Uses the `BASE` table horizontal axis first. Scripts without a record
+    /// fall back to the `DFLT` script.
+    ///
+    /// When a baseline is not defined, it will be synthesized:
+    ///
+    /// - `Roman` is at zero.
+    /// - `IdeographicBottom` and `IdeographicTop` are the typographic descender and ascender,
+    ///   or are derived from each other using the em size.
+    /// - `IdeographicFaceBottom` and `IdeographicFaceTop` are inset by 5% of the em size
+    ///   from the ideographic em-box.
+    /// - `Hanging` is the capital height.
+    /// - `Math` is the `MATH` axis height, or the half of the x height.
+    ///
+    /// `BASE` defines baselines per script, so `language` doesn't affect the result yet.
+    /// It's present so the signature matches language-aware layout code.
+    ///
+    /// This method is affected by variation axes only through the fallback metrics.
+    pub fn baseline(
+        &self,
+        kind: base::BaselineTag,
+        script: Tag,
+        language: Option<Tag>,
+    ) -> Option<i16> {
+        use base::BaselineTag;
+
+        let _ = language;
+        let units_per_em = self.units_per_em()?;
+        let value = self.base.and_then(|table| {
+            table.horizontal_baseline(kind, script, units_per_em)
+        });
+        if value.is_some() {
+            return value;
+        }
+
+        let inset = (units_per_em / 20) as i16;
+        match kind {
+            BaselineTag::Roman => Some(0),
+            BaselineTag::IdeographicBottom => self.typographic_descender(),
+            BaselineTag::IdeographicTop => self.typographic_ascender(),
+            BaselineTag::IdeographicFaceBottom => {
+                self.baseline(BaselineTag::IdeographicBottom, script, language)?
+                    .checked_add(inset)
+            }
+            BaselineTag::IdeographicFaceTop => {
+                self.baseline(BaselineTag::IdeographicTop, script, language)?
+                    .checked_sub(inset)
+            }
+            BaselineTag::Hanging => self.capital_height(),
+            BaselineTag::Math => {
+                self.math
+                    .and_then(|table| table.constants())
+                    .map(|constants| constants.axis_height().value)
+                    .or_else(|| self.x_height().map(|h| h / 2))
+            }
+        }
+    }
+
+    /// Returns a [Color table](https://docs.microsoft.com/en-us/typography/opentype/spec/colr).
+    ///
+    /// Returns `None` when `COLR` table is not present or malformed.
+    ///
+    /// Variable paints, color stops and clip boxes are resolved
+    /// using the current variation coordinates.
+    #[inline]
+    pub fn colr_table(&self) -> Option<colr::Table<'a>> {
+        #[cfg(feature = "variable-fonts")]
+        {
+            let mut table = self.colr?;
+            table.set_coordinates(self.coords());
+            Some(table)
+        }
+
+        #[cfg(not(feature = "variable-fonts"))]
+        {
+            self.colr
+        }
+    }
+
+    /// Returns a [Color Bitmap Location table](https://docs.microsoft.com/en-us/typography/opentype/spec/cblc).
+    ///
+    /// Returns `None` when `CBLC` table is not present or malformed.
+    ///
+    /// Glyph images can be accessed via `glyph_raster_image()`.
+    #[inline]
+    pub fn cblc_table(&self) -> Option<cblc::Table<'a>> {
+        self.cblc
+    }
+
+    /// Returns an [Embedded Bitmap Location table](https://docs.microsoft.com/en-us/typography/opentype/spec/eblc).
+    ///
+    /// Returns `None` when `EBLC` table is not present or malformed.
+    ///
+    /// `EBLC` has the same structure as `CBLC`, therefore `cblc::Table` is used.
+    /// Apple's `bloc` table is used when `EBLC` is not present.
+    /// Glyph images can be accessed via `glyph_raster_image()`.
+    #[inline]
+    pub fn eblc_table(&self) -> Option<cblc::Table<'a>> {
+        self.eblc
+    }
+
+    /// Returns an [Embedded Bitmap Scaling table](https://docs.microsoft.com/en-us/typography/opentype/spec/ebsc).
+    ///
+    /// Returns `None` when `EBSC` table is not present or malformed.
+    #[inline]
+    pub fn ebsc_table(&self) -> Option<ebsc::Table<'a>> {
+        self.ebsc
+    }
+
+    /// Returns an [Apple Font Descriptors table](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6fdsc.html).
+    ///
+    /// Returns `None` when `fdsc` table is not present or malformed.
+    #[inline]
+    pub fn fdsc_table(&self) -> Option<fdsc::Table<'a>> {
+        self.fdsc
+    }
+
+    /// Returns an [Apple Font Metrics table](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6fmtx.html).
+    ///
+    /// Returns `None` when `fmtx` table is not present or malformed.
+    #[inline]
+    pub fn fmtx_table(&self) -> Option<fmtx::Table> {
+        self.fmtx
+    }
+
+    /// Returns coordinates of a point referenced by the `fmtx` table,
+    /// like `fmtx_table()?.horizontal.before`.
+    ///
+    /// Coordinates are in font units and are not affected by hinting or variation axes.
+    ///
+    /// Returns `None` when `fmtx` table is not present
+    /// or when the metrics glyph is not a simple `glyf` glyph with such point.
+    pub fn fmtx_point(&self, index: u8) -> Option<(i16, i16)> {
+        let fmtx = self.fmtx?;
+        let point = glyf::simple_glyph_point(self.loca?, self.glyf?, fmtx.glyph_id, u16::from(index))?;
+        Some((point.x, point.y))
+    }
+
+    /// Returns a [Color Palette table](https://docs.microsoft.com/en-us/typography/opentype/spec/cpal).
+    ///
+    /// Returns `None` when `CPAL` table is not present or malformed.
+    #[inline]
+    pub fn cpal_table(&self) -> Option<cpal::Table<'a>> {
+        self.cpal
+    }
+
+    /// Returns an [SVG table](https://docs.microsoft.com/en-us/typography/opentype/spec/svg).
+    ///
+    /// Returns `None` when `SVG ` table is not present or malformed.
+    #[inline]
+    pub fn svg_table(&self) -> Option<svg::Table<'a>> {
+        self.svg_
+    }
+
+    /// Returns a [Standard Bitmap Graphics table](https://docs.microsoft.com/en-us/typography/opentype/spec/sbix).
+    ///
+    /// Returns `None` when `sbix` table is not present or malformed.
+    #[inline]
+    pub fn sbix_table(&self) -> Option<sbix::Table<'a>> {
+        self.sbix
+    }
+
+    /// Returns an [Apple Zapf table](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6Zapf.html).
+    ///
+    /// Returns `None` when `Zapf` table is not present or malformed.
+    #[inline]
+    pub fn zapf_table(&self) -> Option<zapf::Table<'a>> {
+        self.zapf
+    }
+
+    /// Returns a [Justification table](https://docs.microsoft.com/en-us/typography/opentype/spec/jstf).
+    ///
+    /// Returns `None` when `JSTF` table is not present or malformed.
+    #[inline]
+    pub fn jstf_table(&self) -> Option<jstf::Table<'a>> {
+        self.jstf
+    }
+
+    /// Returns a [Mathematical Typesetting table](https://docs.microsoft.com/en-us/typography/opentype/spec/math).
+    ///
+    /// Returns `None` when `MATH` table is not present or malformed.
+    #[inline]
+    pub fn math_table(&self) -> Option<math::Table<'a>> {
+        self.math
+    }
+
+    /// Returns a [Merge table](https://docs.microsoft.com/en-us/typography/opentype/spec/merg).
+    ///
+    /// Returns `None` when `MERG` table is not present or malformed.
+    #[inline]
+    pub fn merg_table(&self) -> Option<merg::Table<'a>> {
+        self.merg
+    }
+
+    /// Returns a [Metadata table](https://docs.microsoft.com/en-us/typography/opentype/spec/meta).
+    ///
+    /// Returns `None` when `meta` table is not present or malformed.
+    #[inline]
+    pub fn meta_table(&self) -> Option<meta::Table<'a>> {
+        self.meta
+    }
+
+    /// Returns a [PCL 5 table](https://docs.microsoft.com/en-us/typography/opentype/spec/pclt).
+    ///
+    /// Returns `None` when `PCLT` table is not present or malformed.
+    #[inline]
+    pub fn pclt_table(&self) -> Option<pclt::Table<'a>> {
+        self.pclt
+    }
+
+    /// Returns a iterator over kerning subtables.
+    ///
+    /// Supports both
+    /// [OpenType](https://docs.microsoft.com/en-us/typography/opentype/spec/kern)
+    /// and
+    /// [Apple Advanced Typography](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6kern.html)
+    /// variants.
+    pub fn kerning_subtables(&self) -> kern::Subtables {
+        self.kern.unwrap_or_default()
+    }
+
+    /// Returns a horizontal kerning for a pair of glyphs.
+    ///
+    /// Uses pair adjustment lookups of the `GPOS` `kern` feature in all scripts.
+    /// When there are none, horizontal `kern` table subtables are used instead.
+    /// Contextual kerning and device tables are not supported.
+    ///
+    /// Returns `None` when the pair is not kerned.
+    pub fn glyphs_kerning(&self, left: GlyphId, right: GlyphId) -> Option<i16> {
+        self.pair_kerning().glyphs_kerning(left, right)
+    }
+
+    /// Resolves kerning sources used by `Face::glyphs_kerning`.
+    fn pair_kerning(&self) -> PairKerning<'a> {
+        PairKerning {
+            gpos: self.gpos.map(gpos::KerningLookups::new).filter(|lookups| !lookups.is_empty()),
+            kern: self.kern.unwrap_or_default(),
+        }
+    }
+
+    /// Returns a horizontal advance of a string in font units.
+    ///
+    /// This is a simple alternative to text shaping, which is enough for sizing UI labels.
+    /// Characters are mapped using `Face::glyph_index`, missing ones use the `.notdef` glyph.
+    /// When `kerning` is set, `Face::glyphs_kerning` is applied to adjacent glyphs.
+    ///
+    /// Variable faces are measured using the current variation coordinates,
+    /// which can be changed via `Face::set_variation`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+    /// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+    /// let advance = face.glyph_hor_advance(face.glyph_index('A').unwrap()).unwrap();
+    /// assert_eq!(face.measure_text("AA", true), i32::from(advance) * 2);
+    /// ```
+    pub fn measure_text(&self, text: &str, kerning: bool) -> i32 {
+        // Kerning lookups are resolved once instead of for each pair.
+        let kerning = if kerning { Some(self.pair_kerning()) } else { None };
+        let mut width = 0i32;
+        let mut prev = None;
+        for glyph_id in self.glyph_indices(text) {
+            let glyph_id = glyph_id.unwrap_or(GlyphId(0));
+            width = width.saturating_add(i32::from(self.glyph_hor_advance(glyph_id).unwrap_or(0)));
+            if let (Some(kerning), Some(prev)) = (kerning, prev) {
+                width = width.saturating_add(i32::from(kerning.glyphs_kerning(prev, glyph_id).unwrap_or(0)));
+            }
+
+            prev = Some(glyph_id);
+        }
+
+        width
+    }
+
+    /// Outlines a glyph and returns its tight bounding box.
+    ///
+    /// **Warning**: since `ttf-parser` is a pull parser,
+    /// `OutlineBuilder` will emit segments even when outline is partially malformed.
+    /// You must check `outline_glyph()` result before using
+    /// `OutlineBuilder`'s output.
+    ///
+    /// `gvar`, `glyf`, `CFF` and `CFF2` tables are supported.
+    /// And they will be accesses in this specific order.
+    ///
+    /// This method is affected by variation axes.
+    ///
+    /// Returns `None` when glyph has no outline or on error.
     ///
     /// # Example
     ///
@@ -1556,6 +4395,7 @@ impl<'a> Face<'a> {
                     self.glyf?,
                     gvar_table,
                     self.coords(),
+                    self.variation_rounding,
                     glyph_id,
                     builder,
                 );
@@ -1580,6 +4420,130 @@ impl<'a> Face<'a> {
         None
     }
 
+    /// Returns glyph's outline as an SVG path data string.
+    ///
+    /// A shorthand for `Face::outline_glyph` with `SvgPathBuilder`.
+    ///
+    /// Returns `None` when glyph has no outline or on error.
+    #[cfg(feature = "std")]
+    pub fn glyph_svg_path(
+        &self,
+        glyph_id: GlyphId,
+        options: SvgPathOptions,
+    ) -> Option<std::string::String> {
+        let mut builder = SvgPathBuilder::new(options);
+        self.outline_glyph(glyph_id, &mut builder)?;
+        Some(builder.into_string())
+    }
+
+    /// Returns glyph's outline contours.
+    ///
+    /// A shorthand for `Face::outline_glyph` with `ContoursBuilder`.
+    ///
+    /// Returns `None` when glyph has no outline or on error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+    /// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+    /// for contour in face.glyph_contours(ttf_parser::GlyphId(1)).unwrap() {
+    ///     assert!(contour.is_closed());
+    ///     assert_eq!(contour.segments().last().unwrap().end(), contour.start());
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn glyph_contours(&self, glyph_id: GlyphId) -> Option<Contours> {
+        let mut builder = ContoursBuilder::new();
+        self.outline_glyph(glyph_id, &mut builder)?;
+        Some(Contours { contours: builder.into_contours().into_iter() })
+    }
+
+    /// Returns glyph's outline as a `kurbo::BezPath`.
+    ///
+    /// A shorthand for `Face::outline_glyph` with `BezPathBuilder`.
+    ///
+    /// Returns `None` when glyph has no outline or on error.
+    #[cfg(feature = "kurbo")]
+    pub fn glyph_bez_path(&self, glyph_id: GlyphId) -> Option<kurbo::BezPath> {
+        let mut builder = BezPathBuilder::new();
+        self.outline_glyph(glyph_id, &mut builder)?;
+        Some(builder.into_path())
+    }
+
+    /// Checks that a point is inside a glyph outline.
+    ///
+    /// `x` and `y` are in font units. Curves are flattened with a 0.1 units tolerance.
+    ///
+    /// Returns `false` when glyph has no outline or on error.
+    ///
+    /// This method is affected by variation axes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ttf_parser::{FillRule, GlyphId};
+    ///
+    /// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+    /// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+    /// // The stem and the counter of 'A'.
+    /// assert!(face.glyph_contains_point(GlyphId(1), 100.0, 100.0, FillRule::NonZero));
+    /// assert!(!face.glyph_contains_point(GlyphId(1), 270.0, 400.0, FillRule::NonZero));
+    /// ```
+    pub fn glyph_contains_point(
+        &self,
+        glyph_id: GlyphId,
+        x: f32,
+        y: f32,
+        fill_rule: FillRule,
+    ) -> bool {
+        let mut counter = outline::WindingCounter::new(x, y);
+        if self.outline_glyph(glyph_id, &mut FlattenBuilder::new(&mut counter, 0.1)).is_none() {
+            return false;
+        }
+
+        counter.contains(fill_rule)
+    }
+
+    /// Returns a stable hash of glyph's outline.
+    ///
+    /// A shorthand for `Face::outline_glyph` with `OutlineHasher`.
+    /// Composite glyphs are hashed as a final outline,
+    /// so glyphs that look the same will have the same hash.
+    ///
+    /// Returns `None` when glyph has no outline or on error.
+    ///
+    /// This method is affected by variation axes.
+    pub fn glyph_outline_hash(&self, glyph_id: GlyphId) -> Option<u64> {
+        let mut hasher = OutlineHasher::new();
+        self.outline_glyph(glyph_id, &mut hasher)?;
+        Some(hasher.finish())
+    }
+
+    /// Returns a stable hash of a composite glyph structure.
+    ///
+    /// Each component is represented by its outline hash and transform,
+    /// so composite glyphs built from identical components will have the same hash,
+    /// even when component glyph IDs are different.
+    ///
+    /// Returns `None` when glyph is not a `glyf` composite glyph or on error.
+    pub fn glyph_components_hash(&self, glyph_id: GlyphId) -> Option<u64> {
+        let mut hasher = OutlineHasher::new();
+        for component in glyf::components(self.loca?, self.glyf?, glyph_id)? {
+            // Empty components, like a space, are hashed as zero.
+            hasher.write_u64(self.glyph_outline_hash(component.glyph_id).unwrap_or(0));
+            let ts = component.transform;
+            for n in &[ts.a, ts.b, ts.c, ts.d] {
+                hasher.write_i32(outline::quantize(*n, 16384.0));
+            }
+
+            hasher.write_i32(outline::quantize(ts.e, 64.0));
+            hasher.write_i32(outline::quantize(ts.f, 64.0));
+        }
+
+        Some(hasher.finish())
+    }
+
     /// Returns a tight glyph bounding box.
     ///
     /// Unless the current face has a `glyf` table, this is just a shorthand for `outline_glyph()`
@@ -1627,35 +4591,58 @@ impl<'a> Face<'a> {
     /// `pixels_per_em` allows selecting a preferred image size. The chosen size will
     /// be closer to an upper one. So when font has 64px and 96px images and `pixels_per_em`
     /// is set to 72, 96px image will be returned.
+    /// When there are no larger images, the largest one will be returned.
     /// To get the largest image simply use `std::u16::MAX`.
+    /// Use `RasterGlyphImage::scale` to render the image at the requested size.
     ///
     /// Note that this method will return an encoded image. It should be decoded
     /// by the caller. We don't validate or preprocess it in any way.
     ///
-    /// Currently, only PNG images are supported.
+    /// Currently, only PNG images and uncompressed bitmaps are supported.
     ///
     /// Also, a font can contain both: images and outlines. So when this method returns `None`
     /// you should also try `outline_glyph()` afterwards.
     ///
     /// There are multiple ways an image can be stored in a TrueType font
-    /// and this method supports only `sbix`, `CBLC`+`CBDT`.
-    /// Font's tables be accesses in this specific order.
-    #[inline]
+    /// and this method supports only `sbix`, `CBLC`+`CBDT` and `EBLC`+`EBDT`
+    /// (or Apple's `bloc`+`bdat`).
+    /// Strikes from all of them are considered, while strikes without the glyph are ignored.
+    /// When multiple tables have strikes of the same size, they are preferred
+    /// in this specific order.
     pub fn glyph_raster_image(
         &self,
         glyph_id: GlyphId,
         pixels_per_em: u16,
     ) -> Option<RasterGlyphImage> {
-        if let Some(sbix_data) = self.sbix {
-            return sbix::parse(sbix_data, self.number_of_glyphs, glyph_id, pixels_per_em, 0);
+        let sbix_image = self.sbix
+            .and_then(|table| sbix::parse(table, glyph_id, pixels_per_em));
+
+        let cbdt_image = match (self.cblc, self.cbdt) {
+            (Some(cblc), Some(cbdt_data)) => cblc.find_location(glyph_id, pixels_per_em)
+                .and_then(|location| cbdt::parse(cbdt_data, location)),
+            _ => None,
+        };
+
+        let ebdt_image = match (self.eblc, self.ebdt) {
+            (Some(eblc), Some(ebdt_data)) => eblc.find_location(glyph_id, pixels_per_em)
+                .and_then(|location| ebdt::parse(ebdt_data, location)),
+            _ => None,
+        };
+
+        let mut best: Option<RasterGlyphImage> = None;
+        for image in [sbix_image, cbdt_image, ebdt_image].iter().flatten() {
+            let best_ppem = best.map(|image| image.pixels_per_em).unwrap_or(0);
+            if is_better_strike(pixels_per_em, image.pixels_per_em, best_ppem) {
+                best = Some(*image);
+            }
         }
 
-        if let (Some(cblc_data), Some(cbdt_data)) = (self.cblc, self.cbdt) {
-            let location = cblc::find_location(cblc_data, glyph_id, pixels_per_em)?;
-            return cbdt::parse(cbdt_data, location);
+        let mut image = best?;
+        if image.pixels_per_em != 0 {
+            image.scale = f32::from(pixels_per_em) / f32::from(image.pixels_per_em);
         }
 
-        None
+        Some(image)
     }
 
     /// Returns a reference to a glyph's SVG image.
@@ -1667,12 +4654,112 @@ impl<'a> Face<'a> {
     /// or even decompressed (in case of SVGZ) by the caller.
     /// We don't validate or preprocess it in any way.
     ///
+    /// A single document can contain multiple glyphs. Use `svg_table()`
+    /// to get the range of glyphs covered by the document.
+    ///
     /// Also, a font can contain both: images and outlines. So when this method returns `None`
     /// you should also try `outline_glyph()` afterwards.
     #[inline]
     pub fn glyph_svg_image(&self, glyph_id: GlyphId) -> Option<&'a [u8]> {
-        self.svg_
-            .and_then(|svg_data| svg::parse(svg_data, glyph_id))
+        self.svg_?.find(glyph_id).map(|doc| doc.data)
+    }
+
+    /// Returns an iterator over glyph's color layers.
+    ///
+    /// Each layer is a pair of a glyph ID, which outline should be filled,
+    /// and a `CPAL` palette entry index. The index `0xFFFF` means the text foreground color.
+    /// Layers are ordered from bottom to top.
+    ///
+    /// Only `COLR` version 0 layers are supported.
+    ///
+    /// Returns `None` when the glyph is not a color glyph.
+    #[inline]
+    pub fn glyph_color_layers(
+        &self,
+        glyph_id: GlyphId,
+    ) -> Option<impl Iterator<Item = (GlyphId, u16)> + 'a> {
+        self.colr?.glyph_layers(glyph_id)
+    }
+
+    /// Paints a color glyph.
+    ///
+    /// Uses `COLR` version 1 paints or version 0 layers when present.
+    /// Otherwise, paints glyph's outline with the `foreground` color.
+    /// Colors are resolved using the specified `CPAL` palette,
+    /// where the entry `0xFFFF` means the `foreground` color.
+    ///
+    /// Glyph outlines are requested via `Painter::outline_glyph`.
+    ///
+    /// Returns `None` when the glyph has neither color data nor an outline
+    /// or when painting of a color glyph was aborted. See `colr::Table::paint_glyph`.
+    pub fn paint_color_glyph(
+        &self,
+        glyph_id: GlyphId,
+        palette: u16,
+        foreground: cpal::Color,
+        painter: &mut dyn colr::Painter<'a>,
+    ) -> Option<()> {
+        let colors = colr::Palette::new(self.cpal, palette, foreground);
+        if let Some(table) = self.colr_table() {
+            if table.base_glyph_paint(glyph_id).is_some() || table.glyph_layers(glyph_id).is_some() {
+                return table.paint_glyph(glyph_id, colors, painter);
+            }
+        }
+
+        // Not a color glyph. Fallback to a monochrome outline.
+        self.outline_glyph(glyph_id, &mut DummyOutline)?;
+        painter.outline_glyph(glyph_id);
+        painter.push_clip();
+        painter.paint(colr::Fill::Solid(foreground));
+        painter.pop_clip();
+        Some(())
+    }
+
+    /// Checks that face has color glyphs.
+    ///
+    /// Simply checks the presence of `COLR`, `SVG `, `sbix` or `CBLC`+`CBDT` tables.
+    /// Monochrome `EBDT` bitmaps are not considered as color glyphs.
+    #[inline]
+    pub fn is_color_font(&self) -> bool {
+        !self.color_glyph_formats().is_empty()
+    }
+
+    /// Returns color glyph formats present in the face.
+    ///
+    /// Simply checks the presence of tables.
+    /// Use `glyph_color_formats` to check a specific glyph.
+    #[inline]
+    pub fn color_glyph_formats(&self) -> ColorGlyphFormats {
+        ColorGlyphFormats {
+            colr: self.colr.is_some(),
+            svg: self.svg_.is_some(),
+            sbix: self.sbix.is_some(),
+            cbdt: self.cblc.is_some() && self.cbdt.is_some(),
+        }
+    }
+
+    /// Returns formats in which the glyph has a color representation.
+    ///
+    /// Raster images are checked in all strikes.
+    /// Returns an empty set for a glyph that has only an outline.
+    pub fn glyph_color_formats(&self, glyph_id: GlyphId) -> ColorGlyphFormats {
+        let colr = self.colr.map(|table| {
+            table.base_glyph_paint(glyph_id).is_some() || table.glyph_layers(glyph_id).is_some()
+        });
+
+        let cbdt = match (self.cblc, self.cbdt) {
+            (Some(cblc), Some(_)) => cblc.find_location(glyph_id, core::u16::MAX).is_some(),
+            _ => false,
+        };
+
+        ColorGlyphFormats {
+            colr: colr.unwrap_or(false),
+            svg: self.svg_.and_then(|table| table.find(glyph_id)).is_some(),
+            sbix: self.sbix
+                .map(|table| table.strikes().any(|s| s.resolved_glyph_data(glyph_id).is_some()))
+                .unwrap_or(false),
+            cbdt,
+        }
     }
 
     /// Returns an iterator over variation axes.
@@ -1682,6 +4769,116 @@ impl<'a> Face<'a> {
         self.fvar.map(|fvar| fvar.axes()).unwrap_or_default()
     }
 
+    /// Returns face's *Variations PostScript Name Prefix* (name ID 25).
+    ///
+    /// When not set, a typographic family name with non-alphanumeric characters removed
+    /// is used, as described in
+    /// [Adobe Technical Note #5902](https://adobe-type-tools.github.io/font-tech-notes/pdfs/5902.AdobePSNameGeneration.pdf).
+    #[cfg(all(feature = "std", feature = "variable-fonts"))]
+    pub fn variation_post_script_name_prefix(&self) -> Option<std::string::String> {
+        if let Some(prefix) = self.first_name(&[name_id::VARIATIONS_POST_SCRIPT_NAME_PREFIX]) {
+            return Some(prefix);
+        }
+
+        let family = self.typographic_family_name()?;
+        Some(family.chars().filter(|c| c.is_ascii_alphanumeric()).collect())
+    }
+
+    /// Returns face's PostScript name for the specified variation coordinates.
+    ///
+    /// The name is generated according to
+    /// [Adobe Technical Note #5902](https://adobe-type-tools.github.io/font-tech-notes/pdfs/5902.AdobePSNameGeneration.pdf),
+    /// like `NotoSans-wght500wdth80`.
+    /// Axes without a value in `variations` or with a default value are omitted.
+    /// When all axes are omitted, the *PostScript* name (6) is returned.
+    ///
+    /// Returns `None` when face is not variable, has no suitable names
+    /// or the name is longer than 127 characters.
+    #[cfg(all(feature = "std", feature = "variable-fonts"))]
+    pub fn variation_post_script_name(&self, variations: &[Variation]) -> Option<std::string::String> {
+        let fvar = self.fvar?;
+        let prefix = self.variation_post_script_name_prefix()?;
+        let name = fvar::variation_post_script_name(&prefix, fvar.axes(), variations)?;
+        if name.len() == prefix.len() {
+            if let Some(name) = self.first_name(&[name_id::POST_SCRIPT_NAME]) {
+                return Some(name);
+            }
+        }
+
+        Some(name)
+    }
+
+    /// Returns an iterator over raw `gvar` variation tuples of a glyph.
+    ///
+    /// Unlike [`outline_glyph`](#method.outline_glyph), tuples are not applied
+    /// and do not depend on the current variation coordinates.
+    /// Useful for tools that need to inspect the variation data.
+    ///
+    /// Returns `None` when face doesn't have a `gvar` table or data is malformed.
+    #[cfg(feature = "variable-fonts")]
+    #[inline]
+    pub fn glyph_variation_tuples(&self, glyph_id: GlyphId) -> Option<GlyphVariationTuples<'a>> {
+        gvar::glyph_variation_tuples(self.loca?, self.glyf?, self.gvar.as_ref()?, glyph_id)
+    }
+
+    /// Returns a variation axis by its tag.
+    ///
+    /// Returns `None` when face is not variable or doesn't have such axis.
+    #[cfg(feature = "variable-fonts")]
+    #[inline]
+    pub fn variation_axis(&self, tag: Tag) -> Option<VariationAxis> {
+        self.fvar.and_then(|fvar| fvar.axis_by_tag(tag))
+    }
+
+    /// Returns an iterator over variation named instances.
+    #[cfg(feature = "variable-fonts")]
+    #[inline]
+    pub fn variation_instances(&self) -> VariationInstances<'a> {
+        self.fvar.map(|fvar| fvar.instances()).unwrap_or_default()
+    }
+
+    /// Returns a variation named instance by its subfamily name, like `SemiBold`.
+    ///
+    /// Names in all languages are checked. Comparison ignores case and whitespace.
+    ///
+    /// Returns `None` when face is not variable or doesn't have such instance.
+    #[cfg(all(feature = "std", feature = "variable-fonts"))]
+    pub fn named_instance_by_name(&self, name: &str) -> Option<VariationInstance<'a>> {
+        self.fvar?.instance_by_name(self.names(), name)
+    }
+
+    /// Returns a PostScript name of a variation named instance.
+    ///
+    /// Uses the instance's PostScript name ID when set. Otherwise, the name is generated
+    /// according to
+    /// [Adobe Technical Note #5902](https://adobe-type-tools.github.io/font-tech-notes/pdfs/5902.AdobePSNameGeneration.pdf)
+    /// from the *Variations PostScript Name Prefix* and the instance's subfamily name,
+    /// like `NotoSans-CondensedBold`, falling back to instance's coordinates.
+    #[cfg(all(feature = "std", feature = "variable-fonts"))]
+    pub fn variation_instance_post_script_name(
+        &self,
+        instance: &VariationInstance,
+    ) -> Option<std::string::String> {
+        if let Some(id) = instance.post_script_name_id() {
+            if let Some(name) = self.first_name(&[id]) {
+                return Some(name);
+            }
+        }
+
+        let prefix = self.variation_post_script_name_prefix()?;
+        if let Some(subfamily) = self.first_name(&[instance.subfamily_name_id()]) {
+            let mut name = prefix;
+            name.push('-');
+            name.extend(subfamily.chars().filter(|c| c.is_ascii_alphanumeric()));
+            if name.len() <= 127 {
+                return Some(name);
+            }
+        }
+
+        let variations: std::vec::Vec<Variation> = instance.coordinates().collect();
+        self.variation_post_script_name(&variations)
+    }
+
     /// Sets a variation axis coordinate.
     ///
     /// This is the only mutable method in the library.
@@ -1693,31 +4890,97 @@ impl<'a> Face<'a> {
     /// Returns `None` when face is not variable or doesn't have such axis.
     #[cfg(feature = "variable-fonts")]
     pub fn set_variation(&mut self, axis: Tag, value: f32) -> Option<()> {
+        let (idx, value) = self.unmapped_variation(axis, value)?;
+        self.unmapped_coordinates.data[idx] = value;
+
+        // `avar` must be applied to unmapped coordinates,
+        // otherwise previously set coordinates would be mapped multiple times.
+        self.coordinates = self.map_coordinates(&self.unmapped_coordinates);
+
+        Some(())
+    }
+
+    /// Returns a copy of the face pinned to the specified variation location.
+    ///
+    /// Axes that are not listed use their default values,
+    /// regardless of the current face coordinates.
+    /// Unknown axes are ignored.
+    ///
+    /// The returned face can be used just like a static one.
+    #[cfg(feature = "variable-fonts")]
+    pub fn pinned(&self, variations: &[(Tag, f32)]) -> PinnedFace<'a> {
+        let mut face = self.clone();
+        for coord in face.unmapped_coordinates.as_mut_slice() {
+            *coord = NormalizedCoordinate::default();
+        }
+        face.coordinates = face.map_coordinates(&face.unmapped_coordinates);
+
+        for &(axis, value) in variations {
+            let _ = face.set_variation(axis, value);
+        }
+
+        PinnedFace(face)
+    }
+
+    /// Converts a user-space axis value into a normalized coordinate.
+    ///
+    /// The value is clamped to the axis range, normalized relative to the axis default,
+    /// mapped using `avar` and rounded to f2.14.
+    /// Since `avar` version 2 can depend on other axes, the current coordinates
+    /// of the remaining axes are taken into account.
+    ///
+    /// Unlike [`set_variation`](#method.set_variation), doesn't modify the face.
+    ///
+    /// Returns `None` when face is not variable or doesn't have such axis.
+    #[cfg(feature = "variable-fonts")]
+    pub fn normalize_variation(&self, axis: Tag, value: f32) -> Option<NormalizedCoordinate> {
+        let (idx, value) = self.unmapped_variation(axis, value)?;
+        let mut coordinates = self.unmapped_coordinates;
+        coordinates.data[idx] = value;
+        self.map_coordinates(&coordinates).as_slice().get(idx).cloned()
+    }
+
+    #[cfg(feature = "variable-fonts")]
+    fn unmapped_variation(&self, axis: Tag, value: f32) -> Option<(usize, NormalizedCoordinate)> {
         if !self.is_variable() {
             return None;
         }
 
-        let v = self
-            .variation_axes()
-            .enumerate()
-            .find(|(_, a)| a.tag == axis);
-        if let Some((idx, a)) = v {
-            if idx >= MAX_VAR_COORDS {
-                return None;
-            }
-
-            self.coordinates.data[idx] = a.normalized_value(value);
-        } else {
+        let (idx, a) = self.variation_axes().enumerate().find(|(_, a)| a.tag == axis)?;
+        if idx >= MAX_VAR_COORDS {
             return None;
         }
 
-        // TODO: optimize
+        Some((idx, a.normalized_value(value)))
+    }
+
+    #[cfg(feature = "variable-fonts")]
+    fn map_coordinates(&self, unmapped: &VarCoords) -> VarCoords {
+        let mut coordinates = *unmapped;
         if let Some(avar) = self.avar {
             // Ignore error.
-            let _ = avar.map_coordinates(self.coordinates.as_mut_slice());
+            let _ = avar.map_coordinates(coordinates.as_mut_slice());
         }
 
-        Some(())
+        coordinates
+    }
+
+    /// Sets a rounding mode for variation deltas.
+    ///
+    /// Affects glyph outlines and all methods that are affected by variation axes.
+    ///
+    /// Default: `VariationRounding::Legacy`.
+    #[cfg(feature = "variable-fonts")]
+    #[inline]
+    pub fn set_variation_rounding(&mut self, rounding: VariationRounding) {
+        self.variation_rounding = rounding;
+    }
+
+    /// Returns the current rounding mode for variation deltas.
+    #[cfg(feature = "variable-fonts")]
+    #[inline]
+    pub fn variation_rounding(&self) -> VariationRounding {
+        self.variation_rounding
     }
 
     /// Returns the current normalized variation coordinates.
@@ -1727,6 +4990,16 @@ impl<'a> Face<'a> {
         self.coordinates.as_slice()
     }
 
+    /// Returns the current normalized variation coordinates.
+    ///
+    /// Coordinates are in the `fvar` axes order, with `avar` mapping applied.
+    /// An alias for [`variation_coordinates`](#method.variation_coordinates).
+    #[cfg(feature = "variable-fonts")]
+    #[inline]
+    pub fn normalized_coordinates(&self) -> &[NormalizedCoordinate] {
+        self.variation_coordinates()
+    }
+
     /// Checks that face has non-default variation coordinates.
     #[cfg(feature = "variable-fonts")]
     #[inline]
@@ -1734,6 +5007,24 @@ impl<'a> Face<'a> {
         self.coordinates.as_slice().iter().any(|c| c.0 != 0)
     }
 
+    /// Returns an `MVAR` metrics delta for the current variation coordinates.
+    ///
+    /// `tag` is an [MVAR value tag](https://docs.microsoft.com/en-us/typography/opentype/spec/mvar#value-tags),
+    /// like `xhgt` or `gsp0`. Most of the tags are already applied by the corresponding
+    /// `Face` methods, so this method is mostly useful for tags without one.
+    ///
+    /// Returns `None` when face is not variable, doesn't have an `MVAR` table
+    /// or such tag.
+    #[cfg(feature = "variable-fonts")]
+    #[inline]
+    pub fn metrics_variation(&self, tag: Tag) -> Option<f32> {
+        if !self.is_variable() {
+            return None;
+        }
+
+        self.mvar?.metrics_offset(tag, self.coords())
+    }
+
     #[cfg(feature = "variable-fonts")]
     #[inline]
     fn metrics_var_offset(&self, tag: Tag) -> f32 {
@@ -1752,8 +5043,8 @@ impl<'a> Face<'a> {
     #[inline]
     fn apply_metrics_variation_to(&self, tag: Tag, value: &mut i16) {
         if self.is_variable() {
-            let v = f32::from(*value) + self.metrics_var_offset(tag);
-            // TODO: Should probably round it, but f32::round is not available in core.
+            let offset = self.metrics_var_offset(tag);
+            let v = f32::from(*value) + self.variation_rounding.round(offset);
             if let Some(v) = i16::try_num_from(v) {
                 *value = v;
             }
@@ -1769,6 +5060,14 @@ impl<'a> Face<'a> {
     fn coords(&self) -> &[NormalizedCoordinate] {
         self.coordinates.as_slice()
     }
+
+    #[cfg(feature = "variable-fonts")]
+    #[inline]
+    fn phantom_points_deltas(&self, glyph_id: GlyphId) -> Option<[(f32, f32); 4]> {
+        gvar::phantom_points_deltas(
+            self.loca?, self.glyf?, self.gvar.as_ref()?, self.coords(), glyph_id,
+        )
+    }
 }
 
 impl fmt::Debug for Face<'_> {
@@ -1777,6 +5076,33 @@ impl fmt::Debug for Face<'_> {
     }
 }
 
+/// A font face pinned to a fixed variation location.
+///
+/// Dereferences to `Face`, so all metrics, outlines and feature variations queries
+/// are answered at the pinned location. Unlike `Face`, the location cannot be changed.
+///
+/// Created via `Face::pinned`.
+#[cfg(feature = "variable-fonts")]
+#[derive(Clone)]
+pub struct PinnedFace<'a>(Face<'a>);
+
+#[cfg(feature = "variable-fonts")]
+impl<'a> core::ops::Deref for PinnedFace<'a> {
+    type Target = Face<'a>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "variable-fonts")]
+impl fmt::Debug for PinnedFace<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PinnedFace()")
+    }
+}
+
 /// Returns the number of fonts stored in a TrueType font collection.
 ///
 /// Returns `None` if a provided data is not a TrueType font collection.
@@ -1791,10 +5117,710 @@ pub fn fonts_in_collection(data: &[u8]) -> Option<u32> {
     s.read::<u32>()
 }
 
+
+/// A table record of a face in a font collection.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CollectionTableRecord {
+    /// Table's tag.
+    pub tag: Tag,
+
+    /// Table's offset from the start of the collection data.
+    pub offset: u32,
+
+    /// Table's length.
+    pub length: u32,
+
+    /// Indicates that the table data is shared with other faces of the collection.
+    pub is_shared: bool,
+}
+
+/// A TrueType font collection digital signature location.
+///
+/// Present only in the collection header version 2.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CollectionSignature {
+    /// Signature's offset from the start of the collection data.
+    pub offset: u32,
+
+    /// Signature's length.
+    pub length: u32,
+}
+
+/// A TrueType font collection header.
+///
+/// Allows inspecting how tables are shared between collection faces.
+#[derive(Clone, Copy)]
+pub struct FontCollection<'a> {
+    data: &'a [u8],
+    offsets: LazyArray32<'a, Offset32>,
+    signature: Option<CollectionSignature>,
+}
+
+impl<'a> FontCollection<'a> {
+    /// Parses a font collection header.
+    ///
+    /// Returns `None` if a provided data is not a TrueType font collection.
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        if s.read::<Magic>()? != Magic::FontCollection {
+            return None;
+        }
+
+        let major_version: u16 = s.read()?;
+        s.skip::<u16>(); // minor version
+        let number_of_faces: u32 = s.read()?;
+        let offsets = s.read_array32(number_of_faces)?;
+
+        let mut signature = None;
+        if major_version >= 2 {
+            let tag: u32 = s.read()?;
+            let length: u32 = s.read()?;
+            let offset: u32 = s.read()?;
+            // The tag is set to zero when there is no signature.
+            if tag == 0x44534947 { // DSIG
+                signature = Some(CollectionSignature { offset, length });
+            }
+        }
+
+        Some(FontCollection { data, offsets, signature })
+    }
+
+    /// Returns the number of faces in the collection.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        self.offsets.len()
+    }
+
+    /// Checks that the collection has no faces.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.len() == 0
+    }
+
+    /// Returns the collection digital signature location.
+    #[inline]
+    pub fn signature(&self) -> Option<CollectionSignature> {
+        self.signature
+    }
+
+    /// Returns the collection [Digital Signature table](https://docs.microsoft.com/en-us/typography/opentype/spec/dsig).
+    ///
+    /// Returns `None` when the collection is not signed or the table is malformed.
+    pub fn signature_table(&self) -> Option<dsig::Table<'a>> {
+        let signature = self.signature?;
+        let start = usize::num_from(signature.offset);
+        let end = start.checked_add(usize::num_from(signature.length))?;
+        dsig::Table::parse(self.data.get(start..end)?)
+    }
+
+    fn face_records(&self, index: u32) -> Option<LazyArray16<'a, TableRecord>> {
+        let offset = self.offsets.get(index)?.to_usize();
+        let mut s = Stream::new_at(self.data, offset)?;
+        if s.read::<Magic>()? == Magic::FontCollection {
+            return None;
+        }
+
+        let num_tables: u16 = s.read()?;
+        s.advance(6); // searchRange (u16) + entrySelector (u16) + rangeShift (u16)
+        s.read_array16(num_tables)
+    }
+
+    /// Returns an iterator over face's table records.
+    ///
+    /// A table is shared when any other face references the same data offset.
+    /// Faces with a malformed header are ignored during the check.
+    pub fn table_records(
+        &self,
+        index: u32,
+    ) -> Option<impl Iterator<Item = CollectionTableRecord> + 'a> {
+        let records = self.face_records(index)?;
+        let collection = *self;
+        Some(records.into_iter().map(move |record| {
+            let is_shared = (0..collection.len())
+                .filter(|i| *i != index)
+                .filter_map(|i| collection.face_records(i))
+                .any(|records| records.into_iter().any(|r| r.offset == record.offset));
+
+            CollectionTableRecord {
+                tag: record.tag,
+                offset: record.offset,
+                length: record.length,
+                is_shared,
+            }
+        }))
+    }
+}
+
+impl fmt::Debug for FontCollection<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FontCollection()")
+    }
+}
+
+/// Kerning sources resolved once for kerning many glyph pairs.
+#[derive(Clone, Copy)]
+struct PairKerning<'a> {
+    /// `GPOS` lookups, which take precedence over the `kern` table when present.
+    gpos: Option<gpos::KerningLookups<'a>>,
+    kern: kern::Subtables<'a>,
+}
+
+impl PairKerning<'_> {
+    fn glyphs_kerning(&self, left: GlyphId, right: GlyphId) -> Option<i16> {
+        if let Some(ref lookups) = self.gpos {
+            return lookups.glyphs_kerning(left, right);
+        }
+
+        let mut kerning = None;
+        let subtables = self.kern
+            .filter(|st| st.is_horizontal() && !st.is_variable() && !st.has_cross_stream());
+        for subtable in subtables {
+            if let Some(value) = subtable.glyphs_kerning(left, right) {
+                kerning = Some(kerning.unwrap_or(0i16).saturating_add(value));
+            }
+        }
+
+        kerning
+    }
+}
+
+/// Checks that a character doesn't require a glyph.
+fn is_ignorable_char(c: char) -> bool {
+    match c {
+        '\u{180B}'..='\u{180D}' | '\u{180F}' => true, // Mongolian free variation selectors
+        '\u{200C}' | '\u{200D}' => true, // ZWNJ and ZWJ
+        '\u{FE00}'..='\u{FE0F}' => true, // variation selectors
+        '\u{E0100}'..='\u{E01EF}' => true, // variation selectors supplement
+        _ => c.is_control(),
+    }
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn normalized_coordinate_from_f32() {
+        assert_eq!(NormalizedCoordinate::from(0.0).get(), 0);
+        assert_eq!(NormalizedCoordinate::from(0.5).get(), 8192);
+        assert_eq!(NormalizedCoordinate::from(-1.0).get(), -16384);
+        assert_eq!(NormalizedCoordinate::from(2.0).get(), 16384);
+        // Rounded instead of truncated.
+        assert_eq!(NormalizedCoordinate::from(0.99999).get(), 16384);
+        assert_eq!(NormalizedCoordinate::from(-0.99999).get(), -16384);
+    }
+
+    #[cfg(feature = "variable-fonts")]
+    #[test]
+    fn variation_rounding() {
+        assert_eq!(VariationRounding::Legacy.round(-1.5), -1.5);
+        assert_eq!(VariationRounding::Legacy.round_to_integer(-1.5), -1.0);
+        assert_eq!(VariationRounding::Nearest.round_to_integer(-1.5), -2.0);
+        assert_eq!(VariationRounding::Nearest.round(1.5), 2.0);
+        assert_eq!(VariationRounding::Nearest.round(-1.4), -1.0);
+        assert_eq!(VariationRounding::Truncate.round(1.7), 1.0);
+        assert_eq!(VariationRounding::Truncate.round_to_integer(-1.7), -1.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_values() {
+        use serde::Deserialize;
+        use serde::de::value::{Error, I16Deserializer, SeqDeserializer, StrDeserializer};
+
+        // Coordinates are clamped like in `From<i16>`.
+        let coord = NormalizedCoordinate::deserialize(I16Deserializer::<Error>::new(20000)).unwrap();
+        assert_eq!(coord.get(), 16384);
+
+        let format = RasterImageFormat::deserialize(StrDeserializer::<Error>::new("PNG")).unwrap();
+        assert_eq!(format, RasterImageFormat::PNG);
+        assert!(RasterImageFormat::deserialize(StrDeserializer::<Error>::new("GIF")).is_err());
+
+        let metrics = LineMetrics::deserialize(SeqDeserializer::<_, Error>::new([-100i16, 50].iter().cloned())).unwrap();
+        assert_eq!(metrics, LineMetrics { position: -100, thickness: 50 });
+
+        let rect = Rect::deserialize(SeqDeserializer::<_, Error>::new([1i16, 2, 3, 4].iter().cloned())).unwrap();
+        assert_eq!(rect, Rect { x_min: 1, y_min: 2, x_max: 3, y_max: 4 });
+    }
+
+    #[test]
+    fn strike_selection() {
+        // A larger strike is preferred.
+        assert!(is_better_strike(20, 30, 0));
+        assert!(is_better_strike(20, 30, 10));
+        assert!(!is_better_strike(20, 10, 30));
+        // The smallest of larger strikes is preferred.
+        assert!(is_better_strike(20, 25, 30));
+        assert!(!is_better_strike(20, 30, 25));
+        // The largest strike is used when there are no larger ones.
+        assert!(is_better_strike(40, 30, 25));
+        assert!(!is_better_strike(20, 20, 20));
+    }
+
+    #[test]
+    fn monospaced() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert!(!face.is_monospaced());
+    }
+
+    /// Creates a writer for a face with a 100..=900 `wght` axis.
+    #[cfg(feature = "variable-fonts")]
+    fn wght_writer(face: &Face) -> FontWriter {
+        use crate::writer::TtfType::*;
+
+        let mut writer = FontWriter::from_face(face);
+        writer.set_table(Tag::from_bytes(b"fvar"), crate::writer::convert(&[
+            UInt16(1), // major version
+            UInt16(0), // minor version
+            UInt16(16), // axes array offset
+            UInt16(2), // reserved
+            UInt16(1), // axis count
+            UInt16(20), // axis size
+            UInt16(0), // instance count
+            UInt16(8), // instance size
+            // Axis 0
+            Raw(b"wght"), // tag
+            Int32(100 << 16), // min value
+            Int32(400 << 16), // default value
+            Int32(900 << 16), // max value
+            UInt16(0), // flags
+            UInt16(256), // name ID
+        ]));
+        writer
+    }
+
+    /// Creates a writer for a face with a `wght` axis and `MVAR` deltas,
+    /// which are applied at the maximum weight.
+    #[cfg(feature = "variable-fonts")]
+    fn mvar_writer(face: &Face, records: &[(&'static [u8; 4], i16)]) -> FontWriter {
+        use crate::writer::TtfType::*;
+
+        let mut writer = wght_writer(face);
+        let count = records.len() as u16;
+        let mut mvar = vec![
+            UInt32(0x00010000), // version
+            UInt16(0), // reserved
+            UInt16(8), // value record size
+            UInt16(count), // value record count
+            UInt16(12 + 8 * count), // item variation store offset
+        ];
+        for (i, (tag, _)) in records.iter().enumerate() {
+            mvar.extend_from_slice(&[
+                Raw(*tag), // value tag
+                UInt16(0), // delta-set outer index
+                UInt16(i as u16), // delta-set inner index
+            ]);
+        }
+        mvar.extend_from_slice(&[
+            // Item Variation Store
+            UInt16(1), // format
+            UInt32(12), // region list offset
+            UInt16(1), // data count
+            UInt32(22), // data offset [0]
+            // Region List
+            UInt16(1), // axis count
+            UInt16(1), // region count
+            Int16(0), // start coordinate
+            Int16(0x4000), // peak coordinate
+            Int16(0x4000), // end coordinate
+            // Item Variation Data
+            UInt16(count), // item count
+            UInt16(1), // short delta count
+            UInt16(1), // region index count
+            UInt16(0), // region index [0]
+        ]);
+        for (_, delta) in records {
+            mvar.push(Int16(*delta)); // delta
+        }
+        writer.set_table(Tag::from_bytes(b"MVAR"), crate::writer::convert(&mvar));
+        writer
+    }
+
+    #[cfg(feature = "variable-fonts")]
+    #[test]
+    fn phantom_points_ver_advance() {
+        use crate::writer::TtfType::*;
+
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let mut writer = wght_writer(&face);
+
+        let mut vhea = crate::writer::convert(&[
+            UInt32(0x00011000), // version
+            Int16(500), // ascender
+            Int16(-500), // descender
+        ]);
+        vhea.resize(34, 0);
+        vhea.extend_from_slice(&crate::writer::convert(&[
+            UInt16(1), // number of long vertical metrics
+        ]));
+        writer.set_table(Tag::from_bytes(b"vhea"), vhea);
+        writer.set_table(Tag::from_bytes(b"vmtx"), crate::writer::convert(&[
+            UInt16(1000), // advance height [0]
+            Int16(100), // top side bearing [0]
+            Int16(100), // top side bearing [1]
+        ]));
+
+        writer.set_table(Tag::from_bytes(b"gvar"), crate::writer::convert(&[
+            UInt32(0x00010000), // version
+            UInt16(1), // axis count
+            UInt16(0), // shared tuple count
+            UInt32(26), // offset to shared tuples
+            UInt16(2), // glyph count
+            UInt16(0), // flags: short offsets
+            UInt32(26), // offset to glyph variation data array
+            UInt16(0), // offset 0
+            UInt16(9), // offset 1: 18 / 2
+            UInt16(9), // offset 2
+            // Glyph 0 variation data
+            UInt16(1), // tuple variation count: 1
+            UInt16(10), // offset to serialized data
+            // Tuple 0
+            UInt16(8), // serialized data size
+            UInt16(0xA000), // tuple index: embedded peak, private point numbers
+            Int16(0x4000), // peak: 1.0
+            // Tuple 0 point numbers
+            UInt8(2), // count
+            UInt8(0x01), // control: 2 bytes
+            UInt8(10), // top side bearing phantom point
+            UInt8(1), // advance height phantom point
+            // Tuple 0 deltas
+            UInt8(0x81), // control: 2 x zeros
+            UInt8(0x01), // control: 2 bytes
+            Int8(30), // y 10
+            Int8(-10), // y 11
+        ]));
+
+        let font = writer.finish();
+        let mut face = Face::from_slice(&font, 0).unwrap();
+        assert_eq!(face.glyph_ver_advance(GlyphId(0)), Some(1000));
+
+        face.set_variation(Tag::from_bytes(b"wght"), 900.0).unwrap();
+        assert_eq!(face.glyph_ver_advance(GlyphId(0)), Some(1040));
+        assert_eq!(face.glyph_hor_advance(GlyphId(0)), Some(600));
+        // No variation data.
+        assert_eq!(face.glyph_ver_advance(GlyphId(1)), Some(1000));
+
+        face.set_variation(Tag::from_bytes(b"wght"), 650.0).unwrap();
+        assert_eq!(face.glyph_ver_advance(GlyphId(0)), Some(1020));
+    }
+
+    #[cfg(feature = "variable-fonts")]
+    #[test]
+    fn vertical_variations() {
+        use crate::writer::TtfType::*;
+
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let mut writer = wght_writer(&face);
+
+        let mut vhea = crate::writer::convert(&[
+            UInt32(0x00011000), // version
+            Int16(500), // ascender
+            Int16(-500), // descender
+        ]);
+        vhea.resize(34, 0);
+        vhea.extend_from_slice(&crate::writer::convert(&[
+            UInt16(1), // number of long vertical metrics
+        ]));
+        writer.set_table(Tag::from_bytes(b"vhea"), vhea);
+        writer.set_table(Tag::from_bytes(b"vmtx"), crate::writer::convert(&[
+            UInt16(1000), // advance height [0]
+            Int16(100), // top side bearing [0]
+            Int16(100), // top side bearing [1]
+        ]));
+        writer.set_table(Tag::from_bytes(b"VORG"), crate::writer::convert(&[
+            UInt16(1), // major version
+            UInt16(0), // minor version
+            Int16(880), // default vertical origin
+            UInt16(0), // number of metrics
+        ]));
+
+        writer.set_table(Tag::from_bytes(b"VVAR"), crate::writer::convert(&[
+            UInt32(0x00010000), // version
+            UInt32(24), // item variation store offset
+            UInt32(58), // advance height mapping offset
+            UInt32(0), // top side bearing mapping offset
+            UInt32(0), // bottom side bearing mapping offset
+            UInt32(63), // vertical origin mapping offset
+            // Item Variation Store
+            UInt16(1), // format
+            UInt32(12), // region list offset
+            UInt16(1), // data count
+            UInt32(22), // data offset [0]
+            // Region List
+            UInt16(1), // axis count
+            UInt16(1), // region count
+            Int16(0), // start coordinate
+            Int16(0x4000), // peak coordinate
+            Int16(0x4000), // end coordinate
+            // Item Variation Data
+            UInt16(2), // item count
+            UInt16(1), // short delta count
+            UInt16(1), // region index count
+            UInt16(0), // region index [0]
+            Int16(40), // delta [0]
+            Int16(-20), // delta [1]
+            // Advance Height Mapping
+            UInt8(0), // format
+            UInt8(0), // entry format: 1 byte, 1 inner index bit
+            UInt16(1), // map count
+            UInt8(0), // entry [0]: outer 0, inner 0
+            // Vertical Origin Mapping
+            UInt8(0), // format
+            UInt8(0), // entry format: 1 byte, 1 inner index bit
+            UInt16(1), // map count
+            UInt8(1), // entry [0]: outer 0, inner 1
+        ]));
+
+        let font = writer.finish();
+        let mut face = Face::from_slice(&font, 0).unwrap();
+        assert_eq!(face.glyph_ver_advance(GlyphId(0)), Some(1000));
+        assert_eq!(face.glyph_y_origin(GlyphId(0)), Some(880));
+
+        face.set_variation(Tag::from_bytes(b"wght"), 900.0).unwrap();
+        assert_eq!(face.glyph_ver_advance(GlyphId(0)), Some(1040));
+        assert_eq!(face.glyph_y_origin(GlyphId(0)), Some(860));
+        // Glyphs after the last mapping entry use it.
+        assert_eq!(face.glyph_ver_advance(GlyphId(1)), Some(1040));
+        assert_eq!(face.glyph_y_origin(GlyphId(1)), Some(860));
+
+        face.set_variation(Tag::from_bytes(b"wght"), 650.0).unwrap();
+        assert_eq!(face.glyph_ver_advance(GlyphId(0)), Some(1020));
+        assert_eq!(face.glyph_y_origin(GlyphId(0)), Some(870));
+    }
+
+    #[cfg(feature = "variable-fonts")]
+    #[test]
+    fn mvar_metrics() {
+        use crate::writer::TtfType::*;
+
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(face.metrics_variation(Tag::from_bytes(b"gsp0")), None);
+
+        // Records must be sorted by tag.
+        let mut writer = mvar_writer(&face, &[
+            (b"gsp0", 40),
+            (b"hasc", 100),
+            (b"hcla", 50),
+            (b"hcld", -20),
+            (b"vcof", 5),
+            (b"vcrn", 3),
+            (b"vcrs", 1),
+        ]);
+
+        let mut os_2 = crate::writer::convert(&[
+            UInt16(1), // version
+        ]);
+        os_2.resize(74, 0);
+        os_2.extend_from_slice(&crate::writer::convert(&[
+            UInt16(900), // Windows ascender
+            UInt16(300), // Windows descender
+        ]));
+        os_2.resize(86, 0);
+        writer.set_table(Tag::from_bytes(b"OS/2"), os_2);
+
+        let mut vhea = crate::writer::convert(&[
+            UInt32(0x00011000), // version
+            Int16(500), // ascender
+            Int16(-500), // descender
+        ]);
+        vhea.resize(18, 0);
+        vhea.extend_from_slice(&crate::writer::convert(&[
+            Int16(0), // caret slope rise
+            Int16(1), // caret slope run
+            Int16(0), // caret offset
+        ]));
+        vhea.resize(34, 0);
+        vhea.extend_from_slice(&crate::writer::convert(&[
+            UInt16(1), // number of long vertical metrics
+        ]));
+        writer.set_table(Tag::from_bytes(b"vhea"), vhea);
+
+        let font = writer.finish();
+        let mut face = Face::from_slice(&font, 0).unwrap();
+        // Deltas are not applied at default coordinates.
+        assert_eq!(face.windows_ascender(), Some(900));
+        assert_eq!(face.vertical_caret_slope_run(), Some(1));
+        assert_eq!(face.metrics_variation(Tag::from_bytes(b"gsp0")), Some(0.0));
+
+        face.set_variation(Tag::from_bytes(b"wght"), 900.0).unwrap();
+        assert_eq!(face.windows_ascender(), Some(950));
+        assert_eq!(face.windows_descender(), Some(280));
+        assert_eq!(face.vertical_caret_slope_rise(), Some(1));
+        assert_eq!(face.vertical_caret_slope_run(), Some(4));
+        assert_eq!(face.vertical_caret_offset(), Some(5));
+        // `hasc` is not applied to `hhea` metrics.
+        assert_eq!(face.ascender(), 1024);
+        assert_eq!(face.metrics_variation(Tag::from_bytes(b"gsp0")), Some(40.0));
+        assert_eq!(face.metrics_variation(Tag::from_bytes(b"xhgt")), None);
+
+        // Half way, deltas are interpolated.
+        face.set_variation(Tag::from_bytes(b"wght"), 650.0).unwrap();
+        assert_eq!(face.windows_ascender(), Some(925));
+        assert_eq!(face.metrics_variation(Tag::from_bytes(b"gsp0")), Some(20.0));
+    }
+
+    #[cfg(feature = "variable-fonts")]
+    #[test]
+    fn variable_decoration_metrics() {
+        use crate::writer::TtfType::*;
+
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(face.underline_position(), None);
+        assert_eq!(face.strikeout_thickness(), None);
+
+        let mut writer = mvar_writer(&face, &[
+            (b"stro", 20),
+            (b"strs", 5),
+            (b"undo", -10),
+            (b"unds", 10),
+        ]);
+
+        let mut post = crate::writer::convert(&[
+            UInt32(0x00030000), // version: 3.0
+            UInt32(0), // italic angle
+            Int16(-100), // underline position
+            Int16(50), // underline thickness
+        ]);
+        post.resize(32, 0);
+        writer.set_table(Tag::from_bytes(b"post"), post);
+
+        let mut os_2 = crate::writer::convert(&[
+            UInt16(1), // version
+        ]);
+        os_2.resize(26, 0);
+        os_2.extend_from_slice(&crate::writer::convert(&[
+            Int16(40), // strikeout size
+            Int16(260), // strikeout position
+        ]));
+        os_2.resize(86, 0);
+        writer.set_table(Tag::from_bytes(b"OS/2"), os_2);
+
+        let font = writer.finish();
+        let mut face = Face::from_slice(&font, 0).unwrap();
+        assert_eq!(face.underline_position(), Some(-100));
+        assert_eq!(face.underline_thickness(), Some(50));
+        assert_eq!(face.strikeout_position(), Some(260));
+        assert_eq!(face.strikeout_thickness(), Some(40));
+
+        face.set_variation(Tag::from_bytes(b"wght"), 900.0).unwrap();
+        assert_eq!(face.underline_position(), Some(-110));
+        assert_eq!(face.underline_thickness(), Some(60));
+        assert_eq!(face.strikeout_position(), Some(280));
+        assert_eq!(face.strikeout_thickness(), Some(45));
+        assert_eq!(face.underline_metrics(), Some(LineMetrics { position: -110, thickness: 60 }));
+    }
+
+    #[test]
+    fn baselines() {
+        use crate::base::BaselineTag;
+
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let latn = Tag::from_bytes(b"latn");
+        let hani = Tag::from_bytes(b"hani");
+        assert_eq!(face.baseline(BaselineTag::Roman, latn, None), Some(0));
+        // No `OS/2` and `MATH` tables.
+        assert_eq!(face.baseline(BaselineTag::IdeographicBottom, hani, None), None);
+        assert_eq!(face.baseline(BaselineTag::Math, latn, None), None);
+    }
+
+    #[test]
+    fn ver_origin() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        // No `VORG` and `vmtx`.
+        assert_eq!(face.glyph_ver_origin(GlyphId(1)), face.ascender());
+    }
+
+    #[test]
+    fn smallest_code_points_of_malformed_groups() {
+        use crate::writer::TtfType::*;
+
+        let data = crate::writer::convert(&[
+            UInt16(0), // version
+            UInt16(2), // number of subtables
+            // Encoding Record [0]
+            UInt16(3), // platform ID: Windows
+            UInt16(1), // encoding ID: Unicode BMP
+            UInt32(20), // subtable offset
+            // Encoding Record [1]
+            UInt16(3), // platform ID: Windows
+            UInt16(10), // encoding ID: Unicode full repertoire
+            UInt32(52), // subtable offset
+            // Subtable [0]
+            UInt16(4), // format
+            UInt16(32), // length
+            UInt16(0), // language
+            UInt16(4), // 2 x segCount
+            UInt16(4), // search range
+            UInt16(1), // entry selector
+            UInt16(0), // range shift
+            UInt16(0x42), // end char code [0]
+            UInt16(0xFFFF), // end char code [1]
+            UInt16(0), // reserved
+            UInt16(0x42), // start char code [0]
+            UInt16(0xFFFF), // start char code [1]
+            Int16(-0x41), // delta [0]
+            Int16(1), // delta [1]
+            UInt16(0), // offset [0]
+            UInt16(0), // offset [1]
+            // Subtable [1]
+            UInt16(12), // format
+            UInt16(0), // reserved
+            UInt32(40), // length
+            UInt32(0), // language
+            UInt32(2), // number of groups
+            // Group [0]
+            UInt32(0x41), // start char code
+            UInt32(0x41), // end char code
+            UInt32(1), // start glyph ID
+            // Group [1]: a corrupted end and out of range glyph IDs
+            UInt32(0x100), // start char code
+            UInt32(4278191298), // end char code
+            UInt32(0x10000), // start glyph ID
+        ]);
+
+        let subtables = cmap::parse(&data).unwrap();
+        let glyphs: GlyphSet = [GlyphId(1), GlyphId(2), GlyphId(5)].iter().cloned().collect();
+        // The smallest code point is used even when it's in the next subtable.
+        assert_eq!(smallest_code_points(subtables, &glyphs, 3), [None, Some(0x41), None]);
+        assert_eq!(smallest_code_points(subtables, &GlyphSet::new(), 3), [None, None, None]);
+    }
+
+    #[test]
+    fn fmtx_point() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(face.fmtx_point(0), None);
+    }
+
+    #[test]
+    fn size_report() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let report = face.size_report();
+
+        let tables_size: u64 = report.tables.iter().map(|t| u64::from(t.length)).sum();
+        let directory_size = 12 + 16 * report.tables.len() as u64;
+        assert_eq!(report.total_size, tables_size + directory_size);
+        assert!(report.tables.windows(2).all(|w| w[0].length >= w[1].length));
+        let percentage: f32 = report.tables.iter().map(|t| t.percentage).sum();
+        assert!(percentage > 0.0 && percentage < 100.0);
+
+        let glyphs = report.glyphs.unwrap();
+        let glyf_len = face.table_data(Tag::from_bytes(b"glyf")).unwrap().len() as u64;
+        assert!(glyphs.total <= glyf_len);
+        assert!(glyphs.min <= glyphs.median && glyphs.median <= glyphs.max);
+        assert_eq!(glyphs.largest.len() + usize::from(glyphs.empty), 2);
+        assert_eq!(glyphs.largest[0].1, glyphs.max);
+    }
+
     #[test]
     fn empty_font() {
         assert_eq!(
@@ -1819,6 +5845,452 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "image-decoding")]
+    fn decode_bitmap() {
+        let image = RasterGlyphImage {
+            x: 0,
+            y: 0,
+            width: 3,
+            height: 2,
+            pixels_per_em: 12,
+            format: RasterImageFormat::BitmapGray2,
+            data: &[0b1100_0100, 0b0010_0000],
+            scale: 1.0,
+        };
+
+        let rgba = image.decode().unwrap();
+        assert_eq!((rgba.width, rgba.height), (3, 2));
+        assert_eq!(rgba.data.iter().skip(3).step_by(4).cloned().collect::<std::vec::Vec<_>>(),
+                   &[255, 0, 85, 0, 170, 0]);
+
+        let image = RasterGlyphImage { format: RasterImageFormat::BitmapMonoPacked, ..image };
+        let rgba = image.decode().unwrap();
+        assert_eq!(rgba.data.iter().skip(3).step_by(4).cloned().collect::<std::vec::Vec<_>>(),
+                   &[255, 255, 0, 0, 0, 255]);
+
+        let image = RasterGlyphImage { format: RasterImageFormat::JPEG, ..image };
+        assert!(image.decode().is_none());
+    }
+
+    #[test]
+    fn face_is_send_and_sync() {
+        fn check<T: Send + Sync>() {}
+        check::<Face>();
+    }
+
+    #[test]
+    fn color_glyph_formats() {
+        use crate::writer::TtfType::*;
+
+        let data = crate::writer::convert(&[
+            TrueTypeMagic, // magic
+            UInt16(4), // numTables
+            UInt16(0), // searchRange
+            UInt16(0), // entrySelector
+            UInt16(0), // rangeShift
+            // Table Record [0]
+            Raw(b"SVG "), // tag
+            UInt32(0), // checksum
+            UInt32(172), // offset
+            UInt32(26), // length
+            // Table Record [1]
+            Raw(b"head"), // tag
+            UInt32(0), // checksum
+            UInt32(76), // offset
+            UInt32(54), // length
+            // Table Record [2]
+            Raw(b"hhea"), // tag
+            UInt32(0), // checksum
+            UInt32(130), // offset
+            UInt32(36), // length
+            // Table Record [3]
+            Raw(b"maxp"), // tag
+            UInt32(0), // checksum
+            UInt32(166), // offset
+            UInt32(6), // length
+            // Font Header Table
+            UInt32(0x00010000), // version
+            UInt32(0x00010000), // font revision
+            UInt32(0), // checksum adjustment
+            UInt32(0x5F0F3CF5), // magic number
+            UInt16(0), // flags
+            UInt16(2048), // units per em
+            Raw(&[0; 34]), // the rest of the table
+            // Horizontal Header Table
+            Raw(&[0; 36]),
+            // Maximum Profile Table
+            UInt32(0x00005000), // version
+            UInt16(2), // number of glyphs
+            // SVG Table
+            UInt16(0), // version
+            UInt32(10), // document list offset
+            UInt32(0), // reserved
+            UInt16(1), // count
+            UInt16(1), // start glyph
+            UInt16(1), // end glyph
+            UInt32(14), // document offset
+            UInt32(2), // document length
+            UInt8(b'<'), UInt8(b'>'), // document
+        ]);
+
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert!(face.is_color_font());
+        assert_eq!(face.color_glyph_formats(), ColorGlyphFormats { svg: true, ..Default::default() });
+        assert!(face.glyph_color_formats(GlyphId(0)).is_empty());
+        assert!(face.glyph_color_formats(GlyphId(1)).svg);
+        assert!(face.tables().svg.is_some());
+        assert!(face.tables().cmap.is_none());
+
+        // Required tables are limited too.
+        let limits = Limits { max_table_size: 25, ..Limits::default() };
+        let options = ParseOptions { limits, ..ParseOptions::default() };
+        let face = Face::from_slice_with_options(&data, 0, options);
+        assert_eq!(face.unwrap_err(), FaceParsingError::NoHeadTable);
+    }
+
+    #[test]
+    fn table_size_limit() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let limits = Limits { max_table_size: 60, ..Limits::default() };
+        // Ignored tables are not errors even in the strict mode.
+        let options = ParseOptions { mode: ParsingMode::Strict, limits };
+        let face = Face::from_slice_with_options(&data, 0, options).unwrap();
+        assert!(face.table_data(Tag::from_bytes(b"glyf")).is_none());
+        assert!(face.table_data(Tag::from_bytes(b"head")).is_some());
+
+        let errors: std::vec::Vec<_> = face.table_errors().map(|e| (e.tag, e.kind)).collect();
+        assert_eq!(errors, &[(Tag::from_bytes(b"glyf"), TableErrorKind::LimitExceeded)]);
+    }
+
+    #[test]
+    fn table_errors() {
+        use crate::writer::TtfType::*;
+
+        let data = crate::writer::convert(&[
+            TrueTypeMagic, // magic
+            UInt16(7), // numTables
+            UInt16(0), // searchRange
+            UInt16(0), // entrySelector
+            UInt16(0), // rangeShift
+            // Table Records
+            Raw(b"GDEF"), UInt32(0), UInt32(124), UInt32(4), // tag, checksum, offset, length
+            Raw(b"OS/2"), UInt32(0), UInt32(128), UInt32(4), // tag, checksum, offset, length
+            Raw(b"head"), UInt32(0), UInt32(132), UInt32(54), // tag, checksum, offset, length
+            Raw(b"hhea"), UInt32(0), UInt32(188), UInt32(36), // tag, checksum, offset, length
+            Raw(b"maxp"), UInt32(0), UInt32(224), UInt32(6), // tag, checksum, offset, length
+            Raw(b"name"), UInt32(0), UInt32(1000), UInt32(10), // tag, checksum, offset, length
+            Raw(b"post"), UInt32(0), UInt32(232), UInt32(32), // tag, checksum, offset, length
+            // Glyph Definition Table
+            UInt16(1), // major version
+            UInt16(0), // minor version
+            // OS/2 Table
+            UInt16(0), // version
+            UInt16(0), // average char width
+            // Font Header Table
+            UInt32(0x00010000), // version
+            UInt32(0x00010000), // font revision
+            UInt32(0), // checksum adjustment
+            UInt32(0x5F0F3CF5), // magic number
+            UInt16(0), // flags
+            UInt16(2048), // units per em
+            Raw(&[0; 34]), // the rest of the table
+            UInt16(0), // padding
+            // Horizontal Header Table
+            Raw(&[0; 36]),
+            // Maximum Profile Table
+            UInt32(0x00005000), // version
+            UInt16(1), // number of glyphs
+            UInt16(0), // padding
+            // PostScript Table
+            UInt16(9), // major version
+            Raw(&[0; 30]), // the rest of the table
+        ]);
+
+        let face = Face::from_slice(&data, 0).unwrap();
+        let errors: std::vec::Vec<_> = face.table_errors().map(|e| (e.tag, e.kind)).collect();
+        assert_eq!(errors, &[
+            (Tag::from_bytes(b"GDEF"), TableErrorKind::Malformed),
+            (Tag::from_bytes(b"OS/2"), TableErrorKind::Truncated),
+            (Tag::from_bytes(b"name"), TableErrorKind::OutOfBounds),
+            (Tag::from_bytes(b"post"), TableErrorKind::UnsupportedVersion),
+        ]);
+
+        let options = ParseOptions { mode: ParsingMode::Strict, ..ParseOptions::default() };
+        assert_eq!(
+            Face::from_slice_with_options(&data, 0, options).unwrap_err(),
+            FaceParsingError::MalformedTable(TableError {
+                tag: Tag::from_bytes(b"GDEF"),
+                kind: TableErrorKind::Malformed,
+            })
+        );
+    }
+
+    #[test]
+    fn warnings() {
+        use crate::writer::TtfType::*;
+
+        let data = crate::writer::convert(&[
+            TrueTypeMagic, // magic
+            UInt16(5), // numTables
+            UInt16(0), // searchRange
+            UInt16(0), // entrySelector
+            UInt16(0), // rangeShift
+            // Table Records
+            Raw(b"hhea"), UInt32(0), UInt32(92), UInt32(36), // tag, checksum, offset, length
+            Raw(b"head"), UInt32(0), UInt32(128), UInt32(54), // tag, checksum, offset, length
+            Raw(b"maxp"), UInt32(0), UInt32(184), UInt32(6), // tag, checksum, offset, length
+            Raw(b"hmtx"), UInt32(0), UInt32(192), UInt32(8), // tag, checksum, offset, length
+            Raw(b"cmap"), UInt32(0), UInt32(200), UInt32(24), // tag, checksum, offset, length
+            // Horizontal Header Table
+            Raw(&[0; 34]), // the start of the table
+            UInt16(1), // number of h metrics
+            // Font Header Table
+            UInt32(0x00010000), // version
+            UInt32(0x00010000), // font revision
+            UInt32(0), // checksum adjustment
+            UInt32(0x5F0F3CF5), // magic number
+            UInt16(0), // flags
+            UInt16(2048), // units per em
+            Raw(&[0; 34]), // the rest of the table
+            UInt16(0), // padding
+            // Maximum Profile Table
+            UInt32(0x00005000), // version
+            UInt16(1), // number of glyphs
+            UInt16(0), // padding
+            // Horizontal Metrics Table
+            UInt16(500), // advance width [0]
+            Int16(0), // side bearing [0]
+            Int16(0), // extra data
+            Int16(0), // extra data
+            // Character to Glyph Index Mapping Table
+            UInt16(0), // version
+            UInt16(1), // number of tables
+            UInt16(0), // platform ID
+            UInt16(3), // encoding ID
+            UInt32(12), // offset
+            // Subtable Format 6
+            UInt16(6), // format
+            UInt16(12), // length
+            UInt16(0), // language
+            UInt16(0x41), // first code
+            UInt16(1), // entry count
+            UInt16(5), // glyph [0]
+        ]);
+
+        let face = Face::from_slice(&data, 0).unwrap();
+        let warnings: std::vec::Vec<_> = face.warnings().collect();
+        assert_eq!(warnings, &[
+            Warning::UnsortedTableRecords,
+            Warning::UnexpectedTableLength { tag: Tag::from_bytes(b"hmtx"), expected: 4, actual: 8 },
+            Warning::GlyphIdOutOfBounds { tag: Tag::from_bytes(b"cmap"), glyph_id: GlyphId(5) },
+        ]);
+
+        let report = sanitize(&data, 0);
+        assert!(!report.is_ok());
+        assert_eq!(report.warnings, &[Warning::UnsortedTableRecords]);
+        assert_eq!(report.tables.len(), 5);
+        assert!(report.tables[0].is_ok());
+        assert_eq!(report.tables[3].tag, Tag::from_bytes(b"hmtx"));
+        assert_eq!(report.tables[3].warnings.len(), 1);
+        assert_eq!(sanitize(&[], 0).error, Some(FaceParsingError::UnknownMagic));
+    }
+
+    #[test]
+    fn checksums() {
+        use crate::writer::TtfType::*;
+
+        let data = crate::writer::convert(&[
+            TrueTypeMagic, // magic
+            UInt16(3), // numTables
+            UInt16(0), // searchRange
+            UInt16(0), // entrySelector
+            UInt16(0), // rangeShift
+            // Table Record [0]
+            Raw(b"head"), // tag
+            UInt32(0x5F1144F5), // checksum
+            UInt32(60), // offset
+            UInt32(54), // length
+            // Table Record [1]
+            Raw(b"hhea"), // tag
+            UInt32(0), // checksum
+            UInt32(116), // offset
+            UInt32(36), // length
+            // Table Record [2]
+            Raw(b"maxp"), // tag
+            UInt32(1), // checksum
+            UInt32(152), // offset
+            UInt32(6), // length
+            // Font Header Table
+            UInt32(0x00010000), // version
+            UInt32(0x00010000), // font revision
+            UInt32(0xB55994F2), // checksum adjustment
+            UInt32(0x5F0F3CF5), // magic number
+            UInt16(0), // flags
+            UInt16(2048), // units per em
+            Raw(&[0; 34]), // the rest of the table
+            UInt16(0), // padding
+            // Horizontal Header Table
+            Raw(&[0; 36]),
+            // Maximum Profile Table
+            UInt32(0x00005000), // version
+            UInt16(1), // number of glyphs
+        ]);
+
+        let face = Face::from_slice(&data, 0).unwrap();
+        let checksums: std::vec::Vec<_> = face.table_checksums().collect();
+        assert_eq!(checksums.len(), 3);
+        assert!(checksums[0].is_valid());
+        assert!(checksums[1].is_valid());
+        assert_eq!(checksums[2].actual, 0x00015000);
+        assert!(!checksums[2].is_valid());
+
+        let adjustment = face.checksum_adjustment().unwrap();
+        assert_eq!(adjustment.tag, Tag::from_bytes(b"head"));
+        assert!(adjustment.is_valid());
+    }
+
+    #[test]
+    fn bitmap_only_font() {
+        use crate::writer::TtfType::*;
+
+        let data = crate::writer::convert(&[
+            TrueTypeMagic, // magic
+            UInt16(3), // numTables
+            UInt16(0), // searchRange
+            UInt16(0), // entrySelector
+            UInt16(0), // rangeShift
+            // Table Record [0]
+            Raw(b"bhed"), // tag
+            UInt32(0), // checksum
+            UInt32(60), // offset
+            UInt32(54), // length
+            // Table Record [1]
+            Raw(b"hhea"), // tag
+            UInt32(0), // checksum
+            UInt32(114), // offset
+            UInt32(36), // length
+            // Table Record [2]
+            Raw(b"maxp"), // tag
+            UInt32(0), // checksum
+            UInt32(150), // offset
+            UInt32(6), // length
+            // Bitmap Header Table
+            UInt32(0x00010000), // version
+            UInt32(0x00010000), // font revision
+            UInt32(0), // checksum adjustment
+            UInt32(0x5F0F3CF5), // magic number
+            UInt16(0), // flags
+            UInt16(2048), // units per em
+            Raw(&[0; 34]), // the rest of the table
+            // Horizontal Header Table
+            Raw(&[0; 36]),
+            // Maximum Profile Table
+            UInt32(0x00005000), // version
+            UInt16(1), // number of glyphs
+        ]);
+
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(face.units_per_em(), Some(2048));
+        assert!(face.glyph_bounding_box(GlyphId(0)).is_none());
+
+        let tags: std::vec::Vec<_> = face.table_records().map(|r| r.tag).collect();
+        assert_eq!(tags, &[Tag::from_bytes(b"bhed"), Tag::from_bytes(b"hhea"), Tag::from_bytes(b"maxp")]);
+        assert_eq!(face.table_data(Tag::from_bytes(b"maxp")).map(|data| data.len()), Some(6));
+
+        let options = ParseOptions { mode: ParsingMode::Strict, ..ParseOptions::default() };
+        assert!(Face::from_slice_with_options(&data, 0, options).is_ok());
+    }
+
+    #[test]
+    fn raw_tables() {
+        use crate::writer::TtfType::*;
+
+        let data = crate::writer::convert(&[
+            TrueTypeMagic, // magic
+            UInt16(4), // numTables
+            UInt16(0), // searchRange
+            UInt16(0), // entrySelector
+            UInt16(0), // rangeShift
+            // Table Record [0]
+            Raw(b"head"), // tag
+            UInt32(0x01020304), // checksum
+            UInt32(76), // offset
+            UInt32(54), // length
+            // Table Record [1]
+            Raw(b"hhea"), // tag
+            UInt32(0), // checksum
+            UInt32(132), // offset
+            UInt32(36), // length
+            // Table Record [2]
+            Raw(b"maxp"), // tag
+            UInt32(0), // checksum
+            UInt32(168), // offset
+            UInt32(6), // length
+            // Table Record [3]
+            Raw(b"zzzz"), // tag
+            UInt32(0xAABBCCDD), // checksum
+            UInt32(176), // offset
+            UInt32(3), // length
+            // Font Header Table
+            UInt32(0x00010000), // version
+            UInt32(0x00010000), // font revision
+            UInt32(0), // checksum adjustment
+            UInt32(0x5F0F3CF5), // magic number
+            UInt16(0), // flags
+            UInt16(1000), // units per em
+            Raw(&[0; 34]), // the rest of the table
+            UInt16(0), // padding
+            // Horizontal Header Table
+            Raw(&[0; 36]),
+            // Maximum Profile Table
+            UInt32(0x00005000), // version
+            UInt16(1), // number of glyphs
+            UInt16(0), // padding
+            // Unknown Table
+            Raw(&[1, 2, 3]),
+        ]);
+
+        let face = Face::from_slice(&data, 0).unwrap();
+        let records: std::vec::Vec<_> = face.table_records().collect();
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0], TableRecord {
+            tag: Tag::from_bytes(b"head"),
+            checksum: 0x01020304,
+            offset: 76,
+            length: 54,
+        });
+        assert_eq!(records[3], TableRecord {
+            tag: Tag::from_bytes(b"zzzz"),
+            checksum: 0xAABBCCDD,
+            offset: 176,
+            length: 3,
+        });
+
+        // Unsupported tables are accessible too.
+        assert_eq!(face.table_data(Tag::from_bytes(b"zzzz")), Some(&[1, 2, 3][..]));
+        assert_eq!(face.table_data(Tag::from_bytes(b"hhea")).map(|data| data.len()), Some(36));
+        assert_eq!(face.table_data(Tag::from_bytes(b"glyf")), None);
+    }
+
+    #[test]
+    fn face_tables() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let tables = face.tables();
+        assert!(tables.cmap.is_some());
+        assert!(tables.meta.is_none());
+        assert!(tables.svg.is_none());
+        assert_eq!(Some(tables.head), face.table_data(Tag::from_bytes(b"head")));
+        assert_eq!(tables.hmtx, face.table_data(Tag::from_bytes(b"hmtx")));
+        assert!(tables.glyf.is_some());
+        assert!(tables.loca.is_some());
+        assert!(tables.cff.is_none());
+        assert!(tables.os_2.is_none());
+        assert!(tables.name.is_none());
+        assert!(tables.post.is_none());
+    }
+
     #[test]
     fn tables_count_overflow() {
         let data = &[
@@ -1835,6 +6307,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn apple_magic() {
+        for magic in &[b"true", b"typ1"] {
+            let mut data = magic.to_vec();
+            data.extend_from_slice(&[
+                0x00, 0x00, // numTables: 0
+                0x00, 0x00, // searchRange: 0
+                0x00, 0x00, // entrySelector: 0
+                0x00, 0x00, // rangeShift: 0
+            ]);
+
+            assert_eq!(
+                Face::from_slice(&data, 0).unwrap_err(),
+                FaceParsingError::NoHeadTable
+            );
+        }
+    }
+
     #[test]
     fn empty_font_collection() {
         let data = &[
@@ -1851,6 +6341,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn font_collection_signature() {
+        let data = &[
+            0x74, 0x74, 0x63, 0x66, // magic
+            0x00, 0x02, // majorVersion: 2
+            0x00, 0x00, // minorVersion: 0
+            0x00, 0x00, 0x00, 0x00, // numFonts: 0
+            0x44, 0x53, 0x49, 0x47, // ulDsigTag: DSIG
+            0x00, 0x00, 0x00, 0x08, // ulDsigLength: 8
+            0x00, 0x00, 0x00, 0x18, // ulDsigOffset: 24
+            // DSIG
+            0x00, 0x00, 0x00, 0x01, // version: 1
+            0x00, 0x00, // numSignatures: 0
+            0x00, 0x01, // flags: cannot be resigned
+        ];
+
+        let collection = FontCollection::parse(data).unwrap();
+        assert!(collection.is_empty());
+        assert_eq!(collection.signature(), Some(CollectionSignature { offset: 24, length: 8 }));
+        let table = collection.signature_table().unwrap();
+        assert!(table.is_empty());
+        assert!(table.flags().cannot_be_resigned());
+    }
+
     #[test]
     fn font_collection_num_fonts_overflow() {
         let data = &[
@@ -1867,6 +6381,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn font_collection() {
+        use crate::writer::TtfType::*;
+
+        let data = crate::writer::convert(&[
+            Raw(b"ttcf"), // magic
+            UInt16(1), // majorVersion
+            UInt16(0), // minorVersion
+            UInt32(2), // numFonts
+            UInt32(20), // offset [0]
+            UInt32(80), // offset [1]
+            // Face [0]
+            TrueTypeMagic, // magic
+            UInt16(3), // numTables
+            UInt16(0), // searchRange
+            UInt16(0), // entrySelector
+            UInt16(0), // rangeShift
+            Raw(b"head"), UInt32(0), UInt32(140), UInt32(54), // table record [0]
+            Raw(b"hhea"), UInt32(0), UInt32(248), UInt32(36), // table record [1]
+            Raw(b"maxp"), UInt32(0), UInt32(284), UInt32(6), // table record [2]
+            // Face [1]
+            TrueTypeMagic, // magic
+            UInt16(3), // numTables
+            UInt16(0), // searchRange
+            UInt16(0), // entrySelector
+            UInt16(0), // rangeShift
+            Raw(b"head"), UInt32(0), UInt32(194), UInt32(54), // table record [0]
+            Raw(b"hhea"), UInt32(0), UInt32(248), UInt32(36), // table record [1]
+            Raw(b"maxp"), UInt32(0), UInt32(284), UInt32(6), // table record [2]
+            // Font Header Table [0]
+            UInt32(0x00010000), // version
+            UInt32(0x00010000), // font revision
+            UInt32(0), // checksum adjustment
+            UInt32(0x5F0F3CF5), // magic number
+            UInt16(0), // flags
+            UInt16(1000), // units per em
+            Raw(&[0; 34]), // the rest of the table
+            // Font Header Table [1]
+            UInt32(0x00010000), // version
+            UInt32(0x00010000), // font revision
+            UInt32(0), // checksum adjustment
+            UInt32(0x5F0F3CF5), // magic number
+            UInt16(0), // flags
+            UInt16(2048), // units per em
+            Raw(&[0; 34]), // the rest of the table
+            // Horizontal Header Table
+            Raw(&[0; 36]),
+            // Maximum Profile Table
+            UInt32(0x00005000), // version
+            UInt16(1), // number of glyphs
+        ]);
+
+        assert_eq!(fonts_in_collection(&data), Some(2));
+
+        let collection = FontCollection::parse(&data).unwrap();
+        assert_eq!(collection.len(), 2);
+        assert!(collection.signature().is_none());
+        let records: std::vec::Vec<_> = collection.table_records(1).unwrap().collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].tag, Tag::from_bytes(b"head"));
+        assert!(!records[0].is_shared);
+        assert!(records[1].is_shared);
+        assert!(records[2].is_shared);
+        assert!(collection.table_records(2).is_none());
+        assert_eq!(Face::from_slice(&data, 0).unwrap().units_per_em(), Some(1000));
+        assert_eq!(Face::from_slice(&data, 1).unwrap().units_per_em(), Some(2048));
+        assert_eq!(
+            Face::from_slice(&data, 2).unwrap_err(),
+            FaceParsingError::FaceIndexOutOfBounds
+        );
+    }
+
     #[test]
     fn font_index_overflow() {
         let data = &[
@@ -1883,4 +6469,85 @@ mod tests {
             FaceParsingError::FaceIndexOutOfBounds
         );
     }
+
+    #[test]
+    fn glyph_indices() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let text = "ABAA\u{10000}A";
+        let glyphs: std::vec::Vec<_> = face.glyph_indices(text).collect();
+        let expected: std::vec::Vec<_> = text.chars().map(|c| face.glyph_index(c)).collect();
+        assert_eq!(glyphs, expected);
+        assert_eq!(glyphs, &[Some(GlyphId(1)), None, Some(GlyphId(1)), Some(GlyphId(1)), None, Some(GlyphId(1))]);
+        assert_eq!(face.glyph_indices("").count(), 0);
+    }
+
+    #[test]
+    fn ignorable_chars() {
+        assert!(is_ignorable_char('\n'));
+        assert!(is_ignorable_char('\u{7F}'));
+        assert!(is_ignorable_char('\u{180B}'));
+        assert!(is_ignorable_char('\u{200D}'));
+        assert!(is_ignorable_char('\u{FE0F}'));
+        assert!(is_ignorable_char('\u{E01EF}'));
+        assert!(!is_ignorable_char(' '));
+        assert!(!is_ignorable_char('\u{180E}'));
+        assert!(!is_ignorable_char('\u{200B}'));
+        assert!(!is_ignorable_char('\u{E01F0}'));
+    }
+
+    #[test]
+    fn text_coverage() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        assert!(face.supports_text(""));
+        assert!(face.supports_text("A\u{200D}A\u{E0100}\r\n"));
+        assert_eq!(face.first_unsupported_char("AAB"), Some('B'));
+        assert_eq!(face.first_unsupported_char("A\u{FE0F}\u{1F600}B"), Some('\u{1F600}'));
+        // Spaces require a glyph.
+        assert_eq!(face.first_unsupported_char("A A"), Some(' '));
+    }
+
+    #[test]
+    fn windows_line_spacing() {
+        let hhea = LineSpacing { ascender: 1024, descender: -400, line_gap: 0 };
+        // The rest of the 1424 units `hhea` line height goes to the line gap.
+        let windows = hhea.to_windows(900, 300).unwrap();
+        assert_eq!(windows, LineSpacing { ascender: 900, descender: -300, line_gap: 224 });
+        assert_eq!(windows.line_height(), hhea.line_height());
+        // Clipping metrics can be taller than the line.
+        assert_eq!(hhea.to_windows(1200, 400).unwrap().line_gap, 0);
+        assert_eq!(hhea.to_windows(40000, 0), None);
+    }
+
+    #[test]
+    fn numbering_system_digits() {
+        assert_eq!(NumberingSystem::Latin.digit(0), Some('0'));
+        assert_eq!(NumberingSystem::Latin.digit(9), Some('9'));
+        assert_eq!(NumberingSystem::ArabicIndic.digit(3), Some('\u{0663}'));
+        assert_eq!(NumberingSystem::FullWidth.digit(9), Some('\u{FF19}'));
+        assert_eq!(NumberingSystem::Latin.digit(10), None);
+    }
+
+    #[test]
+    fn fullwidth_digit_glyphs() {
+        let glyphs = DigitGlyphs::resolve(NumberingSystem::FullWidth, |c| match c {
+            '\u{2212}' => Some(GlyphId(1)), // minus sign
+            '\u{FF0D}' => Some(GlyphId(2)), // fullwidth hyphen-minus
+            '\u{FF10}'..='\u{FF19}' => Some(GlyphId(3 + c as u16 - 0xFF10)), // fullwidth digits
+            _ => None,
+        });
+        assert_eq!(glyphs.digits[0], Some(GlyphId(3)));
+        assert_eq!(glyphs.digits[9], Some(GlyphId(12)));
+        assert_eq!(glyphs.minus, Some(GlyphId(1)));
+
+        // Without a minus sign, a fullwidth hyphen-minus is used.
+        let glyph_index = |c| match c {
+            '-' => Some(GlyphId(1)), // hyphen-minus
+            '\u{FF0D}' => Some(GlyphId(2)), // fullwidth hyphen-minus
+            _ => None,
+        };
+        assert_eq!(DigitGlyphs::resolve(NumberingSystem::FullWidth, glyph_index).minus, Some(GlyphId(2)));
+        assert_eq!(DigitGlyphs::resolve(NumberingSystem::Latin, glyph_index).minus, Some(GlyphId(1)));
+    }
 }
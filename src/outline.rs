@@ -0,0 +1,704 @@
+use crate::OutlineBuilder;
+
+// Limits the number of lines per curve, so tiny tolerances will not hang the caller.
+const MAX_SEGMENTS: u32 = 256;
+
+/// An `OutlineBuilder` adapter that flattens curves into lines.
+///
+/// Quadratic and cubic curves are split into `line_to` segments,
+/// so the deviation from the original curve does not exceed the tolerance.
+/// Other segments are passed as is.
+///
+/// # Example
+///
+/// ```
+/// use ttf_parser::{FlattenBuilder, GlyphId, OutlineBuilder};
+///
+/// struct Counter(usize);
+/// impl OutlineBuilder for Counter {
+///     fn move_to(&mut self, _: f32, _: f32) {}
+///     fn line_to(&mut self, _: f32, _: f32) { self.0 += 1; }
+///     fn quad_to(&mut self, _: f32, _: f32, _: f32, _: f32) { unreachable!() }
+///     fn curve_to(&mut self, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32) { unreachable!() }
+///     fn close(&mut self) {}
+/// }
+///
+/// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+/// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+/// let mut counter = Counter(0);
+/// face.outline_glyph(GlyphId(1), &mut FlattenBuilder::new(&mut counter, 0.5)).unwrap();
+/// assert_eq!(counter.0, 11);
+/// ```
+pub struct FlattenBuilder<'a> {
+    builder: &'a mut dyn OutlineBuilder,
+    tolerance: f32,
+    x: f32,
+    y: f32,
+}
+
+impl<'a> FlattenBuilder<'a> {
+    /// Creates a new adapter.
+    ///
+    /// `tolerance` is the maximum distance between a curve and its lines, in outline units.
+    /// Must be positive.
+    #[inline]
+    pub fn new(builder: &'a mut dyn OutlineBuilder, tolerance: f32) -> Self {
+        FlattenBuilder { builder, tolerance, x: 0.0, y: 0.0 }
+    }
+
+    /// Returns the tolerance.
+    #[inline]
+    pub fn tolerance(&self) -> f32 {
+        self.tolerance
+    }
+
+    fn emit_lines<F>(&mut self, count: u32, f: F)
+        where F: Fn(f32) -> (f32, f32)
+    {
+        // The last point is emitted separately to keep the curve end point exact.
+        for i in 1..count {
+            let (x, y) = f(i as f32 / count as f32);
+            self.builder.line_to(x, y);
+        }
+    }
+}
+
+impl core::fmt::Debug for FlattenBuilder<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "FlattenBuilder()")
+    }
+}
+
+impl OutlineBuilder for FlattenBuilder<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.builder.move_to(x, y);
+        self.x = x;
+        self.y = y;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.builder.line_to(x, y);
+        self.x = x;
+        self.y = y;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (x0, y0) = (self.x, self.y);
+        // Wang's formula: n = sqrt(|p0 - 2p1 + p2| / (4 * tolerance)).
+        let dx = x0 - 2.0 * x1 + x;
+        let dy = y0 - 2.0 * y1 + y;
+        let tolerance_sq = self.tolerance * self.tolerance;
+        let count = segments_count((dx * dx + dy * dy) / (16.0 * tolerance_sq), 4);
+        self.emit_lines(count, |t| {
+            let mt = 1.0 - t;
+            (
+                mt * mt * x0 + 2.0 * mt * t * x1 + t * t * x,
+                mt * mt * y0 + 2.0 * mt * t * y1 + t * t * y,
+            )
+        });
+
+        self.line_to(x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (x0, y0) = (self.x, self.y);
+        // Wang's formula: n = sqrt(3 * max(|p0 - 2p1 + p2|, |p1 - 2p2 + p3|) / (4 * tolerance)).
+        let dx1 = x0 - 2.0 * x1 + x2;
+        let dy1 = y0 - 2.0 * y1 + y2;
+        let dx2 = x1 - 2.0 * x2 + x;
+        let dy2 = y1 - 2.0 * y2 + y;
+        let dist_sq = f32::max(dx1 * dx1 + dy1 * dy1, dx2 * dx2 + dy2 * dy2);
+        let tolerance_sq = self.tolerance * self.tolerance;
+        let count = segments_count(dist_sq * 9.0 / (16.0 * tolerance_sq), 4);
+        self.emit_lines(count, |t| {
+            let mt = 1.0 - t;
+            let a = mt * mt * mt;
+            let b = 3.0 * mt * mt * t;
+            let c = 3.0 * mt * t * t;
+            let d = t * t * t;
+            (
+                a * x0 + b * x1 + c * x2 + d * x,
+                a * y0 + b * y1 + c * y2 + d * y,
+            )
+        });
+
+        self.line_to(x, y);
+    }
+
+    fn close(&mut self) {
+        self.builder.close();
+    }
+}
+
+/// An `OutlineBuilder` adapter that converts quadratic curves into cubic ones.
+///
+/// The conversion is exact. Useful for consumers that support only cubic curves,
+/// like the ones designed for `CFF` outlines.
+pub struct CubicBuilder<'a> {
+    builder: &'a mut dyn OutlineBuilder,
+    x: f32,
+    y: f32,
+}
+
+impl<'a> CubicBuilder<'a> {
+    /// Creates a new adapter.
+    #[inline]
+    pub fn new(builder: &'a mut dyn OutlineBuilder) -> Self {
+        CubicBuilder { builder, x: 0.0, y: 0.0 }
+    }
+}
+
+impl core::fmt::Debug for CubicBuilder<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "CubicBuilder()")
+    }
+}
+
+impl OutlineBuilder for CubicBuilder<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.builder.move_to(x, y);
+        self.x = x;
+        self.y = y;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.builder.line_to(x, y);
+        self.x = x;
+        self.y = y;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (x0, y0) = (self.x, self.y);
+        self.curve_to(
+            x0 + 2.0 / 3.0 * (x1 - x0), y0 + 2.0 / 3.0 * (y1 - y0),
+            x + 2.0 / 3.0 * (x1 - x), y + 2.0 / 3.0 * (y1 - y),
+            x, y,
+        );
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.builder.curve_to(x1, y1, x2, y2, x, y);
+        self.x = x;
+        self.y = y;
+    }
+
+    fn close(&mut self) {
+        self.builder.close();
+    }
+}
+
+/// An `OutlineBuilder` adapter that approximates cubic curves with quadratic ones.
+///
+/// Each cubic curve is split into pieces, so the deviation of the quadratic curves
+/// from the original one does not exceed the tolerance.
+/// Useful for consumers that support only quadratic curves, like GPU renderers.
+pub struct QuadBuilder<'a> {
+    builder: &'a mut dyn OutlineBuilder,
+    tolerance: f32,
+    x: f32,
+    y: f32,
+}
+
+impl<'a> QuadBuilder<'a> {
+    /// Creates a new adapter.
+    ///
+    /// `tolerance` is the maximum distance between a cubic curve and its approximation,
+    /// in outline units. Must be positive.
+    #[inline]
+    pub fn new(builder: &'a mut dyn OutlineBuilder, tolerance: f32) -> Self {
+        QuadBuilder { builder, tolerance, x: 0.0, y: 0.0 }
+    }
+
+    /// Returns the tolerance.
+    #[inline]
+    pub fn tolerance(&self) -> f32 {
+        self.tolerance
+    }
+}
+
+impl core::fmt::Debug for QuadBuilder<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "QuadBuilder()")
+    }
+}
+
+impl OutlineBuilder for QuadBuilder<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.builder.move_to(x, y);
+        self.x = x;
+        self.y = y;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.builder.line_to(x, y);
+        self.x = x;
+        self.y = y;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.builder.quad_to(x1, y1, x, y);
+        self.x = x;
+        self.y = y;
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (x0, y0) = (self.x, self.y);
+
+        // The error of a single quadratic approximation is sqrt(3) / 36 * |p3 - 3p2 + 3p1 - p0|
+        // and splitting a curve into `n` pieces reduces it by `n^3`.
+        let dx = x - 3.0 * x2 + 3.0 * x1 - x0;
+        let dy = y - 3.0 * y2 + 3.0 * y1 - y0;
+        let tolerance_sq = self.tolerance * self.tolerance;
+        let count = segments_count((dx * dx + dy * dy) * 3.0 / (1296.0 * tolerance_sq), 6);
+
+        let point = |t: f32| {
+            let mt = 1.0 - t;
+            let a = mt * mt * mt;
+            let b = 3.0 * mt * mt * t;
+            let c = 3.0 * mt * t * t;
+            let d = t * t * t;
+            (a * x0 + b * x1 + c * x2 + d * x, a * y0 + b * y1 + c * y2 + d * y)
+        };
+
+        let derivative = |t: f32| {
+            let mt = 1.0 - t;
+            let a = 3.0 * mt * mt;
+            let b = 6.0 * mt * t;
+            let c = 3.0 * t * t;
+            (
+                a * (x1 - x0) + b * (x2 - x1) + c * (x - x2),
+                a * (y1 - y0) + b * (y2 - y1) + c * (y - y2),
+            )
+        };
+
+        let step = 1.0 / count as f32;
+        for i in 0..count {
+            let t0 = i as f32 * step;
+            let t1 = t0 + step;
+            let (px0, py0) = point(t0);
+            let (px3, py3) = if i + 1 == count { (x, y) } else { point(t1) };
+            let (dx0, dy0) = derivative(t0);
+            let (dx3, dy3) = derivative(t1);
+
+            // Control points of the cubic piece.
+            let (px1, py1) = (px0 + dx0 * step / 3.0, py0 + dy0 * step / 3.0);
+            let (px2, py2) = (px3 - dx3 * step / 3.0, py3 - dy3 * step / 3.0);
+
+            self.builder.quad_to(
+                (3.0 * (px1 + px2) - px0 - px3) / 4.0,
+                (3.0 * (py1 + py2) - py0 - py3) / 4.0,
+                px3, py3,
+            );
+        }
+
+        self.x = x;
+        self.y = y;
+    }
+
+    fn close(&mut self) {
+        self.builder.close();
+    }
+}
+
+/// A fill rule.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillRule {
+    /// A point is inside when the winding number is not zero.
+    ///
+    /// TrueType and CFF outlines are designed to be filled with this rule.
+    NonZero,
+    /// A point is inside when the winding number is odd.
+    EvenOdd,
+}
+
+impl Default for FillRule {
+    #[inline]
+    fn default() -> Self {
+        FillRule::NonZero
+    }
+}
+
+// Computes a winding number of a point. Accepts only lines.
+pub(crate) struct WindingCounter {
+    x: f32,
+    y: f32,
+    start_x: f32,
+    start_y: f32,
+    prev_x: f32,
+    prev_y: f32,
+    winding: i32,
+}
+
+impl WindingCounter {
+    pub(crate) fn new(x: f32, y: f32) -> Self {
+        WindingCounter { x, y, start_x: 0.0, start_y: 0.0, prev_x: 0.0, prev_y: 0.0, winding: 0 }
+    }
+
+    pub(crate) fn contains(&mut self, fill_rule: FillRule) -> bool {
+        // The last contour may be left open.
+        self.close();
+
+        match fill_rule {
+            FillRule::NonZero => self.winding != 0,
+            FillRule::EvenOdd => self.winding % 2 != 0,
+        }
+    }
+}
+
+impl OutlineBuilder for WindingCounter {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.close();
+        self.start_x = x;
+        self.start_y = y;
+        self.prev_x = x;
+        self.prev_y = y;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (x0, y0) = (self.prev_x, self.prev_y);
+        // Checks on which side of the line the point is.
+        let side = (x - x0) * (self.y - y0) - (self.x - x0) * (y - y0);
+        if y0 <= self.y {
+            if y > self.y && side > 0.0 {
+                self.winding += 1;
+            }
+        } else if y <= self.y && side < 0.0 {
+            self.winding -= 1;
+        }
+
+        self.prev_x = x;
+        self.prev_y = y;
+    }
+
+    fn quad_to(&mut self, _: f32, _: f32, x: f32, y: f32) {
+        debug_assert!(false, "curves must be flattened");
+        self.line_to(x, y);
+    }
+
+    fn curve_to(&mut self, _: f32, _: f32, _: f32, _: f32, x: f32, y: f32) {
+        debug_assert!(false, "curves must be flattened");
+        self.line_to(x, y);
+    }
+
+    fn close(&mut self) {
+        if self.prev_x != self.start_x || self.prev_y != self.start_y {
+            self.line_to(self.start_x, self.start_y);
+        }
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// An `OutlineBuilder` that computes a stable outline hash.
+///
+/// Unlike `core::hash::Hasher` implementations, the result does not depend on
+/// the platform or the crate version, so it can be stored and compared
+/// across faces and runs. A 64-bit FNV-1a hash is used.
+///
+/// Outlines are normalized first:
+///
+/// - coordinates are rounded to 1/64 of a unit
+/// - a line to the contour start followed by a ClosePath is ignored,
+///   so explicitly and implicitly closed contours are the same
+///
+/// # Example
+///
+/// ```
+/// use ttf_parser::{GlyphId, OutlineHasher};
+///
+/// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+/// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+/// let mut hasher = OutlineHasher::new();
+/// face.outline_glyph(GlyphId(1), &mut hasher).unwrap();
+/// assert_eq!(Some(hasher.finish()), face.glyph_outline_hash(GlyphId(1)));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct OutlineHasher {
+    hash: u64,
+    start: (i32, i32),
+    // A LineTo is delayed until we know that it's not an explicit contour closing.
+    pending_line: Option<(i32, i32)>,
+}
+
+impl OutlineHasher {
+    /// Creates a new hasher.
+    #[inline]
+    pub fn new() -> Self {
+        OutlineHasher { hash: FNV_OFFSET_BASIS, start: (0, 0), pending_line: None }
+    }
+
+    /// Returns the hash.
+    #[inline]
+    pub fn finish(&self) -> u64 {
+        let mut hasher = *self;
+        hasher.flush_line();
+        hasher.hash
+    }
+
+    pub(crate) fn write_bytes(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.hash ^= u64::from(*b);
+            self.hash = self.hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn write_i32(&mut self, n: i32) {
+        self.write_bytes(&n.to_le_bytes());
+    }
+
+    #[inline]
+    pub(crate) fn write_u64(&mut self, n: u64) {
+        self.write_bytes(&n.to_le_bytes());
+    }
+
+    fn write_command(&mut self, command: u8, points: &[(i32, i32)]) {
+        self.flush_line();
+        self.write_bytes(&[command]);
+        for &(x, y) in points {
+            self.write_i32(x);
+            self.write_i32(y);
+        }
+    }
+
+    fn flush_line(&mut self) {
+        if let Some((x, y)) = self.pending_line.take() {
+            self.write_bytes(b"L");
+            self.write_i32(x);
+            self.write_i32(y);
+        }
+    }
+}
+
+impl Default for OutlineHasher {
+    #[inline]
+    fn default() -> Self {
+        OutlineHasher::new()
+    }
+}
+
+/// Rounds a value to 1/`scale` and returns it as an integer.
+#[inline]
+pub(crate) fn quantize(n: f32, scale: f32) -> i32 {
+    let n = n * scale;
+    if n < 0.0 { (n - 0.5) as i32 } else { (n + 0.5) as i32 }
+}
+
+#[inline]
+fn quantize_point(x: f32, y: f32) -> (i32, i32) {
+    (quantize(x, 64.0), quantize(y, 64.0))
+}
+
+impl OutlineBuilder for OutlineHasher {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let p = quantize_point(x, y);
+        self.write_command(b'M', &[p]);
+        self.start = p;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.flush_line();
+        self.pending_line = Some(quantize_point(x, y));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.write_command(b'Q', &[quantize_point(x1, y1), quantize_point(x, y)]);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let points = [quantize_point(x1, y1), quantize_point(x2, y2), quantize_point(x, y)];
+        self.write_command(b'C', &points);
+    }
+
+    fn close(&mut self) {
+        if self.pending_line == Some(self.start) {
+            self.pending_line = None;
+        }
+
+        self.write_command(b'Z', &[]);
+    }
+}
+
+// Returns the smallest `n`, so `n^power >= bound`.
+//
+// Comparing powers avoids square roots, which are not available in `no_std`.
+fn segments_count(bound: f32, power: u32) -> u32 {
+    let mut n = 1;
+    while n < MAX_SEGMENTS && pow(n as f32, power) < bound {
+        n += 1;
+    }
+
+    n
+}
+
+#[inline]
+fn pow(n: f32, power: u32) -> f32 {
+    (0..power).fold(1.0, |acc, _| acc * n)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::String;
+    use std::vec::Vec;
+    use crate::{SvgPathBuilder, SvgPathOptions};
+
+    #[derive(Default)]
+    struct Points(Vec<(f32, f32)>);
+
+    impl OutlineBuilder for Points {
+        fn move_to(&mut self, x: f32, y: f32) { self.0.push((x, y)); }
+        fn line_to(&mut self, x: f32, y: f32) { self.0.push((x, y)); }
+        fn quad_to(&mut self, _: f32, _: f32, _: f32, _: f32) { panic!() }
+        fn curve_to(&mut self, _: f32, _: f32, _: f32, _: f32, _: f32, _: f32) { panic!() }
+        fn close(&mut self) {}
+    }
+
+    #[test]
+    fn quad() {
+        let mut points = Points::default();
+        let mut builder = FlattenBuilder::new(&mut points, 1.0);
+        builder.move_to(0.0, 0.0);
+        // |p0 - 2p1 + p2| = 200, so sqrt(200 / 4) = 7.07 -> 8 segments.
+        builder.quad_to(50.0, 100.0, 100.0, 0.0);
+        builder.close();
+        assert_eq!(points.0.len(), 1 + 8);
+        assert_eq!(points.0[4], (50.0, 50.0));
+        assert_eq!(points.0[8], (100.0, 0.0));
+    }
+
+    #[test]
+    fn cubic() {
+        let mut points = Points::default();
+        let mut builder = FlattenBuilder::new(&mut points, 1.0);
+        builder.move_to(0.0, 0.0);
+        // max(|p0 - 2p1 + p2|, |p1 - 2p2 + p3|) = 141.4, so sqrt(0.75 * 141.4) = 10.3 -> 11 segments.
+        builder.curve_to(0.0, 100.0, 100.0, 100.0, 100.0, 0.0);
+        assert_eq!(points.0.len(), 1 + 11);
+        assert_eq!(points.0[11], (100.0, 0.0));
+    }
+
+    #[test]
+    fn straight_curve() {
+        let mut points = Points::default();
+        let mut builder = FlattenBuilder::new(&mut points, 0.1);
+        builder.move_to(0.0, 0.0);
+        builder.quad_to(5.0, 5.0, 10.0, 10.0);
+        assert_eq!(points.0, vec![(0.0, 0.0), (10.0, 10.0)]);
+    }
+
+    #[test]
+    fn zero_tolerance() {
+        let mut points = Points::default();
+        let mut builder = FlattenBuilder::new(&mut points, 0.0);
+        builder.move_to(0.0, 0.0);
+        builder.quad_to(50.0, 100.0, 100.0, 0.0);
+        assert_eq!(points.0.len() as u32, 1 + MAX_SEGMENTS);
+    }
+
+    fn svg(f: impl FnOnce(&mut dyn OutlineBuilder)) -> String {
+        let mut path = SvgPathBuilder::new(SvgPathOptions { precision: Some(2), flip_y: false });
+        f(&mut path);
+        path.into_string()
+    }
+
+    #[test]
+    fn quad_to_cubic() {
+        let path = svg(|builder| {
+            let mut builder = CubicBuilder::new(builder);
+            builder.move_to(0.0, 0.0);
+            builder.quad_to(30.0, 60.0, 60.0, 0.0);
+            builder.line_to(0.0, 0.0);
+            builder.close();
+        });
+        assert_eq!(path, "M 0 0 C 20 40 40 40 60 0 L 0 0 Z");
+    }
+
+    #[test]
+    fn elevated_cubic_to_quad() {
+        let path = svg(|builder| {
+            let mut builder = QuadBuilder::new(builder, 0.1);
+            builder.move_to(0.0, 0.0);
+            builder.curve_to(20.0, 40.0, 40.0, 40.0, 60.0, 0.0);
+        });
+        assert_eq!(path, "M 0 0 Q 30 60 60 0");
+    }
+
+    #[test]
+    fn cubic_to_quad() {
+        let path = svg(|builder| {
+            let mut builder = QuadBuilder::new(builder, 1.0);
+            builder.move_to(0.0, 0.0);
+            // The error of a single quadratic curve is sqrt(3) / 36 * 200 = 9.6,
+            // so 3 pieces are required.
+            builder.curve_to(0.0, 100.0, 100.0, 100.0, 100.0, 0.0);
+        });
+        assert_eq!(path.matches('Q').count(), 3);
+        assert!(path.ends_with(" 100 0"));
+    }
+
+    fn contains(x: f32, y: f32, fill_rule: FillRule) -> bool {
+        // Two nested squares. The inner one is either a hole or an overlap.
+        let mut counter = WindingCounter::new(x, y);
+        counter.move_to(0.0, 0.0);
+        counter.line_to(10.0, 0.0);
+        counter.line_to(10.0, 10.0);
+        counter.line_to(0.0, 10.0);
+        counter.close();
+        counter.move_to(2.0, 2.0);
+        counter.line_to(8.0, 2.0);
+        counter.line_to(8.0, 8.0);
+        counter.line_to(2.0, 8.0);
+        counter.contains(fill_rule)
+    }
+
+    #[test]
+    fn winding() {
+        assert!(contains(1.0, 1.0, FillRule::NonZero));
+        assert!(contains(1.0, 1.0, FillRule::EvenOdd));
+        assert!(contains(5.0, 5.0, FillRule::NonZero));
+        assert!(!contains(5.0, 5.0, FillRule::EvenOdd));
+        assert!(!contains(11.0, 5.0, FillRule::NonZero));
+        assert!(!contains(-1.0, 5.0, FillRule::EvenOdd));
+    }
+
+    fn hash(f: impl FnOnce(&mut OutlineHasher)) -> u64 {
+        let mut hasher = OutlineHasher::new();
+        f(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn outline_hash() {
+        let triangle = |hasher: &mut OutlineHasher| {
+            hasher.move_to(0.0, 0.0);
+            hasher.line_to(10.0, 0.0);
+            hasher.quad_to(10.0, 10.0, 5.0, 10.0);
+        };
+
+        let implicit = hash(|hasher| {
+            triangle(hasher);
+            hasher.close();
+        });
+
+        let explicit = hash(|hasher| {
+            triangle(hasher);
+            hasher.line_to(0.001, 0.0);
+            hasher.close();
+        });
+
+        let open = hash(|hasher| {
+            triangle(hasher);
+            hasher.line_to(0.0, 0.0);
+        });
+
+        assert_eq!(implicit, explicit);
+        assert_ne!(implicit, open);
+        // The hash must be stable.
+        assert_eq!(implicit, 0x94c32073a1795756);
+        assert_eq!(hash(|_| {}), FNV_OFFSET_BASIS);
+    }
+}
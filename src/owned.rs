@@ -0,0 +1,145 @@
+use std::boxed::Box;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::vec::Vec;
+
+use crate::{Face, FaceParsingError, ParseOptions};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A font data container that can be stored in `OwnedFace`.
+///
+/// Implemented only for containers whose data cannot change while borrowed,
+/// so a face validated on creation can always be derived again.
+///
+/// Memory-mapped files are not supported, since a mapped file can be modified
+/// by other processes and mapping requires unsafe code.
+pub trait FaceData: AsRef<[u8]> + private::Sealed {}
+
+impl private::Sealed for Vec<u8> {}
+impl FaceData for Vec<u8> {}
+impl private::Sealed for Box<[u8]> {}
+impl FaceData for Box<[u8]> {}
+impl private::Sealed for Arc<[u8]> {}
+impl FaceData for Arc<[u8]> {}
+impl private::Sealed for Rc<[u8]> {}
+impl FaceData for Rc<[u8]> {}
+
+/// A font face that owns its data.
+///
+/// Unlike `Face`, can be stored without borrowing the font data,
+/// which is useful for caching parsed fonts.
+/// The data is validated once and the borrowed `Face` is re-derived on demand.
+///
+/// The data can be stored in a `Vec<u8>`, `Box<[u8]>`, `Arc<[u8]>` or `Rc<[u8]>`.
+///
+/// # Example
+///
+/// ```
+/// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+/// let owned_face = ttf_parser::OwnedFace::from_vec(data, 0).unwrap();
+/// assert_eq!(owned_face.as_face().units_per_em(), Some(1000));
+/// ```
+#[derive(Clone)]
+pub struct OwnedFace<D = Vec<u8>> {
+    data: D,
+    index: u32,
+    options: ParseOptions,
+}
+
+impl OwnedFace<Vec<u8>> {
+    /// Creates a new `OwnedFace` from the font data.
+    ///
+    /// See `Face::from_slice` for details.
+    #[inline]
+    pub fn from_vec(data: Vec<u8>, index: u32) -> Result<Self, FaceParsingError> {
+        Self::from_data(data, index)
+    }
+
+    /// Returns the font data, consuming the face.
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+impl<D: FaceData> OwnedFace<D> {
+    /// Creates a new `OwnedFace` from the font data stored in any supported container.
+    ///
+    /// See `Face::from_slice` for details.
+    #[inline]
+    pub fn from_data(data: D, index: u32) -> Result<Self, FaceParsingError> {
+        Self::from_data_with_options(data, index, ParseOptions::default())
+    }
+
+    /// Creates a new `OwnedFace` from the font data using specified options.
+    ///
+    /// See `Face::from_slice_with_options` for details.
+    pub fn from_data_with_options(
+        data: D,
+        index: u32,
+        options: ParseOptions,
+    ) -> Result<Self, FaceParsingError> {
+        Face::from_slice_with_options(data.as_ref(), index, options)?;
+        Ok(OwnedFace { data, index, options })
+    }
+
+    /// Returns a borrowed `Face`.
+    ///
+    /// The face is parsed on each call, just like via `Face::from_slice`,
+    /// which is not free. Keep the returned face when making multiple queries.
+    pub fn as_face(&self) -> Face<'_> {
+        match Face::from_slice_with_options(self.data.as_ref(), self.index, self.options) {
+            Ok(face) => face,
+            // Parsing is deterministic and the data cannot change after validation.
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Returns the font data.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        self.data.as_ref()
+    }
+
+    /// Returns the face index in a font collection.
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns the font data container, consuming the face.
+    #[inline]
+    pub fn into_inner(self) -> D {
+        self.data
+    }
+}
+
+impl<D> core::fmt::Debug for OwnedFace<D> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "OwnedFace()")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_data() {
+        assert_eq!(OwnedFace::from_vec(vec![0; 4], 0).unwrap_err(), FaceParsingError::UnknownMagic);
+        let data: std::sync::Arc<[u8]> = vec![0; 4].into();
+        assert_eq!(OwnedFace::from_data(data, 0).unwrap_err(), FaceParsingError::UnknownMagic);
+    }
+
+    #[test]
+    fn shared_data() {
+        let data: std::sync::Arc<[u8]> = std::fs::read("tests/fonts/demo.ttf").unwrap().into();
+        let face = OwnedFace::from_data(data.clone(), 0).unwrap();
+        assert_eq!(face.as_face().number_of_glyphs(), 2);
+        assert_eq!(face.data(), &data[..]);
+    }
+}
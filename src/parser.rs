@@ -1,14 +1,32 @@
 //! Binary parsing utils.
 //!
-//! This module should not be used directly, unless you're planning to parse
-//! some tables manually.
+//! The same zero-copy primitives the library itself is built on.
+//! Can be used to parse vendor-specific or not yet supported tables.
+//!
+//! # Example
+//!
+//! ```
+//! use ttf_parser::parser::{LazyArray16, Offset, Offset16, Stream};
+//!
+//! // A made-up table: a version, an offset to an array and the array itself.
+//! let data = [0, 1, 0, 4, 0, 2, 0, 10, 0, 20];
+//! let mut s = Stream::new(&data);
+//! let version: u16 = s.read().unwrap();
+//! let offset: Offset16 = s.read().unwrap();
+//!
+//! let mut s = Stream::new_at(&data, offset.to_usize()).unwrap();
+//! let count: u16 = s.read().unwrap();
+//! let values: LazyArray16<u16> = s.read_array16(count).unwrap();
+//! assert_eq!(version, 1);
+//! assert_eq!(values.into_iter().collect::<Vec<_>>(), [10, 20]);
+//! ```
 
 use core::ops::Range;
 use core::convert::{TryFrom, TryInto};
 
 /// A trait for parsing raw binary data.
 ///
-/// This is a low-level, internal trait that should not be used directly.
+/// Implement it for custom records to read them via `Stream` and lazy arrays.
 pub trait FromData: Sized {
     /// Object's raw data size.
     ///
@@ -111,6 +129,12 @@ impl F2DOT14 {
     pub fn to_f32(&self) -> f32 {
         f32::from(self.0) / 16384.0
     }
+
+    /// Converts i16 to f64.
+    #[inline]
+    pub fn to_f64(&self) -> f64 {
+        f64::from(self.0) / 16384.0
+    }
 }
 
 impl FromData for F2DOT14 {
@@ -124,9 +148,32 @@ impl FromData for F2DOT14 {
 
 
 /// A 32-bit signed fixed-point number (16.16).
+///
+/// Stored as f32, so values with more than 24 significant bits lose precision.
+/// Use `Fixed::parse_f64` when the exact value is required.
 #[derive(Clone, Copy, Debug)]
 pub struct Fixed(pub f32);
 
+impl Fixed {
+    /// Returns the value as f32.
+    #[inline]
+    pub fn to_f32(&self) -> f32 {
+        self.0
+    }
+
+    /// Returns the value as f64.
+    #[inline]
+    pub fn to_f64(&self) -> f64 {
+        f64::from(self.0)
+    }
+
+    /// Parses a 16.16 number without a precision loss.
+    #[inline]
+    pub fn parse_f64(data: &[u8]) -> Option<f64> {
+        i32::parse(data).map(|n| f64::from(n) / 65536.0)
+    }
+}
+
 impl FromData for Fixed {
     const SIZE: usize = 4;
 
@@ -138,6 +185,33 @@ impl FromData for Fixed {
 }
 
 
+/// A date and time (`LONGDATETIME`).
+///
+/// A signed number of seconds since 12:00 midnight, January 1, 1904, UTC.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct LongDateTime(pub i64);
+
+impl LongDateTime {
+    /// Converts to a Unix timestamp.
+    ///
+    /// Returns `None` on overflow.
+    #[inline]
+    pub fn to_unix_timestamp(&self) -> Option<i64> {
+        // A number of seconds between 1904-01-01 and 1970-01-01.
+        self.0.checked_sub(2_082_844_800)
+    }
+}
+
+impl FromData for LongDateTime {
+    const SIZE: usize = 8;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        data.try_into().ok().map(i64::from_be_bytes).map(LongDateTime)
+    }
+}
+
+
 /// A safe u32 to usize casting.
 ///
 /// Rust doesn't implement `From<u32> for usize`,
@@ -666,6 +740,37 @@ impl FromData for Option<Offset16> {
 }
 
 
+/// A type-safe u24 offset.
+#[derive(Clone, Copy, Debug)]
+pub struct Offset24(pub u32);
+
+impl Offset for Offset24 {
+    #[inline]
+    fn to_usize(&self) -> usize {
+        usize::num_from(self.0)
+    }
+}
+
+impl FromData for Offset24 {
+    const SIZE: usize = 3;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        U24::parse(data).map(|n| Offset24(n.0))
+    }
+}
+
+impl FromData for Option<Offset24> {
+    const SIZE: usize = Offset24::SIZE;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let offset = Offset24::parse(data)?;
+        if offset.0 != 0 { Some(Some(offset)) } else { Some(None) }
+    }
+}
+
+
 /// A type-safe u32 offset.
 #[derive(Clone, Copy, Debug)]
 pub struct Offset32(pub u32);
@@ -718,3 +823,43 @@ pub(crate) fn f32_bound(min: f32, val: f32, max: f32) -> f32 {
 
     val
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn f2dot14() {
+        assert_eq!(F2DOT14(-16384).to_f64(), -1.0);
+        assert_eq!(F2DOT14(8192).to_f64(), 0.5);
+        assert_eq!(F2DOT14(1).to_f64(), 1.0 / 16384.0);
+    }
+
+    #[test]
+    fn fixed() {
+        let data = [0x00, 0x01, 0x80, 0x00]; // 1.5
+        assert_eq!(Fixed::parse(&data).unwrap().to_f32(), 1.5);
+        assert_eq!(Fixed::parse(&data).unwrap().to_f64(), 1.5);
+        assert_eq!(Fixed::parse_f64(&data), Some(1.5));
+
+        // Doesn't fit into f32.
+        let data = [0x7F, 0xFF, 0xFF, 0xFF];
+        assert_eq!(Fixed::parse_f64(&data), Some(32767.0 + 65535.0 / 65536.0));
+        assert_eq!(Fixed::parse(&data).unwrap().to_f64(), 32768.0);
+
+        assert_eq!(Fixed::parse_f64(&data[..3]), None);
+    }
+
+    #[test]
+    fn long_date_time() {
+        let data = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+        let time = LongDateTime::parse(&data).unwrap();
+        assert_eq!(time, LongDateTime(-1));
+        assert_eq!(time.to_unix_timestamp(), Some(-2_082_844_801));
+
+        assert_eq!(LongDateTime(2_082_844_800).to_unix_timestamp(), Some(0));
+        assert_eq!(LongDateTime(core::i64::MIN).to_unix_timestamp(), None);
+        assert_eq!(LongDateTime::parse(&data[..7]), None);
+    }
+}
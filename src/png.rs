@@ -0,0 +1,399 @@
+//! A minimal PNG decoder.
+//!
+//! Supports all color types, bit depths and interlacing,
+//! but ignores gamma, color profiles and ancillary chunks except `tRNS`.
+//! Checksums are not validated.
+
+use core::convert::TryFrom;
+use std::vec::Vec;
+
+use crate::{RgbaImage, zlib};
+use crate::parser::Stream;
+
+/// Adam7 passes as `(x0, y0, dx, dy)`.
+const ADAM7_PASSES: [(u32, u32, u32, u32); 7] = [
+    (0, 0, 8, 8),
+    (4, 0, 8, 8),
+    (0, 4, 4, 8),
+    (2, 0, 4, 4),
+    (0, 2, 2, 4),
+    (1, 0, 2, 2),
+    (0, 1, 1, 2),
+];
+
+
+#[derive(Clone, Copy, PartialEq)]
+enum ColorType {
+    Grayscale,
+    Rgb,
+    Indexed,
+    GrayscaleAlpha,
+    Rgba,
+}
+
+impl ColorType {
+    fn channels(self) -> u32 {
+        match self {
+            ColorType::Grayscale | ColorType::Indexed => 1,
+            ColorType::GrayscaleAlpha => 2,
+            ColorType::Rgb => 3,
+            ColorType::Rgba => 4,
+        }
+    }
+}
+
+struct Header {
+    width: u32,
+    height: u32,
+    bit_depth: u8,
+    color_type: ColorType,
+    interlaced: bool,
+}
+
+impl Header {
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let width: u32 = s.read()?;
+        let height: u32 = s.read()?;
+        let bit_depth: u8 = s.read()?;
+        let color_type = match s.read::<u8>()? {
+            0 => ColorType::Grayscale,
+            2 => ColorType::Rgb,
+            3 => ColorType::Indexed,
+            4 => ColorType::GrayscaleAlpha,
+            6 => ColorType::Rgba,
+            _ => return None,
+        };
+        let compression: u8 = s.read()?;
+        let filter: u8 = s.read()?;
+        let interlace: u8 = s.read()?;
+
+        let is_valid_depth = match color_type {
+            ColorType::Grayscale => [1, 2, 4, 8, 16].contains(&bit_depth),
+            ColorType::Indexed => [1, 2, 4, 8].contains(&bit_depth),
+            _ => bit_depth == 8 || bit_depth == 16,
+        };
+
+        if width == 0 || height == 0 || !is_valid_depth
+            || compression != 0 || filter != 0 || interlace > 1
+        {
+            return None;
+        }
+
+        Some(Header { width, height, bit_depth, color_type, interlaced: interlace == 1 })
+    }
+
+    fn bits_per_pixel(&self) -> u32 {
+        self.color_type.channels() * u32::from(self.bit_depth)
+    }
+
+    /// Returns a row length in bytes, without the filter type byte.
+    fn stride(&self, width: u32) -> Option<usize> {
+        let bits = u64::from(width) * u64::from(self.bits_per_pixel());
+        usize::try_from(bits / 8 + u64::from(bits % 8 != 0)).ok()
+    }
+
+    /// Returns a pass size. An empty pass has a zero width or height.
+    fn pass_size(&self, pass: (u32, u32, u32, u32)) -> (u32, u32) {
+        let (x0, y0, dx, dy) = pass;
+        let size = |len: u32, start: u32, step: u32| {
+            if len > start { (len - start - 1) / step + 1 } else { 0 }
+        };
+
+        (size(self.width, x0, dx), size(self.height, y0, dy))
+    }
+
+    /// Returns the length of the decompressed image data.
+    fn data_len(&self) -> Option<usize> {
+        let mut len = 0usize;
+        let mut add_pass = |width: u32, height: u32| -> Option<()> {
+            if width != 0 && height != 0 {
+                let row = self.stride(width)?.checked_add(1)?;
+                len = len.checked_add(row.checked_mul(usize::try_from(height).ok()?)?)?;
+            }
+
+            Some(())
+        };
+
+        if self.interlaced {
+            for pass in &ADAM7_PASSES {
+                let (width, height) = self.pass_size(*pass);
+                add_pass(width, height)?;
+            }
+        } else {
+            add_pass(self.width, self.height)?;
+        }
+
+        Some(len)
+    }
+}
+
+
+/// Decodes a PNG image.
+pub(crate) fn decode(data: &[u8]) -> Option<RgbaImage> {
+    const SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    if !data.starts_with(SIGNATURE) {
+        return None;
+    }
+
+    let mut s = Stream::new_at(data, SIGNATURE.len())?;
+    let mut header = None;
+    let mut palette: &[u8] = &[];
+    let mut transparency: &[u8] = &[];
+    let mut compressed = Vec::new();
+    loop {
+        let len: u32 = s.read()?;
+        let kind = s.read_bytes(4)?;
+        let chunk = s.read_bytes(usize::try_from(len).ok()?)?;
+        s.skip::<u32>(); // crc
+
+        match kind {
+            b"IHDR" => header = Some(Header::parse(chunk)?),
+            b"PLTE" => palette = chunk,
+            b"tRNS" => transparency = chunk,
+            b"IDAT" => compressed.extend_from_slice(chunk),
+            b"IEND" => break,
+            _ => {}
+        }
+    }
+
+    let header = header?;
+    if header.color_type == ColorType::Indexed && palette.is_empty() {
+        return None;
+    }
+
+    let raw = zlib::decompress(&compressed, header.data_len()?)?;
+
+    let len = usize::try_from(header.width).ok()?
+        .checked_mul(usize::try_from(header.height).ok()?)?
+        .checked_mul(4)?;
+    let mut image = RgbaImage {
+        width: header.width,
+        height: header.height,
+        data: vec![0; len],
+    };
+
+    let decoder = PixelDecoder { header: &header, palette, transparency };
+    if header.interlaced {
+        let mut offset = 0;
+        for pass in &ADAM7_PASSES {
+            offset += decoder.decode_pass(&raw[offset..], *pass, &mut image)?;
+        }
+    } else {
+        decoder.decode_pass(&raw, (0, 0, 1, 1), &mut image)?;
+    }
+
+    Some(image)
+}
+
+
+struct PixelDecoder<'a> {
+    header: &'a Header,
+    palette: &'a [u8],
+    transparency: &'a [u8],
+}
+
+impl PixelDecoder<'_> {
+    /// Decodes a pass and returns the number of consumed bytes.
+    fn decode_pass(
+        &self,
+        data: &[u8],
+        pass: (u32, u32, u32, u32),
+        image: &mut RgbaImage,
+    ) -> Option<usize> {
+        let (width, height) = if self.header.interlaced {
+            self.header.pass_size(pass)
+        } else {
+            (self.header.width, self.header.height)
+        };
+
+        if width == 0 || height == 0 {
+            return Some(0);
+        }
+
+        let (x0, y0, dx, dy) = pass;
+        let stride = self.header.stride(width)?;
+        // Filters operate on bytes of the previous pixel, but at least one byte.
+        let pixel_len = core::cmp::max(1, self.header.bits_per_pixel() as usize / 8);
+
+        let mut prev_row = vec![0; stride];
+        let mut row = vec![0; stride];
+        let mut offset = 0;
+        for y in 0..height {
+            let filter = *data.get(offset)?;
+            row.copy_from_slice(data.get(offset + 1..offset + 1 + stride)?);
+            offset += stride + 1;
+
+            unfilter(filter, &mut row, &prev_row, pixel_len)?;
+
+            for x in 0..width {
+                let rgba = self.pixel(&row, x)?;
+                let px = x0 + x * dx;
+                let py = y0 + y * dy;
+                let index = (py as usize * image.width as usize + px as usize) * 4;
+                image.data.get_mut(index..index + 4)?.copy_from_slice(&premultiply(rgba));
+            }
+
+            core::mem::swap(&mut row, &mut prev_row);
+        }
+
+        Some(offset)
+    }
+
+    fn pixel(&self, row: &[u8], x: u32) -> Option<[u8; 4]> {
+        let channels = self.header.color_type.channels();
+        let sample = |channel: u32| sample(row, x * channels + channel, self.header.bit_depth);
+        let to_u8 = |value: u16| scale_to_u8(value, self.header.bit_depth);
+
+        let rgba = match self.header.color_type {
+            ColorType::Grayscale => {
+                let gray = sample(0)?;
+                let alpha = if Stream::read_at::<u16>(self.transparency, 0) == Some(gray) { 0 } else { 255 };
+                let gray = to_u8(gray);
+                [gray, gray, gray, alpha]
+            }
+            ColorType::Rgb => {
+                let (r, g, b) = (sample(0)?, sample(1)?, sample(2)?);
+                let key = Stream::new(self.transparency);
+                let mut key = key;
+                let is_transparent = self.transparency.len() == 6
+                    && key.read::<u16>() == Some(r)
+                    && key.read::<u16>() == Some(g)
+                    && key.read::<u16>() == Some(b);
+                [to_u8(r), to_u8(g), to_u8(b), if is_transparent { 0 } else { 255 }]
+            }
+            ColorType::Indexed => {
+                let index = usize::from(sample(0)?);
+                let color = self.palette.get(index * 3..index * 3 + 3)?;
+                let alpha = self.transparency.get(index).cloned().unwrap_or(255);
+                [color[0], color[1], color[2], alpha]
+            }
+            ColorType::GrayscaleAlpha => {
+                let gray = to_u8(sample(0)?);
+                [gray, gray, gray, to_u8(sample(1)?)]
+            }
+            ColorType::Rgba => {
+                [to_u8(sample(0)?), to_u8(sample(1)?), to_u8(sample(2)?), to_u8(sample(3)?)]
+            }
+        };
+
+        Some(rgba)
+    }
+}
+
+/// Reads a sample by index from a row.
+fn sample(row: &[u8], index: u32, bit_depth: u8) -> Option<u16> {
+    let index = index as usize;
+    match bit_depth {
+        16 => Stream::read_at::<u16>(row, index * 2),
+        8 => row.get(index).map(|n| u16::from(*n)),
+        _ => {
+            let depth = usize::from(bit_depth);
+            let byte = *row.get(index * depth / 8)?;
+            let shift = 8 - depth - (index * depth % 8);
+            let mask = (1u16 << depth) - 1;
+            Some((u16::from(byte) >> shift) & mask)
+        }
+    }
+}
+
+fn scale_to_u8(value: u16, bit_depth: u8) -> u8 {
+    match bit_depth {
+        1 => (value * 255) as u8,
+        2 => (value * 85) as u8,
+        4 => (value * 17) as u8,
+        16 => (value >> 8) as u8,
+        _ => value as u8,
+    }
+}
+
+fn premultiply(rgba: [u8; 4]) -> [u8; 4] {
+    let alpha = u16::from(rgba[3]);
+    let mul = |c: u8| ((u16::from(c) * alpha + 127) / 255) as u8;
+    [mul(rgba[0]), mul(rgba[1]), mul(rgba[2]), rgba[3]]
+}
+
+fn unfilter(filter: u8, row: &mut [u8], prev_row: &[u8], pixel_len: usize) -> Option<()> {
+    match filter {
+        0 => {}
+        1 => {
+            for i in pixel_len..row.len() {
+                row[i] = row[i].wrapping_add(row[i - pixel_len]);
+            }
+        }
+        2 => {
+            for (byte, up) in row.iter_mut().zip(prev_row) {
+                *byte = byte.wrapping_add(*up);
+            }
+        }
+        3 => {
+            for i in 0..row.len() {
+                let left = if i >= pixel_len { u16::from(row[i - pixel_len]) } else { 0 };
+                let avg = (left + u16::from(prev_row[i])) / 2;
+                row[i] = row[i].wrapping_add(avg as u8);
+            }
+        }
+        4 => {
+            for i in 0..row.len() {
+                let (left, up_left) = if i >= pixel_len {
+                    (row[i - pixel_len], prev_row[i - pixel_len])
+                } else {
+                    (0, 0)
+                };
+                row[i] = row[i].wrapping_add(paeth(left, prev_row[i], up_left));
+            }
+        }
+        _ => return None,
+    }
+
+    Some(())
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = i16::from(a) + i16::from(b) - i16::from(c);
+    let pa = (p - i16::from(a)).abs();
+    let pb = (p - i16::from(b)).abs();
+    let pc = (p - i16::from(c)).abs();
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paeth_predictor() {
+        assert_eq!(paeth(10, 20, 15), 15);
+        assert_eq!(paeth(10, 20, 10), 20);
+        assert_eq!(paeth(10, 20, 20), 10);
+    }
+
+    #[test]
+    fn rgba_image() {
+        // A 2x1 RGBA image: opaque red and half-transparent white.
+        let data = [
+            0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, // signature
+            0, 0, 0, 13, b'I', b'H', b'D', b'R', // IHDR chunk
+            0, 0, 0, 2, 0, 0, 0, 1, 8, 6, 0, 0, 0, // width, height, depth, color type, etc.
+            0, 0, 0, 0, // crc
+            0, 0, 0, 20, b'I', b'D', b'A', b'T', // IDAT chunk
+            0x78, 0x01, 0x01, 0x09, 0x00, 0xF6, 0xFF, // zlib and stored block headers
+            0, 255, 0, 0, 255, 255, 255, 255, 128, // filter type and pixels
+            0, 0, 0, 0, // adler32
+            0, 0, 0, 0, // crc
+            0, 0, 0, 0, b'I', b'E', b'N', b'D', // IEND chunk
+            0, 0, 0, 0, // crc
+        ];
+
+        let image = decode(&data).unwrap();
+        assert_eq!((image.width, image.height), (2, 1));
+        assert_eq!(image.data, &[255, 0, 0, 255, 128, 128, 128, 128]);
+    }
+}
@@ -0,0 +1,82 @@
+use std::vec::Vec;
+
+use crate::{Face, FaceParsingError, ParseOptions, Tag, TableError, Warning};
+
+/// A table sanitization result.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TableReport {
+    /// Table's tag.
+    pub tag: Tag,
+
+    /// A parsing error.
+    pub error: Option<TableError>,
+
+    /// Table's anomalies.
+    pub warnings: Vec<Warning>,
+}
+
+impl TableReport {
+    /// Checks that the table has neither errors nor warnings.
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none() && self.warnings.is_empty()
+    }
+}
+
+/// A font sanitization report.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SanitizeReport {
+    /// An error that prevents face parsing.
+    ///
+    /// When set, tables are not checked.
+    pub error: Option<FaceParsingError>,
+
+    /// Per-table results in the font directory order.
+    pub tables: Vec<TableReport>,
+
+    /// Anomalies that are not related to a specific table.
+    pub warnings: Vec<Warning>,
+}
+
+impl SanitizeReport {
+    /// Checks that the font has neither errors nor warnings.
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none() && self.warnings.is_empty() && self.tables.iter().all(|t| t.is_ok())
+    }
+}
+
+/// Checks font's structural invariants.
+///
+/// Combines `Face::table_errors` and `Face::warnings` into a per-table report.
+/// Tables that are not supported are checked only for being in bounds.
+///
+/// Can be used as a pre-flight check before serving fonts.
+pub fn sanitize(data: &[u8], index: u32) -> SanitizeReport {
+    let face = match Face::from_slice_with_options(data, index, ParseOptions::default()) {
+        Ok(face) => face,
+        Err(e) => {
+            return SanitizeReport { error: Some(e), tables: Vec::new(), warnings: Vec::new() };
+        }
+    };
+
+    let mut tables: Vec<TableReport> = face.table_records()
+        .map(|record| TableReport { tag: record.tag, error: None, warnings: Vec::new() })
+        .collect();
+
+    for error in face.table_errors() {
+        if let Some(table) = tables.iter_mut().find(|t| t.tag == error.tag) {
+            table.error = Some(error);
+        }
+    }
+
+    let mut warnings = Vec::new();
+    for warning in face.warnings() {
+        let table = warning.tag().and_then(|tag| tables.iter_mut().find(|t| t.tag == tag));
+        match table {
+            Some(table) => table.warnings.push(warning),
+            None => warnings.push(warning),
+        }
+    }
+
+    SanitizeReport { error: None, tables, warnings }
+}
@@ -0,0 +1,201 @@
+use crate::{Face, GlyphId, Rect};
+
+/// A rectangle in pixels.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(missing_docs)]
+pub struct ScaledRect {
+    pub x_min: f32,
+    pub y_min: f32,
+    pub x_max: f32,
+    pub y_max: f32,
+}
+
+/// A face scaled to a specific size.
+///
+/// Converts font units to pixels, so callers don't have to deal with `units_per_em`.
+/// All values are in pixels, with the Y axis pointing up.
+///
+/// Can be created via `Face::scaled`.
+///
+/// # Example
+///
+/// ```
+/// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+/// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+/// let scaled = face.scaled(16.0).unwrap();
+/// assert_eq!(scaled.ascender(), 1024.0 * 16.0 / 1000.0);
+/// ```
+#[derive(Clone, Copy)]
+pub struct ScaledFace<'f, 'a> {
+    face: &'f Face<'a>,
+    pixels_per_em: f32,
+    scale: f32,
+}
+
+impl<'f, 'a> ScaledFace<'f, 'a> {
+    pub(crate) fn new(face: &'f Face<'a>, pixels_per_em: f32) -> Option<Self> {
+        let units_per_em = face.units_per_em()?;
+        Some(ScaledFace {
+            face,
+            pixels_per_em,
+            scale: pixels_per_em / f32::from(units_per_em),
+        })
+    }
+
+    /// Returns the underlying face.
+    #[inline]
+    pub fn face(&self) -> &'f Face<'a> {
+        self.face
+    }
+
+    /// Returns pixels per em.
+    #[inline]
+    pub fn pixels_per_em(&self) -> f32 {
+        self.pixels_per_em
+    }
+
+    /// Returns a factor to convert font units to pixels.
+    #[inline]
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Converts a value in font units to pixels.
+    #[inline]
+    pub fn scale_value(&self, value: f32) -> f32 {
+        value * self.scale
+    }
+
+    /// Returns a scaled `Face::ascender`.
+    #[inline]
+    pub fn ascender(&self) -> f32 {
+        self.scale_value(f32::from(self.face.ascender()))
+    }
+
+    /// Returns a scaled `Face::descender`.
+    #[inline]
+    pub fn descender(&self) -> f32 {
+        self.scale_value(f32::from(self.face.descender()))
+    }
+
+    /// Returns a scaled `Face::height`.
+    #[inline]
+    pub fn height(&self) -> f32 {
+        self.ascender() - self.descender()
+    }
+
+    /// Returns a scaled `Face::line_gap`.
+    #[inline]
+    pub fn line_gap(&self) -> f32 {
+        self.scale_value(f32::from(self.face.line_gap()))
+    }
+
+    /// Returns a scaled `Face::glyph_hor_advance`.
+    #[inline]
+    pub fn glyph_hor_advance(&self, glyph_id: GlyphId) -> Option<f32> {
+        self.face.glyph_hor_advance(glyph_id).map(|v| self.scale_value(f32::from(v)))
+    }
+
+    /// Returns a scaled `Face::glyph_ver_advance`.
+    #[inline]
+    pub fn glyph_ver_advance(&self, glyph_id: GlyphId) -> Option<f32> {
+        self.face.glyph_ver_advance(glyph_id).map(|v| self.scale_value(f32::from(v)))
+    }
+
+    /// Returns a scaled `Face::glyph_hor_side_bearing`.
+    #[inline]
+    pub fn glyph_hor_side_bearing(&self, glyph_id: GlyphId) -> Option<f32> {
+        self.face.glyph_hor_side_bearing(glyph_id).map(|v| self.scale_value(f32::from(v)))
+    }
+
+    /// Returns a scaled `Face::glyph_bounding_box`.
+    #[inline]
+    pub fn glyph_bounding_box(&self, glyph_id: GlyphId) -> Option<ScaledRect> {
+        self.face.glyph_bounding_box(glyph_id).map(|rect| self.scale_rect(rect))
+    }
+
+    /// Returns a scaled `Face::global_bounding_box`.
+    #[inline]
+    pub fn global_bounding_box(&self) -> ScaledRect {
+        self.scale_rect(self.face.global_bounding_box())
+    }
+
+    /// Returns a scaled `Face::measure_text`.
+    #[inline]
+    pub fn measure_text(&self, text: &str, kerning: bool) -> f32 {
+        self.scale_value(self.face.measure_text(text, kerning) as f32)
+    }
+
+    fn scale_rect(&self, rect: Rect) -> ScaledRect {
+        ScaledRect {
+            x_min: self.scale_value(f32::from(rect.x_min)),
+            y_min: self.scale_value(f32::from(rect.y_min)),
+            x_max: self.scale_value(f32::from(rect.x_max)),
+            y_max: self.scale_value(f32::from(rect.y_max)),
+        }
+    }
+}
+
+impl core::fmt::Debug for ScaledFace<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "ScaledFace()")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FontWriter, Tag};
+
+    #[test]
+    fn metrics() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let scaled = face.scaled(16.0).unwrap();
+        assert_eq!(scaled.pixels_per_em(), 16.0);
+        assert_eq!(scaled.scale(), 0.016);
+        assert_eq!(scaled.ascender(), 1024.0 * 0.016);
+        assert_eq!(scaled.descender(), -400.0 * 0.016);
+        assert_eq!(scaled.height(), scaled.ascender() - scaled.descender());
+        assert_eq!(scaled.line_gap(), 0.0);
+        assert_eq!(scaled.glyph_hor_advance(GlyphId(1)), Some(540.0 * 0.016));
+        assert_eq!(scaled.glyph_hor_side_bearing(GlyphId(1)), Some(6.0 * 0.016));
+        assert_eq!(scaled.glyph_ver_advance(GlyphId(1)), None);
+        assert_eq!(scaled.glyph_hor_advance(GlyphId(2)), None);
+        assert_eq!(scaled.glyph_bounding_box(GlyphId(1)), Some(ScaledRect {
+            x_min: 6.0 * 0.016,
+            y_min: 0.0,
+            x_max: 541.0 * 0.016,
+            y_max: 656.0 * 0.016,
+        }));
+        assert_eq!(scaled.measure_text("AA", false), 1080.0 * 0.016);
+    }
+
+    #[test]
+    fn units_per_em() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let mut head = face.table_data(Tag::from_bytes(b"head")).unwrap().to_vec();
+
+        // A power of two, so values are exact.
+        head[18..20].copy_from_slice(&2048u16.to_be_bytes());
+        let mut writer = FontWriter::from_face(&face);
+        writer.set_table(Tag::from_bytes(b"head"), head.clone());
+        let font = writer.finish();
+        let face = Face::from_slice(&font, 0).unwrap();
+        let scaled = face.scaled(32.0).unwrap();
+        assert_eq!(scaled.scale(), 1.0 / 64.0);
+        assert_eq!(scaled.ascender(), 16.0);
+        assert_eq!(scaled.glyph_hor_advance(GlyphId(1)), Some(8.4375));
+
+        // Invalid.
+        head[18..20].copy_from_slice(&0u16.to_be_bytes());
+        let mut writer = FontWriter::from_face(&face);
+        writer.set_table(Tag::from_bytes(b"head"), head);
+        let font = writer.finish();
+        let face = Face::from_slice(&font, 0).unwrap();
+        assert!(face.scaled(32.0).is_none());
+    }
+}
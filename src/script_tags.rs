@@ -0,0 +1,255 @@
+// https://docs.microsoft.com/en-us/typography/opentype/spec/scripttags
+
+use crate::Tag;
+
+/// Returns OpenType script tags for a Unicode script, in the order of preference.
+///
+/// `script` is an [ISO 15924](https://unicode.org/iso15924/) code, which is also
+/// the short name of the Unicode `Script` property value, like `Latn` or `Deva`.
+///
+/// Indic and Myanmar scripts have two tags: the new one, like `dev2`, comes first,
+/// followed by the old one, like `deva`. A shaper should use the first tag present
+/// in a font's script list.
+///
+/// Hiragana and Katakana share the same `kana` tag.
+///
+/// # Example
+///
+/// ```
+/// use ttf_parser::{opentype_script_tags, Tag};
+///
+/// let mut tags = opentype_script_tags(Tag::from_bytes(b"Beng"));
+/// assert_eq!(tags.next(), Some(Tag::from_bytes(b"bng2")));
+/// assert_eq!(tags.next(), Some(Tag::from_bytes(b"beng")));
+/// assert_eq!(tags.next(), None);
+/// ```
+pub fn opentype_script_tags(script: Tag) -> impl Iterator<Item = Tag> {
+    SCRIPT_TAGS.iter()
+        .filter(move |(iso, _)| Tag::from_bytes(iso) == script)
+        .map(|(_, tag)| Tag::from_bytes(tag))
+}
+
+/// Returns a Unicode script for an OpenType script tag.
+///
+/// The script is returned as an [ISO 15924](https://unicode.org/iso15924/) code.
+/// Both new and old Indic tags are supported.
+/// The `kana` tag resolves to Katakana (`Kana`).
+///
+/// Returns `None` for unknown tags and `DFLT`.
+pub fn unicode_script(tag: Tag) -> Option<Tag> {
+    SCRIPT_TAGS.iter()
+        .find(|(_, t)| Tag::from_bytes(t) == tag)
+        .map(|(iso, _)| Tag::from_bytes(iso))
+}
+
+// (ISO 15924 code, OpenType tag). New tags must precede old ones.
+static SCRIPT_TAGS: &[(&[u8; 4], &[u8; 4])] = &[
+(b"Adlm", b"adlm"),
+    (b"Aghb", b"aghb"),
+    (b"Ahom", b"ahom"),
+    (b"Arab", b"arab"),
+    (b"Armi", b"armi"),
+    (b"Armn", b"armn"),
+    (b"Avst", b"avst"),
+    (b"Bali", b"bali"),
+    (b"Bamu", b"bamu"),
+    (b"Bass", b"bass"),
+    (b"Batk", b"batk"),
+    (b"Beng", b"bng2"),
+    (b"Beng", b"beng"),
+    (b"Bhks", b"bhks"),
+    (b"Bopo", b"bopo"),
+    (b"Brah", b"brah"),
+    (b"Brai", b"brai"),
+    (b"Bugi", b"bugi"),
+    (b"Buhd", b"buhd"),
+    (b"Cakm", b"cakm"),
+    (b"Cans", b"cans"),
+    (b"Cari", b"cari"),
+    (b"Cham", b"cham"),
+    (b"Cher", b"cher"),
+    (b"Chrs", b"chrs"),
+    (b"Copt", b"copt"),
+    (b"Cpmn", b"cpmn"),
+    (b"Cprt", b"cprt"),
+    (b"Cyrl", b"cyrl"),
+    (b"Deva", b"dev2"),
+    (b"Deva", b"deva"),
+    (b"Diak", b"diak"),
+    (b"Dogr", b"dogr"),
+    (b"Dsrt", b"dsrt"),
+    (b"Dupl", b"dupl"),
+    (b"Egyp", b"egyp"),
+    (b"Elba", b"elba"),
+    (b"Elym", b"elym"),
+    (b"Ethi", b"ethi"),
+    (b"Geor", b"geor"),
+    (b"Glag", b"glag"),
+    (b"Gong", b"gong"),
+    (b"Gonm", b"gonm"),
+    (b"Goth", b"goth"),
+    (b"Gran", b"gran"),
+    (b"Grek", b"grek"),
+    (b"Gujr", b"gjr2"),
+    (b"Gujr", b"gujr"),
+    (b"Guru", b"gur2"),
+    (b"Guru", b"guru"),
+    (b"Hang", b"hang"),
+    (b"Hani", b"hani"),
+    (b"Hano", b"hano"),
+    (b"Hatr", b"hatr"),
+    (b"Hebr", b"hebr"),
+    (b"Hluw", b"hluw"),
+    (b"Hmng", b"hmng"),
+    (b"Hmnp", b"hmnp"),
+    (b"Hung", b"hung"),
+    (b"Ital", b"ital"),
+    (b"Java", b"java"),
+    (b"Kali", b"kali"),
+    (b"Kana", b"kana"),
+    (b"Hira", b"kana"),
+    (b"Kawi", b"kawi"),
+    (b"Khar", b"khar"),
+    (b"Khmr", b"khmr"),
+    (b"Khoj", b"khoj"),
+    (b"Kits", b"kits"),
+    (b"Knda", b"knd2"),
+    (b"Knda", b"knda"),
+    (b"Kthi", b"kthi"),
+    (b"Lana", b"lana"),
+    (b"Laoo", b"lao "),
+    (b"Latn", b"latn"),
+    (b"Lepc", b"lepc"),
+    (b"Limb", b"limb"),
+    (b"Lina", b"lina"),
+    (b"Linb", b"linb"),
+    (b"Lisu", b"lisu"),
+    (b"Lyci", b"lyci"),
+    (b"Lydi", b"lydi"),
+    (b"Mahj", b"mahj"),
+    (b"Maka", b"maka"),
+    (b"Mand", b"mand"),
+    (b"Mani", b"mani"),
+    (b"Marc", b"marc"),
+    (b"Medf", b"medf"),
+    (b"Mend", b"mend"),
+    (b"Merc", b"merc"),
+    (b"Mero", b"mero"),
+    (b"Mlym", b"mlm2"),
+    (b"Mlym", b"mlym"),
+    (b"Modi", b"modi"),
+    (b"Mong", b"mong"),
+    (b"Mroo", b"mroo"),
+    (b"Mtei", b"mtei"),
+    (b"Mult", b"mult"),
+    (b"Mymr", b"mym2"),
+    (b"Mymr", b"mymr"),
+    (b"Nagm", b"nagm"),
+    (b"Nand", b"nand"),
+    (b"Narb", b"narb"),
+    (b"Nbat", b"nbat"),
+    (b"Newa", b"newa"),
+    (b"Nkoo", b"nko "),
+    (b"Nshu", b"nshu"),
+    (b"Ogam", b"ogam"),
+    (b"Olck", b"olck"),
+    (b"Orkh", b"orkh"),
+    (b"Orya", b"ory2"),
+    (b"Orya", b"orya"),
+    (b"Osge", b"osge"),
+    (b"Osma", b"osma"),
+    (b"Ougr", b"ougr"),
+    (b"Palm", b"palm"),
+    (b"Pauc", b"pauc"),
+    (b"Perm", b"perm"),
+    (b"Phag", b"phag"),
+    (b"Phli", b"phli"),
+    (b"Phlp", b"phlp"),
+    (b"Phnx", b"phnx"),
+    (b"Plrd", b"plrd"),
+    (b"Prti", b"prti"),
+    (b"Rjng", b"rjng"),
+    (b"Rohg", b"rohg"),
+    (b"Runr", b"runr"),
+    (b"Samr", b"samr"),
+    (b"Sarb", b"sarb"),
+    (b"Saur", b"saur"),
+    (b"Sgnw", b"sgnw"),
+    (b"Shaw", b"shaw"),
+    (b"Shrd", b"shrd"),
+    (b"Sidd", b"sidd"),
+    (b"Sind", b"sind"),
+    (b"Sinh", b"sinh"),
+    (b"Sogd", b"sogd"),
+    (b"Sogo", b"sogo"),
+    (b"Sora", b"sora"),
+    (b"Soyo", b"soyo"),
+    (b"Sund", b"sund"),
+    (b"Sylo", b"sylo"),
+    (b"Syrc", b"syrc"),
+    (b"Tagb", b"tagb"),
+    (b"Takr", b"takr"),
+    (b"Tale", b"tale"),
+    (b"Talu", b"talu"),
+    (b"Taml", b"tml2"),
+    (b"Taml", b"taml"),
+    (b"Tang", b"tang"),
+    (b"Tavt", b"tavt"),
+    (b"Telu", b"tel2"),
+    (b"Telu", b"telu"),
+    (b"Tfng", b"tfng"),
+    (b"Tglg", b"tglg"),
+    (b"Thaa", b"thaa"),
+    (b"Thai", b"thai"),
+    (b"Tibt", b"tibt"),
+    (b"Tirh", b"tirh"),
+    (b"Tnsa", b"tnsa"),
+    (b"Toto", b"toto"),
+    (b"Ugar", b"ugar"),
+    (b"Vaii", b"vai "),
+    (b"Vith", b"vith"),
+    (b"Wara", b"wara"),
+    (b"Wcho", b"wcho"),
+    (b"Xpeo", b"xpeo"),
+    (b"Xsux", b"xsux"),
+    (b"Yezi", b"yezi"),
+    (b"Yiii", b"yi  "),
+    (b"Zanb", b"zanb"),
+    (b"Zmth", b"math"),
+];
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_opentype() {
+        let tags = |s| opentype_script_tags(Tag::from_bytes(s)).collect::<std::vec::Vec<_>>();
+        assert_eq!(tags(b"Latn"), [Tag::from_bytes(b"latn")]);
+        assert_eq!(tags(b"Deva"), [Tag::from_bytes(b"dev2"), Tag::from_bytes(b"deva")]);
+        assert_eq!(tags(b"Mymr"), [Tag::from_bytes(b"mym2"), Tag::from_bytes(b"mymr")]);
+        assert_eq!(tags(b"Hira"), [Tag::from_bytes(b"kana")]);
+        assert_eq!(tags(b"Laoo"), [Tag::from_bytes(b"lao ")]);
+        assert_eq!(tags(b"Zmth"), [Tag::from_bytes(b"math")]);
+        assert!(tags(b"Zzzz").is_empty());
+    }
+
+    #[test]
+    fn to_unicode() {
+        assert_eq!(unicode_script(Tag::from_bytes(b"tml2")), Some(Tag::from_bytes(b"Taml")));
+        assert_eq!(unicode_script(Tag::from_bytes(b"taml")), Some(Tag::from_bytes(b"Taml")));
+        assert_eq!(unicode_script(Tag::from_bytes(b"kana")), Some(Tag::from_bytes(b"Kana")));
+        assert_eq!(unicode_script(Tag::from_bytes(b"yi  ")), Some(Tag::from_bytes(b"Yiii")));
+        assert_eq!(unicode_script(Tag::from_bytes(b"DFLT")), None);
+    }
+
+    #[test]
+    fn unique() {
+        for (i, (iso, tag)) in SCRIPT_TAGS.iter().enumerate() {
+            for (iso2, tag2) in &SCRIPT_TAGS[i + 1..] {
+                assert!(iso != iso2 || tag != tag2);
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Helpers shared by code that reads and writes sfnt font data.
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use crate::parser::Stream;
+
+/// The offset table size.
+#[cfg(feature = "std")]
+pub(crate) const SFNT_HEADER_SIZE: usize = 12;
+/// A table record size.
+#[cfg(feature = "std")]
+pub(crate) const TABLE_RECORD_SIZE: usize = 16;
+
+/// A value the checksum of the whole font must be equal to.
+pub(crate) const CHECKSUM_MAGIC: u32 = 0xB1B0AFBA;
+
+pub(crate) const HEAD_CHECKSUM_ADJUSTMENT_OFFSET: usize = 8;
+#[cfg(all(feature = "std", feature = "variable-fonts"))]
+pub(crate) const HEAD_BBOX_OFFSET: usize = 36;
+#[cfg(feature = "std")]
+pub(crate) const HEAD_INDEX_TO_LOC_FORMAT_OFFSET: usize = 50;
+
+// `vhea` has the same layout.
+#[cfg(all(feature = "std", feature = "variable-fonts"))]
+pub(crate) const HHEA_ADVANCE_MAX_OFFSET: usize = 10;
+#[cfg(feature = "std")]
+pub(crate) const HHEA_NUMBER_OF_METRICS_OFFSET: usize = 34;
+
+/// Calculates an sfnt checksum, treating 4 bytes at `zeroed_offset` as zeros.
+///
+/// Data is summed as big-endian `u32` values, with the last value padded with zeros.
+pub(crate) fn checksum(data: &[u8], zeroed_offset: Option<usize>) -> u32 {
+    let mut sum = 0u32;
+    let mut s = Stream::new(data);
+    while let Some(n) = s.read::<u32>() {
+        sum = sum.wrapping_add(n);
+    }
+
+    if let Some(tail) = s.tail() {
+        let mut last = [0; 4];
+        last[..tail.len()].copy_from_slice(tail);
+        sum = sum.wrapping_add(u32::from_be_bytes(last));
+    }
+
+    // The offset is not necessarily aligned, so bytes are subtracted one by one.
+    if let Some(offset) = zeroed_offset {
+        for (i, byte) in data.iter().enumerate().skip(offset).take(4) {
+            sum = sum.wrapping_sub(u32::from(*byte) << (24 - (i % 4) * 8));
+        }
+    }
+
+    sum
+}
+
+/// Returns a length rounded up to a 4-byte boundary.
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn padded_len(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+/// Pads data with zeros to a 4-byte boundary.
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn pad(data: &mut Vec<u8>) {
+    let len = padded_len(data.len());
+    data.resize(len, 0);
+}
+
+/// Calculates `searchRange`, `entrySelector` and `rangeShift`
+/// for `count` binary search records of `size` bytes.
+#[cfg(feature = "std")]
+pub(crate) fn search_params(count: u16, size: u16) -> (u16, u16, u16) {
+    if count == 0 {
+        return (0, 0, 0);
+    }
+
+    let mut entry_selector = 0u16;
+    while count >> (entry_selector + 1) != 0 {
+        entry_selector += 1;
+    }
+
+    let search_range = (1u32 << entry_selector) * u32::from(size);
+    let range_shift = u32::from(count) * u32::from(size) - search_range;
+    (search_range as u16, entry_selector, range_shift as u16)
+}
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn write_u16(data: &mut [u8], offset: usize, n: u16) -> Option<()> {
+    data.get_mut(offset..offset + 2)?.copy_from_slice(&n.to_be_bytes());
+    Some(())
+}
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn write_u32(data: &mut [u8], offset: usize, n: u32) -> Option<()> {
+    data.get_mut(offset..offset + 4)?.copy_from_slice(&n.to_be_bytes());
+    Some(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(checksum(&[], None), 0);
+        assert_eq!(checksum(&[0, 0, 0, 1, 0, 0, 0, 2], None), 3);
+        assert_eq!(checksum(&[0, 0, 0, 1, 2], None), 0x02000001);
+        assert_eq!(checksum(&[0xFF; 8], None), 0xFFFFFFFE);
+    }
+
+    #[test]
+    fn zeroed_checksum() {
+        assert_eq!(checksum(&[0, 0, 0, 1, 0, 0, 0, 2], Some(4)), 1);
+        assert_eq!(checksum(&[0, 0, 0, 1, 0, 0, 0, 2], Some(8)), 3);
+        // Unaligned.
+        assert_eq!(checksum(&[0, 0, 0, 1, 2, 3, 4, 5], Some(1)), 0x00030405);
+        // Partially out of bounds.
+        assert_eq!(checksum(&[0, 0, 0, 1, 2], Some(3)), 0);
+    }
+
+    #[test]
+    fn search_params_values() {
+        assert_eq!(search_params(0, 16), (0, 0, 0));
+        assert_eq!(search_params(1, 16), (16, 0, 0));
+        assert_eq!(search_params(5, 16), (64, 2, 16));
+        assert_eq!(search_params(16, 16), (256, 4, 0));
+        assert_eq!(search_params(39, 2), (64, 5, 14));
+    }
+
+    #[test]
+    fn padding() {
+        let mut data = vec![1, 2, 3, 4, 5];
+        pad(&mut data);
+        assert_eq!(data, &[1, 2, 3, 4, 5, 0, 0, 0]);
+        pad(&mut data);
+        assert_eq!(data.len(), 8);
+    }
+}
@@ -0,0 +1,272 @@
+use std::vec::Vec;
+
+use crate::{cff1, Face, GlyphId, Tag};
+#[cfg(feature = "variable-fonts")]
+use crate::cff2;
+
+/// The number of glyphs in `GlyphSizeSummary::largest`.
+const LARGEST_GLYPHS_COUNT: usize = 10;
+
+/// A table size.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TableSize {
+    /// Table's tag.
+    pub tag: Tag,
+
+    /// Table's length in bytes.
+    pub length: u32,
+
+    /// Table's share of the total font size in a 0..=100 range.
+    pub percentage: f32,
+}
+
+/// Per-glyph data size distribution.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GlyphSizeSummary {
+    /// A tag of the table that stores glyph outlines, like `glyf` or `CFF `.
+    pub tag: Tag,
+
+    /// The total size of all glyphs in bytes.
+    pub total: u64,
+
+    /// The number of glyphs without data, like a space.
+    pub empty: u16,
+
+    /// The smallest non-empty glyph size in bytes.
+    pub min: u32,
+
+    /// The largest glyph size in bytes.
+    pub max: u32,
+
+    /// The mean non-empty glyph size in bytes.
+    pub mean: f32,
+
+    /// The median non-empty glyph size in bytes.
+    pub median: u32,
+
+    /// Up to 10 largest glyphs with their sizes, largest first.
+    pub largest: Vec<(GlyphId, u32)>,
+}
+
+/// A font size report.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SizeReport {
+    /// The face size in bytes.
+    ///
+    /// Includes the font directory and all tables without padding.
+    /// Tables shared by collection faces are counted for each face.
+    pub total_size: u64,
+
+    /// Table sizes, largest first.
+    pub tables: Vec<TableSize>,
+
+    /// Glyph outlines size distribution.
+    ///
+    /// Only `glyf`, `CFF ` and `CFF2` tables are supported.
+    pub glyphs: Option<GlyphSizeSummary>,
+}
+
+impl<'a> Face<'a> {
+    /// Returns a report of table sizes and a glyph size distribution.
+    ///
+    /// Useful for finding out what makes a font large.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+    /// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+    /// let report = face.size_report();
+    /// let glyphs = report.glyphs.unwrap();
+    /// assert_eq!(glyphs.tag, ttf_parser::Tag::from_bytes(b"glyf"));
+    /// assert_eq!(glyphs.largest[0].0, ttf_parser::GlyphId(1));
+    /// ```
+    pub fn size_report(&self) -> SizeReport {
+        // Offset table and table records.
+        let directory_size = 12 + 16 * self.table_records().count() as u64;
+        let total_size = directory_size
+            + self.table_records().map(|r| u64::from(r.length)).sum::<u64>();
+
+        let mut tables: Vec<TableSize> = self.table_records().map(|record| TableSize {
+            tag: record.tag,
+            length: record.length,
+            percentage: (f64::from(record.length) / total_size as f64 * 100.0) as f32,
+        }).collect();
+        tables.sort_by_key(|t| core::cmp::Reverse(t.length));
+
+        SizeReport { total_size, tables, glyphs: self.glyph_size_summary() }
+    }
+
+    fn glyph_size_summary(&self) -> Option<GlyphSizeSummary> {
+        let ids = (0..self.number_of_glyphs.get()).map(GlyphId);
+
+        let (tag, sizes): (&[u8; 4], Vec<u32>) = if let (Some(loca), Some(_)) = (self.loca, self.glyf) {
+            (b"glyf", ids.map(|id| glyph_len(loca.glyph_range(id).map(|r| r.len()))).collect())
+        } else if let Some(ref metadata) = self.cff1 {
+            (b"CFF ", ids.map(|id| glyph_len(cff1::glyph_charstring_len(metadata, id))).collect())
+        } else {
+            #[cfg(feature = "variable-fonts")]
+            {
+                let metadata = self.cff2.as_ref()?;
+                (b"CFF2", ids.map(|id| glyph_len(cff2::glyph_charstring_len(metadata, id))).collect())
+            }
+
+            #[cfg(not(feature = "variable-fonts"))]
+            {
+                return None;
+            }
+        };
+
+        let mut largest: Vec<(GlyphId, u32)> = sizes.iter().enumerate()
+            .filter(|(_, size)| **size != 0)
+            .map(|(id, size)| (GlyphId(id as u16), *size))
+            .collect();
+        // A stable sort keeps glyphs with the same size in ID order.
+        largest.sort_by_key(|g| core::cmp::Reverse(g.1));
+        largest.truncate(LARGEST_GLYPHS_COUNT);
+
+        let mut non_empty: Vec<u32> = sizes.iter().cloned().filter(|size| *size != 0).collect();
+        non_empty.sort();
+
+        let total: u64 = non_empty.iter().map(|size| u64::from(*size)).sum();
+        let mean = if non_empty.is_empty() { 0.0 } else { (total as f64 / non_empty.len() as f64) as f32 };
+
+        Some(GlyphSizeSummary {
+            tag: Tag::from_bytes(tag),
+            total,
+            empty: (sizes.len() - non_empty.len()) as u16,
+            min: non_empty.first().cloned().unwrap_or(0),
+            max: non_empty.last().cloned().unwrap_or(0),
+            mean,
+            median: non_empty.get(non_empty.len() / 2).cloned().unwrap_or(0),
+            largest,
+        })
+    }
+}
+
+/// Treats glyphs with malformed data as empty.
+fn glyph_len(len: Option<usize>) -> u32 {
+    len.map(|len| len as u32).unwrap_or(0)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FontWriter;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    /// Replaces `glyf` outlines in the demo font with a CFF-based table.
+    fn cff_font(tag: &[u8; 4], table: Vec<u8>) -> Vec<u8> {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let mut writer = FontWriter::from_face(&face);
+        writer.remove_table(Tag::from_bytes(b"glyf"));
+        writer.remove_table(Tag::from_bytes(b"loca"));
+        writer.set_table(Tag::from_bytes(tag), table);
+        writer.finish()
+    }
+
+    #[test]
+    fn cff1_glyphs() {
+        let font = cff_font(b"CFF ", writer::convert(&[
+            // Header
+            UInt8(1), // major version
+            UInt8(0), // minor version
+            UInt8(4), // header size
+            UInt8(0), // absolute offset
+            // Name INDEX
+            UInt16(0), // count
+            // Top DICT INDEX
+            UInt16(1), // count
+            UInt8(1), // offset size
+            UInt8(1), // index [0]
+            UInt8(3), // index [1]
+            CFFInt(17), UInt8(17), // char strings offset
+            // String INDEX
+            UInt16(0), // count
+            // Global Subroutines INDEX
+            UInt16(0), // count
+            // CharStrings INDEX
+            UInt16(2), // count
+            UInt8(1), // offset size
+            UInt8(1), // index [0]
+            UInt8(1), // index [1]
+            UInt8(5), // index [2]
+            // Glyph 0: empty
+            // Glyph 1
+            CFFInt(10), CFFInt(20), UInt8(21), // rmoveto
+            UInt8(14), // endchar
+        ]));
+
+        let face = Face::from_slice(&font, 0).unwrap();
+        let report = face.size_report();
+        assert!(report.tables.iter().any(|t| t.tag == Tag::from_bytes(b"CFF ")));
+        assert!(report.tables.iter().all(|t| t.tag != Tag::from_bytes(b"glyf")));
+
+        assert_eq!(report.glyphs, Some(GlyphSizeSummary {
+            tag: Tag::from_bytes(b"CFF "),
+            total: 4,
+            empty: 1,
+            min: 4,
+            max: 4,
+            mean: 4.0,
+            median: 4,
+            largest: vec![(GlyphId(1), 4)],
+        }));
+    }
+
+    #[cfg(feature = "variable-fonts")]
+    #[test]
+    fn cff2_glyphs() {
+        let font = cff_font(b"CFF2", writer::convert(&[
+            // Header
+            UInt8(2), // major version
+            UInt8(0), // minor version
+            UInt8(5), // header size
+            UInt16(2), // top dict length
+            // Top DICT
+            CFFInt(11), UInt8(17), // char strings offset
+            // Global Subroutines INDEX
+            UInt32(0), // count
+            // CharStrings INDEX
+            UInt32(2), // count
+            UInt8(1), // offset size
+            UInt8(1), // index [0]
+            UInt8(4), // index [1]
+            UInt8(10), // index [2]
+            // Glyph 0
+            CFFInt(10), CFFInt(20), UInt8(21), // rmoveto
+            // Glyph 1
+            CFFInt(10), CFFInt(20), UInt8(21), // rmoveto
+            CFFInt(5), CFFInt(5), UInt8(5), // rlineto
+        ]));
+
+        let face = Face::from_slice(&font, 0).unwrap();
+        assert_eq!(face.size_report().glyphs, Some(GlyphSizeSummary {
+            tag: Tag::from_bytes(b"CFF2"),
+            total: 9,
+            empty: 0,
+            min: 3,
+            max: 6,
+            mean: 4.5,
+            median: 6,
+            largest: vec![(GlyphId(1), 6), (GlyphId(0), 3)],
+        }));
+    }
+
+    #[test]
+    fn no_outlines() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let mut writer = FontWriter::from_face(&face);
+        writer.remove_table(Tag::from_bytes(b"glyf"));
+        let font = writer.finish();
+
+        let face = Face::from_slice(&font, 0).unwrap();
+        let report = face.size_report();
+        assert_eq!(report.glyphs, None);
+        assert_eq!(report.tables.len(), face.table_records().count());
+    }
+}
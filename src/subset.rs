@@ -0,0 +1,600 @@
+//! Font subsetting.
+//!
+//! Produces a new font that contains only the requested glyphs.
+//!
+//! Only TrueType (`glyf`-based) fonts are supported.
+//! Tables that are not listed below are removed.
+//!
+//! - `glyf`, `loca`, `hmtx`, `vmtx`, `cmap` and `post` are rebuilt for the retained glyphs
+//! - `head`, `hhea`, `vhea`, `maxp` and `OS/2` are updated
+//! - `name`, `cvt `, `fpgm`, `prep`, `gasp`, `meta` and `VDMX` are copied as is
+//! - `GDEF`, `GSUB`, `GPOS` and `kern` are handled according to `LayoutTables`
+
+use core::cmp;
+use core::convert::TryFrom;
+use std::vec::Vec;
+
+use crate::sfnt::{
+    pad, search_params, write_u16, write_u32, HEAD_INDEX_TO_LOC_FORMAT_OFFSET, HHEA_NUMBER_OF_METRICS_OFFSET,
+};
+use crate::tables::{cmap, glyf, loca, post};
+use crate::{Face, FontWriter, GlyphId, GlyphSet, Tag};
+
+const MAXP_NUMBER_OF_GLYPHS_OFFSET: usize = 4;
+const OS2_FIRST_CHAR_INDEX_OFFSET: usize = 64;
+const POST_HEADER_SIZE: usize = 32;
+
+/// Tables that do not depend on glyphs and are copied as is.
+const COPIED_TABLES: &[&[u8; 4]] = &[b"VDMX", b"cvt ", b"fpgm", b"gasp", b"meta", b"name", b"prep"];
+
+/// Glyph-indexed layout tables.
+const LAYOUT_TABLES: &[&[u8; 4]] = &[b"GDEF", b"GPOS", b"GSUB", b"kern"];
+
+/// Layout tables handling.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LayoutTables {
+    /// `GDEF`, `GSUB`, `GPOS` and `kern` tables are removed.
+    ///
+    /// Glyph IDs are remapped, so the retained glyphs are stored without gaps.
+    Drop,
+
+    /// Glyphs that can be produced by `GSUB` are retained as well
+    /// and `GDEF`, `GSUB`, `GPOS` and `kern` tables are copied as is.
+    ///
+    /// Since layout tables are not rewritten, glyph IDs are preserved
+    /// and removed glyphs become empty.
+    Closure,
+}
+
+impl Default for LayoutTables {
+    #[inline]
+    fn default() -> Self {
+        LayoutTables::Drop
+    }
+}
+
+/// Subsetting options.
+#[derive(Clone, Default, Debug)]
+pub struct SubsetOptions {
+    /// Glyphs to retain.
+    ///
+    /// `.notdef` and components of composite glyphs are always retained.
+    pub glyphs: GlyphSet,
+
+    /// Characters to retain.
+    ///
+    /// Glyphs are resolved via `cmap`. Characters without a glyph are ignored.
+    pub chars: Vec<char>,
+
+    /// Layout tables handling.
+    pub layout_tables: LayoutTables,
+}
+
+/// Creates a font subset.
+///
+/// The `cmap` table of the new font maps all characters of the original font
+/// that point to the retained glyphs, not only `SubsetOptions::chars`.
+///
+/// Returns `None` when the face is not a TrueType one or on error.
+///
+/// # Example
+///
+/// ```
+/// use ttf_parser::subset::{subset, SubsetOptions};
+///
+/// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+/// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+/// let options = SubsetOptions { chars: vec!['A'], ..SubsetOptions::default() };
+/// let data = subset(&face, &options).unwrap();
+/// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+/// assert_eq!(face.number_of_glyphs(), 2);
+/// ```
+pub fn subset(face: &Face, options: &SubsetOptions) -> Option<Vec<u8>> {
+    let loca_table = face.loca?;
+    let glyf_table = face.glyf?;
+
+    let mut glyphs = options.glyphs.clone();
+    glyphs.insert(GlyphId(0));
+    for c in &options.chars {
+        if let Some(glyph_id) = face.glyph_index(*c) {
+            glyphs.insert(glyph_id);
+        }
+    }
+
+    let keep_layout = options.layout_tables == LayoutTables::Closure;
+    if keep_layout {
+        if let Some(gsub) = face.gsub {
+            gsub.closure(&mut glyphs);
+        }
+    }
+
+    components_closure(loca_table, glyf_table, &mut glyphs);
+
+    let map = GlyphMap::new(&glyphs, face.number_of_glyphs(), keep_layout);
+    let number_of_glyphs = map.len()?;
+
+    let mut writer = FontWriter::new(0x00010000);
+    let (glyf_data, loca_data) = write_glyf(loca_table, glyf_table, &map)?;
+    writer.set_table(Tag::from_bytes(b"glyf"), glyf_data);
+    writer.set_table(Tag::from_bytes(b"loca"), loca_data);
+
+    let mut head = face.head.to_vec();
+    // Long offsets are always used.
+    write_u16(&mut head, HEAD_INDEX_TO_LOC_FORMAT_OFFSET, 1)?;
+    writer.set_table(Tag::from_bytes(b"head"), head);
+
+    let mut maxp = face.maxp.to_vec();
+    write_u16(&mut maxp, MAXP_NUMBER_OF_GLYPHS_OFFSET, number_of_glyphs)?;
+    writer.set_table(Tag::from_bytes(b"maxp"), maxp);
+
+    // Every glyph gets a full metrics record.
+    let hmtx = face.hmtx?;
+    let mut hhea = face.hhea.to_vec();
+    write_u16(&mut hhea, HHEA_NUMBER_OF_METRICS_OFFSET, number_of_glyphs)?;
+    writer.set_table(Tag::from_bytes(b"hhea"), hhea);
+    writer.set_table(Tag::from_bytes(b"hmtx"), write_metrics(&map, |g| {
+        (hmtx.advance(g).unwrap_or(0), hmtx.side_bearing(g).unwrap_or(0))
+    }));
+
+    if let (Some(vhea), Some(vmtx)) = (face.vhea, face.vmtx) {
+        let mut vhea = vhea.to_vec();
+        // `vhea` has the same layout as `hhea`.
+        write_u16(&mut vhea, HHEA_NUMBER_OF_METRICS_OFFSET, number_of_glyphs)?;
+        writer.set_table(Tag::from_bytes(b"vhea"), vhea);
+        writer.set_table(Tag::from_bytes(b"vmtx"), write_metrics(&map, |g| {
+            (vmtx.advance(g).unwrap_or(0), vmtx.side_bearing(g).unwrap_or(0))
+        }));
+    }
+
+    let mappings = char_mappings(face.character_mapping_subtables(), &map);
+    writer.set_table(Tag::from_bytes(b"cmap"), write_cmap(&mappings));
+
+    if let Some(data) = face.table_data(Tag::from_bytes(b"OS/2")) {
+        let mut os2 = data.to_vec();
+        // First and last characters are limited to BMP.
+        let first = mappings.first().map(|m| m.0).unwrap_or(0).min(0xFFFF) as u16;
+        let last = mappings.last().map(|m| m.0).unwrap_or(0).min(0xFFFF) as u16;
+        // Ignore too short tables.
+        let _ = write_u16(&mut os2, OS2_FIRST_CHAR_INDEX_OFFSET, first);
+        let _ = write_u16(&mut os2, OS2_FIRST_CHAR_INDEX_OFFSET + 2, last);
+        writer.set_table(Tag::from_bytes(b"OS/2"), os2);
+    }
+
+    if let Some(data) = face.table_data(Tag::from_bytes(b"post")) {
+        writer.set_table(Tag::from_bytes(b"post"), write_post(face, data, &map)?);
+    }
+
+    for tag in COPIED_TABLES {
+        copy_table(face, Tag::from_bytes(tag), &mut writer);
+    }
+
+    if keep_layout {
+        for tag in LAYOUT_TABLES {
+            copy_table(face, Tag::from_bytes(tag), &mut writer);
+        }
+    }
+
+    Some(writer.finish())
+}
+
+fn copy_table(face: &Face, tag: Tag, writer: &mut FontWriter) {
+    if let Some(data) = face.table_data(tag) {
+        writer.set_table(tag, data.to_vec());
+    }
+}
+
+/// Adds components of composite glyphs.
+fn components_closure(loca_table: loca::Table, glyf_table: &[u8], glyphs: &mut GlyphSet) {
+    let mut stack: Vec<GlyphId> = glyphs.iter().collect();
+    while let Some(glyph_id) = stack.pop() {
+        if let Some(components) = glyf::components(loca_table, glyf_table, glyph_id) {
+            for component in components {
+                // Each glyph is processed once, so cycles are not a problem.
+                if glyphs.insert(component.glyph_id) {
+                    stack.push(component.glyph_id);
+                }
+            }
+        }
+    }
+}
+
+struct GlyphMap {
+    // Old glyph IDs indexed by new ones. `None` indicates a removed glyph.
+    glyphs: Vec<Option<GlyphId>>,
+    retain_ids: bool,
+}
+
+impl GlyphMap {
+    fn new(glyphs: &GlyphSet, number_of_glyphs: u16, retain_ids: bool) -> Self {
+        let retained = glyphs.iter().filter(|g| g.0 < number_of_glyphs);
+        let glyphs = if retain_ids {
+            let len = retained.clone().last().map(|g| usize::from(g.0) + 1).unwrap_or(0);
+            let mut list = vec![None; len];
+            for glyph_id in retained {
+                list[usize::from(glyph_id.0)] = Some(glyph_id);
+            }
+            list
+        } else {
+            retained.map(Some).collect()
+        };
+
+        GlyphMap { glyphs, retain_ids }
+    }
+
+    fn len(&self) -> Option<u16> {
+        u16::try_from(self.glyphs.len()).ok()
+    }
+
+    /// Returns a new glyph ID.
+    fn get(&self, glyph_id: GlyphId) -> Option<GlyphId> {
+        if self.retain_ids {
+            match self.glyphs.get(usize::from(glyph_id.0)) {
+                Some(Some(_)) => Some(glyph_id),
+                _ => None,
+            }
+        } else {
+            self.glyphs
+                .binary_search(&Some(glyph_id))
+                .ok()
+                .map(|index| GlyphId(index as u16))
+        }
+    }
+}
+
+fn write_glyf(
+    loca_table: loca::Table,
+    glyf_table: &[u8],
+    map: &GlyphMap,
+) -> Option<(Vec<u8>, Vec<u8>)> {
+    let mut glyf_data = Vec::new();
+    let mut loca_data = Vec::new();
+    for old_id in &map.glyphs {
+        loca_data.extend_from_slice(&u32::try_from(glyf_data.len()).ok()?.to_be_bytes());
+
+        let range = old_id.and_then(|id| loca_table.glyph_range(id));
+        if let Some(data) = range.and_then(|range| glyf_table.get(range)) {
+            let start = glyf_data.len();
+            glyf_data.extend_from_slice(data);
+            if !map.retain_ids {
+                // Components are always retained, so the fallback is not used in practice.
+                glyf::remap_components(&mut glyf_data[start..], |id| {
+                    map.get(id).unwrap_or(GlyphId(0))
+                })?;
+            }
+
+            pad(&mut glyf_data);
+        }
+    }
+
+    loca_data.extend_from_slice(&u32::try_from(glyf_data.len()).ok()?.to_be_bytes());
+    Some((glyf_data, loca_data))
+}
+
+fn write_metrics(map: &GlyphMap, metrics: impl Fn(GlyphId) -> (u16, i16)) -> Vec<u8> {
+    let mut data = Vec::with_capacity(map.glyphs.len() * 4);
+    for old_id in &map.glyphs {
+        let (advance, side_bearing) = old_id.map(&metrics).unwrap_or((0, 0));
+        data.extend_from_slice(&advance.to_be_bytes());
+        data.extend_from_slice(&side_bearing.to_be_bytes());
+    }
+
+    data
+}
+
+/// Returns sorted code points with new glyph IDs.
+///
+/// Uses a format 12 Unicode subtable when present, since format 4 is limited to BMP.
+fn char_mappings(mut subtables: cmap::Subtables, map: &GlyphMap) -> Vec<(u32, u16)> {
+    let mut mappings = Vec::new();
+    let subtable = subtables
+        .filter(|s| s.is_unicode())
+        .find(|s| s.format() == cmap::Format::SegmentedCoverage)
+        .or_else(|| subtables.find(|s| s.is_unicode()));
+    if let Some(subtable) = subtable {
+        subtable.mappings(|c, id| {
+            // Characters mapped to `.notdef` are pointless.
+            if let Some(new_id) = map.get(id).filter(|id| id.0 != 0) {
+                mappings.push((c, new_id.0));
+            }
+
+            true
+        });
+    }
+
+    mappings.sort_unstable();
+    mappings.dedup_by_key(|m| m.0);
+    mappings
+}
+
+/// Splits mappings into ranges of consecutive code points mapped to consecutive glyphs.
+fn char_ranges(mappings: &[(u32, u16)]) -> Vec<(u32, u32, u16)> {
+    let mut ranges: Vec<(u32, u32, u16)> = Vec::new();
+    for &(c, glyph_id) in mappings {
+        if let Some(last) = ranges.last_mut() {
+            let next_id = u32::from(last.2) + (c - last.0);
+            if last.1 + 1 == c && next_id == u32::from(glyph_id) {
+                last.1 = c;
+                continue;
+            }
+        }
+
+        ranges.push((c, c, glyph_id));
+    }
+
+    ranges
+}
+
+fn write_cmap(mappings: &[(u32, u16)]) -> Vec<u8> {
+    let ranges = char_ranges(mappings);
+    let bmp_ranges: Vec<_> = ranges.iter().cloned().filter(|r| r.1 <= 0xFFFF).collect();
+    let format4 = write_cmap_format4(&bmp_ranges);
+    // Format 12 is required only for characters outside BMP,
+    // and format 4 can be too large for big fonts.
+    let format12 = if format4.is_none() || bmp_ranges.len() != ranges.len() {
+        Some(write_cmap_format12(&ranges))
+    } else {
+        None
+    };
+
+    let mut subtables = Vec::new();
+    if let Some(data) = format4 {
+        subtables.push((1u16, data));
+    }
+
+    if let Some(data) = format12 {
+        subtables.push((10u16, data));
+    }
+
+    let mut data = Vec::new();
+    data.extend_from_slice(&0u16.to_be_bytes()); // version
+    data.extend_from_slice(&(subtables.len() as u16).to_be_bytes());
+    let mut offset = 4 + subtables.len() * 8;
+    for (encoding_id, subtable) in &subtables {
+        data.extend_from_slice(&3u16.to_be_bytes()); // platform ID: Windows
+        data.extend_from_slice(&encoding_id.to_be_bytes());
+        data.extend_from_slice(&(offset as u32).to_be_bytes());
+        offset += subtable.len();
+    }
+
+    for (_, subtable) in subtables {
+        data.extend_from_slice(&subtable);
+    }
+
+    data
+}
+
+fn write_cmap_format4(ranges: &[(u32, u32, u16)]) -> Option<Vec<u8>> {
+    // The last segment must map 0xFFFF, so ranges are split before it.
+    let mut segments: Vec<(u16, u16, u16)> = ranges.iter()
+        .filter(|r| r.0 < 0xFFFF)
+        .map(|r| (r.0 as u16, cmp::min(r.1, 0xFFFE) as u16, r.2.wrapping_sub(r.0 as u16)))
+        .collect();
+    segments.push((0xFFFF, 0xFFFF, 1));
+
+    let seg_count = u16::try_from(segments.len()).ok()?;
+    let length = u16::try_from(16 + segments.len() * 8).ok()?;
+    let (search_range, entry_selector, range_shift) = search_params(seg_count, 2);
+
+    let mut data = Vec::with_capacity(usize::from(length));
+    for n in &[4, length, 0, seg_count * 2, search_range, entry_selector, range_shift] {
+        data.extend_from_slice(&n.to_be_bytes());
+    }
+
+    for segment in &segments {
+        data.extend_from_slice(&segment.1.to_be_bytes()); // end code
+    }
+
+    data.extend_from_slice(&0u16.to_be_bytes()); // reserved pad
+    for segment in &segments {
+        data.extend_from_slice(&segment.0.to_be_bytes()); // start code
+    }
+
+    for segment in &segments {
+        data.extend_from_slice(&segment.2.to_be_bytes()); // id delta
+    }
+
+    for _ in &segments {
+        data.extend_from_slice(&0u16.to_be_bytes()); // id range offset
+    }
+
+    Some(data)
+}
+
+fn write_cmap_format12(ranges: &[(u32, u32, u16)]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(16 + ranges.len() * 12);
+    data.extend_from_slice(&12u16.to_be_bytes()); // format
+    data.extend_from_slice(&0u16.to_be_bytes()); // reserved
+    data.extend_from_slice(&(16 + ranges.len() as u32 * 12).to_be_bytes());
+    data.extend_from_slice(&0u32.to_be_bytes()); // language
+    data.extend_from_slice(&(ranges.len() as u32).to_be_bytes());
+    for &(start, end, glyph_id) in ranges {
+        data.extend_from_slice(&start.to_be_bytes());
+        data.extend_from_slice(&end.to_be_bytes());
+        data.extend_from_slice(&u32::from(glyph_id).to_be_bytes());
+    }
+
+    data
+}
+
+/// Writes a version 2.0 table when glyphs have names and version 3.0 otherwise.
+fn write_post(face: &Face, data: &[u8], map: &GlyphMap) -> Option<Vec<u8>> {
+    let mut post = data.get(..POST_HEADER_SIZE)?.to_vec();
+    let names: Vec<Option<&str>> = map.glyphs.iter()
+        .map(|id| id.and_then(|id| face.glyph_name(id)))
+        .collect();
+
+    if names.iter().all(|name| name.is_none()) {
+        write_u32(&mut post, 0, 0x00030000)?;
+        return Some(post);
+    }
+
+    write_u32(&mut post, 0, 0x00020000)?;
+    post.extend_from_slice(&(names.len() as u16).to_be_bytes());
+
+    let mut custom_names: Vec<&str> = Vec::new();
+    for name in &names {
+        let name = name.unwrap_or(".notdef");
+        let index = match post::MACINTOSH_NAMES.iter().position(|n| *n == name) {
+            Some(index) => index,
+            // Names are stored as Pascal strings.
+            None if name.len() <= 255 => {
+                let index = match custom_names.iter().position(|n| *n == name) {
+                    Some(index) => index,
+                    None => {
+                        custom_names.push(name);
+                        custom_names.len() - 1
+                    }
+                };
+
+                post::MACINTOSH_NAMES.len() + index
+            }
+            None => 0,
+        };
+
+        post.extend_from_slice(&u16::try_from(index).ok()?.to_be_bytes());
+    }
+
+    for name in custom_names {
+        post.push(name.len() as u8);
+        post.extend_from_slice(name.as_bytes());
+    }
+
+    Some(post)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn demo() -> Vec<u8> {
+        std::fs::read("tests/fonts/demo.ttf").unwrap()
+    }
+
+    #[test]
+    fn chars() {
+        let data = demo();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let options = SubsetOptions { chars: vec!['A'], ..SubsetOptions::default() };
+        let data = subset(&face, &options).unwrap();
+        let subset = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(subset.number_of_glyphs(), 2);
+        assert_eq!(subset.glyph_index('A'), Some(GlyphId(1)));
+        assert_eq!(subset.glyph_hor_advance(GlyphId(1)), face.glyph_hor_advance(GlyphId(1)));
+        assert_eq!(subset.glyph_svg_path(GlyphId(1), Default::default()),
+                   face.glyph_svg_path(GlyphId(1), Default::default()));
+        assert_eq!(crate::sfnt::checksum(&data, None), 0xB1B0AFBA);
+    }
+
+    #[test]
+    fn notdef_only() {
+        let data = demo();
+        let face = Face::from_slice(&data, 0).unwrap();
+        let data = subset(&face, &SubsetOptions::default()).unwrap();
+        let subset = Face::from_slice(&data, 0).unwrap();
+        assert_eq!(subset.number_of_glyphs(), 1);
+        assert_eq!(subset.glyph_index('A'), None);
+    }
+
+    #[test]
+    fn glyph_map() {
+        let glyphs: GlyphSet = [0, 5, 9, 20].iter().map(|g| GlyphId(*g)).collect();
+        let map = GlyphMap::new(&glyphs, 10, false);
+        assert_eq!(map.len(), Some(3));
+        assert_eq!(map.get(GlyphId(9)), Some(GlyphId(2)));
+        assert_eq!(map.get(GlyphId(4)), None);
+
+        let map = GlyphMap::new(&glyphs, 10, true);
+        assert_eq!(map.len(), Some(10));
+        assert_eq!(map.get(GlyphId(5)), Some(GlyphId(5)));
+        assert_eq!(map.get(GlyphId(4)), None);
+        assert_eq!(map.get(GlyphId(20)), None);
+    }
+
+    #[test]
+    fn cmap() {
+        let data = write_cmap(&[(0x41, 1), (0x42, 2), (0x44, 3), (0x1F600, 4)]);
+        let subtables = crate::cmap::parse(&data).unwrap();
+        assert_eq!(subtables.count(), 2);
+        for subtable in subtables {
+            assert_eq!(subtable.glyph_index(0x42), Some(GlyphId(2)));
+            assert_eq!(subtable.glyph_index(0x43), None);
+            assert_eq!(subtable.glyph_index(0x44), Some(GlyphId(3)));
+        }
+
+        let subtable = crate::cmap::parse(&data).unwrap().nth(1).unwrap();
+        assert_eq!(subtable.glyph_index(0x1F600), Some(GlyphId(4)));
+    }
+
+    #[test]
+    fn cmap_format4_end() {
+        // A range that ends at 0xFFFF is split before the format 4 sentinel.
+        let data = write_cmap(&[(0xFFFD, 1), (0xFFFE, 2), (0xFFFF, 3)]);
+        let subtables = crate::cmap::parse(&data).unwrap();
+        assert_eq!(subtables.count(), 1);
+        let subtable = crate::cmap::parse(&data).unwrap().next().unwrap();
+        assert_eq!(subtable.glyph_index(0xFFFD), Some(GlyphId(1)));
+        assert_eq!(subtable.glyph_index(0xFFFE), Some(GlyphId(2)));
+        // The sentinel maps to `.notdef`.
+        assert_eq!(subtable.glyph_index(0xFFFF), Some(GlyphId(0)));
+    }
+
+    #[test]
+    fn supplementary_chars() {
+        use crate::writer::TtfType::*;
+
+        let data = crate::writer::convert(&[
+            UInt16(0), // version
+            UInt16(2), // number of subtables
+            // Encoding Record [0]
+            UInt16(3), // platform ID: Windows
+            UInt16(1), // encoding ID: Unicode BMP
+            UInt32(20), // subtable offset
+            // Encoding Record [1]
+            UInt16(3), // platform ID: Windows
+            UInt16(10), // encoding ID: Unicode full repertoire
+            UInt32(52), // subtable offset
+            // Subtable [0]
+            UInt16(4), // format
+            UInt16(32), // length
+            UInt16(0), // language
+            UInt16(4), // 2 x segCount
+            UInt16(4), // search range
+            UInt16(1), // entry selector
+            UInt16(0), // range shift
+            UInt16(0x41), // end char code [0]
+            UInt16(0xFFFF), // end char code [1]
+            UInt16(0), // reserved
+            UInt16(0x41), // start char code [0]
+            UInt16(0xFFFF), // start char code [1]
+            Int16(-0x40), // delta [0]
+            Int16(1), // delta [1]
+            UInt16(0), // offset [0]
+            UInt16(0), // offset [1]
+            // Subtable [1]
+            UInt16(12), // format
+            UInt16(0), // reserved
+            UInt32(40), // length
+            UInt32(0), // language
+            UInt32(2), // number of groups
+            // Group [0]
+            UInt32(0x41), // start char code
+            UInt32(0x41), // end char code
+            UInt32(1), // start glyph ID
+            // Group [1]
+            UInt32(0x1D538), // start char code
+            UInt32(0x1D538), // end char code
+            UInt32(5), // start glyph ID
+        ]);
+
+        let glyphs: GlyphSet = [0, 1, 5].iter().map(|g| GlyphId(*g)).collect();
+        let map = GlyphMap::new(&glyphs, 10, false);
+        let mappings = char_mappings(crate::cmap::parse(&data).unwrap(), &map);
+        assert_eq!(mappings, [(0x41, 1), (0x1D538, 2)]);
+
+        // Format 12 is written for characters outside BMP.
+        let data = write_cmap(&mappings);
+        let subtable = crate::cmap::parse(&data).unwrap().nth(1).unwrap();
+        assert_eq!(subtable.format(), crate::cmap::Format::SegmentedCoverage);
+        assert_eq!(subtable.glyph_index(0x1D538), Some(GlyphId(2)));
+    }
+}
@@ -0,0 +1,149 @@
+use core::fmt::Write;
+use std::string::String;
+
+use crate::OutlineBuilder;
+
+/// `SvgPathBuilder` options.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub struct SvgPathOptions {
+    /// The maximum number of digits after the decimal point.
+    ///
+    /// Trailing zeros are removed. When not set, numbers are written as is.
+    pub precision: Option<u8>,
+
+    /// Flips the Y axis, since it points down in SVG.
+    pub flip_y: bool,
+}
+
+/// An `OutlineBuilder` that writes an SVG path data string.
+///
+/// The result can be used as a `d` attribute of an SVG `path` element.
+///
+/// # Example
+///
+/// ```
+/// use ttf_parser::{GlyphId, SvgPathBuilder, SvgPathOptions};
+///
+/// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+/// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+/// let mut builder = SvgPathBuilder::new(SvgPathOptions { flip_y: true, ..SvgPathOptions::default() });
+/// face.outline_glyph(GlyphId(1), &mut builder).unwrap();
+/// assert!(builder.as_str().starts_with("M 173 -267 L 369 -267"));
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct SvgPathBuilder {
+    options: SvgPathOptions,
+    path: String,
+}
+
+impl SvgPathBuilder {
+    /// Creates a new builder.
+    #[inline]
+    pub fn new(options: SvgPathOptions) -> Self {
+        SvgPathBuilder { options, path: String::new() }
+    }
+
+    /// Returns the path data.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the path data, consuming the builder.
+    #[inline]
+    pub fn into_string(self) -> String {
+        self.path
+    }
+
+    /// Removes the path data, so the builder can be reused.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.path.clear();
+    }
+
+    fn push_command(&mut self, command: char, points: &[(f32, f32)]) {
+        if !self.path.is_empty() {
+            self.path.push(' ');
+        }
+
+        self.path.push(command);
+        for &(x, y) in points {
+            let y = if self.options.flip_y { -y } else { y };
+            self.path.push(' ');
+            self.push_number(x);
+            self.path.push(' ');
+            self.push_number(y);
+        }
+    }
+
+    fn push_number(&mut self, n: f32) {
+        let start = self.path.len();
+        match self.options.precision {
+            Some(precision) => {
+                let _ = write!(self.path, "{:.*}", usize::from(precision), n);
+                if self.path[start..].contains('.') {
+                    let len = self.path.trim_end_matches('0').trim_end_matches('.').len();
+                    self.path.truncate(len);
+                }
+            }
+            None => {
+                let _ = write!(self.path, "{}", n);
+            }
+        }
+
+        // Rounding can produce a negative zero.
+        if &self.path[start..] == "-0" {
+            self.path.truncate(start);
+            self.path.push('0');
+        }
+    }
+}
+
+impl OutlineBuilder for SvgPathBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.push_command('M', &[(x, y)]);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.push_command('L', &[(x, y)]);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.push_command('Q', &[(x1, y1), (x, y)]);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.push_command('C', &[(x1, y1), (x2, y2), (x, y)]);
+    }
+
+    fn close(&mut self) {
+        self.push_command('Z', &[]);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(options: SvgPathOptions) -> String {
+        let mut builder = SvgPathBuilder::new(options);
+        builder.move_to(1.0, 2.5);
+        builder.quad_to(1.25, -0.001, 3.0, 4.0);
+        builder.curve_to(0.0, 0.0, 1.0, 1.0, 2.0, 2.0);
+        builder.close();
+        builder.into_string()
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(build(SvgPathOptions::default()),
+                   "M 1 2.5 Q 1.25 -0.001 3 4 C 0 0 1 1 2 2 Z");
+    }
+
+    #[test]
+    fn precision_and_flip() {
+        let options = SvgPathOptions { precision: Some(1), flip_y: true };
+        assert_eq!(build(options), "M 1 -2.5 Q 1.2 0 3 -4 C 0 0 1 -1 2 -2 Z");
+    }
+}
@@ -3,22 +3,29 @@
 use core::convert::TryFrom;
 use core::num::NonZeroU16;
 
-use crate::NormalizedCoordinate;
-use crate::parser::{Stream, FromData, LazyArray16};
+use crate::{NormalizedCoordinate, MAX_VAR_COORDS};
+use crate::parser::{Stream, FromData, LazyArray16, Offset, Offset32, f32_bound};
+use crate::var_store::ItemVariationStore;
+use super::hvar::DeltaSetIndexMap;
 
 
 #[derive(Clone, Copy)]
 pub(crate) struct Table<'a> {
     axis_count: NonZeroU16,
     data: &'a [u8],
+    /// Version 2.0 axis index mapping.
+    axis_index_map: Option<DeltaSetIndexMap<'a>>,
+    /// Version 2.0 variation data.
+    variation_store: Option<ItemVariationStore<'a>>,
 }
 
 impl<'a> Table<'a> {
     pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let table_data = data;
         let mut s = Stream::new(data);
 
         let version: u32 = s.read()?;
-        if version != 0x00010000 {
+        if !(version == 0x00010000 || version == 0x00020000) {
             return None;
         }
 
@@ -35,9 +42,25 @@ impl<'a> Table<'a> {
             s.advance_checked(AxisValueMapRecord::SIZE * usize::from(count))?;
         }
 
+        let mut axis_index_map = None;
+        let mut variation_store = None;
+        if version == 0x00020000 {
+            // Offsets are from the start of the table.
+            if let Some(offset) = s.read::<Option<Offset32>>()? {
+                axis_index_map = Some(DeltaSetIndexMap::new(table_data.get(offset.to_usize()..)?));
+            }
+
+            if let Some(offset) = s.read::<Option<Offset32>>()? {
+                let s = Stream::new_at(table_data, offset.to_usize())?;
+                variation_store = Some(ItemVariationStore::parse(s)?);
+            }
+        }
+
         Some(Table {
             axis_count,
             data,
+            axis_index_map,
+            variation_store,
         })
     }
 
@@ -47,12 +70,37 @@ impl<'a> Table<'a> {
         }
 
         let mut s = Stream::new(self.data);
-        for coord in coordinates {
+        for coord in coordinates.iter_mut() {
             let count: u16 = s.read()?;
             let map = s.read_array16::<AxisValueMapRecord>(count)?;
             *coord = NormalizedCoordinate::from(map_value(&map, coord.0)?);
         }
 
+        if let Some(store) = self.variation_store {
+            if coordinates.len() > MAX_VAR_COORDS {
+                return None;
+            }
+
+            // All deltas are calculated using segment-mapped coordinates.
+            let mut mapped = [NormalizedCoordinate::default(); MAX_VAR_COORDS];
+            mapped[..coordinates.len()].copy_from_slice(coordinates);
+            let mapped = &mapped[..coordinates.len()];
+
+            for (i, coord) in coordinates.iter_mut().enumerate() {
+                let (outer_index, inner_index) = match self.axis_index_map {
+                    Some(map) => map.map_index(i as u32)?,
+                    // 'If no mapping is present, the axis index is used as the inner index.'
+                    None => (0, i as u16),
+                };
+
+                let delta = store.parse_delta(outer_index, inner_index, mapped).unwrap_or(0.0);
+                // Round to the nearest integer.
+                let delta = (if delta >= 0.0 { delta + 0.5 } else { delta - 0.5 }) as i32;
+                let value = f32_bound(-16384.0, f32::from(coord.0) + delta as f32, 16384.0) as i16;
+                *coord = NormalizedCoordinate::from(value);
+            }
+        }
+
         Some(())
     }
 }
@@ -125,3 +173,87 @@ impl FromData for AxisValueMapRecord {
         })
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn map_coordinates() {
+        let data = writer::convert(&[
+            UInt32(0x00010000), // version: 1.0
+            UInt16(0), // reserved
+            UInt16(2), // axis count
+            // Segment map 0
+            UInt16(4), // position map count
+            Int16(-16384), Int16(-16384), // -1.0 -> -1.0
+            Int16(0), Int16(0), // 0.0 -> 0.0
+            Int16(8192), Int16(12288), // 0.5 -> 0.75
+            Int16(16384), Int16(16384), // 1.0 -> 1.0
+            // Segment map 1
+            UInt16(0), // position map count
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+
+        let mut coords = [NormalizedCoordinate::from(0.5), NormalizedCoordinate::from(0.5)];
+        table.map_coordinates(&mut coords).unwrap();
+        assert_eq!(coords[0].get(), 12288);
+        assert_eq!(coords[1].get(), 8192);
+
+        let mut coords = [NormalizedCoordinate::from(0.25), NormalizedCoordinate::from(-0.5)];
+        table.map_coordinates(&mut coords).unwrap();
+        assert_eq!(coords[0].get(), 6144);
+        assert_eq!(coords[1].get(), -8192);
+
+        let mut coords = [NormalizedCoordinate::from(0.25)];
+        assert!(table.map_coordinates(&mut coords).is_none());
+    }
+
+    #[test]
+    fn version_2() {
+        let data = writer::convert(&[
+            UInt32(0x00020000), // version: 2.0
+            UInt16(0), // reserved
+            UInt16(1), // axis count
+            // Segment map 0
+            UInt16(0), // position map count
+            UInt32(0), // axis index map offset: none
+            UInt32(18), // variation store offset
+            // Item variation store
+            UInt16(1), // format
+            UInt32(12), // variation region list offset
+            UInt16(1), // item variation data count
+            UInt32(22), // item variation data offset
+            // Variation region list
+            UInt16(1), // axis count
+            UInt16(1), // region count
+            Int16(0), // start coordinate: 0.0
+            Int16(16384), // peak coordinate: 1.0
+            Int16(16384), // end coordinate: 1.0
+            // Item variation data
+            UInt16(1), // item count
+            UInt16(1), // word delta count
+            UInt16(1), // region index count
+            UInt16(0), // region index 0
+            Int16(-4096), // delta: -0.25
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+
+        let mut coords = [NormalizedCoordinate::from(1.0)];
+        table.map_coordinates(&mut coords).unwrap();
+        assert_eq!(coords[0].get(), 12288);
+
+        let mut coords = [NormalizedCoordinate::from(0.5)];
+        table.map_coordinates(&mut coords).unwrap();
+        assert_eq!(coords[0].get(), 6144);
+
+        let mut coords = [NormalizedCoordinate::from(-0.5)];
+        table.map_coordinates(&mut coords).unwrap();
+        assert_eq!(coords[0].get(), -8192);
+    }
+}
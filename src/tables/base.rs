@@ -0,0 +1,558 @@
+/*!
+A [Baseline table](https://docs.microsoft.com/en-us/typography/opentype/spec/base) implementation.
+*/
+
+use core::convert::TryFrom;
+
+use crate::{Device, GlyphId, Tag};
+use crate::parser::{Stream, FromData, LazyArray16, Offset, Offset16};
+
+
+#[derive(Clone, Copy)]
+struct TagRecord {
+    tag: Tag,
+    offset: Option<Offset16>,
+}
+
+impl FromData for TagRecord {
+    const SIZE: usize = 6;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(TagRecord {
+            tag: s.read::<Tag>()?,
+            offset: s.read::<Option<Offset16>>()?,
+        })
+    }
+}
+
+
+#[derive(Clone, Copy)]
+struct FeatureMinMaxRecord {
+    tag: Tag,
+    min_offset: Option<Offset16>,
+    max_offset: Option<Offset16>,
+}
+
+impl FromData for FeatureMinMaxRecord {
+    const SIZE: usize = 8;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(FeatureMinMaxRecord {
+            tag: s.read::<Tag>()?,
+            min_offset: s.read::<Option<Offset16>>()?,
+            max_offset: s.read::<Option<Offset16>>()?,
+        })
+    }
+}
+
+
+/// A [Baseline table](https://docs.microsoft.com/en-us/typography/opentype/spec/base).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    horizontal: Option<Axis<'a>>,
+    vertical: Option<Axis<'a>>,
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let major_version: u16 = s.read()?;
+        s.skip::<u16>(); // minor version
+        if major_version != 1 {
+            return None;
+        }
+
+        let horizontal_offset: Option<Offset16> = s.read()?;
+        let vertical_offset: Option<Offset16> = s.read()?;
+
+        let axis = |offset: Option<Offset16>| {
+            data.get(offset?.to_usize()..).and_then(Axis::parse)
+        };
+
+        Some(Table {
+            horizontal: axis(horizontal_offset),
+            vertical: axis(vertical_offset),
+        })
+    }
+
+    /// Returns baseline data for horizontal text layout.
+    #[inline]
+    pub fn horizontal_axis(&self) -> Option<Axis<'a>> {
+        self.horizontal
+    }
+
+    /// Returns baseline data for vertical text layout.
+    #[inline]
+    pub fn vertical_axis(&self) -> Option<Axis<'a>> {
+        self.vertical
+    }
+
+    /// Returns a horizontal baseline position for the specified script.
+    ///
+    /// Scripts without a record fall back to the `DFLT` script.
+    /// A missing ideographic em-box edge is derived from the opposite one.
+    pub(crate) fn horizontal_baseline(
+        &self,
+        kind: BaselineTag,
+        script: Tag,
+        units_per_em: u16,
+    ) -> Option<i16> {
+        let axis = self.horizontal?;
+        let script = axis.script(script)
+            .or_else(|| axis.script(Tag::from_bytes(b"DFLT")))?;
+        let baseline = |kind: BaselineTag| {
+            script.baseline(kind.to_tag()).map(|coord| coord.coordinate())
+        };
+
+        let em = i32::from(units_per_em);
+        baseline(kind).or_else(|| match kind {
+            BaselineTag::IdeographicBottom => {
+                baseline(BaselineTag::IdeographicTop)
+                    .and_then(|top| i16::try_from(i32::from(top) - em).ok())
+            }
+            BaselineTag::IdeographicTop => {
+                baseline(BaselineTag::IdeographicBottom)
+                    .and_then(|bottom| i16::try_from(i32::from(bottom) + em).ok())
+            }
+            _ => None,
+        })
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
+}
+
+
+/// An [Axis table](https://docs.microsoft.com/en-us/typography/opentype/spec/base#axis-tables-horizaxis-and-vertaxis).
+#[derive(Clone, Copy)]
+pub struct Axis<'a> {
+    tags: LazyArray16<'a, Tag>,
+    scripts_data: &'a [u8],
+    scripts: LazyArray16<'a, TagRecord>,
+}
+
+impl<'a> Axis<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let tags_offset: Option<Offset16> = s.read()?;
+        let scripts_offset: Offset16 = s.read()?;
+
+        // Baseline tags are optional, but without them
+        // scripts can still provide min/max extents.
+        let mut tags = LazyArray16::default();
+        if let Some(offset) = tags_offset {
+            let mut s = Stream::new_at(data, offset.to_usize())?;
+            let count: u16 = s.read()?;
+            tags = s.read_array16(count)?;
+        }
+
+        let scripts_data = data.get(scripts_offset.to_usize()..)?;
+        let mut s = Stream::new(scripts_data);
+        let count: u16 = s.read()?;
+        let scripts = s.read_array16(count)?;
+
+        Some(Axis { tags, scripts_data, scripts })
+    }
+
+    /// Returns an iterator over baseline tags used by this axis.
+    #[inline]
+    pub fn baseline_tags(&self) -> impl Iterator<Item = Tag> + 'a {
+        self.tags.into_iter()
+    }
+
+    /// Returns an iterator over script tags described by this axis.
+    #[inline]
+    pub fn script_tags(&self) -> impl Iterator<Item = Tag> + 'a {
+        self.scripts.into_iter().map(|record| record.tag)
+    }
+
+    /// Returns baseline data for a script.
+    pub fn script(&self, tag: Tag) -> Option<Script<'a>> {
+        let record = self.scripts.into_iter().find(|record| record.tag == tag)?;
+        let data = self.scripts_data.get(record.offset?.to_usize()..)?;
+        Script::parse(data, self.tags)
+    }
+}
+
+impl core::fmt::Debug for Axis<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Axis()")
+    }
+}
+
+
+/// A [Base Script table](https://docs.microsoft.com/en-us/typography/opentype/spec/base#basescript-table).
+#[derive(Clone, Copy)]
+pub struct Script<'a> {
+    data: &'a [u8],
+    tags: LazyArray16<'a, Tag>,
+    values: Option<&'a [u8]>,
+    default_min_max: Option<MinMax<'a>>,
+    languages: LazyArray16<'a, TagRecord>,
+}
+
+impl<'a> Script<'a> {
+    fn parse(data: &'a [u8], tags: LazyArray16<'a, Tag>) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let values_offset: Option<Offset16> = s.read()?;
+        let default_min_max_offset: Option<Offset16> = s.read()?;
+        let count: u16 = s.read()?;
+        let languages = s.read_array16(count)?;
+
+        let values = values_offset.and_then(|offset| data.get(offset.to_usize()..));
+        let default_min_max = default_min_max_offset
+            .and_then(|offset| data.get(offset.to_usize()..))
+            .and_then(MinMax::parse);
+
+        Some(Script { data, tags, values, default_min_max, languages })
+    }
+
+    /// Returns a baseline tag, that the script uses by default.
+    pub fn default_baseline(&self) -> Option<Tag> {
+        let index: u16 = Stream::read_at(self.values?, 0)?;
+        self.tags.get(index)
+    }
+
+    /// Returns a baseline position for the specified baseline tag.
+    pub fn baseline(&self, tag: Tag) -> Option<Coord<'a>> {
+        let data = self.values?;
+        let (index, _) = self.tags.into_iter().enumerate().find(|(_, t)| *t == tag)?;
+        let mut s = Stream::new_at(data, 2)?;
+        let count: u16 = s.read()?;
+        let offsets = s.read_array16::<Offset16>(count)?;
+        let offset = offsets.get(index as u16)?;
+        Coord::parse(data.get(offset.to_usize()..)?)
+    }
+
+    /// Returns min/max extents, that apply to all languages without explicit data.
+    #[inline]
+    pub fn default_min_max(&self) -> Option<MinMax<'a>> {
+        self.default_min_max
+    }
+
+    /// Returns min/max extents for a language system.
+    pub fn language_min_max(&self, tag: Tag) -> Option<MinMax<'a>> {
+        let record = self.languages.into_iter().find(|record| record.tag == tag)?;
+        MinMax::parse(self.data.get(record.offset?.to_usize()..)?)
+    }
+}
+
+impl core::fmt::Debug for Script<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Script()")
+    }
+}
+
+
+/// A [MinMax table](https://docs.microsoft.com/en-us/typography/opentype/spec/base#minmax-table).
+#[derive(Clone, Copy)]
+pub struct MinMax<'a> {
+    data: &'a [u8],
+    min_offset: Option<Offset16>,
+    max_offset: Option<Offset16>,
+    features: LazyArray16<'a, FeatureMinMaxRecord>,
+}
+
+impl<'a> MinMax<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let min_offset: Option<Offset16> = s.read()?;
+        let max_offset: Option<Offset16> = s.read()?;
+        let count: u16 = s.read()?;
+        let features = s.read_array16(count)?;
+        Some(MinMax { data, min_offset, max_offset, features })
+    }
+
+    /// Returns the minimum extent.
+    #[inline]
+    pub fn min(&self) -> Option<Coord<'a>> {
+        self.coord(self.min_offset)
+    }
+
+    /// Returns the maximum extent.
+    #[inline]
+    pub fn max(&self) -> Option<Coord<'a>> {
+        self.coord(self.max_offset)
+    }
+
+    /// Returns the minimum extent, when the specified feature is enabled.
+    pub fn feature_min(&self, tag: Tag) -> Option<Coord<'a>> {
+        let record = self.features.into_iter().find(|record| record.tag == tag)?;
+        self.coord(record.min_offset)
+    }
+
+    /// Returns the maximum extent, when the specified feature is enabled.
+    pub fn feature_max(&self, tag: Tag) -> Option<Coord<'a>> {
+        let record = self.features.into_iter().find(|record| record.tag == tag)?;
+        self.coord(record.max_offset)
+    }
+
+    fn coord(&self, offset: Option<Offset16>) -> Option<Coord<'a>> {
+        Coord::parse(self.data.get(offset?.to_usize()..)?)
+    }
+}
+
+impl core::fmt::Debug for MinMax<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "MinMax()")
+    }
+}
+
+
+/// A [Base Coordinate table](https://docs.microsoft.com/en-us/typography/opentype/spec/base#basecoord-tables).
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug)]
+pub enum Coord<'a> {
+    /// A single coordinate.
+    Format1 {
+        coordinate: i16,
+    },
+    /// A coordinate, that can be adjusted by a glyph contour point after hinting.
+    Format2 {
+        coordinate: i16,
+        reference_glyph: GlyphId,
+        base_coord_point: u16,
+    },
+    /// A coordinate with a device table.
+    Format3 {
+        coordinate: i16,
+        device: Option<Device<'a>>,
+    },
+}
+
+impl<'a> Coord<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let format: u16 = s.read()?;
+        match format {
+            1 => Some(Coord::Format1 {
+                coordinate: s.read()?,
+            }),
+            2 => Some(Coord::Format2 {
+                coordinate: s.read()?,
+                reference_glyph: s.read()?,
+                base_coord_point: s.read()?,
+            }),
+            3 => {
+                let coordinate: i16 = s.read()?;
+                let device_offset: Option<Offset16> = s.read()?;
+                let device = device_offset
+                    .and_then(|offset| Device::parse(data.get(offset.to_usize()..)?));
+                Some(Coord::Format3 { coordinate, device })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns coordinate's value in font design units.
+    #[inline]
+    pub fn coordinate(&self) -> i16 {
+        match *self {
+            Coord::Format1 { coordinate } |
+            Coord::Format2 { coordinate, .. } |
+            Coord::Format3 { coordinate, .. } => coordinate,
+        }
+    }
+}
+
+
+/// A [baseline tag](https://docs.microsoft.com/en-us/typography/opentype/spec/baselinetags).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BaselineTag {
+    /// A hanging baseline (`hang`), used by Tibetan and similar scripts.
+    Hanging,
+    /// An ideographic character face bottom edge (`icfb`).
+    IdeographicFaceBottom,
+    /// An ideographic character face top edge (`icft`).
+    IdeographicFaceTop,
+    /// An ideographic em-box bottom edge (`ideo`).
+    IdeographicBottom,
+    /// An ideographic em-box top edge (`idtp`).
+    IdeographicTop,
+    /// A math characters baseline (`math`).
+    Math,
+    /// An alphabetic baseline (`romn`), used by Latin, Cyrillic, Greek and similar scripts.
+    Roman,
+}
+
+impl BaselineTag {
+    /// Returns a baseline tag as stored in the `BASE` table.
+    #[inline]
+    pub fn to_tag(self) -> Tag {
+        match self {
+            BaselineTag::Hanging => Tag::from_bytes(b"hang"),
+            BaselineTag::IdeographicFaceBottom => Tag::from_bytes(b"icfb"),
+            BaselineTag::IdeographicFaceTop => Tag::from_bytes(b"icft"),
+            BaselineTag::IdeographicBottom => Tag::from_bytes(b"ideo"),
+            BaselineTag::IdeographicTop => Tag::from_bytes(b"idtp"),
+            BaselineTag::Math => Tag::from_bytes(b"math"),
+            BaselineTag::Roman => Tag::from_bytes(b"romn"),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn horizontal_axis() {
+        let data = writer::convert(&[
+            UInt16(1), // major version
+            UInt16(0), // minor version
+            UInt16(8), // horizontal axis offset
+            UInt16(0), // vertical axis offset
+            // Axis
+            UInt16(4), // baseline tags offset
+            UInt16(14), // scripts offset
+            // Baseline Tags
+            UInt16(2), // count
+            Raw(b"ideo"), // tag [0]
+            Raw(b"romn"), // tag [1]
+            // Scripts
+            UInt16(1), // count
+            Raw(b"latn"), // script tag [0]
+            UInt16(8), // script offset [0]
+            // Script
+            UInt16(12), // values offset
+            UInt16(24), // default min/max offset
+            UInt16(1), // language count
+            Raw(b"ENG "), // language tag [0]
+            UInt16(38), // min/max offset [0]
+            // Values
+            UInt16(1), // default baseline index
+            UInt16(2), // count
+            UInt16(8), // coord offset [0]
+            UInt16(8), // coord offset [1]
+            // Coord
+            UInt16(1), // format
+            Int16(-120), // coordinate
+            // Default MinMax
+            UInt16(6), // min offset
+            UInt16(0), // max offset
+            UInt16(0), // feature count
+            // Coord
+            UInt16(2), // format
+            Int16(-200), // coordinate
+            UInt16(3), // reference glyph
+            UInt16(7), // point
+            // Language MinMax
+            UInt16(0), // min offset
+            UInt16(14), // max offset
+            UInt16(1), // feature count
+            Raw(b"vert"), // feature tag [0]
+            UInt16(14), // min offset [0]
+            UInt16(0), // max offset [0]
+            // Coord
+            UInt16(1), // format
+            Int16(800), // coordinate
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert!(table.vertical_axis().is_none());
+        let axis = table.horizontal_axis().unwrap();
+        assert_eq!(axis.baseline_tags().count(), 2);
+        assert_eq!(axis.script_tags().next(), Some(Tag::from_bytes(b"latn")));
+        assert!(axis.script(Tag::from_bytes(b"cyrl")).is_none());
+
+        let script = axis.script(Tag::from_bytes(b"latn")).unwrap();
+        assert_eq!(script.default_baseline(), Some(Tag::from_bytes(b"romn")));
+        assert_eq!(script.baseline(Tag::from_bytes(b"ideo")).unwrap().coordinate(), -120);
+        assert!(script.baseline(Tag::from_bytes(b"hang")).is_none());
+
+        let min_max = script.default_min_max().unwrap();
+        match min_max.min().unwrap() {
+            Coord::Format2 { coordinate, reference_glyph, base_coord_point } => {
+                assert_eq!(coordinate, -200);
+                assert_eq!(reference_glyph, GlyphId(3));
+                assert_eq!(base_coord_point, 7);
+            }
+            _ => panic!("must be a format 2"),
+        }
+        assert!(min_max.max().is_none());
+
+        let min_max = script.language_min_max(Tag::from_bytes(b"ENG ")).unwrap();
+        assert!(min_max.min().is_none());
+        assert_eq!(min_max.max().unwrap().coordinate(), 800);
+        assert_eq!(min_max.feature_min(Tag::from_bytes(b"vert")).unwrap().coordinate(), 800);
+        assert!(min_max.feature_max(Tag::from_bytes(b"vert")).is_none());
+        assert!(script.language_min_max(Tag::from_bytes(b"DEU ")).is_none());
+    }
+
+    #[test]
+    fn horizontal_baseline() {
+        let data = writer::convert(&[
+            UInt16(1), // major version
+            UInt16(0), // minor version
+            UInt16(8), // horizontal axis offset
+            UInt16(0), // vertical axis offset
+            // Axis
+            UInt16(4), // baseline tags offset
+            UInt16(14), // scripts offset
+            // Baseline Tags
+            UInt16(2), // count
+            Raw(b"hang"), // tag [0]
+            Raw(b"ideo"), // tag [1]
+            // Scripts
+            UInt16(2), // count
+            Raw(b"DFLT"), // script tag [0]
+            UInt16(14), // script offset [0]
+            Raw(b"hani"), // script tag [1]
+            UInt16(36), // script offset [1]
+            // Script 0
+            UInt16(6), // values offset
+            UInt16(0), // default min/max offset
+            UInt16(0), // language count
+            // Values
+            UInt16(1), // default baseline index
+            UInt16(2), // count
+            UInt16(8), // coord offset [0]
+            UInt16(12), // coord offset [1]
+            // Coord
+            UInt16(1), // format
+            Int16(600), // coordinate
+            // Coord
+            UInt16(1), // format
+            Int16(-120), // coordinate
+            // Script 1
+            UInt16(6), // values offset
+            UInt16(0), // default min/max offset
+            UInt16(0), // language count
+            // Values
+            UInt16(1), // default baseline index
+            UInt16(2), // count
+            UInt16(8), // coord offset [0]
+            UInt16(12), // coord offset [1]
+            // Coord
+            UInt16(1), // format
+            Int16(700), // coordinate
+            // Coord
+            UInt16(1), // format
+            Int16(-100), // coordinate
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        let hani = Tag::from_bytes(b"hani");
+        let latn = Tag::from_bytes(b"latn");
+        assert_eq!(table.horizontal_baseline(BaselineTag::Hanging, hani, 1000), Some(700));
+        assert_eq!(table.horizontal_baseline(BaselineTag::IdeographicBottom, hani, 1000), Some(-100));
+        // Derived using the em size.
+        assert_eq!(table.horizontal_baseline(BaselineTag::IdeographicTop, hani, 1000), Some(900));
+        assert_eq!(table.horizontal_baseline(BaselineTag::IdeographicFaceTop, hani, 1000), None);
+        assert_eq!(table.horizontal_baseline(BaselineTag::Roman, hani, 1000), None);
+
+        // Falls back to `DFLT`.
+        assert_eq!(table.horizontal_baseline(BaselineTag::Hanging, latn, 1000), Some(600));
+        assert_eq!(table.horizontal_baseline(BaselineTag::IdeographicBottom, latn, 1000), Some(-120));
+    }
+}
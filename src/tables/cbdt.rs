@@ -4,7 +4,7 @@ use crate::{RasterGlyphImage, RasterImageFormat};
 use crate::parser::{Stream, NumFrom};
 use super::cblc::{BitmapFormat, Location};
 
-pub fn parse(
+pub(crate) fn parse(
     data: &[u8],
     location: Location,
 ) -> Option<RasterGlyphImage> {
@@ -25,6 +25,7 @@ pub fn parse(
                 width: u16::from(width),
                 height: u16::from(height),
                 pixels_per_em: location.ppem,
+                scale: 1.0,
                 format: RasterImageFormat::PNG,
                 data,
             })
@@ -47,6 +48,7 @@ pub fn parse(
                 width: u16::from(width),
                 height: u16::from(height),
                 pixels_per_em: location.ppem,
+                scale: 1.0,
                 format: RasterImageFormat::PNG,
                 data,
             })
@@ -61,9 +63,11 @@ pub fn parse(
                 width: u16::from(location.metrics.width),
                 height: u16::from(location.metrics.height),
                 pixels_per_em: location.ppem,
+                scale: 1.0,
                 format: RasterImageFormat::PNG,
                 data,
             })
         }
+        _ => None, // `EBDT` formats.
     }
 }
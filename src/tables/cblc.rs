@@ -1,158 +1,283 @@
-// https://docs.microsoft.com/en-us/typography/opentype/spec/cblc
+/*!
+A [Color Bitmap Location table](https://docs.microsoft.com/en-us/typography/opentype/spec/cblc) implementation.
+*/
 
-use crate::GlyphId;
-use crate::parser::{Stream, FromData, Offset, Offset16, Offset32, NumFrom};
+use crate::{GlyphId, is_better_strike};
+use crate::parser::{Stream, FromData, LazyArray32, Offset, Offset16, Offset32, NumFrom};
 
 #[derive(Clone, Copy, PartialEq, Debug)]
-pub enum BitmapFormat {
+pub(crate) enum BitmapFormat {
+    Format1,
+    Format2,
+    Format5,
+    Format6,
+    Format7,
     Format17,
     Format18,
     Format19,
 }
 
 #[derive(Clone, Copy, Default, Debug)]
-pub struct Metrics {
+pub(crate) struct Metrics {
     pub x: i8,
     pub y: i8,
     pub width: u8,
     pub height: u8,
 }
 
+impl Metrics {
+    /// Parses [big glyph metrics](https://docs.microsoft.com/en-us/typography/opentype/spec/eblc#bigglyphmetrics).
+    ///
+    /// Only horizontal metrics are used.
+    pub(crate) fn parse_big(s: &mut Stream) -> Option<Self> {
+        let height: u8 = s.read()?;
+        let width: u8 = s.read()?;
+        let x: i8 = s.read()?;
+        let y: i8 = s.read()?;
+        s.skip::<u8>(); // hor_advance
+        s.skip::<i8>(); // ver_bearing_x
+        s.skip::<i8>(); // ver_bearing_y
+        s.skip::<u8>(); // ver_advance
+        Some(Metrics { x, y, width, height })
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
-pub struct Location {
+pub(crate) struct Location {
     pub format: BitmapFormat,
     pub offset: usize,
     pub metrics: Metrics,
     pub ppem: u16,
+    pub bit_depth: u8,
 }
 
-pub fn find_location(
-    data: &[u8],
-    glyph_id: GlyphId,
-    pixels_per_em: u16,
-) -> Option<Location> {
-    let mut s = Stream::new(data);
-
-    // The CBLC table version is a bit tricky, so we are ignoring it for now.
-    // The CBLC table is based on EBLC table, which was based on the `bloc` table.
-    // And before the CBLC table specification was finished, some fonts,
-    // notably Noto Emoji, have used version 2.0, but the final spec allows only 3.0.
-    // So there are perfectly valid fonts in the wild, which have an invalid version.
-    s.skip::<u32>(); // version
-
-    let size_table = select_bitmap_size_table(glyph_id, pixels_per_em, s)?;
-    let info = select_index_subtable(data, size_table, glyph_id)?;
-
-    let mut s = Stream::new_at(data, info.offset)?;
-    let index_format: u16 = s.read()?;
-    let image_format: u16 = s.read()?;
-    let mut image_offset = s.read::<Offset32>()?.to_usize();
-
-    let image_format = match image_format {
-        17 => BitmapFormat::Format17,
-        18 => BitmapFormat::Format18,
-        19 => BitmapFormat::Format19,
-        _ => return None, // Invalid format.
-    };
-
-    // TODO: I wasn't able to find fonts with index 4 and 5, so they are untested.
-
-    let glyph_diff = glyph_id.0.checked_sub(info.start_glyph_id.0)?;
-    let metrics = Metrics::default();
-    match index_format {
-        1 => {
-            s.advance(usize::from(glyph_diff) * Offset32::SIZE);
-            let offset: Offset32 = s.read()?;
-            image_offset += offset.to_usize();
-        }
-        2 => {
-            let image_size: u32 = s.read()?;
-            image_offset += usize::from(glyph_diff).checked_mul(usize::num_from(image_size))?;
-        }
-        3 => {
-            s.advance(usize::from(glyph_diff) * Offset16::SIZE);
-            let offset: Offset16 = s.read()?;
-            image_offset += offset.to_usize();
-        }
-        4 => {
-            let num_glyphs: u32 = s.read()?;
-            let num_glyphs = num_glyphs.checked_add(1)?;
-            let pairs = s.read_array32::<GlyphIdOffsetPair>(num_glyphs)?;
-            let pair = pairs.into_iter().find(|pair| pair.glyph_id == glyph_id)?;
-            image_offset += pair.offset.to_usize();
-        }
-        5 => {
-            let image_size: u32 = s.read()?;
-            s.advance(8); // big metrics
-            let num_glyphs: u32 = s.read()?;
-            let glyphs = s.read_array32::<GlyphId>(num_glyphs)?;
-            let (index, _) = glyphs.binary_search(&glyph_id)?;
-            image_offset = image_offset
-                .checked_add(usize::num_from(index).checked_mul(usize::num_from(image_size))?)?;
-        }
-        _ => return None, // Invalid format.
-    }
 
-    Some(Location {
-        format: image_format,
-        offset: image_offset,
-        metrics,
-        ppem: size_table.ppem,
-    })
+/// [Line metrics](https://docs.microsoft.com/en-us/typography/opentype/spec/eblc#sbitlinemetrics)
+/// of a strike.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineMetrics {
+    pub ascender: i8,
+    pub descender: i8,
+    pub width_max: u8,
 }
 
+impl LineMetrics {
+    pub(crate) fn parse(s: &mut Stream) -> Option<Self> {
+        let ascender: i8 = s.read()?;
+        let descender: i8 = s.read()?;
+        let width_max: u8 = s.read()?;
+        s.advance(9); // Caret and side bearing metrics, and padding.
+        Some(LineMetrics { ascender, descender, width_max })
+    }
+}
 
-#[derive(Clone, Copy)]
-struct BitmapSizeTable {
+
+/// A bitmap strike. Also known as a BitmapSize record.
+#[derive(Clone, Copy, Debug)]
+pub struct Strike {
+    /// The first glyph ID in the strike.
+    pub start_glyph_id: GlyphId,
+    /// The last glyph ID in the strike.
+    pub end_glyph_id: GlyphId,
+    /// Horizontal pixels per em.
+    pub ppem_x: u8,
+    /// Vertical pixels per em.
+    pub ppem_y: u8,
+    /// Bits per pixel. Always 32 in `CBLC`. Can be 1, 2, 4 or 8 in `EBLC`.
+    pub bit_depth: u8,
+    /// Horizontal line metrics.
+    pub horizontal: LineMetrics,
+    /// Vertical line metrics.
+    pub vertical: LineMetrics,
+    flags: u8,
     subtable_array_offset: Offset32,
     number_of_subtables: u32,
-    ppem: u16,
-    // Many fields are omitted.
 }
 
-fn select_bitmap_size_table(
-    glyph_id: GlyphId,
-    pixels_per_em: u16,
-    mut s: Stream,
-) -> Option<BitmapSizeTable> {
-    let subtable_count: u32 = s.read()?;
-    let orig_s = s.clone();
-
-    let mut idx = None;
-    let mut max_ppem = 0;
-    for i in 0..subtable_count {
-        // The BitmapSize Table is larger than 32 bytes, so we cannot use scripts/gen-tables.py
-
-        // Check that the current subtable contains a provided glyph id.
-        s.advance(40); // Jump to `start_glyph_index`.
-        let start_glyph_id: GlyphId = s.read()?;
-        let end_glyph_id: GlyphId = s.read()?;
-        let ppem = u16::from(s.read::<u8>()?);
+impl FromData for Strike {
+    const SIZE: usize = 48;
 
-        if !(start_glyph_id..=end_glyph_id).contains(&glyph_id) {
-            s.advance(4); // Jump to the end of the subtable.
-            continue;
-        }
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let subtable_array_offset: Offset32 = s.read()?;
+        s.skip::<u32>(); // index_tables_size
+        let number_of_subtables: u32 = s.read()?;
+        s.skip::<u32>(); // color_ref
+        let horizontal = LineMetrics::parse(&mut s)?;
+        let vertical = LineMetrics::parse(&mut s)?;
+        Some(Strike {
+            start_glyph_id: s.read()?,
+            end_glyph_id: s.read()?,
+            ppem_x: s.read()?,
+            ppem_y: s.read()?,
+            bit_depth: s.read()?,
+            flags: s.read()?,
+            horizontal,
+            vertical,
+            subtable_array_offset,
+            number_of_subtables,
+        })
+    }
+}
+
+impl Strike {
+    /// Checks that the strike has horizontal metrics.
+    #[inline]
+    pub fn is_horizontal(&self) -> bool {
+        self.flags & 0x01 != 0
+    }
 
-        // Select a best matching subtable based on `pixels_per_em`.
-        if (pixels_per_em <= ppem && ppem < max_ppem) || (pixels_per_em > max_ppem && ppem > max_ppem) {
-            idx = Some(usize::num_from(i));
-            max_ppem = ppem;
+    /// Checks that the strike has vertical metrics.
+    #[inline]
+    pub fn is_vertical(&self) -> bool {
+        self.flags & 0x02 != 0
+    }
+
+    /// Checks that the strike covers the glyph.
+    #[inline]
+    pub fn contains(&self, glyph_id: GlyphId) -> bool {
+        (self.start_glyph_id..=self.end_glyph_id).contains(&glyph_id)
+    }
+}
+
+
+/// A [Color Bitmap Location table](https://docs.microsoft.com/en-us/typography/opentype/spec/cblc).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    data: &'a [u8],
+    strikes: LazyArray32<'a, Strike>,
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+
+        // The CBLC table version is a bit tricky, so we are ignoring it for now.
+        // The CBLC table is based on EBLC table, which was based on the `bloc` table.
+        // And before the CBLC table specification was finished, some fonts,
+        // notably Noto Emoji, have used version 2.0, but the final spec allows only 3.0.
+        // So there are perfectly valid fonts in the wild, which have an invalid version.
+        s.skip::<u32>(); // version
+
+        let count: u32 = s.read()?;
+        let strikes = s.read_array32(count)?;
+        Some(Table { data, strikes })
+    }
+
+    /// Returns the number of strikes.
+    #[inline]
+    pub fn strikes_count(&self) -> u32 {
+        self.strikes.len()
+    }
+
+    /// Returns a strike by index.
+    #[inline]
+    pub fn strike(&self, index: u32) -> Option<Strike> {
+        self.strikes.get(index)
+    }
+
+    /// Returns an iterator over strikes.
+    #[inline]
+    pub fn strikes(&self) -> impl Iterator<Item = Strike> + 'a {
+        self.strikes.into_iter()
+    }
+
+    /// Selects a best matching strike, which covers the glyph, based on `pixels_per_em`.
+    pub fn best_strike(&self, glyph_id: GlyphId, pixels_per_em: u16) -> Option<Strike> {
+        let mut best: Option<Strike> = None;
+        for strike in self.strikes.into_iter().filter(|s| s.contains(glyph_id)) {
+            let best_ppem = best.map(|s| u16::from(s.ppem_x)).unwrap_or(0);
+            if is_better_strike(pixels_per_em, u16::from(strike.ppem_x), best_ppem) {
+                best = Some(strike);
+            }
         }
+
+        best
     }
 
-    let mut s = orig_s;
-    s.advance(idx? * 48); // 48 is BitmapSize Table size
+    pub(crate) fn find_location(
+        &self,
+        glyph_id: GlyphId,
+        pixels_per_em: u16,
+    ) -> Option<Location> {
+        let strike = self.best_strike(glyph_id, pixels_per_em)?;
+        let info = select_index_subtable(self.data, strike, glyph_id)?;
+
+        let mut s = Stream::new_at(self.data, info.offset)?;
+        let index_format: u16 = s.read()?;
+        let image_format: u16 = s.read()?;
+        let mut image_offset = s.read::<Offset32>()?.to_usize();
+
+        // Composite formats 8 and 9 are not supported.
+        let image_format = match image_format {
+            1 => BitmapFormat::Format1,
+            2 => BitmapFormat::Format2,
+            5 => BitmapFormat::Format5,
+            6 => BitmapFormat::Format6,
+            7 => BitmapFormat::Format7,
+            17 => BitmapFormat::Format17,
+            18 => BitmapFormat::Format18,
+            19 => BitmapFormat::Format19,
+            _ => return None, // Invalid format.
+        };
 
-    let subtable_array_offset: Offset32 = s.read()?;
-    s.skip::<u32>(); // index_tables_size
-    let number_of_subtables: u32 = s.read()?;
+        // TODO: I wasn't able to find fonts with index 4 and 5, so they are untested.
 
-    Some(BitmapSizeTable {
-        subtable_array_offset,
-        number_of_subtables,
-        ppem: max_ppem,
-    })
+        let glyph_diff = glyph_id.0.checked_sub(info.start_glyph_id.0)?;
+        let mut metrics = Metrics::default();
+        match index_format {
+            1 => {
+                s.advance(usize::from(glyph_diff) * Offset32::SIZE);
+                let offset: Offset32 = s.read()?;
+                image_offset += offset.to_usize();
+            }
+            2 => {
+                let image_size: u32 = s.read()?;
+                // Format 5 and 19 glyphs use metrics shared by all glyphs in the subtable.
+                metrics = Metrics::parse_big(&mut s)?;
+                image_offset += usize::from(glyph_diff).checked_mul(usize::num_from(image_size))?;
+            }
+            3 => {
+                s.advance(usize::from(glyph_diff) * Offset16::SIZE);
+                let offset: Offset16 = s.read()?;
+                image_offset += offset.to_usize();
+            }
+            4 => {
+                let num_glyphs: u32 = s.read()?;
+                let num_glyphs = num_glyphs.checked_add(1)?;
+                let pairs = s.read_array32::<GlyphIdOffsetPair>(num_glyphs)?;
+                let pair = pairs.into_iter().find(|pair| pair.glyph_id == glyph_id)?;
+                image_offset += pair.offset.to_usize();
+            }
+            5 => {
+                let image_size: u32 = s.read()?;
+                metrics = Metrics::parse_big(&mut s)?;
+                let num_glyphs: u32 = s.read()?;
+                let glyphs = s.read_array32::<GlyphId>(num_glyphs)?;
+                let (index, _) = glyphs.binary_search(&glyph_id)?;
+                image_offset = image_offset
+                    .checked_add(usize::num_from(index).checked_mul(usize::num_from(image_size))?)?;
+            }
+            _ => return None, // Invalid format.
+        }
+
+        Some(Location {
+            format: image_format,
+            offset: image_offset,
+            metrics,
+            ppem: u16::from(strike.ppem_x),
+            bit_depth: strike.bit_depth,
+        })
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
 }
 
 
@@ -164,17 +289,17 @@ struct IndexSubtableInfo {
 
 fn select_index_subtable(
     data: &[u8],
-    size_table: BitmapSizeTable,
+    strike: Strike,
     glyph_id: GlyphId,
 ) -> Option<IndexSubtableInfo> {
-    let mut s = Stream::new_at(data, size_table.subtable_array_offset.to_usize())?;
-    for _ in 0..size_table.number_of_subtables {
+    let mut s = Stream::new_at(data, strike.subtable_array_offset.to_usize())?;
+    for _ in 0..strike.number_of_subtables {
         let start_glyph_id: GlyphId = s.read()?;
         let end_glyph_id: GlyphId = s.read()?;
         let offset: Offset32 = s.read()?;
 
         if (start_glyph_id..=end_glyph_id).contains(&glyph_id) {
-            let offset = size_table.subtable_array_offset.to_usize() + offset.to_usize();
+            let offset = strike.subtable_array_offset.to_usize() + offset.to_usize();
             return Some(IndexSubtableInfo {
                 start_glyph_id,
                 offset,
@@ -187,7 +312,7 @@ fn select_index_subtable(
 
 
 #[derive(Clone, Copy)]
-pub struct GlyphIdOffsetPair {
+struct GlyphIdOffsetPair {
     glyph_id: GlyphId,
     offset: Offset16,
 }
@@ -204,3 +329,122 @@ impl FromData for GlyphIdOffsetPair {
         })
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn shared_metrics() {
+        let data = writer::convert(&[
+            UInt16(3), // major version
+            UInt16(0), // minor version
+            UInt32(1), // strikes count
+            // Bitmap Size
+            UInt32(56), // index subtable array offset
+            UInt32(28), // index tables size
+            UInt32(1), // index subtables count
+            UInt32(0), // color ref
+            Int8(20), Int8(-5), UInt8(30), // horizontal line metrics
+            Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), UInt8(0), UInt8(0),
+            Int8(0), Int8(0), UInt8(0), // vertical line metrics
+            Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), UInt8(0), UInt8(0),
+            UInt16(2), // start glyph
+            UInt16(5), // end glyph
+            UInt8(25), // ppem x
+            UInt8(25), // ppem y
+            UInt8(32), // bit depth
+            UInt8(1), // flags
+            // Index Subtable Array
+            UInt16(2), // first glyph
+            UInt16(5), // last glyph
+            UInt32(8), // additional offset to index subtable
+            // Index Subtable
+            UInt16(2), // index format
+            UInt16(19), // image format
+            UInt32(100), // image data offset
+            UInt32(10), // image size
+            UInt8(16), // height
+            UInt8(15), // width
+            Int8(1), // horizontal bearing x
+            Int8(14), // horizontal bearing y
+            UInt8(17), // horizontal advance
+            Int8(0), // vertical bearing x
+            Int8(0), // vertical bearing y
+            UInt8(0), // vertical advance
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        let strike = table.strike(0).unwrap();
+        assert_eq!(strike.horizontal.ascender, 20);
+        assert_eq!(strike.horizontal.descender, -5);
+        assert!(strike.is_horizontal());
+        assert!(!strike.is_vertical());
+        assert!(table.best_strike(GlyphId(1), 20).is_none());
+        assert!(table.best_strike(GlyphId(5), 20).is_some());
+
+        let location = table.find_location(GlyphId(4), 20).unwrap();
+        assert_eq!(location.format, BitmapFormat::Format19);
+        assert_eq!(location.offset, 120);
+        assert_eq!(location.ppem, 25);
+        assert_eq!((location.metrics.x, location.metrics.y), (1, 14));
+        assert_eq!((location.metrics.width, location.metrics.height), (15, 16));
+    }
+
+    #[test]
+    fn apple_bitmap_location() {
+        // `bloc` has the same structure as `CBLC`, but with version 2.0.
+        let data = writer::convert(&[
+            UInt32(0x00020000), // version
+            UInt32(1), // strikes count
+            // Bitmap Size
+            UInt32(56), // index subtable array offset
+            UInt32(28), // index tables size
+            UInt32(1), // index subtables count
+            UInt32(0), // color ref
+            Int8(8), Int8(0), UInt8(8), // horizontal line metrics
+            Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), UInt8(0), UInt8(0),
+            Int8(0), Int8(0), UInt8(0), // vertical line metrics
+            Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), UInt8(0), UInt8(0),
+            UInt16(1), // start glyph
+            UInt16(1), // end glyph
+            UInt8(8), // ppem x
+            UInt8(8), // ppem y
+            UInt8(1), // bit depth
+            UInt8(1), // flags
+            // Index Subtable Array
+            UInt16(1), // first glyph
+            UInt16(1), // last glyph
+            UInt32(8), // additional offset to index subtable
+            // Index Subtable
+            UInt16(2), // index format
+            UInt16(5), // image format
+            UInt32(4), // image data offset
+            UInt32(8), // image size
+            UInt8(8), // height
+            UInt8(8), // width
+            Int8(0), // horizontal bearing x
+            Int8(8), // horizontal bearing y
+            UInt8(8), // horizontal advance
+            Int8(0), // vertical bearing x
+            Int8(0), // vertical bearing y
+            UInt8(0), // vertical advance
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        let location = table.find_location(GlyphId(1), 8).unwrap();
+        assert_eq!(location.format, BitmapFormat::Format5);
+
+        let mut bdat = writer::convert(&[
+            UInt32(0x00020000), // version
+        ]);
+        bdat.extend_from_slice(&[0xAA; 8]);
+        let image = crate::ebdt::parse(&bdat, location).unwrap();
+        assert_eq!(image.format, crate::RasterImageFormat::BitmapMonoPacked);
+        assert_eq!((image.x, image.y, image.width, image.height), (0, 0, 8, 8));
+        assert_eq!(image.data, &[0xAA; 8]);
+    }
+}
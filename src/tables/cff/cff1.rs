@@ -13,6 +13,7 @@ use super::argstack::ArgumentsStack;
 use super::charset::{STANDARD_ENCODING, Charset, parse_charset};
 use super::charstring::CharStringParser;
 use super::dict::DictionaryParser;
+use super::encoding::{Encoding, parse_encoding};
 use super::index::{Index, parse_index, skip_index};
 use super::std_names::STANDARD_NAMES;
 
@@ -56,12 +57,15 @@ mod operator {
     pub const HFLEX1: u8                    = 36;
     pub const FLEX1: u8                     = 37;
     pub const FIXED_16_16: u8               = 255;
+    // A two-byte Type 1 operator. Not defined in Type 2.
+    pub const SEAC: u8                      = 6;
 }
 
 /// Enumerates some operators defined in the Adobe Technical Note #5176,
 /// Table 9 Top DICT Operator Entries
 mod top_dict_operator {
     pub const CHARSET_OFFSET: u16               = 15;
+    pub const ENCODING_OFFSET: u16              = 16;
     pub const CHAR_STRINGS_OFFSET: u16          = 17;
     pub const PRIVATE_DICT_SIZE_AND_OFFSET: u16 = 18;
     pub const ROS: u16                          = 1230;
@@ -72,7 +76,155 @@ mod top_dict_operator {
 /// Enumerates some operators defined in the Adobe Technical Note #5176,
 /// Table 23 Private DICT Operators
 mod private_dict_operator {
+    pub const BLUE_VALUES: u16              = 6;
+    pub const OTHER_BLUES: u16              = 7;
+    pub const FAMILY_BLUES: u16             = 8;
+    pub const FAMILY_OTHER_BLUES: u16       = 9;
+    pub const STD_HW: u16                   = 10;
+    pub const STD_VW: u16                   = 11;
     pub const LOCAL_SUBROUTINES_OFFSET: u16 = 19;
+    pub const DEFAULT_WIDTH_X: u16          = 20;
+    pub const NOMINAL_WIDTH_X: u16          = 21;
+    pub const BLUE_SCALE: u16               = 1209;
+    pub const BLUE_SHIFT: u16               = 1210;
+    pub const BLUE_FUZZ: u16                = 1211;
+    pub const STEM_SNAP_H: u16              = 1212;
+    pub const STEM_SNAP_V: u16              = 1213;
+    pub const FORCE_BOLD: u16               = 1214;
+    pub const LANGUAGE_GROUP: u16           = 1217;
+}
+
+// The largest Private DICT array is BlueValues/FamilyBlues with up to 7 pairs.
+const MAX_PRIVATE_DICT_ARRAY_LEN: usize = 14;
+
+/// A Private DICT array of absolute values.
+#[derive(Clone, Copy, Default, Debug)]
+struct PrivateDictArray {
+    data: [f32; MAX_PRIVATE_DICT_ARRAY_LEN],
+    len: u8,
+}
+
+impl PrivateDictArray {
+    // Arrays are stored as deltas, so we have to accumulate them.
+    fn from_deltas(deltas: &[f32]) -> Self {
+        let mut array = PrivateDictArray::default();
+        let mut value = 0.0;
+        for (i, delta) in deltas.iter().take(MAX_PRIVATE_DICT_ARRAY_LEN).enumerate() {
+            value += delta;
+            array.data[i] = value;
+            array.len += 1;
+        }
+
+        array
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[f32] {
+        &self.data[..usize::from(self.len)]
+    }
+}
+
+/// Hinting-related values of a
+/// [CFF Private DICT](http://wwwimages.adobe.com/content/dam/Adobe/en/devnet/font/pdfs/5176.CFF.pdf).
+///
+/// Missing values are set to their defaults according to the specification.
+#[derive(Clone, Copy, Debug)]
+pub struct PrivateDict {
+    blue_values: PrivateDictArray,
+    other_blues: PrivateDictArray,
+    family_blues: PrivateDictArray,
+    family_other_blues: PrivateDictArray,
+    stem_snap_h: PrivateDictArray,
+    stem_snap_v: PrivateDictArray,
+
+    /// Dominant horizontal stem width.
+    pub std_hw: Option<f32>,
+
+    /// Dominant vertical stem width.
+    pub std_vw: Option<f32>,
+
+    /// Overshoot suppression point size factor.
+    pub blue_scale: f32,
+
+    /// Overshoot enforcement threshold.
+    pub blue_shift: f32,
+
+    /// Extends the range of alignment zones.
+    pub blue_fuzz: f32,
+
+    /// Indicates that glyphs should be emboldened at small sizes.
+    pub force_bold: bool,
+
+    /// Language group. 1 indicates CJK glyphs.
+    pub language_group: i32,
+
+    /// A glyph width used when a charstring has no width.
+    pub default_width_x: f32,
+
+    /// A value added to a charstring width.
+    pub nominal_width_x: f32,
+}
+
+impl Default for PrivateDict {
+    fn default() -> Self {
+        PrivateDict {
+            blue_values: PrivateDictArray::default(),
+            other_blues: PrivateDictArray::default(),
+            family_blues: PrivateDictArray::default(),
+            family_other_blues: PrivateDictArray::default(),
+            stem_snap_h: PrivateDictArray::default(),
+            stem_snap_v: PrivateDictArray::default(),
+            std_hw: None,
+            std_vw: None,
+            blue_scale: 0.039625,
+            blue_shift: 7.0,
+            blue_fuzz: 1.0,
+            force_bold: false,
+            language_group: 0,
+            default_width_x: 0.0,
+            nominal_width_x: 0.0,
+        }
+    }
+}
+
+impl PrivateDict {
+    /// Returns alignment zones as absolute bottom/top pairs.
+    ///
+    /// The first pair is a baseline overshoot zone.
+    #[inline]
+    pub fn blue_values(&self) -> &[f32] {
+        self.blue_values.as_slice()
+    }
+
+    /// Returns additional bottom alignment zones as absolute bottom/top pairs.
+    #[inline]
+    pub fn other_blues(&self) -> &[f32] {
+        self.other_blues.as_slice()
+    }
+
+    /// Returns family-wide alignment zones as absolute bottom/top pairs.
+    #[inline]
+    pub fn family_blues(&self) -> &[f32] {
+        self.family_blues.as_slice()
+    }
+
+    /// Returns family-wide bottom alignment zones as absolute bottom/top pairs.
+    #[inline]
+    pub fn family_other_blues(&self) -> &[f32] {
+        self.family_other_blues.as_slice()
+    }
+
+    /// Returns common horizontal stem widths.
+    #[inline]
+    pub fn stem_snap_h(&self) -> &[f32] {
+        self.stem_snap_h.as_slice()
+    }
+
+    /// Returns common vertical stem widths.
+    #[inline]
+    pub fn stem_snap_v(&self) -> &[f32] {
+        self.stem_snap_v.as_slice()
+    }
 }
 
 /// Enumerates Charset IDs defined in the Adobe Technical Note #5176, Table 22
@@ -82,6 +234,12 @@ mod charset_id {
     pub const EXPERT_SUBSET: usize = 2;
 }
 
+/// Enumerates Encoding IDs defined in the Adobe Technical Note #5176, Table 16
+mod encoding_id {
+    pub const STANDARD: usize = 0;
+    pub const EXPERT: usize = 1;
+}
+
 
 #[derive(Clone, Copy, Debug)]
 pub struct Metadata<'a> {
@@ -93,6 +251,7 @@ pub struct Metadata<'a> {
     global_subrs: Index<'a>,
     charset: Charset<'a>,
     char_strings: Index<'a>,
+    number_of_glyphs: u16,
     kind: FontKind<'a>,
 }
 
@@ -105,12 +264,30 @@ pub enum FontKind<'a> {
 #[derive(Clone, Copy, Default, Debug)]
 pub struct SIDMetadata<'a> {
     local_subrs: Index<'a>,
+    private_dict: &'a [u8],
+    encoding: Encoding<'a>,
 }
 
 #[derive(Clone, Copy, Default, Debug)]
 pub struct CIDMetadata<'a> {
     fd_array: Index<'a>,
     fd_select: FDSelect<'a>,
+    ros: Option<(StringId, StringId, i32)>,
+}
+
+/// A character collection of a CID-keyed font.
+///
+/// Set by the Top DICT `ROS` operator.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Ros<'a> {
+    /// A registry, like `Adobe`.
+    pub registry: &'a str,
+
+    /// An ordering, like `Japan1`.
+    pub ordering: &'a str,
+
+    /// A supplement number.
+    pub supplement: i32,
 }
 
 pub(crate) fn parse_metadata(data: &[u8]) -> Option<Metadata> {
@@ -179,6 +356,7 @@ pub(crate) fn parse_metadata(data: &[u8]) -> Option<Metadata> {
         global_subrs,
         charset,
         char_strings,
+        number_of_glyphs,
         kind,
     })
 }
@@ -192,6 +370,19 @@ fn parse_sid_metadata(data: &[u8], top_dict: TopDict) -> Option<FontKind> {
 
     // Parse Global Subroutines INDEX.
     let mut metadata = SIDMetadata::default();
+    if let Some(range) = top_dict.private_dict_range.clone() {
+        metadata.private_dict = data.get(range)?;
+    }
+
+    metadata.encoding = match top_dict.encoding_offset {
+        Some(encoding_id::STANDARD) => Encoding::new_standard(),
+        Some(encoding_id::EXPERT) => Encoding::new_expert(),
+        // A malformed Encoding doesn't affect outlines, so it's not an error.
+        Some(offset) => Stream::new_at(data, offset)
+            .and_then(|mut s| parse_encoding(&mut s))
+            .unwrap_or_default(),
+        None => Encoding::new_standard(), // default
+    };
 
     match (top_dict.private_dict_range, subroutines_offset) {
         (Some(private_dict_range), Some(subroutines_offset)) => {
@@ -210,19 +401,16 @@ fn parse_sid_metadata(data: &[u8], top_dict: TopDict) -> Option<FontKind> {
 }
 
 fn parse_cid_metadata(data: &[u8], top_dict: TopDict, number_of_glyphs: u16) -> Option<FontKind> {
-    let (charset_offset, fd_array_offset, fd_select_offset) =
-        match (top_dict.charset_offset, top_dict.fd_array_offset, top_dict.fd_select_offset) {
-            (Some(a), Some(b), Some(c)) => (a, b, c),
-            _ => return None, // charset, FDArray and FDSelect must be set.
+    // A charset is not required to parse outlines, so we do not check it.
+    // Malformed fonts with a predefined or missing charset are handled in `glyph_cid`.
+    let (fd_array_offset, fd_select_offset) =
+        match (top_dict.fd_array_offset, top_dict.fd_select_offset) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return None, // FDArray and FDSelect must be set.
         };
 
-    if charset_offset <= charset_id::EXPERT_SUBSET {
-        // 'There are no predefined charsets for CID fonts.'
-        // Adobe Technical Note #5176, chapter 18 CID-keyed Fonts
-        return None;
-    }
-
     let mut metadata = CIDMetadata::default();
+    metadata.ros = top_dict.ros;
 
     metadata.fd_array = {
         let mut s = Stream::new_at(data, fd_array_offset)?;
@@ -240,9 +428,11 @@ fn parse_cid_metadata(data: &[u8], top_dict: TopDict, number_of_glyphs: u16) ->
 #[derive(Default)]
 struct TopDict {
     charset_offset: Option<usize>,
+    encoding_offset: Option<usize>,
     char_strings_offset: usize,
     private_dict_range: Option<Range<usize>>,
     has_ros: bool,
+    ros: Option<(StringId, StringId, i32)>,
     fd_array_offset: Option<usize>,
     fd_select_offset: Option<usize>,
 }
@@ -262,6 +452,9 @@ fn parse_top_dict(s: &mut Stream) -> Option<TopDict> {
             top_dict_operator::CHARSET_OFFSET => {
                 top_dict.charset_offset = dict_parser.parse_offset();
             }
+            top_dict_operator::ENCODING_OFFSET => {
+                top_dict.encoding_offset = dict_parser.parse_offset();
+            }
             top_dict_operator::CHAR_STRINGS_OFFSET => {
                 top_dict.char_strings_offset = dict_parser.parse_offset()?;
             }
@@ -270,6 +463,8 @@ fn parse_top_dict(s: &mut Stream) -> Option<TopDict> {
             }
             top_dict_operator::ROS => {
                 top_dict.has_ros = true;
+                // Malformed operands do not make a font non-CID.
+                top_dict.ros = parse_ros(&mut dict_parser);
             }
             top_dict_operator::FD_ARRAY => {
                 top_dict.fd_array_offset = dict_parser.parse_offset();
@@ -284,6 +479,20 @@ fn parse_top_dict(s: &mut Stream) -> Option<TopDict> {
     Some(top_dict)
 }
 
+fn parse_ros(dict_parser: &mut DictionaryParser) -> Option<(StringId, StringId, i32)> {
+    dict_parser.parse_operands()?;
+    let operands = dict_parser.operands();
+    if operands.len() != 3 {
+        return None;
+    }
+
+    Some((
+        StringId(u16::try_from(operands[0]).ok()?),
+        StringId(u16::try_from(operands[1]).ok()?),
+        operands[2],
+    ))
+}
+
 fn parse_private_dict(data: &[u8]) -> Option<usize> {
     let mut operands_buffer = [0; MAX_OPERANDS_LEN];
     let mut dict_parser = DictionaryParser::new(data, &mut operands_buffer);
@@ -296,6 +505,63 @@ fn parse_private_dict(data: &[u8]) -> Option<usize> {
     None
 }
 
+fn parse_private_dict_values(data: &[u8]) -> Option<PrivateDict> {
+    let mut dict = PrivateDict::default();
+    let mut operands_buffer = [0; MAX_OPERANDS_LEN];
+    let mut real_operands_buffer = [0.0; MAX_OPERANDS_LEN];
+    let mut dict_parser = DictionaryParser::new(data, &mut operands_buffer);
+    while let Some(operator) = dict_parser.parse_next() {
+        let operands = dict_parser.parse_real_operands(&mut real_operands_buffer)?;
+        let first = operands.first().cloned();
+        match operator.get() {
+            private_dict_operator::BLUE_VALUES => {
+                dict.blue_values = PrivateDictArray::from_deltas(operands);
+            }
+            private_dict_operator::OTHER_BLUES => {
+                dict.other_blues = PrivateDictArray::from_deltas(operands);
+            }
+            private_dict_operator::FAMILY_BLUES => {
+                dict.family_blues = PrivateDictArray::from_deltas(operands);
+            }
+            private_dict_operator::FAMILY_OTHER_BLUES => {
+                dict.family_other_blues = PrivateDictArray::from_deltas(operands);
+            }
+            private_dict_operator::STEM_SNAP_H => {
+                dict.stem_snap_h = PrivateDictArray::from_deltas(operands);
+            }
+            private_dict_operator::STEM_SNAP_V => {
+                dict.stem_snap_v = PrivateDictArray::from_deltas(operands);
+            }
+            private_dict_operator::STD_HW => dict.std_hw = first,
+            private_dict_operator::STD_VW => dict.std_vw = first,
+            private_dict_operator::BLUE_SCALE => {
+                dict.blue_scale = first.unwrap_or(dict.blue_scale);
+            }
+            private_dict_operator::BLUE_SHIFT => {
+                dict.blue_shift = first.unwrap_or(dict.blue_shift);
+            }
+            private_dict_operator::BLUE_FUZZ => {
+                dict.blue_fuzz = first.unwrap_or(dict.blue_fuzz);
+            }
+            private_dict_operator::FORCE_BOLD => {
+                dict.force_bold = first.map(|n| n != 0.0).unwrap_or(false);
+            }
+            private_dict_operator::LANGUAGE_GROUP => {
+                dict.language_group = first.and_then(i32::try_num_from).unwrap_or(0);
+            }
+            private_dict_operator::DEFAULT_WIDTH_X => {
+                dict.default_width_x = first.unwrap_or(0.0);
+            }
+            private_dict_operator::NOMINAL_WIDTH_X => {
+                dict.nominal_width_x = first.unwrap_or(0.0);
+            }
+            _ => {}
+        }
+    }
+
+    Some(dict)
+}
+
 fn parse_font_dict(data: &[u8]) -> Option<Range<usize>> {
     let mut operands_buffer = [0; MAX_OPERANDS_LEN];
     let mut dict_parser = DictionaryParser::new(data, &mut operands_buffer);
@@ -334,18 +600,109 @@ fn parse_cid_local_subrs<'a>(
 }
 
 pub fn glyph_name<'a>(metadata: &'a Metadata, glyph_id: GlyphId) -> Option<&'a str> {
+    match metadata.kind {
+        FontKind::SID(_) => string(metadata, metadata.charset.gid_to_sid(glyph_id)?),
+        FontKind::CID(_) => None,
+    }
+}
+
+pub fn ros<'a>(metadata: &Metadata<'a>) -> Option<Ros<'a>> {
+    match metadata.kind {
+        FontKind::SID(_) => None,
+        FontKind::CID(ref cid) => {
+            let (registry, ordering, supplement) = cid.ros?;
+            Some(Ros {
+                registry: string(metadata, registry)?,
+                ordering: string(metadata, ordering)?,
+                supplement,
+            })
+        }
+    }
+}
+
+/// Resolves a string using standard strings and the String INDEX.
+fn string<'a>(metadata: &Metadata<'a>, sid: StringId) -> Option<&'a str> {
+    let sid = usize::from(sid.0);
+    match STANDARD_NAMES.get(sid) {
+        Some(name) => Some(name),
+        None => {
+            let idx = u32::try_from(sid - STANDARD_NAMES.len()).ok()?;
+            let name = metadata.strings.get(idx)?;
+            core::str::from_utf8(name).ok()
+        }
+    }
+}
+
+/// Returns the length of glyph's charstring in bytes.
+#[cfg(feature = "std")]
+pub(crate) fn glyph_charstring_len(metadata: &Metadata, glyph_id: GlyphId) -> Option<usize> {
+    metadata.char_strings.get(u32::from(glyph_id.0)).map(|data| data.len())
+}
+
+pub fn private_dict(metadata: &Metadata, glyph_id: GlyphId) -> Option<PrivateDict> {
+    let data = match metadata.kind {
+        FontKind::SID(ref sid) => sid.private_dict,
+        FontKind::CID(ref cid) => {
+            // Each Font DICT has its own Private DICT.
+            let font_dict_index = cid.fd_select.font_dict_index(glyph_id)?;
+            let font_dict_data = cid.fd_array.get(u32::from(font_dict_index))?;
+            let range = parse_font_dict(font_dict_data)?;
+            metadata.table_data.get(range)?
+        }
+    };
+
+    parse_private_dict_values(data)
+}
+
+pub fn glyph_cid(metadata: &Metadata, glyph_id: GlyphId) -> Option<u16> {
+    match metadata.kind {
+        FontKind::SID(_) => None,
+        FontKind::CID(_) => {
+            if u32::from(glyph_id.0) >= metadata.char_strings.len() {
+                return None;
+            }
+
+            match metadata.charset {
+                // 'There are no predefined charsets for CID fonts.'
+                // Adobe Technical Note #5176, chapter 18 CID-keyed Fonts
+                //
+                // So we assume an identity mapping, like other libraries do.
+                Charset::ISOAdobe | Charset::Expert | Charset::ExpertSubset => Some(glyph_id.0),
+                // In CID fonts, a charset maps glyph IDs to CIDs instead of SIDs.
+                _ => metadata.charset.gid_to_sid(glyph_id).map(|cid| cid.0),
+            }
+        }
+    }
+}
+
+pub fn glyph_index_by_code(metadata: &Metadata, code: u8) -> Option<GlyphId> {
+    match metadata.kind {
+        FontKind::SID(ref sid) => {
+            let glyph_id = sid.encoding.code_to_gid(&metadata.charset, metadata.number_of_glyphs, code)?;
+            if u32::from(glyph_id.0) < metadata.char_strings.len() {
+                Some(glyph_id)
+            } else {
+                None
+            }
+        }
+        // CID fonts do not have an encoding.
+        FontKind::CID(_) => None,
+    }
+}
+
+pub fn glyph_index_by_name(metadata: &Metadata, name: &str) -> Option<GlyphId> {
     match metadata.kind {
         FontKind::SID(_) => {
-            let sid = metadata.charset.gid_to_sid(glyph_id)?;
-            let sid = usize::from(sid.0);
-            match STANDARD_NAMES.get(sid) {
-                Some(name) => Some(name),
+            let sid = match STANDARD_NAMES.iter().position(|n| *n == name) {
+                Some(idx) => idx,
                 None => {
-                    let idx = u32::try_from(sid - STANDARD_NAMES.len()).ok()?;
-                    let name = metadata.strings.get(idx)?;
-                    core::str::from_utf8(name).ok()
+                    let idx = metadata.strings.into_iter().position(|n| n == name.as_bytes())?;
+                    STANDARD_NAMES.len().checked_add(idx)?
                 }
-            }
+            };
+
+            let sid = StringId(u16::try_from(sid).ok()?);
+            metadata.charset.sid_to_gid(sid, metadata.number_of_glyphs)
         }
         FontKind::CID(_) => None,
     }
@@ -531,35 +888,54 @@ fn _parse_char_string(
                     operator::FLEX => p.parse_flex()?,
                     operator::HFLEX1 => p.parse_hflex1()?,
                     operator::FLEX1 => p.parse_flex1()?,
+                    operator::SEAC => {
+                        // The Type 1 'seac' operator is not allowed in Type 2 charstrings,
+                        // but some fonts converted from Type 1 still use it.
+                        if p.stack.len() != 5 {
+                            return Err(CFFError::InvalidArgumentsStackLength);
+                        }
+
+                        let accent_code = p.stack.pop();
+                        let base_code = p.stack.pop();
+                        let ady = p.stack.pop();
+                        let adx = p.stack.pop();
+                        let asb = p.stack.pop();
+
+                        // The accent origin is relative to its left side bearing.
+                        parse_seac(ctx, p, depth, adx - asb, ady, base_code, accent_code)?;
+
+                        if !p.is_first_move_to {
+                            p.is_first_move_to = true;
+                            p.builder.close();
+                        }
+
+                        if !s.at_end() {
+                            return Err(CFFError::DataAfterEndChar);
+                        }
+
+                        ctx.has_endchar = true;
+
+                        break;
+                    }
                     _ => return Err(CFFError::UnsupportedOperator),
                 }
             }
             operator::ENDCHAR => {
-                if p.stack.len() == 4 || (!ctx.width_parsed && p.stack.len() == 5) {
-                    // Process 'seac'.
-                    let accent_char = seac_code_to_glyph_id(&ctx.metadata.charset, p.stack.pop())
-                        .ok_or(CFFError::InvalidSeacCode)?;
-                    let base_char = seac_code_to_glyph_id(&ctx.metadata.charset, p.stack.pop())
-                        .ok_or(CFFError::InvalidSeacCode)?;
+                if p.stack.len() == 4 || p.stack.len() == 5 {
+                    // Process the deprecated 'seac' form of 'endchar'.
+                    let accent_code = p.stack.pop();
+                    let base_code = p.stack.pop();
                     let dy = p.stack.pop();
                     let dx = p.stack.pop();
 
-                    if !ctx.width_parsed {
+                    // The fifth operand is a width. Like FreeType, we accept it
+                    // even when the width was already set by a previous operator.
+                    if !p.stack.is_empty() {
                         p.stack.pop();
                         ctx.width_parsed = true;
                     }
 
-                    ctx.has_seac = true;
-
-                    let base_char_string = ctx.metadata.char_strings.get(u32::from(base_char.0))
-                        .ok_or(CFFError::InvalidSeacCode)?;
-                    _parse_char_string(ctx, base_char_string, depth + 1, p)?;
-                    p.x = dx;
-                    p.y = dy;
-
-                    let accent_char_string = ctx.metadata.char_strings.get(u32::from(accent_char.0))
-                        .ok_or(CFFError::InvalidSeacCode)?;
-                    _parse_char_string(ctx, accent_char_string, depth + 1, p)?;
+                    parse_seac(ctx, p, depth, dx, dy, base_code, accent_code)?;
                 } else if p.stack.len() == 1 && !ctx.width_parsed {
                     p.stack.pop();
                     ctx.width_parsed = true;
@@ -677,25 +1053,54 @@ fn _parse_char_string(
     Ok(())
 }
 
-fn seac_code_to_glyph_id(charset: &Charset, n: f32) -> Option<GlyphId> {
-    let code = u8::try_num_from(n)?;
+fn parse_seac(
+    ctx: &mut CharStringParserContext,
+    p: &mut CharStringParser,
+    depth: u8,
+    dx: f32,
+    dy: f32,
+    base_code: f32,
+    accent_code: f32,
+) -> Result<(), CFFError> {
+    // Components can use 'seac' too, so we have to limit the recursion.
+    if depth == STACK_LIMIT {
+        return Err(CFFError::NestingLimitReached);
+    }
 
-    let sid = STANDARD_ENCODING[code as usize];
-    let sid = StringId(u16::from(sid));
+    let number_of_glyphs = ctx.metadata.number_of_glyphs;
+    let accent_char = seac_code_to_glyph_id(&ctx.metadata.charset, number_of_glyphs, accent_code)
+        .ok_or(CFFError::InvalidSeacCode)?;
+    let base_char = seac_code_to_glyph_id(&ctx.metadata.charset, number_of_glyphs, base_code)
+        .ok_or(CFFError::InvalidSeacCode)?;
 
-    match charset {
-        Charset::ISOAdobe => {
-            // ISO Adobe charset only defines string ids up to 228 (zcaron)
-            if code <= 228 { Some(GlyphId(sid.0)) } else { None }
-        }
-        Charset::Expert | Charset::ExpertSubset => None,
-        _ => charset.sid_to_gid(sid),
+    ctx.has_seac = true;
+
+    let base_char_string = ctx.metadata.char_strings.get(u32::from(base_char.0))
+        .ok_or(CFFError::InvalidSeacCode)?;
+    _parse_char_string(ctx, base_char_string, depth + 1, p)?;
+    p.x = dx;
+    p.y = dy;
+
+    let accent_char_string = ctx.metadata.char_strings.get(u32::from(accent_char.0))
+        .ok_or(CFFError::InvalidSeacCode)?;
+    _parse_char_string(ctx, accent_char_string, depth + 1, p)
+}
+
+fn seac_code_to_glyph_id(charset: &Charset, number_of_glyphs: u16, n: f32) -> Option<GlyphId> {
+    let code = u8::try_num_from(n)?;
+
+    // 'seac' components must be present in the Standard Encoding.
+    let sid = STANDARD_ENCODING[usize::from(code)];
+    if sid == 0 {
+        return None;
     }
+
+    charset.sid_to_gid(StringId(u16::from(sid)), number_of_glyphs)
 }
 
 
 #[derive(Clone, Copy, Debug)]
-enum FDSelect<'a> {
+pub(crate) enum FDSelect<'a> {
     Format0(LazyArray16<'a, u8>),
     Format3(&'a [u8]), // It's easier to parse it in-place.
 }
@@ -707,7 +1112,7 @@ impl Default for FDSelect<'_> {
 }
 
 impl FDSelect<'_> {
-    fn font_dict_index(&self, glyph_id: GlyphId) -> Option<u8> {
+    pub(crate) fn font_dict_index(&self, glyph_id: GlyphId) -> Option<u8> {
         match self {
             FDSelect::Format0(ref array) => array.get(glyph_id.0),
             FDSelect::Format3(ref data) => {
@@ -742,7 +1147,7 @@ impl FDSelect<'_> {
     }
 }
 
-fn parse_fd_select<'a>(number_of_glyphs: u16, s: &mut Stream<'a>) -> Option<FDSelect<'a>> {
+pub(crate) fn parse_fd_select<'a>(number_of_glyphs: u16, s: &mut Stream<'a>) -> Option<FDSelect<'a>> {
     let format: u8 = s.read()?;
     match format {
         0 => Some(FDSelect::Format0(s.read_array16::<u8>(number_of_glyphs)?)),
@@ -1559,6 +1964,54 @@ mod tests {
         assert!(parse_metadata(&data).is_none());
     }
 
+    #[test]
+    fn malformed_encoding() {
+        let data = writer::convert(&[
+            // Header
+            UInt8(1), // major version
+            UInt8(0), // minor version
+            UInt8(4), // header size
+            UInt8(0), // absolute offset
+
+            // Name INDEX
+            UInt16(0), // count
+
+            // Top DICT
+            // INDEX
+            UInt16(1), // count
+            UInt8(1), // offset size
+            UInt8(1), // index[0]
+            UInt8(6), // index[1]
+            // Data
+            CFFInt(1000), // out of bounds offset
+            UInt8(top_dict_operator::ENCODING_OFFSET as u8),
+            CFFInt(20),
+            UInt8(top_dict_operator::CHAR_STRINGS_OFFSET as u8),
+
+            // String INDEX
+            UInt16(0), // count
+
+            // Global Subroutines INDEX
+            UInt16(0), // count
+
+            // CharString INDEX
+            UInt16(1), // count
+            UInt8(1), // offset size
+            UInt8(1), // index[0]
+            UInt8(4), // index[1]
+            // Data
+            CFFInt(10),
+            UInt8(operator::HORIZONTAL_MOVE_TO),
+            UInt8(operator::ENDCHAR),
+        ]);
+
+        let metadata = parse_metadata(&data).unwrap();
+        let mut builder = Builder(String::new());
+        let char_str = metadata.char_strings.get(0).unwrap();
+        parse_char_string(char_str, &metadata, GlyphId(0), &mut builder).unwrap();
+        assert_eq!(builder.0, "M 10 0 Z ");
+    }
+
     #[test]
     fn invalid_char_string_offset() {
         let data = writer::convert(&[
@@ -1665,4 +2118,180 @@ mod tests {
         let top_dict = parse_top_dict(&mut Stream::new(data)).unwrap();
         assert!(top_dict.private_dict_range.is_none());
     }
+
+    #[test]
+    fn cid_font() {
+        let data = writer::convert(&[
+            // Header
+            UInt8(1), // major version
+            UInt8(0), // minor version
+            UInt8(4), // header size
+            UInt8(0), // absolute offset
+            // Name INDEX
+            UInt16(0), // count
+            // Top DICT INDEX
+            UInt16(1), // count
+            UInt8(1), // offset size
+            UInt8(1), // index [0]
+            UInt8(16), // index [1]
+            // Top DICT
+            CFFInt(391), CFFInt(392), CFFInt(2), UInt8(12), UInt8(30), // ROS
+            CFFInt(45), UInt8(top_dict_operator::CHAR_STRINGS_OFFSET as u8),
+            CFFInt(51), UInt8(12), UInt8(36), // FDArray
+            CFFInt(59), UInt8(12), UInt8(37), // FDSelect
+            // String INDEX
+            UInt16(2), // count
+            UInt8(1), // offset size
+            UInt8(1), // index [0]
+            UInt8(6), // index [1]
+            UInt8(12), // index [2]
+            Raw(b"Adobe"), // string [0]
+            Raw(b"Japan1"), // string [1]
+            // Global Subroutines INDEX
+            UInt16(0), // count
+            // CharStrings INDEX
+            UInt16(1), // count
+            UInt8(1), // offset size
+            UInt8(1), // index [0]
+            UInt8(2), // index [1]
+            UInt8(operator::ENDCHAR), // charstring [0]
+            // FDArray INDEX
+            UInt16(1), // count
+            UInt8(1), // offset size
+            UInt8(1), // index [0]
+            UInt8(4), // index [1]
+            CFFInt(0), CFFInt(0), UInt8(top_dict_operator::PRIVATE_DICT_SIZE_AND_OFFSET as u8),
+            // FDSelect
+            UInt8(0), // format
+            UInt8(0), // font DICT index [0]
+        ]);
+
+        let metadata = parse_metadata(&data).unwrap();
+        assert_eq!(ros(&metadata), Some(Ros { registry: "Adobe", ordering: "Japan1", supplement: 2 }));
+        assert_eq!(glyph_cid(&metadata, GlyphId(0)), Some(0));
+        assert_eq!(glyph_cid(&metadata, GlyphId(1)), None);
+        assert_eq!(glyph_name(&metadata, GlyphId(0)), None);
+    }
+
+    #[test]
+    fn fd_select_format_0() {
+        let data = &[
+            0x00, // format: 0
+            0x00, // glyph 0: 0
+            0x01, // glyph 1: 1
+            0x01, // glyph 2: 1
+        ];
+
+        let fd_select = parse_fd_select(3, &mut Stream::new(data)).unwrap();
+        assert_eq!(fd_select.font_dict_index(GlyphId(0)), Some(0));
+        assert_eq!(fd_select.font_dict_index(GlyphId(2)), Some(1));
+        assert_eq!(fd_select.font_dict_index(GlyphId(3)), None);
+    }
+
+    #[test]
+    fn fd_select_format_3() {
+        let data = &[
+            0x03, // format: 3
+            0x00, 0x02, // number of ranges: 2
+            0x00, 0x00, // first glyph: 0
+            0x01, // font DICT index: 1
+            0x00, 0x0A, // first glyph: 10
+            0x00, // font DICT index: 0
+            0x00, 0x14, // sentinel: 20
+        ];
+
+        let fd_select = parse_fd_select(20, &mut Stream::new(data)).unwrap();
+        assert_eq!(fd_select.font_dict_index(GlyphId(0)), Some(1));
+        assert_eq!(fd_select.font_dict_index(GlyphId(9)), Some(1));
+        assert_eq!(fd_select.font_dict_index(GlyphId(10)), Some(0));
+        assert_eq!(fd_select.font_dict_index(GlyphId(19)), Some(0));
+        assert_eq!(fd_select.font_dict_index(GlyphId(20)), None);
+    }
+
+    #[test]
+    fn endchar_seac() {
+        let data = writer::convert(&[
+            // Header
+            UInt8(1), // major version
+            UInt8(0), // minor version
+            UInt8(4), // header size
+            UInt8(0), // absolute offset
+            // Name INDEX
+            UInt16(0), // count
+            // Top DICT INDEX
+            UInt16(1), // count
+            UInt8(1), // offset size
+            UInt8(1), // index [0]
+            UInt8(3), // index [1]
+            CFFInt(17), UInt8(top_dict_operator::CHAR_STRINGS_OFFSET as u8),
+            // String INDEX
+            UInt16(0), // count
+            // Global Subroutines INDEX
+            UInt16(0), // count
+            // CharStrings INDEX
+            UInt16(5), // count
+            UInt8(1), // offset size
+            UInt8(1), // index [0]
+            UInt8(7), // index [1]
+            UInt8(11), // index [2]
+            UInt8(15), // index [3]
+            UInt8(20), // index [4]
+            UInt8(30), // index [5]
+            // Glyph 0: with a width
+            CFFInt(100), CFFInt(10), CFFInt(20), CFFInt(32), CFFInt(33), UInt8(operator::ENDCHAR),
+            // Glyph 1: space, the base
+            CFFInt(10), CFFInt(20), UInt8(operator::MOVE_TO), UInt8(operator::ENDCHAR),
+            // Glyph 2: exclam, the accent
+            CFFInt(1), CFFInt(1), UInt8(operator::MOVE_TO), UInt8(operator::ENDCHAR),
+            // Glyph 3: without a width
+            CFFInt(10), CFFInt(20), CFFInt(32), CFFInt(33), UInt8(operator::ENDCHAR),
+            // Glyph 4: with a width set by hstem
+            CFFInt(100), CFFInt(0), CFFInt(10), UInt8(operator::HORIZONTAL_STEM),
+            CFFInt(5), CFFInt(10), CFFInt(20), CFFInt(32), CFFInt(33), UInt8(operator::ENDCHAR),
+        ]);
+
+        let metadata = parse_metadata(&data).unwrap();
+        for id in &[0, 3, 4] {
+            let mut builder = Builder(String::new());
+            let char_str = metadata.char_strings.get(*id).unwrap();
+            parse_char_string(char_str, &metadata, GlyphId(*id as u16), &mut builder).unwrap();
+            assert_eq!(builder.0, "M 10 20 Z M 11 21 Z ");
+        }
+    }
+
+    #[test]
+    fn seac_codes() {
+        let charset = Charset::ISOAdobe;
+        assert_eq!(seac_code_to_glyph_id(&charset, 1000, 65.0), Some(GlyphId(34))); // A
+        assert_eq!(seac_code_to_glyph_id(&charset, 1000, 193.0), Some(GlyphId(124))); // grave
+        assert_eq!(seac_code_to_glyph_id(&charset, 1000, 0.0), None); // not in Standard Encoding
+        assert_eq!(seac_code_to_glyph_id(&charset, 1000, 256.0), None);
+        assert_eq!(seac_code_to_glyph_id(&Charset::Expert, 1000, 65.0), None);
+        // A glyph that is not present in the font.
+        assert_eq!(seac_code_to_glyph_id(&charset, 34, 65.0), None);
+    }
+
+    #[test]
+    fn private_dict_values() {
+        let data = &[
+            129, 149, 248, 136, 149, 6, // BlueValues: -10 10 500 10
+            189, 10, // StdHW: 50
+            30, 0x0A, 0x03, 0x75, 0xFF, 12, 9, // BlueScale: 0.0375
+            140, 12, 14, // ForceBold: 1
+            239, 21, // nominalWidthX: 100
+        ];
+
+        let dict = parse_private_dict_values(data).unwrap();
+        assert_eq!(dict.blue_values(), &[-10.0, 0.0, 500.0, 510.0]);
+        assert_eq!(dict.other_blues(), &[]);
+        assert_eq!(dict.stem_snap_h(), &[]);
+        assert_eq!(dict.std_hw, Some(50.0));
+        assert_eq!(dict.std_vw, None);
+        assert!((dict.blue_scale - 0.0375).abs() < 1e-6);
+        assert_eq!(dict.blue_shift, 7.0);
+        assert_eq!(dict.blue_fuzz, 1.0);
+        assert!(dict.force_bold);
+        assert_eq!(dict.default_width_x, 0.0);
+        assert_eq!(dict.nominal_width_x, 100.0);
+    }
 }
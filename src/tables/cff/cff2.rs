@@ -9,6 +9,7 @@ use crate::parser::{Stream, NumFrom, TryNumFrom};
 use crate::var_store::*;
 use super::{Builder, CFFError, calc_subroutine_bias, conv_subroutine_index};
 use super::argstack::ArgumentsStack;
+use super::cff1::{FDSelect, parse_fd_select};
 use super::charstring::CharStringParser;
 use super::dict::DictionaryParser;
 use super::index::{Index, parse_index};
@@ -61,6 +62,7 @@ mod top_dict_operator {
     pub const CHAR_STRINGS_OFFSET: u16      = 17;
     pub const VARIATION_STORE_OFFSET: u16   = 24;
     pub const FONT_DICT_INDEX_OFFSET: u16   = 1236;
+    pub const FONT_DICT_SELECT_OFFSET: u16  = 1237;
 }
 
 // https://docs.microsoft.com/en-us/typography/opentype/spec/cff2#table-10-font-dict-operator-entries
@@ -71,14 +73,20 @@ mod font_dict_operator {
 // https://docs.microsoft.com/en-us/typography/opentype/spec/cff2#table-16-private-dict-operators
 mod private_dict_operator {
     pub const LOCAL_SUBROUTINES_OFFSET: u16 = 19;
+    pub const VS_INDEX: u16                 = 22;
 }
 
 
 #[derive(Clone, Copy, Default)]
 pub struct Metadata<'a> {
+    // The whole CFF2 table.
+    // Used to resolve Private DICT and local subroutines.
+    table_data: &'a [u8],
+
     global_subrs: Index<'a>,
-    local_subrs: Index<'a>,
     char_strings: Index<'a>,
+    font_dicts: Index<'a>,
+    fd_select: Option<FDSelect<'a>>,
     item_variation_store: ItemVariationStore<'a>,
 }
 
@@ -104,6 +112,7 @@ pub(crate) fn parse_metadata(data: &[u8]) -> Option<Metadata> {
     let top_dict = parse_top_dict(top_dict_data)?;
 
     let mut metadata = Metadata::default();
+    metadata.table_data = data;
 
     // Parse Global Subroutines INDEX.
     metadata.global_subrs = parse_index::<u32>(&mut s)?;
@@ -119,30 +128,72 @@ pub(crate) fn parse_metadata(data: &[u8]) -> Option<Metadata> {
         metadata.item_variation_store = ItemVariationStore::parse(s)?;
     }
 
-    // TODO: simplify
     if let Some(offset) = top_dict.font_dict_index_offset {
         let mut s = Stream::new_at(data, offset)?;
-        'outer: for font_dict_data in parse_index::<u32>(&mut s)? {
-            if let Some(private_dict_range) = parse_font_dict(font_dict_data) {
-                // 'Private DICT size and offset, from start of the CFF2 table.'
-                let private_dict_data = data.get(private_dict_range.clone())?;
-                if let Some(subroutines_offset) = parse_private_dict(private_dict_data) {
-                    // 'The local subroutines offset is relative to the beginning
-                    // of the Private DICT data.'
-                    if let Some(start) = private_dict_range.start.checked_add(subroutines_offset) {
-                        let data = data.get(start..data.len())?;
-                        let mut s = Stream::new(data);
-                        metadata.local_subrs = parse_index::<u32>(&mut s)?;
-                        break 'outer;
-                    }
-                }
-            }
-        }
+        metadata.font_dicts = parse_index::<u32>(&mut s)?;
+    }
+
+    // 'FDSelect is present only if FDArray contains more than one Font DICT.'
+    if let Some(offset) = top_dict.font_dict_select_offset {
+        let number_of_glyphs = u16::try_from(metadata.char_strings.len()).ok()?;
+        let mut s = Stream::new_at(data, offset)?;
+        metadata.fd_select = Some(parse_fd_select(number_of_glyphs, &mut s)?);
     }
 
     Some(metadata)
 }
 
+/// Glyph-specific data stored in a Private DICT.
+#[derive(Clone, Copy, Default)]
+struct PrivateDictData<'a> {
+    local_subrs: Index<'a>,
+    vsindex: u16,
+}
+
+/// In CFF2, each glyph references a Font DICT via FDSelect, so to get local subroutines
+/// and a default variation store index we have to:
+///   1. Find Font DICT index via FDSelect by GID. Use the first one when FDSelect is not set.
+///   2. Get Font DICT data from FDArray using this index.
+///   3. Get a Private DICT offset from a Font DICT.
+///   4. Get a local subroutine offset and `vsindex` from Private DICT.
+///   5. Parse a local subroutine at offset.
+fn parse_glyph_private_dict<'a>(
+    metadata: &Metadata<'a>,
+    glyph_id: GlyphId,
+) -> Option<PrivateDictData<'a>> {
+    let font_dict_index = match metadata.fd_select {
+        Some(ref fd_select) => fd_select.font_dict_index(glyph_id)?,
+        None => 0,
+    };
+
+    let font_dict_data = metadata.font_dicts.get(u32::from(font_dict_index))?;
+    // 'Private DICT size and offset, from start of the CFF2 table.'
+    let private_dict_range = parse_font_dict(font_dict_data)?;
+    let private_dict_data = metadata.table_data.get(private_dict_range.clone())?;
+    let (subroutines_offset, vsindex) = parse_private_dict(private_dict_data);
+
+    let mut dict = PrivateDictData {
+        vsindex,
+        ..PrivateDictData::default()
+    };
+
+    if let Some(subroutines_offset) = subroutines_offset {
+        // 'The local subroutines offset is relative to the beginning
+        // of the Private DICT data.'
+        let start = private_dict_range.start.checked_add(subroutines_offset)?;
+        let mut s = Stream::new(metadata.table_data.get(start..)?);
+        dict.local_subrs = parse_index::<u32>(&mut s)?;
+    }
+
+    Some(dict)
+}
+
+
+/// Returns the length of glyph's charstring in bytes.
+#[cfg(feature = "std")]
+pub(crate) fn glyph_charstring_len(metadata: &Metadata, glyph_id: GlyphId) -> Option<usize> {
+    metadata.char_strings.get(u32::from(glyph_id.0)).map(|data| data.len())
+}
 
 pub(crate) fn outline(
     metadata: &Metadata,
@@ -151,13 +202,16 @@ pub(crate) fn outline(
     builder: &mut dyn OutlineBuilder,
 ) -> Option<Rect> {
     let data = metadata.char_strings.get(u32::from(glyph_id.0))?;
-    parse_char_string(data, metadata, coordinates, builder).ok()
+    // Private DICT is optional, so we should not fail when it is missing.
+    let private_dict = parse_glyph_private_dict(metadata, glyph_id).unwrap_or_default();
+    parse_char_string(data, metadata, private_dict, coordinates, builder).ok()
 }
 
 #[derive(Clone, Copy, Default)]
 struct TopDictData {
     char_strings_offset: usize,
     font_dict_index_offset: Option<usize>,
+    font_dict_select_offset: Option<usize>,
     variation_store_offset: Option<usize>,
 }
 
@@ -171,6 +225,8 @@ fn parse_top_dict(data: &[u8]) -> Option<TopDictData> {
             dict_data.char_strings_offset = dict_parser.parse_offset()?;
         } else if operator.get() == top_dict_operator::FONT_DICT_INDEX_OFFSET {
             dict_data.font_dict_index_offset = dict_parser.parse_offset();
+        } else if operator.get() == top_dict_operator::FONT_DICT_SELECT_OFFSET {
+            dict_data.font_dict_select_offset = dict_parser.parse_offset();
         } else if operator.get() == top_dict_operator::VARIATION_STORE_OFFSET {
             dict_data.variation_store_offset = dict_parser.parse_offset();
         }
@@ -208,24 +264,37 @@ fn parse_font_dict(data: &[u8]) -> Option<Range<usize>> {
     private_dict_range
 }
 
-fn parse_private_dict(data: &[u8]) -> Option<usize> {
+/// Returns a local subroutines offset and a default variation store index.
+fn parse_private_dict(data: &[u8]) -> (Option<usize>, u16) {
     let mut subroutines_offset = None;
+    let mut vsindex = 0;
     let mut operands_buffer = [0; MAX_OPERANDS_LEN];
     let mut dict_parser = DictionaryParser::new(data, &mut operands_buffer);
     while let Some(operator) = dict_parser.parse_next() {
-        if operator.get() == private_dict_operator::LOCAL_SUBROUTINES_OFFSET {
-            dict_parser.parse_operands()?;
-            let operands = dict_parser.operands();
+        let operator = operator.get();
+        if operator != private_dict_operator::LOCAL_SUBROUTINES_OFFSET
+            && operator != private_dict_operator::VS_INDEX
+        {
+            continue;
+        }
 
-            if operands.len() == 1 {
-                subroutines_offset = usize::try_from(operands[0]).ok();
-            }
+        if dict_parser.parse_operands().is_none() {
+            continue;
+        }
 
-            break;
+        let operands = dict_parser.operands();
+        if operands.len() != 1 {
+            continue;
+        }
+
+        if operator == private_dict_operator::LOCAL_SUBROUTINES_OFFSET {
+            subroutines_offset = usize::try_from(operands[0]).ok();
+        } else {
+            vsindex = u16::try_from(operands[0]).unwrap_or(0);
         }
     }
 
-    subroutines_offset
+    (subroutines_offset, vsindex)
 }
 
 /// CFF2 allows up to 65535 scalars, but an average font will have 3-5.
@@ -277,6 +346,7 @@ impl Scalars {
 
 struct CharStringParserContext<'a> {
     metadata: &'a Metadata<'a>,
+    local_subrs: Index<'a>,
     coordinates: &'a [NormalizedCoordinate],
     scalars: Scalars,
     had_vsindex: bool,
@@ -304,11 +374,13 @@ impl CharStringParserContext<'_> {
 fn parse_char_string(
     data: &[u8],
     metadata: &Metadata,
+    private_dict: PrivateDictData,
     coordinates: &[NormalizedCoordinate],
     builder: &mut dyn OutlineBuilder,
 ) -> Result<Rect, CFFError> {
     let mut ctx = CharStringParserContext {
         metadata,
+        local_subrs: private_dict.local_subrs,
         coordinates,
         scalars: Scalars::default(),
         had_vsindex: false,
@@ -317,7 +389,8 @@ fn parse_char_string(
     };
 
     // Load scalars at default index.
-    ctx.update_scalars(0)?;
+    // It can be overridden by the `vsindex` operator.
+    ctx.update_scalars(private_dict.vsindex)?;
 
     let mut inner_builder = Builder {
         builder,
@@ -402,9 +475,9 @@ fn _parse_char_string(
                     return Err(CFFError::NestingLimitReached);
                 }
 
-                let subroutine_bias = calc_subroutine_bias(ctx.metadata.local_subrs.len());
+                let subroutine_bias = calc_subroutine_bias(ctx.local_subrs.len());
                 let index = conv_subroutine_index(p.stack.pop(), subroutine_bias)?;
-                let char_string = ctx.metadata.local_subrs.get(index)
+                let char_string = ctx.local_subrs.get(index)
                     .ok_or(CFFError::InvalidSubroutineIndex)?;
                 _parse_char_string(ctx, char_string, depth + 1, p)?;
             }
@@ -531,3 +604,20 @@ fn _parse_char_string(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn private_dict() {
+        let data = &[
+            140, 22, // vsindex: 1
+            139, 139, 140, 23, // blend: 0 0 1
+            239, 19, // local subroutines offset: 100
+        ];
+
+        assert_eq!(parse_private_dict(data), (Some(100), 1));
+        assert_eq!(parse_private_dict(&[]), (None, 0));
+    }
+}
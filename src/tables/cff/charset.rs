@@ -99,13 +99,30 @@ pub(crate) enum Charset<'a> {
 }
 
 impl Charset<'_> {
-    pub fn sid_to_gid(&self, sid: StringId) -> Option<GlyphId> {
+    /// Returns a glyph ID for a string ID.
+    ///
+    /// Glyph IDs that are not less than `number_of_glyphs` are rejected,
+    /// since predefined charsets can be longer than the font.
+    pub fn sid_to_gid(&self, sid: StringId, number_of_glyphs: u16) -> Option<GlyphId> {
+        self.find_glyph(sid).filter(|id| id.0 < number_of_glyphs)
+    }
+
+    fn find_glyph(&self, sid: StringId) -> Option<GlyphId> {
         if sid.0 == 0 {
             return Some(GlyphId(0));
         }
 
         match self {
-            Charset::ISOAdobe | Charset::Expert | Charset::ExpertSubset => None,
+            Charset::ISOAdobe => {
+                // ISO Adobe SIDs are equal to glyph IDs.
+                if sid.0 <= 228 { Some(GlyphId(sid.0)) } else { None }
+            }
+            Charset::Expert => {
+                EXPERT_ENCODING.iter().position(|n| *n == sid.0).map(|n| GlyphId(n as u16))
+            }
+            Charset::ExpertSubset => {
+                EXPERT_SUBSET_ENCODING.iter().position(|n| *n == sid.0).map(|n| GlyphId(n as u16))
+            }
             Charset::Format0(ref array) => {
                 // First glyph is omitted, so we have to add 1.
                 array.into_iter().position(|n| n == sid).map(|n| GlyphId(n as u16 + 1))
@@ -244,3 +261,47 @@ pub(crate) fn parse_charset<'a>(number_of_glyphs: u16, s: &mut Stream<'a>) -> Op
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn predefined_charsets() {
+        let charset = Charset::ISOAdobe;
+        assert_eq!(charset.sid_to_gid(StringId(5), 1000), Some(GlyphId(5)));
+        assert_eq!(charset.gid_to_sid(GlyphId(5)), Some(StringId(5)));
+        // Predefined charsets are longer than most fonts.
+        assert_eq!(charset.sid_to_gid(StringId(5), 5), None);
+        assert_eq!(charset.sid_to_gid(StringId(229), 1000), None);
+
+        let charset = Charset::Expert;
+        assert_eq!(charset.sid_to_gid(StringId(229), 1000), Some(GlyphId(2)));
+        assert_eq!(charset.gid_to_sid(GlyphId(2)), Some(StringId(229)));
+        assert_eq!(charset.sid_to_gid(StringId(2), 1000), None);
+
+        let charset = Charset::ExpertSubset;
+        assert_eq!(charset.sid_to_gid(StringId(231), 1000), Some(GlyphId(2)));
+        assert_eq!(charset.gid_to_sid(GlyphId(2)), Some(StringId(231)));
+    }
+
+    #[test]
+    fn format_1() {
+        let data = &[
+            0x01, // format: 1
+            0x01, 0x00, // first: 256
+            0x01, // left: 1
+            0x00, 0x05, // first: 5
+            0x00, // left: 0
+        ];
+
+        let charset = parse_charset(4, &mut Stream::new(data)).unwrap();
+        assert_eq!(charset.sid_to_gid(StringId(0), 4), Some(GlyphId(0)));
+        assert_eq!(charset.sid_to_gid(StringId(256), 1000), Some(GlyphId(1)));
+        assert_eq!(charset.sid_to_gid(StringId(257), 1000), Some(GlyphId(2)));
+        assert_eq!(charset.sid_to_gid(StringId(5), 4), Some(GlyphId(3)));
+        assert_eq!(charset.sid_to_gid(StringId(5), 3), None);
+        assert_eq!(charset.sid_to_gid(StringId(6), 4), None);
+        assert_eq!(charset.gid_to_sid(GlyphId(3)), Some(StringId(5)));
+    }
+}
@@ -99,6 +99,27 @@ impl<'a> DictionaryParser<'a> {
         Some(())
     }
 
+    /// Parses operands of the current operator as real numbers.
+    ///
+    /// Unlike `parse_operands`, doesn't ignore real (float) operands.
+    /// Operands that do not fit into `buffer` are ignored.
+    pub fn parse_real_operands<'b>(&self, buffer: &'b mut [f32]) -> Option<&'b [f32]> {
+        let mut s = Stream::new_at(self.data, self.operands_offset)?;
+        let mut len = 0;
+        while !s.at_end() && len < buffer.len() {
+            let b: u8 = s.read()?;
+            // 0..=21 bytes are operators.
+            if is_dict_one_byte_op(b) {
+                break;
+            }
+
+            buffer[len] = parse_real_number(b, &mut s)?;
+            len += 1;
+        }
+
+        Some(&buffer[..len])
+    }
+
     #[inline]
     pub fn operands(&self) -> &[i32] {
         &self.operands[..usize::from(self.operands_len)]
@@ -184,6 +205,69 @@ pub fn parse_number(b0: u8, s: &mut Stream) -> Option<i32> {
     }
 }
 
+// Just like `parse_number`, but also parses real numbers.
+pub fn parse_real_number(b0: u8, s: &mut Stream) -> Option<f32> {
+    if b0 != 30 {
+        return parse_number(b0, s).map(|n| n as f32);
+    }
+
+    // Adobe Technical Note #5176, Table 5 Nibble Definitions
+    //
+    // We cannot use `str::parse`, since it requires `core::num::dec2flt`,
+    // which is pretty large. So we are accumulating digits manually.
+    let mut mantissa = 0.0f64;
+    let mut mantissa_exponent = 0i32;
+    let mut exponent = 0i32;
+    let mut is_negative = false;
+    let mut is_negative_exponent = false;
+    let mut after_dot = false;
+    let mut in_exponent = false;
+    'outer: loop {
+        let b1: u8 = s.read()?;
+        for &nibble in &[b1 >> 4, b1 & 15] {
+            match nibble {
+                0..=9 => {
+                    if in_exponent {
+                        // Prevent overflow. Such exponents are pointless anyway.
+                        exponent = (exponent * 10 + i32::from(nibble)).min(1000);
+                    } else {
+                        mantissa = mantissa * 10.0 + f64::from(nibble);
+                        if after_dot {
+                            mantissa_exponent -= 1;
+                        }
+                    }
+                }
+                0xa => after_dot = true,
+                0xb => in_exponent = true,
+                0xc => {
+                    in_exponent = true;
+                    is_negative_exponent = true;
+                }
+                0xd => return None, // Reserved.
+                0xe => is_negative = true,
+                _ => break 'outer, // END_OF_FLOAT_FLAG
+            }
+        }
+    }
+
+    if is_negative_exponent {
+        exponent = -exponent;
+    }
+
+    // `f64::powi` is not available in `no_std`.
+    let mut n = mantissa;
+    let exponent = exponent + mantissa_exponent;
+    for _ in 0..exponent.abs() {
+        if exponent > 0 { n *= 10.0; } else { n /= 10.0; }
+    }
+
+    if is_negative {
+        n = -n;
+    }
+
+    Some(n as f32)
+}
+
 // Just like `parse_number`, but doesn't actually parses the data.
 pub fn skip_number(b0: u8, s: &mut Stream) -> Option<()> {
     match b0 {
@@ -222,4 +306,19 @@ mod tests {
         assert_eq!(parse_number(0x1D, &mut Stream::new(&[0x00, 0x01, 0x86, 0xA0])).unwrap(), 100000);
         assert_eq!(parse_number(0x1D, &mut Stream::new(&[0xFF, 0xFE, 0x79, 0x60])).unwrap(), -100000);
     }
+
+    #[test]
+    fn parse_dict_real_number() {
+        assert_eq!(parse_real_number(0xFA, &mut Stream::new(&[0x7C])).unwrap(), 1000.0);
+        // -2.25
+        assert_eq!(parse_real_number(0x1E, &mut Stream::new(&[0xE2, 0xA2, 0x5F])).unwrap(), -2.25);
+        // 0.140541E-3
+        let n = parse_real_number(0x1E, &mut Stream::new(&[0x0A, 0x14, 0x05, 0x41, 0xC3, 0xFF])).unwrap();
+        assert!((n - 0.000140541).abs() < 1e-9);
+        // 0.039625
+        let n = parse_real_number(0x1E, &mut Stream::new(&[0x0A, 0x03, 0x96, 0x25, 0xFF])).unwrap();
+        assert!((n - 0.039625).abs() < 1e-7);
+        // Unterminated.
+        assert!(parse_real_number(0x1E, &mut Stream::new(&[0x0A, 0x03])).is_none());
+    }
 }
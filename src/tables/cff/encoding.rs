@@ -0,0 +1,222 @@
+use crate::GlyphId;
+use crate::parser::{Stream, FromData, LazyArray16};
+use super::StringId;
+use super::charset::{STANDARD_ENCODING, Charset};
+
+/// The Expert Encoding as defined in the Adobe Technical Note #5176 Appendix B.
+const EXPERT_ENCODING: [u16;256] = [
+      0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,
+      0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,
+      1,  229,  230,    0,  231,  232,  233,  234,  235,  236,  237,  238,   13,   14,   15,   99,
+    239,  240,  241,  242,  243,  244,  245,  246,  247,  248,   27,   28,  249,  250,  251,  252,
+      0,  253,  254,  255,  256,  257,    0,    0,    0,  258,    0,    0,  259,  260,  261,  262,
+      0,    0,  263,  264,  265,    0,  266,  109,  110,  267,  268,  269,    0,  270,  271,  272,
+    273,  274,  275,  276,  277,  278,  279,  280,  281,  282,  283,  284,  285,  286,  287,  288,
+    289,  290,  291,  292,  293,  294,  295,  296,  297,  298,  299,  300,  301,  302,  303,    0,
+      0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,
+      0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,
+      0,  304,  305,  306,    0,    0,  307,  308,  309,  310,  311,    0,  312,    0,    0,  313,
+      0,    0,  314,  315,    0,    0,  316,  317,  318,    0,    0,    0,  158,  155,  163,  319,
+    320,  321,  322,  323,  324,  325,    0,    0,  326,  150,  164,  169,  327,  328,  329,  330,
+    331,  332,  333,  334,  335,  336,  337,  338,  339,  340,  341,  342,  343,  344,  345,  346,
+    347,  348,  349,  350,  351,  352,  353,  354,  355,  356,  357,  358,  359,  360,  361,  362,
+    363,  364,  365,  366,  367,  368,  369,  370,  371,  372,  373,  374,  375,  376,  377,  378,
+];
+
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Format1Range {
+    first: u8,
+    left: u8,
+}
+
+impl FromData for Format1Range {
+    const SIZE: usize = 2;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(Format1Range {
+            first: s.read::<u8>()?,
+            left: s.read::<u8>()?,
+        })
+    }
+}
+
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Supplement {
+    code: u8,
+    name: StringId,
+}
+
+impl FromData for Supplement {
+    const SIZE: usize = 3;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(Supplement {
+            code: s.read::<u8>()?,
+            name: s.read::<StringId>()?,
+        })
+    }
+}
+
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum EncodingKind<'a> {
+    Standard,
+    Expert,
+    Format0(LazyArray16<'a, u8>),
+    Format1(LazyArray16<'a, Format1Range>),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Encoding<'a> {
+    kind: EncodingKind<'a>,
+    supplements: LazyArray16<'a, Supplement>,
+}
+
+impl Default for Encoding<'_> {
+    fn default() -> Self {
+        Self::new_standard()
+    }
+}
+
+impl<'a> Encoding<'a> {
+    pub fn new_standard() -> Self {
+        Encoding {
+            kind: EncodingKind::Standard,
+            supplements: LazyArray16::default(),
+        }
+    }
+
+    pub fn new_expert() -> Self {
+        Encoding {
+            kind: EncodingKind::Expert,
+            supplements: LazyArray16::default(),
+        }
+    }
+
+    pub fn code_to_gid(&self, charset: &Charset, number_of_glyphs: u16, code: u8) -> Option<GlyphId> {
+        // Supplements can override the main encoding.
+        if let Some(supplement) = self.supplements.into_iter().find(|s| s.code == code) {
+            return charset.sid_to_gid(supplement.name, number_of_glyphs);
+        }
+
+        match self.kind {
+            EncodingKind::Standard => {
+                let sid = StringId(u16::from(STANDARD_ENCODING[usize::from(code)]));
+                if sid.0 == 0 { None } else { charset.sid_to_gid(sid, number_of_glyphs) }
+            }
+            EncodingKind::Expert => {
+                let sid = StringId(EXPERT_ENCODING[usize::from(code)]);
+                if sid.0 == 0 { None } else { charset.sid_to_gid(sid, number_of_glyphs) }
+            }
+            EncodingKind::Format0(ref table) => {
+                // First glyph is omitted, so we have to add 1.
+                table.into_iter().position(|c| c == code).map(|i| GlyphId(i as u16 + 1))
+            }
+            EncodingKind::Format1(ref table) => {
+                let mut glyph_id = GlyphId(1);
+                for range in *table {
+                    let last = u16::from(range.first) + u16::from(range.left);
+                    if range.first <= code && u16::from(code) <= last {
+                        glyph_id.0 += u16::from(code - range.first);
+                        return Some(glyph_id);
+                    }
+
+                    glyph_id.0 = glyph_id.0.checked_add(u16::from(range.left) + 1)?;
+                }
+
+                None
+            }
+        }
+    }
+}
+
+pub(crate) fn parse_encoding<'a>(s: &mut Stream<'a>) -> Option<Encoding<'a>> {
+    let format: u8 = s.read()?;
+    // The first high-bit in format indicates that a Supplemental encoding is present.
+    let kind = match format & 0x7F {
+        0 => {
+            let count: u8 = s.read()?;
+            EncodingKind::Format0(s.read_array16::<u8>(u16::from(count))?)
+        }
+        1 => {
+            let count: u8 = s.read()?;
+            EncodingKind::Format1(s.read_array16::<Format1Range>(u16::from(count))?)
+        }
+        _ => return None,
+    };
+
+    let supplements = if format & 0x80 != 0 {
+        let count: u8 = s.read()?;
+        s.read_array16::<Supplement>(u16::from(count))?
+    } else {
+        LazyArray16::default()
+    };
+
+    Some(Encoding { kind, supplements })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn predefined() {
+        let charset = Charset::ISOAdobe;
+        let encoding = Encoding::new_standard();
+        assert_eq!(encoding.code_to_gid(&charset, 1000, b'A'), Some(GlyphId(34)));
+        assert_eq!(encoding.code_to_gid(&charset, 1000, 0), None);
+        assert_eq!(encoding.code_to_gid(&charset, 34, b'A'), None);
+
+        // dollaroldstyle, which is not a part of the ISO Adobe charset.
+        let encoding = Encoding::new_expert();
+        assert_eq!(encoding.code_to_gid(&charset, 1000, b'$'), None);
+        assert_eq!(encoding.code_to_gid(&Charset::Expert, 1000, b'$'), Some(GlyphId(4)));
+        assert_eq!(encoding.code_to_gid(&Charset::Expert, 1000, b'a'), Some(GlyphId(55))); // Asmall
+    }
+
+    #[test]
+    fn format_0() {
+        let data = &[
+            0x80, // format: 0 with supplements
+            0x02, // number of codes: 2
+            0x41, // code [0]: A
+            0x42, // code [1]: B
+            0x01, // number of supplements: 1
+            0x61, // code: a
+            0x00, 0x22, // glyph SID: 34 (A)
+        ];
+
+        let charset = Charset::ISOAdobe;
+        let encoding = parse_encoding(&mut Stream::new(data)).unwrap();
+        assert_eq!(encoding.code_to_gid(&charset, 1000, b'A'), Some(GlyphId(1)));
+        assert_eq!(encoding.code_to_gid(&charset, 1000, b'B'), Some(GlyphId(2)));
+        assert_eq!(encoding.code_to_gid(&charset, 1000, b'C'), None);
+        assert_eq!(encoding.code_to_gid(&charset, 1000, b'a'), Some(GlyphId(34)));
+    }
+
+    #[test]
+    fn format_1() {
+        let data = &[
+            0x01, // format: 1
+            0x02, // number of ranges: 2
+            0x41, // first code: A
+            0x02, // codes left: 2
+            0x61, // first code: a
+            0x00, // codes left: 0
+        ];
+
+        let charset = Charset::ISOAdobe;
+        let encoding = parse_encoding(&mut Stream::new(data)).unwrap();
+        assert_eq!(encoding.code_to_gid(&charset, 1000, b'A'), Some(GlyphId(1)));
+        assert_eq!(encoding.code_to_gid(&charset, 1000, b'C'), Some(GlyphId(3)));
+        assert_eq!(encoding.code_to_gid(&charset, 1000, b'D'), None);
+        assert_eq!(encoding.code_to_gid(&charset, 1000, b'a'), Some(GlyphId(4)));
+        assert_eq!(encoding.code_to_gid(&charset, 1000, b'b'), None);
+    }
+}
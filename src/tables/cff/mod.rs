@@ -4,6 +4,7 @@ mod argstack;
 mod charset;
 mod charstring;
 mod dict;
+mod encoding;
 mod index;
 mod std_names;
 
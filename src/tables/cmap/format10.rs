@@ -32,3 +32,26 @@ pub fn codepoints(data: &[u8], mut f: impl FnMut(u32)) -> Option<()> {
 
     Some(())
 }
+
+/// Calls `f` for all code points that are mapped to a non-zero glyph ID.
+///
+/// Stops when `f` returns `false`.
+pub fn mappings(data: &[u8], mut f: impl FnMut(u32, u16) -> bool) -> Option<()> {
+    let mut s = Stream::new(data);
+    s.skip::<u16>(); // format
+    s.skip::<u16>(); // reserved
+    s.skip::<u32>(); // length
+    s.skip::<u32>(); // language
+    let first_code_point: u32 = s.read()?;
+    let count: u32 = s.read()?;
+    let glyphs = s.read_array32::<u16>(count)?;
+
+    for (i, id) in glyphs.into_iter().enumerate() {
+        let code_point = first_code_point.checked_add(i as u32)?;
+        if id != 0 && !f(code_point, id) {
+            break;
+        }
+    }
+
+    Some(())
+}
@@ -33,15 +33,19 @@ pub fn parse(data: &[u8], code_point: u32) -> Option<u16> {
     s.skip::<u32>(); // language
     let count: u32 = s.read()?;
     let groups = s.read_array32::<SequentialMapGroup>(count)?;
-    for group in groups {
-        let start_char_code = group.start_char_code;
-        if code_point >= start_char_code && code_point <= group.end_char_code {
-            let id = group.start_glyph_id.checked_add(code_point)?.checked_sub(start_char_code)?;
-            return u16::try_from(id).ok();
+    // Groups must be sorted by the start character code.
+    let (_, group) = groups.binary_search_by(|group| {
+        if group.end_char_code < code_point {
+            core::cmp::Ordering::Less
+        } else if group.start_char_code > code_point {
+            core::cmp::Ordering::Greater
+        } else {
+            core::cmp::Ordering::Equal
         }
-    }
+    })?;
 
-    None
+    let id = group.start_glyph_id.checked_add(code_point)?.checked_sub(group.start_char_code)?;
+    u16::try_from(id).ok()
 }
 
 pub fn codepoints(data: &[u8], mut f: impl FnMut(u32)) -> Option<()> {
@@ -60,3 +64,173 @@ pub fn codepoints(data: &[u8], mut f: impl FnMut(u32)) -> Option<()> {
 
     Some(())
 }
+
+/// Calls `f` for all code points that are mapped to a non-zero glyph ID.
+///
+/// Group ends are clamped to the Unicode range, and groups that overlap
+/// the previous ones are clipped, so each code point is visited only once.
+/// Stops when `f` returns `false`.
+pub fn mappings(data: &[u8], mut f: impl FnMut(u32, u16) -> bool) -> Option<()> {
+    let mut s = Stream::new(data);
+    s.skip::<u16>(); // format
+    s.skip::<u16>(); // reserved
+    s.skip::<u32>(); // length
+    s.skip::<u32>(); // language
+    let count: u32 = s.read()?;
+    let groups = s.read_array32::<SequentialMapGroup>(count)?;
+    // The first code point that wasn't visited yet.
+    let mut next = 0;
+    for group in groups {
+        let start = core::cmp::max(group.start_char_code, next);
+        let end = core::cmp::min(group.end_char_code, super::MAX_CODE_POINT);
+        if start > end {
+            continue;
+        }
+
+        for code_point in start..=end {
+            let id = group.start_glyph_id.checked_add(code_point - group.start_char_code);
+            match id.and_then(|id| u16::try_from(id).ok()) {
+                Some(0) => {}
+                Some(id) => {
+                    if !f(code_point, id) {
+                        return Some(());
+                    }
+                }
+                // All the following glyph IDs are out of range too.
+                None => break,
+            }
+        }
+
+        next = end + 1;
+    }
+
+    Some(())
+}
+
+
+/// Returns the first glyph ID that is not less than `number_of_glyphs`.
+///
+/// Groups are checked arithmetically, since they can cover the whole Unicode range.
+pub fn glyph_id_out_of_bounds(data: &[u8], number_of_glyphs: u16) -> Option<u16> {
+    let mut s = Stream::new(data);
+    s.skip::<u16>(); // format
+    s.skip::<u16>(); // reserved
+    s.skip::<u32>(); // length
+    s.skip::<u32>(); // language
+    let count: u32 = s.read()?;
+    let groups = s.read_array32::<SequentialMapGroup>(count)?;
+    let number_of_glyphs = u32::from(number_of_glyphs);
+    groups.into_iter().find_map(|group| {
+        let len = group.end_char_code.checked_sub(group.start_char_code)?;
+        let end_glyph_id = group.start_glyph_id.saturating_add(len);
+        if end_glyph_id < number_of_glyphs {
+            return None;
+        }
+
+        u16::try_from(core::cmp::max(group.start_glyph_id, number_of_glyphs)).ok()
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, mappings, glyph_id_out_of_bounds};
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn multiple_groups() {
+        let data = writer::convert(&[
+            UInt16(12), // format
+            UInt16(0), // reserved
+            UInt32(52), // length
+            UInt32(0), // language
+            UInt32(3), // number of groups
+            // Group [0]
+            UInt32(0x20), // start char code
+            UInt32(0x7E), // end char code
+            UInt32(1), // start glyph ID
+            // Group [1]
+            UInt32(0x410), // start char code
+            UInt32(0x44F), // end char code
+            UInt32(96), // start glyph ID
+            // Group [2]
+            UInt32(0x1F600), // start char code
+            UInt32(0x1F600), // end char code
+            UInt32(160), // start glyph ID
+        ]);
+
+        assert_eq!(parse(&data, 0x20), Some(1));
+        assert_eq!(parse(&data, 0x41), Some(34));
+        assert_eq!(parse(&data, 0x7E), Some(95));
+        assert_eq!(parse(&data, 0x7F), None);
+        assert_eq!(parse(&data, 0x44F), Some(159));
+        assert_eq!(parse(&data, 0x1F600), Some(160));
+        assert_eq!(parse(&data, 0x1F601), None);
+        assert_eq!(parse(&data, 0x10), None);
+    }
+
+    #[test]
+    fn glyph_id_out_of_bounds_in_groups() {
+        let data = writer::convert(&[
+            UInt16(12), // format
+            UInt16(0), // reserved
+            UInt32(40), // length
+            UInt32(0), // language
+            UInt32(2), // number of groups
+            // Group [0]
+            UInt32(0x20), // start char code
+            UInt32(0x7E), // end char code
+            UInt32(1), // start glyph ID
+            // Group [1]
+            UInt32(0), // start char code
+            UInt32(0x10FFFF), // end char code
+            UInt32(10), // start glyph ID
+        ]);
+
+        assert_eq!(glyph_id_out_of_bounds(&data, 200), Some(200));
+        assert_eq!(glyph_id_out_of_bounds(&data, 50), Some(50));
+        assert_eq!(glyph_id_out_of_bounds(&data, 5), Some(5));
+        assert_eq!(glyph_id_out_of_bounds(&data, 0xFFFF), Some(0xFFFF));
+    }
+
+    #[test]
+    fn mappings_of_malformed_groups() {
+        let data = writer::convert(&[
+            UInt16(12), // format
+            UInt16(0), // reserved
+            UInt32(52), // length
+            UInt32(0), // language
+            UInt32(3), // number of groups
+            // Group [0]
+            UInt32(0x10FF00), // start char code
+            UInt32(4278191298), // end char code: out of Unicode range
+            UInt32(1), // start glyph ID
+            // Group [1]: overlaps the previous one
+            UInt32(0x10FFFF), // start char code
+            UInt32(0x10FFFF), // end char code
+            UInt32(5), // start glyph ID
+            // Group [2]: start > end
+            UInt32(0x20), // start char code
+            UInt32(0x10), // end char code
+            UInt32(1), // start glyph ID
+        ]);
+
+        let mut count = 0;
+        let mut last = None;
+        mappings(&data, |c, id| {
+            count += 1;
+            last = Some((c, id));
+            true
+        }).unwrap();
+        assert_eq!(count, 256);
+        assert_eq!(last, Some((0x10FFFF, 256)));
+
+        let mut count = 0;
+        mappings(&data, |_, _| {
+            count += 1;
+            count < 10
+        }).unwrap();
+        assert_eq!(count, 10);
+    }
+}
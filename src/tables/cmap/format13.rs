@@ -27,3 +27,56 @@ pub fn codepoints(data: &[u8], f: impl FnMut(u32)) -> Option<()> {
     // same as for format 12.
     super::format12::codepoints(data, f)
 }
+
+/// Calls `f` for all code points that are mapped to a non-zero glyph ID.
+///
+/// Uses the same clamping as `format12::mappings`.
+pub fn mappings(data: &[u8], mut f: impl FnMut(u32, u16) -> bool) -> Option<()> {
+    let mut s = Stream::new(data);
+    s.skip::<u16>(); // format
+    s.skip::<u16>(); // reserved
+    s.skip::<u32>(); // length
+    s.skip::<u32>(); // language
+    let count: u32 = s.read()?;
+    let groups = s.read_array32::<super::format12::SequentialMapGroup>(count)?;
+    // The first code point that wasn't visited yet.
+    let mut next = 0;
+    for group in groups {
+        let start = core::cmp::max(group.start_char_code, next);
+        let end = core::cmp::min(group.end_char_code, super::MAX_CODE_POINT);
+        if start > end {
+            continue;
+        }
+
+        // All codepoints of a group are mapped to the same glyph.
+        match u16::try_from(group.start_glyph_id) {
+            Ok(0) | Err(_) => {}
+            Ok(id) => {
+                for code_point in start..=end {
+                    if !f(code_point, id) {
+                        return Some(());
+                    }
+                }
+            }
+        }
+
+        next = end + 1;
+    }
+
+    Some(())
+}
+
+pub fn glyph_id_out_of_bounds(data: &[u8], number_of_glyphs: u16) -> Option<u16> {
+    let mut s = Stream::new(data);
+    s.skip::<u16>(); // format
+    s.skip::<u16>(); // reserved
+    s.skip::<u32>(); // length
+    s.skip::<u32>(); // language
+    let count: u32 = s.read()?;
+    let groups = s.read_array32::<super::format12::SequentialMapGroup>(count)?;
+    // All codepoints of a group are mapped to the same glyph.
+    groups.into_iter()
+        .filter(|group| group.start_char_code <= group.end_char_code)
+        .filter_map(|group| u16::try_from(group.start_glyph_id).ok())
+        .find(|id| *id >= number_of_glyphs)
+}
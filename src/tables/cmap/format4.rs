@@ -36,27 +36,13 @@ pub fn parse(data: &[u8], code_point: u32) -> Option<u16> {
             if start_value > code_point {
                 end = index;
             } else {
-                let id_range_offset = id_range_offsets.get(index)?;
-                let id_delta = id_deltas.get(index)?;
-                if id_range_offset == 0 {
-                    return Some(code_point.wrapping_add(id_delta as u16));
-                }
-
-                let delta = (u32::from(code_point) - u32::from(start_value)) * 2;
-                let delta = u16::try_from(delta).ok()?;
-
-                let id_range_offset_pos = (id_range_offset_pos + usize::from(index) * 2) as u16;
-                let pos = id_range_offset_pos.wrapping_add(delta);
-                let pos = pos.wrapping_add(id_range_offset);
-                let glyph_array_value: u16 = Stream::read_at(data, usize::from(pos))?;
-
-                // 0 indicates missing glyph.
-                if glyph_array_value == 0 {
-                    return None;
-                }
-
-                let glyph_id = (glyph_array_value as i16).wrapping_add(id_delta);
-                return u16::try_from(glyph_id).ok();
+                let segment = Segment {
+                    start: start_value,
+                    id_delta: id_deltas.get(index)?,
+                    id_range_offset: id_range_offsets.get(index)?,
+                    id_range_offset_pos: id_range_offset_pos + usize::from(index) * 2,
+                };
+                return segment.glyph_id(data, code_point);
             }
         } else {
             start = index + 1;
@@ -66,6 +52,37 @@ pub fn parse(data: &[u8], code_point: u32) -> Option<u16> {
     None
 }
 
+struct Segment {
+    start: u16,
+    id_delta: i16,
+    id_range_offset: u16,
+    id_range_offset_pos: usize,
+}
+
+impl Segment {
+    fn glyph_id(&self, data: &[u8], code_point: u16) -> Option<u16> {
+        if self.id_range_offset == 0 {
+            return Some(code_point.wrapping_add(self.id_delta as u16));
+        }
+
+        let delta = (u32::from(code_point) - u32::from(self.start)) * 2;
+        let delta = u16::try_from(delta).ok()?;
+
+        let id_range_offset_pos = self.id_range_offset_pos as u16;
+        let pos = id_range_offset_pos.wrapping_add(delta);
+        let pos = pos.wrapping_add(self.id_range_offset);
+        let glyph_array_value: u16 = Stream::read_at(data, usize::from(pos))?;
+
+        // 0 indicates missing glyph.
+        if glyph_array_value == 0 {
+            return None;
+        }
+
+        let glyph_id = (glyph_array_value as i16).wrapping_add(self.id_delta);
+        u16::try_from(glyph_id).ok()
+    }
+}
+
 pub fn codepoints(data: &[u8], mut f: impl FnMut(u32)) -> Option<()> {
     let mut s = Stream::new(data);
     s.advance(6); // format + length + language
@@ -90,9 +107,62 @@ pub fn codepoints(data: &[u8], mut f: impl FnMut(u32)) -> Option<()> {
     Some(())
 }
 
+/// Calls `f` for all code points that are mapped to a non-zero glyph ID.
+///
+/// Glyph IDs are resolved per segment, without a lookup for each code point.
+/// Segments that overlap the previous ones are clipped, so each code point
+/// is visited only once. Stops when `f` returns `false`.
+pub fn mappings(data: &[u8], mut f: impl FnMut(u32, u16) -> bool) -> Option<()> {
+    let mut s = Stream::new(data);
+    s.advance(6); // format + length + language
+    let seg_count_x2: u16 = s.read()?;
+    if seg_count_x2 < 2 {
+        return None;
+    }
+
+    let seg_count = seg_count_x2 / 2;
+    s.advance(6); // searchRange + entrySelector + rangeShift
+
+    let end_codes = s.read_array16::<u16>(seg_count)?;
+    s.skip::<u16>(); // reservedPad
+    let start_codes = s.read_array16::<u16>(seg_count)?;
+    let id_deltas = s.read_array16::<i16>(seg_count)?;
+    let id_range_offset_pos = s.offset();
+    let id_range_offsets = s.read_array16::<u16>(seg_count)?;
+
+    // The first code point that wasn't visited yet.
+    let mut next = 0u32;
+    for index in 0..seg_count {
+        let segment = Segment {
+            start: start_codes.get(index)?,
+            id_delta: id_deltas.get(index)?,
+            id_range_offset: id_range_offsets.get(index)?,
+            id_range_offset_pos: id_range_offset_pos + usize::from(index) * 2,
+        };
+
+        let start = core::cmp::max(u32::from(segment.start), next);
+        let end = u32::from(end_codes.get(index)?);
+        if start > end {
+            continue;
+        }
+
+        for code_point in start..=end {
+            if let Some(id) = segment.glyph_id(data, code_point as u16).filter(|id| *id != 0) {
+                if !f(code_point, id) {
+                    return Some(());
+                }
+            }
+        }
+
+        next = end + 1;
+    }
+
+    Some(())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{parse, codepoints};
+    use super::{parse, codepoints, mappings};
 
     #[test]
     fn single_glyph() {
@@ -205,6 +275,14 @@ mod tests {
         assert_eq!(parse(data, 0x48), Some(6));
         assert_eq!(parse(data, 0x49), Some(7));
         assert_eq!(parse(data, 0x4A), None);
+
+        let mut vec = vec![];
+        mappings(data, |c, id| {
+            vec.push((c, id));
+            true
+        }).unwrap();
+        // The 0xFFFF sentinel is mapped to `.notdef`.
+        assert_eq!(vec, [(0x41, 1), (0x43, 2), (0x44, 3), (0x45, 4), (0x47, 5), (0x48, 6), (0x49, 7)]);
     }
 
     #[test]
@@ -26,18 +26,26 @@ mod format14;
 
 pub use format14::GlyphVariationResult;
 
+/// The last Unicode code point.
+const MAX_CODE_POINT: u32 = 0x10FFFF;
+
 
 /// An iterator over
 /// [character encoding](https://docs.microsoft.com/en-us/typography/opentype/spec/cmap)
 /// subtables.
 #[derive(Clone, Copy, Default)]
-#[allow(missing_debug_implementations)]
 pub struct Subtables<'a> {
     data: &'a [u8],
     records: LazyArray16<'a, EncodingRecord>,
     index: u16,
 }
 
+impl core::fmt::Debug for Subtables<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Subtables()")
+    }
+}
+
 impl<'a> Iterator for Subtables<'a> {
     type Item = Subtable<'a>;
 
@@ -69,6 +77,7 @@ impl<'a> Iterator for Subtables<'a> {
 
 
 /// A character encoding subtable.
+#[derive(Clone, Copy)]
 pub struct Subtable<'a> {
     platform_id: PlatformId,
     encoding_id: u16,
@@ -223,6 +232,66 @@ impl<'a> Subtable<'a> {
             },
         };
     }
+
+    /// Calls `f` for all codepoints that are mapped to a non-zero glyph ID.
+    ///
+    /// Unlike `codepoints` followed by `glyph_index`, glyph IDs are resolved
+    /// per segment or group. Group ends are clamped to U+10FFFF and each codepoint
+    /// is visited only once, so malformed subtables cannot cause excessive work.
+    ///
+    /// Stops when `f` returns `false`.
+    pub fn mappings<F: FnMut(u32, GlyphId) -> bool>(&self, mut f: F) {
+        let mut f = |c, id| f(c, GlyphId(id));
+        let _ = match self.format {
+            Format::SegmentMappingToDeltaValues => {
+                format4::mappings(self.subtable_data, f)
+            }
+            Format::TrimmedArray => {
+                format10::mappings(self.subtable_data, f)
+            }
+            Format::SegmentedCoverage => {
+                format12::mappings(self.subtable_data, f)
+            }
+            Format::ManyToOneRangeMappings => {
+                format13::mappings(self.subtable_data, f)
+            }
+            _ => {
+                // Other formats are limited to 16-bit codepoints.
+                let mut done = false;
+                self.codepoints(|c| {
+                    if !done {
+                        if let Some(id) = self.glyph_index(c) {
+                            done = !f(c, id.0);
+                        }
+                    }
+                });
+                None
+            }
+        };
+    }
+
+    /// Returns the first glyph ID that is not less than `number_of_glyphs`.
+    ///
+    /// Segmented formats are checked without iterating over all codepoints.
+    pub(crate) fn glyph_id_out_of_bounds(&self, number_of_glyphs: u16) -> Option<GlyphId> {
+        match self.format {
+            Format::SegmentedCoverage => {
+                format12::glyph_id_out_of_bounds(self.subtable_data, number_of_glyphs).map(GlyphId)
+            }
+            Format::ManyToOneRangeMappings => {
+                format13::glyph_id_out_of_bounds(self.subtable_data, number_of_glyphs).map(GlyphId)
+            }
+            _ => {
+                let mut glyph_id = None;
+                self.codepoints(|c| {
+                    if glyph_id.is_none() {
+                        glyph_id = self.glyph_index(c).filter(|id| id.0 >= number_of_glyphs);
+                    }
+                });
+                glyph_id
+            }
+        }
+    }
 }
 
 impl<'a> core::fmt::Debug for Subtable<'a> {
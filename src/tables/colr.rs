@@ -0,0 +1,1880 @@
+/*!
+A [Color table](https://docs.microsoft.com/en-us/typography/opentype/spec/colr) implementation.
+*/
+
+use core::convert::TryFrom;
+
+use crate::GlyphId;
+use crate::tables::cpal::{self, Color};
+use crate::parser::{Stream, FromData, F2DOT14, Fixed, LazyArray16, LazyArray32, f32_bound};
+use crate::parser::{Offset, Offset24, Offset32};
+#[cfg(feature = "variable-fonts")] use crate::{NormalizedCoordinate, VarCoords};
+#[cfg(feature = "variable-fonts")] use crate::tables::hvar::DeltaSetIndexMap;
+#[cfg(feature = "variable-fonts")] use crate::var_store::ItemVariationStore;
+
+/// The maximum depth of a paint graph.
+///
+/// Protects from stack overflow on malformed fonts.
+const MAX_NESTING_DEPTH: usize = 64;
+
+/// The maximum number of paints visited during a single traversal.
+///
+/// Protects from exponential traversal time on malformed fonts,
+/// where paints are shared by multiple parents.
+const MAX_VISITS: u16 = 10_000;
+
+
+#[derive(Clone, Copy)]
+struct BaseGlyphRecord {
+    glyph_id: GlyphId,
+    first_layer_index: u16,
+    layers_count: u16,
+}
+
+impl FromData for BaseGlyphRecord {
+    const SIZE: usize = 6;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(BaseGlyphRecord {
+            glyph_id: s.read::<GlyphId>()?,
+            first_layer_index: s.read::<u16>()?,
+            layers_count: s.read::<u16>()?,
+        })
+    }
+}
+
+
+#[derive(Clone, Copy)]
+struct LayerRecord {
+    glyph_id: GlyphId,
+    palette_index: u16,
+}
+
+impl FromData for LayerRecord {
+    const SIZE: usize = 4;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(LayerRecord {
+            glyph_id: s.read::<GlyphId>()?,
+            palette_index: s.read::<u16>()?,
+        })
+    }
+}
+
+
+#[derive(Clone, Copy)]
+struct BaseGlyphPaintRecord {
+    glyph_id: GlyphId,
+    paint_offset: Offset32,
+}
+
+impl FromData for BaseGlyphPaintRecord {
+    const SIZE: usize = 6;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(BaseGlyphPaintRecord {
+            glyph_id: s.read::<GlyphId>()?,
+            paint_offset: s.read::<Offset32>()?,
+        })
+    }
+}
+
+
+#[derive(Clone, Copy)]
+struct ClipRecord {
+    start_glyph_id: GlyphId,
+    end_glyph_id: GlyphId,
+    clip_box_offset: Offset24,
+}
+
+impl FromData for ClipRecord {
+    const SIZE: usize = 7;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(ClipRecord {
+            start_glyph_id: s.read::<GlyphId>()?,
+            end_glyph_id: s.read::<GlyphId>()?,
+            clip_box_offset: s.read::<Offset24>()?,
+        })
+    }
+}
+
+
+/// A [clip box](https://docs.microsoft.com/en-us/typography/opentype/spec/colr#color-glyph-clip-boxes).
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ClipBox {
+    pub x_min: f32,
+    pub y_min: f32,
+    pub x_max: f32,
+    pub y_max: f32,
+}
+
+
+/// A [Color table](https://docs.microsoft.com/en-us/typography/opentype/spec/colr).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    data: &'a [u8],
+    base_glyphs: LazyArray16<'a, BaseGlyphRecord>,
+    layers: LazyArray16<'a, LayerRecord>,
+    // Version 1 data.
+    base_glyph_paints_offset: usize,
+    base_glyph_paints: LazyArray32<'a, BaseGlyphPaintRecord>,
+    layer_paints_offset: usize,
+    layer_paints: LazyArray32<'a, Offset32>,
+    clips_offset: usize,
+    clips: LazyArray32<'a, ClipRecord>,
+    variations: Variations<'a>,
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let version: u16 = s.read()?;
+        if version > 1 {
+            return None;
+        }
+
+        let base_glyphs_count: u16 = s.read()?;
+        let base_glyphs_offset: Option<Offset32> = s.read()?;
+        let layers_offset: Option<Offset32> = s.read()?;
+        let layers_count: u16 = s.read()?;
+
+        let mut base_glyphs = LazyArray16::default();
+        if let Some(offset) = base_glyphs_offset {
+            base_glyphs = Stream::new_at(data, offset.to_usize())?.read_array16(base_glyphs_count)?;
+        }
+
+        let mut layers = LazyArray16::default();
+        if let Some(offset) = layers_offset {
+            layers = Stream::new_at(data, offset.to_usize())?.read_array16(layers_count)?;
+        }
+
+        let mut table = Table {
+            data,
+            base_glyphs,
+            layers,
+            base_glyph_paints_offset: 0,
+            base_glyph_paints: LazyArray32::default(),
+            layer_paints_offset: 0,
+            layer_paints: LazyArray32::default(),
+            clips_offset: 0,
+            clips: LazyArray32::default(),
+            variations: Variations::default(),
+        };
+
+        if version == 0 {
+            return Some(table);
+        }
+
+        let base_glyph_paints_offset: Option<Offset32> = s.read()?;
+        let layer_paints_offset: Option<Offset32> = s.read()?;
+        let clips_offset: Option<Offset32> = s.read()?;
+        let var_index_map_offset: Option<Offset32> = s.read()?;
+        let variation_store_offset: Option<Offset32> = s.read()?;
+
+        if let Some(offset) = base_glyph_paints_offset {
+            let mut s = Stream::new_at(data, offset.to_usize())?;
+            let count: u32 = s.read()?;
+            table.base_glyph_paints_offset = offset.to_usize();
+            table.base_glyph_paints = s.read_array32(count)?;
+        }
+
+        if let Some(offset) = layer_paints_offset {
+            let mut s = Stream::new_at(data, offset.to_usize())?;
+            let count: u32 = s.read()?;
+            table.layer_paints_offset = offset.to_usize();
+            table.layer_paints = s.read_array32(count)?;
+        }
+
+        if let Some(offset) = clips_offset {
+            let mut s = Stream::new_at(data, offset.to_usize())?;
+            let format: u8 = s.read()?;
+            if format == 1 {
+                let count: u32 = s.read()?;
+                table.clips_offset = offset.to_usize();
+                table.clips = s.read_array32(count)?;
+            }
+        }
+
+        #[cfg(feature = "variable-fonts")]
+        {
+            table.variations.index_map = var_index_map_offset
+                .and_then(|offset| data.get(offset.to_usize()..))
+                .map(DeltaSetIndexMap::new);
+            table.variations.store = variation_store_offset
+                .and_then(|offset| Stream::new_at(data, offset.to_usize()))
+                .and_then(ItemVariationStore::parse);
+        }
+
+        #[cfg(not(feature = "variable-fonts"))]
+        {
+            let _ = (var_index_map_offset, variation_store_offset);
+        }
+
+        Some(table)
+    }
+
+    /// Sets variation coordinates, that are used to resolve variable data.
+    #[cfg(feature = "variable-fonts")]
+    #[inline]
+    pub(crate) fn set_coordinates(&mut self, coordinates: &[NormalizedCoordinate]) {
+        self.variations.coordinates = VarCoords::from_slice(coordinates);
+    }
+
+    /// Returns an iterator over glyph's color layers.
+    ///
+    /// Each layer is a glyph ID, which outline should be filled,
+    /// and a palette entry index. The index `0xFFFF` means the text foreground color.
+    /// Layers are ordered from bottom to top.
+    ///
+    /// Returns `None` when the glyph has no `COLR` version 0 record.
+    pub fn glyph_layers(&self, glyph_id: GlyphId) -> Option<impl Iterator<Item = (GlyphId, u16)> + 'a> {
+        let (_, record) = self.base_glyphs.binary_search_by(|r| r.glyph_id.cmp(&glyph_id))?;
+        let end = record.first_layer_index.checked_add(record.layers_count)?;
+        let layers = self.layers.slice(record.first_layer_index..end)?;
+        Some(layers.into_iter().map(|layer| (layer.glyph_id, layer.palette_index)))
+    }
+
+    /// Returns a root paint of a `COLR` version 1 glyph.
+    pub fn base_glyph_paint(&self, glyph_id: GlyphId) -> Option<PaintRef> {
+        let (_, record) = self.base_glyph_paints
+            .binary_search_by(|r| r.glyph_id.cmp(&glyph_id))?;
+        PaintRef::new(self.base_glyph_paints_offset, record.paint_offset)
+    }
+
+    /// Returns a paint from the layer list.
+    ///
+    /// Used by `Paint::ColrLayers`.
+    pub fn layer_paint(&self, index: u32) -> Option<PaintRef> {
+        let offset = self.layer_paints.get(index)?;
+        PaintRef::new(self.layer_paints_offset, offset)
+    }
+
+    /// Returns a clip box of a `COLR` version 1 glyph.
+    ///
+    /// Renderers should clip the glyph's paint graph with it.
+    /// A variable clip box is resolved using the current variation coordinates.
+    ///
+    /// Returns `None` when the glyph has no clip box. In which case,
+    /// the glyph bounds are unknown without traversing the paint graph.
+    pub fn clip_box(&self, glyph_id: GlyphId) -> Option<ClipBox> {
+        let (_, record) = self.clips.binary_search_by(|r| {
+            use core::cmp::Ordering;
+            if glyph_id < r.start_glyph_id {
+                Ordering::Greater
+            } else if glyph_id > r.end_glyph_id {
+                Ordering::Less
+            } else {
+                Ordering::Equal
+            }
+        })?;
+
+        let offset = self.clips_offset.checked_add(record.clip_box_offset.to_usize())?;
+        let mut s = Stream::new_at(self.data, offset)?;
+        let format: u8 = s.read()?;
+        let mut clip_box = ClipBox {
+            x_min: read_fword(&mut s)?,
+            y_min: read_fword(&mut s)?,
+            x_max: read_fword(&mut s)?,
+            y_max: read_fword(&mut s)?,
+        };
+
+        match format {
+            1 => {}
+            2 => {
+                let base: u32 = s.read()?;
+                clip_box.x_min += self.variations.delta(base, 0);
+                clip_box.y_min += self.variations.delta(base, 1);
+                clip_box.x_max += self.variations.delta(base, 2);
+                clip_box.y_max += self.variations.delta(base, 3);
+            }
+            _ => return None,
+        }
+
+        Some(clip_box)
+    }
+
+    /// Parses a paint.
+    ///
+    /// Variable paints are resolved using the current variation coordinates.
+    pub fn paint(&self, paint_ref: PaintRef) -> Option<Paint<'a>> {
+        let offset = paint_ref.to_usize();
+        let mut s = Stream::new_at(self.data, offset)?;
+        let format: u8 = s.read()?;
+
+        let mut paint = match format {
+            1 => {
+                let layers_count: u8 = s.read()?;
+                let first_layer_index: u32 = s.read()?;
+                Paint::ColrLayers { first_layer_index, layers_count }
+            }
+            2 | 3 => Paint::Solid {
+                palette_index: s.read()?,
+                alpha: s.read::<F2DOT14>()?.to_f32(),
+            },
+            4 | 5 => Paint::LinearGradient(LinearGradient {
+                color_line: self.read_color_line(&mut s, offset, format == 5)?,
+                x0: read_fword(&mut s)?,
+                y0: read_fword(&mut s)?,
+                x1: read_fword(&mut s)?,
+                y1: read_fword(&mut s)?,
+                x2: read_fword(&mut s)?,
+                y2: read_fword(&mut s)?,
+            }),
+            6 | 7 => Paint::RadialGradient(RadialGradient {
+                color_line: self.read_color_line(&mut s, offset, format == 7)?,
+                x0: read_fword(&mut s)?,
+                y0: read_fword(&mut s)?,
+                r0: f32::from(s.read::<u16>()?),
+                x1: read_fword(&mut s)?,
+                y1: read_fword(&mut s)?,
+                r1: f32::from(s.read::<u16>()?),
+            }),
+            8 | 9 => Paint::SweepGradient(SweepGradient {
+                color_line: self.read_color_line(&mut s, offset, format == 9)?,
+                center_x: read_fword(&mut s)?,
+                center_y: read_fword(&mut s)?,
+                start_angle: read_angle(&mut s)?,
+                end_angle: read_angle(&mut s)?,
+            }),
+            10 => {
+                let paint = read_child(&mut s, offset)?;
+                Paint::Glyph { glyph_id: s.read()?, paint }
+            }
+            11 => Paint::ColrGlyph { glyph_id: s.read()? },
+            12 | 13 => {
+                let paint = read_child(&mut s, offset)?;
+                let transform_offset: Offset24 = s.read()?;
+                let mut s = Stream::new_at(self.data, offset + transform_offset.to_usize())?;
+                let mut transform = Transform {
+                    xx: s.read::<Fixed>()?.0,
+                    yx: s.read::<Fixed>()?.0,
+                    xy: s.read::<Fixed>()?.0,
+                    yy: s.read::<Fixed>()?.0,
+                    dx: s.read::<Fixed>()?.0,
+                    dy: s.read::<Fixed>()?.0,
+                };
+
+                // A variable transform has its own variation index base.
+                if format == 13 {
+                    let base: u32 = s.read()?;
+                    let d = |i| self.variations.delta(base, i) / 65536.0;
+                    transform.xx += d(0);
+                    transform.yx += d(1);
+                    transform.xy += d(2);
+                    transform.yy += d(3);
+                    transform.dx += d(4);
+                    transform.dy += d(5);
+                }
+
+                Paint::Transform { transform, paint }
+            }
+            14 | 15 => {
+                let paint = read_child(&mut s, offset)?;
+                Paint::Translate {
+                    dx: read_fword(&mut s)?,
+                    dy: read_fword(&mut s)?,
+                    paint,
+                }
+            }
+            16 ..= 23 => {
+                let paint = read_child(&mut s, offset)?;
+                let uniform = format >= 20;
+                let scale_x = s.read::<F2DOT14>()?.to_f32();
+                let scale_y = if uniform { scale_x } else { s.read::<F2DOT14>()?.to_f32() };
+                let (center_x, center_y) = read_center(&mut s, format)?;
+                Paint::Scale { scale_x, scale_y, center_x, center_y, paint }
+            }
+            24 ..= 27 => {
+                let paint = read_child(&mut s, offset)?;
+                let angle = read_angle(&mut s)?;
+                let (center_x, center_y) = read_center(&mut s, format)?;
+                Paint::Rotate { angle, center_x, center_y, paint }
+            }
+            28 ..= 31 => {
+                let paint = read_child(&mut s, offset)?;
+                let x_skew_angle = read_angle(&mut s)?;
+                let y_skew_angle = read_angle(&mut s)?;
+                let (center_x, center_y) = read_center(&mut s, format)?;
+                Paint::Skew { x_skew_angle, y_skew_angle, center_x, center_y, paint }
+            }
+            32 => {
+                let source = read_child(&mut s, offset)?;
+                let mode = CompositeMode::parse(s.read()?)?;
+                let backdrop = read_child(&mut s, offset)?;
+                Paint::Composite { source, mode, backdrop }
+            }
+            _ => return None,
+        };
+
+        // Variable formats are odd, except `ColrLayers`, `ColrGlyph` and `Transform`,
+        // and have a variation index base after all the values.
+        if format % 2 == 1 && format != 1 && format != 11 && format != 13 {
+            let base: u32 = s.read()?;
+            self.apply_deltas(&mut paint, format, base);
+        }
+
+        Some(paint)
+    }
+
+    fn apply_deltas(&self, paint: &mut Paint<'a>, format: u8, base: u32) {
+        let fword = |i| self.variations.delta(base, i);
+        let f2dot14 = |i| self.variations.delta(base, i) / 16384.0;
+        let angle = |i| self.variations.delta(base, i) / 16384.0 * 180.0;
+
+        match paint {
+            Paint::Solid { alpha, .. } => {
+                *alpha += f2dot14(0);
+            }
+            Paint::LinearGradient(g) => {
+                g.x0 += fword(0);
+                g.y0 += fword(1);
+                g.x1 += fword(2);
+                g.y1 += fword(3);
+                g.x2 += fword(4);
+                g.y2 += fword(5);
+            }
+            Paint::RadialGradient(g) => {
+                g.x0 += fword(0);
+                g.y0 += fword(1);
+                g.r0 += fword(2);
+                g.x1 += fword(3);
+                g.y1 += fword(4);
+                g.r1 += fword(5);
+            }
+            Paint::SweepGradient(g) => {
+                g.center_x += fword(0);
+                g.center_y += fword(1);
+                g.start_angle += angle(2);
+                g.end_angle += angle(3);
+            }
+            Paint::Translate { dx, dy, .. } => {
+                *dx += fword(0);
+                *dy += fword(1);
+            }
+            Paint::Scale { scale_x, scale_y, center_x, center_y, .. } => {
+                // Uniform scale has a single value.
+                let center_index = if format >= 20 {
+                    let d = f2dot14(0);
+                    *scale_x += d;
+                    *scale_y += d;
+                    1
+                } else {
+                    *scale_x += f2dot14(0);
+                    *scale_y += f2dot14(1);
+                    2
+                };
+
+                *center_x += fword(center_index);
+                *center_y += fword(center_index + 1);
+            }
+            Paint::Rotate { angle: a, center_x, center_y, .. } => {
+                *a += angle(0);
+                *center_x += fword(1);
+                *center_y += fword(2);
+            }
+            Paint::Skew { x_skew_angle, y_skew_angle, center_x, center_y, .. } => {
+                *x_skew_angle += angle(0);
+                *y_skew_angle += angle(1);
+                *center_x += fword(2);
+                *center_y += fword(3);
+            }
+            _ => {}
+        }
+    }
+
+    fn read_color_line(
+        &self,
+        s: &mut Stream,
+        paint_offset: usize,
+        is_variable: bool,
+    ) -> Option<ColorLine<'a>> {
+        let offset: Offset24 = s.read()?;
+        let data = self.data.get(paint_offset.checked_add(offset.to_usize())?..)?;
+        ColorLine::parse(data, is_variable, self.variations)
+    }
+
+    /// Traverses a paint graph of a `COLR` version 1 glyph in depth-first order.
+    ///
+    /// Children are visited in painting order: `ColrLayers` from bottom to top,
+    /// `Composite` backdrop first and then source. `ColrGlyph` is followed into
+    /// the referenced glyph's root paint.
+    ///
+    /// Paints that reference one of their ancestors, malformed paints
+    /// and paints nested deeper than 64 levels are skipped.
+    ///
+    /// The traversal is aborted after 10000 visited paints.
+    ///
+    /// Returns `None` when the glyph has no `COLR` version 1 paint
+    /// or when the traversal was aborted.
+    pub fn traverse(&self, glyph_id: GlyphId, visitor: &mut dyn PaintVisitor<'a>) -> Option<()> {
+        let root = self.base_glyph_paint(glyph_id)?;
+        let mut path = Path::new();
+        self.traverse_impl(root, &mut path, visitor);
+        if path.is_exhausted { None } else { Some(()) }
+    }
+
+    fn traverse_impl(
+        &self,
+        paint_ref: PaintRef,
+        path: &mut Path,
+        visitor: &mut dyn PaintVisitor<'a>,
+    ) {
+        if !path.push(paint_ref) {
+            return;
+        }
+
+        if let Some(paint) = self.paint(paint_ref) {
+            visitor.enter(&paint);
+
+            match paint {
+                Paint::ColrLayers { first_layer_index, layers_count } => {
+                    for i in 0..u32::from(layers_count) {
+                        let child = first_layer_index.checked_add(i)
+                            .and_then(|index| self.layer_paint(index));
+                        if let Some(child) = child {
+                            self.traverse_impl(child, path, visitor);
+                        }
+                    }
+                }
+                Paint::ColrGlyph { glyph_id } => {
+                    if let Some(child) = self.base_glyph_paint(glyph_id) {
+                        self.traverse_impl(child, path, visitor);
+                    }
+                }
+                Paint::Glyph { paint, .. } |
+                Paint::Transform { paint, .. } |
+                Paint::Translate { paint, .. } |
+                Paint::Scale { paint, .. } |
+                Paint::Rotate { paint, .. } |
+                Paint::Skew { paint, .. } => {
+                    self.traverse_impl(paint, path, visitor);
+                }
+                Paint::Composite { source, backdrop, .. } => {
+                    self.traverse_impl(backdrop, path, visitor);
+                    self.traverse_impl(source, path, visitor);
+                }
+                Paint::Solid { .. } |
+                Paint::LinearGradient(_) |
+                Paint::RadialGradient(_) |
+                Paint::SweepGradient(_) => {}
+            }
+
+            visitor.leave(&paint);
+        }
+
+        path.len -= 1;
+    }
+
+    /// Paints a color glyph.
+    ///
+    /// `COLR` version 1 paints have priority over version 0 layers.
+    /// Version 0 layers are painted as glyph clips filled with solid colors.
+    ///
+    /// Root paints and `ColrGlyph` paints are clipped with glyph's clip box, when present.
+    /// Paints that reference one of their ancestors, malformed paints
+    /// and paints nested deeper than 64 levels are skipped.
+    ///
+    /// Painting is aborted after 10000 visited paints. In this case,
+    /// the painter has already received a part of the commands.
+    ///
+    /// Returns `None` when the glyph is not a color glyph or when painting was aborted.
+    pub fn paint_glyph(
+        &self,
+        glyph_id: GlyphId,
+        palette: Palette<'a>,
+        painter: &mut dyn Painter<'a>,
+    ) -> Option<()> {
+        if let Some(root) = self.base_glyph_paint(glyph_id) {
+            let mut path = Path::new();
+            self.paint_colr_glyph(glyph_id, root, palette, &mut path, painter);
+            return if path.is_exhausted { None } else { Some(()) };
+        }
+
+        for (layer_glyph_id, palette_index) in self.glyph_layers(glyph_id)? {
+            painter.outline_glyph(layer_glyph_id);
+            painter.push_clip();
+            painter.paint(Fill::Solid(palette.color(palette_index, 1.0)));
+            painter.pop_clip();
+        }
+
+        Some(())
+    }
+
+    fn paint_colr_glyph(
+        &self,
+        glyph_id: GlyphId,
+        root: PaintRef,
+        palette: Palette<'a>,
+        path: &mut Path,
+        painter: &mut dyn Painter<'a>,
+    ) {
+        match self.clip_box(glyph_id) {
+            Some(clip_box) => {
+                painter.push_clip_box(clip_box);
+                self.paint_impl(root, palette, path, painter);
+                painter.pop_clip();
+            }
+            None => self.paint_impl(root, palette, path, painter),
+        }
+    }
+
+    fn paint_impl(
+        &self,
+        paint_ref: PaintRef,
+        palette: Palette<'a>,
+        path: &mut Path,
+        painter: &mut dyn Painter<'a>,
+    ) {
+        if !path.push(paint_ref) {
+            return;
+        }
+
+        if let Some(paint) = self.paint(paint_ref) {
+            match paint {
+                Paint::ColrLayers { first_layer_index, layers_count } => {
+                    for i in 0..u32::from(layers_count) {
+                        let child = first_layer_index.checked_add(i)
+                            .and_then(|index| self.layer_paint(index));
+                        if let Some(child) = child {
+                            self.paint_impl(child, palette, path, painter);
+                        }
+                    }
+                }
+                Paint::Solid { palette_index, alpha } => {
+                    painter.paint(Fill::Solid(palette.color(palette_index, alpha)));
+                }
+                Paint::LinearGradient(gradient) => {
+                    painter.paint(Fill::LinearGradient(gradient, palette));
+                }
+                Paint::RadialGradient(gradient) => {
+                    painter.paint(Fill::RadialGradient(gradient, palette));
+                }
+                Paint::SweepGradient(gradient) => {
+                    painter.paint(Fill::SweepGradient(gradient, palette));
+                }
+                Paint::Glyph { glyph_id, paint } => {
+                    painter.outline_glyph(glyph_id);
+                    painter.push_clip();
+                    self.paint_impl(paint, palette, path, painter);
+                    painter.pop_clip();
+                }
+                Paint::ColrGlyph { glyph_id } => {
+                    if let Some(child) = self.base_glyph_paint(glyph_id) {
+                        self.paint_colr_glyph(glyph_id, child, palette, path, painter);
+                    }
+                }
+                Paint::Transform { transform, paint } => {
+                    painter.push_transform(transform);
+                    self.paint_impl(paint, palette, path, painter);
+                    painter.pop_transform();
+                }
+                Paint::Translate { dx, dy, paint } => {
+                    painter.push_transform(Transform { xx: 1.0, yx: 0.0, xy: 0.0, yy: 1.0, dx, dy });
+                    self.paint_impl(paint, palette, path, painter);
+                    painter.pop_transform();
+                }
+                Paint::Scale { scale_x, scale_y, center_x, center_y, paint } => {
+                    // Scaling around the center is a scale followed by a translation.
+                    painter.push_transform(Transform {
+                        xx: scale_x, yx: 0.0,
+                        xy: 0.0, yy: scale_y,
+                        dx: center_x - scale_x * center_x,
+                        dy: center_y - scale_y * center_y,
+                    });
+                    self.paint_impl(paint, palette, path, painter);
+                    painter.pop_transform();
+                }
+                Paint::Rotate { angle, center_x, center_y, paint } => {
+                    painter.push_rotate(angle, center_x, center_y);
+                    self.paint_impl(paint, palette, path, painter);
+                    painter.pop_transform();
+                }
+                Paint::Skew { x_skew_angle, y_skew_angle, center_x, center_y, paint } => {
+                    painter.push_skew(x_skew_angle, y_skew_angle, center_x, center_y);
+                    self.paint_impl(paint, palette, path, painter);
+                    painter.pop_transform();
+                }
+                Paint::Composite { source, mode, backdrop } => {
+                    painter.push_layer(CompositeMode::SourceOver);
+                    self.paint_impl(backdrop, palette, path, painter);
+                    painter.push_layer(mode);
+                    self.paint_impl(source, palette, path, painter);
+                    painter.pop_layer();
+                    painter.pop_layer();
+                }
+            }
+        }
+
+        path.len -= 1;
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
+}
+
+
+/// Variation data used to resolve variable paints.
+#[cfg(feature = "variable-fonts")]
+#[derive(Clone, Copy, Default)]
+struct Variations<'a> {
+    index_map: Option<DeltaSetIndexMap<'a>>,
+    store: Option<ItemVariationStore<'a>>,
+    coordinates: VarCoords,
+}
+
+#[cfg(feature = "variable-fonts")]
+impl Variations<'_> {
+    /// Returns a delta for a value at `index` relative to the variation index base.
+    fn delta(&self, base: u32, index: u32) -> f32 {
+        self.delta_impl(base, index).unwrap_or(0.0)
+    }
+
+    fn delta_impl(&self, base: u32, index: u32) -> Option<f32> {
+        // 0xFFFFFFFF indicates that the value is not variable.
+        if base == 0xFFFF_FFFF || self.coordinates.as_slice().iter().all(|c| c.get() == 0) {
+            return None;
+        }
+
+        let index = base.checked_add(index)?;
+        let (outer, inner) = match self.index_map {
+            Some(map) => map.map_index(index)?,
+            // Without a mapping, an index is an outer index in the high word
+            // and an inner index in the low word.
+            None => ((index >> 16) as u16, (index & 0xFFFF) as u16),
+        };
+
+        self.store?.parse_delta(outer, inner, self.coordinates.as_slice())
+    }
+}
+
+#[cfg(not(feature = "variable-fonts"))]
+#[derive(Clone, Copy, Default)]
+struct Variations<'a>(core::marker::PhantomData<&'a ()>);
+
+#[cfg(not(feature = "variable-fonts"))]
+impl Variations<'_> {
+    #[inline]
+    fn delta(&self, _: u32, _: u32) -> f32 {
+        0.0
+    }
+}
+
+
+/// A list of paints from the root to the current one.
+///
+/// Also counts visited paints for the whole traversal.
+struct Path {
+    refs: [PaintRef; MAX_NESTING_DEPTH],
+    len: usize,
+    visits_left: u16,
+    is_exhausted: bool,
+}
+
+impl Path {
+    fn new() -> Self {
+        Path {
+            refs: [PaintRef(0); MAX_NESTING_DEPTH],
+            len: 0,
+            visits_left: MAX_VISITS,
+            is_exhausted: false,
+        }
+    }
+
+    /// Returns `false` when the paint is already in the path, when the path is full
+    /// or when the visits limit is reached.
+    fn push(&mut self, paint_ref: PaintRef) -> bool {
+        if self.visits_left == 0 {
+            self.is_exhausted = true;
+            return false;
+        }
+
+        if self.len == MAX_NESTING_DEPTH || self.refs[..self.len].contains(&paint_ref) {
+            return false;
+        }
+
+        self.visits_left -= 1;
+        self.refs[self.len] = paint_ref;
+        self.len += 1;
+        true
+    }
+}
+
+
+#[inline]
+fn read_fword(s: &mut Stream) -> Option<f32> {
+    s.read::<i16>().map(f32::from)
+}
+
+/// Reads an angle in degrees. Stored as a multiple of 180 degrees.
+#[inline]
+fn read_angle(s: &mut Stream) -> Option<f32> {
+    s.read::<F2DOT14>().map(|n| n.to_f32() * 180.0)
+}
+
+/// Reads a child paint offset, which is relative to the parent paint.
+#[inline]
+fn read_child(s: &mut Stream, parent_offset: usize) -> Option<PaintRef> {
+    PaintRef::new(parent_offset, s.read::<Offset24>()?)
+}
+
+/// Reads a center point of scale, rotate and skew paints.
+///
+/// Only formats 18, 22, 26 and 30 and their variable counterparts have one.
+/// Other formats use the origin.
+#[inline]
+fn read_center(s: &mut Stream, format: u8) -> Option<(f32, f32)> {
+    if (format / 2) % 2 == 1 {
+        Some((read_fword(s)?, read_fword(s)?))
+    } else {
+        Some((0.0, 0.0))
+    }
+}
+
+
+/// A reference to a [Paint table](https://docs.microsoft.com/en-us/typography/opentype/spec/colr#paint-tables).
+///
+/// Can be resolved using `Table::paint`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PaintRef(u32);
+
+impl PaintRef {
+    #[inline]
+    fn new(base: usize, offset: impl Offset) -> Option<Self> {
+        let offset = base.checked_add(offset.to_usize())?;
+        u32::try_from(offset).ok().map(PaintRef)
+    }
+
+    #[inline]
+    fn to_usize(self) -> usize {
+        self.0 as usize
+    }
+}
+
+
+/// An affine transformation matrix.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Transform {
+    pub xx: f32, pub yx: f32,
+    pub xy: f32, pub yy: f32,
+    pub dx: f32, pub dy: f32,
+}
+
+
+/// A [composite mode](https://docs.microsoft.com/en-us/typography/opentype/spec/colr#format-32-paintcomposite).
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CompositeMode {
+    Clear,
+    Source,
+    Destination,
+    SourceOver,
+    DestinationOver,
+    SourceIn,
+    DestinationIn,
+    SourceOut,
+    DestinationOut,
+    SourceAtop,
+    DestinationAtop,
+    Xor,
+    Plus,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Multiply,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+impl CompositeMode {
+    fn parse(n: u8) -> Option<Self> {
+        match n {
+            0 => Some(CompositeMode::Clear),
+            1 => Some(CompositeMode::Source),
+            2 => Some(CompositeMode::Destination),
+            3 => Some(CompositeMode::SourceOver),
+            4 => Some(CompositeMode::DestinationOver),
+            5 => Some(CompositeMode::SourceIn),
+            6 => Some(CompositeMode::DestinationIn),
+            7 => Some(CompositeMode::SourceOut),
+            8 => Some(CompositeMode::DestinationOut),
+            9 => Some(CompositeMode::SourceAtop),
+            10 => Some(CompositeMode::DestinationAtop),
+            11 => Some(CompositeMode::Xor),
+            12 => Some(CompositeMode::Plus),
+            13 => Some(CompositeMode::Screen),
+            14 => Some(CompositeMode::Overlay),
+            15 => Some(CompositeMode::Darken),
+            16 => Some(CompositeMode::Lighten),
+            17 => Some(CompositeMode::ColorDodge),
+            18 => Some(CompositeMode::ColorBurn),
+            19 => Some(CompositeMode::HardLight),
+            20 => Some(CompositeMode::SoftLight),
+            21 => Some(CompositeMode::Difference),
+            22 => Some(CompositeMode::Exclusion),
+            23 => Some(CompositeMode::Multiply),
+            24 => Some(CompositeMode::Hue),
+            25 => Some(CompositeMode::Saturation),
+            26 => Some(CompositeMode::Color),
+            27 => Some(CompositeMode::Luminosity),
+            _ => None,
+        }
+    }
+}
+
+
+/// A [color line extend mode](https://docs.microsoft.com/en-us/typography/opentype/spec/colr#color-references-colorstop-and-colorline).
+///
+/// Defines how a gradient is painted outside of the `0..1` stops range.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Extend {
+    /// Uses the nearest stop color.
+    Pad,
+    /// Repeats the color line.
+    Repeat,
+    /// Repeats the color line, reversing every second repetition.
+    Reflect,
+}
+
+
+/// A [color stop](https://docs.microsoft.com/en-us/typography/opentype/spec/colr#color-references-colorstop-and-colorline).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ColorStop {
+    /// A position on the color line.
+    ///
+    /// Usually in the `0..1` range, but can be outside of it.
+    pub offset: f32,
+    /// A palette entry index. The index `0xFFFF` means the text foreground color.
+    pub palette_index: u16,
+    /// An alpha multiplier in the `0..1` range.
+    pub alpha: f32,
+}
+
+
+/// A [color line](https://docs.microsoft.com/en-us/typography/opentype/spec/colr#color-references-colorstop-and-colorline).
+#[derive(Clone, Copy)]
+pub struct ColorLine<'a> {
+    extend: Extend,
+    stops: &'a [u8],
+    stop_size: usize,
+    variations: Variations<'a>,
+}
+
+impl<'a> ColorLine<'a> {
+    fn parse(data: &'a [u8], is_variable: bool, variations: Variations<'a>) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let extend = match s.read::<u8>()? {
+            1 => Extend::Repeat,
+            2 => Extend::Reflect,
+            // Unknown modes must be treated as `Pad`.
+            _ => Extend::Pad,
+        };
+
+        // A variable stop has an additional 32-bit variation index.
+        let stop_size = if is_variable { 10 } else { 6 };
+        let count: u16 = s.read()?;
+        let stops = s.read_bytes(usize::from(count) * stop_size)?;
+        Some(ColorLine { extend, stops, stop_size, variations })
+    }
+
+    /// Returns an extend mode.
+    #[inline]
+    pub fn extend(&self) -> Extend {
+        self.extend
+    }
+
+    /// Returns the number of color stops.
+    #[inline]
+    pub fn len(&self) -> u16 {
+        (self.stops.len() / self.stop_size) as u16
+    }
+
+    /// Checks that there are no color stops.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.stops.is_empty()
+    }
+
+    /// Returns a color stop at the specified index.
+    ///
+    /// Stops are stored in the font order, which is not necessarily sorted by offset.
+    pub fn get(&self, index: u16) -> Option<ColorStop> {
+        let mut s = Stream::new_at(self.stops, usize::from(index) * self.stop_size)?;
+        let mut stop = ColorStop {
+            offset: s.read::<F2DOT14>()?.to_f32(),
+            palette_index: s.read()?,
+            alpha: s.read::<F2DOT14>()?.to_f32(),
+        };
+
+        if self.stop_size == 10 {
+            let base: u32 = s.read()?;
+            stop.offset += self.variations.delta(base, 0) / 16384.0;
+            stop.alpha += self.variations.delta(base, 1) / 16384.0;
+        }
+
+        Some(stop)
+    }
+
+    /// Returns an iterator over color stops.
+    #[inline]
+    pub fn stops(&self) -> impl Iterator<Item = ColorStop> + 'a {
+        let line = *self;
+        (0..self.len()).filter_map(move |i| line.get(i))
+    }
+}
+
+impl PartialEq for ColorLine<'_> {
+    /// Color lines are equal when they have the same extend mode and reference the same stops.
+    fn eq(&self, other: &Self) -> bool {
+        self.extend == other.extend
+            && self.stop_size == other.stop_size
+            && core::ptr::eq(self.stops, other.stops)
+    }
+}
+
+impl core::fmt::Debug for ColorLine<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("ColorLine")
+            .field("extend", &self.extend)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+
+/// A [linear gradient](https://docs.microsoft.com/en-us/typography/opentype/spec/colr#linear-gradients).
+///
+/// Points 0 and 1 define the gradient direction, while point 2 defines its rotation.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LinearGradient<'a> {
+    pub x0: f32, pub y0: f32,
+    pub x1: f32, pub y1: f32,
+    pub x2: f32, pub y2: f32,
+    pub color_line: ColorLine<'a>,
+}
+
+
+/// A [radial gradient](https://docs.microsoft.com/en-us/typography/opentype/spec/colr#radial-gradients).
+///
+/// Defined by two circles: the start one and the end one.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RadialGradient<'a> {
+    pub x0: f32, pub y0: f32, pub r0: f32,
+    pub x1: f32, pub y1: f32, pub r1: f32,
+    pub color_line: ColorLine<'a>,
+}
+
+
+/// A [sweep gradient](https://docs.microsoft.com/en-us/typography/opentype/spec/colr#sweep-gradients).
+///
+/// Angles are in counter-clockwise degrees.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SweepGradient<'a> {
+    pub center_x: f32,
+    pub center_y: f32,
+    pub start_angle: f32,
+    pub end_angle: f32,
+    pub color_line: ColorLine<'a>,
+}
+
+
+/// A [Paint table](https://docs.microsoft.com/en-us/typography/opentype/spec/colr#paint-tables).
+///
+/// Variable formats are mapped to the same variants as static ones.
+/// Angles are in counter-clockwise degrees.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Paint<'a> {
+    /// Paints a slice of the layer list, from bottom to top.
+    ColrLayers {
+        first_layer_index: u32,
+        layers_count: u8,
+    },
+    /// Fills with a palette color.
+    ///
+    /// The palette index `0xFFFF` means the text foreground color.
+    Solid {
+        palette_index: u16,
+        alpha: f32,
+    },
+    /// Fills with a linear gradient.
+    LinearGradient(LinearGradient<'a>),
+    /// Fills with a radial gradient.
+    RadialGradient(RadialGradient<'a>),
+    /// Fills with a sweep gradient.
+    SweepGradient(SweepGradient<'a>),
+    /// Clips the child paint with a glyph outline.
+    Glyph {
+        glyph_id: GlyphId,
+        paint: PaintRef,
+    },
+    /// Paints another `COLR` version 1 glyph.
+    ColrGlyph {
+        glyph_id: GlyphId,
+    },
+    /// Transforms the child paint with an affine matrix.
+    Transform {
+        transform: Transform,
+        paint: PaintRef,
+    },
+    /// Translates the child paint.
+    Translate {
+        dx: f32,
+        dy: f32,
+        paint: PaintRef,
+    },
+    /// Scales the child paint around the center.
+    Scale {
+        scale_x: f32,
+        scale_y: f32,
+        center_x: f32,
+        center_y: f32,
+        paint: PaintRef,
+    },
+    /// Rotates the child paint around the center.
+    Rotate {
+        angle: f32,
+        center_x: f32,
+        center_y: f32,
+        paint: PaintRef,
+    },
+    /// Skews the child paint around the center.
+    Skew {
+        x_skew_angle: f32,
+        y_skew_angle: f32,
+        center_x: f32,
+        center_y: f32,
+        paint: PaintRef,
+    },
+    /// Composes the source paint over the backdrop paint.
+    Composite {
+        source: PaintRef,
+        mode: CompositeMode,
+        backdrop: PaintRef,
+    },
+}
+
+
+/// A paint graph visitor.
+///
+/// See `Table::traverse` for details.
+pub trait PaintVisitor<'a> {
+    /// Called when entering a paint, before its children.
+    fn enter(&mut self, paint: &Paint<'a>);
+
+    /// Called when leaving a paint, after its children.
+    fn leave(&mut self, paint: &Paint<'a>);
+}
+
+
+/// A color palette used to resolve paint colors.
+#[derive(Clone, Copy, Debug)]
+pub struct Palette<'a> {
+    table: Option<cpal::Table<'a>>,
+    index: u16,
+    foreground: Color,
+}
+
+impl<'a> Palette<'a> {
+    /// Creates a new palette from a `CPAL` table, a palette index and the text foreground color.
+    #[inline]
+    pub fn new(table: Option<cpal::Table<'a>>, index: u16, foreground: Color) -> Self {
+        Palette { table, index, foreground }
+    }
+
+    /// Resolves a palette entry color and multiplies its alpha.
+    ///
+    /// The index `0xFFFF` and unknown entries resolve to the text foreground color.
+    pub fn color(&self, palette_index: u16, alpha: f32) -> Color {
+        let mut color = self.table
+            .and_then(|table| table.color(self.index, palette_index))
+            .unwrap_or(self.foreground);
+
+        let alpha = f32_bound(0.0, alpha, 1.0);
+        color.alpha = (f32::from(color.alpha) * alpha + 0.5) as u8;
+        color
+    }
+}
+
+
+/// A resolved fill.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug)]
+pub enum Fill<'a> {
+    Solid(Color),
+    /// Color stops can be resolved using the palette.
+    LinearGradient(LinearGradient<'a>, Palette<'a>),
+    /// Color stops can be resolved using the palette.
+    RadialGradient(RadialGradient<'a>, Palette<'a>),
+    /// Color stops can be resolved using the palette.
+    SweepGradient(SweepGradient<'a>, Palette<'a>),
+}
+
+
+/// A color glyph painter.
+///
+/// Each `push_*` call is matched by the corresponding `pop_*` call.
+///
+/// See `Table::paint_glyph` and `Face::paint_color_glyph` for details.
+pub trait Painter<'a> {
+    /// Outlines a glyph, which will be used by the next `push_clip` call.
+    ///
+    /// Usually implemented via `Face::outline_glyph`.
+    fn outline_glyph(&mut self, glyph_id: GlyphId);
+
+    /// Fills the current clip.
+    fn paint(&mut self, fill: Fill<'a>);
+
+    /// Pushes a clip with the last outlined glyph.
+    fn push_clip(&mut self);
+
+    /// Pushes a clip with a rectangle.
+    fn push_clip_box(&mut self, clip_box: ClipBox);
+
+    /// Pops the last clip.
+    fn pop_clip(&mut self);
+
+    /// Pushes a new layer, which will be composed using the specified mode when popped.
+    fn push_layer(&mut self, mode: CompositeMode);
+
+    /// Pops and composes the last layer.
+    fn pop_layer(&mut self);
+
+    /// Pushes an affine transformation.
+    fn push_transform(&mut self, transform: Transform);
+
+    /// Pushes a counter-clockwise rotation in degrees around the center.
+    ///
+    /// Rotations are not converted into a matrix, since it requires
+    /// trigonometric functions, which are not available in `no_std`.
+    fn push_rotate(&mut self, angle: f32, center_x: f32, center_y: f32);
+
+    /// Pushes a counter-clockwise skew in degrees around the center.
+    ///
+    /// Just like rotations, skews are not converted into a matrix.
+    fn push_skew(&mut self, x_skew_angle: f32, y_skew_angle: f32, center_x: f32, center_y: f32);
+
+    /// Pops the last transformation, rotation or skew.
+    fn pop_transform(&mut self);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn layers() {
+        let data = writer::convert(&[
+            UInt16(0), // version
+            UInt16(2), // base glyphs count
+            UInt32(14), // base glyphs offset
+            UInt32(26), // layers offset
+            UInt16(3), // layers count
+            // Base Glyph Record [0]
+            UInt16(2), // glyph
+            UInt16(0), // first layer index
+            UInt16(1), // layers count
+            // Base Glyph Record [1]
+            UInt16(4), // glyph
+            UInt16(1), // first layer index
+            UInt16(2), // layers count
+            // Layer Record [0]
+            UInt16(10), // glyph
+            UInt16(1), // palette index
+            // Layer Record [1]
+            UInt16(11), // glyph
+            UInt16(0xFFFF), // palette index
+            // Layer Record [2]
+            UInt16(12), // glyph
+            UInt16(3), // palette index
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert!(table.glyph_layers(GlyphId(3)).is_none());
+
+        let mut layers = table.glyph_layers(GlyphId(2)).unwrap();
+        assert_eq!(layers.next(), Some((GlyphId(10), 1)));
+        assert!(layers.next().is_none());
+
+        let mut layers = table.glyph_layers(GlyphId(4)).unwrap();
+        assert_eq!(layers.next(), Some((GlyphId(11), 0xFFFF)));
+        assert_eq!(layers.next(), Some((GlyphId(12), 3)));
+        assert!(layers.next().is_none());
+    }
+
+    #[test]
+    fn out_of_bounds_layers() {
+        let data = writer::convert(&[
+            UInt16(0), // version
+            UInt16(1), // base glyphs count
+            UInt32(14), // base glyphs offset
+            UInt32(20), // layers offset
+            UInt16(1), // layers count
+            // Base Glyph Record [0]
+            UInt16(2), // glyph
+            UInt16(0), // first layer index
+            UInt16(2), // layers count
+            // Layer Record [0]
+            UInt16(10), // glyph
+            UInt16(1), // palette index
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert!(table.glyph_layers(GlyphId(2)).is_none());
+    }
+
+    struct Recorder<'a>(std::vec::Vec<(bool, Paint<'a>)>);
+
+    impl<'a> PaintVisitor<'a> for Recorder<'a> {
+        fn enter(&mut self, paint: &Paint<'a>) {
+            self.0.push((true, *paint));
+        }
+
+        fn leave(&mut self, paint: &Paint<'a>) {
+            self.0.push((false, *paint));
+        }
+    }
+
+    #[test]
+    fn paint_graph() {
+        let data = writer::convert(&[
+            UInt16(1), // version
+            UInt16(0), // base glyphs count
+            UInt32(0), // base glyphs offset
+            UInt32(0), // layers offset
+            UInt16(0), // layers count
+            UInt32(34), // base glyph list offset
+            UInt32(50), // layer list offset
+            UInt32(0), // clip list offset
+            UInt32(0), // variation index map offset
+            UInt32(0), // item variation store offset
+            // Base Glyph List
+            UInt32(2), // count
+            UInt16(1), // glyph [0]
+            UInt32(28), // paint offset [0]
+            UInt16(2), // glyph [1]
+            UInt32(48), // paint offset [1]
+            // Layer List
+            UInt32(2), // count
+            UInt32(18), // paint offset [0]
+            UInt32(29), // paint offset [1]
+            // Paint Colr Layers
+            UInt8(1), // format
+            UInt8(2), // layers count
+            UInt32(0), // first layer index
+            // Paint Glyph
+            UInt8(10), // format
+            UInt24(6), // paint offset
+            UInt16(5), // glyph
+            // Paint Solid
+            UInt8(2), // format
+            UInt16(3), // palette index
+            Int16(0x4000), // alpha
+            // Paint Colr Glyph
+            UInt8(11), // format
+            UInt16(2), // glyph
+            // Paint Rotate Around Center
+            UInt8(26), // format
+            UInt24(10), // paint offset
+            Int16(0x2000), // angle
+            Int16(10), // center x
+            Int16(20), // center y
+            // Paint Colr Glyph
+            UInt8(11), // format
+            UInt16(1), // glyph, which is a cycle
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert!(table.base_glyph_paint(GlyphId(3)).is_none());
+        assert!(table.glyph_layers(GlyphId(1)).is_none());
+
+        let mut recorder = Recorder(std::vec::Vec::new());
+        table.traverse(GlyphId(1), &mut recorder).unwrap();
+
+        let layers = Paint::ColrLayers { first_layer_index: 0, layers_count: 2 };
+        let solid = Paint::Solid { palette_index: 3, alpha: 1.0 };
+        let glyph = match recorder.0[1].1 {
+            paint @ Paint::Glyph { glyph_id: GlyphId(5), .. } => paint,
+            _ => panic!("must be a glyph paint"),
+        };
+        let colr_glyph2 = Paint::ColrGlyph { glyph_id: GlyphId(2) };
+        let rotate = match recorder.0[6].1 {
+            paint @ Paint::Rotate { angle, center_x, center_y, .. } => {
+                assert_eq!(angle, 90.0);
+                assert_eq!((center_x, center_y), (10.0, 20.0));
+                paint
+            }
+            _ => panic!("must be a rotate paint"),
+        };
+        let colr_glyph1 = Paint::ColrGlyph { glyph_id: GlyphId(1) };
+
+        assert_eq!(recorder.0, &[
+            (true, layers),
+            (true, glyph),
+            (true, solid),
+            (false, solid),
+            (false, glyph),
+            (true, colr_glyph2),
+            (true, rotate),
+            (true, colr_glyph1),
+            (false, colr_glyph1),
+            (false, rotate),
+            (false, colr_glyph2),
+            (false, layers),
+        ]);
+    }
+
+    #[test]
+    fn shared_paints() {
+        const COMPOSITES_COUNT: u32 = 20;
+
+        let mut data = std::vec![
+            UInt16(1), // version
+            UInt16(0), // base glyphs count
+            UInt32(0), // base glyphs offset
+            UInt32(0), // layers offset
+            UInt16(0), // layers count
+            UInt32(34), // base glyph list offset
+            UInt32(0), // layer list offset
+            UInt32(0), // clip list offset
+            UInt32(0), // variation index map offset
+            UInt32(0), // item variation store offset
+            // Base Glyph List
+            UInt32(1), // count
+            UInt16(1), // glyph [0]
+            UInt32(10), // paint offset [0]
+        ];
+
+        // Each composite uses the next paint as both source and backdrop,
+        // so the number of paths doubles on each level.
+        for _ in 0..COMPOSITES_COUNT {
+            data.extend_from_slice(&[
+                // Paint Composite
+                UInt8(32), // format
+                UInt24(8), // source paint offset
+                UInt8(3), // composite mode
+                UInt24(8), // backdrop paint offset
+            ]);
+        }
+
+        data.extend_from_slice(&[
+            // Paint Solid
+            UInt8(2), // format
+            UInt16(0), // palette index
+            Int16(0x4000), // alpha
+        ]);
+
+        let data = writer::convert(&data);
+        let table = Table::parse(&data).unwrap();
+
+        let mut recorder = Recorder(std::vec::Vec::new());
+        assert!(table.traverse(GlyphId(1), &mut recorder).is_none());
+        assert_eq!(recorder.0.len(), usize::from(MAX_VISITS) * 2);
+
+        let mut painter = PainterRecorder(std::vec::Vec::new());
+        let palette = Palette::new(None, 0, Color { red: 0, green: 0, blue: 0, alpha: 255 });
+        assert!(table.paint_glyph(GlyphId(1), palette, &mut painter).is_none());
+    }
+
+    #[test]
+    fn gradients() {
+        let data = writer::convert(&[
+            UInt16(1), // version
+            UInt16(0), // base glyphs count
+            UInt32(0), // base glyphs offset
+            UInt32(0), // layers offset
+            UInt16(0), // layers count
+            UInt32(34), // base glyph list offset
+            UInt32(0), // layer list offset
+            UInt32(0), // clip list offset
+            UInt32(0), // variation index map offset
+            UInt32(0), // item variation store offset
+            // Base Glyph List
+            UInt32(2), // count
+            UInt16(1), // glyph [0]
+            UInt32(16), // paint offset [0]
+            UInt16(2), // glyph [1]
+            UInt32(59), // paint offset [1]
+            // Paint Var Linear Gradient
+            UInt8(5), // format
+            UInt24(20), // color line offset
+            Int16(0), // x0
+            Int16(0), // y0
+            Int16(100), // x1
+            Int16(0), // y1
+            Int16(0), // x2
+            Int16(-100), // y2
+            UInt32(0), // variation index base
+            // Var Color Line
+            UInt8(2), // extend
+            UInt16(2), // count
+            Int16(0), // stop offset [0]
+            UInt16(1), // palette index [0]
+            Int16(0x4000), // alpha [0]
+            UInt32(0), // variation index base [0]
+            Int16(0x4000), // stop offset [1]
+            UInt16(0xFFFF), // palette index [1]
+            Int16(0x2000), // alpha [1]
+            UInt32(0), // variation index base [1]
+            // Paint Sweep Gradient
+            UInt8(8), // format
+            UInt24(12), // color line offset
+            Int16(50), // center x
+            Int16(60), // center y
+            Int16(0), // start angle
+            Int16(0x4000), // end angle
+            // Color Line
+            UInt8(7), // extend
+            UInt16(1), // count
+            Int16(0x2000), // stop offset [0]
+            UInt16(4), // palette index [0]
+            Int16(0x4000), // alpha [0]
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        let paint = table.paint(table.base_glyph_paint(GlyphId(1)).unwrap()).unwrap();
+        let gradient = match paint {
+            Paint::LinearGradient(gradient) => gradient,
+            _ => panic!("must be a linear gradient"),
+        };
+        assert_eq!((gradient.x1, gradient.y2), (100.0, -100.0));
+        assert_eq!(gradient.color_line.extend(), Extend::Reflect);
+        assert_eq!(gradient.color_line.len(), 2);
+        assert!(gradient.color_line.get(2).is_none());
+        let mut stops = gradient.color_line.stops();
+        assert_eq!(stops.next(), Some(ColorStop { offset: 0.0, palette_index: 1, alpha: 1.0 }));
+        assert_eq!(stops.next(), Some(ColorStop { offset: 1.0, palette_index: 0xFFFF, alpha: 0.5 }));
+        assert!(stops.next().is_none());
+
+        let paint = table.paint(table.base_glyph_paint(GlyphId(2)).unwrap()).unwrap();
+        let gradient = match paint {
+            Paint::SweepGradient(gradient) => gradient,
+            _ => panic!("must be a sweep gradient"),
+        };
+        assert_eq!((gradient.center_x, gradient.center_y), (50.0, 60.0));
+        assert_eq!((gradient.start_angle, gradient.end_angle), (0.0, 180.0));
+        assert_eq!(gradient.color_line.extend(), Extend::Pad);
+        assert_eq!(gradient.color_line.get(0).unwrap().offset, 0.5);
+    }
+
+    #[cfg(feature = "variable-fonts")]
+    #[test]
+    fn variable_paints() {
+        use crate::NormalizedCoordinate;
+
+        let data = writer::convert(&[
+            UInt16(1), // version
+            UInt16(0), // base glyphs count
+            UInt32(0), // base glyphs offset
+            UInt32(0), // layers offset
+            UInt16(0), // layers count
+            UInt32(34), // base glyph list offset
+            UInt32(0), // layer list offset
+            UInt32(0), // clip list offset
+            UInt32(0), // variation index map offset
+            UInt32(92), // item variation store offset
+            // Base Glyph List
+            UInt32(2), // count
+            UInt16(1), // glyph [0]
+            UInt32(16), // paint offset [0]
+            UInt16(2), // glyph [1]
+            UInt32(25), // paint offset [1]
+            // Paint Var Solid
+            UInt8(3), // format
+            UInt16(2), // palette index
+            Int16(0x2000), // alpha
+            UInt32(0), // variation index base
+            // Paint Var Linear Gradient
+            UInt8(5), // format
+            UInt24(20), // color line offset
+            Int16(0), // x0
+            Int16(0), // y0
+            Int16(100), // x1
+            Int16(0), // y1
+            Int16(0), // x2
+            Int16(-100), // y2
+            UInt32(0xFFFF_FFFF), // variation index base
+            // Var Color Line
+            UInt8(0), // extend
+            UInt16(1), // count
+            Int16(0), // stop offset [0]
+            UInt16(1), // palette index [0]
+            Int16(0x4000), // alpha [0]
+            UInt32(1), // variation index base [0]
+            // Item Variation Store
+            UInt16(1), // format
+            UInt32(12), // region list offset
+            UInt16(1), // data count
+            UInt32(22), // data offset [0]
+            // Region List
+            UInt16(1), // axis count
+            UInt16(1), // region count
+            Int16(0), // start coordinate
+            Int16(0x4000), // peak coordinate
+            Int16(0x4000), // end coordinate
+            // Item Variation Data
+            UInt16(3), // item count
+            UInt16(1), // short delta count
+            UInt16(1), // region index count
+            UInt16(0), // region index [0]
+            Int16(0x1000), // delta [0]
+            Int16(0x2000), // delta [1]
+            Int16(-0x1000), // delta [2]
+        ]);
+
+        let mut table = Table::parse(&data).unwrap();
+        let solid = table.base_glyph_paint(GlyphId(1)).unwrap();
+        let gradient = table.base_glyph_paint(GlyphId(2)).unwrap();
+        assert_eq!(table.paint(solid), Some(Paint::Solid { palette_index: 2, alpha: 0.5 }));
+
+        table.set_coordinates(&[NormalizedCoordinate::from(1.0)]);
+        assert_eq!(table.paint(solid), Some(Paint::Solid { palette_index: 2, alpha: 0.75 }));
+
+        let gradient = match table.paint(gradient).unwrap() {
+            Paint::LinearGradient(gradient) => gradient,
+            _ => panic!("must be a linear gradient"),
+        };
+        assert_eq!((gradient.x1, gradient.y2), (100.0, -100.0));
+        let stop = gradient.color_line.get(0).unwrap();
+        assert_eq!((stop.offset, stop.alpha), (0.5, 0.75));
+    }
+
+    #[test]
+    fn clip_boxes() {
+        let data = writer::convert(&[
+            UInt16(1), // version
+            UInt16(0), // base glyphs count
+            UInt32(0), // base glyphs offset
+            UInt32(0), // layers offset
+            UInt16(0), // layers count
+            UInt32(0), // base glyph list offset
+            UInt32(0), // layer list offset
+            UInt32(34), // clip list offset
+            UInt32(0), // variation index map offset
+            UInt32(0), // item variation store offset
+            // Clip List
+            UInt8(1), // format
+            UInt32(2), // count
+            UInt16(2), // start glyph [0]
+            UInt16(4), // end glyph [0]
+            UInt24(19), // clip box offset [0]
+            UInt16(7), // start glyph [1]
+            UInt16(7), // end glyph [1]
+            UInt24(28), // clip box offset [1]
+            // Clip Box
+            UInt8(1), // format
+            Int16(-10), // x min
+            Int16(-20), // y min
+            Int16(500), // x max
+            Int16(600), // y max
+            // Var Clip Box
+            UInt8(2), // format
+            Int16(0), // x min
+            Int16(0), // y min
+            Int16(100), // x max
+            Int16(100), // y max
+            UInt32(0), // variation index base
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert!(table.clip_box(GlyphId(1)).is_none());
+        assert!(table.clip_box(GlyphId(5)).is_none());
+
+        let clip_box = ClipBox { x_min: -10.0, y_min: -20.0, x_max: 500.0, y_max: 600.0 };
+        assert_eq!(table.clip_box(GlyphId(2)), Some(clip_box));
+        assert_eq!(table.clip_box(GlyphId(4)), Some(clip_box));
+
+        // No variation store, so defaults are used.
+        let clip_box = ClipBox { x_min: 0.0, y_min: 0.0, x_max: 100.0, y_max: 100.0 };
+        assert_eq!(table.clip_box(GlyphId(7)), Some(clip_box));
+    }
+
+    struct PainterRecorder(std::vec::Vec<std::string::String>);
+
+    impl<'a> Painter<'a> for PainterRecorder {
+        fn outline_glyph(&mut self, glyph_id: GlyphId) {
+            self.0.push(std::format!("outline {}", glyph_id.0));
+        }
+
+        fn paint(&mut self, fill: Fill<'a>) {
+            match fill {
+                Fill::Solid(c) => {
+                    self.0.push(std::format!("solid {} {} {} {}", c.red, c.green, c.blue, c.alpha));
+                }
+                _ => self.0.push("gradient".into()),
+            }
+        }
+
+        fn push_clip(&mut self) {
+            self.0.push("push clip".into());
+        }
+
+        fn push_clip_box(&mut self, clip_box: ClipBox) {
+            self.0.push(std::format!("push clip box {} {}", clip_box.x_min, clip_box.x_max));
+        }
+
+        fn pop_clip(&mut self) {
+            self.0.push("pop clip".into());
+        }
+
+        fn push_layer(&mut self, mode: CompositeMode) {
+            self.0.push(std::format!("push layer {:?}", mode));
+        }
+
+        fn pop_layer(&mut self) {
+            self.0.push("pop layer".into());
+        }
+
+        fn push_transform(&mut self, ts: Transform) {
+            self.0.push(std::format!("push transform {} {} {} {}", ts.xx, ts.yy, ts.dx, ts.dy));
+        }
+
+        fn push_rotate(&mut self, angle: f32, _: f32, _: f32) {
+            self.0.push(std::format!("push rotate {}", angle));
+        }
+
+        fn push_skew(&mut self, x_skew_angle: f32, y_skew_angle: f32, _: f32, _: f32) {
+            self.0.push(std::format!("push skew {} {}", x_skew_angle, y_skew_angle));
+        }
+
+        fn pop_transform(&mut self) {
+            self.0.push("pop transform".into());
+        }
+    }
+
+    #[test]
+    fn paint_glyph() {
+        let data = writer::convert(&[
+            UInt16(1), // version
+            UInt16(1), // base glyphs count
+            UInt32(34), // base glyphs offset
+            UInt32(40), // layers offset
+            UInt16(1), // layers count
+            UInt32(44), // base glyph list offset
+            UInt32(0), // layer list offset
+            UInt32(86), // clip list offset
+            UInt32(0), // variation index map offset
+            UInt32(0), // item variation store offset
+            // Base Glyph Record
+            UInt16(2), // glyph
+            UInt16(0), // first layer index
+            UInt16(1), // layers count
+            // Layer Record
+            UInt16(7), // glyph
+            UInt16(1), // palette index
+            // Base Glyph List
+            UInt32(1), // count
+            UInt16(1), // glyph [0]
+            UInt32(10), // paint offset [0]
+            // Paint Composite
+            UInt8(32), // format
+            UInt24(8), // source paint offset
+            UInt8(23), // composite mode
+            UInt24(13), // backdrop paint offset
+            // Paint Solid
+            UInt8(2), // format
+            UInt16(0), // palette index
+            Int16(0x2000), // alpha
+            // Paint Scale
+            UInt8(16), // format
+            UInt24(8), // paint offset
+            Int16(-0x8000), // scale x
+            Int16(0x4000), // scale y
+            // Paint Glyph
+            UInt8(10), // format
+            UInt24(6), // paint offset
+            UInt16(5), // glyph
+            // Paint Solid
+            UInt8(2), // format
+            UInt16(0xFFFF), // palette index
+            Int16(0x4000), // alpha
+            // Clip List
+            UInt8(1), // format
+            UInt32(1), // count
+            UInt16(1), // start glyph
+            UInt16(1), // end glyph
+            UInt24(12), // clip box offset
+            // Clip Box
+            UInt8(1), // format
+            Int16(0), // x min
+            Int16(0), // y min
+            Int16(100), // x max
+            Int16(100), // y max
+        ]);
+
+        let cpal_data = writer::convert(&[
+            UInt16(0), // version
+            UInt16(2), // entries count
+            UInt16(1), // palettes count
+            UInt16(2), // colors count
+            UInt32(14), // colors offset
+            UInt16(0), // first color index
+            // Colors
+            UInt8(0), UInt8(0), UInt8(255), UInt8(255), // color [0]
+            UInt8(255), UInt8(0), UInt8(0), UInt8(255), // color [1]
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        let cpal_table = cpal::Table::parse(&cpal_data);
+        let foreground = Color { red: 0, green: 255, blue: 0, alpha: 255 };
+        let palette = Palette::new(cpal_table, 0, foreground);
+
+        let mut recorder = PainterRecorder(std::vec::Vec::new());
+        assert!(table.paint_glyph(GlyphId(3), palette, &mut recorder).is_none());
+        assert!(recorder.0.is_empty());
+
+        table.paint_glyph(GlyphId(1), palette, &mut recorder).unwrap();
+        assert_eq!(recorder.0, &[
+            "push clip box 0 100",
+            "push layer SourceOver",
+            "push transform -2 1 0 0",
+            "outline 5",
+            "push clip",
+            "solid 0 255 0 255",
+            "pop clip",
+            "pop transform",
+            "push layer Multiply",
+            "solid 255 0 0 128",
+            "pop layer",
+            "pop layer",
+            "pop clip",
+        ]);
+
+        let mut recorder = PainterRecorder(std::vec::Vec::new());
+        table.paint_glyph(GlyphId(2), palette, &mut recorder).unwrap();
+        assert_eq!(recorder.0, &[
+            "outline 7",
+            "push clip",
+            "solid 0 0 255 255",
+            "pop clip",
+        ]);
+    }
+}
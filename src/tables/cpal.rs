@@ -0,0 +1,245 @@
+/*!
+A [Color Palette table](https://docs.microsoft.com/en-us/typography/opentype/spec/cpal) implementation.
+*/
+
+use crate::parser::{Stream, FromData, LazyArray16, Offset, Offset32};
+
+
+/// An RGBA color.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    pub alpha: u8,
+}
+
+impl FromData for Color {
+    const SIZE: usize = 4;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        // Stored in the BGRA order.
+        let mut s = Stream::new(data);
+        let blue = s.read::<u8>()?;
+        let green = s.read::<u8>()?;
+        let red = s.read::<u8>()?;
+        let alpha = s.read::<u8>()?;
+        Some(Color { red, green, blue, alpha })
+    }
+}
+
+
+/// [Palette type flags](https://docs.microsoft.com/en-us/typography/opentype/spec/cpal#palette-type-array).
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct PaletteFlags(pub u32);
+
+impl PaletteFlags {
+    /// Checks that the palette is appropriate to use with a light background.
+    #[inline]
+    pub fn usable_with_light_background(self) -> bool {
+        self.0 & 0x0001 != 0
+    }
+
+    /// Checks that the palette is appropriate to use with a dark background.
+    #[inline]
+    pub fn usable_with_dark_background(self) -> bool {
+        self.0 & 0x0002 != 0
+    }
+}
+
+
+/// A [Color Palette table](https://docs.microsoft.com/en-us/typography/opentype/spec/cpal).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    entries_count: u16,
+    colors: LazyArray16<'a, Color>,
+    first_color_indices: LazyArray16<'a, u16>,
+    // Version 1 data.
+    types: LazyArray16<'a, u32>,
+    labels: LazyArray16<'a, u16>,
+    entry_labels: LazyArray16<'a, u16>,
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let version: u16 = s.read()?;
+        if version > 1 {
+            return None;
+        }
+
+        let entries_count: u16 = s.read()?;
+        let palettes_count: u16 = s.read()?;
+        let colors_count: u16 = s.read()?;
+        let colors_offset: Offset32 = s.read()?;
+        let first_color_indices = s.read_array16(palettes_count)?;
+        let colors = Stream::new_at(data, colors_offset.to_usize())?.read_array16(colors_count)?;
+
+        let mut table = Table {
+            entries_count,
+            colors,
+            first_color_indices,
+            types: LazyArray16::default(),
+            labels: LazyArray16::default(),
+            entry_labels: LazyArray16::default(),
+        };
+
+        if version == 0 {
+            return Some(table);
+        }
+
+        let types_offset: Option<Offset32> = s.read()?;
+        let labels_offset: Option<Offset32> = s.read()?;
+        let entry_labels_offset: Option<Offset32> = s.read()?;
+
+        // Optional arrays are ignored when malformed.
+        table.types = read_array(data, types_offset, palettes_count).unwrap_or_default();
+        table.labels = read_array(data, labels_offset, palettes_count).unwrap_or_default();
+        table.entry_labels = read_array(data, entry_labels_offset, entries_count)
+            .unwrap_or_default();
+
+        Some(table)
+    }
+
+    /// Returns the number of palettes.
+    #[inline]
+    pub fn palettes_count(&self) -> u16 {
+        self.first_color_indices.len()
+    }
+
+    /// Returns the number of entries in each palette.
+    #[inline]
+    pub fn entries_count(&self) -> u16 {
+        self.entries_count
+    }
+
+    /// Returns a color of a palette entry.
+    pub fn color(&self, palette: u16, entry: u16) -> Option<Color> {
+        if entry >= self.entries_count {
+            return None;
+        }
+
+        let first = self.first_color_indices.get(palette)?;
+        self.colors.get(first.checked_add(entry)?)
+    }
+
+    /// Returns an iterator over palette colors.
+    pub fn palette(&self, palette: u16) -> Option<impl Iterator<Item = Color> + 'a> {
+        let first = self.first_color_indices.get(palette)?;
+        let end = first.checked_add(self.entries_count)?;
+        Some(self.colors.slice(first..end)?.into_iter())
+    }
+
+    /// Returns palette flags.
+    ///
+    /// Flags are always empty for `CPAL` version 0.
+    #[inline]
+    pub fn palette_flags(&self, palette: u16) -> PaletteFlags {
+        PaletteFlags(self.types.get(palette).unwrap_or(0))
+    }
+
+    /// Returns palette's name ID in the `name` table.
+    ///
+    /// Returns `None` for `CPAL` version 0 or when the palette has no label.
+    #[inline]
+    pub fn palette_label(&self, palette: u16) -> Option<u16> {
+        self.labels.get(palette).filter(|id| *id != 0xFFFF)
+    }
+
+    /// Returns palette entry's name ID in the `name` table.
+    ///
+    /// Returns `None` for `CPAL` version 0 or when the entry has no label.
+    #[inline]
+    pub fn entry_label(&self, entry: u16) -> Option<u16> {
+        self.entry_labels.get(entry).filter(|id| *id != 0xFFFF)
+    }
+}
+
+fn read_array<'a, T: FromData>(
+    data: &'a [u8],
+    offset: Option<Offset32>,
+    count: u16,
+) -> Option<LazyArray16<'a, T>> {
+    Stream::new_at(data, offset?.to_usize())?.read_array16(count)
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn version_0() {
+        let data = writer::convert(&[
+            UInt16(0), // version
+            UInt16(2), // entries count
+            UInt16(2), // palettes count
+            UInt16(3), // colors count
+            UInt32(16), // colors offset
+            UInt16(0), // first color index [0]
+            UInt16(1), // first color index [1]
+            // Colors
+            UInt8(10), UInt8(20), UInt8(30), UInt8(255), // color [0]
+            UInt8(0), UInt8(0), UInt8(255), UInt8(128), // color [1]
+            UInt8(255), UInt8(0), UInt8(0), UInt8(255), // color [2]
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.palettes_count(), 2);
+        assert_eq!(table.entries_count(), 2);
+        assert_eq!(table.color(0, 0), Some(Color { red: 30, green: 20, blue: 10, alpha: 255 }));
+        assert_eq!(table.color(1, 1), Some(Color { red: 0, green: 0, blue: 255, alpha: 255 }));
+        assert!(table.color(0, 2).is_none());
+        assert!(table.color(2, 0).is_none());
+        assert_eq!(table.palette(1).unwrap().count(), 2);
+        assert_eq!(table.palette_flags(0), PaletteFlags(0));
+        assert!(table.palette_label(0).is_none());
+        assert!(table.entry_label(0).is_none());
+    }
+
+    #[test]
+    fn version_1() {
+        let data = writer::convert(&[
+            UInt16(1), // version
+            UInt16(1), // entries count
+            UInt16(2), // palettes count
+            UInt16(2), // colors count
+            UInt32(28), // colors offset
+            UInt16(0), // first color index [0]
+            UInt16(1), // first color index [1]
+            UInt32(36), // palette types offset
+            UInt32(44), // palette labels offset
+            UInt32(48), // palette entry labels offset
+            // Colors
+            UInt8(255), UInt8(255), UInt8(255), UInt8(255), // color [0]
+            UInt8(0), UInt8(0), UInt8(0), UInt8(255), // color [1]
+            // Palette Types
+            UInt32(1), // type [0]
+            UInt32(2), // type [1]
+            // Palette Labels
+            UInt16(256), // label [0]
+            UInt16(0xFFFF), // label [1]
+            // Palette Entry Labels
+            UInt16(300), // label [0]
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert!(table.palette_flags(0).usable_with_light_background());
+        assert!(!table.palette_flags(0).usable_with_dark_background());
+        assert!(table.palette_flags(1).usable_with_dark_background());
+        assert_eq!(table.palette_label(0), Some(256));
+        assert!(table.palette_label(1).is_none());
+        assert_eq!(table.entry_label(0), Some(300));
+        assert_eq!(table.color(1, 0), Some(Color { red: 0, green: 0, blue: 0, alpha: 255 }));
+    }
+}
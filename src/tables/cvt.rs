@@ -0,0 +1,101 @@
+/*!
+A [Control Value table](https://docs.microsoft.com/en-us/typography/opentype/spec/cvt) implementation.
+
+Control values are used by TrueType hinting instructions,
+which are stored in `fpgm` and `prep` tables and glyphs themselves.
+*/
+
+use core::convert::TryFrom;
+
+use crate::parser::{Stream, LazyArray32};
+
+
+/// A [Control Value table](https://docs.microsoft.com/en-us/typography/opentype/spec/cvt).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    values: LazyArray32<'a, i16>,
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
+        // A trailing odd byte is ignored.
+        let count = u32::try_from(data.len() / 2).ok()?;
+        let values = Stream::new(data).read_array32::<i16>(count)?;
+        Some(Table { values })
+    }
+
+    /// Returns the number of control values.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        self.values.len()
+    }
+
+    /// Checks that the table has no control values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.len() == 0
+    }
+
+    /// Returns a control value in font units.
+    #[inline]
+    pub fn get(&self, index: u32) -> Option<i16> {
+        self.values.get(index)
+    }
+
+    /// Returns an iterator over control values.
+    #[inline]
+    pub fn values(&self) -> Values<'a> {
+        Values { table: *self, index: 0 }
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
+}
+
+
+/// An iterator over control values.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct Values<'a> {
+    table: Table<'a>,
+    index: u32,
+}
+
+impl<'a> Iterator for Values<'a> {
+    type Item = i16;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.table.get(self.index)?;
+        self.index += 1;
+        Some(value)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn values() {
+        let data = writer::convert(&[
+            Int16(10), // value [0]
+            Int16(-300), // value [1]
+            Int16(1500), // value [2]
+            UInt8(1), // trailing byte
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.get(1), Some(-300));
+        assert_eq!(table.get(3), None);
+        let values: std::vec::Vec<_> = table.values().collect();
+        assert_eq!(values, [10, -300, 1500]);
+    }
+}
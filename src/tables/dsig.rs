@@ -0,0 +1,246 @@
+/*!
+A [Digital Signature table](https://docs.microsoft.com/en-us/typography/opentype/spec/dsig) implementation.
+
+Signatures are only exposed and are not verified.
+*/
+
+use crate::parser::{Stream, FromData, LazyArray16, NumFrom};
+
+
+/// [Digital signature flags](https://docs.microsoft.com/en-us/typography/opentype/spec/dsig#table-structure).
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct SignatureFlags(pub u16);
+
+impl SignatureFlags {
+    /// Checks that the font cannot be resigned.
+    #[inline]
+    pub fn cannot_be_resigned(self) -> bool {
+        self.0 & 0x0001 != 0
+    }
+}
+
+
+#[derive(Clone, Copy)]
+struct SignatureRecord {
+    format: u32,
+    length: u32,
+    offset: u32,
+}
+
+impl FromData for SignatureRecord {
+    const SIZE: usize = 12;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(SignatureRecord {
+            format: s.read::<u32>()?,
+            length: s.read::<u32>()?,
+            offset: s.read::<u32>()?,
+        })
+    }
+}
+
+
+/// A digital signature.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Signature<'a> {
+    /// A signature format.
+    ///
+    /// Only format 1 is defined.
+    pub format: u32,
+    /// Signature data.
+    ///
+    /// A PKCS#7 packet for format 1 and a raw signature block for unknown formats.
+    pub data: &'a [u8],
+}
+
+
+/// A [Digital Signature table](https://docs.microsoft.com/en-us/typography/opentype/spec/dsig).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    data: &'a [u8],
+    flags: SignatureFlags,
+    records: LazyArray16<'a, SignatureRecord>,
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let version: u32 = s.read()?;
+        if version != 1 {
+            return None;
+        }
+
+        let count: u16 = s.read()?;
+        let flags = SignatureFlags(s.read()?);
+        let records = s.read_array16::<SignatureRecord>(count)?;
+
+        Some(Table { data, flags, records })
+    }
+
+    /// Returns table's version.
+    ///
+    /// Only version 1 is supported.
+    #[inline]
+    pub fn version(&self) -> u32 {
+        1
+    }
+
+    /// Returns table's flags.
+    #[inline]
+    pub fn flags(&self) -> SignatureFlags {
+        self.flags
+    }
+
+    /// Returns the number of signatures.
+    #[inline]
+    pub fn len(&self) -> u16 {
+        self.records.len()
+    }
+
+    /// Checks that the table has no signatures.
+    ///
+    /// Fonts often contain such a stub table.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.records.len() == 0
+    }
+
+    /// Returns a signature at index.
+    ///
+    /// Returns `None` when index is out of bounds or signature data is malformed.
+    pub fn get(&self, index: u16) -> Option<Signature<'a>> {
+        let record = self.records.get(index)?;
+        let start = usize::num_from(record.offset);
+        let end = start.checked_add(usize::num_from(record.length))?;
+        let block = self.data.get(start..end)?;
+
+        let data = if record.format == 1 {
+            let mut s = Stream::new(block);
+            s.skip::<u16>(); // reserved1
+            s.skip::<u16>(); // reserved2
+            let len: u32 = s.read()?;
+            s.read_bytes(usize::num_from(len))?
+        } else {
+            block
+        };
+
+        Some(Signature { format: record.format, data })
+    }
+
+    /// Returns an iterator over signatures.
+    #[inline]
+    pub fn signatures(&self) -> Signatures<'a> {
+        Signatures { table: *self, index: 0 }
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
+}
+
+
+/// An iterator over digital signatures.
+///
+/// Malformed signatures are skipped.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct Signatures<'a> {
+    table: Table<'a>,
+    index: u16,
+}
+
+impl<'a> Iterator for Signatures<'a> {
+    type Item = Signature<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.table.len() {
+            let index = self.index;
+            self.index += 1;
+
+            if let Some(signature) = self.table.get(index) {
+                return Some(signature);
+            }
+        }
+
+        None
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn basic() {
+        let data = writer::convert(&[
+            UInt32(1), // version
+            UInt16(3), // number of signatures
+            UInt16(1), // flags: cannot be resigned
+            // Signature record 0
+            UInt32(1), // format
+            UInt32(12), // length
+            UInt32(44), // offset
+            // Signature record 1
+            UInt32(1), // format
+            UInt32(8), // length
+            UInt32(100), // offset: out of bounds
+            // Signature record 2
+            UInt32(2), // format
+            UInt32(2), // length
+            UInt32(56), // offset
+            // Signature block 0
+            UInt16(0), // reserved1
+            UInt16(0), // reserved2
+            UInt32(4), // signature length
+            Raw(&[0x30, 0x82, 0x01, 0x00]), // signature
+            // Signature block 2
+            Raw(&[0xAA, 0xBB]),
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.version(), 1);
+        assert!(table.flags().cannot_be_resigned());
+        assert_eq!(table.len(), 3);
+        assert!(!table.is_empty());
+        assert_eq!(table.get(0), Some(Signature { format: 1, data: &[0x30, 0x82, 0x01, 0x00] }));
+        assert_eq!(table.get(1), None);
+        assert_eq!(table.get(3), None);
+
+        let mut signatures = table.signatures();
+        assert_eq!(signatures.next().map(|s| s.format), Some(1));
+        assert_eq!(signatures.next(), Some(Signature { format: 2, data: &[0xAA, 0xBB] }));
+        assert_eq!(signatures.next(), None);
+    }
+
+    #[test]
+    fn stub() {
+        let data = writer::convert(&[
+            UInt32(1), // version
+            UInt16(0), // number of signatures
+            UInt16(0), // flags
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert!(table.is_empty());
+        assert!(!table.flags().cannot_be_resigned());
+        assert_eq!(table.signatures().count(), 0);
+    }
+
+    #[test]
+    fn unknown_version() {
+        let data = writer::convert(&[
+            UInt32(2), // version
+            UInt16(0), // number of signatures
+            UInt16(0), // flags
+        ]);
+
+        assert!(Table::parse(&data).is_none());
+    }
+}
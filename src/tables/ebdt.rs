@@ -0,0 +1,128 @@
+// https://docs.microsoft.com/en-us/typography/opentype/spec/ebdt
+
+use crate::{RasterGlyphImage, RasterImageFormat};
+use crate::parser::Stream;
+use super::cblc::{BitmapFormat, Location, Metrics};
+
+pub(crate) fn parse<'a>(
+    data: &'a [u8],
+    location: Location,
+) -> Option<RasterGlyphImage<'a>> {
+    let mut s = Stream::new_at(data, location.offset)?;
+    let (metrics, is_packed) = match location.format {
+        BitmapFormat::Format1 => (parse_small_metrics(&mut s)?, false),
+        BitmapFormat::Format2 => (parse_small_metrics(&mut s)?, true),
+        // Metrics are stored in `EBLC`.
+        BitmapFormat::Format5 => (location.metrics, true),
+        BitmapFormat::Format6 => (Metrics::parse_big(&mut s)?, false),
+        BitmapFormat::Format7 => (Metrics::parse_big(&mut s)?, true),
+        _ => return None, // `CBDT` formats.
+    };
+
+    let format = match (location.bit_depth, is_packed) {
+        (1, false) => RasterImageFormat::BitmapMono,
+        (1, true) => RasterImageFormat::BitmapMonoPacked,
+        (2, false) => RasterImageFormat::BitmapGray2,
+        (2, true) => RasterImageFormat::BitmapGray2Packed,
+        (4, false) => RasterImageFormat::BitmapGray4,
+        (4, true) => RasterImageFormat::BitmapGray4Packed,
+        (8, _) => RasterImageFormat::BitmapGray8,
+        _ => return None,
+    };
+
+    // Image data length is not stored, so we have to calculate it.
+    let width = usize::from(metrics.width);
+    let height = usize::from(metrics.height);
+    let bit_depth = usize::from(location.bit_depth);
+    let data_len = if is_packed {
+        bits_to_bytes(width * height * bit_depth)
+    } else {
+        bits_to_bytes(width * bit_depth) * height
+    };
+
+    Some(RasterGlyphImage {
+        x: i16::from(metrics.x),
+        // `y` in EBDT is a bottom bound, not top one.
+        y: i16::from(metrics.y) - i16::from(metrics.height),
+        width: u16::from(metrics.width),
+        height: u16::from(metrics.height),
+        pixels_per_em: location.ppem,
+        format,
+        scale: 1.0,
+        data: s.read_bytes(data_len)?,
+    })
+}
+
+#[inline]
+fn bits_to_bytes(bits: usize) -> usize {
+    bits / 8 + usize::from(bits & 7 != 0)
+}
+
+fn parse_small_metrics(s: &mut Stream) -> Option<Metrics> {
+    let height: u8 = s.read()?;
+    let width: u8 = s.read()?;
+    let x: i8 = s.read()?;
+    let y: i8 = s.read()?;
+    s.skip::<u8>(); // advance
+    Some(Metrics { x, y, width, height })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    fn location(format: BitmapFormat, bit_depth: u8) -> Location {
+        Location {
+            format,
+            offset: 4,
+            metrics: Metrics { x: 0, y: 3, width: 3, height: 3 },
+            ppem: 12,
+            bit_depth,
+        }
+    }
+
+    #[test]
+    fn mono() {
+        let data = writer::convert(&[
+            UInt16(2), // major version
+            UInt16(0), // minor version
+            // Glyph Bitmap
+            UInt8(2), // height
+            UInt8(3), // width
+            Int8(1), // bearing x
+            Int8(2), // bearing y
+            UInt8(4), // advance
+            UInt8(0b1010_0000), // row [0]
+            UInt8(0b0100_0000), // row [1]
+        ]);
+
+        let image = parse(&data, location(BitmapFormat::Format1, 1)).unwrap();
+        assert_eq!(image.format, RasterImageFormat::BitmapMono);
+        assert_eq!((image.x, image.y), (1, 0));
+        assert_eq!((image.width, image.height), (3, 2));
+        assert_eq!(image.data, &[0b1010_0000, 0b0100_0000]);
+    }
+
+    #[test]
+    fn packed() {
+        let data = writer::convert(&[
+            UInt16(2), // major version
+            UInt16(0), // minor version
+            // Glyph Bitmap
+            UInt8(0b1010_1010), // pixels [0..8]
+            UInt8(0b1000_0000), // pixel [8]
+        ]);
+
+        let image = parse(&data, location(BitmapFormat::Format5, 1)).unwrap();
+        assert_eq!(image.format, RasterImageFormat::BitmapMonoPacked);
+        assert_eq!((image.width, image.height), (3, 3));
+        assert_eq!(image.data.len(), 2);
+
+        assert!(parse(&data, location(BitmapFormat::Format5, 2)).is_none());
+        assert!(parse(&data, location(BitmapFormat::Format5, 3)).is_none());
+        assert!(parse(&data, location(BitmapFormat::Format17, 1)).is_none());
+    }
+}
@@ -0,0 +1,119 @@
+/*!
+An [Embedded Bitmap Scaling table](https://docs.microsoft.com/en-us/typography/opentype/spec/ebsc) implementation.
+*/
+
+use crate::parser::{Stream, FromData, LazyArray32};
+use crate::tables::cblc::LineMetrics;
+
+
+/// A bitmap scale record.
+///
+/// Describes a size, which has no strike, but can be rendered by scaling another strike.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BitmapScale {
+    /// Horizontal line metrics of the target size.
+    pub horizontal: LineMetrics,
+    /// Vertical line metrics of the target size.
+    pub vertical: LineMetrics,
+    /// Target horizontal pixels per em.
+    pub ppem_x: u8,
+    /// Target vertical pixels per em.
+    pub ppem_y: u8,
+    /// Horizontal pixels per em of a strike that should be scaled.
+    pub substitute_ppem_x: u8,
+    /// Vertical pixels per em of a strike that should be scaled.
+    pub substitute_ppem_y: u8,
+}
+
+impl FromData for BitmapScale {
+    const SIZE: usize = 28;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(BitmapScale {
+            horizontal: LineMetrics::parse(&mut s)?,
+            vertical: LineMetrics::parse(&mut s)?,
+            ppem_x: s.read()?,
+            ppem_y: s.read()?,
+            substitute_ppem_x: s.read()?,
+            substitute_ppem_y: s.read()?,
+        })
+    }
+}
+
+
+/// An [Embedded Bitmap Scaling table](https://docs.microsoft.com/en-us/typography/opentype/spec/ebsc).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    scales: LazyArray32<'a, BitmapScale>,
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let major_version: u16 = s.read()?;
+        s.skip::<u16>(); // minor version
+        if major_version != 2 {
+            return None;
+        }
+
+        let count: u32 = s.read()?;
+        let scales = s.read_array32(count)?;
+        Some(Table { scales })
+    }
+
+    /// Returns an iterator over bitmap scales.
+    #[inline]
+    pub fn scales(&self) -> impl Iterator<Item = BitmapScale> + 'a {
+        self.scales.into_iter()
+    }
+
+    /// Returns a bitmap scale for the specified size.
+    ///
+    /// Returns `None` when the size should not be substituted.
+    pub fn find(&self, ppem_x: u8, ppem_y: u8) -> Option<BitmapScale> {
+        self.scales.into_iter().find(|scale| scale.ppem_x == ppem_x && scale.ppem_y == ppem_y)
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn scales() {
+        let data = writer::convert(&[
+            UInt16(2), // major version
+            UInt16(0), // minor version
+            UInt32(1), // scales count
+            // Bitmap Scale
+            Int8(9), Int8(-3), UInt8(12), // horizontal line metrics
+            Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), UInt8(0), UInt8(0),
+            Int8(0), Int8(0), UInt8(0), // vertical line metrics
+            Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), Int8(0), UInt8(0), UInt8(0),
+            UInt8(12), // ppem x
+            UInt8(12), // ppem y
+            UInt8(16), // substitute ppem x
+            UInt8(16), // substitute ppem y
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.scales().count(), 1);
+        assert!(table.find(16, 16).is_none());
+
+        let scale = table.find(12, 12).unwrap();
+        assert_eq!(scale.horizontal.ascender, 9);
+        assert_eq!(scale.horizontal.descender, -3);
+        assert_eq!((scale.substitute_ppem_x, scale.substitute_ppem_y), (16, 16));
+    }
+}
@@ -0,0 +1,155 @@
+/*!
+An [Apple Font Descriptors table](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6fdsc.html)
+implementation.
+*/
+
+use crate::Tag;
+use crate::parser::{Stream, FromData, Fixed, LazyArray32};
+
+
+/// A font descriptor.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Descriptor {
+    /// A descriptor tag, like `wght`.
+    pub tag: Tag,
+    /// A descriptor value.
+    pub value: f32,
+}
+
+impl FromData for Descriptor {
+    const SIZE: usize = 8;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(Descriptor {
+            tag: s.read::<Tag>()?,
+            value: s.read::<Fixed>()?.0,
+        })
+    }
+}
+
+
+/// An [Apple Font Descriptors table](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6fdsc.html).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    descriptors: LazyArray32<'a, Descriptor>,
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let version: u32 = s.read()?;
+        if version != 0x00010000 {
+            return None;
+        }
+
+        let count: u32 = s.read()?;
+        let descriptors = s.read_array32::<Descriptor>(count)?;
+        Some(Table { descriptors })
+    }
+
+    /// Returns a list of descriptors.
+    #[inline]
+    pub fn descriptors(&self) -> LazyArray32<'a, Descriptor> {
+        self.descriptors
+    }
+
+    /// Returns a descriptor value by tag.
+    #[inline]
+    pub fn get(&self, tag: Tag) -> Option<f32> {
+        self.descriptors.into_iter().find(|d| d.tag == tag).map(|d| d.value)
+    }
+
+    /// Returns a weight, where 1.0 is regular.
+    ///
+    /// Uses the `wght` descriptor.
+    #[inline]
+    pub fn weight(&self) -> Option<f32> {
+        self.get(Tag::from_bytes(b"wght"))
+    }
+
+    /// Returns a width, where 1.0 is normal.
+    ///
+    /// Uses the `wdth` descriptor.
+    #[inline]
+    pub fn width(&self) -> Option<f32> {
+        self.get(Tag::from_bytes(b"wdth"))
+    }
+
+    /// Returns a slant, where 0.0 is upright.
+    ///
+    /// Uses the `slnt` descriptor.
+    #[inline]
+    pub fn slant(&self) -> Option<f32> {
+        self.get(Tag::from_bytes(b"slnt"))
+    }
+
+    /// Returns an optical point size.
+    ///
+    /// Uses the `opsz` descriptor.
+    #[inline]
+    pub fn optical_size(&self) -> Option<f32> {
+        self.get(Tag::from_bytes(b"opsz"))
+    }
+
+    /// Returns a non-alphabetic font code, like 2 for a Dingbats font.
+    ///
+    /// Uses the `nalf` descriptor. Unlike other descriptors, the value is an integer.
+    #[inline]
+    pub fn non_alphabetic(&self) -> Option<u32> {
+        self.descriptors.into_iter()
+            .find(|d| d.tag == Tag::from_bytes(b"nalf"))
+            .map(|d| (d.value * 65536.0) as u32)
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn basic() {
+        let data = writer::convert(&[
+            UInt32(0x00010000), // version
+            UInt32(3), // number of descriptors
+            // Descriptor 0
+            Raw(b"wght"), // tag
+            Int32(0x00018000), // value: 1.5
+            // Descriptor 1
+            Raw(b"slnt"), // tag
+            Int32(-0x00004000), // value: -0.25
+            // Descriptor 2
+            Raw(b"nalf"), // tag
+            UInt32(2), // value: Dingbats
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.descriptors().len(), 3);
+        assert_eq!(table.weight(), Some(1.5));
+        assert_eq!(table.width(), None);
+        assert_eq!(table.slant(), Some(-0.25));
+        assert_eq!(table.optical_size(), None);
+        assert_eq!(table.non_alphabetic(), Some(2));
+    }
+
+    #[test]
+    fn truncated() {
+        let data = writer::convert(&[
+            UInt32(0x00010000), // version
+            UInt32(1), // number of descriptors
+            Raw(b"wght"), // tag
+        ]);
+
+        assert!(Table::parse(&data).is_none());
+    }
+}
@@ -0,0 +1,94 @@
+/*!
+An [Apple Font Metrics table](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6fmtx.html)
+implementation.
+
+Instead of storing metrics directly, the table references points of a special glyph,
+so metrics follow the glyph when it's varied or hinted.
+*/
+
+use crate::GlyphId;
+use crate::parser::Stream;
+
+
+/// Point indices of a single direction metrics.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MetricsPoints {
+    /// A point that defines the ascent.
+    pub before: u8,
+    /// A point that defines the descent.
+    pub after: u8,
+    /// A point that defines the caret head.
+    pub caret_head: u8,
+    /// A point that defines the caret base.
+    pub caret_base: u8,
+}
+
+
+/// An [Apple Font Metrics table](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6fmtx.html).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Table {
+    /// A glyph whose points define the metrics.
+    pub glyph_id: GlyphId,
+    /// Horizontal metrics points.
+    pub horizontal: MetricsPoints,
+    /// Vertical metrics points.
+    pub vertical: MetricsPoints,
+}
+
+impl Table {
+    pub(crate) fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let version: u32 = s.read()?;
+        if version != 0x00020000 {
+            return None;
+        }
+
+        let glyph_index: u32 = s.read()?;
+        let glyph_id = GlyphId(core::convert::TryFrom::try_from(glyph_index).ok()?);
+
+        let mut points = || -> Option<MetricsPoints> {
+            Some(MetricsPoints {
+                before: s.read()?,
+                after: s.read()?,
+                caret_head: s.read()?,
+                caret_base: s.read()?,
+            })
+        };
+
+        let horizontal = points()?;
+        let vertical = points()?;
+        Some(Table { glyph_id, horizontal, vertical })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn basic() {
+        let data = writer::convert(&[
+            UInt32(0x00020000), // version
+            UInt32(5), // glyph index
+            UInt8(0), // horizontal before
+            UInt8(1), // horizontal after
+            UInt8(2), // horizontal caret head
+            UInt8(3), // horizontal caret base
+            UInt8(4), // vertical before
+            UInt8(5), // vertical after
+            UInt8(6), // vertical caret head
+            UInt8(7), // vertical caret base
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.glyph_id, GlyphId(5));
+        assert_eq!(table.horizontal, MetricsPoints { before: 0, after: 1, caret_head: 2, caret_base: 3 });
+        assert_eq!(table.vertical.caret_base, 7);
+
+        // Truncated.
+        assert!(Table::parse(&data[..15]).is_none());
+    }
+}
@@ -2,7 +2,13 @@
 
 use core::num::NonZeroU16;
 
-use crate::{Tag, NormalizedCoordinate};
+#[cfg(feature = "std")]
+use std::string::String;
+
+use crate::{Tag, NormalizedCoordinate, Variation};
+use crate::stat;
+#[cfg(feature = "std")]
+use crate::Names;
 use crate::parser::{Stream, FromData, Fixed, Offset16, Offset, LazyArray16, LazyArrayIter16, f32_bound};
 
 
@@ -10,6 +16,7 @@ use crate::parser::{Stream, FromData, Fixed, Offset16, Offset, LazyArray16, Lazy
 #[allow(missing_docs)]
 #[repr(C)]
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VariationAxis {
     pub tag: Tag,
     pub min_value: f32,
@@ -37,12 +44,25 @@ impl VariationAxis {
 
         NormalizedCoordinate::from(v)
     }
+
+    /// Returns a user-space value for a normalized variation coordinate.
+    pub(crate) fn user_value(&self, coordinate: NormalizedCoordinate) -> f32 {
+        let v = f32::from(coordinate.get()) / 16384.0;
+        if v < 0.0 {
+            self.def_value + v * (self.def_value - self.min_value)
+        } else {
+            self.def_value + v * (self.max_value - self.def_value)
+        }
+    }
 }
 
 
 #[derive(Clone, Copy)]
 pub(crate) struct Table<'a> {
     axes: LazyArray16<'a, VariationAxisRecord>,
+    instances: &'a [u8],
+    instance_count: u16,
+    instance_size: u16,
 }
 
 impl<'a> Table<'a> {
@@ -56,6 +76,9 @@ impl<'a> Table<'a> {
         let axes_array_offset: Offset16 = s.read()?;
         s.skip::<u16>(); // reserved
         let axis_count: u16 = s.read()?;
+        s.skip::<u16>(); // axisSize
+        let instance_count: u16 = s.read()?;
+        let instance_size: u16 = s.read()?;
 
         // 'If axisCount is zero, then the font is not functional as a variable font,
         // and must be treated as a non-variable font;
@@ -65,14 +88,77 @@ impl<'a> Table<'a> {
         let mut s = Stream::new_at(data, axes_array_offset.to_usize())?;
         let axes = s.read_array16::<VariationAxisRecord>(axis_count.get())?;
 
-        Some(Table { axes })
+        // Instances are stored right after axes.
+        // An instance has at least `subfamilyNameID`, `flags` and coordinates.
+        let min_instance_size = usize::from(axis_count.get()) * Fixed::SIZE + 4;
+        let (instances, instance_count) = if usize::from(instance_size) >= min_instance_size {
+            let len = usize::from(instance_count) * usize::from(instance_size);
+            match s.read_bytes(len) {
+                Some(instances) => (instances, instance_count),
+                None => (&[][..], 0),
+            }
+        } else {
+            (&[][..], 0)
+        };
+
+        Some(Table { axes, instances, instance_count, instance_size })
     }
 
     pub fn axes(&self) -> VariationAxes<'a> {
         VariationAxes { iter: self.axes.into_iter() }
     }
 
-    // TODO: add axis_by_tag
+    pub fn instances(&self) -> VariationInstances<'a> {
+        VariationInstances {
+            axes: self.axes,
+            data: self.instances,
+            size: self.instance_size,
+            index: 0,
+            total: self.instance_count,
+        }
+    }
+
+    pub fn axis_by_tag(&self, tag: Tag) -> Option<VariationAxis> {
+        self.axes().find(|axis| axis.tag == tag)
+    }
+
+    /// Returns a user value of the specified axis for `coordinates`.
+    ///
+    /// Axes missing from this table are looked up in the `STAT` table.
+    pub(crate) fn style_axis_value(
+        &self,
+        stat: Option<stat::Table>,
+        coordinates: &[NormalizedCoordinate],
+        tag: Tag,
+    ) -> Option<f32> {
+        let axes = || self.axes().zip(coordinates.iter());
+        if let Some((axis, coord)) = axes().find(|(axis, _)| axis.tag == tag) {
+            return Some(axis.user_value(*coord));
+        }
+
+        // Coordinates are stored normalized, so values are compared after normalization.
+        stat?.matching_axis_value(tag, |tag, value| {
+            axes().any(|(axis, coord)| axis.tag == tag && axis.normalized_value(value) == *coord)
+        })
+    }
+
+    /// Returns a named instance with a matching subfamily name in any language.
+    ///
+    /// Comparison ignores case and whitespace.
+    #[cfg(feature = "std")]
+    pub(crate) fn instance_by_name(&self, names: Names, name: &str) -> Option<VariationInstance<'a>> {
+        fn normalize(s: &str) -> String {
+            s.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).collect()
+        }
+
+        let query = normalize(name);
+        self.instances().find(|instance| {
+            names
+                .with_name_id(instance.subfamily_name_id())
+                .filter_map(|name| name.to_string())
+                .any(|name| normalize(&name) == query)
+        })
+    }
 }
 
 
@@ -111,6 +197,154 @@ impl<'a> Iterator for VariationAxes<'a> {
 }
 
 
+/// A [named instance](https://docs.microsoft.com/en-us/typography/opentype/spec/fvar#instancerecord).
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct VariationInstance<'a> {
+    axes: LazyArray16<'a, VariationAxisRecord>,
+    coordinates: LazyArray16<'a, Fixed>,
+    subfamily_name_id: u16,
+    post_script_name_id: Option<u16>,
+}
+
+impl<'a> VariationInstance<'a> {
+    /// Returns instance's subfamily name ID in the `name` table.
+    #[inline]
+    pub fn subfamily_name_id(&self) -> u16 {
+        self.subfamily_name_id
+    }
+
+    /// Returns instance's PostScript name ID in the `name` table.
+    ///
+    /// Returns `None` when not set.
+    #[inline]
+    pub fn post_script_name_id(&self) -> Option<u16> {
+        self.post_script_name_id
+    }
+
+    /// Returns an iterator over instance's coordinates in the `fvar` axes order.
+    #[inline]
+    pub fn coordinates(&self) -> impl Iterator<Item = Variation> + 'a {
+        self.axes.into_iter().zip(self.coordinates).map(|(axis, value)| {
+            Variation { axis: axis.axis_tag, value: value.0 }
+        })
+    }
+}
+
+
+/// An iterator over named instances.
+#[derive(Clone, Copy, Default)]
+#[allow(missing_debug_implementations)]
+pub struct VariationInstances<'a> {
+    axes: LazyArray16<'a, VariationAxisRecord>,
+    data: &'a [u8],
+    size: u16,
+    index: u16,
+    total: u16,
+}
+
+impl<'a> Iterator for VariationInstances<'a> {
+    type Item = VariationInstance<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.total {
+            return None;
+        }
+
+        let start = usize::from(self.index) * usize::from(self.size);
+        self.index += 1;
+
+        let mut s = Stream::new(self.data.get(start..start + usize::from(self.size))?);
+        let subfamily_name_id: u16 = s.read()?;
+        s.skip::<u16>(); // flags
+        let coordinates = s.read_array16::<Fixed>(self.axes.len())?;
+        // `postScriptNameID` is optional and 0xFFFF indicates that it's not set.
+        let post_script_name_id = s.read::<u16>().filter(|id| *id != 0xFFFF);
+
+        Some(VariationInstance {
+            axes: self.axes,
+            coordinates,
+            subfamily_name_id,
+            post_script_name_id,
+        })
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        usize::from(self.total.saturating_sub(self.index))
+    }
+}
+
+
+/// Builds a variation PostScript name according to
+/// [Adobe Technical Note #5902](https://adobe-type-tools.github.io/font-tech-notes/pdfs/5902.AdobePSNameGeneration.pdf).
+///
+/// Axes without a value in `variations` or with a default value are omitted.
+///
+/// Returns `None` when the name is longer than 127 characters.
+#[cfg(feature = "std")]
+pub(crate) fn variation_post_script_name(
+    prefix: &str,
+    axes: VariationAxes,
+    variations: &[Variation],
+) -> Option<String> {
+    // https://adobe-type-tools.github.io/font-tech-notes/pdfs/5902.AdobePSNameGeneration.pdf
+    const MAX_LEN: usize = 127;
+
+    let mut name = String::from(prefix);
+    for axis in axes {
+        let value = match variations.iter().find(|v| v.axis == axis.tag) {
+            Some(v) => f32_bound(axis.min_value, v.value, axis.max_value),
+            None => continue,
+        };
+
+        if value == axis.def_value {
+            continue;
+        }
+
+        // Axes are separated from the prefix by a hyphen, but not from each other.
+        if name.len() == prefix.len() {
+            name.push('-');
+        }
+
+        for c in axis.tag.to_chars().iter().filter(|c| **c != ' ') {
+            name.push(*c);
+        }
+
+        push_axis_value(value, &mut name);
+    }
+
+    if name.len() > MAX_LEN {
+        return None;
+    }
+
+    Some(name)
+}
+
+/// Writes a value with up to 5 fractional digits and without trailing zeros.
+#[cfg(feature = "std")]
+fn push_axis_value(value: f32, name: &mut String) {
+    use core::fmt::Write;
+
+    const SCALE: u64 = 100_000;
+
+    // `f32::round` is not available in `no_std`.
+    let scaled = (f64::from(value).abs() * SCALE as f64 + 0.5) as u64;
+    if value < 0.0 && scaled != 0 {
+        name.push('-');
+    }
+
+    let _ = write!(name, "{}", scaled / SCALE);
+
+    let fraction = scaled % SCALE;
+    if fraction != 0 {
+        let digits = std::format!("{:05}", fraction);
+        name.push('.');
+        name.push_str(digits.trim_end_matches('0'));
+    }
+}
+
+
 #[derive(Clone, Copy)]
 struct VariationAxisRecord {
     axis_tag: Tag,
@@ -137,3 +371,194 @@ impl FromData for VariationAxisRecord {
         })
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    fn axes_table() -> std::vec::Vec<u8> {
+        writer::convert(&[
+            UInt32(0x00010000), // version: 1.0
+            UInt16(16), // axes array offset
+            UInt16(2), // reserved
+            UInt16(2), // axis count
+            UInt16(20), // axis size
+            UInt16(2), // instance count
+            UInt16(14), // instance size
+            // Axis 0
+            Raw(b"wght"), // tag
+            Int32(100 << 16), // min value: 100
+            Int32(400 << 16), // default value: 400
+            Int32(900 << 16), // max value: 900
+            UInt16(0), // flags
+            UInt16(256), // name ID
+            // Axis 1
+            Raw(b"wdth"), // tag
+            Int32(50 << 16), // min value: 50
+            Int32(100 << 16), // default value: 100
+            Int32(200 << 16), // max value: 200
+            UInt16(0), // flags
+            UInt16(257), // name ID
+            // Instance 0
+            UInt16(258), // subfamily name ID
+            UInt16(0), // flags
+            Int32(700 << 16), // coordinate 0: 700
+            Int32(100 << 16), // coordinate 1: 100
+            UInt16(259), // PostScript name ID
+            // Instance 1
+            UInt16(260), // subfamily name ID
+            UInt16(0), // flags
+            Int32(400 << 16), // coordinate 0: 400
+            Int32(75 << 16), // coordinate 1: 75
+            UInt16(0xFFFF), // PostScript name ID: not set
+        ])
+    }
+
+    #[test]
+    fn axis_by_tag() {
+        let data = axes_table();
+        let table = Table::parse(&data).unwrap();
+
+        let axis = table.axis_by_tag(Tag::from_bytes(b"wdth")).unwrap();
+        assert_eq!(axis.min_value, 50.0);
+        assert_eq!(axis.def_value, 100.0);
+        assert_eq!(axis.max_value, 200.0);
+        assert_eq!(axis.name_id, 257);
+        assert!(!axis.hidden);
+        assert!(table.axis_by_tag(Tag::from_bytes(b"opsz")).is_none());
+    }
+
+    #[test]
+    fn instances() {
+        let data = axes_table();
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.instances().count(), 2);
+
+        let mut instances = table.instances();
+        let instance = instances.next().unwrap();
+        assert_eq!(instance.subfamily_name_id(), 258);
+        assert_eq!(instance.post_script_name_id(), Some(259));
+        let mut coordinates = instance.coordinates();
+        assert_eq!(coordinates.next(), Some(variation(b"wght", 700.0)));
+        assert_eq!(coordinates.next(), Some(variation(b"wdth", 100.0)));
+        assert_eq!(coordinates.next(), None);
+
+        let instance = instances.next().unwrap();
+        assert_eq!(instance.subfamily_name_id(), 260);
+        assert_eq!(instance.post_script_name_id(), None);
+        assert!(instances.next().is_none());
+    }
+
+    #[test]
+    fn instance_by_name() {
+        let data = axes_table();
+        let table = Table::parse(&data).unwrap();
+        let names = writer::mac_name_table(&[
+            (256, "Weight"),
+            (258, "Semi Bold"),
+            (260, "Black"),
+        ]);
+        let names = crate::name::parse(&names).unwrap();
+
+        let instance = table.instance_by_name(names, "SemiBold").unwrap();
+        assert_eq!(instance.subfamily_name_id(), 258);
+        assert_eq!(instance.coordinates().next(), Some(variation(b"wght", 700.0)));
+        assert_eq!(table.instance_by_name(names, " black ").unwrap().subfamily_name_id(), 260);
+        // Axis names are not instance names.
+        assert!(table.instance_by_name(names, "Weight").is_none());
+        assert!(table.instance_by_name(names, "Bold").is_none());
+    }
+
+    #[test]
+    fn style_axis_value() {
+        let data = axes_table();
+        let table = Table::parse(&data).unwrap();
+        let stat = writer::convert(&[
+            UInt16(1), // major version
+            UInt16(1), // minor version
+            UInt16(8), // design axis size
+            UInt16(2), // design axis count
+            UInt32(20), // design axes offset
+            UInt16(2), // axis value count
+            UInt32(36), // axis value offsets offset
+            UInt16(2), // elided fallback name ID
+            // Axis 0
+            Raw(b"wght"), // tag
+            UInt16(256), // name ID
+            UInt16(0), // ordering
+            // Axis 1
+            Raw(b"ital"), // tag
+            UInt16(257), // name ID
+            UInt16(1), // ordering
+            // Axis value offsets
+            UInt16(4), // offset 0
+            UInt16(16), // offset 1
+            // Axis value 0
+            UInt16(1), // format
+            UInt16(1), // axis index
+            UInt16(2), // flags: elidable
+            UInt16(258), // value name ID
+            Int32(0), // value: 0
+            // Axis value 1
+            UInt16(4), // format
+            UInt16(2), // axis count
+            UInt16(0), // flags
+            UInt16(259), // value name ID
+            UInt16(0), // axis index
+            Int32(700 << 16), // value: 700
+            UInt16(1), // axis index
+            Int32(1 << 16), // value: 1
+        ]);
+        let stat = stat::Table::parse(&stat);
+
+        let wght = Tag::from_bytes(b"wght");
+        let ital = Tag::from_bytes(b"ital");
+        let regular = [NormalizedCoordinate::from(0i16); 2];
+        assert_eq!(table.style_axis_value(stat, &regular, wght), Some(400.0));
+        assert_eq!(table.style_axis_value(stat, &regular, ital), Some(0.0));
+        assert_eq!(table.style_axis_value(None, &regular, ital), None);
+
+        // The multi-axis value matches the normalized weight.
+        let axis = table.axis_by_tag(wght).unwrap();
+        let bold = [axis.normalized_value(700.0), NormalizedCoordinate::from(0i16)];
+        assert_eq!(table.style_axis_value(stat, &bold, ital), Some(1.0));
+    }
+
+    fn variation(axis: &[u8; 4], value: f32) -> Variation {
+        Variation { axis: Tag::from_bytes(axis), value }
+    }
+
+    #[test]
+    fn post_script_name() {
+        let data = axes_table();
+        let axes = Table::parse(&data).unwrap().axes();
+
+        let name = variation_post_script_name(
+            "NotoSans", axes, &[variation(b"wght", 500.0), variation(b"wdth", 80.0)],
+        );
+        assert_eq!(name.as_ref().map(|s| s.as_str()), Some("NotoSans-wght500wdth80"));
+
+        let name = variation_post_script_name("NotoSans", axes, &[variation(b"wdth", 62.5)]);
+        assert_eq!(name.as_ref().map(|s| s.as_str()), Some("NotoSans-wdth62.5"));
+
+        let name = variation_post_script_name("NotoSans", axes, &[variation(b"wght", 400.0)]);
+        assert_eq!(name.as_ref().map(|s| s.as_str()), Some("NotoSans"));
+    }
+
+    #[test]
+    fn axis_value() {
+        let format = |value| {
+            let mut s = String::new();
+            push_axis_value(value, &mut s);
+            s
+        };
+
+        assert_eq!(format(500.0), "500");
+        assert_eq!(format(-12.5), "-12.5");
+        assert_eq!(format(0.333333), "0.33333");
+        assert_eq!(format(-0.000001), "0");
+    }
+}
@@ -1,8 +1,12 @@
 // https://docs.microsoft.com/en-us/typography/opentype/spec/glyf
 
+#[cfg(all(feature = "std", feature = "variable-fonts"))]
+use core::convert::TryFrom;
 use core::num::NonZeroU16;
 
 use crate::parser::{Stream, F2DOT14, LazyArray16, NumFrom};
+#[cfg(all(feature = "std", feature = "variable-fonts"))]
+use crate::parser::TryNumFrom;
 use crate::{loca, GlyphId, OutlineBuilder, Rect, BBox};
 
 pub(crate) struct Builder<'a> {
@@ -528,6 +532,234 @@ pub(crate) fn glyph_bbox(
     })
 }
 
+/// Returns a point of a simple glyph.
+///
+/// Returns `None` for composite and empty glyphs.
+pub(crate) fn simple_glyph_point(
+    loca_table: loca::Table,
+    glyf_table: &[u8],
+    glyph_id: GlyphId,
+    index: u16,
+) -> Option<GlyphPoint> {
+    let range = loca_table.glyph_range(glyph_id)?;
+    let glyph_data = glyf_table.get(range)?;
+    let mut s = Stream::new(glyph_data);
+    let number_of_contours: i16 = s.read()?;
+    let number_of_contours = NonZeroU16::new(core::convert::TryFrom::try_from(number_of_contours).ok()?)?;
+    s.advance(8); // bbox
+    parse_simple_outline(s.tail()?, number_of_contours)?.nth(usize::from(index))
+}
+
+/// Returns components of a composite glyph.
+///
+/// Returns `None` for simple and empty glyphs.
+pub(crate) fn components<'a>(
+    loca_table: loca::Table<'a>,
+    glyf_table: &'a [u8],
+    glyph_id: GlyphId,
+) -> Option<CompositeGlyphIter<'a>> {
+    let range = loca_table.glyph_range(glyph_id)?;
+    let mut s = Stream::new(glyf_table.get(range)?);
+    let number_of_contours: i16 = s.read()?;
+    if number_of_contours >= 0 {
+        return None;
+    }
+
+    s.advance(8); // bbox
+    Some(CompositeGlyphIter::new(s.tail()?))
+}
+
+/// Replaces component glyph IDs of a composite glyph.
+///
+/// Does nothing for simple glyphs.
+#[cfg(feature = "std")]
+pub(crate) fn remap_components(
+    glyph_data: &mut [u8],
+    f: impl Fn(GlyphId) -> GlyphId,
+) -> Option<()> {
+    let number_of_contours: i16 = Stream::read_at(glyph_data, 0)?;
+    if number_of_contours >= 0 {
+        return Some(());
+    }
+
+    let mut offset = 10; // number of contours and bbox
+    loop {
+        let flags = CompositeGlyphFlags(Stream::read_at::<u16>(glyph_data, offset)?);
+        let glyph_id: GlyphId = Stream::read_at(glyph_data, offset + 2)?;
+        let new_id = f(glyph_id).0.to_be_bytes();
+        glyph_data.get_mut(offset + 2..offset + 4)?.copy_from_slice(&new_id);
+
+        offset += 4;
+        offset += if flags.arg_1_and_2_are_words() { 4 } else { 2 };
+        if flags.we_have_a_two_by_two() {
+            offset += 8;
+        } else if flags.we_have_an_x_and_y_scale() {
+            offset += 4;
+        } else if flags.we_have_a_scale() {
+            offset += 2;
+        }
+
+        if !flags.more_components() {
+            return Some(());
+        }
+    }
+}
+
+/// Writes a glyph with points deltas applied.
+///
+/// For simple glyphs, a delta per point is expected,
+/// and for composite glyphs, a component offset delta per component.
+/// Missing deltas are treated as zero. Coordinates are rounded to integers.
+///
+/// The bounding box is copied as is.
+#[cfg(all(feature = "std", feature = "variable-fonts"))]
+pub(crate) fn apply_deltas(
+    glyph_data: &[u8],
+    deltas: &[(f32, f32)],
+    out: &mut std::vec::Vec<u8>,
+) -> Option<()> {
+    let number_of_contours: i16 = Stream::read_at(glyph_data, 0)?;
+    if number_of_contours > 0 {
+        let number_of_contours = NonZeroU16::new(number_of_contours as u16)?;
+        apply_simple_deltas(glyph_data, number_of_contours, deltas, out)
+    } else if number_of_contours < 0 {
+        apply_component_deltas(glyph_data, deltas, out)
+    } else {
+        out.extend_from_slice(glyph_data);
+        Some(())
+    }
+}
+
+#[cfg(all(feature = "std", feature = "variable-fonts"))]
+fn apply_simple_deltas(
+    glyph_data: &[u8],
+    number_of_contours: NonZeroU16,
+    deltas: &[(f32, f32)],
+    out: &mut std::vec::Vec<u8>,
+) -> Option<()> {
+    const ON_CURVE_POINT: u8 = 0x01;
+    const X_SHORT_VECTOR: u8 = 0x02;
+    const Y_SHORT_VECTOR: u8 = 0x04;
+    const X_IS_SAME_OR_POSITIVE: u8 = 0x10;
+    const Y_IS_SAME_OR_POSITIVE: u8 = 0x20;
+
+    let points = parse_simple_outline(glyph_data.get(10..)?, number_of_contours)?;
+    if points.points_left == 0 {
+        // Nothing to vary.
+        out.extend_from_slice(glyph_data);
+        return Some(());
+    }
+
+    // Number of contours, bbox, end points and instructions are copied as is.
+    let mut s = Stream::new(glyph_data);
+    s.advance(10 + usize::from(number_of_contours.get()) * 2);
+    let instructions_len: u16 = s.read()?;
+    s.advance(usize::from(instructions_len));
+    out.extend_from_slice(glyph_data.get(..s.offset())?);
+
+    let mut flags = std::vec::Vec::new();
+    let mut x_coords = std::vec::Vec::new();
+    let mut y_coords = std::vec::Vec::new();
+    let (mut prev_x, mut prev_y) = (0i16, 0i16);
+    for (i, point) in points.enumerate() {
+        let (dx, dy) = deltas.get(i).cloned().unwrap_or((0.0, 0.0));
+        let x = i16::try_num_from(f32::from(point.x) + dx.round())?;
+        let y = i16::try_num_from(f32::from(point.y) + dy.round())?;
+
+        let mut flag = if point.on_curve_point { ON_CURVE_POINT } else { 0 };
+        flag |= write_coordinate(i32::from(x) - i32::from(prev_x),
+                                 X_SHORT_VECTOR, X_IS_SAME_OR_POSITIVE, &mut x_coords)?;
+        flag |= write_coordinate(i32::from(y) - i32::from(prev_y),
+                                 Y_SHORT_VECTOR, Y_IS_SAME_OR_POSITIVE, &mut y_coords)?;
+        flags.push(flag);
+        prev_x = x;
+        prev_y = y;
+    }
+
+    out.extend_from_slice(&flags);
+    out.extend_from_slice(&x_coords);
+    out.extend_from_slice(&y_coords);
+    Some(())
+}
+
+/// Writes a coordinate delta using the shortest form and returns its flags.
+#[cfg(all(feature = "std", feature = "variable-fonts"))]
+fn write_coordinate(n: i32, short_flag: u8, same_flag: u8, out: &mut std::vec::Vec<u8>) -> Option<u8> {
+    if n == 0 {
+        Some(same_flag)
+    } else if let Ok(abs) = u8::try_from(n.abs()) {
+        out.push(abs);
+        Some(if n > 0 { short_flag | same_flag } else { short_flag })
+    } else {
+        out.extend_from_slice(&i16::try_from(n).ok()?.to_be_bytes());
+        Some(0)
+    }
+}
+
+#[cfg(all(feature = "std", feature = "variable-fonts"))]
+fn apply_component_deltas(
+    glyph_data: &[u8],
+    deltas: &[(f32, f32)],
+    out: &mut std::vec::Vec<u8>,
+) -> Option<()> {
+    const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+
+    out.extend_from_slice(glyph_data.get(..10)?); // number of contours and bbox
+    let mut s = Stream::new(glyph_data);
+    s.advance(10);
+    let mut index = 0;
+    loop {
+        let flags = CompositeGlyphFlags(s.read::<u16>()?);
+        let glyph_id: GlyphId = s.read()?;
+        let args_len = if flags.arg_1_and_2_are_words() { 4 } else { 2 };
+        let args = s.read_bytes(args_len)?;
+        if flags.args_are_xy_values() {
+            let (dx, dy) = if flags.arg_1_and_2_are_words() {
+                (f32::from(Stream::read_at::<i16>(args, 0)?), f32::from(Stream::read_at::<i16>(args, 2)?))
+            } else {
+                (f32::from(Stream::read_at::<i8>(args, 0)?), f32::from(Stream::read_at::<i8>(args, 1)?))
+            };
+
+            // Offsets are always written as words, since deltas may not fit into bytes.
+            let (delta_x, delta_y) = deltas.get(index).cloned().unwrap_or((0.0, 0.0));
+            let dx = i16::try_num_from(dx + delta_x.round())?;
+            let dy = i16::try_num_from(dy + delta_y.round())?;
+            out.extend_from_slice(&(flags.0 | ARG_1_AND_2_ARE_WORDS).to_be_bytes());
+            out.extend_from_slice(&glyph_id.0.to_be_bytes());
+            out.extend_from_slice(&dx.to_be_bytes());
+            out.extend_from_slice(&dy.to_be_bytes());
+        } else {
+            // Point numbers are copied as is.
+            out.extend_from_slice(&flags.0.to_be_bytes());
+            out.extend_from_slice(&glyph_id.0.to_be_bytes());
+            out.extend_from_slice(args);
+        }
+
+        let transform_len = if flags.we_have_a_two_by_two() {
+            8
+        } else if flags.we_have_an_x_and_y_scale() {
+            4
+        } else if flags.we_have_a_scale() {
+            2
+        } else {
+            0
+        };
+        out.extend_from_slice(s.read_bytes(transform_len)?);
+
+        index += 1;
+        if !flags.more_components() {
+            break;
+        }
+    }
+
+    // Instructions.
+    if let Some(tail) = s.tail() {
+        out.extend_from_slice(tail);
+    }
+
+    Some(())
+}
+
 #[inline]
 fn outline_impl(
     loca_table: loca::Table,
@@ -662,3 +894,153 @@ fn resolve_coords_len(
 
     Some((x_coords_len, y_coords_len))
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use crate::IndexToLocationFormat;
+    use writer::TtfType::*;
+
+    #[test]
+    fn composite_components() {
+        let loca = writer::convert(&[
+            UInt16(0), // offset [0]
+            UInt16(0), // offset [1]
+            UInt16(5), // offset [2]
+            UInt16(14), // offset [3]
+        ]);
+        let glyf = writer::convert(&[
+            // Glyph 0 is empty.
+            // Glyph 1
+            Int16(1), // number of contours
+            Int16(0), Int16(0), Int16(0), Int16(0), // bbox
+            // Glyph 2
+            Int16(-1), // number of contours
+            Int16(0), Int16(0), Int16(0), Int16(0), // bbox
+            UInt16(0x0002), // flags: args are xy values
+            UInt16(1), // glyph id
+            Int8(10), // dx
+            Int8(-5), // dy
+            Raw(&[0; 2]), // padding
+        ]);
+
+        let loca = loca::Table::parse(&loca, NonZeroU16::new(3).unwrap(),
+                                      IndexToLocationFormat::Short).unwrap();
+        assert!(components(loca, &glyf, GlyphId(0)).is_none());
+        assert!(components(loca, &glyf, GlyphId(1)).is_none());
+        let components: std::vec::Vec<_> = components(loca, &glyf, GlyphId(2)).unwrap().collect();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].glyph_id, GlyphId(1));
+        assert_eq!(components[0].transform.e, 10.0);
+        assert_eq!(components[0].transform.f, -5.0);
+
+        let mut glyph = glyf[10..].to_vec();
+        remap_components(&mut glyph, |id| GlyphId(id.0 + 2)).unwrap();
+        assert_eq!(&glyph[12..14], &[0, 3]);
+    }
+
+    #[test]
+    fn simple_glyph_points() {
+        let loca = writer::convert(&[
+            UInt16(0), // offset [0]
+            UInt16(0), // offset [1]
+            UInt16(11), // offset [2]
+        ]);
+        let glyf = writer::convert(&[
+            // Glyph 0 is empty.
+            // Glyph 1
+            Int16(1), // number of contours
+            Int16(0), Int16(0), Int16(100), Int16(300), // bbox
+            UInt16(2), // end point [0]
+            UInt16(0), // instructions length
+            UInt8(0x31), // flags [0]: on curve, x and y are same
+            UInt8(0x33), // flags [1]: on curve, positive short x, y is same
+            UInt8(0x02), // flags [2]: negative short x
+            UInt8(100), // x [1]
+            UInt8(50), // x [2]
+            Int16(300), // y [2]
+            UInt8(0), // padding
+        ]);
+
+        let loca = loca::Table::parse(&loca, NonZeroU16::new(2).unwrap(),
+                                      IndexToLocationFormat::Short).unwrap();
+        assert!(simple_glyph_point(loca, &glyf, GlyphId(0), 0).is_none());
+        let point = simple_glyph_point(loca, &glyf, GlyphId(1), 2).unwrap();
+        assert_eq!((point.x, point.y), (50, 300));
+        assert!(simple_glyph_point(loca, &glyf, GlyphId(1), 3).is_none());
+    }
+
+    #[cfg(feature = "variable-fonts")]
+    #[test]
+    fn simple_glyph_deltas() {
+        let glyph = writer::convert(&[
+            Int16(1), // number of contours
+            Int16(0), Int16(0), Int16(100), Int16(300), // bbox
+            UInt16(2), // end point [0]
+            UInt16(0), // instructions length
+            UInt8(0x31), // flags [0]: on curve, x and y are same
+            UInt8(0x33), // flags [1]: on curve, positive short x, y is same
+            UInt8(0x02), // flags [2]: negative short x
+            UInt8(100), // x [1]
+            UInt8(50), // x [2]
+            Int16(300), // y [2]
+        ]);
+
+        let mut out = std::vec::Vec::new();
+        apply_deltas(&glyph, &[(1.4, 0.0), (0.0, -0.6), (300.0, 0.0)], &mut out).unwrap();
+        assert_eq!(out, writer::convert(&[
+            Int16(1), // number of contours
+            Int16(0), Int16(0), Int16(100), Int16(300), // bbox
+            UInt16(2), // end point [0]
+            UInt16(0), // instructions length
+            UInt8(0x33), // flags [0]: on curve, positive short x, y is same
+            UInt8(0x17), // flags [1]: on curve, positive short x, negative short y
+            UInt8(0x12), // flags [2]: positive short x
+            UInt8(1), // x [0]
+            UInt8(99), // x [1]
+            UInt8(250), // x [2]
+            UInt8(1), // y [1]
+            Int16(301), // y [2]
+        ]));
+    }
+
+    #[cfg(feature = "variable-fonts")]
+    #[test]
+    fn composite_glyph_deltas() {
+        let glyph = writer::convert(&[
+            Int16(-1), // number of contours
+            Int16(0), Int16(0), Int16(0), Int16(0), // bbox
+            // Component [0]
+            UInt16(0x002A), // flags: args are xy values, scale, more components
+            UInt16(1), // glyph id
+            Int8(10), // dx
+            Int8(-5), // dy
+            Int16(0x4000), // scale: 1.0
+            // Component [1]
+            UInt16(0x0000), // flags
+            UInt16(2), // glyph id
+            UInt8(3), // parent point
+            UInt8(4), // child point
+        ]);
+
+        let mut out = std::vec::Vec::new();
+        apply_deltas(&glyph, &[(0.6, 2.0), (5.0, 5.0)], &mut out).unwrap();
+        assert_eq!(out, writer::convert(&[
+            Int16(-1), // number of contours
+            Int16(0), Int16(0), Int16(0), Int16(0), // bbox
+            // Component [0]
+            UInt16(0x002B), // flags: words, args are xy values, scale, more components
+            UInt16(1), // glyph id
+            Int16(11), // dx
+            Int16(-3), // dy
+            Int16(0x4000), // scale: 1.0
+            // Component [1]
+            UInt16(0x0000), // flags
+            UInt16(2), // glyph id
+            UInt8(3), // parent point
+            UInt8(4), // child point
+        ]));
+    }
+}
@@ -0,0 +1,340 @@
+// https://docs.microsoft.com/en-us/typography/opentype/spec/gpos
+
+use crate::parser::{Offset, Offset16, Offset32, Stream};
+use crate::{ClassDefinitionTable, CoverageTable, GlyphId, LookupTable, Tag};
+
+// GPOS and GSUB tables have the same header.
+pub use super::gsub::Table;
+
+const PAIR_ADJUSTMENT: u16 = 2;
+const EXTENSION: u16 = 9;
+
+const X_PLACEMENT: u16 = 0x0001;
+const Y_PLACEMENT: u16 = 0x0002;
+const X_ADVANCE: u16 = 0x0004;
+
+/// The number of `kern` feature lookups that are resolved in advance.
+const MAX_RESOLVED_LOOKUPS: usize = 64;
+
+/// Pair adjustment lookups of the `kern` feature,
+/// resolved once for kerning many glyph pairs.
+#[derive(Clone, Copy)]
+pub struct KerningLookups<'a> {
+    table: Table<'a>,
+    indices: [u16; MAX_RESOLVED_LOOKUPS],
+    /// `None` when there are too many lookups to store.
+    /// They are resolved for each pair in this case.
+    len: Option<usize>,
+}
+
+impl<'a> KerningLookups<'a> {
+    pub fn new(table: Table<'a>) -> Self {
+        let mut indices = [0; MAX_RESOLVED_LOOKUPS];
+        let mut len = Some(0);
+        for (i, index) in table.feature_lookup_indices(Tag::from_bytes(b"kern")).enumerate() {
+            match indices.get_mut(i) {
+                Some(slot) => *slot = index,
+                None => {
+                    len = None;
+                    break;
+                }
+            }
+
+            len = Some(i + 1);
+        }
+
+        KerningLookups { table, indices, len }
+    }
+
+    /// Checks that the `kern` feature has no lookups.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == Some(0)
+    }
+
+    /// Returns a horizontal kerning for a pair of glyphs.
+    ///
+    /// Only the first glyph X advance is used. Device tables are ignored.
+    ///
+    /// Returns `None` when no lookup has this pair.
+    pub fn glyphs_kerning(&self, left: GlyphId, right: GlyphId) -> Option<i16> {
+        match self.len {
+            Some(len) => {
+                let lookups = self.indices[..len].iter().filter_map(|index| self.table.lookup(*index));
+                lookups_kerning(lookups, left, right)
+            }
+            None => {
+                lookups_kerning(self.table.feature_lookups(Tag::from_bytes(b"kern")), left, right)
+            }
+        }
+    }
+}
+
+fn lookups_kerning<'a>(
+    lookups: impl Iterator<Item = LookupTable<'a>>,
+    left: GlyphId,
+    right: GlyphId,
+) -> Option<i16> {
+    let mut kerning = None;
+    for lookup in lookups {
+        let value = lookup.subtables().find_map(|data| {
+            subtable_kerning(lookup.lookup_type(), data, left, right)
+        });
+
+        if let Some(value) = value {
+            kerning = Some(kerning.unwrap_or(0i16).saturating_add(value));
+        }
+    }
+
+    kerning
+}
+
+fn subtable_kerning(lookup_type: u16, data: &[u8], left: GlyphId, right: GlyphId) -> Option<i16> {
+    let mut s = Stream::new(data);
+    let format: u16 = s.read()?;
+    if lookup_type == EXTENSION {
+        let extension_type: u16 = s.read()?;
+        let offset: Offset32 = s.read()?;
+        if format != 1 || extension_type != PAIR_ADJUSTMENT {
+            return None;
+        }
+
+        return subtable_kerning(extension_type, data.get(offset.to_usize()..)?, left, right);
+    }
+
+    if lookup_type != PAIR_ADJUSTMENT {
+        return None;
+    }
+
+    let coverage_offset: Offset16 = s.read()?;
+    let value_format1: u16 = s.read()?;
+    let value_format2: u16 = s.read()?;
+    let coverage = CoverageTable::new(data.get(coverage_offset.to_usize()..)?);
+    let coverage_index = coverage.index(left)?;
+    let records_size = value_record_size(value_format1) + value_record_size(value_format2);
+
+    let value_record = match format {
+        1 => {
+            let count: u16 = s.read()?;
+            let pair_sets = s.read_array16::<Offset16>(count)?;
+            let pair_set_data = data.get(pair_sets.get(coverage_index)?.to_usize()..)?;
+            let mut s = Stream::new(pair_set_data);
+            let count: u16 = s.read()?;
+            // A pair value record consists of the second glyph and two value records.
+            let record_size = 2 + records_size;
+            let records = s.read_bytes(usize::from(count) * record_size)?;
+
+            // Records are sorted by the second glyph.
+            let mut start = 0;
+            let mut end = usize::from(count);
+            loop {
+                if start >= end {
+                    return None;
+                }
+
+                let index = (start + end) / 2;
+                let mut s = Stream::new_at(records, index * record_size)?;
+                let second: GlyphId = s.read()?;
+                if second < right {
+                    start = index + 1;
+                } else if second > right {
+                    end = index;
+                } else {
+                    break s.read_bytes(records_size)?;
+                }
+            }
+        }
+        2 => {
+            let class_def1_offset: Offset16 = s.read()?;
+            let class_def2_offset: Offset16 = s.read()?;
+            let class1_count: u16 = s.read()?;
+            let class2_count: u16 = s.read()?;
+            let class1 = ClassDefinitionTable::new(data.get(class_def1_offset.to_usize()..)?)
+                .get(left).0;
+            let class2 = ClassDefinitionTable::new(data.get(class_def2_offset.to_usize()..)?)
+                .get(right).0;
+            if class1 >= class1_count || class2 >= class2_count {
+                return None;
+            }
+
+            let index = usize::from(class1) * usize::from(class2_count) + usize::from(class2);
+            s.advance(index * records_size);
+            s.read_bytes(records_size)?
+        }
+        _ => return None,
+    };
+
+    if value_format1 & X_ADVANCE == 0 {
+        return Some(0);
+    }
+
+    // Skip fields preceding X advance.
+    let placements = value_format1 & (X_PLACEMENT | Y_PLACEMENT);
+    Stream::new_at(value_record, placements.count_ones() as usize * 2)?.read::<i16>()
+}
+
+#[inline]
+fn value_record_size(format: u16) -> usize {
+    // Each field is 2 bytes long. Only the lower 8 bits are defined.
+    (format & 0x00FF).count_ones() as usize * 2
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    fn gpos(subtable: &[writer::TtfType]) -> std::vec::Vec<u8> {
+        let mut data = vec![
+            UInt16(1), UInt16(0), // version
+            UInt16(10), // script list offset
+            UInt16(12), // feature list offset
+            UInt16(26), // lookup list offset
+            // Script List
+            UInt16(0), // count
+            // Feature List
+            UInt16(1), // count
+            Raw(b"kern"), // feature tag [0]
+            UInt16(8), // feature offset [0]
+            // Feature
+            UInt16(0), // feature params offset
+            UInt16(1), // lookup count
+            UInt16(0), // lookup index [0]
+            // Lookup List
+            UInt16(1), // count
+            UInt16(4), // lookup offset [0]
+            // Lookup
+            UInt16(2), // lookup type: pair adjustment
+            UInt16(0), // lookup flag
+            UInt16(1), // subtable count
+            UInt16(8), // subtable offset [0]
+        ];
+        data.extend_from_slice(subtable);
+        writer::convert(&data)
+    }
+
+    #[test]
+    fn format1() {
+        let data = gpos(&[
+            UInt16(1), // format
+            UInt16(14), // coverage offset
+            UInt16(0x0005), // value format 1: X placement | X advance
+            UInt16(0), // value format 2
+            UInt16(1), // pair set count
+            UInt16(20), // pair set offset [0]
+            UInt16(0), // padding
+            // Coverage
+            UInt16(1), // format
+            UInt16(1), // glyph count
+            UInt16(1), // glyph [0]
+            // Pair Set
+            UInt16(2), // count
+            // Pair Value Record [0]
+            UInt16(2), // second glyph
+            Int16(5), // X placement
+            Int16(-50), // X advance
+            // Pair Value Record [1]
+            UInt16(4), // second glyph
+            Int16(0), // X placement
+            Int16(-20), // X advance
+        ]);
+
+        let lookups = KerningLookups::new(Table::parse(&data).unwrap());
+        assert!(!lookups.is_empty());
+        assert_eq!(lookups.glyphs_kerning(GlyphId(1), GlyphId(2)), Some(-50));
+        assert_eq!(lookups.glyphs_kerning(GlyphId(1), GlyphId(4)), Some(-20));
+        assert_eq!(lookups.glyphs_kerning(GlyphId(1), GlyphId(3)), None);
+        assert_eq!(lookups.glyphs_kerning(GlyphId(2), GlyphId(2)), None);
+    }
+
+    #[test]
+    fn format2() {
+        let data = gpos(&[
+            UInt16(2), // format
+            UInt16(24), // coverage offset
+            UInt16(0x0004), // value format 1: X advance
+            UInt16(0), // value format 2
+            UInt16(30), // class definition 1 offset
+            UInt16(38), // class definition 2 offset
+            UInt16(1), // class 1 count
+            UInt16(2), // class 2 count
+            // Class 1 Record [0]
+            Int16(0), // X advance [0]
+            Int16(-30), // X advance [1]
+            Raw(&[0; 4]), // padding
+            // Coverage
+            UInt16(1), // format
+            UInt16(1), // glyph count
+            UInt16(1), // glyph [0]
+            // Class Definition 1
+            UInt16(1), // format
+            UInt16(1), // start glyph
+            UInt16(1), // count
+            UInt16(0), // class [0]
+            // Class Definition 2
+            UInt16(1), // format
+            UInt16(5), // start glyph
+            UInt16(1), // count
+            UInt16(1), // class [0]
+        ]);
+
+        let lookups = KerningLookups::new(Table::parse(&data).unwrap());
+        assert_eq!(lookups.glyphs_kerning(GlyphId(1), GlyphId(5)), Some(-30));
+        assert_eq!(lookups.glyphs_kerning(GlyphId(1), GlyphId(6)), Some(0));
+        assert_eq!(lookups.glyphs_kerning(GlyphId(2), GlyphId(5)), None);
+    }
+
+    #[test]
+    fn many_lookups() {
+        // All lookups share the same subtable.
+        let count = MAX_RESOLVED_LOOKUPS as u16 + 1;
+        let mut data = vec![
+            UInt16(1), UInt16(0), // version
+            UInt16(10), // script list offset
+            UInt16(12), // feature list offset
+            UInt16(24 + count * 2), // lookup list offset
+            // Script List
+            UInt16(0), // count
+            // Feature List
+            UInt16(1), // count
+            Raw(b"kern"), // feature tag [0]
+            UInt16(8), // feature offset [0]
+            // Feature
+            UInt16(0), // feature params offset
+            UInt16(count), // lookup count
+        ];
+        data.extend((0..count).map(UInt16)); // lookup indices
+        data.push(UInt16(count)); // lookup count
+        data.extend((0..count).map(|_| UInt16(2 + count * 2))); // lookup offsets
+        data.extend_from_slice(&[
+            // Lookup
+            UInt16(2), // lookup type: pair adjustment
+            UInt16(0), // lookup flag
+            UInt16(1), // subtable count
+            UInt16(8), // subtable offset [0]
+            // Subtable
+            UInt16(1), // format
+            UInt16(12), // coverage offset
+            UInt16(0x0004), // value format 1: X advance
+            UInt16(0), // value format 2
+            UInt16(1), // pair set count
+            UInt16(18), // pair set offset [0]
+            // Coverage
+            UInt16(1), // format
+            UInt16(1), // glyph count
+            UInt16(1), // glyph [0]
+            // Pair Set
+            UInt16(1), // count
+            UInt16(2), // second glyph
+            Int16(-1), // X advance
+        ]);
+
+        let data = writer::convert(&data);
+        let lookups = KerningLookups::new(Table::parse(&data).unwrap());
+        assert_eq!(lookups.len, None);
+        assert_eq!(lookups.glyphs_kerning(GlyphId(1), GlyphId(2)), Some(-(count as i16)));
+        assert_eq!(lookups.glyphs_kerning(GlyphId(2), GlyphId(1)), None);
+    }
+}
@@ -1,7 +1,30 @@
 use crate::{
     parser::{Offset, Offset16, Stream},
-    FeatureListTable, FeatureVariations, LookupListTable, ScriptListTable,
+    FeatureListTable, LangSysTable, Limits, LookupListKind, LookupListTable, LookupStatistics, LookupTable,
+    ScriptListTable, Tag,
 };
+#[cfg(feature = "variable-fonts")]
+use crate::FeatureVariations;
+#[cfg(feature = "std")]
+use crate::{
+    parser::{FromData, LazyArray16, Offset32},
+    CoverageTable, GlyphId, GlyphSet, LookupCoverages, LookupGraph,
+};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(feature = "std")]
+const SINGLE: u16 = 1;
+#[cfg(feature = "std")]
+const MULTIPLE: u16 = 2;
+#[cfg(feature = "std")]
+const ALTERNATE: u16 = 3;
+#[cfg(feature = "std")]
+const LIGATURE: u16 = 4;
+#[cfg(feature = "std")]
+const EXTENSION: u16 = 7;
+#[cfg(feature = "std")]
+const REVERSE_CHAINING_SINGLE: u16 = 8;
 
 #[derive(Clone, Copy)]
 pub struct Table<'a> {
@@ -46,4 +69,278 @@ impl<'a> Table<'a> {
             feature_variations,
         })
     }
+
+    #[cfg(feature = "std")]
+    pub fn lookup_graph(&self, kind: LookupListKind, limits: Limits) -> LookupGraph {
+        LookupGraph::new(self.lookup_list_table, kind, limits)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn lookup_coverages(&self, kind: LookupListKind, limits: Limits) -> LookupCoverages {
+        LookupCoverages::new(self.lookup_list_table, kind, limits)
+    }
+
+    /// Resolves a language system.
+    #[inline]
+    pub fn lang_sys(&self, scripts: &[Tag], language: Option<Tag>) -> Option<LangSysTable<'a>> {
+        self.script_list_table.lang_sys(scripts, language)
+    }
+
+    /// Returns a tag of a feature at a specified index in the feature list.
+    #[inline]
+    pub fn feature_tag(&self, index: u16) -> Option<Tag> {
+        self.feature_list_table.feature_tag(index)
+    }
+
+    /// Returns lookups referenced by features with a specified tag in all scripts.
+    ///
+    /// Lookups are returned once, in the lookup list order.
+    pub fn feature_lookups(&self, tag: Tag) -> impl Iterator<Item = LookupTable<'a>> + 'a {
+        let lookups = self.lookup_list_table;
+        self.feature_lookup_indices(tag).filter_map(move |index| lookups.get(index))
+    }
+
+    /// Returns indices of lookups referenced by features with a specified tag in all scripts.
+    ///
+    /// Indices are returned once, in ascending order.
+    pub fn feature_lookup_indices(&self, tag: Tag) -> impl Iterator<Item = u16> + 'a {
+        let features = self.feature_list_table;
+        (0..self.lookup_list_table.len()).filter(move |index| {
+            features.features(tag).any(|f| f.lookup_indices().into_iter().any(|i| i == *index))
+        })
+    }
+
+    /// Returns a lookup at a specified index in the lookup list.
+    #[inline]
+    pub fn lookup(&self, index: u16) -> Option<LookupTable<'a>> {
+        self.lookup_list_table.get(index)
+    }
+
+    /// Adds all glyphs that can be produced from `glyphs` by substitution lookups.
+    ///
+    /// Contextual lookups are ignored, since the lookups they reference
+    /// are processed anyway. Therefore, the result can contain glyphs
+    /// that are not reachable in practice.
+    #[cfg(feature = "std")]
+    pub fn closure(&self, glyphs: &mut GlyphSet) {
+        // Each iteration can make new ligatures and substitutions possible.
+        // Stop when nothing has changed. The number of iterations is limited
+        // in case of malformed fonts.
+        for _ in 0..=self.lookup_list_table.len() {
+            let mut new_glyphs = Vec::new();
+            for index in 0..self.lookup_list_table.len() {
+                if let Some(lookup) = self.lookup_list_table.get(index) {
+                    for data in lookup.subtables() {
+                        subtable_closure(lookup.lookup_type(), data, glyphs, &mut new_glyphs);
+                    }
+                }
+            }
+
+            let len = glyphs.len();
+            glyphs.extend(new_glyphs);
+            if glyphs.len() == len {
+                break;
+            }
+        }
+    }
+
+    pub fn lookup_statistics(&self, kind: LookupListKind, limits: Limits) -> LookupStatistics {
+        LookupStatistics::new(self.lookup_list_table, kind, limits)
+    }
+
+    #[cfg(feature = "variable-fonts")]
+    pub fn feature_variation_index(
+        &self,
+        coordinates: &[crate::NormalizedCoordinate],
+    ) -> Option<u32> {
+        self.feature_variations?.find_index(coordinates)
+    }
+}
+
+#[cfg(feature = "std")]
+fn subtable_closure(
+    lookup_type: u16,
+    data: &[u8],
+    glyphs: &GlyphSet,
+    new_glyphs: &mut Vec<GlyphId>,
+) -> Option<()> {
+    let mut s = Stream::new(data);
+    let format: u16 = s.read()?;
+    if lookup_type == EXTENSION {
+        let extension_type: u16 = s.read()?;
+        let offset: Offset32 = s.read()?;
+        if format != 1 || extension_type == EXTENSION {
+            return None;
+        }
+
+        return subtable_closure(extension_type, data.get(offset.to_usize()..)?, glyphs, new_glyphs);
+    }
+
+    let coverage_offset: Offset16 = s.read()?;
+    let coverage = CoverageTable::new(data.get(coverage_offset.to_usize()..)?);
+    match (lookup_type, format) {
+        (SINGLE, 1) => {
+            let delta: i16 = s.read()?;
+            for glyph in glyphs {
+                if coverage.contains(glyph) {
+                    // Addition is modulo 65536.
+                    new_glyphs.push(GlyphId(glyph.0.wrapping_add(delta as u16)));
+                }
+            }
+        }
+        (SINGLE, 2) => {
+            let count: u16 = s.read()?;
+            let substitutes = s.read_array16::<GlyphId>(count)?;
+            for glyph in glyphs {
+                if let Some(index) = coverage.index(glyph) {
+                    new_glyphs.extend(substitutes.get(index));
+                }
+            }
+        }
+        (MULTIPLE, 1) | (ALTERNATE, 1) => {
+            // Sequence and alternate set tables have the same structure.
+            let count: u16 = s.read()?;
+            let offsets = s.read_array16::<Offset16>(count)?;
+            for glyph in glyphs {
+                if let Some(index) = coverage.index(glyph) {
+                    let set = offsets.get(index).and_then(|o| data.get(o.to_usize()..));
+                    if let Some(set) = set.and_then(parse_glyph_array) {
+                        new_glyphs.extend(set);
+                    }
+                }
+            }
+        }
+        (LIGATURE, 1) => {
+            let count: u16 = s.read()?;
+            let offsets = s.read_array16::<Offset16>(count)?;
+            for glyph in glyphs {
+                if let Some(index) = coverage.index(glyph) {
+                    if let Some(set) = offsets.get(index).and_then(|o| data.get(o.to_usize()..)) {
+                        ligature_set_closure(set, glyphs, new_glyphs);
+                    }
+                }
+            }
+        }
+        (REVERSE_CHAINING_SINGLE, 1) => {
+            let backtrack_count: u16 = s.read()?;
+            s.advance(usize::from(backtrack_count) * Offset16::SIZE);
+            let lookahead_count: u16 = s.read()?;
+            s.advance(usize::from(lookahead_count) * Offset16::SIZE);
+            let count: u16 = s.read()?;
+            let substitutes = s.read_array16::<GlyphId>(count)?;
+            for glyph in glyphs {
+                if let Some(index) = coverage.index(glyph) {
+                    new_glyphs.extend(substitutes.get(index));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Some(())
+}
+
+#[cfg(feature = "std")]
+fn parse_glyph_array(data: &[u8]) -> Option<LazyArray16<'_, GlyphId>> {
+    let mut s = Stream::new(data);
+    let count: u16 = s.read()?;
+    s.read_array16(count)
+}
+
+#[cfg(feature = "std")]
+fn ligature_set_closure(data: &[u8], glyphs: &GlyphSet, new_glyphs: &mut Vec<GlyphId>) -> Option<()> {
+    let mut s = Stream::new(data);
+    let count: u16 = s.read()?;
+    for offset in s.read_array16::<Offset16>(count)? {
+        let mut s = Stream::new(data.get(offset.to_usize()..)?);
+        let ligature: GlyphId = s.read()?;
+        let component_count: u16 = s.read()?;
+        // The first component is the covered glyph.
+        let components = s.read_array16::<GlyphId>(component_count.saturating_sub(1))?;
+        if components.into_iter().all(|g| glyphs.contains(g)) {
+            new_glyphs.push(ligature);
+        }
+    }
+
+    Some(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    fn gsub(lookups: &[writer::TtfType]) -> std::vec::Vec<u8> {
+        let mut data = vec![
+            UInt16(1), UInt16(0), // version
+            UInt16(10), // script list offset
+            UInt16(12), // feature list offset
+            UInt16(14), // lookup list offset
+            // Script List
+            UInt16(0), // count
+            // Feature List
+            UInt16(0), // count
+        ];
+        data.extend_from_slice(lookups);
+        writer::convert(&data)
+    }
+
+    fn closure(data: &[u8], glyphs: &[u16]) -> std::vec::Vec<u16> {
+        let mut set: GlyphSet = glyphs.iter().map(|g| GlyphId(*g)).collect();
+        Table::parse(data).unwrap().closure(&mut set);
+        set.iter().map(|g| g.0).collect()
+    }
+
+    #[test]
+    fn single_and_ligature() {
+        let data = gsub(&[
+            // Lookup List
+            UInt16(2), // count
+            UInt16(6), // lookup offset [0]
+            UInt16(32), // lookup offset [1]
+            // Lookup [0]
+            UInt16(1), // lookup type: single
+            UInt16(0), // lookup flag
+            UInt16(1), // subtable count
+            UInt16(8), // subtable offset [0]
+            // Single Substitution
+            UInt16(1), // format
+            UInt16(6), // coverage offset
+            Int16(10), // delta
+            // Coverage
+            UInt16(1), // format
+            UInt16(2), // glyph count
+            UInt16(1), // glyph [0]
+            UInt16(2), // glyph [1]
+            Raw(&[0; 4]), // padding
+            // Lookup [1]
+            UInt16(4), // lookup type: ligature
+            UInt16(0), // lookup flag
+            UInt16(1), // subtable count
+            UInt16(8), // subtable offset [0]
+            // Ligature Substitution
+            UInt16(1), // format
+            UInt16(8), // coverage offset
+            UInt16(1), // ligature set count
+            UInt16(14), // ligature set offset [0]
+            // Coverage
+            UInt16(1), // format
+            UInt16(1), // glyph count
+            UInt16(11), // glyph [0]
+            // Ligature Set
+            UInt16(1), // count
+            UInt16(4), // ligature offset [0]
+            // Ligature
+            UInt16(50), // ligature glyph
+            UInt16(2), // component count
+            UInt16(12), // component [1]
+        ]);
+
+        assert_eq!(closure(&data, &[1]), vec![1, 11]);
+        assert_eq!(closure(&data, &[3]), vec![3]);
+        // Both components are produced by the single substitution.
+        assert_eq!(closure(&data, &[1, 2]), vec![1, 2, 11, 12, 50]);
+    }
 }
@@ -4,8 +4,10 @@
 use core::cmp;
 use core::convert::TryFrom;
 use core::num::NonZeroU16;
+#[cfg(feature = "std")]
+use std::vec::Vec;
 
-use crate::{loca, GlyphId, OutlineBuilder, Rect, BBox, NormalizedCoordinate};
+use crate::{loca, GlyphId, OutlineBuilder, Rect, BBox, NormalizedCoordinate, VariationRounding};
 use crate::parser::{Stream, Offset, Offset16, Offset32, LazyArray16, F2DOT14};
 use crate::glyf::{self, Transform};
 
@@ -87,6 +89,59 @@ impl<'a> Table<'a> {
             return None;
         }
 
+        let data = self.glyph_variation_data(glyph_id)?;
+
+        // Ignore empty data.
+        if data.is_empty() {
+            return Some(());
+        }
+
+        parse_variation_data(coordinates, &self.shared_tuple_records, points_len, data, tuples)
+    }
+
+    /// Returns an iterator over raw variation tuples of a glyph.
+    ///
+    /// `points_len` is the number of glyph points without phantom points.
+    fn glyph_variation_tuples(
+        &self,
+        glyph_id: GlyphId,
+        points_len: u16,
+    ) -> Option<GlyphVariationTuples<'a>> {
+        const SHARED_POINT_NUMBERS_FLAG: u16 = 0x8000;
+        const COUNT_MASK: u16 = 0x0FFF;
+
+        let mut tuples = GlyphVariationTuples {
+            axis_count: self.axis_count.get(),
+            shared_tuple_records: self.shared_tuple_records,
+            shared_point_numbers: None,
+            points_len: points_len.checked_add(PHANTOM_POINTS_LEN as u16)?,
+            headers: Stream::new(&[]),
+            serialized: Stream::new(&[]),
+            index: 0,
+            count: 0,
+        };
+
+        let data = self.glyph_variation_data(glyph_id)?;
+        if data.is_empty() {
+            return Some(tuples);
+        }
+
+        let mut s = Stream::new(data);
+        let tuple_variation_count: u16 = s.read()?;
+        let data_offset: Offset16 = s.read()?;
+
+        let mut serialized = Stream::new_at(data, data_offset.to_usize())?;
+        if tuple_variation_count & SHARED_POINT_NUMBERS_FLAG != 0 {
+            tuples.shared_point_numbers = PackedPointsIter::new(&mut serialized)?;
+        }
+
+        tuples.headers = s;
+        tuples.serialized = serialized;
+        tuples.count = tuple_variation_count & COUNT_MASK;
+        Some(tuples)
+    }
+
+    fn glyph_variation_data(&self, glyph_id: GlyphId) -> Option<&'a [u8]> {
         let next_glyph_id = glyph_id.0.checked_add(1)?;
 
         let (start, end) = match self.offsets {
@@ -100,22 +155,271 @@ impl<'a> Table<'a> {
             }
         };
 
-        // Ignore empty data.
-        if start == end {
-            return Some(());
+        self.glyphs_variation_data.get(start..end)
+    }
+}
+
+
+/// A raw glyph variation tuple.
+///
+/// Can be used to inspect `gvar` data without applying it.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct GlyphVariationTuple<'a> {
+    peak_tuple: LazyArray16<'a, F2DOT14>,
+    intermediate_region: Option<(LazyArray16<'a, F2DOT14>, LazyArray16<'a, F2DOT14>)>,
+    point_numbers: Option<PackedPointsIter<'a>>,
+    deltas_count: u16,
+    deltas_data: &'a [u8],
+}
+
+impl<'a> GlyphVariationTuple<'a> {
+    /// Returns tuple's peak coordinates in the `fvar` axes order.
+    #[inline]
+    pub fn peak(&self) -> impl Iterator<Item = NormalizedCoordinate> + 'a {
+        self.peak_tuple.into_iter().map(|v| NormalizedCoordinate::from(v.0))
+    }
+
+    /// Returns tuple's intermediate region start coordinates.
+    ///
+    /// Returns `None` when the tuple doesn't have an intermediate region.
+    #[inline]
+    pub fn intermediate_start(&self) -> Option<impl Iterator<Item = NormalizedCoordinate> + 'a> {
+        let (start, _) = self.intermediate_region?;
+        Some(start.into_iter().map(|v| NormalizedCoordinate::from(v.0)))
+    }
+
+    /// Returns tuple's intermediate region end coordinates.
+    ///
+    /// Returns `None` when the tuple doesn't have an intermediate region.
+    #[inline]
+    pub fn intermediate_end(&self) -> Option<impl Iterator<Item = NormalizedCoordinate> + 'a> {
+        let (_, end) = self.intermediate_region?;
+        Some(end.into_iter().map(|v| NormalizedCoordinate::from(v.0)))
+    }
+
+    /// Returns point numbers referenced by the tuple.
+    ///
+    /// Point numbers can be repeated.
+    ///
+    /// Returns `None` when all glyph points, including four phantom points, are referenced.
+    #[inline]
+    pub fn point_numbers(&self) -> Option<impl Iterator<Item = u16> + 'a> {
+        // The first number is absolute and the rest are differences from the previous one.
+        Some(self.point_numbers?.scan(0u16, |n, delta| {
+            *n = n.checked_add(delta)?;
+            Some(*n)
+        }))
+    }
+
+    /// Returns raw X and Y deltas.
+    ///
+    /// Deltas are in the same order as `point_numbers` or glyph points.
+    #[inline]
+    pub fn deltas(&self) -> impl Iterator<Item = (i16, i16)> + 'a {
+        let mut iter = PackedDeltasIter::new(1.0, self.deltas_count, self.deltas_data);
+        core::iter::from_fn(move || iter.next().map(|(x, y)| (x as i16, y as i16)))
+    }
+}
+
+
+/// An iterator over raw glyph variation tuples.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct GlyphVariationTuples<'a> {
+    axis_count: u16,
+    shared_tuple_records: LazyArray16<'a, F2DOT14>,
+    shared_point_numbers: Option<PackedPointsIter<'a>>,
+    points_len: u16,
+    headers: Stream<'a>,
+    serialized: Stream<'a>,
+    index: u16,
+    count: u16,
+}
+
+impl<'a> Iterator for GlyphVariationTuples<'a> {
+    type Item = GlyphVariationTuple<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.count {
+            return None;
         }
 
-        let data = self.glyphs_variation_data.get(start..end)?;
-        parse_variation_data(coordinates, &self.shared_tuple_records, points_len, data, tuples)
+        self.index += 1;
+
+        let header = parse_raw_tuple_variation_header(
+            self.axis_count, &self.shared_tuple_records, &mut self.headers,
+        );
+        let header = match header {
+            Some(v) => v,
+            None => {
+                // Stop on malformed data.
+                self.index = self.count;
+                return None;
+            }
+        };
+
+        let mut s = Stream::new(self.serialized.read_bytes(usize::from(header.serialized_data_len))?);
+        let point_numbers = if header.has_private_point_numbers {
+            PackedPointsIter::new(&mut s)?
+        } else {
+            self.shared_point_numbers
+        };
+
+        let deltas_count = match point_numbers {
+            Some(iter) => u16::try_from(iter.count()).ok()?,
+            None => self.points_len,
+        };
+
+        Some(GlyphVariationTuple {
+            peak_tuple: header.peak_tuple,
+            intermediate_region: header.intermediate_region,
+            point_numbers,
+            deltas_count,
+            deltas_data: s.tail()?,
+        })
+    }
+}
+
+
+/// Returns an iterator over raw variation tuples of a glyph.
+pub(crate) fn glyph_variation_tuples<'a>(
+    loca_table: loca::Table,
+    glyf_table: &[u8],
+    gvar_table: &Table<'a>,
+    glyph_id: GlyphId,
+) -> Option<GlyphVariationTuples<'a>> {
+    let points_len = glyph_points_len(loca_table, glyf_table, glyph_id)?;
+    gvar_table.glyph_variation_tuples(glyph_id, points_len)
+}
+
+/// Returns phantom points deltas of a glyph.
+///
+/// Phantom points are stored in the following order:
+/// left side bearing, advance width, top side bearing and advance height points.
+///
+/// Used to calculate variable metrics when `HVAR` or `VVAR` tables are not present.
+pub(crate) fn phantom_points_deltas(
+    loca_table: loca::Table,
+    glyf_table: &[u8],
+    gvar_table: &Table,
+    coordinates: &[NormalizedCoordinate],
+    glyph_id: GlyphId,
+) -> Option<[(f32, f32); PHANTOM_POINTS_LEN]> {
+    if coordinates.len() != usize::from(gvar_table.axis_count.get()) {
+        return None;
+    }
+
+    let points_len = glyph_points_len(loca_table, glyf_table, glyph_id)?;
+    let tuples = gvar_table.glyph_variation_tuples(glyph_id, points_len)?;
+
+    let mut phantom_deltas = [(0.0, 0.0); PHANTOM_POINTS_LEN];
+    let mut add_delta = |point: u16, (x, y): (i16, i16), scalar: f32| {
+        if let Some(idx) = point.checked_sub(points_len) {
+            if let Some(delta) = phantom_deltas.get_mut(usize::from(idx)) {
+                delta.0 += f32::from(x) * scalar;
+                delta.1 += f32::from(y) * scalar;
+            }
+        }
+    };
+
+    for tuple in tuples {
+        let scalar = tuple_scalar(coordinates, tuple.peak_tuple, tuple.intermediate_region)?;
+        if scalar <= 0.0 {
+            continue;
+        }
+
+        if let Some(point_numbers) = tuple.point_numbers() {
+            for (point, delta) in point_numbers.zip(tuple.deltas()) {
+                add_delta(point, delta, scalar);
+            }
+        } else {
+            // Phantom points are always at the end.
+            for (point, delta) in tuple.deltas().enumerate().skip(usize::from(points_len)) {
+                add_delta(u16::try_from(point).ok()?, delta, scalar);
+            }
+        }
+    }
+
+    Some(phantom_deltas)
+}
+
+/// Returns the number of glyph points without phantom points.
+///
+/// For composite glyphs, each component is counted as a single point.
+fn glyph_points_len(loca_table: loca::Table, glyf_table: &[u8], glyph_id: GlyphId) -> Option<u16> {
+    // An empty glyph has only phantom points.
+    let mut points_len = 0;
+    if let Some(data) = loca_table.glyph_range(glyph_id).and_then(|range| glyf_table.get(range)) {
+        let mut s = Stream::new(data);
+        let number_of_contours: i16 = s.read()?;
+        s.advance(8); // Skip bbox.
+
+        if number_of_contours > 0 {
+            let number_of_contours = NonZeroU16::new(number_of_contours as u16)?;
+            points_len = glyf::parse_simple_outline(s.tail()?, number_of_contours)?.points_left;
+        } else if number_of_contours < 0 {
+            let components = glyf::CompositeGlyphIter::new(s.tail()?);
+            points_len = u16::try_from(components.count()).ok()?;
+        }
     }
+
+    Some(points_len)
 }
 
 
+/// Returns glyph points deltas.
+///
+/// For composite glyphs, contains a component offset delta per component.
+/// Phantom points are not included.
+#[cfg(feature = "std")]
+pub(crate) fn glyph_deltas(
+    loca_table: loca::Table,
+    glyf_table: &[u8],
+    gvar_table: &Table,
+    coordinates: &[NormalizedCoordinate],
+    glyph_id: GlyphId,
+) -> Option<Vec<(f32, f32)>> {
+    let range = loca_table.glyph_range(glyph_id)?;
+    let mut s = Stream::new(glyf_table.get(range)?);
+    let number_of_contours: i16 = s.read()?;
+    s.advance(8); // Skip bbox.
+
+    let mut tuples = VariationTuples {
+        headers: [VariationTuple::default(); MAX_TUPLES_LEN as usize],
+        len: 0,
+    };
+
+    let mut deltas = Vec::new();
+    if number_of_contours > 0 {
+        let number_of_contours = NonZeroU16::new(number_of_contours as u16)?;
+        let mut glyph_points = glyf::parse_simple_outline(s.tail()?, number_of_contours)?;
+        let all_glyph_points = glyph_points.clone();
+        let points_len = glyph_points.points_left;
+        gvar_table.parse_variation_data(glyph_id, coordinates, points_len, &mut tuples)?;
+
+        while let Some(point) = glyph_points.next() {
+            let (x, y) = tuples.apply(all_glyph_points.clone(), glyph_points.clone(), point)?;
+            deltas.push((x - f32::from(point.x), y - f32::from(point.y)));
+        }
+    } else if number_of_contours < 0 {
+        let components_count = glyf::CompositeGlyphIter::new(s.tail()?).count() as u16;
+        gvar_table.parse_variation_data(glyph_id, coordinates, components_count, &mut tuples)?;
+
+        for _ in 0..components_count {
+            deltas.push(tuples.apply_null()?);
+        }
+    }
+
+    Some(deltas)
+}
+
 pub(crate) fn outline(
     loca_table: loca::Table,
     glyf_table: &[u8],
     gvar_table: &Table,
     coordinates: &[NormalizedCoordinate],
+    rounding: VariationRounding,
     glyph_id: GlyphId,
     builder: &mut dyn OutlineBuilder,
 ) -> Option<Rect> {
@@ -124,18 +428,24 @@ pub(crate) fn outline(
     let range = loca_table.glyph_range(glyph_id)?;
     let glyph_data = glyf_table.get(range)?;
 
-    outline_var_impl(loca_table, glyf_table, gvar_table,
-                     glyph_id, glyph_data, coordinates, 0, &mut b);
+    let ctx = OutlineContext { loca_table, glyf_table, gvar_table, coordinates, rounding };
+    outline_var_impl(&ctx, glyph_id, glyph_data, 0, &mut b);
     b.bbox.and_then(|bbox| bbox.to_rect())
 }
 
-fn outline_var_impl<'a>(
-    loca_table: loca::Table,
-    glyf_table: &[u8],
-    gvar_table: &Table,
+/// Tables and variation settings shared by all components of a glyph.
+struct OutlineContext<'a> {
+    loca_table: loca::Table<'a>,
+    glyf_table: &'a [u8],
+    gvar_table: &'a Table<'a>,
+    coordinates: &'a [NormalizedCoordinate],
+    rounding: VariationRounding,
+}
+
+fn outline_var_impl(
+    ctx: &OutlineContext,
     glyph_id: GlyphId,
     data: &[u8],
-    coordinates: &[NormalizedCoordinate],
     depth: u8,
     builder: &mut glyf::Builder,
 ) -> Option<()> {
@@ -167,10 +477,12 @@ fn outline_var_impl<'a>(
         let mut glyph_points = glyf::parse_simple_outline(s.tail()?, number_of_contours)?;
         let all_glyph_points = glyph_points.clone();
         let points_len = glyph_points.points_left;
-        gvar_table.parse_variation_data(glyph_id, coordinates, points_len, &mut tuples)?;
+        ctx.gvar_table.parse_variation_data(glyph_id, ctx.coordinates, points_len, &mut tuples)?;
 
         while let Some(point) = glyph_points.next() {
             let (x, y) = tuples.apply(all_glyph_points.clone(), glyph_points.clone(), point)?;
+            let x = f32::from(point.x) + ctx.rounding.round(x - f32::from(point.x));
+            let y = f32::from(point.y) + ctx.rounding.round(y - f32::from(point.y));
             builder.push_point(x, y, point.on_curve_point, point.last_point);
         }
 
@@ -188,10 +500,11 @@ fn outline_var_impl<'a>(
 
         let mut components = glyf::CompositeGlyphIter::new(s.tail()?);
         let components_count = components.clone().count() as u16;
-        gvar_table.parse_variation_data(glyph_id, coordinates, components_count, &mut tuples)?;
+        ctx.gvar_table.parse_variation_data(glyph_id, ctx.coordinates, components_count, &mut tuples)?;
 
         while let Some(component) = components.next() {
             let (tx, ty) = tuples.apply_null()?;
+            let (tx, ty) = (ctx.rounding.round(tx), ctx.rounding.round(ty));
 
             let mut transform = builder.transform;
 
@@ -204,12 +517,9 @@ fn outline_var_impl<'a>(
             transform = Transform::combine(transform, component.transform);
 
             let mut b = glyf::Builder::new(transform, builder.bbox, builder.builder);
-            let range = loca_table.glyph_range(component.glyph_id)?;
-            let glyph_data = glyf_table.get(range)?;
-            outline_var_impl(
-                loca_table, glyf_table, gvar_table, component.glyph_id,
-                glyph_data, coordinates, depth + 1, &mut b,
-            )?;
+            let range = ctx.loca_table.glyph_range(component.glyph_id)?;
+            let glyph_data = ctx.glyf_table.get(range)?;
+            outline_var_impl(ctx, component.glyph_id, glyph_data, depth + 1, &mut b)?;
 
             // Take updated bbox.
             builder.bbox = b.bbox;
@@ -225,7 +535,7 @@ fn outline_var_impl<'a>(
 // https://docs.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#tuple-variation-store-header
 fn parse_variation_data<'a>(
     coordinates: &[NormalizedCoordinate],
-    shared_tuple_records: &LazyArray16<F2DOT14>,
+    shared_tuple_records: &LazyArray16<'a, F2DOT14>,
     points_len: u16,
     data: &'a [u8],
     tuples: &mut VariationTuples<'a>,
@@ -244,8 +554,9 @@ fn parse_variation_data<'a>(
 
     // 'The number of tuple variation tables can be any number between 1 and 4095.'
     // No need to check for 4095, because this is 0x0FFF that we masked before.
+    // Some fonts still have zero tuples, which simply means no variations.
     if tuple_variation_count == 0 {
-        return None;
+        return Some(());
     }
 
     if tuple_variation_count >= MAX_TUPLES_LEN {
@@ -331,7 +642,7 @@ impl<'a> VariationTuples<'a> {
         for tuple in self.as_mut_slice() {
             if let Some(ref mut set_points) = tuple.set_points {
                 if set_points.next()? {
-                    if let Some((x_delta, y_delta)) = tuple.deltas.next() {
+                    if let Some((x_delta, y_delta)) = next_delta(&mut tuple.deltas, set_points) {
                         // Remember the last set point and delta.
                         tuple.prev_point = Some(PointAndDelta {
                             x: point.x, y: point.y, x_delta, y_delta
@@ -384,7 +695,7 @@ impl<'a> VariationTuples<'a> {
         for tuple in self.as_mut_slice() {
             if let Some(ref mut set_points) = tuple.set_points {
                 if set_points.next()? {
-                    if let Some((x_delta, y_delta)) = tuple.deltas.next() {
+                    if let Some((x_delta, y_delta)) = next_delta(&mut tuple.deltas, set_points) {
                         x += x_delta;
                         y += y_delta;
                     }
@@ -401,6 +712,22 @@ impl<'a> VariationTuples<'a> {
     }
 }
 
+/// Returns a delta for the current set point.
+///
+/// Deltas of a repeated point number are accumulated.
+#[inline]
+fn next_delta(deltas: &mut PackedDeltasIter, set_points: &SetPointsIter) -> Option<(f32, f32)> {
+    let (mut x, mut y) = deltas.next()?;
+    for _ in 0..set_points.repeats() {
+        if let Some((x_delta, y_delta)) = deltas.next() {
+            x += x_delta;
+            y += y_delta;
+        }
+    }
+
+    Some((x, y))
+}
+
 
 #[derive(Clone, Copy, Default, Debug)]
 struct TupleVariationHeaderData {
@@ -413,7 +740,7 @@ struct TupleVariationHeaderData {
 fn parse_variation_tuples<'a>(
     count: u16,
     coordinates: &[NormalizedCoordinate],
-    shared_tuple_records: &LazyArray16<F2DOT14>,
+    shared_tuple_records: &LazyArray16<'a, F2DOT14>,
     shared_point_numbers: Option<PackedPointsIter<'a>>,
     points_len: u16,
     mut main_s: Stream<'a>,
@@ -440,12 +767,12 @@ fn parse_variation_tuples<'a>(
             shared_point_numbers.clone()
         };
 
-        // TODO: this
         // Since the packed representation can include zero values,
         // it is possible for a given point number to be repeated in the derived point number list.
         // In that case, there will be multiple delta values in the deltas data
         // associated with that point number. All of these deltas must be applied
         // cumulatively to the given point.
+        // This is handled by `SetPointsIter::repeats` and `next_delta`.
 
         let deltas_count = if let Some(point_numbers) = point_numbers.clone() {
             u16::try_from(point_numbers.clone().count()).ok()?
@@ -475,46 +802,28 @@ fn parse_variation_tuples<'a>(
 }
 
 // https://docs.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#tuplevariationheader
-fn parse_tuple_variation_header(
+fn parse_tuple_variation_header<'a>(
     coordinates: &[NormalizedCoordinate],
-    shared_tuple_records: &LazyArray16<F2DOT14>,
-    s: &mut Stream,
+    shared_tuple_records: &LazyArray16<'a, F2DOT14>,
+    s: &mut Stream<'a>,
 ) -> Option<TupleVariationHeaderData> {
-    const EMBEDDED_PEAK_TUPLE_FLAG: u16 = 0x8000;
-    const INTERMEDIATE_REGION_FLAG: u16 = 0x4000;
-    const PRIVATE_POINT_NUMBERS_FLAG: u16 = 0x2000;
-    const TUPLE_INDEX_MASK: u16 = 0x0FFF;
-
-    let serialized_data_size: u16 = s.read()?;
-    let tuple_index: u16 = s.read()?;
-
-    let has_embedded_peak_tuple = tuple_index & EMBEDDED_PEAK_TUPLE_FLAG != 0;
-    let has_intermediate_region = tuple_index & INTERMEDIATE_REGION_FLAG != 0;
-    let has_private_point_numbers = tuple_index & PRIVATE_POINT_NUMBERS_FLAG != 0;
-    let tuple_index = tuple_index & TUPLE_INDEX_MASK;
-
     let axis_count = coordinates.len() as u16;
+    let raw = parse_raw_tuple_variation_header(axis_count, shared_tuple_records, s)?;
+    Some(TupleVariationHeaderData {
+        scalar: tuple_scalar(coordinates, raw.peak_tuple, raw.intermediate_region)?,
+        has_private_point_numbers: raw.has_private_point_numbers,
+        serialized_data_len: raw.serialized_data_len,
+    })
+}
 
-    let peak_tuple = if has_embedded_peak_tuple {
-        s.read_array16::<F2DOT14>(axis_count)?
-    } else {
-        // Use shared tuples.
-        let start = tuple_index.checked_mul(axis_count)?;
-        let end = start.checked_add(axis_count)?;
-        shared_tuple_records.slice(start..end)?
-    };
-
-    let (start_tuple, end_tuple) = if has_intermediate_region {
-        (s.read_array16::<F2DOT14>(axis_count)?, s.read_array16::<F2DOT14>(axis_count)?)
-    } else {
-        (LazyArray16::<F2DOT14>::default(), LazyArray16::<F2DOT14>::default())
-    };
-
-    let mut header = TupleVariationHeaderData {
-        scalar: 0.0,
-        has_private_point_numbers,
-        serialized_data_len: serialized_data_size,
-    };
+fn tuple_scalar(
+    coordinates: &[NormalizedCoordinate],
+    peak_tuple: LazyArray16<F2DOT14>,
+    intermediate_region: Option<(LazyArray16<F2DOT14>, LazyArray16<F2DOT14>)>,
+) -> Option<f32> {
+    let axis_count = coordinates.len() as u16;
+    let (start_tuple, end_tuple) = intermediate_region.unwrap_or_default();
+    let has_intermediate_region = intermediate_region.is_some();
 
     // Calculate the scalar value according to the pseudo-code described at:
     // https://docs.microsoft.com/en-us/typography/opentype/spec/otvaroverview#algorithm-for-interpolation-of-instance-values
@@ -534,7 +843,7 @@ fn parse_tuple_variation_header(
             }
 
             if v < start || v > end {
-                return Some(header);
+                return Some(0.0);
             }
 
             if v < peak {
@@ -549,14 +858,65 @@ fn parse_tuple_variation_header(
         } else if v == 0 || v < cmp::min(0, peak) || v > cmp::max(0, peak) {
             // 'If the instance coordinate is out of range for some axis, then the
             // region and its associated deltas are not applicable.'
-            return Some(header);
+            return Some(0.0);
         } else {
             scalar *= f32::from(v) / f32::from(peak);
         }
     }
 
-    header.scalar = scalar;
-    Some(header)
+    Some(scalar)
+}
+
+struct RawTupleVariationHeader<'a> {
+    serialized_data_len: u16,
+    peak_tuple: LazyArray16<'a, F2DOT14>,
+    intermediate_region: Option<(LazyArray16<'a, F2DOT14>, LazyArray16<'a, F2DOT14>)>,
+    has_private_point_numbers: bool,
+}
+
+fn parse_raw_tuple_variation_header<'a>(
+    axis_count: u16,
+    shared_tuple_records: &LazyArray16<'a, F2DOT14>,
+    s: &mut Stream<'a>,
+) -> Option<RawTupleVariationHeader<'a>> {
+    const EMBEDDED_PEAK_TUPLE_FLAG: u16 = 0x8000;
+    const INTERMEDIATE_REGION_FLAG: u16 = 0x4000;
+    const PRIVATE_POINT_NUMBERS_FLAG: u16 = 0x2000;
+    const TUPLE_INDEX_MASK: u16 = 0x0FFF;
+
+    let serialized_data_size: u16 = s.read()?;
+    let tuple_index: u16 = s.read()?;
+
+    let has_embedded_peak_tuple = tuple_index & EMBEDDED_PEAK_TUPLE_FLAG != 0;
+    let has_intermediate_region = tuple_index & INTERMEDIATE_REGION_FLAG != 0;
+    let has_private_point_numbers = tuple_index & PRIVATE_POINT_NUMBERS_FLAG != 0;
+    let tuple_index = tuple_index & TUPLE_INDEX_MASK;
+
+    let peak_tuple = if has_embedded_peak_tuple {
+        s.read_array16::<F2DOT14>(axis_count)?
+    } else {
+        // Use shared tuples.
+        let start = tuple_index.checked_mul(axis_count)?;
+        let end = start.checked_add(axis_count)?;
+        shared_tuple_records.slice(start..end)?
+    };
+
+    let (start_tuple, end_tuple) = if has_intermediate_region {
+        (s.read_array16::<F2DOT14>(axis_count)?, s.read_array16::<F2DOT14>(axis_count)?)
+    } else {
+        (LazyArray16::<F2DOT14>::default(), LazyArray16::<F2DOT14>::default())
+    };
+
+    Some(RawTupleVariationHeader {
+        serialized_data_len: serialized_data_size,
+        peak_tuple,
+        intermediate_region: if has_intermediate_region {
+            Some((start_tuple, end_tuple))
+        } else {
+            None
+        },
+        has_private_point_numbers,
+    })
 }
 
 
@@ -718,13 +1078,14 @@ mod packed_points {
     pub struct SetPointsIter<'a> {
         iter: PackedPointsIter<'a>,
         unref_count: u16,
+        repeats: u16,
     }
 
     impl<'a> SetPointsIter<'a> {
         #[inline]
         pub fn new(mut iter: PackedPointsIter<'a>) -> Self {
             let unref_count = iter.next().unwrap_or(0);
-            SetPointsIter { iter, unref_count }
+            SetPointsIter { iter, unref_count, repeats: 0 }
         }
 
         #[inline]
@@ -735,7 +1096,13 @@ mod packed_points {
             iter.points_left = 0;
 
             let unref_count = iter.next().unwrap_or(0);
-            SetPointsIter { iter, unref_count }
+            SetPointsIter { iter, unref_count, repeats: 0 }
+        }
+
+        /// Returns how many times the last set point was repeated.
+        #[inline]
+        pub fn repeats(&self) -> u16 {
+            self.repeats
         }
     }
 
@@ -744,15 +1111,20 @@ mod packed_points {
 
         #[inline]
         fn next(&mut self) -> Option<Self::Item> {
+            self.repeats = 0;
+
             if self.unref_count != 0 {
                 self.unref_count -= 1;
                 return Some(false);
             }
 
-            if let Some(unref_count) = self.iter.next() {
-                self.unref_count = unref_count;
-                if self.unref_count != 0 {
-                    self.unref_count -= 1;
+            // A zero delta means that the same point is referenced once again.
+            for unref_count in &mut self.iter {
+                if unref_count == 0 {
+                    self.repeats = self.repeats.saturating_add(1);
+                } else {
+                    self.unref_count = unref_count - 1;
+                    break;
                 }
             }
 
@@ -826,6 +1198,25 @@ mod packed_points {
             assert_eq!(iter.next().unwrap(), true); // Endlessly true.
         }
 
+        #[test]
+        fn repeated_point() {
+            let data = vec![
+                3, // total count
+                gen_control(NewControl { deltas_are_words: false, run_count: 3 }),
+                1, 0, 2
+            ];
+
+            let points_iter = PackedPointsIter::new(&mut Stream::new(&data)).unwrap().unwrap();
+            let mut iter = SetPointsIter::new(points_iter);
+            assert_eq!(iter.next().unwrap(), false);
+            assert_eq!(iter.next().unwrap(), true);
+            assert_eq!(iter.repeats(), 1);
+            assert_eq!(iter.next().unwrap(), false);
+            assert_eq!(iter.repeats(), 0);
+            assert_eq!(iter.next().unwrap(), true);
+            assert_eq!(iter.repeats(), 0);
+        }
+
         #[test]
         fn set_1_and_2() {
             let data = vec![
@@ -1436,9 +1827,15 @@ fn infer_deltas(
         // If not, find the last point with delta in the current contour.
         let mut last_point = None;
         let mut deltas = tuple.deltas.clone();
-        for (point, is_set) in points.clone().zip(points_set.clone()) {
+        let mut points_set = points_set.clone();
+        for point in points.clone() {
+            let is_set = match points_set.next() {
+                Some(v) => v,
+                None => break,
+            };
+
             if is_set {
-                if let Some((x_delta, y_delta)) = deltas.next() {
+                if let Some((x_delta, y_delta)) = next_delta(&mut deltas, &points_set) {
                     last_point = Some(PointAndDelta {
                         x: point.x,
                         y: point.y,
@@ -1465,9 +1862,15 @@ fn infer_deltas(
         // If the current point is not the last one in the contour,
         // find the first set delta in the current contour.
         let mut deltas = tuple.deltas.clone();
-        for (point, is_set) in points.clone().zip(points_set.clone()) {
+        let mut points_set = points_set.clone();
+        for point in points.clone() {
+            let is_set = match points_set.next() {
+                Some(v) => v,
+                None => break,
+            };
+
             if is_set {
-                if let Some((x_delta, y_delta)) = deltas.next() {
+                if let Some((x_delta, y_delta)) = next_delta(&mut deltas, &points_set) {
                     next_point = Some(PointAndDelta {
                         x: point.x,
                         y: point.y,
@@ -1503,7 +1906,7 @@ fn infer_deltas(
             // First, we have to skip already processed contours.
             if contour != current_contour {
                 if is_set {
-                    let _ = deltas.next();
+                    let _ = next_delta(&mut deltas, &points_set);
                 }
 
                 contour = all_points.current_contour();
@@ -1511,7 +1914,7 @@ fn infer_deltas(
             }
 
             if is_set {
-                let (x_delta, y_delta) = deltas.next().unwrap_or((0.0, 0.0));
+                let (x_delta, y_delta) = next_delta(&mut deltas, &points_set).unwrap_or((0.0, 0.0));
                 next_point = Some(PointAndDelta {
                     x: point.x,
                     y: point.y,
@@ -1566,3 +1969,127 @@ fn infer_delta(
         (1.0 - d) * prev_delta + d * next_delta
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn raw_tuples() {
+        let data = writer::convert(&[
+            UInt32(0x00010000), // version
+            UInt16(1), // axis count
+            UInt16(1), // shared tuple count
+            UInt32(24), // offset to shared tuples
+            UInt16(1), // glyph count
+            UInt16(0), // flags: short offsets
+            UInt32(26), // offset to glyph variation data array
+            UInt16(0), // offset 0
+            UInt16(17), // offset 1: 34 / 2
+            // Shared tuples
+            Int16(0x4000), // peak: 1.0
+            // Glyph variation data
+            UInt16(0x8002), // tuple variation count: 2 with shared point numbers
+            UInt16(18), // offset to serialized data
+            // Tuple 0
+            UInt16(6), // serialized data size
+            UInt16(0), // tuple index: shared tuple 0
+            // Tuple 1
+            UInt16(6), // serialized data size
+            UInt16(0xE000), // tuple index: embedded peak, intermediate region, private points
+            Int16(0x2000), // peak: 0.5
+            Int16(0), // intermediate start: 0.0
+            Int16(0x4000), // intermediate end: 1.0
+            // Shared point numbers
+            UInt8(2), // count
+            UInt8(0x01), // control: 2 bytes
+            UInt8(1), // point 1
+            UInt8(2), // point 3
+            // Tuple 0 deltas
+            UInt8(0x01), // control: 2 bytes
+            Int8(10), // x 0
+            Int8(-5), // x 1
+            UInt8(0x01), // control: 2 bytes
+            Int8(3), // y 0
+            Int8(4), // y 1
+            // Tuple 1 private point numbers
+            UInt8(1), // count
+            UInt8(0x00), // control: 1 byte
+            UInt8(0), // point 0
+            // Tuple 1 deltas
+            UInt8(0x00), // control: 1 byte
+            Int8(7), // x 0
+            UInt8(0x80), // control: 1 zero
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        let mut tuples = table.glyph_variation_tuples(GlyphId(0), 4).unwrap();
+
+        let tuple = tuples.next().unwrap();
+        assert_eq!(tuple.peak().collect::<Vec<_>>(), vec![NormalizedCoordinate::from(1.0)]);
+        assert!(tuple.intermediate_start().is_none());
+        assert!(tuple.intermediate_end().is_none());
+        assert_eq!(tuple.point_numbers().unwrap().collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(tuple.deltas().collect::<Vec<_>>(), vec![(10, 3), (-5, 4)]);
+
+        let tuple = tuples.next().unwrap();
+        assert_eq!(tuple.peak().collect::<Vec<_>>(), vec![NormalizedCoordinate::from(0.5)]);
+        assert_eq!(tuple.intermediate_start().unwrap().collect::<Vec<_>>(),
+                   vec![NormalizedCoordinate::from(0.0)]);
+        assert_eq!(tuple.intermediate_end().unwrap().collect::<Vec<_>>(),
+                   vec![NormalizedCoordinate::from(1.0)]);
+        assert_eq!(tuple.point_numbers().unwrap().collect::<Vec<_>>(), vec![0]);
+        assert_eq!(tuple.deltas().collect::<Vec<_>>(), vec![(7, 0)]);
+
+        assert!(tuples.next().is_none());
+    }
+
+    #[test]
+    fn phantom_points() {
+        let data = writer::convert(&[
+            UInt32(0x00010000), // version
+            UInt16(1), // axis count
+            UInt16(0), // shared tuple count
+            UInt32(24), // offset to shared tuples
+            UInt16(1), // glyph count
+            UInt16(0), // flags: short offsets
+            UInt32(24), // offset to glyph variation data array
+            UInt16(0), // offset 0
+            UInt16(8), // offset 1: 16 / 2
+            // Glyph variation data
+            UInt16(1), // tuple variation count: 1
+            UInt16(10), // offset to serialized data
+            // Tuple 0
+            UInt16(6), // serialized data size
+            UInt16(0x8000), // tuple index: embedded peak
+            Int16(0x4000), // peak: 1.0
+            // Tuple 0 deltas
+            UInt8(0x03), // control: 4 bytes
+            Int8(-10), // x 0
+            Int8(20), // x 1
+            Int8(0), // x 2
+            Int8(0), // x 3
+            UInt8(0x83), // control: 4 zeros
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+
+        // An empty glyph.
+        let loca_data = writer::convert(&[UInt16(0), UInt16(0)]);
+        let loca_table = loca::Table::parse(
+            &loca_data, NonZeroU16::new(1).unwrap(), crate::IndexToLocationFormat::Short,
+        ).unwrap();
+
+        let coordinates = [NormalizedCoordinate::from(0.5)];
+        let deltas = phantom_points_deltas(loca_table, &[], &table, &coordinates, GlyphId(0)).unwrap();
+        assert_eq!(deltas, [(-5.0, 0.0), (10.0, 0.0), (0.0, 0.0), (0.0, 0.0)]);
+
+        let coordinates = [NormalizedCoordinate::from(-0.5)];
+        let deltas = phantom_points_deltas(loca_table, &[], &table, &coordinates, GlyphId(0)).unwrap();
+        assert_eq!(deltas, [(0.0, 0.0); 4]);
+    }
+}
@@ -0,0 +1,81 @@
+// https://docs.microsoft.com/en-us/typography/opentype/spec/hdmx
+
+use crate::parser::{Stream, NumFrom};
+
+
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    records: &'a [u8],
+    number_of_records: u16,
+    record_size: usize,
+}
+
+impl<'a> Table<'a> {
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+
+        let version: u16 = s.read()?;
+        if version != 0 {
+            return None;
+        }
+
+        let number_of_records: u16 = s.read()?;
+        let record_size = usize::num_from(s.read::<u32>()?);
+        // A record has at least `pixelSize` and `maxWidth`.
+        if record_size < 2 {
+            return None;
+        }
+
+        let records = s.read_bytes(usize::from(number_of_records).checked_mul(record_size)?)?;
+
+        Some(Table {
+            records,
+            number_of_records,
+            record_size,
+        })
+    }
+
+    /// Returns a maximum advance width in pixels for an exact `pixels_per_em`.
+    pub fn max_advance(&self, pixels_per_em: u16) -> Option<u8> {
+        let mut s = Stream::new(self.records);
+        for _ in 0..self.number_of_records {
+            let pixel_size: u8 = s.read()?;
+            let max_width: u8 = s.read()?;
+            if u16::from(pixel_size) == pixels_per_em {
+                return Some(max_width);
+            }
+
+            s.advance(self.record_size - 2); // widths and padding
+        }
+
+        None
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_advance() {
+        let data = &[
+            0x00, 0x00, // version: 0
+            0x00, 0x02, // number of records: 2
+            0x00, 0x00, 0x00, 0x04, // record size: 4
+            // Record 0
+            0x0B, // pixel size: 11
+            0x07, // max width: 7
+            0x06, 0x07, // widths
+            // Record 1
+            0x0C, // pixel size: 12
+            0x08, // max width: 8
+            0x07, 0x08, // widths
+        ];
+
+        let table = Table::parse(data).unwrap();
+        assert_eq!(table.max_advance(11), Some(7));
+        assert_eq!(table.max_advance(12), Some(8));
+        assert_eq!(table.max_advance(13), None);
+    }
+}
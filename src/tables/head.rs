@@ -1,18 +1,28 @@
 // https://docs.microsoft.com/en-us/typography/opentype/spec/head
 
 use crate::Rect;
-use crate::parser::Stream;
+use crate::parser::{Stream, Fixed, LongDateTime};
 
 
 const TABLE_SIZE: usize = 54;
+const FONT_REVISION_OFFSET: usize = 4;
+const FLAGS_OFFSET: usize = 16;
 const UNITS_PER_EM_OFFSET: usize = 18;
+const CREATED_OFFSET: usize = 20;
+const MODIFIED_OFFSET: usize = 28;
 const BBOX_OFFSET: usize = 36;
+const MAC_STYLE_OFFSET: usize = 44;
+const LOWEST_REC_PPEM_OFFSET: usize = 46;
 const INDEX_TO_LOC_FORMAT_OFFSET: usize = 50;
 
 
+/// A [`loca`](https://docs.microsoft.com/en-us/typography/opentype/spec/loca) offsets format.
 #[derive(Clone, Copy, PartialEq, Debug)]
-pub(crate) enum IndexToLocationFormat {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IndexToLocationFormat {
+    /// 16-bit offsets divided by 2.
     Short,
+    /// 32-bit offsets.
     Long,
 }
 
@@ -25,6 +35,16 @@ pub fn parse(data: &[u8]) -> Option<&[u8]> {
     }
 }
 
+#[inline]
+pub fn font_revision(data: &[u8]) -> Option<f32> {
+    Stream::read_at::<Fixed>(data, FONT_REVISION_OFFSET).map(|v| v.0)
+}
+
+#[inline]
+pub fn flags(data: &[u8]) -> Option<u16> {
+    Stream::read_at(data, FLAGS_OFFSET)
+}
+
 #[inline]
 pub fn units_per_em(data: &[u8]) -> Option<u16> {
     let num: u16 = Stream::read_at(data, UNITS_PER_EM_OFFSET)?;
@@ -46,8 +66,34 @@ pub fn global_bbox(data: &[u8]) -> Option<Rect> {
     })
 }
 
+/// Returns a creation time as a Unix timestamp.
+#[inline]
+pub fn created(data: &[u8]) -> Option<i64> {
+    read_timestamp(data, CREATED_OFFSET)
+}
+
+/// Returns a modification time as a Unix timestamp.
+#[inline]
+pub fn modified(data: &[u8]) -> Option<i64> {
+    read_timestamp(data, MODIFIED_OFFSET)
+}
+
+fn read_timestamp(data: &[u8], offset: usize) -> Option<i64> {
+    Stream::read_at::<LongDateTime>(data, offset)?.to_unix_timestamp()
+}
+
+#[inline]
+pub fn mac_style(data: &[u8]) -> Option<u16> {
+    Stream::read_at(data, MAC_STYLE_OFFSET)
+}
+
+#[inline]
+pub fn lowest_rec_ppem(data: &[u8]) -> Option<u16> {
+    Stream::read_at(data, LOWEST_REC_PPEM_OFFSET)
+}
+
 #[inline]
-pub(crate) fn index_to_loc_format(data: &[u8]) -> Option<IndexToLocationFormat> {
+pub fn index_to_loc_format(data: &[u8]) -> Option<IndexToLocationFormat> {
     let format: i16 = Stream::read_at(data, INDEX_TO_LOC_FORMAT_OFFSET)?;
     match format {
         0 => Some(IndexToLocationFormat::Short),
@@ -55,3 +101,43 @@ pub(crate) fn index_to_loc_format(data: &[u8]) -> Option<IndexToLocationFormat>
         _ => None,
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic() {
+        let data = &[
+            0x00, 0x01, 0x00, 0x00, // version: 1.0
+            0x00, 0x02, 0x80, 0x00, // font revision: 2.5
+            0x00, 0x00, 0x00, 0x00, // checksum adjustment
+            0x5F, 0x0F, 0x3C, 0xF5, // magic number
+            0x00, 0x0B, // flags: 11
+            0x03, 0xE8, // units per EM: 1000
+            0x00, 0x00, 0x00, 0x00, 0x7C, 0x25, 0xB0, 0x80, // created: 1970-01-01
+            0x00, 0x00, 0x00, 0x00, 0xDA, 0x31, 0x91, 0x80, // modified: 2020-01-01
+            0xFF, 0x9C, // x min: -100
+            0xFF, 0x38, // y min: -200
+            0x03, 0xE8, // x max: 1000
+            0x03, 0x84, // y max: 900
+            0x00, 0x03, // mac style: bold, italic
+            0x00, 0x08, // lowest rec PPEM: 8
+            0x00, 0x02, // font direction hint: 2
+            0x00, 0x01, // index to location format: long
+            0x00, 0x00, // glyph data format: 0
+        ];
+
+        let data = parse(data).unwrap();
+        assert_eq!(font_revision(data), Some(2.5));
+        assert_eq!(flags(data), Some(11));
+        assert_eq!(units_per_em(data), Some(1000));
+        assert_eq!(created(data), Some(0));
+        assert_eq!(modified(data), Some(1_577_836_800));
+        assert_eq!(global_bbox(data), Some(Rect { x_min: -100, y_min: -200, x_max: 1000, y_max: 900 }));
+        assert_eq!(mac_style(data), Some(3));
+        assert_eq!(lowest_rec_ppem(data), Some(8));
+        assert_eq!(index_to_loc_format(data), Some(IndexToLocationFormat::Long));
+    }
+}
@@ -9,6 +9,10 @@ const TABLE_SIZE: usize = 36;
 const ASCENDER_OFFSET: usize = 4;
 const DESCENDER_OFFSET: usize = 6;
 const LINE_GAP_OFFSET: usize = 8;
+const ADVANCE_WIDTH_MAX_OFFSET: usize = 10;
+const CARET_SLOPE_RISE_OFFSET: usize = 18;
+const CARET_SLOPE_RUN_OFFSET: usize = 20;
+const CARET_OFFSET_OFFSET: usize = 22;
 const NUMBER_OF_H_METRICS_OFFSET: usize = 34;
 
 
@@ -36,7 +40,71 @@ pub fn line_gap(data: &[u8]) -> i16 {
     Stream::read_at::<i16>(data, LINE_GAP_OFFSET).unwrap_or(0)
 }
 
+#[inline]
+pub fn advance_width_max(data: &[u8]) -> u16 {
+    Stream::read_at::<u16>(data, ADVANCE_WIDTH_MAX_OFFSET).unwrap_or(0)
+}
+
+#[inline]
+pub fn caret_slope_rise(data: &[u8]) -> i16 {
+    Stream::read_at::<i16>(data, CARET_SLOPE_RISE_OFFSET).unwrap_or(1)
+}
+
+#[inline]
+pub fn caret_slope_run(data: &[u8]) -> i16 {
+    Stream::read_at::<i16>(data, CARET_SLOPE_RUN_OFFSET).unwrap_or(0)
+}
+
+#[inline]
+pub fn caret_offset(data: &[u8]) -> i16 {
+    Stream::read_at::<i16>(data, CARET_OFFSET_OFFSET).unwrap_or(0)
+}
+
 #[inline]
 pub fn number_of_h_metrics(data: &[u8]) -> Option<NonZeroU16> {
     Stream::read_at::<u16>(data, NUMBER_OF_H_METRICS_OFFSET).and_then(NonZeroU16::new)
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn caret() {
+        let data = writer::convert(&[
+            UInt32(0x00010000), // version
+            Int16(800), // ascender
+            Int16(-200), // descender
+            Int16(90), // line gap
+            UInt16(1000), // advance width max
+            Int16(0), // min left side bearing
+            Int16(0), // min right side bearing
+            Int16(0), // x max extent
+            Int16(1000), // caret slope rise
+            Int16(213), // caret slope run
+            Int16(-20), // caret offset
+            Int16(0), // reserved
+            Int16(0), // reserved
+            Int16(0), // reserved
+            Int16(0), // reserved
+            Int16(0), // metric data format
+            UInt16(2), // number of h metrics
+        ]);
+
+        let data = parse(&data).unwrap();
+        assert_eq!(ascender(data), 800);
+        assert_eq!(descender(data), -200);
+        assert_eq!(line_gap(data), 90);
+        assert_eq!(caret_slope_rise(data), 1000);
+        assert_eq!(caret_slope_run(data), 213);
+        assert_eq!(caret_offset(data), -20);
+        assert_eq!(number_of_h_metrics(data).map(|n| n.get()), Some(2));
+
+        // Missing data falls back to a vertical caret.
+        assert_eq!(caret_slope_rise(&[]), 1);
+        assert_eq!(caret_slope_run(&[]), 0);
+    }
+}
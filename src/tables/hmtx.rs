@@ -1,5 +1,6 @@
 // https://docs.microsoft.com/en-us/typography/opentype/spec/hmtx
 
+use core::convert::TryFrom;
 use core::num::NonZeroU16;
 
 use crate::GlyphId;
@@ -30,7 +31,7 @@ impl FromData for HorizontalMetrics {
 pub struct Table<'a> {
     metrics: LazyArray16<'a, HorizontalMetrics>,
     bearings: Option<LazyArray16<'a, i16>>,
-    number_of_metrics: u16, // Sum of long metrics + bearings.
+    number_of_metrics: u16, // The number of glyphs with an advance.
 }
 
 impl<'a> Table<'a> {
@@ -40,21 +41,34 @@ impl<'a> Table<'a> {
         number_of_glyphs: NonZeroU16,
     ) -> Option<Self> {
         let mut s = Stream::new(data);
-        let metrics = s.read_array16::<HorizontalMetrics>(number_of_hmetrics.get())?;
 
-        let mut number_of_metrics = number_of_hmetrics.get();
+        // Many fonts have a truncated table, so instead of failing,
+        // we are reading as many records as possible.
+        let available = u16::try_from(data.len() / HorizontalMetrics::SIZE).unwrap_or(u16::MAX);
+        let is_truncated = number_of_hmetrics.get() > available;
+        let metrics_count = number_of_hmetrics.get().min(available);
+        if metrics_count == 0 {
+            return None;
+        }
+
+        let metrics = s.read_array16::<HorizontalMetrics>(metrics_count)?;
 
         // 'If the number_of_hmetrics is less than the total number of glyphs,
         // then that array is followed by an array for the left side bearing values
         // of the remaining glyphs.'
         let bearings_count = number_of_glyphs.get().checked_sub(number_of_hmetrics.get());
-        let bearings = if let Some(count) = bearings_count {
-            number_of_metrics += count;
-            s.read_array16::<i16>(count)
-        } else {
-            None
+        let bearings = match bearings_count {
+            Some(count) if !is_truncated => {
+                let available = s.tail().map(|data| data.len() / i16::SIZE).unwrap_or(0);
+                let count = count.min(u16::try_from(available).unwrap_or(u16::MAX));
+                s.read_array16::<i16>(count)
+            }
+            _ => None,
         };
 
+        // All glyphs must have an advance, even when some records are missing.
+        let number_of_metrics = number_of_glyphs.get().max(number_of_hmetrics.get());
+
         Some(Table {
             metrics,
             bearings,
@@ -99,6 +113,45 @@ impl<'a> Table<'a> {
             None
         }
     }
+
+    /// Checks that glyphs have a single advance or, when `is_marked` is set,
+    /// single and double advances. Zero advances and `is_mark` glyphs are ignored.
+    ///
+    /// Returns `is_marked` when there are no non-zero advances.
+    pub(crate) fn is_monospaced(
+        &self,
+        is_marked: bool,
+        number_of_glyphs: u16,
+        is_mark: impl Fn(GlyphId) -> bool,
+    ) -> bool {
+        let advances = (0..number_of_glyphs)
+            .map(GlyphId)
+            .filter(|glyph_id| !is_mark(*glyph_id))
+            .filter_map(|glyph_id| self.advance(glyph_id))
+            .filter(|n| *n != 0);
+
+        let mut widths: [Option<u16>; 2] = [None, None];
+        for advance in advances {
+            if widths.contains(&Some(advance)) {
+                continue;
+            }
+
+            match widths {
+                [None, _] => widths[0] = Some(advance),
+                [Some(_), None] => widths[1] = Some(advance),
+                _ => return false,
+            }
+        }
+
+        match widths {
+            [None, _] => is_marked,
+            [Some(_), None] => true,
+            [Some(a), Some(b)] => {
+                let (a, b) = (u32::from(a.min(b)), u32::from(a.max(b)));
+                is_marked && a * 2 == b
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -189,4 +242,67 @@ mod tests {
         assert_eq!(table.advance(GlyphId(2)), None);
         assert_eq!(table.side_bearing(GlyphId(2)), None);
     }
+
+    #[test]
+    fn truncated_metrics() {
+        let data = &[
+            0x00, 0x01, // advance width [0]: 1
+            0x00, 0x02, // side bearing [0]: 2
+
+            0x00, 0x03, // advance width [1]: 3
+            0x00, 0x04, // side bearing [1]: 4
+        ];
+
+        let table = Table::parse(data, nzu16!(4), nzu16!(4)).unwrap();
+        assert_eq!(table.advance(GlyphId(1)), Some(3));
+        assert_eq!(table.side_bearing(GlyphId(1)), Some(4));
+        assert_eq!(table.advance(GlyphId(3)), Some(3));
+        assert_eq!(table.side_bearing(GlyphId(3)), None);
+        assert_eq!(table.advance(GlyphId(4)), None);
+    }
+
+    #[test]
+    fn truncated_bearings() {
+        let data = &[
+            0x00, 0x01, // advance width [0]: 1
+            0x00, 0x02, // side bearing [0]: 2
+
+            0x00, 0x03, // side bearing [1]: 3
+        ];
+
+        let table = Table::parse(data, nzu16!(1), nzu16!(3)).unwrap();
+        assert_eq!(table.advance(GlyphId(1)), Some(1));
+        assert_eq!(table.side_bearing(GlyphId(1)), Some(3));
+        assert_eq!(table.advance(GlyphId(2)), Some(1));
+        assert_eq!(table.side_bearing(GlyphId(2)), None);
+        assert_eq!(table.advance(GlyphId(3)), None);
+    }
+
+    #[test]
+    fn monospaced() {
+        let data = &[
+            0x01, 0xF4, // advance width [0]: 500
+            0x00, 0x00, // side bearing [0]: 0
+            0x00, 0x00, // advance width [1]: 0
+            0x00, 0x00, // side bearing [1]: 0
+            0x03, 0xE8, // advance width [2]: 1000
+            0x00, 0x00, // side bearing [2]: 0
+            0x02, 0x58, // advance width [3]: 600
+            0x00, 0x00, // side bearing [3]: 0
+        ];
+
+        let table = Table::parse(data, nzu16!(4), nzu16!(4)).unwrap();
+        let no_marks = |_| false;
+        // Zero advances are ignored.
+        assert!(table.is_monospaced(false, 2, no_marks));
+        // Double advances are allowed only when marked.
+        assert!(!table.is_monospaced(false, 3, no_marks));
+        assert!(table.is_monospaced(true, 3, no_marks));
+        assert!(!table.is_monospaced(true, 4, no_marks));
+        // Marks are ignored.
+        assert!(table.is_monospaced(false, 4, |id: GlyphId| id.0 > 1));
+        // No non-zero advances.
+        assert!(!table.is_monospaced(false, 4, |id: GlyphId| id.0 != 1));
+        assert!(table.is_monospaced(true, 4, |id: GlyphId| id.0 != 1));
+    }
 }
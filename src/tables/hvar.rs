@@ -3,9 +3,11 @@
 use core::convert::TryFrom;
 
 use crate::{GlyphId, NormalizedCoordinate};
-use crate::parser::{Stream, Offset, Offset32};
+use crate::parser::{Stream, Offset, Offset32, NumFrom};
 use crate::var_store::ItemVariationStore;
 
+const VERT_ORIGIN_MAPPING_OFFSET: usize = 20;
+
 #[derive(Clone, Copy)]
 pub struct Table<'a> {
     data: &'a [u8],
@@ -36,7 +38,7 @@ impl<'a> Table<'a> {
     }
 }
 
-
+#[derive(Clone, Copy)]
 pub struct DeltaSetIndexMap<'a> {
     data: &'a [u8],
 }
@@ -49,11 +51,19 @@ impl<'a> DeltaSetIndexMap<'a> {
 
     #[inline]
     pub fn map(&self, glyph_id: GlyphId) -> Option<(u16, u16)> {
-        let mut idx = glyph_id.0;
+        self.map_index(u32::from(glyph_id.0))
+    }
 
+    /// Maps an index to outer and inner delta-set indices.
+    pub fn map_index(&self, mut idx: u32) -> Option<(u16, u16)> {
         let mut s = Stream::new(self.data);
-        let entry_format: u16 = s.read()?;
-        let map_count: u16 = s.read()?;
+        let format: u8 = s.read()?;
+        let entry_format: u8 = s.read()?;
+        let map_count = match format {
+            0 => u32::from(s.read::<u16>()?),
+            1 => s.read::<u32>()?,
+            _ => return None,
+        };
 
         if map_count == 0 {
             return None;
@@ -67,7 +77,7 @@ impl<'a> DeltaSetIndexMap<'a> {
         let entry_size = ((entry_format >> 4) & 3) + 1;
         let inner_index_bit_count = u32::from((entry_format & 0xF) + 1);
 
-        s.advance(usize::from(entry_size) * usize::from(idx));
+        s.advance(usize::from(entry_size) * usize::num_from(idx));
 
         let mut n = 0u32;
         for b in s.read_bytes(usize::from(entry_size))? {
@@ -112,3 +122,16 @@ pub(crate) fn glyph_side_bearing_offset(
     let (outer_idx, inner_idx) = DeltaSetIndexMap::new(set_data).map(glyph_id)?;
     table.variation_store.parse_delta(outer_idx, inner_idx, coordinates)
 }
+
+#[inline]
+pub(crate) fn glyph_y_origin_offset(
+    table: Table,
+    glyph_id: GlyphId,
+    coordinates: &[NormalizedCoordinate],
+) -> Option<f32> {
+    // Vertical origin mapping is present only in the `VVAR` table.
+    let offset: Option<Offset32> = Stream::read_at(table.data, VERT_ORIGIN_MAPPING_OFFSET)?;
+    let set_data = table.data.get(offset?.to_usize()..)?;
+    let (outer_idx, inner_idx) = DeltaSetIndexMap::new(set_data).map(glyph_id)?;
+    table.variation_store.parse_delta(outer_idx, inner_idx, coordinates)
+}
@@ -0,0 +1,375 @@
+/*!
+A [Justification table](https://docs.microsoft.com/en-us/typography/opentype/spec/jstf) implementation.
+*/
+
+use crate::{GlyphId, Tag};
+use crate::parser::{Stream, FromData, LazyArray16, Offset, Offset16};
+
+
+#[derive(Clone, Copy)]
+struct TagRecord {
+    tag: Tag,
+    offset: Offset16,
+}
+
+impl FromData for TagRecord {
+    const SIZE: usize = 6;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(TagRecord {
+            tag: s.read::<Tag>()?,
+            offset: s.read::<Offset16>()?,
+        })
+    }
+}
+
+
+/// A [Justification table](https://docs.microsoft.com/en-us/typography/opentype/spec/jstf).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    data: &'a [u8],
+    scripts: LazyArray16<'a, TagRecord>,
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let major_version: u16 = s.read()?;
+        s.skip::<u16>(); // minor version
+        if major_version != 1 {
+            return None;
+        }
+
+        let count: u16 = s.read()?;
+        let scripts = s.read_array16(count)?;
+        Some(Table { data, scripts })
+    }
+
+    /// Returns an iterator over script tags.
+    #[inline]
+    pub fn script_tags(&self) -> impl Iterator<Item = Tag> + 'a {
+        self.scripts.into_iter().map(|record| record.tag)
+    }
+
+    /// Returns justification data for a script.
+    pub fn script(&self, tag: Tag) -> Option<Script<'a>> {
+        let record = self.scripts.into_iter().find(|record| record.tag == tag)?;
+        Script::parse(self.data.get(record.offset.to_usize()..)?)
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
+}
+
+
+/// A [Justification Script table](https://docs.microsoft.com/en-us/typography/opentype/spec/jstf#jstfscript-table).
+#[derive(Clone, Copy)]
+pub struct Script<'a> {
+    data: &'a [u8],
+    extender_glyphs: LazyArray16<'a, GlyphId>,
+    default_language_offset: Option<Offset16>,
+    languages: LazyArray16<'a, TagRecord>,
+}
+
+impl<'a> Script<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let extender_glyphs_offset: Option<Offset16> = s.read()?;
+        let default_language_offset: Option<Offset16> = s.read()?;
+        let count: u16 = s.read()?;
+        let languages = s.read_array16(count)?;
+
+        let mut extender_glyphs = LazyArray16::default();
+        if let Some(offset) = extender_glyphs_offset {
+            let mut s = Stream::new_at(data, offset.to_usize())?;
+            let count: u16 = s.read()?;
+            extender_glyphs = s.read_array16(count)?;
+        }
+
+        Some(Script { data, extender_glyphs, default_language_offset, languages })
+    }
+
+    /// Returns an iterator over glyphs, that can be inserted to extend a line,
+    /// like the kashida in Arabic.
+    #[inline]
+    pub fn extender_glyphs(&self) -> impl Iterator<Item = GlyphId> + 'a {
+        self.extender_glyphs.into_iter()
+    }
+
+    /// Returns justification data, that is used by languages without an explicit record.
+    #[inline]
+    pub fn default_language(&self) -> Option<LangSys<'a>> {
+        LangSys::parse(self.data.get(self.default_language_offset?.to_usize()..)?)
+    }
+
+    /// Returns an iterator over language system tags.
+    #[inline]
+    pub fn language_tags(&self) -> impl Iterator<Item = Tag> + 'a {
+        self.languages.into_iter().map(|record| record.tag)
+    }
+
+    /// Returns justification data for a language system.
+    pub fn language(&self, tag: Tag) -> Option<LangSys<'a>> {
+        let record = self.languages.into_iter().find(|record| record.tag == tag)?;
+        LangSys::parse(self.data.get(record.offset.to_usize()..)?)
+    }
+}
+
+impl core::fmt::Debug for Script<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Script()")
+    }
+}
+
+
+/// A [Justification Language System table](https://docs.microsoft.com/en-us/typography/opentype/spec/jstf#jstflangsys-table).
+#[derive(Clone, Copy)]
+pub struct LangSys<'a> {
+    data: &'a [u8],
+    offsets: LazyArray16<'a, Offset16>,
+}
+
+impl<'a> LangSys<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let count: u16 = s.read()?;
+        let offsets = s.read_array16(count)?;
+        Some(LangSys { data, offsets })
+    }
+
+    /// Returns the number of priorities.
+    #[inline]
+    pub fn len(&self) -> u16 {
+        self.offsets.len()
+    }
+
+    /// Checks that there are no priorities.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.len() == 0
+    }
+
+    /// Returns a priority at the specified index.
+    ///
+    /// Priorities are ordered from the one that should be tried first.
+    pub fn get(&self, index: u16) -> Option<Priority<'a>> {
+        let offset = self.offsets.get(index)?;
+        self.data.get(offset.to_usize()..).map(|data| Priority { data })
+    }
+}
+
+impl core::fmt::Debug for LangSys<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "LangSys()")
+    }
+}
+
+
+/// A [Justification Priority table](https://docs.microsoft.com/en-us/typography/opentype/spec/jstf#jstfpriority-table).
+///
+/// Lists lookups, that should be enabled or disabled to shrink or extend a line.
+#[derive(Clone, Copy)]
+pub struct Priority<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Priority<'a> {
+    /// Returns `GSUB` lookup indices to enable for shrinking.
+    #[inline]
+    pub fn gsub_shrinkage_enable(&self) -> impl Iterator<Item = u16> + 'a {
+        self.mod_list(0)
+    }
+
+    /// Returns `GSUB` lookup indices to disable for shrinking.
+    #[inline]
+    pub fn gsub_shrinkage_disable(&self) -> impl Iterator<Item = u16> + 'a {
+        self.mod_list(2)
+    }
+
+    /// Returns `GPOS` lookup indices to enable for shrinking.
+    #[inline]
+    pub fn gpos_shrinkage_enable(&self) -> impl Iterator<Item = u16> + 'a {
+        self.mod_list(4)
+    }
+
+    /// Returns `GPOS` lookup indices to disable for shrinking.
+    #[inline]
+    pub fn gpos_shrinkage_disable(&self) -> impl Iterator<Item = u16> + 'a {
+        self.mod_list(6)
+    }
+
+    /// Returns `GPOS`-style lookups, that define the maximum shrinkage.
+    #[inline]
+    pub fn shrinkage_max(&self) -> Option<Max<'a>> {
+        self.max(8)
+    }
+
+    /// Returns `GSUB` lookup indices to enable for extension.
+    #[inline]
+    pub fn gsub_extension_enable(&self) -> impl Iterator<Item = u16> + 'a {
+        self.mod_list(10)
+    }
+
+    /// Returns `GSUB` lookup indices to disable for extension.
+    #[inline]
+    pub fn gsub_extension_disable(&self) -> impl Iterator<Item = u16> + 'a {
+        self.mod_list(12)
+    }
+
+    /// Returns `GPOS` lookup indices to enable for extension.
+    #[inline]
+    pub fn gpos_extension_enable(&self) -> impl Iterator<Item = u16> + 'a {
+        self.mod_list(14)
+    }
+
+    /// Returns `GPOS` lookup indices to disable for extension.
+    #[inline]
+    pub fn gpos_extension_disable(&self) -> impl Iterator<Item = u16> + 'a {
+        self.mod_list(16)
+    }
+
+    /// Returns `GPOS`-style lookups, that define the maximum extension.
+    #[inline]
+    pub fn extension_max(&self) -> Option<Max<'a>> {
+        self.max(18)
+    }
+
+    fn subtable(&self, offset: usize) -> Option<&'a [u8]> {
+        let offset: Option<Offset16> = Stream::read_at(self.data, offset)?;
+        self.data.get(offset?.to_usize()..)
+    }
+
+    /// Parses a JstfModList table.
+    fn mod_list(&self, offset: usize) -> impl Iterator<Item = u16> + 'a {
+        let indices = self.subtable(offset).and_then(|data| {
+            let mut s = Stream::new(data);
+            let count: u16 = s.read()?;
+            s.read_array16::<u16>(count)
+        });
+
+        indices.unwrap_or_default().into_iter()
+    }
+
+    fn max(&self, offset: usize) -> Option<Max<'a>> {
+        let data = self.subtable(offset)?;
+        let mut s = Stream::new(data);
+        let count: u16 = s.read()?;
+        let offsets = s.read_array16(count)?;
+        Some(Max { data, offsets })
+    }
+}
+
+impl core::fmt::Debug for Priority<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Priority()")
+    }
+}
+
+
+/// A [Justification Maximum table](https://docs.microsoft.com/en-us/typography/opentype/spec/jstf#jstfmax-table).
+///
+/// Contains `GPOS`-style lookups, which are not a part of the `GPOS` lookup list.
+#[derive(Clone, Copy)]
+pub struct Max<'a> {
+    data: &'a [u8],
+    offsets: LazyArray16<'a, Offset16>,
+}
+
+impl<'a> Max<'a> {
+    /// Returns the number of lookups.
+    #[inline]
+    pub fn len(&self) -> u16 {
+        self.offsets.len()
+    }
+
+    /// Checks that there are no lookups.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.len() == 0
+    }
+
+    /// Returns raw data of a `GPOS` lookup table at the specified index.
+    pub fn lookup_data(&self, index: u16) -> Option<&'a [u8]> {
+        let offset = self.offsets.get(index)?;
+        self.data.get(offset.to_usize()..)
+    }
+}
+
+impl core::fmt::Debug for Max<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Max()")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn priorities() {
+        let data = writer::convert(&[
+            UInt16(1), // major version
+            UInt16(0), // minor version
+            UInt16(1), // script count
+            Raw(b"arab"), // script tag [0]
+            UInt16(12), // script offset [0]
+            // Script
+            UInt16(6), // extender glyphs offset
+            UInt16(10), // default language offset
+            UInt16(0), // language count
+            // Extender Glyphs
+            UInt16(1), // count
+            UInt16(7), // glyph [0]
+            // LangSys
+            UInt16(1), // priority count
+            UInt16(4), // priority offset [0]
+            // Priority
+            UInt16(20), // GSUB shrinkage enable offset
+            UInt16(0), // GSUB shrinkage disable offset
+            UInt16(0), // GPOS shrinkage enable offset
+            UInt16(0), // GPOS shrinkage disable offset
+            UInt16(0), // shrinkage max offset
+            UInt16(0), // GSUB extension enable offset
+            UInt16(0), // GSUB extension disable offset
+            UInt16(0), // GPOS extension enable offset
+            UInt16(20), // GPOS extension disable offset
+            UInt16(26), // extension max offset
+            // ModList
+            UInt16(2), // count
+            UInt16(3), // lookup index [0]
+            UInt16(5), // lookup index [1]
+            // Max
+            UInt16(1), // count
+            UInt16(4), // lookup offset [0]
+            UInt16(1), // lookup type
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert!(table.script(Tag::from_bytes(b"latn")).is_none());
+        let script = table.script(Tag::from_bytes(b"arab")).unwrap();
+        assert_eq!(script.extender_glyphs().collect::<std::vec::Vec<_>>(), &[GlyphId(7)]);
+        assert!(script.language(Tag::from_bytes(b"URD ")).is_none());
+
+        let lang_sys = script.default_language().unwrap();
+        assert_eq!(lang_sys.len(), 1);
+        assert!(lang_sys.get(1).is_none());
+
+        let priority = lang_sys.get(0).unwrap();
+        assert_eq!(priority.gsub_shrinkage_enable().collect::<std::vec::Vec<_>>(), &[3, 5]);
+        assert_eq!(priority.gsub_shrinkage_disable().count(), 0);
+        assert_eq!(priority.gpos_extension_disable().count(), 2);
+        assert!(priority.shrinkage_max().is_none());
+
+        let max = priority.extension_max().unwrap();
+        assert_eq!(max.len(), 1);
+        assert_eq!(max.lookup_data(0), Some(&[0, 1][..]));
+    }
+}
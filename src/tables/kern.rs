@@ -162,7 +162,6 @@ impl core::fmt::Debug for Subtable<'_> {
 
 
 /// An iterator over kerning subtables.
-#[allow(missing_debug_implementations)]
 #[derive(Clone, Copy, Default)]
 pub struct Subtables<'a> {
     /// Indicates an Apple Advanced Typography format.
@@ -175,6 +174,12 @@ pub struct Subtables<'a> {
     stream: Stream<'a>,
 }
 
+impl core::fmt::Debug for Subtables<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Subtables()")
+    }
+}
+
 impl<'a> Iterator for Subtables<'a> {
     type Item = Subtable<'a>;
 
@@ -0,0 +1,67 @@
+// https://docs.microsoft.com/en-us/typography/opentype/spec/ltsh
+
+use crate::GlyphId;
+use crate::parser::Stream;
+
+
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    y_pixels: &'a [u8],
+}
+
+impl<'a> Table<'a> {
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+
+        let version: u16 = s.read()?;
+        if version != 0 {
+            return None;
+        }
+
+        let number_of_glyphs: u16 = s.read()?;
+        let y_pixels = s.read_bytes(usize::from(number_of_glyphs))?;
+
+        Some(Table { y_pixels })
+    }
+
+    /// Returns the ppem starting from which a glyph's advance scales linearly.
+    ///
+    /// `1` indicates that the glyph always scales linearly.
+    #[inline]
+    pub fn linear_threshold(&self, glyph_id: GlyphId) -> Option<u8> {
+        self.y_pixels.get(usize::from(glyph_id.0)).cloned()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_threshold() {
+        let data = &[
+            0x00, 0x00, // version: 0
+            0x00, 0x03, // number of glyphs: 3
+            0x01, // glyph 0: 1
+            0x0C, // glyph 1: 12
+            0xFF, // glyph 2: 255
+        ];
+
+        let table = Table::parse(data).unwrap();
+        assert_eq!(table.linear_threshold(GlyphId(0)), Some(1));
+        assert_eq!(table.linear_threshold(GlyphId(1)), Some(12));
+        assert_eq!(table.linear_threshold(GlyphId(2)), Some(255));
+        assert_eq!(table.linear_threshold(GlyphId(3)), None);
+    }
+
+    #[test]
+    fn invalid_version() {
+        let data = &[
+            0x00, 0x01, // version: 1
+            0x00, 0x00, // number of glyphs: 0
+        ];
+
+        assert!(Table::parse(data).is_none());
+    }
+}
@@ -0,0 +1,1124 @@
+/*!
+A [Mathematical Typesetting table](https://docs.microsoft.com/en-us/typography/opentype/spec/math) implementation.
+*/
+
+use crate::{Device, GlyphId};
+use crate::ggg::CoverageTable;
+use crate::parser::{Stream, FromData, LazyArray16, Offset, Offset16};
+
+
+/// A [Math Value Record](https://docs.microsoft.com/en-us/typography/opentype/spec/math#mathvaluerecord).
+#[derive(Clone, Copy, Debug)]
+pub struct MathValue<'a> {
+    /// A value in font design units.
+    pub value: i16,
+    /// A device table with device-specific corrections.
+    pub device: Option<Device<'a>>,
+}
+
+impl<'a> MathValue<'a> {
+    /// Parses a record. `data` is the parent table data,
+    /// since device offsets are relative to it.
+    fn parse(data: &'a [u8], s: &mut Stream) -> Option<Self> {
+        let value: i16 = s.read()?;
+        let device_offset: Option<Offset16> = s.read()?;
+        let device = device_offset.and_then(|offset| Device::parse(data.get(offset.to_usize()..)?));
+        Some(MathValue { value, device })
+    }
+}
+
+
+/// A [Mathematical Typesetting table](https://docs.microsoft.com/en-us/typography/opentype/spec/math).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    constants: Option<Constants<'a>>,
+    glyph_info: Option<GlyphInfo<'a>>,
+    variants: Option<Variants<'a>>,
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let major_version: u16 = s.read()?;
+        s.skip::<u16>(); // minor version
+        if major_version != 1 {
+            return None;
+        }
+
+        let constants_offset: Option<Offset16> = s.read()?;
+        let constants = constants_offset
+            .and_then(|offset| data.get(offset.to_usize()..))
+            .and_then(Constants::parse);
+
+        let glyph_info_offset: Option<Offset16> = s.read()?;
+        let glyph_info = glyph_info_offset
+            .and_then(|offset| data.get(offset.to_usize()..))
+            .map(|data| GlyphInfo { data });
+
+        let variants_offset: Option<Offset16> = s.read()?;
+        let variants = variants_offset
+            .and_then(|offset| data.get(offset.to_usize()..))
+            .and_then(Variants::parse);
+
+        Some(Table { constants, glyph_info, variants })
+    }
+
+    /// Returns math layout constants.
+    #[inline]
+    pub fn constants(&self) -> Option<Constants<'a>> {
+        self.constants
+    }
+
+    /// Returns per-glyph math information.
+    #[inline]
+    pub fn glyph_info(&self) -> Option<GlyphInfo<'a>> {
+        self.glyph_info
+    }
+
+    /// Returns glyph size variants and assemblies.
+    #[inline]
+    pub fn variants(&self) -> Option<Variants<'a>> {
+        self.variants
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
+}
+
+
+const CONSTANTS_SIZE: usize = 214;
+const SCRIPT_PERCENT_SCALE_DOWN_OFFSET: usize = 0;
+const SCRIPT_SCRIPT_PERCENT_SCALE_DOWN_OFFSET: usize = 2;
+const DELIMITED_SUB_FORMULA_MIN_HEIGHT_OFFSET: usize = 4;
+const DISPLAY_OPERATOR_MIN_HEIGHT_OFFSET: usize = 6;
+const VALUES_OFFSET: usize = 8;
+const RADICAL_DEGREE_BOTTOM_RAISE_PERCENT_OFFSET: usize = 212;
+const VALUE_RECORD_SIZE: usize = 4;
+
+const MATH_LEADING_INDEX: usize = 0;
+const AXIS_HEIGHT_INDEX: usize = 1;
+const ACCENT_BASE_HEIGHT_INDEX: usize = 2;
+const FLATTENED_ACCENT_BASE_HEIGHT_INDEX: usize = 3;
+const SUBSCRIPT_SHIFT_DOWN_INDEX: usize = 4;
+const SUBSCRIPT_TOP_MAX_INDEX: usize = 5;
+const SUBSCRIPT_BASELINE_DROP_MIN_INDEX: usize = 6;
+const SUPERSCRIPT_SHIFT_UP_INDEX: usize = 7;
+const SUPERSCRIPT_SHIFT_UP_CRAMPED_INDEX: usize = 8;
+const SUPERSCRIPT_BOTTOM_MIN_INDEX: usize = 9;
+const SUPERSCRIPT_BASELINE_DROP_MAX_INDEX: usize = 10;
+const SUB_SUPERSCRIPT_GAP_MIN_INDEX: usize = 11;
+const SUPERSCRIPT_BOTTOM_MAX_WITH_SUBSCRIPT_INDEX: usize = 12;
+const SPACE_AFTER_SCRIPT_INDEX: usize = 13;
+const UPPER_LIMIT_GAP_MIN_INDEX: usize = 14;
+const UPPER_LIMIT_BASELINE_RISE_MIN_INDEX: usize = 15;
+const LOWER_LIMIT_GAP_MIN_INDEX: usize = 16;
+const LOWER_LIMIT_BASELINE_DROP_MIN_INDEX: usize = 17;
+const STACK_TOP_SHIFT_UP_INDEX: usize = 18;
+const STACK_TOP_DISPLAY_STYLE_SHIFT_UP_INDEX: usize = 19;
+const STACK_BOTTOM_SHIFT_DOWN_INDEX: usize = 20;
+const STACK_BOTTOM_DISPLAY_STYLE_SHIFT_DOWN_INDEX: usize = 21;
+const STACK_GAP_MIN_INDEX: usize = 22;
+const STACK_DISPLAY_STYLE_GAP_MIN_INDEX: usize = 23;
+const STRETCH_STACK_TOP_SHIFT_UP_INDEX: usize = 24;
+const STRETCH_STACK_BOTTOM_SHIFT_DOWN_INDEX: usize = 25;
+const STRETCH_STACK_GAP_ABOVE_MIN_INDEX: usize = 26;
+const STRETCH_STACK_GAP_BELOW_MIN_INDEX: usize = 27;
+const FRACTION_NUMERATOR_SHIFT_UP_INDEX: usize = 28;
+const FRACTION_NUMERATOR_DISPLAY_STYLE_SHIFT_UP_INDEX: usize = 29;
+const FRACTION_DENOMINATOR_SHIFT_DOWN_INDEX: usize = 30;
+const FRACTION_DENOMINATOR_DISPLAY_STYLE_SHIFT_DOWN_INDEX: usize = 31;
+const FRACTION_NUMERATOR_GAP_MIN_INDEX: usize = 32;
+const FRACTION_NUM_DISPLAY_STYLE_GAP_MIN_INDEX: usize = 33;
+const FRACTION_RULE_THICKNESS_INDEX: usize = 34;
+const FRACTION_DENOMINATOR_GAP_MIN_INDEX: usize = 35;
+const FRACTION_DENOM_DISPLAY_STYLE_GAP_MIN_INDEX: usize = 36;
+const SKEWED_FRACTION_HORIZONTAL_GAP_INDEX: usize = 37;
+const SKEWED_FRACTION_VERTICAL_GAP_INDEX: usize = 38;
+const OVERBAR_VERTICAL_GAP_INDEX: usize = 39;
+const OVERBAR_RULE_THICKNESS_INDEX: usize = 40;
+const OVERBAR_EXTRA_ASCENDER_INDEX: usize = 41;
+const UNDERBAR_VERTICAL_GAP_INDEX: usize = 42;
+const UNDERBAR_RULE_THICKNESS_INDEX: usize = 43;
+const UNDERBAR_EXTRA_DESCENDER_INDEX: usize = 44;
+const RADICAL_VERTICAL_GAP_INDEX: usize = 45;
+const RADICAL_DISPLAY_STYLE_VERTICAL_GAP_INDEX: usize = 46;
+const RADICAL_RULE_THICKNESS_INDEX: usize = 47;
+const RADICAL_EXTRA_ASCENDER_INDEX: usize = 48;
+const RADICAL_KERN_BEFORE_DEGREE_INDEX: usize = 49;
+const RADICAL_KERN_AFTER_DEGREE_INDEX: usize = 50;
+
+
+/// [Math constants](https://docs.microsoft.com/en-us/typography/opentype/spec/math#mathconstants-table).
+///
+/// All values are in font design units, unless stated otherwise.
+#[derive(Clone, Copy)]
+pub struct Constants<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Constants<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        // Make sure that all values can be read.
+        data.get(0..CONSTANTS_SIZE)?;
+        Some(Constants { data })
+    }
+
+    /// Percentage of scaling down for level 1 superscripts and subscripts.
+    #[inline]
+    pub fn script_percent_scale_down(&self) -> i16 {
+        Stream::read_at(self.data, SCRIPT_PERCENT_SCALE_DOWN_OFFSET).unwrap_or(0)
+    }
+
+    /// Percentage of scaling down for level 2 (scriptScript) superscripts and subscripts.
+    #[inline]
+    pub fn script_script_percent_scale_down(&self) -> i16 {
+        Stream::read_at(self.data, SCRIPT_SCRIPT_PERCENT_SCALE_DOWN_OFFSET).unwrap_or(0)
+    }
+
+    /// Minimum height required for a delimited expression to be treated as a sub-formula.
+    #[inline]
+    pub fn delimited_sub_formula_min_height(&self) -> u16 {
+        Stream::read_at(self.data, DELIMITED_SUB_FORMULA_MIN_HEIGHT_OFFSET).unwrap_or(0)
+    }
+
+    /// Minimum height of n-ary operators (such as integral and summation) for formulas in display mode.
+    #[inline]
+    pub fn display_operator_min_height(&self) -> u16 {
+        Stream::read_at(self.data, DISPLAY_OPERATOR_MIN_HEIGHT_OFFSET).unwrap_or(0)
+    }
+
+    /// White space to be left between math formulas to ensure proper line spacing.
+    #[inline]
+    pub fn math_leading(&self) -> MathValue<'a> {
+        self.value(MATH_LEADING_INDEX)
+    }
+
+    /// Axis height of the font.
+    #[inline]
+    pub fn axis_height(&self) -> MathValue<'a> {
+        self.value(AXIS_HEIGHT_INDEX)
+    }
+
+    /// Maximum (ink) height of accent base that does not require raising the accents.
+    #[inline]
+    pub fn accent_base_height(&self) -> MathValue<'a> {
+        self.value(ACCENT_BASE_HEIGHT_INDEX)
+    }
+
+    /// Maximum (ink) height of accent base that does not require flattening the accents.
+    #[inline]
+    pub fn flattened_accent_base_height(&self) -> MathValue<'a> {
+        self.value(FLATTENED_ACCENT_BASE_HEIGHT_INDEX)
+    }
+
+    /// The standard shift down applied to subscript elements.
+    #[inline]
+    pub fn subscript_shift_down(&self) -> MathValue<'a> {
+        self.value(SUBSCRIPT_SHIFT_DOWN_INDEX)
+    }
+
+    /// Maximum allowed height of the (ink) top of subscripts that does not require moving subscripts further down.
+    #[inline]
+    pub fn subscript_top_max(&self) -> MathValue<'a> {
+        self.value(SUBSCRIPT_TOP_MAX_INDEX)
+    }
+
+    /// Minimum allowed drop of the baseline of subscripts relative to the (ink) bottom of the base.
+    #[inline]
+    pub fn subscript_baseline_drop_min(&self) -> MathValue<'a> {
+        self.value(SUBSCRIPT_BASELINE_DROP_MIN_INDEX)
+    }
+
+    /// Standard shift up applied to superscript elements.
+    #[inline]
+    pub fn superscript_shift_up(&self) -> MathValue<'a> {
+        self.value(SUPERSCRIPT_SHIFT_UP_INDEX)
+    }
+
+    /// Standard shift of superscripts relative to the base, in cramped style.
+    #[inline]
+    pub fn superscript_shift_up_cramped(&self) -> MathValue<'a> {
+        self.value(SUPERSCRIPT_SHIFT_UP_CRAMPED_INDEX)
+    }
+
+    /// Minimum allowed height of the (ink) bottom of superscripts that does not require moving subscripts further up.
+    #[inline]
+    pub fn superscript_bottom_min(&self) -> MathValue<'a> {
+        self.value(SUPERSCRIPT_BOTTOM_MIN_INDEX)
+    }
+
+    /// Maximum allowed drop of the baseline of superscripts relative to the (ink) top of the base.
+    #[inline]
+    pub fn superscript_baseline_drop_max(&self) -> MathValue<'a> {
+        self.value(SUPERSCRIPT_BASELINE_DROP_MAX_INDEX)
+    }
+
+    /// Minimum gap between the superscript and subscript ink.
+    #[inline]
+    pub fn sub_superscript_gap_min(&self) -> MathValue<'a> {
+        self.value(SUB_SUPERSCRIPT_GAP_MIN_INDEX)
+    }
+
+    /// The maximum level to which the (ink) bottom of superscript can be pushed to increase the gap between superscript and subscript.
+    #[inline]
+    pub fn superscript_bottom_max_with_subscript(&self) -> MathValue<'a> {
+        self.value(SUPERSCRIPT_BOTTOM_MAX_WITH_SUBSCRIPT_INDEX)
+    }
+
+    /// Extra white space to be added after each subscript and superscript.
+    #[inline]
+    pub fn space_after_script(&self) -> MathValue<'a> {
+        self.value(SPACE_AFTER_SCRIPT_INDEX)
+    }
+
+    /// Minimum gap between the (ink) bottom of the upper limit, and the (ink) top of the base operator.
+    #[inline]
+    pub fn upper_limit_gap_min(&self) -> MathValue<'a> {
+        self.value(UPPER_LIMIT_GAP_MIN_INDEX)
+    }
+
+    /// Minimum distance between baseline of upper limit and (ink) top of the base operator.
+    #[inline]
+    pub fn upper_limit_baseline_rise_min(&self) -> MathValue<'a> {
+        self.value(UPPER_LIMIT_BASELINE_RISE_MIN_INDEX)
+    }
+
+    /// Minimum gap between (ink) top of the lower limit, and (ink) bottom of the base operator.
+    #[inline]
+    pub fn lower_limit_gap_min(&self) -> MathValue<'a> {
+        self.value(LOWER_LIMIT_GAP_MIN_INDEX)
+    }
+
+    /// Minimum distance between baseline of the lower limit and (ink) bottom of the base operator.
+    #[inline]
+    pub fn lower_limit_baseline_drop_min(&self) -> MathValue<'a> {
+        self.value(LOWER_LIMIT_BASELINE_DROP_MIN_INDEX)
+    }
+
+    /// Standard shift up applied to the top element of a stack.
+    #[inline]
+    pub fn stack_top_shift_up(&self) -> MathValue<'a> {
+        self.value(STACK_TOP_SHIFT_UP_INDEX)
+    }
+
+    /// Standard shift up applied to the top element of a stack in display style.
+    #[inline]
+    pub fn stack_top_display_style_shift_up(&self) -> MathValue<'a> {
+        self.value(STACK_TOP_DISPLAY_STYLE_SHIFT_UP_INDEX)
+    }
+
+    /// Standard shift down applied to the bottom element of a stack.
+    #[inline]
+    pub fn stack_bottom_shift_down(&self) -> MathValue<'a> {
+        self.value(STACK_BOTTOM_SHIFT_DOWN_INDEX)
+    }
+
+    /// Standard shift down applied to the bottom element of a stack in display style.
+    #[inline]
+    pub fn stack_bottom_display_style_shift_down(&self) -> MathValue<'a> {
+        self.value(STACK_BOTTOM_DISPLAY_STYLE_SHIFT_DOWN_INDEX)
+    }
+
+    /// Minimum gap between (ink) bottom of the top element of a stack, and the (ink) top of the bottom element.
+    #[inline]
+    pub fn stack_gap_min(&self) -> MathValue<'a> {
+        self.value(STACK_GAP_MIN_INDEX)
+    }
+
+    /// Minimum gap between (ink) bottom of the top element of a stack, and the (ink) top of the bottom element in display style.
+    #[inline]
+    pub fn stack_display_style_gap_min(&self) -> MathValue<'a> {
+        self.value(STACK_DISPLAY_STYLE_GAP_MIN_INDEX)
+    }
+
+    /// Standard shift up applied to the top element of the stretch stack.
+    #[inline]
+    pub fn stretch_stack_top_shift_up(&self) -> MathValue<'a> {
+        self.value(STRETCH_STACK_TOP_SHIFT_UP_INDEX)
+    }
+
+    /// Standard shift down applied to the bottom element of the stretch stack.
+    #[inline]
+    pub fn stretch_stack_bottom_shift_down(&self) -> MathValue<'a> {
+        self.value(STRETCH_STACK_BOTTOM_SHIFT_DOWN_INDEX)
+    }
+
+    /// Minimum gap between the ink of the stretched element, and the (ink) bottom of the element above.
+    #[inline]
+    pub fn stretch_stack_gap_above_min(&self) -> MathValue<'a> {
+        self.value(STRETCH_STACK_GAP_ABOVE_MIN_INDEX)
+    }
+
+    /// Minimum gap between the ink of the stretched element, and the (ink) top of the element below.
+    #[inline]
+    pub fn stretch_stack_gap_below_min(&self) -> MathValue<'a> {
+        self.value(STRETCH_STACK_GAP_BELOW_MIN_INDEX)
+    }
+
+    /// Standard shift up applied to the numerator.
+    #[inline]
+    pub fn fraction_numerator_shift_up(&self) -> MathValue<'a> {
+        self.value(FRACTION_NUMERATOR_SHIFT_UP_INDEX)
+    }
+
+    /// Standard shift up applied to the numerator in display style.
+    #[inline]
+    pub fn fraction_numerator_display_style_shift_up(&self) -> MathValue<'a> {
+        self.value(FRACTION_NUMERATOR_DISPLAY_STYLE_SHIFT_UP_INDEX)
+    }
+
+    /// Standard shift down applied to the denominator.
+    #[inline]
+    pub fn fraction_denominator_shift_down(&self) -> MathValue<'a> {
+        self.value(FRACTION_DENOMINATOR_SHIFT_DOWN_INDEX)
+    }
+
+    /// Standard shift down applied to the denominator in display style.
+    #[inline]
+    pub fn fraction_denominator_display_style_shift_down(&self) -> MathValue<'a> {
+        self.value(FRACTION_DENOMINATOR_DISPLAY_STYLE_SHIFT_DOWN_INDEX)
+    }
+
+    /// Minimum tolerated gap between the (ink) bottom of the numerator and the ink of the fraction bar.
+    #[inline]
+    pub fn fraction_numerator_gap_min(&self) -> MathValue<'a> {
+        self.value(FRACTION_NUMERATOR_GAP_MIN_INDEX)
+    }
+
+    /// Minimum tolerated gap between the (ink) bottom of the numerator and the ink of the fraction bar in display style.
+    #[inline]
+    pub fn fraction_num_display_style_gap_min(&self) -> MathValue<'a> {
+        self.value(FRACTION_NUM_DISPLAY_STYLE_GAP_MIN_INDEX)
+    }
+
+    /// Thickness of the fraction bar.
+    #[inline]
+    pub fn fraction_rule_thickness(&self) -> MathValue<'a> {
+        self.value(FRACTION_RULE_THICKNESS_INDEX)
+    }
+
+    /// Minimum tolerated gap between the (ink) top of the denominator and the ink of the fraction bar.
+    #[inline]
+    pub fn fraction_denominator_gap_min(&self) -> MathValue<'a> {
+        self.value(FRACTION_DENOMINATOR_GAP_MIN_INDEX)
+    }
+
+    /// Minimum tolerated gap between the (ink) top of the denominator and the ink of the fraction bar in display style.
+    #[inline]
+    pub fn fraction_denom_display_style_gap_min(&self) -> MathValue<'a> {
+        self.value(FRACTION_DENOM_DISPLAY_STYLE_GAP_MIN_INDEX)
+    }
+
+    /// Horizontal distance between the top and bottom elements of a skewed fraction.
+    #[inline]
+    pub fn skewed_fraction_horizontal_gap(&self) -> MathValue<'a> {
+        self.value(SKEWED_FRACTION_HORIZONTAL_GAP_INDEX)
+    }
+
+    /// Vertical distance between the ink of the top and bottom elements of a skewed fraction.
+    #[inline]
+    pub fn skewed_fraction_vertical_gap(&self) -> MathValue<'a> {
+        self.value(SKEWED_FRACTION_VERTICAL_GAP_INDEX)
+    }
+
+    /// Distance between the overbar and the (ink) top of he base.
+    #[inline]
+    pub fn overbar_vertical_gap(&self) -> MathValue<'a> {
+        self.value(OVERBAR_VERTICAL_GAP_INDEX)
+    }
+
+    /// Thickness of overbar.
+    #[inline]
+    pub fn overbar_rule_thickness(&self) -> MathValue<'a> {
+        self.value(OVERBAR_RULE_THICKNESS_INDEX)
+    }
+
+    /// Extra white space reserved above the overbar.
+    #[inline]
+    pub fn overbar_extra_ascender(&self) -> MathValue<'a> {
+        self.value(OVERBAR_EXTRA_ASCENDER_INDEX)
+    }
+
+    /// Distance between underbar and (ink) bottom of the base.
+    #[inline]
+    pub fn underbar_vertical_gap(&self) -> MathValue<'a> {
+        self.value(UNDERBAR_VERTICAL_GAP_INDEX)
+    }
+
+    /// Thickness of underbar.
+    #[inline]
+    pub fn underbar_rule_thickness(&self) -> MathValue<'a> {
+        self.value(UNDERBAR_RULE_THICKNESS_INDEX)
+    }
+
+    /// Extra white space reserved below the underbar.
+    #[inline]
+    pub fn underbar_extra_descender(&self) -> MathValue<'a> {
+        self.value(UNDERBAR_EXTRA_DESCENDER_INDEX)
+    }
+
+    /// Space between the (ink) top of the expression and the bar over it.
+    #[inline]
+    pub fn radical_vertical_gap(&self) -> MathValue<'a> {
+        self.value(RADICAL_VERTICAL_GAP_INDEX)
+    }
+
+    /// Space between the (ink) top of the expression and the bar over it in display style.
+    #[inline]
+    pub fn radical_display_style_vertical_gap(&self) -> MathValue<'a> {
+        self.value(RADICAL_DISPLAY_STYLE_VERTICAL_GAP_INDEX)
+    }
+
+    /// Thickness of the radical rule.
+    #[inline]
+    pub fn radical_rule_thickness(&self) -> MathValue<'a> {
+        self.value(RADICAL_RULE_THICKNESS_INDEX)
+    }
+
+    /// Extra white space reserved above the radical.
+    #[inline]
+    pub fn radical_extra_ascender(&self) -> MathValue<'a> {
+        self.value(RADICAL_EXTRA_ASCENDER_INDEX)
+    }
+
+    /// Extra horizontal kern before the degree of a radical, if such is present.
+    #[inline]
+    pub fn radical_kern_before_degree(&self) -> MathValue<'a> {
+        self.value(RADICAL_KERN_BEFORE_DEGREE_INDEX)
+    }
+
+    /// Negative kern after the degree of a radical, if such is present.
+    #[inline]
+    pub fn radical_kern_after_degree(&self) -> MathValue<'a> {
+        self.value(RADICAL_KERN_AFTER_DEGREE_INDEX)
+    }
+
+    /// Height of the bottom of the radical degree, if such is present,
+    /// in proportion to the height (ascender + descender) of the radical sign.
+    #[inline]
+    pub fn radical_degree_bottom_raise_percent(&self) -> i16 {
+        Stream::read_at(self.data, RADICAL_DEGREE_BOTTOM_RAISE_PERCENT_OFFSET).unwrap_or(0)
+    }
+
+    fn value(&self, index: usize) -> MathValue<'a> {
+        let offset = VALUES_OFFSET + index * VALUE_RECORD_SIZE;
+        Stream::new_at(self.data, offset)
+            .and_then(|mut s| MathValue::parse(self.data, &mut s))
+            .unwrap_or(MathValue { value: 0, device: None })
+    }
+}
+
+impl core::fmt::Debug for Constants<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Constants()")
+    }
+}
+
+
+/// A [Math Glyph Info table](https://docs.microsoft.com/en-us/typography/opentype/spec/math#mathglyphinfo-table).
+#[derive(Clone, Copy)]
+pub struct GlyphInfo<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> GlyphInfo<'a> {
+    /// Returns glyph's italics correction.
+    #[inline]
+    pub fn italics_correction(&self, glyph_id: GlyphId) -> Option<MathValue<'a>> {
+        self.value_by_glyph(0, glyph_id)
+    }
+
+    /// Returns glyph's horizontal position for attaching mathematical accents.
+    #[inline]
+    pub fn top_accent_attachment(&self, glyph_id: GlyphId) -> Option<MathValue<'a>> {
+        self.value_by_glyph(2, glyph_id)
+    }
+
+    /// Checks that the glyph is an extended shape.
+    ///
+    /// Extended shapes are glyphs that should be treated specially
+    /// when positioning superscripts and subscripts, like variants of tall operators.
+    #[inline]
+    pub fn is_extended_shape(&self, glyph_id: GlyphId) -> bool {
+        self.subtable(4)
+            .map(|data| CoverageTable::new(data).contains(glyph_id))
+            .unwrap_or(false)
+    }
+
+    /// Returns glyph's kerning information.
+    pub fn kern_info(&self, glyph_id: GlyphId) -> Option<KernInfo<'a>> {
+        let data = self.subtable(6)?;
+        let mut s = Stream::new(data);
+        let coverage_offset: Offset16 = s.read()?;
+        let count: u16 = s.read()?;
+        let coverage = CoverageTable::new(data.get(coverage_offset.to_usize()..)?);
+        let index = coverage.index(glyph_id)?;
+        if index >= count {
+            return None;
+        }
+
+        // Each record has four offsets.
+        s.advance(usize::from(index) * 8);
+        let mut kern = || -> Option<Option<Kern<'a>>> {
+            let offset: Option<Offset16> = s.read()?;
+            Some(offset.and_then(|offset| Kern::parse(data.get(offset.to_usize()..)?)))
+        };
+
+        Some(KernInfo {
+            top_right: kern()?,
+            top_left: kern()?,
+            bottom_right: kern()?,
+            bottom_left: kern()?,
+        })
+    }
+
+    fn subtable(&self, offset: usize) -> Option<&'a [u8]> {
+        let offset: Option<Offset16> = Stream::read_at(self.data, offset)?;
+        self.data.get(offset?.to_usize()..)
+    }
+
+    /// Parses a MathItalicsCorrectionInfo or a MathTopAccentAttachment table.
+    fn value_by_glyph(&self, offset: usize, glyph_id: GlyphId) -> Option<MathValue<'a>> {
+        let data = self.subtable(offset)?;
+        let mut s = Stream::new(data);
+        let coverage_offset: Offset16 = s.read()?;
+        let count: u16 = s.read()?;
+        let coverage = CoverageTable::new(data.get(coverage_offset.to_usize()..)?);
+        let index = coverage.index(glyph_id)?;
+        if index >= count {
+            return None;
+        }
+
+        s.advance(usize::from(index) * VALUE_RECORD_SIZE);
+        MathValue::parse(data, &mut s)
+    }
+}
+
+impl core::fmt::Debug for GlyphInfo<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "GlyphInfo()")
+    }
+}
+
+
+/// A [Math Kern Info Record](https://docs.microsoft.com/en-us/typography/opentype/spec/math#mathkerninforecord).
+///
+/// Each corner of a glyph can have its own kerning.
+#[derive(Clone, Copy, Debug)]
+pub struct KernInfo<'a> {
+    /// A top right corner kerning.
+    pub top_right: Option<Kern<'a>>,
+    /// A top left corner kerning.
+    pub top_left: Option<Kern<'a>>,
+    /// A bottom right corner kerning.
+    pub bottom_right: Option<Kern<'a>>,
+    /// A bottom left corner kerning.
+    pub bottom_left: Option<Kern<'a>>,
+}
+
+
+/// A [Math Kern table](https://docs.microsoft.com/en-us/typography/opentype/spec/math#mathkern-table).
+///
+/// Splits the glyph height into ranges using correction heights,
+/// with a kern value for each range.
+#[derive(Clone, Copy)]
+pub struct Kern<'a> {
+    data: &'a [u8],
+    count: u16,
+}
+
+impl<'a> Kern<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let count: u16 = Stream::read_at(data, 0)?;
+        // There are `count` heights and `count + 1` kern values.
+        let len = 2 + (usize::from(count) * 2 + 1) * VALUE_RECORD_SIZE;
+        data.get(0..len)?;
+        Some(Kern { data, count })
+    }
+
+    /// Returns the number of correction heights.
+    ///
+    /// The number of kern values is larger by one.
+    #[inline]
+    pub fn count(&self) -> u16 {
+        self.count
+    }
+
+    /// Returns a correction height at the specified index.
+    #[inline]
+    pub fn height(&self, index: u16) -> Option<MathValue<'a>> {
+        if index >= self.count {
+            return None;
+        }
+
+        self.value(usize::from(index))
+    }
+
+    /// Returns a kern value at the specified index.
+    #[inline]
+    pub fn kern(&self, index: u16) -> Option<MathValue<'a>> {
+        if index > self.count {
+            return None;
+        }
+
+        self.value(usize::from(self.count) + usize::from(index))
+    }
+
+    /// Returns a kern value for the specified height.
+    ///
+    /// Uses the first range which correction height is larger than `height`,
+    /// or the last kern value when there is no such range.
+    pub fn kern_at_height(&self, height: i16) -> Option<MathValue<'a>> {
+        for i in 0..self.count {
+            if height < self.height(i)?.value {
+                return self.kern(i);
+            }
+        }
+
+        self.kern(self.count)
+    }
+
+    fn value(&self, index: usize) -> Option<MathValue<'a>> {
+        let mut s = Stream::new_at(self.data, 2 + index * VALUE_RECORD_SIZE)?;
+        MathValue::parse(self.data, &mut s)
+    }
+}
+
+impl core::fmt::Debug for Kern<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Kern()")
+    }
+}
+
+
+/// A [Math Variants table](https://docs.microsoft.com/en-us/typography/opentype/spec/math#mathvariants-table).
+#[derive(Clone, Copy)]
+pub struct Variants<'a> {
+    data: &'a [u8],
+    min_connector_overlap: u16,
+    vertical_coverage: Option<CoverageTable<'a>>,
+    horizontal_coverage: Option<CoverageTable<'a>>,
+    vertical_offsets: LazyArray16<'a, Offset16>,
+    horizontal_offsets: LazyArray16<'a, Offset16>,
+}
+
+impl<'a> Variants<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let min_connector_overlap: u16 = s.read()?;
+        let vertical_coverage_offset: Option<Offset16> = s.read()?;
+        let horizontal_coverage_offset: Option<Offset16> = s.read()?;
+        let vertical_count: u16 = s.read()?;
+        let horizontal_count: u16 = s.read()?;
+        let vertical_offsets = s.read_array16(vertical_count)?;
+        let horizontal_offsets = s.read_array16(horizontal_count)?;
+
+        let coverage = |offset: Option<Offset16>| {
+            data.get(offset?.to_usize()..).map(CoverageTable::new)
+        };
+
+        Some(Variants {
+            data,
+            min_connector_overlap,
+            vertical_coverage: coverage(vertical_coverage_offset),
+            horizontal_coverage: coverage(horizontal_coverage_offset),
+            vertical_offsets,
+            horizontal_offsets,
+        })
+    }
+
+    /// Returns the minimum overlap of connecting glyphs during glyph construction,
+    /// in font design units.
+    #[inline]
+    pub fn min_connector_overlap(&self) -> u16 {
+        self.min_connector_overlap
+    }
+
+    /// Returns a construction for growing the glyph in the vertical direction.
+    #[inline]
+    pub fn vertical_construction(&self, glyph_id: GlyphId) -> Option<GlyphConstruction<'a>> {
+        self.construction(self.vertical_coverage?, self.vertical_offsets, glyph_id)
+    }
+
+    /// Returns a construction for growing the glyph in the horizontal direction.
+    #[inline]
+    pub fn horizontal_construction(&self, glyph_id: GlyphId) -> Option<GlyphConstruction<'a>> {
+        self.construction(self.horizontal_coverage?, self.horizontal_offsets, glyph_id)
+    }
+
+    fn construction(
+        &self,
+        coverage: CoverageTable<'a>,
+        offsets: LazyArray16<'a, Offset16>,
+        glyph_id: GlyphId,
+    ) -> Option<GlyphConstruction<'a>> {
+        let index = coverage.index(glyph_id)?;
+        let offset = offsets.get(index)?;
+        GlyphConstruction::parse(self.data.get(offset.to_usize()..)?)
+    }
+}
+
+impl core::fmt::Debug for Variants<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Variants()")
+    }
+}
+
+
+/// A [Math Glyph Variant Record](https://docs.microsoft.com/en-us/typography/opentype/spec/math#mathglyphvariantrecord).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GlyphVariant {
+    /// A variant glyph.
+    pub variant_glyph: GlyphId,
+    /// An advance width or height of the variant, in the direction of requested glyph extension.
+    pub advance_measurement: u16,
+}
+
+impl FromData for GlyphVariant {
+    const SIZE: usize = 4;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(GlyphVariant {
+            variant_glyph: s.read::<GlyphId>()?,
+            advance_measurement: s.read::<u16>()?,
+        })
+    }
+}
+
+
+/// A [Math Glyph Construction table](https://docs.microsoft.com/en-us/typography/opentype/spec/math#mathglyphconstruction-table).
+#[derive(Clone, Copy)]
+pub struct GlyphConstruction<'a> {
+    assembly: Option<GlyphAssembly<'a>>,
+    variants: LazyArray16<'a, GlyphVariant>,
+}
+
+impl<'a> GlyphConstruction<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let assembly_offset: Option<Offset16> = s.read()?;
+        let count: u16 = s.read()?;
+        let variants = s.read_array16(count)?;
+        let assembly = assembly_offset
+            .and_then(|offset| data.get(offset.to_usize()..))
+            .and_then(GlyphAssembly::parse);
+
+        Some(GlyphConstruction { assembly, variants })
+    }
+
+    /// Returns a glyph assembly for constructing the glyph from parts.
+    #[inline]
+    pub fn assembly(&self) -> Option<GlyphAssembly<'a>> {
+        self.assembly
+    }
+
+    /// Returns pre-built glyph variants, in increasing size order.
+    #[inline]
+    pub fn variants(&self) -> impl Iterator<Item = GlyphVariant> + 'a {
+        self.variants.into_iter()
+    }
+}
+
+impl core::fmt::Debug for GlyphConstruction<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "GlyphConstruction()")
+    }
+}
+
+
+/// [Glyph part flags](https://docs.microsoft.com/en-us/typography/opentype/spec/math#glyphpartrecord).
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct PartFlags(pub u16);
+
+impl PartFlags {
+    /// Checks that the part can be repeated to extend the assembly.
+    #[inline]
+    pub fn extender(self) -> bool {
+        self.0 & 0x0001 != 0
+    }
+}
+
+
+/// A [Glyph Part Record](https://docs.microsoft.com/en-us/typography/opentype/spec/math#glyphpartrecord).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GlyphPart {
+    /// A part glyph.
+    pub glyph_id: GlyphId,
+    /// A length of the connector on the starting end of the part.
+    pub start_connector_length: u16,
+    /// A length of the connector on the ending end of the part.
+    pub end_connector_length: u16,
+    /// A full advance width or height of the part.
+    pub full_advance: u16,
+    /// Part flags.
+    pub flags: PartFlags,
+}
+
+impl FromData for GlyphPart {
+    const SIZE: usize = 10;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(GlyphPart {
+            glyph_id: s.read::<GlyphId>()?,
+            start_connector_length: s.read::<u16>()?,
+            end_connector_length: s.read::<u16>()?,
+            full_advance: s.read::<u16>()?,
+            flags: PartFlags(s.read::<u16>()?),
+        })
+    }
+}
+
+
+/// A [Glyph Assembly table](https://docs.microsoft.com/en-us/typography/opentype/spec/math#glyphassembly-table).
+#[derive(Clone, Copy)]
+pub struct GlyphAssembly<'a> {
+    italics_correction: MathValue<'a>,
+    parts: LazyArray16<'a, GlyphPart>,
+}
+
+impl<'a> GlyphAssembly<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let italics_correction = MathValue::parse(data, &mut s)?;
+        let count: u16 = s.read()?;
+        let parts = s.read_array16(count)?;
+        Some(GlyphAssembly { italics_correction, parts })
+    }
+
+    /// Returns an italics correction of the assembled glyph.
+    #[inline]
+    pub fn italics_correction(&self) -> MathValue<'a> {
+        self.italics_correction
+    }
+
+    /// Returns glyph parts, from bottom to top or from left to right.
+    #[inline]
+    pub fn parts(&self) -> impl Iterator<Item = GlyphPart> + 'a {
+        self.parts.into_iter()
+    }
+}
+
+impl core::fmt::Debug for GlyphAssembly<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "GlyphAssembly()")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn constants() {
+        let mut values = vec![
+            UInt16(1), // major version
+            UInt16(0), // minor version
+            UInt16(10), // constants offset
+            UInt16(0), // glyph info offset
+            UInt16(0), // variants offset
+            // Constants
+            Int16(70), // script percent scale down
+            Int16(50), // script script percent scale down
+            UInt16(1300), // delimited sub formula min height
+            UInt16(1500), // display operator min height
+        ];
+
+        for i in 0..51 {
+            values.push(Int16(i * 10)); // value
+            // Only the axis height has a device table.
+            values.push(UInt16(if i == 1 { 214 } else { 0 })); // device offset
+        }
+
+        values.extend_from_slice(&[
+            Int16(60), // radical degree bottom raise percent
+            // Device
+            UInt16(12), // start size
+            UInt16(12), // end size
+            UInt16(3), // delta format
+            UInt16(0x0500), // deltas: [5]
+        ]);
+
+        let data = writer::convert(&values);
+        let table = Table::parse(&data).unwrap();
+        let constants = table.constants().unwrap();
+        assert_eq!(constants.script_percent_scale_down(), 70);
+        assert_eq!(constants.script_script_percent_scale_down(), 50);
+        assert_eq!(constants.delimited_sub_formula_min_height(), 1300);
+        assert_eq!(constants.display_operator_min_height(), 1500);
+        assert_eq!(constants.math_leading().value, 0);
+        assert!(constants.math_leading().device.is_none());
+        assert_eq!(constants.axis_height().value, 10);
+        match constants.axis_height().device {
+            Some(Device::Hinting(device)) => assert_eq!(device.delta(12), 5),
+            _ => panic!("must be a hinting device"),
+        }
+        assert_eq!(constants.fraction_rule_thickness().value, 340);
+        assert_eq!(constants.radical_kern_after_degree().value, 500);
+        assert_eq!(constants.radical_degree_bottom_raise_percent(), 60);
+    }
+
+    #[test]
+    fn truncated_constants() {
+        let data = writer::convert(&[
+            UInt16(1), // major version
+            UInt16(0), // minor version
+            UInt16(10), // constants offset
+            UInt16(0), // glyph info offset
+            UInt16(0), // variants offset
+            Int16(70), // script percent scale down
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert!(table.constants().is_none());
+    }
+
+    #[test]
+    fn glyph_info() {
+        let data = writer::convert(&[
+            UInt16(1), // major version
+            UInt16(0), // minor version
+            UInt16(0), // constants offset
+            UInt16(10), // glyph info offset
+            UInt16(0), // variants offset
+            // Glyph Info
+            UInt16(8), // italics correction offset
+            UInt16(0), // top accent attachment offset
+            UInt16(22), // extended shape coverage offset
+            UInt16(32), // kern info offset
+            // Italics Correction Info
+            UInt16(8), // coverage offset
+            UInt16(1), // count
+            Int16(15), // value
+            UInt16(0), // device offset
+            // Coverage
+            UInt16(1), // format
+            UInt16(1), // count
+            UInt16(5), // glyph
+            // Extended Shape Coverage
+            UInt16(2), // format
+            UInt16(1), // count
+            UInt16(10), // start glyph
+            UInt16(12), // end glyph
+            UInt16(0), // start coverage index
+            // Kern Info
+            UInt16(12), // coverage offset
+            UInt16(1), // count
+            UInt16(18), // top right offset
+            UInt16(0), // top left offset
+            UInt16(0), // bottom right offset
+            UInt16(0), // bottom left offset
+            // Coverage
+            UInt16(1), // format
+            UInt16(1), // count
+            UInt16(5), // glyph
+            // Kern
+            UInt16(1), // height count
+            Int16(100), // height
+            UInt16(0), // device offset
+            Int16(10), // kern
+            UInt16(0), // device offset
+            Int16(20), // kern
+            UInt16(0), // device offset
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert!(table.constants().is_none());
+        let info = table.glyph_info().unwrap();
+        assert_eq!(info.italics_correction(GlyphId(5)).unwrap().value, 15);
+        assert!(info.italics_correction(GlyphId(6)).is_none());
+        assert!(info.top_accent_attachment(GlyphId(5)).is_none());
+        assert!(!info.is_extended_shape(GlyphId(5)));
+        assert!(info.is_extended_shape(GlyphId(11)));
+
+        let kern_info = info.kern_info(GlyphId(5)).unwrap();
+        assert!(kern_info.top_left.is_none());
+        assert!(kern_info.bottom_right.is_none());
+        assert!(kern_info.bottom_left.is_none());
+        let kern = kern_info.top_right.unwrap();
+        assert_eq!(kern.count(), 1);
+        assert_eq!(kern.height(0).unwrap().value, 100);
+        assert!(kern.height(1).is_none());
+        assert_eq!(kern.kern(1).unwrap().value, 20);
+        assert!(kern.kern(2).is_none());
+        assert_eq!(kern.kern_at_height(50).unwrap().value, 10);
+        assert_eq!(kern.kern_at_height(100).unwrap().value, 20);
+        assert!(info.kern_info(GlyphId(6)).is_none());
+    }
+
+    #[test]
+    fn variants() {
+        let data = writer::convert(&[
+            UInt16(1), // major version
+            UInt16(0), // minor version
+            UInt16(0), // constants offset
+            UInt16(0), // glyph info offset
+            UInt16(10), // variants offset
+            // Variants
+            UInt16(20), // min connector overlap
+            UInt16(12), // vertical coverage offset
+            UInt16(0), // horizontal coverage offset
+            UInt16(1), // vertical count
+            UInt16(0), // horizontal count
+            UInt16(18), // vertical construction offset [0]
+            // Coverage
+            UInt16(1), // format
+            UInt16(1), // count
+            UInt16(5), // glyph
+            // Glyph Construction
+            UInt16(12), // assembly offset
+            UInt16(2), // variant count
+            UInt16(6), // variant glyph [0]
+            UInt16(500), // advance measurement [0]
+            UInt16(7), // variant glyph [1]
+            UInt16(800), // advance measurement [1]
+            // Glyph Assembly
+            Int16(3), // italics correction value
+            UInt16(0), // italics correction device offset
+            UInt16(2), // part count
+            UInt16(8), // glyph [0]
+            UInt16(0), // start connector length [0]
+            UInt16(100), // end connector length [0]
+            UInt16(300), // full advance [0]
+            UInt16(0), // flags [0]
+            UInt16(9), // glyph [1]
+            UInt16(100), // start connector length [1]
+            UInt16(100), // end connector length [1]
+            UInt16(200), // full advance [1]
+            UInt16(1), // flags [1]
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        let variants = table.variants().unwrap();
+        assert_eq!(variants.min_connector_overlap(), 20);
+        assert!(variants.horizontal_construction(GlyphId(5)).is_none());
+        assert!(variants.vertical_construction(GlyphId(6)).is_none());
+
+        let construction = variants.vertical_construction(GlyphId(5)).unwrap();
+        let mut iter = construction.variants();
+        assert_eq!(iter.next(), Some(GlyphVariant { variant_glyph: GlyphId(6), advance_measurement: 500 }));
+        assert_eq!(iter.next(), Some(GlyphVariant { variant_glyph: GlyphId(7), advance_measurement: 800 }));
+        assert!(iter.next().is_none());
+
+        let assembly = construction.assembly().unwrap();
+        assert_eq!(assembly.italics_correction().value, 3);
+        let mut iter = assembly.parts();
+        let part = iter.next().unwrap();
+        assert_eq!(part.glyph_id, GlyphId(8));
+        assert_eq!(part.end_connector_length, 100);
+        assert!(!part.flags.extender());
+        let part = iter.next().unwrap();
+        assert_eq!(part.full_advance, 200);
+        assert!(part.flags.extender());
+        assert!(iter.next().is_none());
+    }
+}
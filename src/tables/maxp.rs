@@ -4,6 +4,41 @@ use core::num::NonZeroU16;
 
 use crate::parser::Stream;
 
+/// A [Maximum Profile](https://docs.microsoft.com/en-us/typography/opentype/spec/maxp)
+/// version 1.0 values.
+///
+/// Used by TrueType hinting and to pre-allocate buffers.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaximumProfile {
+    /// Maximum points in a non-composite glyph.
+    pub max_points: u16,
+    /// Maximum contours in a non-composite glyph.
+    pub max_contours: u16,
+    /// Maximum points in a composite glyph.
+    pub max_composite_points: u16,
+    /// Maximum contours in a composite glyph.
+    pub max_composite_contours: u16,
+    /// `1` if instructions do not use the twilight zone, `2` otherwise.
+    pub max_zones: u16,
+    /// Maximum points used in the twilight zone.
+    pub max_twilight_points: u16,
+    /// Number of Storage Area locations.
+    pub max_storage: u16,
+    /// Number of function definitions.
+    pub max_function_defs: u16,
+    /// Number of instruction definitions.
+    pub max_instruction_defs: u16,
+    /// Maximum stack depth across all programs.
+    pub max_stack_elements: u16,
+    /// Maximum byte count for glyph instructions.
+    pub max_size_of_instructions: u16,
+    /// Maximum number of components referenced at the top level of a composite glyph.
+    pub max_component_elements: u16,
+    /// Maximum levels of recursion in composite glyphs.
+    pub max_component_depth: u16,
+}
+
 // We care only about `numGlyphs`.
 pub fn parse(data: &[u8]) -> Option<NonZeroU16> {
     let mut s = Stream::new(data);
@@ -16,6 +51,34 @@ pub fn parse(data: &[u8]) -> Option<NonZeroU16> {
     NonZeroU16::new(n)
 }
 
+/// Parses version 1.0 values.
+///
+/// Returns `None` for version 0.5, which is used by CFF-based fonts.
+pub fn parse_profile(data: &[u8]) -> Option<MaximumProfile> {
+    let mut s = Stream::new(data);
+    let version: u32 = s.read()?;
+    if version != 0x00010000 {
+        return None;
+    }
+
+    s.skip::<u16>(); // numGlyphs
+    Some(MaximumProfile {
+        max_points: s.read()?,
+        max_contours: s.read()?,
+        max_composite_points: s.read()?,
+        max_composite_contours: s.read()?,
+        max_zones: s.read()?,
+        max_twilight_points: s.read()?,
+        max_storage: s.read()?,
+        max_function_defs: s.read()?,
+        max_instruction_defs: s.read()?,
+        max_stack_elements: s.read()?,
+        max_size_of_instructions: s.read()?,
+        max_component_elements: s.read()?,
+        max_component_depth: s.read()?,
+    })
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -50,6 +113,49 @@ mod tests {
         assert_eq!(num_glyphs, Some(1));
     }
 
+    #[test]
+    fn profile() {
+        let profile = super::parse_profile(&[
+            0x00, 0x01, 0x00, 0x00, // version: 1
+            0x00, 0x01, // number of glyphs: 1
+            0x00, 0x01, // maximum points in a non-composite glyph: 1
+            0x00, 0x02, // maximum contours in a non-composite glyph: 2
+            0x00, 0x03, // maximum points in a composite glyph: 3
+            0x00, 0x04, // maximum contours in a composite glyph: 4
+            0x00, 0x02, // maximum zones: 2
+            0x00, 0x06, // maximum twilight points: 6
+            0x00, 0x07, // number of Storage Area locations: 7
+            0x00, 0x08, // number of FDEFs: 8
+            0x00, 0x09, // number of IDEFs: 9
+            0x00, 0x0A, // maximum stack depth: 10
+            0x00, 0x0B, // maximum byte count for glyph instructions: 11
+            0x00, 0x0C, // maximum number of components: 12
+            0x00, 0x0D, // maximum levels of recursion: 13
+        ]).unwrap();
+        assert_eq!(profile.max_points, 1);
+        assert_eq!(profile.max_contours, 2);
+        assert_eq!(profile.max_composite_points, 3);
+        assert_eq!(profile.max_composite_contours, 4);
+        assert_eq!(profile.max_zones, 2);
+        assert_eq!(profile.max_twilight_points, 6);
+        assert_eq!(profile.max_storage, 7);
+        assert_eq!(profile.max_function_defs, 8);
+        assert_eq!(profile.max_instruction_defs, 9);
+        assert_eq!(profile.max_stack_elements, 10);
+        assert_eq!(profile.max_size_of_instructions, 11);
+        assert_eq!(profile.max_component_elements, 12);
+        assert_eq!(profile.max_component_depth, 13);
+    }
+
+    #[test]
+    fn profile_version_05() {
+        let profile = super::parse_profile(&[
+            0x00, 0x00, 0x50, 0x00, // version: 0.3125
+            0x00, 0x01, // number of glyphs: 1
+        ]);
+        assert_eq!(profile, None);
+    }
+
     #[test]
     fn version_1_trimmed() {
         // We don't really care about the data after the number of glyphs.
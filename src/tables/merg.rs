@@ -0,0 +1,180 @@
+/*!
+A [Merge table](https://docs.microsoft.com/en-us/typography/opentype/spec/merg) implementation.
+*/
+
+use crate::GlyphId;
+use crate::ggg::{Class, ClassDefinitionTable};
+use crate::parser::{Stream, LazyArray16, Offset, Offset16};
+
+
+/// [Merge entry flags](https://docs.microsoft.com/en-us/typography/opentype/spec/merg#merge-entry-table).
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct MergeEntry(pub u8);
+
+impl MergeEntry {
+    /// Checks that glyphs should be merged in left-to-right text.
+    #[inline]
+    pub fn merge_ltr(self) -> bool {
+        self.0 & 0x01 != 0
+    }
+
+    /// Checks that glyphs should be grouped in left-to-right text.
+    #[inline]
+    pub fn group_ltr(self) -> bool {
+        self.0 & 0x02 != 0
+    }
+
+    /// Checks that the second glyph is subordinate to the first one in left-to-right text.
+    #[inline]
+    pub fn second_is_subordinate_ltr(self) -> bool {
+        self.0 & 0x04 != 0
+    }
+
+    /// Checks that glyphs should be merged in right-to-left text.
+    #[inline]
+    pub fn merge_rtl(self) -> bool {
+        self.0 & 0x10 != 0
+    }
+
+    /// Checks that glyphs should be grouped in right-to-left text.
+    #[inline]
+    pub fn group_rtl(self) -> bool {
+        self.0 & 0x20 != 0
+    }
+
+    /// Checks that the second glyph is subordinate to the first one in right-to-left text.
+    #[inline]
+    pub fn second_is_subordinate_rtl(self) -> bool {
+        self.0 & 0x40 != 0
+    }
+}
+
+
+/// A [Merge table](https://docs.microsoft.com/en-us/typography/opentype/spec/merg).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    data: &'a [u8],
+    class_count: u16,
+    entries: &'a [u8],
+    class_defs: LazyArray16<'a, Offset16>,
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let version: u16 = s.read()?;
+        if version != 0 {
+            return None;
+        }
+
+        let class_count: u16 = s.read()?;
+        let entries_offset: Offset16 = s.read()?;
+        let class_defs_count: u16 = s.read()?;
+        let class_defs_offset: Offset16 = s.read()?;
+
+        // A square matrix of entries, one row per the first glyph class.
+        let len = usize::from(class_count) * usize::from(class_count);
+        let entries = Stream::new_at(data, entries_offset.to_usize())?.read_bytes(len)?;
+
+        let class_defs = Stream::new_at(data, class_defs_offset.to_usize())?
+            .read_array16(class_defs_count)?;
+
+        Some(Table { data, class_count, entries, class_defs })
+    }
+
+    /// Returns the number of merge classes.
+    #[inline]
+    pub fn class_count(&self) -> u16 {
+        self.class_count
+    }
+
+    /// Returns glyph's merge class.
+    ///
+    /// Class definitions are checked in order and the first non-zero class is used.
+    /// Glyphs not covered by any of them belong to class 0.
+    pub fn glyph_class(&self, glyph_id: GlyphId) -> Class {
+        for offset in self.class_defs {
+            if let Some(data) = self.data.get(offset.to_usize()..) {
+                let class = ClassDefinitionTable::new(data).get(glyph_id);
+                if class.0 != 0 {
+                    return class;
+                }
+            }
+        }
+
+        Class(0)
+    }
+
+    /// Returns a merge entry for a pair of classes.
+    pub fn entry(&self, first: Class, second: Class) -> Option<MergeEntry> {
+        if first.0 >= self.class_count || second.0 >= self.class_count {
+            return None;
+        }
+
+        let index = usize::from(first.0) * usize::from(self.class_count) + usize::from(second.0);
+        self.entries.get(index).map(|flags| MergeEntry(*flags))
+    }
+
+    /// Returns a merge entry for a pair of adjacent glyphs in logical order.
+    #[inline]
+    pub fn glyphs_entry(&self, first: GlyphId, second: GlyphId) -> Option<MergeEntry> {
+        self.entry(self.glyph_class(first), self.glyph_class(second))
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn entries() {
+        let data = writer::convert(&[
+            UInt16(0), // version
+            UInt16(2), // class count
+            UInt16(10), // entries offset
+            UInt16(1), // class definitions count
+            UInt16(14), // class definitions offset
+            // Entries
+            UInt8(0), // [0][0]
+            UInt8(0), // [0][1]
+            UInt8(0x11), // [1][0]
+            UInt8(0x06), // [1][1]
+            // Class Definition Offsets
+            UInt16(16), // offset [0]
+            // Class Definition
+            UInt16(1), // format
+            UInt16(5), // start glyph
+            UInt16(2), // count
+            UInt16(1), // class [0]
+            UInt16(0), // class [1]
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.class_count(), 2);
+        assert_eq!(table.glyph_class(GlyphId(5)), Class(1));
+        assert_eq!(table.glyph_class(GlyphId(6)), Class(0));
+        assert_eq!(table.glyph_class(GlyphId(10)), Class(0));
+        assert!(table.entry(Class(2), Class(0)).is_none());
+
+        let entry = table.glyphs_entry(GlyphId(5), GlyphId(1)).unwrap();
+        assert!(entry.merge_ltr());
+        assert!(entry.merge_rtl());
+        assert!(!entry.group_ltr());
+
+        let entry = table.glyphs_entry(GlyphId(5), GlyphId(5)).unwrap();
+        assert!(!entry.merge_ltr());
+        assert!(entry.group_ltr());
+        assert!(entry.second_is_subordinate_ltr());
+
+        assert_eq!(table.glyphs_entry(GlyphId(1), GlyphId(5)), Some(MergeEntry(0)));
+    }
+}
@@ -0,0 +1,224 @@
+/*!
+A [Metadata table](https://docs.microsoft.com/en-us/typography/opentype/spec/meta) implementation.
+*/
+
+use crate::Tag;
+use crate::parser::{Stream, FromData, LazyArray32, NumFrom};
+
+
+#[derive(Clone, Copy)]
+struct DataMapRecord {
+    tag: Tag,
+    data_offset: u32,
+    data_length: u32,
+}
+
+impl FromData for DataMapRecord {
+    const SIZE: usize = 12;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(DataMapRecord {
+            tag: s.read::<Tag>()?,
+            data_offset: s.read::<u32>()?,
+            data_length: s.read::<u32>()?,
+        })
+    }
+}
+
+
+/// A [Metadata table](https://docs.microsoft.com/en-us/typography/opentype/spec/meta).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    data: &'a [u8],
+    records: LazyArray32<'a, DataMapRecord>,
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let version: u32 = s.read()?;
+        if version != 1 {
+            return None;
+        }
+
+        s.skip::<u32>(); // flags
+        s.skip::<u32>(); // reserved
+        let count: u32 = s.read()?;
+        let records = s.read_array32::<DataMapRecord>(count)?;
+
+        Some(Table { data, records })
+    }
+
+    /// Returns an iterator over metadata entries.
+    #[inline]
+    pub fn entries(&self) -> Entries<'a> {
+        Entries { table: *self, index: 0 }
+    }
+
+    /// Returns raw data for the specified tag.
+    ///
+    /// Returns `None` when there is no such tag or data is malformed.
+    #[inline]
+    pub fn get(&self, tag: Tag) -> Option<&'a [u8]> {
+        self.entries().find(|(t, _)| *t == tag).map(|(_, data)| data)
+    }
+
+    /// Returns an iterator over languages the font was designed for.
+    ///
+    /// Uses the `dlng` entry.
+    #[inline]
+    pub fn design_languages(&self) -> ScriptLangTags<'a> {
+        ScriptLangTags::new(self.get(Tag::from_bytes(b"dlng")).unwrap_or_default())
+    }
+
+    /// Returns an iterator over languages the font supports.
+    ///
+    /// Uses the `slng` entry.
+    #[inline]
+    pub fn supported_languages(&self) -> ScriptLangTags<'a> {
+        ScriptLangTags::new(self.get(Tag::from_bytes(b"slng")).unwrap_or_default())
+    }
+
+    fn entry_at(&self, index: u32) -> Option<(Tag, &'a [u8])> {
+        let record = self.records.get(index)?;
+        let start = usize::num_from(record.data_offset);
+        let end = start.checked_add(usize::num_from(record.data_length))?;
+        Some((record.tag, self.data.get(start..end)?))
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
+}
+
+
+/// An iterator over metadata entries.
+///
+/// Malformed entries are skipped.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct Entries<'a> {
+    table: Table<'a>,
+    index: u32,
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = (Tag, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.table.records.len() {
+            let index = self.index;
+            self.index += 1;
+
+            if let Some(entry) = self.table.entry_at(index) {
+                return Some(entry);
+            }
+        }
+
+        None
+    }
+}
+
+
+/// An iterator over [ScriptLangTag](https://docs.microsoft.com/en-us/typography/opentype/spec/meta#scriptlangtag-values)
+/// values, like `en-Latn` or `Hant`.
+///
+/// Values that are not valid UTF-8 are skipped.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct ScriptLangTags<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> ScriptLangTags<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        ScriptLangTags { data }
+    }
+}
+
+impl<'a> Iterator for ScriptLangTags<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.data.is_empty() {
+            let len = self.data.iter().position(|c| *c == b',').unwrap_or(self.data.len());
+            let value = &self.data[..len];
+            self.data = self.data.get(len + 1..).unwrap_or_default();
+
+            // Values are separated by a comma and optional spaces.
+            if let Ok(value) = core::str::from_utf8(value) {
+                let value = value.trim();
+                if !value.is_empty() {
+                    return Some(value);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn basic() {
+        let data = writer::convert(&[
+            UInt32(1), // version
+            UInt32(0), // flags
+            UInt32(0), // reserved
+            UInt32(3), // data maps count
+            // Data map 0
+            Raw(b"dlng"), // tag
+            UInt32(52), // offset
+            UInt32(4), // length
+            // Data map 1
+            Raw(b"slng"), // tag
+            UInt32(56), // offset
+            UInt32(19), // length
+            // Data map 2
+            Raw(b"appl"), // tag
+            UInt32(100), // offset: out of bounds
+            UInt32(1), // length
+            // Data
+            Raw(b"Jpan"),
+            Raw(b"Jpan, Latn,en-Latn"),
+            UInt8(b','),
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.entries().count(), 2);
+        assert_eq!(table.get(Tag::from_bytes(b"dlng")), Some(&b"Jpan"[..]));
+        assert_eq!(table.get(Tag::from_bytes(b"appl")), None);
+
+        let mut languages = table.design_languages();
+        assert_eq!(languages.next(), Some("Jpan"));
+        assert_eq!(languages.next(), None);
+
+        let mut languages = table.supported_languages();
+        assert_eq!(languages.next(), Some("Jpan"));
+        assert_eq!(languages.next(), Some("Latn"));
+        assert_eq!(languages.next(), Some("en-Latn"));
+        assert_eq!(languages.next(), None);
+    }
+
+    #[test]
+    fn unknown_version() {
+        let data = writer::convert(&[
+            UInt32(2), // version
+            UInt32(0), // flags
+            UInt32(0), // reserved
+            UInt32(0), // data maps count
+        ]);
+
+        assert!(Table::parse(&data).is_none());
+    }
+}
@@ -1,23 +1,43 @@
+pub mod base;
 pub mod cbdt;
 pub mod cblc;
 mod cff;
 pub mod cmap;
+pub mod colr;
+pub mod cpal;
+pub mod cvt;
+pub mod dsig;
+pub mod ebdt;
+pub mod ebsc;
+pub mod fdsc;
+pub mod fmtx;
 pub mod gdef;
 pub mod glyf;
+pub mod gpos;
 pub mod gsub;
+pub mod hdmx;
 pub mod head;
 pub mod hhea;
 pub mod hmtx;
+pub mod jstf;
 pub mod kern;
 pub mod loca;
+pub mod ltsh;
+pub mod math;
 pub mod maxp;
+pub mod merg;
+pub mod meta;
 pub mod name;
 pub mod os2;
+pub mod pclt;
 pub mod post;
 pub mod sbix;
+pub mod stat;
 pub mod svg;
+pub mod vdmx;
 pub mod vhea;
 pub mod vorg;
+pub mod zapf;
 
 #[cfg(feature = "variable-fonts")] pub mod avar;
 #[cfg(feature = "variable-fonts")] pub mod fvar;
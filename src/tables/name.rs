@@ -5,10 +5,7 @@ use std::vec::Vec;
 #[cfg(feature = "std")]
 use std::string::String;
 
-#[cfg(feature = "std")]
-use crate::parser::LazyArray16;
-
-use crate::parser::{Stream, FromData};
+use crate::parser::{Stream, FromData, LazyArray16};
 
 
 /// A list of [name ID](https://docs.microsoft.com/en-us/typography/opentype/spec/name#name-ids)'s.
@@ -46,6 +43,7 @@ pub mod name_id {
 
 /// A [platform ID](https://docs.microsoft.com/en-us/typography/opentype/spec/name#platform-ids).
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub enum PlatformId {
     Unicode,
@@ -90,6 +88,183 @@ fn is_unicode_encoding(platform_id: PlatformId, encoding_id: u16) -> bool {
 }
 
 
+#[inline]
+fn is_mac_roman_encoding(platform_id: PlatformId, encoding_id: u16) -> bool {
+    // https://docs.microsoft.com/en-us/typography/opentype/spec/name#macintosh-encoding-ids-script-manager-codes
+    const MACINTOSH_ROMAN_ENCODING_ID: u16 = 0;
+
+    platform_id == PlatformId::Macintosh && encoding_id == MACINTOSH_ROMAN_ENCODING_ID
+}
+
+/// Mac OS Roman code points for bytes in a 0x80..=0xFF range.
+///
+/// The first half is identical to ASCII.
+#[cfg(feature = "std")]
+const MAC_ROMAN: [u16; 128] = [
+    0x00C4, 0x00C5, 0x00C7, 0x00C9, 0x00D1, 0x00D6, 0x00DC, 0x00E1,
+    0x00E0, 0x00E2, 0x00E4, 0x00E3, 0x00E5, 0x00E7, 0x00E9, 0x00E8,
+    0x00EA, 0x00EB, 0x00ED, 0x00EC, 0x00EE, 0x00EF, 0x00F1, 0x00F3,
+    0x00F2, 0x00F4, 0x00F6, 0x00F5, 0x00FA, 0x00F9, 0x00FB, 0x00FC,
+    0x2020, 0x00B0, 0x00A2, 0x00A3, 0x00A7, 0x2022, 0x00B6, 0x00DF,
+    0x00AE, 0x00A9, 0x2122, 0x00B4, 0x00A8, 0x2260, 0x00C6, 0x00D8,
+    0x221E, 0x00B1, 0x2264, 0x2265, 0x00A5, 0x00B5, 0x2202, 0x2211,
+    0x220F, 0x03C0, 0x222B, 0x00AA, 0x00BA, 0x03A9, 0x00E6, 0x00F8,
+    0x00BF, 0x00A1, 0x00AC, 0x221A, 0x0192, 0x2248, 0x2206, 0x00AB,
+    0x00BB, 0x2026, 0x00A0, 0x00C0, 0x00C3, 0x00D5, 0x0152, 0x0153,
+    0x2013, 0x2014, 0x201C, 0x201D, 0x2018, 0x2019, 0x00F7, 0x25CA,
+    0x00FF, 0x0178, 0x2044, 0x20AC, 0x2039, 0x203A, 0xFB01, 0xFB02,
+    0x2021, 0x00B7, 0x201A, 0x201E, 0x2030, 0x00C2, 0x00CA, 0x00C1,
+    0x00CB, 0x00C8, 0x00CD, 0x00CE, 0x00CF, 0x00CC, 0x00D3, 0x00D4,
+    0xF8FF, 0x00D2, 0x00DA, 0x00DB, 0x00D9, 0x0131, 0x02C6, 0x02DC,
+    0x00AF, 0x02D8, 0x02D9, 0x02DA, 0x00B8, 0x02DD, 0x02DB, 0x02C7,
+];
+
+
+/// Windows language IDs and their BCP 47 tags.
+///
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/name#windows-language-ids
+const WINDOWS_LANGUAGES: &[(u16, &str)] = &[
+    (0x0401, "ar-SA"),
+    (0x0402, "bg-BG"),
+    (0x0403, "ca-ES"),
+    (0x0404, "zh-TW"),
+    (0x0405, "cs-CZ"),
+    (0x0406, "da-DK"),
+    (0x0407, "de-DE"),
+    (0x0408, "el-GR"),
+    (0x0409, "en-US"),
+    (0x040A, "es-ES"),
+    (0x040B, "fi-FI"),
+    (0x040C, "fr-FR"),
+    (0x040D, "he-IL"),
+    (0x040E, "hu-HU"),
+    (0x040F, "is-IS"),
+    (0x0410, "it-IT"),
+    (0x0411, "ja-JP"),
+    (0x0412, "ko-KR"),
+    (0x0413, "nl-NL"),
+    (0x0414, "nb-NO"),
+    (0x0415, "pl-PL"),
+    (0x0416, "pt-BR"),
+    (0x0418, "ro-RO"),
+    (0x0419, "ru-RU"),
+    (0x041A, "hr-HR"),
+    (0x041B, "sk-SK"),
+    (0x041C, "sq-AL"),
+    (0x041D, "sv-SE"),
+    (0x041E, "th-TH"),
+    (0x041F, "tr-TR"),
+    (0x0420, "ur-PK"),
+    (0x0421, "id-ID"),
+    (0x0422, "uk-UA"),
+    (0x0423, "be-BY"),
+    (0x0424, "sl-SI"),
+    (0x0425, "et-EE"),
+    (0x0426, "lv-LV"),
+    (0x0427, "lt-LT"),
+    (0x0429, "fa-IR"),
+    (0x042A, "vi-VN"),
+    (0x042B, "hy-AM"),
+    (0x042D, "eu-ES"),
+    (0x042F, "mk-MK"),
+    (0x0436, "af-ZA"),
+    (0x0437, "ka-GE"),
+    (0x0439, "hi-IN"),
+    (0x043E, "ms-MY"),
+    (0x043F, "kk-KZ"),
+    (0x0441, "sw-KE"),
+    (0x0445, "bn-IN"),
+    (0x0449, "ta-IN"),
+    (0x044A, "te-IN"),
+    (0x044E, "mr-IN"),
+    (0x0804, "zh-CN"),
+    (0x0807, "de-CH"),
+    (0x0809, "en-GB"),
+    (0x080A, "es-MX"),
+    (0x080C, "fr-BE"),
+    (0x0810, "it-CH"),
+    (0x0813, "nl-BE"),
+    (0x0814, "nn-NO"),
+    (0x0816, "pt-PT"),
+    (0x0C04, "zh-HK"),
+    (0x0C07, "de-AT"),
+    (0x0C09, "en-AU"),
+    (0x0C0A, "es-ES"),
+    (0x0C0C, "fr-CA"),
+    (0x1004, "zh-SG"),
+    (0x1009, "en-CA"),
+    (0x1404, "zh-MO"),
+];
+
+/// Macintosh language IDs and their BCP 47 tags.
+///
+/// https://docs.microsoft.com/en-us/typography/opentype/spec/name#macintosh-language-ids
+const MACINTOSH_LANGUAGES: &[(u16, &str)] = &[
+    (0, "en"),
+    (1, "fr"),
+    (2, "de"),
+    (3, "it"),
+    (4, "nl"),
+    (5, "sv"),
+    (6, "es"),
+    (7, "da"),
+    (8, "pt"),
+    (9, "nb"),
+    (10, "he"),
+    (11, "ja"),
+    (12, "ar"),
+    (13, "fi"),
+    (14, "el"),
+    (15, "is"),
+    (16, "mt"),
+    (17, "tr"),
+    (18, "hr"),
+    (19, "zh-Hant"),
+    (20, "ur"),
+    (21, "hi"),
+    (22, "th"),
+    (23, "ko"),
+    (24, "lt"),
+    (25, "pl"),
+    (26, "hu"),
+    (27, "et"),
+    (28, "lv"),
+    (30, "fo"),
+    (31, "fa"),
+    (32, "ru"),
+    (33, "zh-Hans"),
+    (34, "nl-BE"),
+    (35, "ga"),
+    (36, "sq"),
+    (37, "ro"),
+    (38, "cs"),
+    (39, "sk"),
+    (40, "sl"),
+    (41, "yi"),
+    (42, "sr"),
+    (43, "mk"),
+    (44, "bg"),
+    (45, "uk"),
+    (46, "be"),
+    (47, "uz"),
+    (48, "kk"),
+    (51, "hy"),
+    (52, "ka"),
+    (67, "bn"),
+    (74, "ta"),
+    (75, "te"),
+    (80, "vi"),
+    (81, "id"),
+    (83, "ms"),
+];
+
+/// Returns a primary language subtag, like `zh` for `zh-Hant`.
+#[inline]
+fn primary_language(tag: &str) -> &str {
+    tag.split(&['-', '_'][..]).next().unwrap_or(tag)
+}
+
+
 #[derive(Clone, Copy)]
 struct NameRecord {
     platform_id: PlatformId,
@@ -118,11 +293,32 @@ impl FromData for NameRecord {
 }
 
 
+#[derive(Clone, Copy)]
+struct LangTagRecord {
+    length: u16,
+    offset: u16,
+}
+
+impl FromData for LangTagRecord {
+    const SIZE: usize = 4;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(LangTagRecord {
+            length: s.read::<u16>()?,
+            offset: s.read::<u16>()?,
+        })
+    }
+}
+
+
 /// A [Name Record](https://docs.microsoft.com/en-us/typography/opentype/spec/name#name-records).
 #[derive(Clone, Copy)]
 pub struct Name<'a> {
     data: NameRecord,
     strings: &'a [u8],
+    lang_tags: LazyArray16<'a, LangTagRecord>,
 }
 
 impl<'a> Name<'a> {
@@ -141,6 +337,79 @@ impl<'a> Name<'a> {
         self.data.language_id
     }
 
+    /// Returns the [language tag](https://docs.microsoft.com/en-us/typography/opentype/spec/name#naming-table-format-1)
+    /// data as UTF-16BE bytes.
+    ///
+    /// Only `name` table format 1 has language tags, which are referenced
+    /// by language IDs starting from 0x8000.
+    pub fn language_tag_data(&self) -> Option<&'a [u8]> {
+        // https://docs.microsoft.com/en-us/typography/opentype/spec/name#naming-table-format-1
+        const FIRST_LANG_TAG_ID: u16 = 0x8000;
+
+        let index = self.data.language_id.checked_sub(FIRST_LANG_TAG_ID)?;
+        let record = self.lang_tags.get(index)?;
+        let start = usize::from(record.offset);
+        let end = start + usize::from(record.length);
+        self.strings.get(start..end)
+    }
+
+    /// Returns a BCP 47 tag for a known Windows or Macintosh language ID.
+    fn known_language(&self) -> Option<&'static str> {
+        let languages = match self.platform_id() {
+            PlatformId::Windows => WINDOWS_LANGUAGES,
+            PlatformId::Macintosh => MACINTOSH_LANGUAGES,
+            _ => return None,
+        };
+
+        languages.iter().find(|(id, _)| *id == self.language_id()).map(|(_, tag)| *tag)
+    }
+
+    /// Checks that a format 1 language tag is equal to an ASCII `tag`, ignoring case.
+    fn language_tag_eq(&self, tag: &str) -> bool {
+        let data = match self.language_tag_data() {
+            Some(data) => data,
+            None => return false,
+        };
+
+        let units = LazyArray16::<u16>::new(data);
+        units.len() as usize == tag.len() &&
+            units.into_iter().zip(tag.bytes()).all(|(a, b)| {
+                a < 0x80 && (a as u8).eq_ignore_ascii_case(&b)
+            })
+    }
+
+    /// Returns how well the name matches a BCP 47 `language`.
+    ///
+    /// `3` is an exact match, `2` is a primary language match,
+    /// `1` is an English fallback and `0` is no match.
+    fn language_score(&self, language: &str) -> u8 {
+        if self.language_tag_eq(language) {
+            return 3;
+        }
+
+        match self.known_language() {
+            Some(tag) if tag.eq_ignore_ascii_case(language) => 3,
+            Some(tag) if primary_language(tag).eq_ignore_ascii_case(primary_language(language)) => 2,
+            Some(tag) if primary_language(tag) == "en" => 1,
+            _ => 0,
+        }
+    }
+
+    /// Returns the [language tag](https://docs.microsoft.com/en-us/typography/opentype/spec/name#naming-table-format-1),
+    /// like `en` or `zh-Hant`, as a UTF-8 string.
+    ///
+    /// Returns `None` when the name doesn't reference a language tag.
+    #[cfg(feature = "std")]
+    #[inline(never)]
+    pub fn language_tag(&self) -> Option<String> {
+        let mut tag: Vec<u16> = Vec::new();
+        for c in LazyArray16::<u16>::new(self.language_tag_data()?) {
+            tag.push(c);
+        }
+
+        String::from_utf16(&tag).ok()
+    }
+
     /// Returns the [Name ID](https://docs.microsoft.com/en-us/typography/opentype/spec/name#name-ids).
     ///
     /// A predefined list of ID's can be found in the [`name_id`](name_id/index.html) module.
@@ -159,38 +428,87 @@ impl<'a> Name<'a> {
 
     /// Returns the Name's data as a UTF-8 string.
     ///
-    /// Only Unicode names are supported. And since they are stored as UTF-16BE,
+    /// Since names are stored as UTF-16BE or Mac OS Roman,
     /// we can't return `&str` and have to allocate a `String`.
     ///
     /// Supports:
     /// - Unicode Platform ID
     /// - Windows Platform ID + Symbol
     /// - Windows Platform ID + Unicode BMP
+    /// - Macintosh Platform ID + Roman
+    ///
+    /// Returns `None` for other encodings or malformed UTF-16.
     #[cfg(feature = "std")]
     #[inline(never)]
     pub fn to_string(&self) -> Option<String> {
         if self.is_unicode() {
             self.name_from_utf16_be()
+        } else if self.is_mac_roman() {
+            Some(self.name_from_mac_roman())
         } else {
             None
         }
     }
 
+    /// Returns the Name's data as a UTF-8 string, replacing invalid data
+    /// with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// Unlike `to_string`, never fails. Names with an unsupported encoding
+    /// are decoded as ASCII.
+    #[cfg(feature = "std")]
+    #[inline(never)]
+    pub fn to_string_lossy(&self) -> String {
+        if self.is_unicode() {
+            String::from_utf16_lossy(&self.utf16_be_units())
+        } else if self.is_mac_roman() {
+            self.name_from_mac_roman()
+        } else {
+            self.name().iter()
+                .map(|c| if c.is_ascii() { char::from(*c) } else { core::char::REPLACEMENT_CHARACTER })
+                .collect()
+        }
+    }
+
     /// Checks that the current Name data has a Unicode encoding.
     #[inline]
     pub fn is_unicode(&self) -> bool {
         is_unicode_encoding(self.platform_id(), self.encoding_id())
     }
 
+    /// Checks that the current Name data has a Mac OS Roman encoding.
+    #[inline]
+    pub fn is_mac_roman(&self) -> bool {
+        is_mac_roman_encoding(self.platform_id(), self.encoding_id())
+    }
+
     #[cfg(feature = "std")]
-    #[inline(never)]
-    fn name_from_utf16_be(&self) -> Option<String> {
+    fn utf16_be_units(&self) -> Vec<u16> {
         let mut name: Vec<u16> = Vec::new();
         for c in LazyArray16::<u16>::new(self.name()) {
             name.push(c);
         }
 
-        String::from_utf16(&name).ok()
+        name
+    }
+
+    #[cfg(feature = "std")]
+    #[inline(never)]
+    fn name_from_utf16_be(&self) -> Option<String> {
+        String::from_utf16(&self.utf16_be_units()).ok()
+    }
+
+    #[cfg(feature = "std")]
+    #[inline(never)]
+    fn name_from_mac_roman(&self) -> String {
+        self.name().iter().map(|c| {
+            if c.is_ascii() {
+                char::from(*c)
+            } else {
+                let code = MAC_ROMAN[usize::from(*c - 0x80)];
+                // All Mac OS Roman code points are in the BMP and are not surrogates.
+                core::char::from_u32(u32::from(code)).unwrap_or(core::char::REPLACEMENT_CHARACTER)
+            }
+        }).collect()
     }
 }
 
@@ -227,23 +545,117 @@ impl<'a> core::fmt::Debug for Name<'a> {
 
 /// An iterator over font's names.
 #[derive(Clone, Copy, Default)]
-#[allow(missing_debug_implementations)]
 pub struct Names<'a> {
     names: &'a [u8],
     storage: &'a [u8],
+    lang_tags: LazyArray16<'a, LangTagRecord>,
     index: u16,
     total: u16,
 }
 
 impl<'a> Names<'a> {
-    fn new(names: &'a [u8], storage: &'a [u8], total: u16) -> Self {
+    fn new(
+        names: &'a [u8],
+        storage: &'a [u8],
+        lang_tags: LazyArray16<'a, LangTagRecord>,
+        total: u16,
+    ) -> Self {
         Names {
             names,
             storage,
+            lang_tags,
             index: 0,
             total,
         }
     }
+
+    /// Returns an iterator over names with the specified
+    /// [Name ID](https://docs.microsoft.com/en-us/typography/opentype/spec/name#name-ids).
+    #[inline]
+    pub fn with_name_id(self, name_id: u16) -> impl Iterator<Item = Name<'a>> {
+        self.filter(move |name| name.name_id() == name_id)
+    }
+
+    /// Returns a name with the specified
+    /// [Name ID](https://docs.microsoft.com/en-us/typography/opentype/spec/name#name-ids)
+    /// that matches a BCP 47 `language`, like `ja` or `zh-Hant`, the best.
+    ///
+    /// Exact matches are preferred over matching primary language subtags.
+    /// When nothing matches, English names are used and then any remaining one.
+    /// Windows names are preferred over Macintosh ones.
+    /// Names with encodings that cannot be decoded by `Name::to_string` are ignored.
+    pub fn find_for_language(self, name_id: u16, language: &str) -> Option<Name<'a>> {
+        let mut best: Option<(Name, u8)> = None;
+        for name in self.with_name_id(name_id) {
+            if !(name.is_unicode() || name.is_mac_roman()) {
+                continue;
+            }
+
+            let mut score = name.language_score(language) * 2;
+            if name.platform_id() != PlatformId::Macintosh {
+                score += 1;
+            }
+
+            match best {
+                Some((_, best_score)) if best_score >= score => {}
+                _ => best = Some((name, score)),
+            }
+        }
+
+        best.map(|(name, _)| name)
+    }
+
+    /// Returns the first name with one of the specified Name IDs, in order.
+    ///
+    /// Prefers English names.
+    #[cfg(feature = "std")]
+    pub(crate) fn first_name(self, name_ids: &[u16]) -> Option<String> {
+        name_ids.iter()
+            .filter_map(|id| self.find_for_language(*id, "en"))
+            .find_map(|name| name.to_string())
+    }
+
+    /// Returns the *Typographic Family* name, falling back to *Family*.
+    #[cfg(feature = "std")]
+    pub(crate) fn typographic_family_name(self) -> Option<String> {
+        self.first_name(&[name_id::TYPOGRAPHIC_FAMILY, name_id::FAMILY])
+    }
+
+    /// Returns the *Typographic Subfamily* name, falling back to *Subfamily*.
+    #[cfg(feature = "std")]
+    pub(crate) fn typographic_subfamily_name(self) -> Option<String> {
+        self.first_name(&[name_id::TYPOGRAPHIC_SUBFAMILY, name_id::SUBFAMILY])
+    }
+
+    /// Returns the *WWS Family* name, falling back to the typographic family name.
+    #[cfg(feature = "std")]
+    pub(crate) fn wws_family_name(self) -> Option<String> {
+        self.first_name(&[name_id::WWS_FAMILY, name_id::TYPOGRAPHIC_FAMILY, name_id::FAMILY])
+    }
+
+    /// Returns the *WWS Subfamily* name, falling back to the typographic subfamily name.
+    #[cfg(feature = "std")]
+    pub(crate) fn wws_subfamily_name(self) -> Option<String> {
+        self.first_name(&[name_id::WWS_SUBFAMILY, name_id::TYPOGRAPHIC_SUBFAMILY, name_id::SUBFAMILY])
+    }
+
+    /// Returns an iterator over names with the specified platform ID.
+    #[inline]
+    pub fn with_platform_id(self, platform_id: PlatformId) -> impl Iterator<Item = Name<'a>> {
+        self.filter(move |name| name.platform_id() == platform_id)
+    }
+
+    /// Returns an iterator over names with the specified platform-specific language ID.
+    #[inline]
+    pub fn with_language_id(self, language_id: u16) -> impl Iterator<Item = Name<'a>> {
+        self.filter(move |name| name.language_id() == language_id)
+    }
+}
+
+impl core::fmt::Debug for Names<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Names()")
+    }
 }
 
 impl<'a> Iterator for Names<'a> {
@@ -256,6 +668,7 @@ impl<'a> Iterator for Names<'a> {
             Some(Name {
                 data: Stream::read_at::<NameRecord>(self.names, NameRecord::SIZE * index)?,
                 strings: self.storage,
+                lang_tags: self.lang_tags,
             })
         } else {
             None
@@ -270,25 +683,206 @@ impl<'a> Iterator for Names<'a> {
 
 #[inline(never)]
 pub(crate) fn parse(data: &[u8]) -> Option<Names> {
-    // https://docs.microsoft.com/en-us/typography/opentype/spec/name#naming-table-format-1
-    const LANG_TAG_RECORD_SIZE: u16 = 4;
-
     let mut s = Stream::new(data);
     let format: u16 = s.read()?;
     let count: u16 = s.read()?;
-    s.skip::<u16>(); // offset
+    let storage_offset: u16 = s.read()?;
+
+    if format != 0 && format != 1 {
+        return None;
+    }
+
+    let names_data = s.read_bytes(NameRecord::SIZE * usize::from(count))?;
 
-    if format == 0 {
-        let names_data = s.read_bytes(NameRecord::SIZE * usize::from(count))?;
-        Some(Names::new(names_data, s.tail()?, count))
-    } else if format == 1 {
+    // https://docs.microsoft.com/en-us/typography/opentype/spec/name#naming-table-format-1
+    let mut lang_tags = LazyArray16::default();
+    if format == 1 {
         let lang_tag_count: u16 = s.read()?;
-        let lang_tag_len = lang_tag_count.checked_mul(LANG_TAG_RECORD_SIZE)?;
+        lang_tags = s.read_array16::<LangTagRecord>(lang_tag_count)?;
+    }
+
+    let storage = data.get(usize::from(storage_offset)..)?;
+    Some(Names::new(names_data, storage, lang_tags, count))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(platform_id: PlatformId, encoding_id: u16, strings: &[u8]) -> Name {
+        Name {
+            data: NameRecord {
+                platform_id,
+                encoding_id,
+                language_id: 0,
+                name_id: name_id::FAMILY,
+                length: strings.len() as u16,
+                offset: 0,
+            },
+            strings,
+            lang_tags: LazyArray16::default(),
+        }
+    }
+
+    #[test]
+    fn utf16_be() {
+        let data = &[0x00, 0x54, 0x00, 0xE9, 0x00, 0x73, 0x00, 0x74]; // Tést
+        assert_eq!(name(PlatformId::Windows, 1, data).to_string(), Some(String::from("Tést")));
+        assert_eq!(name(PlatformId::Unicode, 3, data).to_string(), Some(String::from("Tést")));
+    }
+
+    #[test]
+    fn invalid_utf16_be() {
+        let data = &[0x00, 0x54, 0xD8, 0x00]; // T + unpaired surrogate
+        let name = name(PlatformId::Windows, 1, data);
+        assert_eq!(name.to_string(), None);
+        assert_eq!(name.to_string_lossy(), "T\u{FFFD}");
+    }
+
+    #[test]
+    fn mac_roman() {
+        let data = &[0x54, 0x8E, 0x73, 0x74, 0xAA]; // Tést™
+        let name = name(PlatformId::Macintosh, 0, data);
+        assert!(name.is_mac_roman());
+        assert_eq!(name.to_string(), Some(String::from("Tést™")));
+        assert_eq!(name.to_string_lossy(), "Tést™");
+    }
+
+    #[test]
+    fn format_1() {
+        let data = &[
+            0x00, 0x01, // format: 1
+            0x00, 0x02, // count: 2
+            0x00, 0x24, // storage offset: 36
+            // Name record 0
+            0x00, 0x03, // platform ID: Windows
+            0x00, 0x01, // encoding ID: Unicode BMP
+            0x04, 0x09, // language ID: English
+            0x00, 0x01, // name ID: family
+            0x00, 0x02, // length: 2
+            0x00, 0x00, // offset: 0
+            // Name record 1
+            0x00, 0x03, // platform ID: Windows
+            0x00, 0x01, // encoding ID: Unicode BMP
+            0x80, 0x00, // language ID: language tag 0
+            0x00, 0x01, // name ID: family
+            0x00, 0x02, // length: 2
+            0x00, 0x02, // offset: 2
+            0x00, 0x01, // language tag count: 1
+            // Language tag record 0
+            0x00, 0x04, // length: 4
+            0x00, 0x04, // offset: 4
+            // Storage
+            0x00, 0x41, // A
+            0x00, 0x42, // B
+            0x00, 0x66, 0x00, 0x72, // fr
+        ];
+
+        let names = parse(data).unwrap();
+        assert_eq!(names.count(), 2);
+
+        let mut iter = names.with_name_id(name_id::FAMILY);
+        let name = iter.next().unwrap();
+        assert_eq!(name.to_string(), Some(String::from("A")));
+        assert_eq!(name.language_tag(), None);
+        let name = iter.next().unwrap();
+        assert_eq!(name.to_string(), Some(String::from("B")));
+        assert_eq!(name.language_tag(), Some(String::from("fr")));
+        assert!(iter.next().is_none());
+
+        assert_eq!(names.with_language_id(0x0409).count(), 1);
+        assert_eq!(names.with_platform_id(PlatformId::Macintosh).count(), 0);
+        assert_eq!(names.with_name_id(name_id::FULL_NAME).count(), 0);
+    }
+
+    #[test]
+    fn find_for_language() {
+        let data = &[
+            0x00, 0x00, // format: 0
+            0x00, 0x04, // count: 4
+            0x00, 0x36, // storage offset: 54
+            // Name record 0
+            0x00, 0x01, // platform ID: Macintosh
+            0x00, 0x00, // encoding ID: Roman
+            0x00, 0x00, // language ID: English
+            0x00, 0x01, // name ID: family
+            0x00, 0x01, // length: 1
+            0x00, 0x00, // offset: 0
+            // Name record 1
+            0x00, 0x03, // platform ID: Windows
+            0x00, 0x01, // encoding ID: Unicode BMP
+            0x04, 0x09, // language ID: en-US
+            0x00, 0x01, // name ID: family
+            0x00, 0x02, // length: 2
+            0x00, 0x01, // offset: 1
+            // Name record 2
+            0x00, 0x03, // platform ID: Windows
+            0x00, 0x01, // encoding ID: Unicode BMP
+            0x04, 0x11, // language ID: ja-JP
+            0x00, 0x01, // name ID: family
+            0x00, 0x02, // length: 2
+            0x00, 0x03, // offset: 3
+            // Name record 3
+            0x00, 0x03, // platform ID: Windows
+            0x00, 0x01, // encoding ID: Unicode BMP
+            0x08, 0x04, // language ID: zh-CN
+            0x00, 0x01, // name ID: family
+            0x00, 0x02, // length: 2
+            0x00, 0x05, // offset: 5
+            // Storage
+            0x4D, // M
+            0x00, 0x45, // E
+            0x00, 0x4A, // J
+            0x00, 0x43, // C
+        ];
+
+        let names = parse(data).unwrap();
+        let find = |language| {
+            names.find_for_language(name_id::FAMILY, language).and_then(|name| name.to_string())
+        };
+
+        assert_eq!(find("ja"), Some(String::from("J")));
+        assert_eq!(find("ja-JP"), Some(String::from("J")));
+        assert_eq!(find("zh-Hans"), Some(String::from("C")));
+        assert_eq!(find("en-GB"), Some(String::from("E")));
+        assert_eq!(find("de"), Some(String::from("E")));
+        assert!(names.find_for_language(name_id::FULL_NAME, "en").is_none());
+    }
+
+    #[test]
+    fn family_names() {
+        let data = crate::writer::mac_name_table(&[
+            (name_id::FAMILY, "Demo Pro Bold Condensed"),
+            (name_id::SUBFAMILY, "Regular"),
+            (name_id::TYPOGRAPHIC_FAMILY, "Demo Pro"),
+            (name_id::TYPOGRAPHIC_SUBFAMILY, "Bold Condensed"),
+            (name_id::WWS_FAMILY, "Demo Pro Condensed"),
+        ]);
+        let names = parse(&data).unwrap();
+        assert_eq!(names.typographic_family_name().unwrap(), "Demo Pro");
+        assert_eq!(names.typographic_subfamily_name().unwrap(), "Bold Condensed");
+        assert_eq!(names.wws_family_name().unwrap(), "Demo Pro Condensed");
+        // No WWS subfamily.
+        assert_eq!(names.wws_subfamily_name().unwrap(), "Bold Condensed");
+
+        // Only legacy names.
+        let data = crate::writer::mac_name_table(&[
+            (name_id::FAMILY, "Demo"),
+            (name_id::SUBFAMILY, "Italic"),
+        ]);
+        let names = parse(&data).unwrap();
+        assert_eq!(names.typographic_family_name().unwrap(), "Demo");
+        assert_eq!(names.typographic_subfamily_name().unwrap(), "Italic");
+        assert_eq!(names.wws_family_name().unwrap(), "Demo");
+        assert_eq!(names.wws_subfamily_name().unwrap(), "Italic");
+    }
 
-        s.advance(usize::from(lang_tag_len)); // langTagRecords
-        let names_data = s.read_bytes(NameRecord::SIZE * usize::from(count))?;
-        Some(Names::new(names_data, s.tail()?, count))
-    } else {
-        None
+    #[test]
+    fn unsupported_encoding() {
+        let data = &[0x54, 0x8E, 0x73, 0x74]; // Japanese encoding
+        let name = name(PlatformId::Macintosh, 1, data);
+        assert_eq!(name.to_string(), None);
+        assert_eq!(name.to_string_lossy(), "T\u{FFFD}st");
     }
 }
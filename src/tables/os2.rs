@@ -1,25 +1,34 @@
 // https://docs.microsoft.com/en-us/typography/opentype/spec/os2
 
-use crate::LineMetrics;
+use crate::{LineMetrics, Tag};
 use crate::parser::Stream;
 
 
 const US_WEIGHT_CLASS_OFFSET: usize = 4;
 const US_WIDTH_CLASS_OFFSET: usize = 6;
+const FS_TYPE_OFFSET: usize = 8;
 const Y_SUBSCRIPT_X_SIZE_OFFSET: usize = 10;
 const Y_SUPERSCRIPT_X_SIZE_OFFSET: usize = 18;
 const Y_STRIKEOUT_SIZE_OFFSET: usize = 26;
 const Y_STRIKEOUT_POSITION_OFFSET: usize = 28;
+const PANOSE_OFFSET: usize = 32;
+const UL_UNICODE_RANGE_OFFSET: usize = 42;
+const ACH_VEND_ID_OFFSET: usize = 58;
 const FS_SELECTION_OFFSET: usize = 62;
 const S_TYPO_ASCENDER_OFFSET: usize = 68;
 const S_TYPO_DESCENDER_OFFSET: usize = 70;
 const S_TYPO_LINE_GAP_OFFSET: usize = 72;
+const US_WIN_ASCENT_OFFSET: usize = 74;
+const US_WIN_DESCENT_OFFSET: usize = 76;
+const UL_CODE_PAGE_RANGE_OFFSET: usize = 78;
 const SX_HEIGHT_OFFSET: usize = 86;
 const S_CAP_HEIGHT_OFFSET: usize = 88;
+const US_LOWER_OPTICAL_POINT_SIZE_OFFSET: usize = 96;
 
 
 /// A font [weight](https://docs.microsoft.com/en-us/typography/opentype/spec/os2#usweightclass).
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub enum Weight {
     Thin,
@@ -81,6 +90,7 @@ impl Default for Weight {
 
 /// A font [width](https://docs.microsoft.com/en-us/typography/opentype/spec/os2#uswidthclass).
 #[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(missing_docs)]
 pub enum Width {
     UltraCondensed,
@@ -110,6 +120,30 @@ impl Width {
             Width::UltraExpanded    => 9,
         }
     }
+
+    /// Returns the nearest width for a `wdth` axis value, which is a percentage of normal width.
+    pub(crate) fn from_percent(percent: f32) -> Self {
+        // Midpoints between 50, 62.5, 75, 87.5, 100, 112.5, 125, 150 and 200.
+        if percent < 56.25 {
+            Width::UltraCondensed
+        } else if percent < 68.75 {
+            Width::ExtraCondensed
+        } else if percent < 81.25 {
+            Width::Condensed
+        } else if percent < 93.75 {
+            Width::SemiCondensed
+        } else if percent < 106.25 {
+            Width::Normal
+        } else if percent < 118.75 {
+            Width::SemiExpanded
+        } else if percent < 137.5 {
+            Width::Expanded
+        } else if percent < 175.0 {
+            Width::ExtraExpanded
+        } else {
+            Width::UltraExpanded
+        }
+    }
 }
 
 impl Default for Width {
@@ -120,9 +154,290 @@ impl Default for Width {
 }
 
 
+/// A font style.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Style {
+    /// An upright face.
+    Normal,
+    /// A face with cursive, specially designed glyphs.
+    Italic,
+    /// A slanted version of an upright face.
+    Oblique,
+}
+
+impl Default for Style {
+    #[inline]
+    fn default() -> Self {
+        Style::Normal
+    }
+}
+
+
+/// Font embedding permissions derived from
+/// [`fsType`](https://docs.microsoft.com/en-us/typography/opentype/spec/os2#fstype).
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Permissions {
+    /// The font may be embedded and permanently installed on the remote system.
+    Installable,
+    /// The font must not be modified, embedded or exchanged in any manner
+    /// without first obtaining permission of the legal owner.
+    Restricted,
+    /// The font may be embedded, and temporarily loaded for viewing or printing.
+    PreviewAndPrint,
+    /// The font may be embedded, and temporarily loaded for viewing, printing and editing.
+    Editable,
+}
+
+
+/// A [PANOSE](https://monotype.github.io/panose/pan1.htm) family kind.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(missing_docs)]
+pub enum PanoseFamilyKind {
+    Any,
+    NoFit,
+    LatinText,
+    LatinHandWritten,
+    LatinDecorative,
+    LatinSymbol,
+    Other(u8),
+}
+
+/// A PANOSE serif style of the Latin Text family kind.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(missing_docs)]
+pub enum PanoseSerifStyle {
+    Cove,
+    ObtuseCove,
+    SquareCove,
+    ObtuseSquareCove,
+    Square,
+    Thin,
+    Oval,
+    Exaggerated,
+    Triangle,
+    NormalSans,
+    ObtuseSans,
+    PerpendicularSans,
+    Flared,
+    Rounded,
+}
+
+impl PanoseSerifStyle {
+    /// Checks that serif style is one of the sans serif styles.
+    #[inline]
+    pub fn is_sans_serif(self) -> bool {
+        self == PanoseSerifStyle::NormalSans
+            || self == PanoseSerifStyle::ObtuseSans
+            || self == PanoseSerifStyle::PerpendicularSans
+    }
+}
+
+/// A PANOSE weight.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(missing_docs)]
+pub enum PanoseWeight {
+    VeryLight,
+    Light,
+    Thin,
+    Book,
+    Medium,
+    Demi,
+    Bold,
+    Heavy,
+    Black,
+    ExtraBlack,
+}
+
+/// A PANOSE proportion of the Latin Text family kind.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(missing_docs)]
+pub enum PanoseProportion {
+    OldStyle,
+    Modern,
+    EvenWidth,
+    Extended,
+    Condensed,
+    VeryExtended,
+    VeryCondensed,
+    Monospaced,
+}
+
+/// A [PANOSE](https://docs.microsoft.com/en-us/typography/opentype/spec/os2#panose)
+/// classification.
+///
+/// The meaning of all digits except the first one depends on the family kind,
+/// so typed getters return `None` for family kinds they are not defined for
+/// and for the *Any* and *No Fit* values.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Panose([u8; 10]);
+
+impl Panose {
+    /// Creates a new `Panose` from raw bytes.
+    #[inline]
+    pub fn from_bytes(bytes: [u8; 10]) -> Self {
+        Panose(bytes)
+    }
+
+    /// Returns raw bytes.
+    #[inline]
+    pub fn to_bytes(self) -> [u8; 10] {
+        self.0
+    }
+
+    /// Returns a family kind.
+    #[inline]
+    pub fn family_kind(&self) -> PanoseFamilyKind {
+        match self.0[0] {
+            0 => PanoseFamilyKind::Any,
+            1 => PanoseFamilyKind::NoFit,
+            2 => PanoseFamilyKind::LatinText,
+            3 => PanoseFamilyKind::LatinHandWritten,
+            4 => PanoseFamilyKind::LatinDecorative,
+            5 => PanoseFamilyKind::LatinSymbol,
+            n => PanoseFamilyKind::Other(n),
+        }
+    }
+
+    /// Returns a serif style.
+    ///
+    /// Defined only for the Latin Text family kind.
+    #[inline]
+    pub fn serif_style(&self) -> Option<PanoseSerifStyle> {
+        if self.family_kind() != PanoseFamilyKind::LatinText {
+            return None;
+        }
+
+        match self.0[1] {
+            2 => Some(PanoseSerifStyle::Cove),
+            3 => Some(PanoseSerifStyle::ObtuseCove),
+            4 => Some(PanoseSerifStyle::SquareCove),
+            5 => Some(PanoseSerifStyle::ObtuseSquareCove),
+            6 => Some(PanoseSerifStyle::Square),
+            7 => Some(PanoseSerifStyle::Thin),
+            8 => Some(PanoseSerifStyle::Oval),
+            9 => Some(PanoseSerifStyle::Exaggerated),
+            10 => Some(PanoseSerifStyle::Triangle),
+            11 => Some(PanoseSerifStyle::NormalSans),
+            12 => Some(PanoseSerifStyle::ObtuseSans),
+            13 => Some(PanoseSerifStyle::PerpendicularSans),
+            14 => Some(PanoseSerifStyle::Flared),
+            15 => Some(PanoseSerifStyle::Rounded),
+            _ => None,
+        }
+    }
+
+    /// Returns a weight.
+    ///
+    /// Defined for all Latin family kinds.
+    #[inline]
+    pub fn weight(&self) -> Option<PanoseWeight> {
+        match self.family_kind() {
+            PanoseFamilyKind::LatinText
+            | PanoseFamilyKind::LatinHandWritten
+            | PanoseFamilyKind::LatinDecorative
+            | PanoseFamilyKind::LatinSymbol => {}
+            _ => return None,
+        }
+
+        match self.0[2] {
+            2 => Some(PanoseWeight::VeryLight),
+            3 => Some(PanoseWeight::Light),
+            4 => Some(PanoseWeight::Thin),
+            5 => Some(PanoseWeight::Book),
+            6 => Some(PanoseWeight::Medium),
+            7 => Some(PanoseWeight::Demi),
+            8 => Some(PanoseWeight::Bold),
+            9 => Some(PanoseWeight::Heavy),
+            10 => Some(PanoseWeight::Black),
+            11 => Some(PanoseWeight::ExtraBlack),
+            _ => None,
+        }
+    }
+
+    /// Returns a proportion.
+    ///
+    /// Defined only for the Latin Text family kind.
+    #[inline]
+    pub fn proportion(&self) -> Option<PanoseProportion> {
+        if self.family_kind() != PanoseFamilyKind::LatinText {
+            return None;
+        }
+
+        match self.0[3] {
+            2 => Some(PanoseProportion::OldStyle),
+            3 => Some(PanoseProportion::Modern),
+            4 => Some(PanoseProportion::EvenWidth),
+            5 => Some(PanoseProportion::Extended),
+            6 => Some(PanoseProportion::Condensed),
+            7 => Some(PanoseProportion::VeryExtended),
+            8 => Some(PanoseProportion::VeryCondensed),
+            9 => Some(PanoseProportion::Monospaced),
+            _ => None,
+        }
+    }
+
+    /// Returns a raw contrast digit.
+    #[inline]
+    pub fn contrast(&self) -> u8 {
+        self.0[4]
+    }
+
+    /// Returns a raw stroke variation digit.
+    #[inline]
+    pub fn stroke_variation(&self) -> u8 {
+        self.0[5]
+    }
+
+    /// Returns a raw arm style digit.
+    #[inline]
+    pub fn arm_style(&self) -> u8 {
+        self.0[6]
+    }
+
+    /// Returns a raw letterform digit.
+    #[inline]
+    pub fn letterform(&self) -> u8 {
+        self.0[7]
+    }
+
+    /// Returns a raw midline digit.
+    #[inline]
+    pub fn midline(&self) -> u8 {
+        self.0[8]
+    }
+
+    /// Returns a raw x-height digit.
+    #[inline]
+    pub fn x_height(&self) -> u8 {
+        self.0[9]
+    }
+
+    /// Checks that PANOSE marks a face as monospaced.
+    ///
+    /// Uses the proportion digit for the Latin Text family kind
+    /// and the spacing digit for the Latin Hand Written and Latin Symbol ones.
+    #[inline]
+    pub fn is_monospaced(&self) -> bool {
+        match self.family_kind() {
+            PanoseFamilyKind::LatinText => self.0[3] == 9,
+            PanoseFamilyKind::LatinHandWritten | PanoseFamilyKind::LatinSymbol => self.0[3] == 3,
+            _ => false,
+        }
+    }
+}
+
+
 /// A script metrics used by subscript and superscript.
 #[repr(C)]
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScriptMetrics {
     /// Horizontal font size.
     pub x_size: i16,
@@ -172,9 +487,9 @@ impl<'a> Table<'a> {
             _ => return None,
         };
 
-        if data.len() != table_len {
-            return None;
-        }
+        // Some fonts have padding at the end of the table,
+        // so we are checking only that the table is not truncated.
+        let data = data.get(0..table_len)?;
 
         Some(Table {
             version: version as u8,
@@ -182,14 +497,24 @@ impl<'a> Table<'a> {
         })
     }
 
+    #[inline]
+    pub fn weight_class(&self) -> u16 {
+        Stream::read_at::<u16>(self.data, US_WEIGHT_CLASS_OFFSET).unwrap_or(0)
+    }
+
     #[inline]
     pub fn weight(&self) -> Weight {
-        Weight::from(Stream::read_at::<u16>(self.data, US_WEIGHT_CLASS_OFFSET).unwrap_or(0))
+        Weight::from(self.weight_class())
+    }
+
+    #[inline]
+    pub fn width_class(&self) -> u16 {
+        Stream::read_at::<u16>(self.data, US_WIDTH_CLASS_OFFSET).unwrap_or(0)
     }
 
     #[inline]
     pub fn width(&self) -> Width {
-        match Stream::read_at::<u16>(self.data, US_WIDTH_CLASS_OFFSET).unwrap_or(0) {
+        match self.width_class() {
             1 => Width::UltraCondensed,
             2 => Width::ExtraCondensed,
             3 => Width::Condensed,
@@ -255,6 +580,17 @@ impl<'a> Table<'a> {
         }
     }
 
+    /// Returns lower and upper optical point sizes in TWIPs, a 1/20 of a point.
+    #[inline]
+    pub fn optical_point_sizes(&self) -> Option<(u16, u16)> {
+        if self.version < 5 {
+            None
+        } else {
+            let mut s = Stream::new_at(self.data, US_LOWER_OPTICAL_POINT_SIZE_OFFSET)?;
+            Some((s.read::<u16>()?, s.read::<u16>()?))
+        }
+    }
+
     #[inline]
     pub fn strikeout_metrics(&self) -> LineMetrics {
         LineMetrics {
@@ -301,7 +637,334 @@ impl<'a> Table<'a> {
     }
 
     #[inline]
-    fn fs_selection(&self) -> u16 {
+    pub fn win_ascent(&self) -> u16 {
+        Stream::read_at::<u16>(self.data, US_WIN_ASCENT_OFFSET).unwrap_or(0)
+    }
+
+    #[inline]
+    pub fn win_descent(&self) -> u16 {
+        Stream::read_at::<u16>(self.data, US_WIN_DESCENT_OFFSET).unwrap_or(0)
+    }
+
+    #[inline]
+    pub fn fs_type(&self) -> u16 {
+        Stream::read_at::<u16>(self.data, FS_TYPE_OFFSET).unwrap_or(0)
+    }
+
+    #[inline]
+    pub fn permissions(&self) -> Permissions {
+        // 'Applications should never set more than one bit, but when multiple bits
+        // are set in older versions, the least-restrictive permission indicated
+        // take precedence.'
+        let n = self.fs_type();
+        if n & 0x0008 != 0 {
+            Permissions::Editable
+        } else if n & 0x0004 != 0 {
+            Permissions::PreviewAndPrint
+        } else if n & 0x0002 != 0 {
+            Permissions::Restricted
+        } else {
+            Permissions::Installable
+        }
+    }
+
+    #[inline]
+    pub fn is_subsetting_allowed(&self) -> bool {
+        // Versions 0 and 1 do not have this flag.
+        self.version < 2 || self.fs_type() & 0x0100 == 0
+    }
+
+    #[inline]
+    pub fn is_outline_embedding_allowed(&self) -> bool {
+        // Versions 0 and 1 do not have this flag.
+        self.version < 2 || self.fs_type() & 0x0200 == 0
+    }
+
+    #[inline]
+    pub fn fs_selection(&self) -> u16 {
         Stream::read_at::<u16>(self.data, FS_SELECTION_OFFSET).unwrap_or(0)
     }
+
+    #[inline]
+    pub fn panose_bytes(&self) -> [u8; 10] {
+        let mut panose = [0; 10];
+        if let Some(data) = self.data.get(PANOSE_OFFSET..PANOSE_OFFSET + panose.len()) {
+            panose.copy_from_slice(data);
+        }
+
+        panose
+    }
+
+    #[inline]
+    pub fn unicode_ranges(&self) -> [u32; 4] {
+        let mut s = Stream::new_at(self.data, UL_UNICODE_RANGE_OFFSET).unwrap_or_default();
+        [
+            s.read::<u32>().unwrap_or(0),
+            s.read::<u32>().unwrap_or(0),
+            s.read::<u32>().unwrap_or(0),
+            s.read::<u32>().unwrap_or(0),
+        ]
+    }
+
+    #[inline]
+    pub fn vendor_id(&self) -> Tag {
+        Stream::read_at::<Tag>(self.data, ACH_VEND_ID_OFFSET).unwrap_or(Tag(0))
+    }
+
+    #[inline]
+    pub fn code_page_ranges(&self) -> Option<[u32; 2]> {
+        if self.version < 1 {
+            None
+        } else {
+            let mut s = Stream::new_at(self.data, UL_CODE_PAGE_RANGE_OFFSET)?;
+            Some([s.read::<u32>()?, s.read::<u32>()?])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn measurement_metrics() {
+        let mut data = writer::convert(&[
+            UInt16(2), // version: 2
+            Int16(0), // average char width
+            UInt16(400), // weight class
+            UInt16(5), // width class
+            UInt16(0), // type flags
+            Int16(650), // subscript x size
+            Int16(600), // subscript y size
+            Int16(0), // subscript x offset
+            Int16(75), // subscript y offset
+            Int16(650), // superscript x size
+            Int16(600), // superscript y size
+            Int16(0), // superscript x offset
+            Int16(350), // superscript y offset
+            Int16(50), // strikeout size
+            Int16(260), // strikeout position
+        ]);
+        data.resize(86, 0);
+        data.extend_from_slice(&writer::convert(&[
+            Int16(500), // x height
+            Int16(700), // capital height
+        ]));
+        data.resize(96, 0);
+
+        // Padding.
+        data.push(0);
+        data.push(0);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.x_height(), Some(500));
+        assert_eq!(table.cap_height(), Some(700));
+        assert_eq!(table.strikeout_metrics(), LineMetrics { position: 260, thickness: 50 });
+        assert_eq!(table.subscript_metrics(), ScriptMetrics {
+            x_size: 650, y_size: 600, x_offset: 0, y_offset: 75,
+        });
+        assert_eq!(table.superscript_metrics(), ScriptMetrics {
+            x_size: 650, y_size: 600, x_offset: 0, y_offset: 350,
+        });
+
+        // Truncated.
+        assert!(Table::parse(&data[..95]).is_none());
+    }
+
+    #[test]
+    fn line_metrics() {
+        let table_data = |version: u16, fs_selection: u16| {
+            let mut data = writer::convert(&[
+                UInt16(version), // version
+            ]);
+            data.resize(62, 0);
+            data.extend_from_slice(&writer::convert(&[
+                UInt16(fs_selection), // selection flags
+                UInt16(0x20), // first char index
+                UInt16(0x41), // last char index
+                Int16(800), // typographic ascender
+                Int16(-200), // typographic descender
+                Int16(100), // typographic line gap
+                UInt16(900), // Windows ascender
+                UInt16(300), // Windows descender
+            ]));
+            data.resize(96, 0);
+            data
+        };
+
+        let data = table_data(4, 0);
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.typo_ascender(), 800);
+        assert_eq!(table.typo_descender(), -200);
+        assert_eq!(table.typo_line_gap(), 100);
+        assert_eq!(table.win_ascent(), 900);
+        assert_eq!(table.win_descent(), 300);
+        assert!(!table.is_use_typo_metrics());
+
+        // USE_TYPO_METRICS
+        let data = table_data(4, 0x0080);
+        assert!(Table::parse(&data).unwrap().is_use_typo_metrics());
+
+        // The flag is ignored before version 4.
+        let data = table_data(3, 0x0080);
+        assert!(!Table::parse(&data).unwrap().is_use_typo_metrics());
+    }
+
+    #[test]
+    fn optical_point_sizes() {
+        let mut data = writer::convert(&[
+            UInt16(5), // version: 5
+        ]);
+        data.resize(96, 0);
+        data.extend_from_slice(&writer::convert(&[
+            UInt16(180), // lower optical point size: 9pt
+            UInt16(480), // upper optical point size: 24pt
+        ]));
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.optical_point_sizes(), Some((180, 480)));
+
+        // Version 4 doesn't have optical sizes.
+        data[1] = 4;
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.optical_point_sizes(), None);
+    }
+
+    #[test]
+    fn raw_fields() {
+        let mut data = writer::convert(&[
+            UInt16(1), // version: 1
+            Int16(500), // average char width
+            UInt16(950), // weight class: out of range
+            UInt16(3), // width class: condensed
+            UInt16(0x0208), // type flags: editable, bitmap only
+            Int16(0), // subscript x size
+            Int16(0), // subscript y size
+            Int16(0), // subscript x offset
+            Int16(0), // subscript y offset
+            Int16(0), // superscript x size
+            Int16(0), // superscript y size
+            Int16(0), // superscript x offset
+            Int16(0), // superscript y offset
+            Int16(0), // strikeout size
+            Int16(0), // strikeout position
+            Int16(0x0801), // family class
+            Raw(&[2, 11, 6, 4, 2, 2, 2, 2, 2, 4]), // panose
+            UInt32(0x00000003), // unicode range 1: Basic Latin, Latin-1 Supplement
+            UInt32(0x10000000), // unicode range 2
+            UInt32(0), // unicode range 3
+            UInt32(0x02000000), // unicode range 4: Non-Plane 0
+            Raw(b"ADBE"), // vendor ID
+            UInt16(0x0021), // selection flags: italic, bold
+            UInt16(0x20), // first char index
+            UInt16(0xFFFF), // last char index
+            Int16(800), // typographic ascender
+            Int16(-200), // typographic descender
+            Int16(0), // typographic line gap
+            UInt16(900), // Windows ascender
+            UInt16(300), // Windows descender
+            UInt32(0x00000001), // code page range 1: Latin 1
+            UInt32(0x80000000), // code page range 2: US
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.weight_class(), 950);
+        assert_eq!(table.weight(), Weight::Other(950));
+        assert_eq!(table.width_class(), 3);
+        assert_eq!(table.width(), Width::Condensed);
+        assert_eq!(table.fs_type(), 0x0208);
+        assert_eq!(table.fs_selection(), 0x0021);
+        assert!(table.is_italic() && table.is_bold());
+        assert_eq!(table.panose_bytes(), [2, 11, 6, 4, 2, 2, 2, 2, 2, 4]);
+        assert_eq!(table.unicode_ranges(), [0x00000003, 0x10000000, 0, 0x02000000]);
+        assert_eq!(table.vendor_id(), Tag::from_bytes(b"ADBE"));
+        assert_eq!(table.code_page_ranges(), Some([0x00000001, 0x80000000]));
+
+        // Version 0 doesn't have code page ranges.
+        data[1] = 0;
+        data.truncate(78);
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.code_page_ranges(), None);
+        assert_eq!(table.vendor_id(), Tag::from_bytes(b"ADBE"));
+    }
+
+    #[test]
+    fn panose() {
+        // Courier New
+        let panose = Panose::from_bytes([2, 7, 3, 9, 2, 2, 5, 2, 4, 4]);
+        assert_eq!(panose.family_kind(), PanoseFamilyKind::LatinText);
+        assert_eq!(panose.serif_style(), Some(PanoseSerifStyle::Thin));
+        assert_eq!(panose.weight(), Some(PanoseWeight::Light));
+        assert_eq!(panose.proportion(), Some(PanoseProportion::Monospaced));
+        assert!(panose.is_monospaced());
+
+        // Arial
+        let panose = Panose::from_bytes([2, 11, 6, 4, 2, 2, 2, 2, 2, 4]);
+        assert!(panose.serif_style().unwrap().is_sans_serif());
+        assert_eq!(panose.weight(), Some(PanoseWeight::Medium));
+        assert!(!panose.is_monospaced());
+
+        // Latin Hand Written, monospaced spacing
+        let panose = Panose::from_bytes([3, 0, 5, 3, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(panose.serif_style(), None);
+        assert_eq!(panose.proportion(), None);
+        assert_eq!(panose.weight(), Some(PanoseWeight::Book));
+        assert!(panose.is_monospaced());
+
+        let panose = Panose::default();
+        assert_eq!(panose.family_kind(), PanoseFamilyKind::Any);
+        assert_eq!(panose.weight(), None);
+        assert!(!panose.is_monospaced());
+    }
+
+    #[test]
+    fn permissions() {
+        fn build_table(version: u16, fs_type: u16) -> Vec<u8> {
+            let mut data = writer::convert(&[
+                UInt16(version), // version
+                Int16(0), // average char width
+                UInt16(400), // weight class
+                UInt16(5), // width class
+                UInt16(fs_type), // type flags
+            ]);
+            data.resize(if version == 1 { 86 } else { 96 }, 0);
+            data
+        }
+
+        let data = build_table(4, 0x0000);
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.permissions(), Permissions::Installable);
+        assert!(table.is_subsetting_allowed());
+        assert!(table.is_outline_embedding_allowed());
+
+        let data = build_table(4, 0x0302);
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.permissions(), Permissions::Restricted);
+        assert!(!table.is_subsetting_allowed());
+        assert!(!table.is_outline_embedding_allowed());
+
+        // The least restrictive permission takes precedence.
+        let data = build_table(1, 0x0306);
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.permissions(), Permissions::PreviewAndPrint);
+        assert!(table.is_subsetting_allowed());
+        assert!(table.is_outline_embedding_allowed());
+
+        let data = build_table(4, 0x0008);
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.permissions(), Permissions::Editable);
+    }
+
+    #[test]
+    fn width_from_percent() {
+        assert_eq!(Width::from_percent(50.0), Width::UltraCondensed);
+        assert_eq!(Width::from_percent(70.0), Width::Condensed);
+        assert_eq!(Width::from_percent(100.0), Width::Normal);
+        assert_eq!(Width::from_percent(112.5), Width::SemiExpanded);
+        assert_eq!(Width::from_percent(150.0), Width::ExtraExpanded);
+        assert_eq!(Width::from_percent(300.0), Width::UltraExpanded);
+    }
 }
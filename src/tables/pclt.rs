@@ -0,0 +1,242 @@
+/*!
+A [PCL 5 table](https://docs.microsoft.com/en-us/typography/opentype/spec/pclt) implementation.
+
+The table is used by legacy PCL 5 printers and is not recommended for new fonts,
+but is still present in many older ones.
+*/
+
+use crate::parser::Stream;
+
+
+const TABLE_SIZE: usize = 54;
+const FONT_NUMBER_OFFSET: usize = 4;
+const PITCH_OFFSET: usize = 8;
+const X_HEIGHT_OFFSET: usize = 10;
+const STYLE_OFFSET: usize = 12;
+const TYPE_FAMILY_OFFSET: usize = 14;
+const CAP_HEIGHT_OFFSET: usize = 16;
+const SYMBOL_SET_OFFSET: usize = 18;
+const TYPEFACE_OFFSET: usize = 20;
+const CHARACTER_COMPLEMENT_OFFSET: usize = 36;
+const FILE_NAME_OFFSET: usize = 44;
+const STROKE_WEIGHT_OFFSET: usize = 50;
+const WIDTH_TYPE_OFFSET: usize = 51;
+const SERIF_STYLE_OFFSET: usize = 52;
+
+
+/// A [PCL 5 table](https://docs.microsoft.com/en-us/typography/opentype/spec/pclt).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
+        let data = data.get(0..TABLE_SIZE)?;
+
+        let version: u32 = Stream::new(data).read()?;
+        if version != 0x00010000 {
+            return None;
+        }
+
+        Some(Table { data })
+    }
+
+    /// Returns a unique font identifier.
+    #[inline]
+    pub fn font_number(&self) -> u32 {
+        Stream::read_at(self.data, FONT_NUMBER_OFFSET).unwrap_or(0)
+    }
+
+    /// Returns a width of the space glyph in font design units.
+    #[inline]
+    pub fn pitch(&self) -> u16 {
+        Stream::read_at(self.data, PITCH_OFFSET).unwrap_or(0)
+    }
+
+    /// Returns a height of the optical line describing the height of lowercase `x`
+    /// in font design units.
+    #[inline]
+    pub fn x_height(&self) -> u16 {
+        Stream::read_at(self.data, X_HEIGHT_OFFSET).unwrap_or(0)
+    }
+
+    /// Returns raw style bits.
+    ///
+    /// Bits 0-1 contain posture, bits 2-4 contain width and bits 5-9 contain structure.
+    #[inline]
+    pub fn style(&self) -> u16 {
+        Stream::read_at(self.data, STYLE_OFFSET).unwrap_or(0)
+    }
+
+    /// Returns a posture part of the style.
+    ///
+    /// `0` is upright, `1` is oblique/italic and `2` is alternate italic.
+    #[inline]
+    pub fn posture(&self) -> u8 {
+        (self.style() & 0x0003) as u8
+    }
+
+    /// Returns a width part of the style.
+    #[inline]
+    pub fn width(&self) -> u8 {
+        ((self.style() >> 2) & 0x0007) as u8
+    }
+
+    /// Returns a structure part of the style.
+    #[inline]
+    pub fn structure(&self) -> u8 {
+        ((self.style() >> 5) & 0x001F) as u8
+    }
+
+    /// Returns raw type family bits.
+    ///
+    /// The top 4 bits contain a vendor code and the rest is a typeface family code.
+    #[inline]
+    pub fn type_family(&self) -> u16 {
+        Stream::read_at(self.data, TYPE_FAMILY_OFFSET).unwrap_or(0)
+    }
+
+    /// Returns a height of the optical line describing the top of uppercase `H`
+    /// in font design units.
+    #[inline]
+    pub fn cap_height(&self) -> u16 {
+        Stream::read_at(self.data, CAP_HEIGHT_OFFSET).unwrap_or(0)
+    }
+
+    /// Returns a PCL symbol set code.
+    #[inline]
+    pub fn symbol_set(&self) -> u16 {
+        Stream::read_at(self.data, SYMBOL_SET_OFFSET).unwrap_or(0)
+    }
+
+    /// Returns a typeface name.
+    ///
+    /// Trailing spaces and NULL bytes are removed.
+    ///
+    /// Returns `None` when the name is not an ASCII string.
+    #[inline]
+    pub fn typeface(&self) -> Option<&'a str> {
+        parse_ascii(self.data.get(TYPEFACE_OFFSET..CHARACTER_COMPLEMENT_OFFSET)?)
+    }
+
+    /// Returns a raw character complement bit field.
+    #[inline]
+    pub fn character_complement(&self) -> [u8; 8] {
+        let mut complement = [0; 8];
+        if let Some(data) = self.data.get(CHARACTER_COMPLEMENT_OFFSET..FILE_NAME_OFFSET) {
+            complement.copy_from_slice(data);
+        }
+
+        complement
+    }
+
+    /// Returns a suggested file name for the font.
+    ///
+    /// Trailing spaces and NULL bytes are removed.
+    ///
+    /// Returns `None` when the name is not an ASCII string.
+    #[inline]
+    pub fn file_name(&self) -> Option<&'a str> {
+        parse_ascii(self.data.get(FILE_NAME_OFFSET..STROKE_WEIGHT_OFFSET)?)
+    }
+
+    /// Returns a stroke weight in a -7..7 range, where `0` is normal.
+    #[inline]
+    pub fn stroke_weight(&self) -> i8 {
+        Stream::read_at(self.data, STROKE_WEIGHT_OFFSET).unwrap_or(0)
+    }
+
+    /// Returns a width type in a -5..5 range, where `0` is normal.
+    #[inline]
+    pub fn width_type(&self) -> i8 {
+        Stream::read_at(self.data, WIDTH_TYPE_OFFSET).unwrap_or(0)
+    }
+
+    /// Returns raw serif style bits.
+    ///
+    /// Bits 0-5 contain a serif style and bits 6-7 contain a serif kind.
+    #[inline]
+    pub fn serif_style(&self) -> u8 {
+        Stream::read_at(self.data, SERIF_STYLE_OFFSET).unwrap_or(0)
+    }
+
+    /// Checks that the face has serifs according to the serif kind bits.
+    ///
+    /// The serif kind is `1` for sans serif and `2` for serif faces.
+    #[inline]
+    pub fn is_serif(&self) -> bool {
+        self.serif_style() >> 6 == 2
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
+}
+
+fn parse_ascii(data: &[u8]) -> Option<&str> {
+    let len = data.iter().rposition(|c| *c != b' ' && *c != 0).map(|n| n + 1).unwrap_or(0);
+    let data = &data[..len];
+    if !data.is_ascii() {
+        return None;
+    }
+
+    core::str::from_utf8(data).ok()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic() {
+        let data = &[
+            0x00, 0x01, 0x00, 0x00, // version: 1.0
+            0x00, 0x00, 0x00, 0x2A, // font number: 42
+            0x01, 0xF4, // pitch: 500
+            0x01, 0xE0, // x height: 480
+            0x00, 0x25, // style: structure 1, width 1, posture 1
+            0x10, 0x05, // type family: 0x1005
+            0x02, 0xBC, // cap height: 700
+            0x00, 0x0E, // symbol set: 14
+            b'T', b'e', b's', b't', b' ', b'S', b'a', b'n', b's', // typeface
+            b' ', b' ', b' ', b' ', b' ', b' ', b' ', // typeface padding
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE, // character complement
+            b'T', b'S', b'A', b'N', 0x00, 0x00, // file name
+            0xFE, // stroke weight: -2
+            0x01, // width type: 1
+            0x80, // serif style: serif kind 2
+            0x00, // reserved
+        ];
+
+        let table = Table::parse(data).unwrap();
+        assert_eq!(table.font_number(), 42);
+        assert_eq!(table.pitch(), 500);
+        assert_eq!(table.x_height(), 480);
+        assert_eq!(table.posture(), 1);
+        assert_eq!(table.width(), 1);
+        assert_eq!(table.structure(), 1);
+        assert_eq!(table.type_family(), 0x1005);
+        assert_eq!(table.cap_height(), 700);
+        assert_eq!(table.symbol_set(), 14);
+        assert_eq!(table.typeface(), Some("Test Sans"));
+        assert_eq!(table.character_complement(), [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE]);
+        assert_eq!(table.file_name(), Some("TSAN"));
+        assert_eq!(table.stroke_weight(), -2);
+        assert_eq!(table.width_type(), 1);
+        assert!(table.is_serif());
+    }
+
+    #[test]
+    fn truncated() {
+        let data = &[
+            0x00, 0x01, 0x00, 0x00, // version: 1.0
+            0x00, 0x00, 0x00, 0x2A, // font number: 42
+        ];
+
+        assert!(Table::parse(data).is_none());
+    }
+}
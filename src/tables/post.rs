@@ -1,7 +1,9 @@
 // https://docs.microsoft.com/en-us/typography/opentype/spec/post
 
 use crate::{LineMetrics, GlyphId};
-use crate::parser::{Stream, Fixed, LazyArray16};
+use core::convert::TryFrom;
+
+use crate::parser::{Stream, Fixed, FromData, LazyArray16};
 
 
 const TABLE_SIZE: usize = 32;
@@ -11,7 +13,7 @@ const UNDERLINE_THICKNESS_OFFSET: usize = 10;
 const IS_FIXED_PITCH_OFFSET: usize = 12;
 
 // https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6post.html
-const MACINTOSH_NAMES: &[&str] = &[
+pub(crate) const MACINTOSH_NAMES: &[&str] = &[
     ".notdef",
     ".null",
     "nonmarkingreturn",
@@ -273,13 +275,30 @@ const MACINTOSH_NAMES: &[&str] = &[
 ];
 
 
+#[derive(Clone, Copy)]
+enum GlyphNames<'a> {
+    /// Version 3.0 has no names.
+    None,
+    /// Version 1.0 contains exactly the standard Macintosh glyph set.
+    Standard,
+    /// Version 2.0 maps glyphs to standard or custom names.
+    Indexes {
+        indexes: LazyArray16<'a, u16>,
+        names: &'a [u8],
+    },
+    /// Version 2.5 stores offsets from glyph IDs to the standard Macintosh order.
+    Offsets(LazyArray16<'a, i8>),
+    /// Version 4.0 maps glyphs to character codes instead of names.
+    CharCodes(LazyArray16<'a, u16>),
+}
+
+
 #[derive(Clone, Copy)]
 pub struct Table<'a> {
     italic_angle: f32,
     underline: LineMetrics,
     is_monospaced: bool,
-    name_indexes: LazyArray16<'a, u16>,
-    names: &'a [u8],
+    glyph_names: GlyphNames<'a>,
 }
 
 impl<'a> Table<'a> {
@@ -305,75 +324,327 @@ impl<'a> Table<'a> {
 
         let is_monospaced = Stream::read_at::<u32>(data, IS_FIXED_PITCH_OFFSET)? != 0;
 
-        let mut name_indexes = LazyArray16::default();
-        let mut names: &[u8] = &[];
-
-        // Only version 2.0 of the table has data at the end.
-        if version == 0x00020000 {
-            let mut s = Stream::new_at(data, TABLE_SIZE)?;
-            let count: u16 = s.read()?;
-            name_indexes = s.read_array16::<u16>(count)?;
-            names = s.tail()?;
-        }
+        // Malformed names should not invalidate the whole table.
+        let glyph_names = parse_glyph_names(data, version).unwrap_or(GlyphNames::None);
 
         Some(Table {
             italic_angle,
             underline,
             is_monospaced,
-            name_indexes,
-            names,
+            glyph_names,
         })
     }
 
+    /// Returns the italic angle in counter-clockwise degrees from the vertical.
     #[inline]
     pub fn italic_angle(&self) -> f32 {
         self.italic_angle
     }
 
+    /// Returns underline position and thickness in font design units.
+    ///
+    /// The position is the top of the underline relative to the baseline.
     #[inline]
     pub fn underline_metrics(&self) -> LineMetrics {
         self.underline
     }
 
+    /// Checks that `isFixedPitch` is set.
     #[inline]
     pub fn is_monospaced(&self) -> bool {
         self.is_monospaced
     }
 
+    /// Returns glyph's name.
+    ///
+    /// Version 1.0 tables use the standard Macintosh order,
+    /// so `number_of_glyphs` is used to reject glyphs that are not present in the font.
     #[inline]
-    pub fn glyph_name(&self, glyph_id: GlyphId) -> Option<&'a str> {
-        let mut index = self.name_indexes.get(glyph_id.0)?;
-
-        // 'If the name index is between 0 and 257, treat the name index
-        // as a glyph index in the Macintosh standard order.'
-        if usize::from(index) < MACINTOSH_NAMES.len() {
-            Some(MACINTOSH_NAMES[usize::from(index)])
-        } else {
-            // 'If the name index is between 258 and 65535, then subtract 258 and use that
-            // to index into the list of Pascal strings at the end of the table.'
-            index -= MACINTOSH_NAMES.len() as u16;
-
-            let mut s = Stream::new(self.names);
-            let mut i = 0;
-            while !s.at_end() && i < core::u16::MAX {
-                let len: u8 = s.read()?;
-
-                if i == index {
-                    if len == 0 {
-                        // Empty name is an error.
-                        break;
-                    } else {
-                        let name = s.read_bytes(usize::from(len))?;
-                        return core::str::from_utf8(name).ok();
-                    }
+    pub fn glyph_name(&self, glyph_id: GlyphId, number_of_glyphs: u16) -> Option<&'a str> {
+        match self.glyph_names {
+            GlyphNames::Standard => {
+                if glyph_id.0 >= number_of_glyphs {
+                    return None;
+                }
+
+                MACINTOSH_NAMES.get(usize::from(glyph_id.0)).cloned()
+            }
+            GlyphNames::Indexes { indexes, names } => {
+                name_by_index(indexes.get(glyph_id.0)?, names)
+            }
+            GlyphNames::Offsets(offsets) => {
+                let index = i32::from(glyph_id.0) + i32::from(offsets.get(glyph_id.0)?);
+                MACINTOSH_NAMES.get(usize::try_from(index).ok()?).cloned()
+            }
+            GlyphNames::None | GlyphNames::CharCodes(_) => None,
+        }
+    }
+
+    /// Returns a glyph ID by its name.
+    pub fn glyph_index_by_name(&self, name: &str, number_of_glyphs: u16) -> Option<GlyphId> {
+        let standard_index = MACINTOSH_NAMES.iter().position(|n| *n == name);
+
+        match self.glyph_names {
+            GlyphNames::Standard => {
+                let index = standard_index?;
+                if index < usize::from(number_of_glyphs) {
+                    Some(GlyphId(index as u16))
                 } else {
-                    s.advance(usize::from(len));
+                    None
                 }
+            }
+            GlyphNames::Indexes { indexes, names } => {
+                let index = match standard_index {
+                    Some(index) => index as u16,
+                    None => MACINTOSH_NAMES.len() as u16 + custom_name_index(name, names)?,
+                };
 
-                i += 1;
+                indexes.into_iter().position(|i| i == index).map(|i| GlyphId(i as u16))
+            }
+            GlyphNames::Offsets(offsets) => {
+                let index = standard_index? as i32;
+                offsets.into_iter().enumerate()
+                    .position(|(i, offset)| i as i32 + i32::from(offset) == index)
+                    .map(|i| GlyphId(i as u16))
             }
+            GlyphNames::None | GlyphNames::CharCodes(_) => None,
+        }
+    }
+
+    /// Returns a character code associated with a glyph.
+    ///
+    /// Only version 4.0 tables, which are used by Apple composite fonts, have them.
+    #[inline]
+    pub fn glyph_char_code(&self, glyph_id: GlyphId) -> Option<u16> {
+        match self.glyph_names {
+            GlyphNames::CharCodes(codes) => {
+                // 0xFFFF indicates that a glyph has no character code.
+                codes.get(glyph_id.0).filter(|code| *code != 0xFFFF)
+            }
+            _ => None,
+        }
+    }
+}
 
-            None
+fn parse_glyph_names(data: &[u8], version: u32) -> Option<GlyphNames<'_>> {
+    match version {
+        0x00010000 => Some(GlyphNames::Standard),
+        0x00020000 => {
+            let mut s = Stream::new_at(data, TABLE_SIZE)?;
+            let count: u16 = s.read()?;
+            let indexes = s.read_array16::<u16>(count)?;
+            let names = s.tail()?;
+            Some(GlyphNames::Indexes { indexes, names })
         }
+        0x00025000 => {
+            let mut s = Stream::new_at(data, TABLE_SIZE)?;
+            let count: u16 = s.read()?;
+            s.read_array16::<i8>(count).map(GlyphNames::Offsets)
+        }
+        0x00040000 => {
+            // Version 4.0 has no glyphs count, so we have to rely on the table size.
+            let mut s = Stream::new_at(data, TABLE_SIZE)?;
+            let count = u16::try_from((data.len() - TABLE_SIZE) / u16::SIZE).ok()?;
+            s.read_array16::<u16>(count).map(GlyphNames::CharCodes)
+        }
+        _ => None,
+    }
+}
+
+fn name_by_index(mut index: u16, names: &[u8]) -> Option<&str> {
+    // 'If the name index is between 0 and 257, treat the name index
+    // as a glyph index in the Macintosh standard order.'
+    if usize::from(index) < MACINTOSH_NAMES.len() {
+        Some(MACINTOSH_NAMES[usize::from(index)])
+    } else {
+        // 'If the name index is between 258 and 65535, then subtract 258 and use that
+        // to index into the list of Pascal strings at the end of the table.'
+        index -= MACINTOSH_NAMES.len() as u16;
+
+        let mut s = Stream::new(names);
+        let mut i = 0;
+        while !s.at_end() && i < core::u16::MAX {
+            let len: u8 = s.read()?;
+
+            if i == index {
+                if len == 0 {
+                    // Empty name is an error.
+                    break;
+                } else {
+                    let name = s.read_bytes(usize::from(len))?;
+                    return core::str::from_utf8(name).ok();
+                }
+            } else {
+                s.advance(usize::from(len));
+            }
+
+            i += 1;
+        }
+
+        None
+    }
+}
+
+/// Returns an index of a name in the list of Pascal strings.
+fn custom_name_index(name: &str, names: &[u8]) -> Option<u16> {
+    let mut s = Stream::new(names);
+    let mut i = 0;
+    while !s.at_end() && i < core::u16::MAX - MACINTOSH_NAMES.len() as u16 {
+        let len: u8 = s.read()?;
+        let data = s.read_bytes(usize::from(len))?;
+        if len != 0 && data == name.as_bytes() {
+            return Some(i);
+        }
+
+        i += 1;
+    }
+
+    None
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &[u8] = &[
+        0x00, 0x00, 0x00, 0x00, // italic angle: 0
+        0x00, 0x00, // underline position: 0
+        0x00, 0x00, // underline thickness: 0
+        0x00, 0x00, 0x00, 0x00, // is fixed pitch: 0
+        0x00, 0x00, 0x00, 0x00, // min memory type 42
+        0x00, 0x00, 0x00, 0x00, // max memory type 42
+        0x00, 0x00, 0x00, 0x00, // min memory type 1
+        0x00, 0x00, 0x00, 0x00, // max memory type 1
+    ];
+
+    #[test]
+    fn header() {
+        let data = &[
+            0x00, 0x03, 0x00, 0x00, // version: 3.0
+            0xFF, 0xF3, 0x80, 0x00, // italic angle: -12.5
+            0xFF, 0x9C, // underline position: -100
+            0x00, 0x32, // underline thickness: 50
+            0x00, 0x00, 0x00, 0x01, // is fixed pitch: 1
+            0x00, 0x00, 0x00, 0x00, // min memory type 42
+            0x00, 0x00, 0x00, 0x00, // max memory type 42
+            0x00, 0x00, 0x00, 0x00, // min memory type 1
+            0x00, 0x00, 0x00, 0x00, // max memory type 1
+        ];
+
+        let table = Table::parse(data).unwrap();
+        assert_eq!(table.italic_angle(), -12.5);
+        assert_eq!(table.underline_metrics(), LineMetrics { position: -100, thickness: 50 });
+        assert!(table.is_monospaced());
+        assert_eq!(table.glyph_name(GlyphId(0), 1), None);
+    }
+
+    #[test]
+    fn truncated_header() {
+        let data = &[
+            0x00, 0x03, 0x00, 0x00, // version: 3.0
+            0x00, 0x00, 0x00, 0x00, // italic angle: 0
+        ];
+
+        assert!(Table::parse(data).is_none());
+    }
+
+    #[test]
+    fn standard_names() {
+        let mut data = vec![0x00, 0x01, 0x00, 0x00]; // version: 1.0
+        data.extend_from_slice(HEADER);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.glyph_name(GlyphId(0), 258), Some(".notdef"));
+        assert_eq!(table.glyph_name(GlyphId(36), 258), Some("A"));
+        assert_eq!(table.glyph_name(GlyphId(36), 10), None);
+        assert_eq!(table.glyph_name(GlyphId(258), 300), None);
+        assert_eq!(table.glyph_index_by_name("A", 258), Some(GlyphId(36)));
+        assert_eq!(table.glyph_index_by_name("A", 10), None);
+        assert_eq!(table.glyph_index_by_name("A.alt", 258), None);
+    }
+
+    #[test]
+    fn custom_names() {
+        let mut data = vec![0x00, 0x02, 0x00, 0x00]; // version: 2.0
+        data.extend_from_slice(HEADER);
+        data.extend_from_slice(&[
+            0x00, 0x04, // number of glyphs: 4
+            0x00, 0x00, // glyph 0: .notdef
+            0x01, 0x03, // glyph 1: custom name 1
+            0x00, 0x24, // glyph 2: A
+            0x01, 0x02, // glyph 3: custom name 0
+            // Names
+            0x05, b'A', b'.', b'a', b'l', b't', // A.alt
+            0x02, b'f', b'f', // ff
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.glyph_name(GlyphId(0), 4), Some(".notdef"));
+        assert_eq!(table.glyph_name(GlyphId(1), 4), Some("ff"));
+        assert_eq!(table.glyph_name(GlyphId(2), 4), Some("A"));
+        assert_eq!(table.glyph_name(GlyphId(3), 4), Some("A.alt"));
+        assert_eq!(table.glyph_name(GlyphId(4), 4), None);
+        assert_eq!(table.glyph_index_by_name(".notdef", 4), Some(GlyphId(0)));
+        assert_eq!(table.glyph_index_by_name("ff", 4), Some(GlyphId(1)));
+        assert_eq!(table.glyph_index_by_name("A", 4), Some(GlyphId(2)));
+        assert_eq!(table.glyph_index_by_name("A.alt", 4), Some(GlyphId(3)));
+        // Standard names that are not used by any glyph.
+        assert_eq!(table.glyph_index_by_name("B", 4), None);
+        assert_eq!(table.glyph_index_by_name("fi", 4), None);
+    }
+
+    #[test]
+    fn missing_custom_names() {
+        let mut data = vec![0x00, 0x02, 0x00, 0x00]; // version: 2.0
+        data.extend_from_slice(HEADER);
+        data.extend_from_slice(&[
+            0x00, 0x02, // number of glyphs: 2
+            0x00, 0x00, // glyph 0: .notdef
+            0x01, 0x02, // glyph 1: custom name 0, which is missing
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.glyph_name(GlyphId(0), 2), Some(".notdef"));
+        assert_eq!(table.glyph_name(GlyphId(1), 2), None);
+        assert_eq!(table.glyph_index_by_name(".notdef", 2), Some(GlyphId(0)));
+        assert_eq!(table.glyph_index_by_name("A", 2), None);
+    }
+
+    #[test]
+    fn offset_names() {
+        let mut data = vec![0x00, 0x02, 0x50, 0x00]; // version: 2.5
+        data.extend_from_slice(HEADER);
+        data.extend_from_slice(&[
+            0x00, 0x03, // number of glyphs: 3
+            0x00, // glyph 0: .notdef
+            0x02, // glyph 1: space
+            0x22, // glyph 2: A
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.glyph_name(GlyphId(0), 3), Some(".notdef"));
+        assert_eq!(table.glyph_name(GlyphId(1), 3), Some("space"));
+        assert_eq!(table.glyph_name(GlyphId(2), 3), Some("A"));
+        assert_eq!(table.glyph_name(GlyphId(3), 3), None);
+        assert_eq!(table.glyph_index_by_name("space", 3), Some(GlyphId(1)));
+        assert_eq!(table.glyph_index_by_name("A", 3), Some(GlyphId(2)));
+        assert_eq!(table.glyph_index_by_name("B", 3), None);
+    }
+
+    #[test]
+    fn char_codes() {
+        let mut data = vec![0x00, 0x04, 0x00, 0x00]; // version: 4.0
+        data.extend_from_slice(HEADER);
+        data.extend_from_slice(&[
+            0xFF, 0xFF, // glyph 0: none
+            0x00, 0x41, // glyph 1: 0x41
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.glyph_char_code(GlyphId(0)), None);
+        assert_eq!(table.glyph_char_code(GlyphId(1)), Some(0x41));
+        assert_eq!(table.glyph_char_code(GlyphId(2)), None);
+        assert_eq!(table.glyph_name(GlyphId(1), 2), None);
     }
 }
@@ -1,112 +1,269 @@
-// https://docs.microsoft.com/en-us/typography/opentype/spec/sbix
+/*!
+A [Standard Bitmap Graphics table](https://docs.microsoft.com/en-us/typography/opentype/spec/sbix) implementation.
+*/
 
 use core::convert::TryFrom;
 use core::num::NonZeroU16;
 
-use crate::{GlyphId, RasterGlyphImage, RasterImageFormat, Tag};
-use crate::parser::{Stream, FromData, Offset, Offset32};
+use crate::{GlyphId, RasterGlyphImage, RasterImageFormat, Tag, is_better_strike};
+use crate::parser::{Stream, FromData, LazyArray32, Offset, Offset32};
 
-pub fn parse(
-    data: &[u8],
-    number_of_glyphs: NonZeroU16,
-    glyph_id: GlyphId,
-    pixels_per_em: u16,
-    depth: u8,
-) -> Option<RasterGlyphImage> {
-    if depth == 10 {
-        return None;
+/// The maximum number of `dupe` records that can be followed.
+///
+/// Protects from infinite loops on malformed fonts.
+const MAX_DUPE_DEPTH: u8 = 10;
+
+
+/// A glyph graphic type.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GraphicType {
+    /// A PNG image.
+    Png,
+    /// A JPEG image.
+    Jpeg,
+    /// A TIFF image.
+    Tiff,
+    /// A PDF document.
+    Pdf,
+    /// A mask.
+    Mask,
+    /// A reference to another glyph's graphic in the same strike.
+    ///
+    /// The glyph ID is stored in the data.
+    Dupe,
+    /// An unknown graphic type.
+    Other(Tag),
+}
+
+impl GraphicType {
+    fn new(tag: Tag) -> Self {
+        match &tag.to_bytes() {
+            b"png " => GraphicType::Png,
+            b"jpg " => GraphicType::Jpeg,
+            b"tiff" => GraphicType::Tiff,
+            b"pdf " => GraphicType::Pdf,
+            b"mask" => GraphicType::Mask,
+            b"dupe" => GraphicType::Dupe,
+            _ => GraphicType::Other(tag),
+        }
     }
+}
 
-    let total_glyphs = u32::from(number_of_glyphs.get().checked_add(1)?);
 
-    let mut s = Stream::new(data);
-    let version: u16 = s.read()?;
-    if version != 1 {
-        return None;
+/// A glyph graphic record.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GlyphData<'a> {
+    /// Horizontal offset of the graphic's left edge in pixels.
+    pub x: i16,
+    /// Vertical offset of the graphic's bottom edge in pixels.
+    pub y: i16,
+    /// Graphic type.
+    pub graphic_type: GraphicType,
+    /// Raw graphic data. It's up to the caller to decode it.
+    pub data: &'a [u8],
+}
+
+impl GlyphData<'_> {
+    /// Returns a referenced glyph ID of a `dupe` record.
+    #[inline]
+    pub fn dupe_glyph_id(&self) -> Option<GlyphId> {
+        if self.graphic_type == GraphicType::Dupe {
+            GlyphId::parse(self.data)
+        } else {
+            None
+        }
     }
+}
 
-    s.skip::<u16>(); // flags
-    let count: u32 = s.read()?;
-    if count == 0 {
-        return None;
+
+/// A bitmap strike.
+#[derive(Clone, Copy)]
+pub struct Strike<'a> {
+    data: &'a [u8],
+    pixels_per_em: u16,
+    ppi: u16,
+    glyph_offsets: LazyArray32<'a, Offset32>,
+}
+
+impl<'a> Strike<'a> {
+    fn parse(data: &'a [u8], number_of_glyphs: NonZeroU16) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let pixels_per_em: u16 = s.read()?;
+        let ppi: u16 = s.read()?;
+        // The last offset points to the end of the last glyph's data.
+        let count = u32::from(number_of_glyphs.get()).checked_add(1)?;
+        let glyph_offsets = s.read_array32(count)?;
+        Some(Strike { data, pixels_per_em, ppi, glyph_offsets })
     }
 
-    let strikes = s.read_array32::<Offset32>(count)?;
+    /// Returns the number of pixels per em the strike was designed for.
+    #[inline]
+    pub fn pixels_per_em(&self) -> u16 {
+        self.pixels_per_em
+    }
 
-    // Select a best matching strike based on `pixels_per_em`.
-    let mut idx = 0;
-    let mut max_ppem = 0;
-    {
-        for (i, offset) in strikes.into_iter().enumerate() {
-            let mut s = Stream::new_at(data, offset.to_usize())?;
-            let ppem: u16 = s.read()?;
-            s.skip::<u16>(); // ppi
+    /// Returns the device pixel density the strike was designed for.
+    #[inline]
+    pub fn ppi(&self) -> u16 {
+        self.ppi
+    }
 
-            if (pixels_per_em <= ppem && ppem < max_ppem) ||
-                (pixels_per_em > max_ppem && ppem > max_ppem)
-            {
-                idx = i as u32;
-                max_ppem = ppem;
+    /// Returns glyph's graphic record.
+    ///
+    /// `dupe` records are returned as is. Use `resolved_glyph_data` to follow them.
+    ///
+    /// Returns `None` when the glyph has no graphic in the strike.
+    pub fn glyph_data(&self, glyph_id: GlyphId) -> Option<GlyphData<'a>> {
+        let start = self.glyph_offsets.get(u32::from(glyph_id.0))?.to_usize();
+        let end = self.glyph_offsets.get(u32::from(glyph_id.0).checked_add(1)?)?.to_usize();
+        if start == end {
+            // No graphic data for that glyph.
+            return None;
+        }
+
+        let data_len = end.checked_sub(start)?.checked_sub(8)?; // 8 is a Glyph data header size.
+        let mut s = Stream::new_at(self.data, start)?;
+        Some(GlyphData {
+            x: s.read()?,
+            y: s.read()?,
+            graphic_type: GraphicType::new(s.read()?),
+            data: s.read_bytes(data_len)?,
+        })
+    }
+
+    /// Returns glyph's graphic record, following `dupe` records.
+    pub fn resolved_glyph_data(&self, mut glyph_id: GlyphId) -> Option<GlyphData<'a>> {
+        for _ in 0..MAX_DUPE_DEPTH {
+            let glyph = self.glyph_data(glyph_id)?;
+            match glyph.dupe_glyph_id() {
+                Some(id) => glyph_id = id,
+                None => return Some(glyph),
             }
         }
+
+        None
     }
+}
 
-    let offset = strikes.get(idx)?;
-    let mut s = Stream::new_at(data, offset.to_usize())?;
-    s.skip::<u16>(); // ppem
-    s.skip::<u16>(); // ppi
+impl core::fmt::Debug for Strike<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Strike({})", self.pixels_per_em)
+    }
+}
 
-    let glyph_offsets = s.read_array32::<Offset32>(total_glyphs)?;
-    let start = glyph_offsets.get(u32::from(glyph_id.0))?.to_usize();
-    let end = glyph_offsets.get(u32::from(glyph_id.0.checked_add(1)?))?.to_usize();
 
-    if start == end {
-        // No bitmap data for that glyph.
-        return None;
+/// A [Standard Bitmap Graphics table](https://docs.microsoft.com/en-us/typography/opentype/spec/sbix).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    data: &'a [u8],
+    flags: u16,
+    strikes: LazyArray32<'a, Offset32>,
+    number_of_glyphs: NonZeroU16,
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8], number_of_glyphs: NonZeroU16) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let version: u16 = s.read()?;
+        if version != 1 {
+            return None;
+        }
+
+        let flags: u16 = s.read()?;
+        let count: u32 = s.read()?;
+        let strikes = s.read_array32(count)?;
+        Some(Table { data, flags, strikes, number_of_glyphs })
     }
 
-    let data_len = end.checked_sub(start)?.checked_sub(8)?; // 8 is a Glyph data header size.
+    /// Checks that outlines should be drawn on top of the graphics.
+    #[inline]
+    pub fn draw_outlines(&self) -> bool {
+        self.flags & 0x0002 != 0
+    }
 
-    let mut s = Stream::new_at(data, offset.to_usize() + start)?;
-    let x: i16 = s.read()?;
-    let y: i16 = s.read()?;
-    let image_type: Tag = s.read()?;
-    let image_data = s.read_bytes(data_len)?;
+    /// Returns the number of strikes.
+    #[inline]
+    pub fn strikes_count(&self) -> u32 {
+        self.strikes.len()
+    }
+
+    /// Returns a strike by index.
+    pub fn strike(&self, index: u32) -> Option<Strike<'a>> {
+        let offset = self.strikes.get(index)?;
+        Strike::parse(self.data.get(offset.to_usize()..)?, self.number_of_glyphs)
+    }
+
+    /// Returns an iterator over strikes.
+    pub fn strikes(&self) -> impl Iterator<Item = Strike<'a>> + 'a {
+        let table = *self;
+        (0..self.strikes_count()).filter_map(move |i| table.strike(i))
+    }
+
+    /// Selects a best matching strike for the specified pixels per em.
+    ///
+    /// The smallest strike that is not smaller than `pixels_per_em` is preferred.
+    /// Otherwise, the largest one is used.
+    pub fn best_strike(&self, pixels_per_em: u16) -> Option<Strike<'a>> {
+        let mut best: Option<Strike> = None;
+        for strike in self.strikes() {
+            let best_ppem = best.map(|s| s.pixels_per_em).unwrap_or(0);
+            if is_better_strike(pixels_per_em, strike.pixels_per_em, best_ppem) {
+                best = Some(strike);
+            }
+        }
+
+        best.or_else(|| self.strike(0))
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
+}
+
+
+pub(crate) fn parse(
+    table: Table,
+    glyph_id: GlyphId,
+    pixels_per_em: u16,
+) -> Option<RasterGlyphImage> {
+    // Unlike `best_strike`, ignores strikes without the glyph.
+    let mut best: Option<(Strike, GlyphData)> = None;
+    for strike in table.strikes() {
+        if let Some(glyph) = strike.resolved_glyph_data(glyph_id) {
+            let best_ppem = best.map(|(s, _)| s.pixels_per_em).unwrap_or(0);
+            if is_better_strike(pixels_per_em, strike.pixels_per_em, best_ppem) {
+                best = Some((strike, glyph));
+            }
+        }
+    }
+
+    let (strike, glyph) = best?;
 
     // We do ignore `pdf` and `mask` intentionally, because Apple docs state that:
     // 'Support for the 'pdf ' and 'mask' data types and sbixDrawOutlines flag
     // are planned for future releases of iOS and OS X.'
-    let format = match &image_type.to_bytes() {
-        b"png " => RasterImageFormat::PNG,
-        b"dupe" => {
-            // 'The special graphicType of 'dupe' indicates that
-            // the data field contains a glyph ID. The bitmap data for
-            // the indicated glyph should be used for the current glyph.'
-            let glyph_id = GlyphId::parse(image_data)?;
-            return parse(data, number_of_glyphs, glyph_id, pixels_per_em, depth + 1);
-        }
-        _ => {
-            // TODO: support JPEG and TIFF
-            return None;
-        }
+    let (format, (width, height)) = match glyph.graphic_type {
+        GraphicType::Png => (RasterImageFormat::PNG, png_size(glyph.data)?),
+        GraphicType::Jpeg => (RasterImageFormat::JPEG, jpeg_size(glyph.data)?),
+        GraphicType::Tiff => (RasterImageFormat::TIFF, tiff_size(glyph.data)?),
+        _ => return None,
     };
 
-    let (width, height) = png_size(image_data)?;
-
     Some(RasterGlyphImage {
-        x,
-        y,
+        x: glyph.x,
+        y: glyph.y,
         width,
         height,
-        pixels_per_em: max_ppem,
+        pixels_per_em: strike.pixels_per_em,
         format,
-        data: image_data,
+        data: glyph.data,
+        scale: 1.0,
     })
 }
 
 // The `sbix` table doesn't store the image size, so we have to parse it manually.
-// Which is quite simple in case of PNG, but way more complex for JPEG.
-// Therefore we are omitting it for now.
 fn png_size(data: &[u8]) -> Option<(u16, u16)> {
     // PNG stores its size as u32 BE at a fixed offset.
     let mut s = Stream::new_at(data, 16)?;
@@ -119,3 +276,190 @@ fn png_size(data: &[u8]) -> Option<(u16, u16)> {
         u16::try_from(height).ok()?,
     ))
 }
+
+fn jpeg_size(data: &[u8]) -> Option<(u16, u16)> {
+    let mut s = Stream::new(data);
+    if s.read::<u16>()? != 0xFFD8 {
+        return None;
+    }
+
+    // The size is stored in a Start Of Frame segment, which can be preceded by any other segments.
+    loop {
+        if s.read::<u8>()? != 0xFF {
+            return None;
+        }
+
+        let mut marker: u8 = s.read()?;
+        while marker == 0xFF {
+            // Skip fill bytes.
+            marker = s.read()?;
+        }
+
+        match marker {
+            // Standalone markers.
+            0x01 | 0xD0..=0xD7 => continue,
+            // SOF0..SOF15, excluding DHT, JPG and DAC.
+            0xC0..=0xCF if marker != 0xC4 && marker != 0xC8 && marker != 0xCC => {
+                s.skip::<u16>(); // length
+                s.skip::<u8>(); // precision
+                let height: u16 = s.read()?;
+                let width: u16 = s.read()?;
+                return Some((width, height));
+            }
+            // Start of scan or end of image before the frame header.
+            0xD9 | 0xDA => return None,
+            _ => {
+                let len: u16 = s.read()?;
+                s.advance_checked(usize::from(len).checked_sub(2)?)?;
+            }
+        }
+    }
+}
+
+fn tiff_size(data: &[u8]) -> Option<(u16, u16)> {
+    // TIFF can be either little or big endian.
+    let is_le = match data.get(0..4)? {
+        b"II*\0" => true,
+        b"MM\0*" => false,
+        _ => return None,
+    };
+
+    let read_u16 = |offset: usize| -> Option<u16> {
+        let bytes = data.get(offset..offset.checked_add(2)?)?;
+        let bytes = [bytes[0], bytes[1]];
+        Some(if is_le { u16::from_le_bytes(bytes) } else { u16::from_be_bytes(bytes) })
+    };
+
+    let read_u32 = |offset: usize| -> Option<u32> {
+        let bytes = data.get(offset..offset.checked_add(4)?)?;
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        Some(if is_le { u32::from_le_bytes(bytes) } else { u32::from_be_bytes(bytes) })
+    };
+
+    const IMAGE_WIDTH: u16 = 256;
+    const IMAGE_LENGTH: u16 = 257;
+    const SHORT: u16 = 3;
+    const LONG: u16 = 4;
+
+    let ifd_offset = usize::try_from(read_u32(4)?).ok()?;
+    let count = read_u16(ifd_offset)?;
+    let mut width = None;
+    let mut height = None;
+    for i in 0..usize::from(count) {
+        let offset = ifd_offset + 2 + i * 12;
+        let tag = read_u16(offset)?;
+        // Values shorter than 4 bytes are stored at the start of the value field.
+        let value = match read_u16(offset + 2)? {
+            SHORT => u32::from(read_u16(offset + 8)?),
+            LONG => read_u32(offset + 8)?,
+            _ => continue,
+        };
+
+        match tag {
+            IMAGE_WIDTH => width = Some(u16::try_from(value).ok()?),
+            IMAGE_LENGTH => height = Some(u16::try_from(value).ok()?),
+            _ => {}
+        }
+    }
+
+    Some((width?, height?))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn strikes() {
+        let data = writer::convert(&[
+            UInt16(1), // version
+            UInt16(2), // flags
+            UInt32(2), // strikes count
+            UInt32(16), // strike offset [0]
+            UInt32(32), // strike offset [1]
+            // Strike [0]
+            UInt16(20), // pixels per em
+            UInt16(72), // ppi
+            UInt32(16), // glyph data offset [0]
+            UInt32(16), // glyph data offset [1]
+            UInt32(16), // glyph data offset [2]
+            // Strike [1]
+            UInt16(40), // pixels per em
+            UInt16(144), // ppi
+            UInt32(16), // glyph data offset [0]
+            UInt32(27), // glyph data offset [1]
+            UInt32(37), // glyph data offset [2]
+            // Glyph Data [0]
+            Int16(1), // x
+            Int16(-2), // y
+            Raw(b"jpg "), // graphic type
+            UInt8(0xFF), UInt8(0xD8), UInt8(0xFF), // data
+            // Glyph Data [1]
+            Int16(0), // x
+            Int16(0), // y
+            Raw(b"dupe"), // graphic type
+            UInt16(0), // glyph
+        ]);
+
+        let table = Table::parse(&data, NonZeroU16::new(2).unwrap()).unwrap();
+        assert!(table.draw_outlines());
+        assert_eq!(table.strikes_count(), 2);
+        assert_eq!(table.strikes().count(), 2);
+
+        let strike = table.strike(0).unwrap();
+        assert_eq!(strike.ppi(), 72);
+        assert!(strike.glyph_data(GlyphId(0)).is_none());
+        assert!(strike.glyph_data(GlyphId(2)).is_none());
+
+        assert_eq!(table.best_strike(10).unwrap().pixels_per_em(), 20);
+        assert_eq!(table.best_strike(30).unwrap().pixels_per_em(), 40);
+        assert_eq!(table.best_strike(50).unwrap().pixels_per_em(), 40);
+
+        let strike = table.strike(1).unwrap();
+        let glyph = strike.glyph_data(GlyphId(0)).unwrap();
+        assert_eq!((glyph.x, glyph.y), (1, -2));
+        assert_eq!(glyph.graphic_type, GraphicType::Jpeg);
+        assert_eq!(glyph.data, &[0xFF, 0xD8, 0xFF]);
+
+        let dupe = strike.glyph_data(GlyphId(1)).unwrap();
+        assert_eq!(dupe.graphic_type, GraphicType::Dupe);
+        assert_eq!(dupe.dupe_glyph_id(), Some(GlyphId(0)));
+        assert_eq!(strike.resolved_glyph_data(GlyphId(1)), Some(glyph));
+    }
+
+    #[test]
+    fn image_size() {
+        let jpeg = writer::convert(&[
+            UInt16(0xFFD8), // start of image
+            UInt16(0xFFE0), // APP0 marker
+            UInt16(4), // length
+            UInt16(0), // data
+            UInt16(0xFFC2), // SOF2 marker
+            UInt16(11), // length
+            UInt8(8), // precision
+            UInt16(20), // height
+            UInt16(30), // width
+        ]);
+        assert_eq!(jpeg_size(&jpeg), Some((30, 20)));
+        assert_eq!(jpeg_size(&jpeg[..10]), None);
+
+        let tiff = writer::convert(&[
+            Raw(b"MM\0*"), // byte order and magic
+            UInt32(8), // IFD offset
+            // IFD
+            UInt16(2), // count
+            UInt16(256), // tag
+            UInt16(3), // type
+            UInt32(1), // count
+            UInt16(30), UInt16(0), // value
+            UInt16(257), // tag
+            UInt16(4), // type
+            UInt32(1), // count
+            UInt32(20), // value
+        ]);
+        assert_eq!(tiff_size(&tiff), Some((30, 20)));
+    }
+}
@@ -0,0 +1,699 @@
+/*!
+A [Style Attributes table](https://docs.microsoft.com/en-us/typography/opentype/spec/stat) implementation.
+*/
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use crate::{Tag, Variation};
+use crate::parser::{Stream, FromData, Fixed, LazyArray16, Offset, Offset16, Offset32};
+
+
+/// A [design axis record](https://docs.microsoft.com/en-us/typography/opentype/spec/stat#axis-records).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AxisRecord {
+    /// An axis tag.
+    pub tag: Tag,
+    /// An axis name ID in the `name` table.
+    pub name_id: u16,
+    /// A value that specifies the axis ordering for name composition.
+    pub ordering: u16,
+}
+
+impl FromData for AxisRecord {
+    const SIZE: usize = 8;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(AxisRecord {
+            tag: s.read::<Tag>()?,
+            name_id: s.read::<u16>()?,
+            ordering: s.read::<u16>()?,
+        })
+    }
+}
+
+
+/// [Axis value flags](https://docs.microsoft.com/en-us/typography/opentype/spec/stat#flags).
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct AxisValueFlags(pub u16);
+
+impl AxisValueFlags {
+    /// Checks that the value applies to an older sibling font
+    /// and can be ignored by applications that support variations.
+    #[inline]
+    pub fn older_sibling_font_attribute(self) -> bool {
+        self.0 & 0x0001 != 0
+    }
+
+    /// Checks that the value name can be omitted when composing a name.
+    #[inline]
+    pub fn elidable(self) -> bool {
+        self.0 & 0x0002 != 0
+    }
+}
+
+
+/// An axis index and value pair used by `AxisValue::Format4`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AxisValueRecord {
+    /// An index into the design axes.
+    pub axis_index: u16,
+    /// An axis value.
+    pub value: f32,
+}
+
+impl FromData for AxisValueRecord {
+    const SIZE: usize = 6;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(AxisValueRecord {
+            axis_index: s.read::<u16>()?,
+            value: s.read::<Fixed>()?.0,
+        })
+    }
+}
+
+
+/// An [axis value](https://docs.microsoft.com/en-us/typography/opentype/spec/stat#axis-value-tables).
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug)]
+pub enum AxisValue<'a> {
+    /// A single value on a single axis.
+    Format1 {
+        axis_index: u16,
+        flags: AxisValueFlags,
+        value_name_id: u16,
+        value: f32,
+    },
+    /// A range of values on a single axis.
+    Format2 {
+        axis_index: u16,
+        flags: AxisValueFlags,
+        value_name_id: u16,
+        nominal_value: f32,
+        range_min_value: f32,
+        range_max_value: f32,
+    },
+    /// A single value on a single axis with a style-linked value, like Regular and Bold.
+    Format3 {
+        axis_index: u16,
+        flags: AxisValueFlags,
+        value_name_id: u16,
+        value: f32,
+        linked_value: f32,
+    },
+    /// A combination of values on multiple axes.
+    Format4 {
+        flags: AxisValueFlags,
+        value_name_id: u16,
+        values: LazyArray16<'a, AxisValueRecord>,
+    },
+}
+
+impl<'a> AxisValue<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let format: u16 = s.read()?;
+        match format {
+            1 => Some(AxisValue::Format1 {
+                axis_index: s.read()?,
+                flags: AxisValueFlags(s.read()?),
+                value_name_id: s.read()?,
+                value: s.read::<Fixed>()?.0,
+            }),
+            2 => Some(AxisValue::Format2 {
+                axis_index: s.read()?,
+                flags: AxisValueFlags(s.read()?),
+                value_name_id: s.read()?,
+                nominal_value: s.read::<Fixed>()?.0,
+                range_min_value: s.read::<Fixed>()?.0,
+                range_max_value: s.read::<Fixed>()?.0,
+            }),
+            3 => Some(AxisValue::Format3 {
+                axis_index: s.read()?,
+                flags: AxisValueFlags(s.read()?),
+                value_name_id: s.read()?,
+                value: s.read::<Fixed>()?.0,
+                linked_value: s.read::<Fixed>()?.0,
+            }),
+            4 => {
+                let count: u16 = s.read()?;
+                Some(AxisValue::Format4 {
+                    flags: AxisValueFlags(s.read()?),
+                    value_name_id: s.read()?,
+                    values: s.read_array16(count)?,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns value's flags.
+    #[inline]
+    pub fn flags(&self) -> AxisValueFlags {
+        match *self {
+            AxisValue::Format1 { flags, .. } |
+            AxisValue::Format2 { flags, .. } |
+            AxisValue::Format3 { flags, .. } |
+            AxisValue::Format4 { flags, .. } => flags,
+        }
+    }
+
+    /// Returns value's name ID in the `name` table.
+    #[inline]
+    pub fn value_name_id(&self) -> u16 {
+        match *self {
+            AxisValue::Format1 { value_name_id, .. } |
+            AxisValue::Format2 { value_name_id, .. } |
+            AxisValue::Format3 { value_name_id, .. } |
+            AxisValue::Format4 { value_name_id, .. } => value_name_id,
+        }
+    }
+}
+
+
+/// A [Style Attributes table](https://docs.microsoft.com/en-us/typography/opentype/spec/stat).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    axes: &'a [u8],
+    axis_size: u16,
+    axis_count: u16,
+    values_data: &'a [u8],
+    value_offsets: LazyArray16<'a, Offset16>,
+    elided_fallback_name_id: Option<u16>,
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let major_version: u16 = s.read()?;
+        let minor_version: u16 = s.read()?;
+        if major_version != 1 {
+            return None;
+        }
+
+        let axis_size: u16 = s.read()?;
+        let axis_count: u16 = s.read()?;
+        let axes_offset: Option<Offset32> = s.read()?;
+        let value_count: u16 = s.read()?;
+        let values_offset: Option<Offset32> = s.read()?;
+
+        // Version 1.0 is deprecated, but still allowed.
+        let elided_fallback_name_id = if minor_version > 0 { s.read::<u16>() } else { None };
+
+        let mut axes: &[u8] = &[];
+        if let Some(offset) = axes_offset {
+            // A record can be larger than we expect.
+            if usize::from(axis_size) < AxisRecord::SIZE {
+                return None;
+            }
+
+            let len = usize::from(axis_count) * usize::from(axis_size);
+            axes = Stream::new_at(data, offset.to_usize())?.read_bytes(len)?;
+        }
+
+        let mut values_data: &[u8] = &[];
+        let mut value_offsets = LazyArray16::default();
+        if let Some(offset) = values_offset {
+            values_data = data.get(offset.to_usize()..)?;
+            value_offsets = Stream::new(values_data).read_array16(value_count)?;
+        }
+
+        Some(Table {
+            axes,
+            axis_size,
+            axis_count: if axes.is_empty() { 0 } else { axis_count },
+            values_data,
+            value_offsets,
+            elided_fallback_name_id,
+        })
+    }
+
+    /// Returns an iterator over design axes.
+    #[inline]
+    pub fn axes(&self) -> AxisRecords<'a> {
+        AxisRecords {
+            data: self.axes,
+            size: self.axis_size,
+            index: 0,
+            total: self.axis_count,
+        }
+    }
+
+    /// Returns an iterator over axis values.
+    ///
+    /// Values with unknown formats or malformed data are skipped.
+    #[inline]
+    pub fn values(&self) -> AxisValues<'a> {
+        AxisValues {
+            data: self.values_data,
+            offsets: self.value_offsets,
+            index: 0,
+        }
+    }
+
+    /// Returns name IDs that compose a style name for the specified coordinates,
+    /// like `Condensed`, `SemiBold` and `Italic`.
+    ///
+    /// Multi-axis values are preferred, then exact single-axis values and then ranges.
+    /// Axes without a value in `variations` are ignored.
+    /// Names are ordered by design axes ordering and elidable ones are omitted.
+    /// When all names were elided, the elided fallback name ID is used.
+    #[cfg(feature = "std")]
+    pub fn style_name_ids(&self, variations: &[Variation]) -> Vec<u16> {
+        // https://docs.microsoft.com/en-us/typography/opentype/spec/stat#axis-value-tables
+        const DEFAULT_ELIDED_FALLBACK_NAME_ID: u16 = 2;
+
+        let axes: Vec<AxisRecord> = self.axes().collect();
+        let coordinate = |axis_index: u16| {
+            let tag = axes.get(usize::from(axis_index))?.tag;
+            variations.iter().find(|v| v.axis == tag).map(|v| v.value)
+        };
+        let ordering = |axis_index: u16| {
+            axes.get(usize::from(axis_index)).map(|a| a.ordering).unwrap_or(core::u16::MAX)
+        };
+
+        // Values describing older sibling fonts must not be used for this font.
+        let values = || self.values().filter(|v| !v.flags().older_sibling_font_attribute());
+
+        // (ordering, flags, name ID)
+        let mut parts: Vec<(u16, AxisValueFlags, u16)> = Vec::new();
+        let mut covered: Vec<u16> = Vec::new();
+
+        for value in values() {
+            if let AxisValue::Format4 { flags, value_name_id, values } = value {
+                let is_match = !values.is_empty() && values.into_iter().all(|r| {
+                    !covered.contains(&r.axis_index) && coordinate(r.axis_index) == Some(r.value)
+                });
+
+                if is_match {
+                    let order = values.into_iter().map(|r| ordering(r.axis_index)).min();
+                    covered.extend(values.into_iter().map(|r| r.axis_index));
+                    parts.push((order.unwrap_or(0), flags, value_name_id));
+                }
+            }
+        }
+
+        for index in 0..axes.len() as u16 {
+            if covered.contains(&index) {
+                continue;
+            }
+
+            let coord = match coordinate(index) {
+                Some(v) => v,
+                None => continue,
+            };
+
+            let mut exact = None;
+            let mut range = None;
+            for value in values() {
+                match value {
+                    AxisValue::Format1 { axis_index, flags, value_name_id, value } |
+                    AxisValue::Format3 { axis_index, flags, value_name_id, value, .. } => {
+                        if axis_index == index && value == coord {
+                            exact = Some((flags, value_name_id));
+                            break;
+                        }
+                    }
+                    AxisValue::Format2 {
+                        axis_index, flags, value_name_id,
+                        nominal_value, range_min_value, range_max_value,
+                    } => {
+                        if axis_index != index || coord < range_min_value || coord > range_max_value {
+                            continue;
+                        }
+
+                        if nominal_value == coord {
+                            exact = Some((flags, value_name_id));
+                            break;
+                        } else if range.is_none() {
+                            range = Some((flags, value_name_id));
+                        }
+                    }
+                    AxisValue::Format4 { .. } => {}
+                }
+            }
+
+            if let Some((flags, name_id)) = exact.or(range) {
+                parts.push((ordering(index), flags, name_id));
+            }
+        }
+
+        parts.sort_by_key(|p| p.0);
+
+        let mut name_ids: Vec<u16> = parts.iter()
+            .filter(|(_, flags, _)| !flags.elidable())
+            .map(|(_, _, name_id)| *name_id)
+            .collect();
+
+        if name_ids.is_empty() {
+            name_ids.push(self.elided_fallback_name_id.unwrap_or(DEFAULT_ELIDED_FALLBACK_NAME_ID));
+        }
+
+        name_ids
+    }
+
+    /// Returns this font's value on the specified design axis for the specified coordinates.
+    ///
+    /// When `variations` have a value for the axis, it's returned as is.
+    /// Otherwise, only values not describing older sibling fonts are checked:
+    /// multi-axis values, where all other axes match `variations`, first
+    /// and then single-axis values. For ranges, the nominal value is used.
+    ///
+    /// Useful for axes that are not present in the `fvar` table,
+    /// like the `ital` axis of a variable font with a separate italic font file.
+    pub fn axis_value(&self, tag: Tag, variations: &[Variation]) -> Option<f32> {
+        if let Some(v) = variations.iter().find(|v| v.axis == tag) {
+            return Some(v.value);
+        }
+
+        self.matching_axis_value(tag, |axis, value| {
+            variations.iter().any(|v| v.axis == axis && v.value == value)
+        })
+    }
+
+    /// Like `axis_value`, but uses `is_current` to check that a value on another axis
+    /// matches the current coordinates.
+    pub(crate) fn matching_axis_value(
+        &self,
+        tag: Tag,
+        is_current: impl Fn(Tag, f32) -> bool,
+    ) -> Option<f32> {
+        let index = self.axes().position(|a| a.tag == tag)? as u16;
+        let is_match = |r: AxisValueRecord| {
+            self.axes().nth(usize::from(r.axis_index)).map(|a| is_current(a.tag, r.value)) == Some(true)
+        };
+
+        // Values describing older sibling fonts must not be used for this font.
+        let values = || self.values().filter(|v| !v.flags().older_sibling_font_attribute());
+
+        let multi_axis_value = values().find_map(|v| match v {
+            AxisValue::Format4 { values, .. } => {
+                let value = values.into_iter().find(|r| r.axis_index == index)?.value;
+                if values.into_iter().filter(|r| r.axis_index != index).all(is_match) {
+                    Some(value)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        });
+
+        multi_axis_value.or_else(|| values().find_map(|v| match v {
+            AxisValue::Format1 { axis_index, value, .. } |
+            AxisValue::Format3 { axis_index, value, .. } if axis_index == index => Some(value),
+            AxisValue::Format2 { axis_index, nominal_value, .. } if axis_index == index => {
+                Some(nominal_value)
+            }
+            _ => None,
+        }))
+    }
+
+    /// Returns a name ID used when all values are elided, like `Regular`.
+    ///
+    /// Only version 1.1 and above have it.
+    #[inline]
+    pub fn elided_fallback_name_id(&self) -> Option<u16> {
+        self.elided_fallback_name_id
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
+}
+
+
+/// An iterator over design axes.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct AxisRecords<'a> {
+    data: &'a [u8],
+    size: u16,
+    index: u16,
+    total: u16,
+}
+
+impl<'a> Iterator for AxisRecords<'a> {
+    type Item = AxisRecord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.total {
+            return None;
+        }
+
+        let offset = usize::from(self.index) * usize::from(self.size);
+        self.index += 1;
+        Stream::read_at(self.data, offset)
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        usize::from(self.total.saturating_sub(self.index))
+    }
+}
+
+
+/// An iterator over axis values.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct AxisValues<'a> {
+    data: &'a [u8],
+    offsets: LazyArray16<'a, Offset16>,
+    index: u16,
+}
+
+impl<'a> Iterator for AxisValues<'a> {
+    type Item = AxisValue<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.offsets.len() {
+            let offset = self.offsets.get(self.index)?;
+            self.index += 1;
+
+            if let Some(value) = self.data.get(offset.to_usize()..).and_then(AxisValue::parse) {
+                return Some(value);
+            }
+        }
+
+        None
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn basic() {
+        let data = writer::convert(&[
+            UInt16(1), // major version
+            UInt16(2), // minor version
+            UInt16(8), // design axis size
+            UInt16(2), // design axis count
+            UInt32(20), // design axes offset
+            UInt16(4), // axis value count
+            UInt32(36), // axis value offsets offset
+            UInt16(2), // elided fallback name ID
+            // Axis 0
+            Raw(b"wght"), // tag
+            UInt16(256), // name ID
+            UInt16(0), // ordering
+            // Axis 1
+            Raw(b"ital"), // tag
+            UInt16(257), // name ID
+            UInt16(1), // ordering
+            // Axis value offsets
+            UInt16(8), // offset 0
+            UInt16(20), // offset 1
+            UInt16(40), // offset 2
+            UInt16(56), // offset 3
+            // Axis value 0
+            UInt16(1), // format
+            UInt16(0), // axis index
+            UInt16(2), // flags: elidable
+            UInt16(258), // value name ID
+            Int32(400 << 16), // value: 400
+            // Axis value 1
+            UInt16(2), // format
+            UInt16(0), // axis index
+            UInt16(0), // flags
+            UInt16(259), // value name ID
+            Int32(700 << 16), // nominal value: 700
+            Int32(650 << 16), // range min value: 650
+            Int32(750 << 16), // range max value: 750
+            // Axis value 2
+            UInt16(3), // format
+            UInt16(1), // axis index
+            UInt16(1), // flags: older sibling font attribute
+            UInt16(260), // value name ID
+            Int32(0), // value: 0
+            Int32(1 << 16), // linked value: 1
+            // Axis value 3
+            UInt16(4), // format
+            UInt16(2), // axis count
+            UInt16(0), // flags
+            UInt16(261), // value name ID
+            UInt16(0), // axis index
+            Int32(700 << 16), // value: 700
+            UInt16(1), // axis index
+            Int32(1 << 16), // value: 1
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.elided_fallback_name_id(), Some(2));
+
+        let mut axes = table.axes();
+        assert_eq!(axes.next(), Some(AxisRecord { tag: Tag::from_bytes(b"wght"), name_id: 256, ordering: 0 }));
+        assert_eq!(axes.next(), Some(AxisRecord { tag: Tag::from_bytes(b"ital"), name_id: 257, ordering: 1 }));
+        assert_eq!(axes.next(), None);
+
+        let mut values = table.values();
+        match values.next().unwrap() {
+            AxisValue::Format1 { axis_index, flags, value_name_id, value } => {
+                assert_eq!(axis_index, 0);
+                assert!(flags.elidable());
+                assert_eq!(value_name_id, 258);
+                assert_eq!(value, 400.0);
+            }
+            _ => panic!("invalid format"),
+        }
+
+        match values.next().unwrap() {
+            AxisValue::Format2 { nominal_value, range_min_value, range_max_value, .. } => {
+                assert_eq!(nominal_value, 700.0);
+                assert_eq!(range_min_value, 650.0);
+                assert_eq!(range_max_value, 750.0);
+            }
+            _ => panic!("invalid format"),
+        }
+
+        let value = values.next().unwrap();
+        assert!(value.flags().older_sibling_font_attribute());
+        assert_eq!(value.value_name_id(), 260);
+        match value {
+            AxisValue::Format3 { value, linked_value, .. } => {
+                assert_eq!(value, 0.0);
+                assert_eq!(linked_value, 1.0);
+            }
+            _ => panic!("invalid format"),
+        }
+
+        match values.next().unwrap() {
+            AxisValue::Format4 { values, .. } => {
+                assert_eq!(values.len(), 2);
+                assert_eq!(values.get(1), Some(AxisValueRecord { axis_index: 1, value: 1.0 }));
+            }
+            _ => panic!("invalid format"),
+        }
+
+        assert!(values.next().is_none());
+
+        let wght = Tag::from_bytes(b"wght");
+        let ital = Tag::from_bytes(b"ital");
+        assert_eq!(table.axis_value(wght, &[]), Some(400.0));
+        assert_eq!(table.axis_value(wght, &[variation(b"wght", 650.0)]), Some(650.0));
+        // Only an older sibling font value is present.
+        assert_eq!(table.axis_value(ital, &[]), None);
+        assert_eq!(table.axis_value(ital, &[variation(b"wght", 400.0)]), None);
+        // A multi-axis value matches the current weight.
+        assert_eq!(table.axis_value(ital, &[variation(b"wght", 700.0)]), Some(1.0));
+        assert_eq!(table.axis_value(Tag::from_bytes(b"wdth"), &[]), None);
+    }
+
+    fn variation(axis: &[u8; 4], value: f32) -> Variation {
+        Variation { axis: Tag::from_bytes(axis), value }
+    }
+
+    #[test]
+    fn style_name_ids() {
+        let data = writer::convert(&[
+            UInt16(1), // major version
+            UInt16(1), // minor version
+            UInt16(8), // design axis size
+            UInt16(3), // design axis count
+            UInt32(20), // design axes offset
+            UInt16(6), // axis value count
+            UInt32(44), // axis value offsets offset
+            UInt16(300), // elided fallback name ID
+            // Axis 0
+            Raw(b"wght"), // tag
+            UInt16(256), // name ID
+            UInt16(1), // ordering
+            // Axis 1
+            Raw(b"wdth"), // tag
+            UInt16(257), // name ID
+            UInt16(0), // ordering
+            // Axis 2
+            Raw(b"ital"), // tag
+            UInt16(258), // name ID
+            UInt16(2), // ordering
+            // Axis value offsets
+            UInt16(12), // offset 0
+            UInt16(24), // offset 1
+            UInt16(44), // offset 2
+            UInt16(56), // offset 3
+            UInt16(68), // offset 4
+            UInt16(80), // offset 5
+            // Axis value 0
+            UInt16(1), // format
+            UInt16(0), // axis index: wght
+            UInt16(2), // flags: elidable
+            UInt16(301), // value name ID: Regular
+            Int32(400 << 16), // value: 400
+            // Axis value 1
+            UInt16(2), // format
+            UInt16(0), // axis index: wght
+            UInt16(0), // flags
+            UInt16(302), // value name ID: SemiBold
+            Int32(600 << 16), // nominal value: 600
+            Int32(550 << 16), // range min value: 550
+            Int32(650 << 16), // range max value: 650
+            // Axis value 2
+            UInt16(1), // format
+            UInt16(1), // axis index: wdth
+            UInt16(0), // flags
+            UInt16(303), // value name ID: Condensed
+            Int32(75 << 16), // value: 75
+            // Axis value 3
+            UInt16(1), // format
+            UInt16(1), // axis index: wdth
+            UInt16(2), // flags: elidable
+            UInt16(304), // value name ID: Normal
+            Int32(100 << 16), // value: 100
+            // Axis value 4
+            UInt16(1), // format
+            UInt16(2), // axis index: ital
+            UInt16(0), // flags
+            UInt16(305), // value name ID: Italic
+            Int32(1 << 16), // value: 1
+            // Axis value 5
+            UInt16(1), // format
+            UInt16(2), // axis index: ital
+            UInt16(1), // flags: older sibling font attribute
+            UInt16(306), // value name ID: Oblique
+            Int32(1 << 16), // value: 1
+        ]);
+
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(
+            table.style_name_ids(&[
+                variation(b"wght", 580.0), variation(b"wdth", 75.0), variation(b"ital", 1.0),
+            ]),
+            vec![303, 302, 305]
+        );
+        assert_eq!(
+            table.style_name_ids(&[variation(b"wght", 400.0), variation(b"wdth", 100.0)]),
+            vec![300]
+        );
+        assert_eq!(table.style_name_ids(&[variation(b"wght", 500.0)]), vec![300]);
+    }
+}
@@ -1,7 +1,11 @@
-// https://docs.microsoft.com/en-us/typography/opentype/spec/svg
+/*!
+An [SVG table](https://docs.microsoft.com/en-us/typography/opentype/spec/svg) implementation.
+*/
+
+use core::ops::RangeInclusive;
 
 use crate::GlyphId;
-use crate::parser::{Stream, FromData, Offset, Offset32, NumFrom};
+use crate::parser::{Stream, FromData, LazyArray16, Offset, Offset32, NumFrom};
 
 
 #[derive(Clone, Copy)]
@@ -28,23 +32,160 @@ impl FromData for SvgDocumentRecord {
 }
 
 
-pub fn parse(
-    data: &[u8],
-    glyph_id: GlyphId,
-) -> Option<&[u8]> {
-    let mut s = Stream::new(data);
-    s.skip::<u16>(); // version
-    let doc_list_offset = s.read::<Option<Offset32>>()??;
+/// An SVG document.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SvgDocument<'a> {
+    /// Raw document data.
+    ///
+    /// Can be gzip-compressed. See `is_compressed`.
+    pub data: &'a [u8],
+    /// The first glyph ID covered by the document.
+    pub start_glyph_id: GlyphId,
+    /// The last glyph ID covered by the document.
+    pub end_glyph_id: GlyphId,
+}
+
+impl<'a> SvgDocument<'a> {
+    /// Returns a range of glyph IDs covered by the document.
+    ///
+    /// Since a single document can describe multiple glyphs,
+    /// the range can be used to cache parsed documents.
+    /// Each glyph is an element with the `glyph{ID}` id.
+    #[inline]
+    pub fn glyphs_range(&self) -> RangeInclusive<GlyphId> {
+        self.start_glyph_id..=self.end_glyph_id
+    }
+
+    /// Checks that the document is gzip-compressed.
+    #[inline]
+    pub fn is_compressed(&self) -> bool {
+        self.data.starts_with(&[0x1F, 0x8B])
+    }
+}
+
+
+/// An [SVG table](https://docs.microsoft.com/en-us/typography/opentype/spec/svg).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    data: &'a [u8],
+    records: LazyArray16<'a, SvgDocumentRecord>,
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        s.skip::<u16>(); // version
+        let doc_list_offset = s.read::<Option<Offset32>>()??;
+
+        let mut s = Stream::new_at(data, doc_list_offset.to_usize())?;
+        let count: u16 = s.read()?;
+        let records = s.read_array16::<SvgDocumentRecord>(count)?;
+
+        // Document offsets are relative to the document list.
+        Some(Table {
+            data: data.get(doc_list_offset.to_usize()..)?,
+            records,
+        })
+    }
+
+    /// Returns the number of documents.
+    #[inline]
+    pub fn len(&self) -> u16 {
+        self.records.len()
+    }
+
+    /// Checks that the table has no documents.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.records.len() == 0
+    }
+
+    /// Returns a document by index.
+    pub fn get(&self, index: u16) -> Option<SvgDocument<'a>> {
+        let record = self.records.get(index)?;
+        let offset = record.svg_doc_offset?.to_usize();
+        let data = Stream::new_at(self.data, offset)?
+            .read_bytes(usize::num_from(record.svg_doc_length))?;
+
+        Some(SvgDocument {
+            data,
+            start_glyph_id: record.start_glyph_id,
+            end_glyph_id: record.end_glyph_id,
+        })
+    }
+
+    /// Returns a document that covers the glyph.
+    pub fn find(&self, glyph_id: GlyphId) -> Option<SvgDocument<'a>> {
+        // Records are sorted and do not overlap.
+        let (index, _) = self.records.binary_search_by(|r| {
+            use core::cmp::Ordering;
+            if glyph_id < r.start_glyph_id {
+                Ordering::Greater
+            } else if glyph_id > r.end_glyph_id {
+                Ordering::Less
+            } else {
+                Ordering::Equal
+            }
+        })?;
+
+        self.get(index)
+    }
+
+    /// Returns an iterator over documents.
+    pub fn documents(&self) -> impl Iterator<Item = SvgDocument<'a>> + 'a {
+        let table = *self;
+        (0..self.len()).filter_map(move |i| table.get(i))
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
 
-    let mut s = Stream::new_at(data, doc_list_offset.to_usize())?;
-    let count: u16 = s.read()?;
-    let records = s.read_array16::<SvgDocumentRecord>(count)?;
-    let record = records.into_iter()
-        .find(|rec| (rec.start_glyph_id..=rec.end_glyph_id).contains(&glyph_id))?;
+    #[test]
+    fn documents() {
+        let data = writer::convert(&[
+            UInt16(0), // version
+            UInt32(10), // document list offset
+            UInt32(0), // reserved
+            // SVG Document List
+            UInt16(2), // count
+            // SVG Document Record [0]
+            UInt16(1), // start glyph
+            UInt16(3), // end glyph
+            UInt32(26), // document offset
+            UInt32(2), // document length
+            // SVG Document Record [1]
+            UInt16(5), // start glyph
+            UInt16(5), // end glyph
+            UInt32(28), // document offset
+            UInt32(3), // document length
+            // SVG Documents
+            UInt8(b'<'), UInt8(b'>'), // document [0]
+            UInt8(0x1F), UInt8(0x8B), UInt8(0x08), // document [1]
+        ]);
 
-    let svg_offset = record.svg_doc_offset?;
-    let mut s = Stream::new_at(data, doc_list_offset.to_usize() + svg_offset.to_usize())?;
-    let svg_data = s.read_bytes(usize::num_from(record.svg_doc_length))?;
+        let table = Table::parse(&data).unwrap();
+        assert_eq!(table.len(), 2);
+        assert!(table.find(GlyphId(0)).is_none());
+        assert!(table.find(GlyphId(4)).is_none());
 
-    Some(svg_data)
+        let doc = table.find(GlyphId(2)).unwrap();
+        assert_eq!(doc.data, b"<>");
+        assert_eq!(doc.glyphs_range(), GlyphId(1)..=GlyphId(3));
+        assert!(!doc.is_compressed());
+
+        let doc = table.find(GlyphId(5)).unwrap();
+        assert!(doc.is_compressed());
+        assert_eq!(table.documents().count(), 2);
+    }
 }
@@ -0,0 +1,157 @@
+// https://docs.microsoft.com/en-us/typography/opentype/spec/vdmx
+
+use crate::parser::{Stream, FromData, LazyArray16, Offset, Offset16};
+
+
+#[derive(Clone, Copy)]
+struct RatioRange {
+    x_ratio: u8,
+    y_start_ratio: u8,
+    y_end_ratio: u8,
+}
+
+impl RatioRange {
+    // We are assuming square pixels, i.e. 1:1 aspect ratio.
+    #[inline]
+    fn matches_square_pixels(&self) -> bool {
+        // 'The record with xRatio = 0, yStartRatio = 0, yEndRatio = 0
+        // is used as a default and matches any aspect ratio.'
+        if self.x_ratio == 0 {
+            return true;
+        }
+
+        self.y_start_ratio <= self.x_ratio && self.x_ratio <= self.y_end_ratio
+    }
+}
+
+impl FromData for RatioRange {
+    const SIZE: usize = 4;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        s.skip::<u8>(); // bCharSet
+        Some(RatioRange {
+            x_ratio: s.read::<u8>()?,
+            y_start_ratio: s.read::<u8>()?,
+            y_end_ratio: s.read::<u8>()?,
+        })
+    }
+}
+
+
+#[derive(Clone, Copy)]
+struct VTableRecord {
+    y_pel_height: u16,
+    y_max: i16,
+    y_min: i16,
+}
+
+impl FromData for VTableRecord {
+    const SIZE: usize = 6;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(VTableRecord {
+            y_pel_height: s.read::<u16>()?,
+            y_max: s.read::<i16>()?,
+            y_min: s.read::<i16>()?,
+        })
+    }
+}
+
+
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    data: &'a [u8],
+    ratios: LazyArray16<'a, RatioRange>,
+    offsets: LazyArray16<'a, Offset16>,
+}
+
+impl<'a> Table<'a> {
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+
+        let version: u16 = s.read()?;
+        if version > 1 {
+            return None;
+        }
+
+        s.skip::<u16>(); // numRecs
+        let number_of_ratios: u16 = s.read()?;
+        let ratios = s.read_array16::<RatioRange>(number_of_ratios)?;
+        let offsets = s.read_array16::<Offset16>(number_of_ratios)?;
+
+        Some(Table {
+            data,
+            ratios,
+            offsets,
+        })
+    }
+
+    /// Returns `yMax` and `yMin` in pixels for a specified `pixels_per_em`.
+    pub fn metrics(&self, pixels_per_em: u16) -> Option<(i16, i16)> {
+        // 'The first matching ratio should be used.'
+        let index = self.ratios.into_iter().position(|r| r.matches_square_pixels())?;
+        let offset = self.offsets.get(index as u16)?;
+
+        let mut s = Stream::new_at(self.data, offset.to_usize())?;
+        let count: u16 = s.read()?;
+        let start_size: u8 = s.read()?;
+        let end_size: u8 = s.read()?;
+        if pixels_per_em < u16::from(start_size) || pixels_per_em > u16::from(end_size) {
+            return None;
+        }
+
+        let records = s.read_array16::<VTableRecord>(count)?;
+        records.binary_search_by(|r| r.y_pel_height.cmp(&pixels_per_em))
+            .map(|(_, r)| (r.y_max, r.y_min))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics() {
+        let data = &[
+            0x00, 0x01, // version: 1
+            0x00, 0x01, // number of groups: 1
+            0x00, 0x02, // number of ratios: 2
+            // Ratio 0
+            0x01, // charset: 1
+            0x02, // x ratio: 2
+            0x01, // y start ratio: 1
+            0x01, // y end ratio: 1
+            // Ratio 1
+            0x01, // charset: 1
+            0x00, // x ratio: 0
+            0x00, // y start ratio: 0
+            0x00, // y end ratio: 0
+            // Offsets
+            0x00, 0x12, // offset [0]: 18
+            0x00, 0x12, // offset [1]: 18
+            // Group 0
+            0x00, 0x02, // number of records: 2
+            0x0A, // start size: 10
+            0x0B, // end size: 11
+            // Record 0
+            0x00, 0x0A, // y pel height: 10
+            0x00, 0x09, // y max: 9
+            0xFF, 0xFE, // y min: -2
+            // Record 1
+            0x00, 0x0B, // y pel height: 11
+            0x00, 0x0A, // y max: 10
+            0xFF, 0xFD, // y min: -3
+        ];
+
+        let table = Table::parse(data).unwrap();
+        assert_eq!(table.metrics(9), None);
+        assert_eq!(table.metrics(10), Some((9, -2)));
+        assert_eq!(table.metrics(11), Some((10, -3)));
+        assert_eq!(table.metrics(12), None);
+    }
+}
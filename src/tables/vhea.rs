@@ -9,6 +9,9 @@ const TABLE_SIZE: usize = 36;
 const ASCENDER_OFFSET: usize = 4;
 const DESCENDER_OFFSET: usize = 6;
 const LINE_GAP_OFFSET: usize = 8;
+const CARET_SLOPE_RISE_OFFSET: usize = 18;
+const CARET_SLOPE_RUN_OFFSET: usize = 20;
+const CARET_OFFSET_OFFSET: usize = 22;
 const NUM_OF_LONG_VER_METRICS_OFFSET: usize = 34;
 
 
@@ -36,6 +39,21 @@ pub fn line_gap(data: &[u8]) -> i16 {
     Stream::read_at::<i16>(data, LINE_GAP_OFFSET).unwrap_or(0)
 }
 
+#[inline]
+pub fn caret_slope_rise(data: &[u8]) -> i16 {
+    Stream::read_at::<i16>(data, CARET_SLOPE_RISE_OFFSET).unwrap_or(0)
+}
+
+#[inline]
+pub fn caret_slope_run(data: &[u8]) -> i16 {
+    Stream::read_at::<i16>(data, CARET_SLOPE_RUN_OFFSET).unwrap_or(1)
+}
+
+#[inline]
+pub fn caret_offset(data: &[u8]) -> i16 {
+    Stream::read_at::<i16>(data, CARET_OFFSET_OFFSET).unwrap_or(0)
+}
+
 #[inline]
 pub fn num_of_long_ver_metrics(data: &[u8]) -> Option<NonZeroU16> {
     Stream::read_at::<u16>(data, NUM_OF_LONG_VER_METRICS_OFFSET).and_then(NonZeroU16::new)
@@ -55,3 +55,26 @@ impl<'a> Table<'a> {
             .unwrap_or(self.default_y)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_origin() {
+        let data = &[
+            0x00, 0x01, 0x00, 0x00, // version: 1.0
+            0x03, 0x70, // default vertical origin: 880
+            0x00, 0x02, // number of metrics: 2
+            0x00, 0x02, // glyph index [0]: 2
+            0x03, 0x84, // vertical origin [0]: 900
+            0x00, 0x05, // glyph index [1]: 5
+            0x03, 0x5C, // vertical origin [1]: 860
+        ];
+
+        let table = Table::parse(data).unwrap();
+        assert_eq!(table.glyph_y_origin(GlyphId(2)), 900);
+        assert_eq!(table.glyph_y_origin(GlyphId(5)), 860);
+        assert_eq!(table.glyph_y_origin(GlyphId(3)), 880);
+    }
+}
@@ -0,0 +1,324 @@
+/*!
+An [Apple Zapf table](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6Zapf.html)
+implementation.
+
+Glyph groups are not supported.
+*/
+
+use crate::GlyphId;
+use crate::parser::{Stream, FromData, LazyArray16, LazyArray32, NumFrom};
+
+
+/// An offset that marks missing data.
+const NO_DATA: u32 = 0xFFFF_FFFF;
+
+/// Kinds below this value are names. The rest are 16-bit identifiers.
+const FIRST_ID_KIND: u8 = 64;
+
+
+/// A glyph name or identifier.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum KindName<'a> {
+    /// A name, like a PostScript name.
+    Name {
+        /// A name kind. Kind 0 is a universal PostScript name.
+        kind: u8,
+        /// A name.
+        name: &'a str,
+    },
+    /// A numeric identifier, like a CID.
+    Id {
+        /// An identifier kind. Always >= 64.
+        kind: u8,
+        /// An identifier.
+        id: u16,
+    },
+}
+
+impl KindName<'_> {
+    /// Returns name's kind.
+    #[inline]
+    pub fn kind(&self) -> u8 {
+        match *self {
+            KindName::Name { kind, .. } | KindName::Id { kind, .. } => kind,
+        }
+    }
+}
+
+
+/// A list of glyph names and identifiers.
+#[derive(Clone, Copy)]
+#[allow(missing_debug_implementations)]
+pub struct KindNames<'a> {
+    stream: Stream<'a>,
+    count: u16,
+}
+
+impl<'a> Iterator for KindNames<'a> {
+    type Item = KindName<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.count > 0 {
+            self.count -= 1;
+
+            let kind: u8 = self.stream.read()?;
+            if kind >= FIRST_ID_KIND {
+                let id: u16 = self.stream.read()?;
+                return Some(KindName::Id { kind, id });
+            }
+
+            // A Pascal string.
+            let len: u8 = self.stream.read()?;
+            let name = self.stream.read_bytes(usize::from(len))?;
+            // Skip names that are not valid UTF-8.
+            if let Ok(name) = core::str::from_utf8(name) {
+                return Some(KindName::Name { kind, name });
+            }
+        }
+
+        None
+    }
+}
+
+
+/// An AAT feature type and setting pair.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Feature {
+    /// A feature type.
+    pub kind: u16,
+    /// A feature setting.
+    pub setting: u16,
+}
+
+impl FromData for Feature {
+    const SIZE: usize = 4;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(Feature {
+            kind: s.read::<u16>()?,
+            setting: s.read::<u16>()?,
+        })
+    }
+}
+
+
+/// Features required to access a glyph.
+#[derive(Clone, Copy, Debug)]
+pub struct FeatureInfo<'a> {
+    /// A context in which features are applied.
+    pub context: u16,
+    /// A list of AAT features.
+    pub features: LazyArray16<'a, Feature>,
+}
+
+
+/// A glyph information.
+#[derive(Clone, Copy, Debug)]
+pub struct GlyphInfo<'a> {
+    /// Unicode values of a glyph.
+    ///
+    /// Values are in UTF-16, so surrogate pairs are possible.
+    pub unicodes: LazyArray16<'a, u16>,
+    /// Features required to access a glyph.
+    pub feature_info: Option<FeatureInfo<'a>>,
+    names_data: &'a [u8],
+    names_count: u16,
+}
+
+impl<'a> GlyphInfo<'a> {
+    /// Returns an iterator over glyph names and identifiers.
+    ///
+    /// Names that are not valid UTF-8 are skipped.
+    #[inline]
+    pub fn names(&self) -> KindNames<'a> {
+        KindNames { stream: Stream::new(self.names_data), count: self.names_count }
+    }
+
+    /// Returns a universal PostScript name.
+    #[inline]
+    pub fn postscript_name(&self) -> Option<&'a str> {
+        self.names().find_map(|name| match name {
+            KindName::Name { kind: 0, name } => Some(name),
+            _ => None,
+        })
+    }
+
+    /// Returns glyph's text decoded from the Unicode values.
+    ///
+    /// Returns `None` when there are no values or they are not valid UTF-16.
+    #[cfg(feature = "std")]
+    pub(crate) fn text(&self) -> Option<std::string::String> {
+        if self.unicodes.is_empty() {
+            return None;
+        }
+
+        core::char::decode_utf16(self.unicodes).collect::<Result<_, _>>().ok()
+    }
+}
+
+
+/// An [Apple Zapf table](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6Zapf.html).
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    data: &'a [u8],
+    offsets: LazyArray32<'a, u32>,
+}
+
+impl<'a> Table<'a> {
+    pub(crate) fn parse(data: &'a [u8], number_of_glyphs: u16) -> Option<Self> {
+        let mut s = Stream::new(data);
+        let version: u32 = s.read()?;
+        if version != 0x00010000 {
+            return None;
+        }
+
+        s.skip::<u32>(); // extra info offset
+        let offsets = s.read_array32::<u32>(u32::from(number_of_glyphs))?;
+        Some(Table { data, offsets })
+    }
+
+    /// Returns glyph's information.
+    ///
+    /// Returns `None` when glyph has no information or data is malformed.
+    pub fn glyph_info(&self, glyph_id: GlyphId) -> Option<GlyphInfo<'a>> {
+        let offset = self.offsets.get(u32::from(glyph_id.0))?;
+        if offset == NO_DATA {
+            return None;
+        }
+
+        let mut s = Stream::new_at(self.data, usize::num_from(offset))?;
+        s.skip::<u32>(); // group offset
+        let feature_offset: u32 = s.read()?;
+        let unicodes_count: u16 = s.read()?;
+        let unicodes = s.read_array16::<u16>(unicodes_count)?;
+        let names_count: u16 = s.read()?;
+        let names_data = s.tail()?;
+
+        let feature_info = if feature_offset != NO_DATA {
+            let mut s = Stream::new_at(self.data, usize::num_from(feature_offset))?;
+            let context: u16 = s.read()?;
+            let count: u16 = s.read()?;
+            Some(FeatureInfo { context, features: s.read_array16::<Feature>(count)? })
+        } else {
+            None
+        };
+
+        Some(GlyphInfo { unicodes, feature_info, names_data, names_count })
+    }
+
+    /// Returns a glyph ID by its universal PostScript name.
+    pub fn glyph_index_by_name(&self, name: &str) -> Option<GlyphId> {
+        (0..self.offsets.len()).find(|id| {
+            let info = self.glyph_info(GlyphId(*id as u16));
+            info.and_then(|info| info.postscript_name()) == Some(name)
+        }).map(|id| GlyphId(id as u16))
+    }
+}
+
+impl core::fmt::Debug for Table<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Table()")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn basic() {
+        let data = writer::convert(&[
+            UInt32(0x00010000), // version
+            UInt32(0), // extra info offset
+            UInt32(20), // glyph info offset [0]
+            UInt32(0xFFFFFFFF), // glyph info offset [1]: none
+            UInt32(1000), // glyph info offset [2]: out of bounds
+            // Glyph info 0
+            UInt32(0xFFFFFFFF), // group offset
+            UInt32(45), // feature info offset
+            UInt16(1), // number of unicodes
+            UInt16(0x0041), // unicode [0]
+            UInt16(3), // number of kind names
+            UInt8(1), // kind: Apple name
+            UInt8(1), // length
+            Raw(b"A"), // name
+            UInt8(0), // kind: universal PostScript name
+            UInt8(3), // length
+            Raw(b"A.1"), // name
+            UInt8(64), // kind: CID
+            UInt16(34), // id
+            // Feature info
+            UInt16(0), // context
+            UInt16(1), // number of features
+            UInt16(3), // feature type
+            UInt16(2), // feature setting
+        ]);
+
+        let table = Table::parse(&data, 3).unwrap();
+        let info = table.glyph_info(GlyphId(0)).unwrap();
+        assert_eq!(info.unicodes.get(0), Some(0x0041));
+        assert_eq!(info.postscript_name(), Some("A.1"));
+
+        let mut names = info.names();
+        assert_eq!(names.next(), Some(KindName::Name { kind: 1, name: "A" }));
+        assert_eq!(names.next().map(|n| n.kind()), Some(0));
+        assert_eq!(names.next(), Some(KindName::Id { kind: 64, id: 34 }));
+        assert_eq!(names.next(), None);
+
+        let feature_info = info.feature_info.unwrap();
+        assert_eq!(feature_info.context, 0);
+        assert_eq!(feature_info.features.get(0), Some(Feature { kind: 3, setting: 2 }));
+
+        assert!(table.glyph_info(GlyphId(1)).is_none());
+        assert!(table.glyph_info(GlyphId(2)).is_none());
+        assert!(table.glyph_info(GlyphId(3)).is_none());
+
+        assert_eq!(table.glyph_index_by_name("A.1"), Some(GlyphId(0)));
+        assert_eq!(table.glyph_index_by_name("A"), None);
+    }
+
+    #[test]
+    fn surrogate_pairs() {
+        let data = writer::convert(&[
+            UInt32(0x00010000), // version
+            UInt32(0), // extra info offset
+            UInt32(20), // glyph info offset [0]
+            UInt32(45), // glyph info offset [1]
+            UInt32(59), // glyph info offset [2]
+            // Glyph info 0
+            UInt32(0xFFFFFFFF), // group offset
+            UInt32(0xFFFFFFFF), // feature info offset
+            UInt16(2), // number of unicodes
+            UInt16(0xD83D), // unicode [0]: high surrogate
+            UInt16(0xDE00), // unicode [1]: low surrogate
+            UInt16(1), // number of kind names
+            UInt8(0), // kind: universal PostScript name
+            UInt8(7), // length
+            Raw(b"u1F600."), // name
+            // Glyph info 1
+            UInt32(0xFFFFFFFF), // group offset
+            UInt32(0xFFFFFFFF), // feature info offset
+            UInt16(1), // number of unicodes
+            UInt16(0xD83D), // unicode [0]: unpaired high surrogate
+            UInt16(0), // number of kind names
+            // Glyph info 2
+            UInt32(0xFFFFFFFF), // group offset
+            UInt32(0xFFFFFFFF), // feature info offset
+            UInt16(0), // number of unicodes
+            UInt16(0), // number of kind names
+        ]);
+
+        let table = Table::parse(&data, 3).unwrap();
+        let info = table.glyph_info(GlyphId(0)).unwrap();
+        assert_eq!(info.text().unwrap(), "\u{1F600}");
+        assert_eq!(info.postscript_name(), Some("u1F600."));
+        assert_eq!(table.glyph_index_by_name("u1F600."), Some(GlyphId(0)));
+        assert_eq!(table.glyph_info(GlyphId(1)).unwrap().text(), None);
+        assert_eq!(table.glyph_info(GlyphId(2)).unwrap().text(), None);
+    }
+}
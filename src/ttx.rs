@@ -0,0 +1,870 @@
+// https://fonttools.readthedocs.io/en/latest/ttx.html
+
+use core::fmt::{self, Write};
+use core::num::NonZeroU16;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::{Face, GlyphId, Tag};
+use crate::parser::{Stream, NumFrom, U24};
+
+// Seconds between 1904-01-01 and 1970-01-01.
+const MAC_EPOCH_DIFF: i64 = 2082844800;
+
+// Composite glyph flags.
+const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+const ARGS_ARE_XY_VALUES: u16 = 0x0002;
+const WE_HAVE_A_SCALE: u16 = 0x0008;
+const MORE_COMPONENTS: u16 = 0x0020;
+const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+const WE_HAVE_INSTRUCTIONS: u16 = 0x0100;
+// Flags that are not derived from the component data.
+const COMPONENT_FLAGS_MASK: u16 = 0x1E14;
+
+/// Dumps a face into a [TTX](https://fonttools.readthedocs.io/en/latest/ttx.html) XML,
+/// compatible with fontTools.
+///
+/// `head`, `hhea`, `vhea`, `maxp`, `OS/2`, `hmtx`, `vmtx`, `cmap`, `loca`, `glyf`,
+/// `name` and `post` tables are dumped as structured data.
+/// The rest, as well as malformed tables, are dumped as hex data.
+///
+/// Glyph names are taken from `Face::glyph_name`. Missing names are generated
+/// in the fontTools style, like `glyph00001`.
+///
+/// # Example
+///
+/// ```
+/// let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+/// let face = ttf_parser::Face::from_slice(&data, 0).unwrap();
+/// let ttx = ttf_parser::dump_ttx(&face);
+/// assert!(ttx.contains(r#"<unitsPerEm value="1000"/>"#));
+/// assert!(ttx.contains(r#"<map code="0x41" name="glyph00001"/>"#));
+/// ```
+pub fn dump_ttx(face: &Face) -> String {
+    let mut w = String::new();
+    // Writing into a `String` never fails.
+    let _ = write_font(face, &mut w);
+    w
+}
+
+fn write_font(face: &Face, w: &mut String) -> fmt::Result {
+    let names = glyph_names(face);
+
+    writeln!(w, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(w, "<ttFont sfntVersion=\"{}\">", sfnt_version(face))?;
+    writeln!(w)?;
+
+    writeln!(w, "  <GlyphOrder>")?;
+    writeln!(w, "    <!-- The 'id' attribute is only for humans; it is ignored when parsed. -->")?;
+    for (id, name) in names.iter().enumerate() {
+        writeln!(w, "    <GlyphID id=\"{}\" name=\"{}\"/>", id, escape(name))?;
+    }
+    writeln!(w, "  </GlyphOrder>")?;
+    writeln!(w)?;
+
+    let mut tags: Vec<Tag> = face.table_records().map(|r| r.tag).collect();
+    tags.sort();
+    tags.dedup();
+    // fontTools always places `DSIG` last.
+    let dsig = Tag::from_bytes(b"DSIG");
+    if let Some(index) = tags.iter().position(|t| *t == dsig) {
+        tags.remove(index);
+        tags.push(dsig);
+    }
+
+    for tag in tags {
+        let data = match face.table_data(tag) {
+            Some(data) => data,
+            None => continue,
+        };
+
+        let name = tag_to_xml(tag);
+        let mut table = String::new();
+        let parsed = match &tag.to_bytes() {
+            b"head" => write_head(data, &mut table),
+            b"hhea" => write_hhea(data, &mut table),
+            b"vhea" => write_vhea(data, &mut table),
+            b"maxp" => write_maxp(data, &mut table),
+            b"OS/2" => write_os2(data, &mut table),
+            b"hmtx" => write_hmtx(face, &names, &mut table),
+            b"vmtx" => write_vmtx(face, &names, &mut table),
+            b"cmap" => write_cmap(data, &names, &mut table),
+            b"loca" => write_loca(&mut table),
+            b"glyf" => write_glyf(face, &names, &mut table),
+            b"name" => write_name(face, &mut table),
+            b"post" => write_post(data, &mut table),
+            _ => None,
+        };
+
+        if parsed.is_none() {
+            table.clear();
+            write_hexdata(data, 4, &mut table)?;
+        }
+
+        writeln!(w, "  <{}>", name)?;
+        w.push_str(&table);
+        writeln!(w, "  </{}>", name)?;
+        writeln!(w)?;
+    }
+
+    writeln!(w, "</ttFont>")
+}
+
+/// Returns unique glyph names in the glyph order.
+fn glyph_names(face: &Face) -> Vec<String> {
+    let mut names: Vec<String> = Vec::with_capacity(usize::from(face.number_of_glyphs()));
+    for id in 0..face.number_of_glyphs() {
+        let mut name = match face.glyph_name(GlyphId(id)) {
+            Some(name) if !name.is_empty() => String::from(name),
+            _ if id == 0 => String::from(".notdef"),
+            _ => format!("glyph{:05}", id),
+        };
+
+        // Duplicated names are suffixed with `#N`, like in fontTools.
+        if names.contains(&name) {
+            let base = name.clone();
+            let mut n = 1;
+            while names.contains(&name) {
+                name = format!("{}#{}", base, n);
+                n += 1;
+            }
+        }
+
+        names.push(name);
+    }
+
+    names
+}
+
+fn sfnt_version(face: &Face) -> String {
+    let magic: &[u8] = match face.font_data.get(0..4) {
+        Some(magic) if magic != b"ttcf" => magic,
+        // Faces inside a collection don't store an offset to their header.
+        _ if face.table_data(Tag::from_bytes(b"CFF ")).is_some() => b"OTTO",
+        _ => &[0, 1, 0, 0],
+    };
+
+    let mut s = String::new();
+    for c in magic {
+        if c.is_ascii_alphanumeric() {
+            s.push(char::from(*c));
+        } else {
+            s.push_str(&format!("\\x{:02x}", c));
+        }
+    }
+
+    s
+}
+
+/// Converts a tag into an XML element name, like fontTools does.
+fn tag_to_xml(tag: Tag) -> String {
+    let bytes = tag.to_bytes();
+    if &bytes == b"OS/2" {
+        return String::from("OS_2");
+    }
+
+    let trimmed_len = bytes.iter().rposition(|c| *c != b' ').map(|i| i + 1).unwrap_or(0);
+    let trimmed = &bytes[..trimmed_len];
+    let is_identifier = trimmed.first().map(|c| c.is_ascii_alphabetic() || *c == b'_') == Some(true)
+        && trimmed.iter().all(|c| c.is_ascii_alphanumeric() || *c == b'_');
+    if is_identifier {
+        return trimmed.iter().map(|c| char::from(*c)).collect();
+    }
+
+    let mut ident = String::new();
+    for c in trimmed {
+        if c.is_ascii_lowercase() || c.is_ascii_digit() {
+            ident.push('_');
+            ident.push(char::from(*c));
+        } else if c.is_ascii_uppercase() {
+            ident.push(char::from(*c));
+            ident.push('_');
+        } else {
+            ident.push_str(&format!("{:x}", c));
+        }
+    }
+
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+
+    ident
+}
+
+fn escape(text: &str) -> String {
+    let mut s = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => s.push_str("&amp;"),
+            '<' => s.push_str("&lt;"),
+            '>' => s.push_str("&gt;"),
+            '"' => s.push_str("&quot;"),
+            // Control characters other than whitespace are not allowed in XML 1.0.
+            '\t' | '\n' | '\r' => s.push(c),
+            '\u{0}'..='\u{1F}' => {}
+            _ => s.push(c),
+        }
+    }
+
+    s
+}
+
+/// Formats a fixed-point number using the shortest decimal representation
+/// that converts back to the same value.
+fn fixed_to_str(value: i32, fraction_bits: u32) -> String {
+    let scale = f64::from(1u32 << fraction_bits);
+    let number = f64::from(value) / scale;
+    for precision in 1..10 {
+        let s = format!("{:.*}", precision, number);
+        let parsed: f64 = s.parse().unwrap_or(0.0);
+        if (parsed * scale).round() == f64::from(value) {
+            return s;
+        }
+    }
+
+    format!("{}", number)
+}
+
+/// Formats bits in groups of 8, like `00000000 00000011`.
+fn binary(value: u32, bits: u32) -> String {
+    let mut s = String::new();
+    for i in (0..bits).rev() {
+        s.push(if value & (1 << i) != 0 { '1' } else { '0' });
+        if i != 0 && i % 8 == 0 {
+            s.push(' ');
+        }
+    }
+
+    s
+}
+
+/// Formats a `LONGDATETIME` value like Python's `time.asctime`.
+fn timestamp(value: i64) -> String {
+    const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    // Dates before 1970 are clamped, like in fontTools.
+    let seconds = value.saturating_sub(MAC_EPOCH_DIFF).max(0);
+    let days = seconds / 86400;
+    let time = seconds % 86400;
+
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = z / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{} {} {:>2} {:02}:{:02}:{:02} {}",
+        WEEKDAYS[(days % 7) as usize], MONTHS[(month - 1) as usize], day,
+        time / 3600, time % 3600 / 60, time % 60, year,
+    )
+}
+
+fn write_hexdata(data: &[u8], indent: usize, w: &mut String) -> fmt::Result {
+    writeln!(w, "{:indent$}<hexdata>", "", indent = indent)?;
+    write_hex_lines(data, indent + 2, w)?;
+    writeln!(w, "{:indent$}</hexdata>", "", indent = indent)
+}
+
+/// Writes data as lines of 16 bytes, grouped by 4 bytes.
+fn write_hex_lines(data: &[u8], indent: usize, w: &mut String) -> fmt::Result {
+    for line in data.chunks(16) {
+        write!(w, "{:indent$}", "", indent = indent)?;
+        for (i, chunk) in line.chunks(4).enumerate() {
+            if i != 0 {
+                w.push(' ');
+            }
+
+            for c in chunk {
+                write!(w, "{:02x}", c)?;
+            }
+        }
+        writeln!(w)?;
+    }
+
+    Ok(())
+}
+
+fn value<T: fmt::Display>(w: &mut String, name: &str, value: T) {
+    let _ = writeln!(w, "    <{} value=\"{}\"/>", name, value);
+}
+
+fn write_head(data: &[u8], w: &mut String) -> Option<()> {
+    let mut s = Stream::new(data);
+    value(w, "tableVersion", fixed_to_str(s.read::<i32>()?, 16));
+    value(w, "fontRevision", fixed_to_str(s.read::<i32>()?, 16));
+    value(w, "checkSumAdjustment", format!("{:#x}", s.read::<u32>()?));
+    value(w, "magicNumber", format!("{:#x}", s.read::<u32>()?));
+    value(w, "flags", binary(u32::from(s.read::<u16>()?), 16));
+    value(w, "unitsPerEm", s.read::<u16>()?);
+    value(w, "created", timestamp(read_datetime(&mut s)?));
+    value(w, "modified", timestamp(read_datetime(&mut s)?));
+    value(w, "xMin", s.read::<i16>()?);
+    value(w, "yMin", s.read::<i16>()?);
+    value(w, "xMax", s.read::<i16>()?);
+    value(w, "yMax", s.read::<i16>()?);
+    value(w, "macStyle", binary(u32::from(s.read::<u16>()?), 16));
+    value(w, "lowestRecPPEM", s.read::<u16>()?);
+    value(w, "fontDirectionHint", s.read::<i16>()?);
+    value(w, "indexToLocFormat", s.read::<i16>()?);
+    value(w, "glyphDataFormat", s.read::<i16>()?);
+    Some(())
+}
+
+fn read_datetime(s: &mut Stream) -> Option<i64> {
+    let high: i32 = s.read()?;
+    let low: u32 = s.read()?;
+    Some(i64::from(high) << 32 | i64::from(low))
+}
+
+fn write_hhea(data: &[u8], w: &mut String) -> Option<()> {
+    write_metrics_header(data, &[
+        "ascent", "descent", "lineGap", "advanceWidthMax", "minLeftSideBearing",
+        "minRightSideBearing", "xMaxExtent", "caretSlopeRise", "caretSlopeRun", "caretOffset",
+        "reserved0", "reserved1", "reserved2", "reserved3", "metricDataFormat",
+    ], "numberOfHMetrics", w)
+}
+
+fn write_vhea(data: &[u8], w: &mut String) -> Option<()> {
+    write_metrics_header(data, &[
+        "ascent", "descent", "lineGap", "advanceHeightMax", "minTopSideBearing",
+        "minBottomSideBearing", "yMaxExtent", "caretSlopeRise", "caretSlopeRun", "caretOffset",
+        "reserved1", "reserved2", "reserved3", "reserved4", "metricDataFormat",
+    ], "numberOfVMetrics", w)
+}
+
+fn write_metrics_header(
+    data: &[u8],
+    fields: &[&str],
+    number_of_metrics: &str,
+    w: &mut String,
+) -> Option<()> {
+    let mut s = Stream::new(data);
+    value(w, "tableVersion", format!("{:#010x}", s.read::<u32>()?));
+    // `advance*Max` is unsigned, but it doesn't matter for real fonts.
+    for name in fields {
+        let n: i16 = s.read()?;
+        if name.starts_with("advance") {
+            value(w, name, n as u16);
+        } else {
+            value(w, name, n);
+        }
+    }
+    value(w, number_of_metrics, s.read::<u16>()?);
+    Some(())
+}
+
+fn write_maxp(data: &[u8], w: &mut String) -> Option<()> {
+    const FIELDS: &[&str] = &[
+        "maxPoints", "maxContours", "maxCompositePoints", "maxCompositeContours", "maxZones",
+        "maxTwilightPoints", "maxStorage", "maxFunctionDefs", "maxInstructionDefs",
+        "maxStackElements", "maxSizeOfInstructions", "maxComponentElements", "maxComponentDepth",
+    ];
+
+    let mut s = Stream::new(data);
+    let version: u32 = s.read()?;
+    value(w, "tableVersion", format!("{:#x}", version));
+    value(w, "numGlyphs", s.read::<u16>()?);
+    if version == 0x00010000 {
+        for name in FIELDS {
+            value(w, name, s.read::<u16>()?);
+        }
+    }
+
+    Some(())
+}
+
+fn write_os2(data: &[u8], w: &mut String) -> Option<()> {
+    let mut s = Stream::new(data);
+    let version: u16 = s.read()?;
+    value(w, "version", version);
+    value(w, "xAvgCharWidth", s.read::<i16>()?);
+    value(w, "usWeightClass", s.read::<u16>()?);
+    value(w, "usWidthClass", s.read::<u16>()?);
+    value(w, "fsType", binary(u32::from(s.read::<u16>()?), 16));
+    for name in &[
+        "ySubscriptXSize", "ySubscriptYSize", "ySubscriptXOffset", "ySubscriptYOffset",
+        "ySuperscriptXSize", "ySuperscriptYSize", "ySuperscriptXOffset", "ySuperscriptYOffset",
+        "yStrikeoutSize", "yStrikeoutPosition", "sFamilyClass",
+    ] {
+        value(w, name, s.read::<i16>()?);
+    }
+
+    w.push_str("    <panose>\n");
+    for name in &[
+        "bFamilyType", "bSerifStyle", "bWeight", "bProportion", "bContrast",
+        "bStrokeVariation", "bArmStyle", "bLetterForm", "bMidline", "bXHeight",
+    ] {
+        let _ = writeln!(w, "      <{} value=\"{}\"/>", name, s.read::<u8>()?);
+    }
+    w.push_str("    </panose>\n");
+
+    for name in &["ulUnicodeRange1", "ulUnicodeRange2", "ulUnicodeRange3", "ulUnicodeRange4"] {
+        value(w, name, binary(s.read::<u32>()?, 32));
+    }
+
+    let vendor: String = s.read_bytes(4)?.iter().map(|c| char::from(*c)).collect();
+    value(w, "achVendID", escape(&vendor));
+    value(w, "fsSelection", binary(u32::from(s.read::<u16>()?), 16));
+    value(w, "usFirstCharIndex", s.read::<u16>()?);
+    value(w, "usLastCharIndex", s.read::<u16>()?);
+    value(w, "sTypoAscender", s.read::<i16>()?);
+    value(w, "sTypoDescender", s.read::<i16>()?);
+    value(w, "sTypoLineGap", s.read::<i16>()?);
+    value(w, "usWinAscent", s.read::<u16>()?);
+    value(w, "usWinDescent", s.read::<u16>()?);
+
+    if version >= 1 {
+        value(w, "ulCodePageRange1", binary(s.read::<u32>()?, 32));
+        value(w, "ulCodePageRange2", binary(s.read::<u32>()?, 32));
+    }
+
+    if version >= 2 {
+        value(w, "sxHeight", s.read::<i16>()?);
+        value(w, "sCapHeight", s.read::<i16>()?);
+        value(w, "usDefaultChar", s.read::<u16>()?);
+        value(w, "usBreakChar", s.read::<u16>()?);
+        value(w, "usMaxContext", s.read::<u16>()?);
+    }
+
+    if version >= 5 {
+        value(w, "usLowerOpticalPointSize", s.read::<u16>()?);
+        value(w, "usUpperOpticalPointSize", s.read::<u16>()?);
+    }
+
+    Some(())
+}
+
+fn write_hmtx(face: &Face, names: &[String], w: &mut String) -> Option<()> {
+    face.hmtx?;
+    write_metrics(names, "width", "lsb", w, |id| {
+        Some((face.glyph_hor_advance(id)?, face.glyph_hor_side_bearing(id)?))
+    })
+}
+
+fn write_vmtx(face: &Face, names: &[String], w: &mut String) -> Option<()> {
+    face.vmtx?;
+    write_metrics(names, "height", "tsb", w, |id| {
+        Some((face.glyph_ver_advance(id)?, face.glyph_ver_side_bearing(id)?))
+    })
+}
+
+fn write_metrics(
+    names: &[String],
+    advance: &str,
+    side_bearing: &str,
+    w: &mut String,
+    metrics: impl Fn(GlyphId) -> Option<(u16, i16)>,
+) -> Option<()> {
+    // fontTools sorts metrics by glyph name.
+    let mut order: Vec<usize> = (0..names.len()).collect();
+    order.sort_by(|a, b| names[*a].cmp(&names[*b]));
+    for index in order {
+        let (a, sb) = metrics(GlyphId(index as u16))?;
+        let _ = writeln!(
+            w, "    <mtx name=\"{}\" {}=\"{}\" {}=\"{}\"/>",
+            escape(&names[index]), advance, a, side_bearing, sb,
+        );
+    }
+
+    Some(())
+}
+
+fn write_cmap(data: &[u8], names: &[String], w: &mut String) -> Option<()> {
+    let subtables = crate::tables::cmap::parse(data)?;
+    let mut s = Stream::new(data);
+    let version: u16 = s.read()?;
+    let count: u16 = s.read()?;
+    if subtables.count() != usize::from(count) {
+        return None;
+    }
+
+    let _ = writeln!(w, "    <tableVersion version=\"{}\"/>", version);
+    for subtable in subtables {
+        let platform_id: u16 = s.read()?;
+        let encoding_id: u16 = s.read()?;
+        let offset: u32 = s.read()?;
+        let subtable_data = data.get(usize::num_from(offset)..)?;
+
+        let mut s = Stream::new(subtable_data);
+        let format: u16 = s.read()?;
+        let element = match format {
+            0 | 2 | 4 | 6 => {
+                s.skip::<u16>(); // length
+                let language: u16 = s.read()?;
+                format!(
+                    "cmap_format_{} platformID=\"{}\" platEncID=\"{}\" language=\"{}\"",
+                    format, platform_id, encoding_id, language,
+                )
+            }
+            12 | 13 => {
+                let reserved: u16 = s.read()?;
+                let length: u32 = s.read()?;
+                let language: u32 = s.read()?;
+                let groups_count: u32 = s.read()?;
+                format!(
+                    "cmap_format_{} platformID=\"{}\" platEncID=\"{}\" format=\"{}\" \
+                     reserved=\"{}\" length=\"{}\" language=\"{}\" nGroups=\"{}\"",
+                    format, platform_id, encoding_id, format,
+                    reserved, length, language, groups_count,
+                )
+            }
+            14 => {
+                let _ = writeln!(
+                    w, "    <cmap_format_14 platformID=\"{}\" platEncID=\"{}\">",
+                    platform_id, encoding_id,
+                );
+                write_variation_sequences(subtable_data, names, w)?;
+                w.push_str("    </cmap_format_14>\n");
+                continue;
+            }
+            _ => {
+                s.skip::<u16>(); // reserved
+                let length: u32 = s.read()?;
+                let _ = writeln!(
+                    w, "    <cmap_format_unknown platformID=\"{}\" platEncID=\"{}\" format=\"{}\">",
+                    platform_id, encoding_id, format,
+                );
+                write_hexdata(subtable_data.get(..usize::num_from(length))?, 6, w).ok()?;
+                w.push_str("    </cmap_format_unknown>\n");
+                continue;
+            }
+        };
+
+        let _ = writeln!(w, "    <{}>", element);
+        // Mappings to `.notdef` are implicit.
+        let mut is_valid = true;
+        subtable.mappings(|c, id| match names.get(usize::from(id.0)) {
+            Some(name) => {
+                let _ = writeln!(w, "      <map code=\"{:#x}\" name=\"{}\"/>", c, escape(name));
+                true
+            }
+            None => {
+                is_valid = false;
+                false
+            }
+        });
+        if !is_valid {
+            return None;
+        }
+        let _ = writeln!(w, "    </cmap_format_{}>", format);
+    }
+
+    Some(())
+}
+
+fn write_variation_sequences(data: &[u8], names: &[String], w: &mut String) -> Option<()> {
+    let mut s = Stream::new(data);
+    s.skip::<u16>(); // format
+    s.skip::<u32>(); // length
+    let count: u32 = s.read()?;
+    for _ in 0..count {
+        let selector = s.read::<U24>()?.0;
+        let default_offset: u32 = s.read()?;
+        let non_default_offset: u32 = s.read()?;
+
+        if default_offset != 0 {
+            let mut s = Stream::new_at(data, usize::num_from(default_offset))?;
+            let count: u32 = s.read()?;
+            for _ in 0..count {
+                let start = s.read::<U24>()?.0;
+                let additional_count: u8 = s.read()?;
+                for c in start..=start + u32::from(additional_count) {
+                    let _ = writeln!(w, "      <map uv=\"{:#x}\" uvs=\"{:#x}\"/>", c, selector);
+                }
+            }
+        }
+
+        if non_default_offset != 0 {
+            let mut s = Stream::new_at(data, usize::num_from(non_default_offset))?;
+            let count: u32 = s.read()?;
+            for _ in 0..count {
+                let c = s.read::<U24>()?.0;
+                let id: u16 = s.read()?;
+                let _ = writeln!(
+                    w, "      <map uv=\"{:#x}\" uvs=\"{:#x}\" name=\"{}\"/>",
+                    c, selector, escape(names.get(usize::from(id))?),
+                );
+            }
+        }
+    }
+
+    Some(())
+}
+
+fn write_loca(w: &mut String) -> Option<()> {
+    w.push_str("    <!-- The 'loca' table will be calculated by the compiler -->\n");
+    Some(())
+}
+
+fn write_glyf(face: &Face, names: &[String], w: &mut String) -> Option<()> {
+    let loca = face.loca?;
+    let glyf = face.glyf?;
+
+    w.push('\n');
+    w.push_str("    <!-- The xMin, yMin, xMax and yMax values\n");
+    w.push_str("         will be recalculated by the compiler. -->\n");
+    w.push('\n');
+
+    for (id, name) in names.iter().enumerate() {
+        let data = loca.glyph_range(GlyphId(id as u16)).and_then(|range| glyf.get(range));
+        match data {
+            Some(data) if data.len() > 10 => write_glyph(name, data, names, w)?,
+            _ => {
+                let _ = writeln!(
+                    w, "    <TTGlyph name=\"{}\"/><!-- contains no outline data -->",
+                    escape(name),
+                );
+            }
+        }
+        w.push('\n');
+    }
+
+    Some(())
+}
+
+fn write_glyph(name: &str, data: &[u8], names: &[String], w: &mut String) -> Option<()> {
+    let mut s = Stream::new(data);
+    let number_of_contours: i16 = s.read()?;
+    let x_min: i16 = s.read()?;
+    let y_min: i16 = s.read()?;
+    let x_max: i16 = s.read()?;
+    let y_max: i16 = s.read()?;
+
+    if number_of_contours == 0 {
+        let _ = writeln!(w, "    <TTGlyph name=\"{}\"/><!-- contains no outline data -->", escape(name));
+        return Some(());
+    }
+
+    let _ = writeln!(
+        w, "    <TTGlyph name=\"{}\" xMin=\"{}\" yMin=\"{}\" xMax=\"{}\" yMax=\"{}\">",
+        escape(name), x_min, y_min, x_max, y_max,
+    );
+
+    let instructions = if number_of_contours > 0 {
+        let contours_count = NonZeroU16::new(number_of_contours as u16)?;
+        let glyph_data = s.tail()?;
+        let mut s = Stream::new(glyph_data);
+        s.advance(usize::from(contours_count.get()) * 2); // end points
+        let instructions_len: u16 = s.read()?;
+        let instructions = s.read_bytes(usize::from(instructions_len))?;
+
+        let mut is_new_contour = true;
+        for point in crate::glyf::parse_simple_outline(glyph_data, contours_count)? {
+            if is_new_contour {
+                w.push_str("      <contour>\n");
+            }
+
+            let _ = writeln!(
+                w, "        <pt x=\"{}\" y=\"{}\" on=\"{}\"/>",
+                point.x, point.y, u8::from(point.on_curve_point),
+            );
+
+            is_new_contour = point.last_point;
+            if is_new_contour {
+                w.push_str("      </contour>\n");
+            }
+        }
+
+        Some(instructions)
+    } else {
+        write_components(&mut s, names, w)?
+    };
+
+    match instructions {
+        Some(data) if !data.is_empty() => {
+            w.push_str("      <instructions>\n");
+            w.push_str("        <bytecode>\n");
+            write_hex_lines(data, 10, w).ok()?;
+            w.push_str("        </bytecode>\n");
+            w.push_str("      </instructions>\n");
+        }
+        Some(_) => w.push_str("      <instructions/>\n"),
+        None => {}
+    }
+
+    w.push_str("    </TTGlyph>\n");
+    Some(())
+}
+
+/// Writes composite glyph components and returns instructions, if any.
+fn write_components<'a>(
+    s: &mut Stream<'a>,
+    names: &[String],
+    w: &mut String,
+) -> Option<Option<&'a [u8]>> {
+    let mut has_instructions = false;
+    loop {
+        let flags: u16 = s.read()?;
+        let glyph_id: u16 = s.read()?;
+        let name = escape(names.get(usize::from(glyph_id))?);
+
+        let (arg1, arg2) = if flags & ARG_1_AND_2_ARE_WORDS != 0 {
+            if flags & ARGS_ARE_XY_VALUES != 0 {
+                (i32::from(s.read::<i16>()?), i32::from(s.read::<i16>()?))
+            } else {
+                (i32::from(s.read::<u16>()?), i32::from(s.read::<u16>()?))
+            }
+        } else if flags & ARGS_ARE_XY_VALUES != 0 {
+            (i32::from(s.read::<i8>()?), i32::from(s.read::<i8>()?))
+        } else {
+            (i32::from(s.read::<u8>()?), i32::from(s.read::<u8>()?))
+        };
+
+        let _ = write!(w, "      <component glyphName=\"{}\"", name);
+        if flags & ARGS_ARE_XY_VALUES != 0 {
+            let _ = write!(w, " x=\"{}\" y=\"{}\"", arg1, arg2);
+        } else {
+            let _ = write!(w, " firstPt=\"{}\" secondPt=\"{}\"", arg1, arg2);
+        }
+
+        let scale_names: &[&str] = if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+            &["scalex", "scale01", "scale10", "scaley"]
+        } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+            &["scalex", "scaley"]
+        } else if flags & WE_HAVE_A_SCALE != 0 {
+            &["scale"]
+        } else {
+            &[]
+        };
+
+        for scale_name in scale_names {
+            let _ = write!(w, " {}=\"{}\"", scale_name, fixed_to_str(i32::from(s.read::<i16>()?), 14));
+        }
+
+        let _ = writeln!(w, " flags=\"{:#x}\"/>", flags & COMPONENT_FLAGS_MASK);
+
+        has_instructions |= flags & WE_HAVE_INSTRUCTIONS != 0;
+        if flags & MORE_COMPONENTS == 0 {
+            break;
+        }
+    }
+
+    if has_instructions {
+        let len: u16 = s.read()?;
+        Some(Some(s.read_bytes(usize::from(len))?))
+    } else {
+        Some(None)
+    }
+}
+
+fn write_name(face: &Face, w: &mut String) -> Option<()> {
+    face.name?;
+    for name in face.names() {
+        let _ = writeln!(
+            w, "    <namerecord nameID=\"{}\" platformID=\"{}\" platEncID=\"{}\" langID=\"{:#x}\">",
+            name.name_id(), name.platform_id() as u16, name.encoding_id(), name.language_id(),
+        );
+        let _ = writeln!(w, "      {}", escape(&name.to_string_lossy()));
+        w.push_str("    </namerecord>\n");
+    }
+
+    Some(())
+}
+
+fn write_post(data: &[u8], w: &mut String) -> Option<()> {
+    let mut s = Stream::new(data);
+    value(w, "formatType", fixed_to_str(s.read::<i32>()?, 16));
+    value(w, "italicAngle", fixed_to_str(s.read::<i32>()?, 16));
+    value(w, "underlinePosition", s.read::<i16>()?);
+    value(w, "underlineThickness", s.read::<i16>()?);
+    value(w, "isFixedPitch", s.read::<u32>()?);
+    value(w, "minMemType42", s.read::<u32>()?);
+    value(w, "maxMemType42", s.read::<u32>()?);
+    value(w, "minMemType1", s.read::<u32>()?);
+    value(w, "maxMemType1", s.read::<u32>()?);
+    Some(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_names() {
+        assert_eq!(tag_to_xml(Tag::from_bytes(b"head")), "head");
+        assert_eq!(tag_to_xml(Tag::from_bytes(b"OS/2")), "OS_2");
+        assert_eq!(tag_to_xml(Tag::from_bytes(b"CFF ")), "CFF");
+        assert_eq!(tag_to_xml(Tag::from_bytes(b"cvt ")), "cvt");
+        assert_eq!(tag_to_xml(Tag::from_bytes(b"SVG ")), "SVG");
+        assert_eq!(tag_to_xml(Tag::from_bytes(b"1abc")), "_1_a_b_c");
+        assert_eq!(tag_to_xml(Tag::from_bytes(b"a/bC")), "_a2f_bC_");
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(fixed_to_str(0x00010000, 16), "1.0");
+        assert_eq!(fixed_to_str(0x00010042, 16), "1.001");
+        assert_eq!(fixed_to_str(-0x000C0000, 16), "-12.0");
+        assert_eq!(fixed_to_str(0x2000, 14), "0.5");
+        assert_eq!(binary(3, 16), "00000000 00000011");
+        assert_eq!(timestamp(0), "Thu Jan  1 00:00:00 1970");
+        assert_eq!(timestamp(3029529600), "Sat Jan  1 00:00:00 2000");
+        assert_eq!(timestamp(3029529600 + 86400 * 59 + 3661), "Tue Feb 29 01:01:01 2000");
+    }
+
+    #[test]
+    fn hexdata() {
+        let mut s = String::new();
+        write_hexdata(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16], 4, &mut s).unwrap();
+        assert_eq!(s, "    <hexdata>\n      00010203 04050607 08090a0b 0c0d0e0f\n      10\n    </hexdata>\n");
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(escape("a<b>&\"c\""), "a&lt;b&gt;&amp;&quot;c&quot;");
+        assert_eq!(escape("a\tb\nc\r"), "a\tb\nc\r");
+        assert_eq!(escape("a\u{0}b\u{1B}c\u{1F}"), "abc");
+    }
+
+    #[test]
+    fn malformed_cmap_groups() {
+        use crate::writer::TtfType::*;
+
+        let data = crate::writer::convert(&[
+            UInt16(0), // version
+            UInt16(1), // number of subtables
+            // Encoding Record
+            UInt16(3), // platform ID: Windows
+            UInt16(10), // encoding ID: Unicode full repertoire
+            UInt32(12), // subtable offset
+            // Subtable
+            UInt16(12), // format
+            UInt16(0), // reserved
+            UInt32(52), // length
+            UInt32(0), // language
+            UInt32(3), // number of groups
+            // Group [0]
+            UInt32(0x41), // start char code
+            UInt32(0x41), // end char code
+            UInt32(1), // start glyph ID
+            // Group [1]: start > end
+            UInt32(0x50), // start char code
+            UInt32(0x42), // end char code
+            UInt32(1), // start glyph ID
+            // Group [2]: a corrupted end and out of range glyph IDs
+            UInt32(0x60), // start char code
+            UInt32(4278191298), // end char code
+            UInt32(0x10000), // start glyph ID
+        ]);
+
+        let names = vec![String::from(".notdef"), String::from("A")];
+        let mut s = String::new();
+        write_cmap(&data, &names, &mut s).unwrap();
+        assert_eq!(s, "    <tableVersion version=\"0\"/>\n    \
+                       <cmap_format_12 platformID=\"3\" platEncID=\"10\" format=\"12\" \
+                       reserved=\"0\" length=\"52\" language=\"0\" nGroups=\"3\">\n      \
+                       <map code=\"0x41\" name=\"A\"/>\n    \
+                       </cmap_format_12>\n");
+    }
+}
@@ -0,0 +1,304 @@
+// https://docs.microsoft.com/en-us/typography/opentype/spec/os2#ulunicoderange1-bits-031ulunicoderange2-bits-3263ulunicoderange3-bits-6495ulunicoderange4-bits-96127
+
+/// A Unicode block associated with an
+/// [OS/2 Unicode range](https://docs.microsoft.com/en-us/typography/opentype/spec/os2#ur) bit.
+///
+/// A single bit can represent multiple blocks.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct UnicodeBlock {
+    /// A bit index in `ulUnicodeRange1..4`.
+    pub bit: u8,
+
+    /// A block name.
+    pub name: &'static str,
+
+    /// The first code point of the block.
+    pub start: u32,
+
+    /// The last code point of the block.
+    pub end: u32,
+}
+
+impl UnicodeBlock {
+    /// Checks that the block contains a code point.
+    #[inline]
+    pub fn contains(&self, code_point: u32) -> bool {
+        self.start <= code_point && code_point <= self.end
+    }
+
+    /// Returns the number of code points in the block.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        if self.is_empty() { 0 } else { self.end - self.start + 1 }
+    }
+
+    /// Checks that the block has no code points.
+    ///
+    /// Predefined blocks are never empty, but a block can be constructed manually.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+}
+
+/// Blocks sorted by bit.
+///
+/// The *Non-Plane 0* bit (57) covers all supplementary planes, since it indicates
+/// that at least one code point beyond the Basic Multilingual Plane is supported.
+const UNICODE_BLOCKS: &[UnicodeBlock] = &[
+    UnicodeBlock { bit: 0, name: "Basic Latin", start: 0x0000, end: 0x007F },
+    UnicodeBlock { bit: 1, name: "Latin-1 Supplement", start: 0x0080, end: 0x00FF },
+    UnicodeBlock { bit: 2, name: "Latin Extended-A", start: 0x0100, end: 0x017F },
+    UnicodeBlock { bit: 3, name: "Latin Extended-B", start: 0x0180, end: 0x024F },
+    UnicodeBlock { bit: 4, name: "IPA Extensions", start: 0x0250, end: 0x02AF },
+    UnicodeBlock { bit: 4, name: "Phonetic Extensions", start: 0x1D00, end: 0x1D7F },
+    UnicodeBlock { bit: 4, name: "Phonetic Extensions Supplement", start: 0x1D80, end: 0x1DBF },
+    UnicodeBlock { bit: 5, name: "Spacing Modifier Letters", start: 0x02B0, end: 0x02FF },
+    UnicodeBlock { bit: 5, name: "Modifier Tone Letters", start: 0xA700, end: 0xA71F },
+    UnicodeBlock { bit: 6, name: "Combining Diacritical Marks", start: 0x0300, end: 0x036F },
+    UnicodeBlock { bit: 6, name: "Combining Diacritical Marks Supplement", start: 0x1DC0, end: 0x1DFF },
+    UnicodeBlock { bit: 7, name: "Greek and Coptic", start: 0x0370, end: 0x03FF },
+    UnicodeBlock { bit: 8, name: "Coptic", start: 0x2C80, end: 0x2CFF },
+    UnicodeBlock { bit: 9, name: "Cyrillic", start: 0x0400, end: 0x04FF },
+    UnicodeBlock { bit: 9, name: "Cyrillic Supplement", start: 0x0500, end: 0x052F },
+    UnicodeBlock { bit: 9, name: "Cyrillic Extended-A", start: 0x2DE0, end: 0x2DFF },
+    UnicodeBlock { bit: 9, name: "Cyrillic Extended-B", start: 0xA640, end: 0xA69F },
+    UnicodeBlock { bit: 10, name: "Armenian", start: 0x0530, end: 0x058F },
+    UnicodeBlock { bit: 11, name: "Hebrew", start: 0x0590, end: 0x05FF },
+    UnicodeBlock { bit: 12, name: "Vai", start: 0xA500, end: 0xA63F },
+    UnicodeBlock { bit: 13, name: "Arabic", start: 0x0600, end: 0x06FF },
+    UnicodeBlock { bit: 13, name: "Arabic Supplement", start: 0x0750, end: 0x077F },
+    UnicodeBlock { bit: 14, name: "NKo", start: 0x07C0, end: 0x07FF },
+    UnicodeBlock { bit: 15, name: "Devanagari", start: 0x0900, end: 0x097F },
+    UnicodeBlock { bit: 16, name: "Bengali", start: 0x0980, end: 0x09FF },
+    UnicodeBlock { bit: 17, name: "Gurmukhi", start: 0x0A00, end: 0x0A7F },
+    UnicodeBlock { bit: 18, name: "Gujarati", start: 0x0A80, end: 0x0AFF },
+    UnicodeBlock { bit: 19, name: "Oriya", start: 0x0B00, end: 0x0B7F },
+    UnicodeBlock { bit: 20, name: "Tamil", start: 0x0B80, end: 0x0BFF },
+    UnicodeBlock { bit: 21, name: "Telugu", start: 0x0C00, end: 0x0C7F },
+    UnicodeBlock { bit: 22, name: "Kannada", start: 0x0C80, end: 0x0CFF },
+    UnicodeBlock { bit: 23, name: "Malayalam", start: 0x0D00, end: 0x0D7F },
+    UnicodeBlock { bit: 24, name: "Thai", start: 0x0E00, end: 0x0E7F },
+    UnicodeBlock { bit: 25, name: "Lao", start: 0x0E80, end: 0x0EFF },
+    UnicodeBlock { bit: 26, name: "Georgian", start: 0x10A0, end: 0x10FF },
+    UnicodeBlock { bit: 26, name: "Georgian Supplement", start: 0x2D00, end: 0x2D2F },
+    UnicodeBlock { bit: 27, name: "Balinese", start: 0x1B00, end: 0x1B7F },
+    UnicodeBlock { bit: 28, name: "Hangul Jamo", start: 0x1100, end: 0x11FF },
+    UnicodeBlock { bit: 29, name: "Latin Extended Additional", start: 0x1E00, end: 0x1EFF },
+    UnicodeBlock { bit: 29, name: "Latin Extended-C", start: 0x2C60, end: 0x2C7F },
+    UnicodeBlock { bit: 29, name: "Latin Extended-D", start: 0xA720, end: 0xA7FF },
+    UnicodeBlock { bit: 30, name: "Greek Extended", start: 0x1F00, end: 0x1FFF },
+    UnicodeBlock { bit: 31, name: "General Punctuation", start: 0x2000, end: 0x206F },
+    UnicodeBlock { bit: 31, name: "Supplemental Punctuation", start: 0x2E00, end: 0x2E7F },
+    UnicodeBlock { bit: 32, name: "Superscripts And Subscripts", start: 0x2070, end: 0x209F },
+    UnicodeBlock { bit: 33, name: "Currency Symbols", start: 0x20A0, end: 0x20CF },
+    UnicodeBlock { bit: 34, name: "Combining Diacritical Marks For Symbols", start: 0x20D0, end: 0x20FF },
+    UnicodeBlock { bit: 35, name: "Letterlike Symbols", start: 0x2100, end: 0x214F },
+    UnicodeBlock { bit: 36, name: "Number Forms", start: 0x2150, end: 0x218F },
+    UnicodeBlock { bit: 37, name: "Arrows", start: 0x2190, end: 0x21FF },
+    UnicodeBlock { bit: 37, name: "Supplemental Arrows-A", start: 0x27F0, end: 0x27FF },
+    UnicodeBlock { bit: 37, name: "Supplemental Arrows-B", start: 0x2900, end: 0x297F },
+    UnicodeBlock { bit: 37, name: "Miscellaneous Symbols and Arrows", start: 0x2B00, end: 0x2BFF },
+    UnicodeBlock { bit: 38, name: "Mathematical Operators", start: 0x2200, end: 0x22FF },
+    UnicodeBlock { bit: 38, name: "Supplemental Mathematical Operators", start: 0x2A00, end: 0x2AFF },
+    UnicodeBlock { bit: 38, name: "Miscellaneous Mathematical Symbols-A", start: 0x27C0, end: 0x27EF },
+    UnicodeBlock { bit: 38, name: "Miscellaneous Mathematical Symbols-B", start: 0x2980, end: 0x29FF },
+    UnicodeBlock { bit: 39, name: "Miscellaneous Technical", start: 0x2300, end: 0x23FF },
+    UnicodeBlock { bit: 40, name: "Control Pictures", start: 0x2400, end: 0x243F },
+    UnicodeBlock { bit: 41, name: "Optical Character Recognition", start: 0x2440, end: 0x245F },
+    UnicodeBlock { bit: 42, name: "Enclosed Alphanumerics", start: 0x2460, end: 0x24FF },
+    UnicodeBlock { bit: 43, name: "Box Drawing", start: 0x2500, end: 0x257F },
+    UnicodeBlock { bit: 44, name: "Block Elements", start: 0x2580, end: 0x259F },
+    UnicodeBlock { bit: 45, name: "Geometric Shapes", start: 0x25A0, end: 0x25FF },
+    UnicodeBlock { bit: 46, name: "Miscellaneous Symbols", start: 0x2600, end: 0x26FF },
+    UnicodeBlock { bit: 47, name: "Dingbats", start: 0x2700, end: 0x27BF },
+    UnicodeBlock { bit: 48, name: "CJK Symbols And Punctuation", start: 0x3000, end: 0x303F },
+    UnicodeBlock { bit: 49, name: "Hiragana", start: 0x3040, end: 0x309F },
+    UnicodeBlock { bit: 50, name: "Katakana", start: 0x30A0, end: 0x30FF },
+    UnicodeBlock { bit: 50, name: "Katakana Phonetic Extensions", start: 0x31F0, end: 0x31FF },
+    UnicodeBlock { bit: 51, name: "Bopomofo", start: 0x3100, end: 0x312F },
+    UnicodeBlock { bit: 51, name: "Bopomofo Extended", start: 0x31A0, end: 0x31BF },
+    UnicodeBlock { bit: 52, name: "Hangul Compatibility Jamo", start: 0x3130, end: 0x318F },
+    UnicodeBlock { bit: 53, name: "Phags-pa", start: 0xA840, end: 0xA87F },
+    UnicodeBlock { bit: 54, name: "Enclosed CJK Letters And Months", start: 0x3200, end: 0x32FF },
+    UnicodeBlock { bit: 55, name: "CJK Compatibility", start: 0x3300, end: 0x33FF },
+    UnicodeBlock { bit: 56, name: "Hangul Syllables", start: 0xAC00, end: 0xD7AF },
+    UnicodeBlock { bit: 57, name: "Non-Plane 0", start: 0x10000, end: 0x10FFFF },
+    UnicodeBlock { bit: 58, name: "Phoenician", start: 0x10900, end: 0x1091F },
+    UnicodeBlock { bit: 59, name: "CJK Unified Ideographs", start: 0x4E00, end: 0x9FFF },
+    UnicodeBlock { bit: 59, name: "CJK Radicals Supplement", start: 0x2E80, end: 0x2EFF },
+    UnicodeBlock { bit: 59, name: "Kangxi Radicals", start: 0x2F00, end: 0x2FDF },
+    UnicodeBlock { bit: 59, name: "Ideographic Description Characters", start: 0x2FF0, end: 0x2FFF },
+    UnicodeBlock { bit: 59, name: "CJK Unified Ideographs Extension A", start: 0x3400, end: 0x4DBF },
+    UnicodeBlock { bit: 59, name: "CJK Unified Ideographs Extension B", start: 0x20000, end: 0x2A6DF },
+    UnicodeBlock { bit: 59, name: "Kanbun", start: 0x3190, end: 0x319F },
+    UnicodeBlock { bit: 60, name: "Private Use Area (plane 0)", start: 0xE000, end: 0xF8FF },
+    UnicodeBlock { bit: 61, name: "CJK Strokes", start: 0x31C0, end: 0x31EF },
+    UnicodeBlock { bit: 61, name: "CJK Compatibility Ideographs", start: 0xF900, end: 0xFAFF },
+    UnicodeBlock { bit: 61, name: "CJK Compatibility Ideographs Supplement", start: 0x2F800, end: 0x2FA1F },
+    UnicodeBlock { bit: 62, name: "Alphabetic Presentation Forms", start: 0xFB00, end: 0xFB4F },
+    UnicodeBlock { bit: 63, name: "Arabic Presentation Forms-A", start: 0xFB50, end: 0xFDFF },
+    UnicodeBlock { bit: 64, name: "Combining Half Marks", start: 0xFE20, end: 0xFE2F },
+    UnicodeBlock { bit: 65, name: "Vertical Forms", start: 0xFE10, end: 0xFE1F },
+    UnicodeBlock { bit: 65, name: "CJK Compatibility Forms", start: 0xFE30, end: 0xFE4F },
+    UnicodeBlock { bit: 66, name: "Small Form Variants", start: 0xFE50, end: 0xFE6F },
+    UnicodeBlock { bit: 67, name: "Arabic Presentation Forms-B", start: 0xFE70, end: 0xFEFF },
+    UnicodeBlock { bit: 68, name: "Halfwidth And Fullwidth Forms", start: 0xFF00, end: 0xFFEF },
+    UnicodeBlock { bit: 69, name: "Specials", start: 0xFFF0, end: 0xFFFF },
+    UnicodeBlock { bit: 70, name: "Tibetan", start: 0x0F00, end: 0x0FFF },
+    UnicodeBlock { bit: 71, name: "Syriac", start: 0x0700, end: 0x074F },
+    UnicodeBlock { bit: 72, name: "Thaana", start: 0x0780, end: 0x07BF },
+    UnicodeBlock { bit: 73, name: "Sinhala", start: 0x0D80, end: 0x0DFF },
+    UnicodeBlock { bit: 74, name: "Myanmar", start: 0x1000, end: 0x109F },
+    UnicodeBlock { bit: 75, name: "Ethiopic", start: 0x1200, end: 0x137F },
+    UnicodeBlock { bit: 75, name: "Ethiopic Supplement", start: 0x1380, end: 0x139F },
+    UnicodeBlock { bit: 75, name: "Ethiopic Extended", start: 0x2D80, end: 0x2DDF },
+    UnicodeBlock { bit: 76, name: "Cherokee", start: 0x13A0, end: 0x13FF },
+    UnicodeBlock { bit: 77, name: "Unified Canadian Aboriginal Syllabics", start: 0x1400, end: 0x167F },
+    UnicodeBlock { bit: 78, name: "Ogham", start: 0x1680, end: 0x169F },
+    UnicodeBlock { bit: 79, name: "Runic", start: 0x16A0, end: 0x16FF },
+    UnicodeBlock { bit: 80, name: "Khmer", start: 0x1780, end: 0x17FF },
+    UnicodeBlock { bit: 80, name: "Khmer Symbols", start: 0x19E0, end: 0x19FF },
+    UnicodeBlock { bit: 81, name: "Mongolian", start: 0x1800, end: 0x18AF },
+    UnicodeBlock { bit: 82, name: "Braille Patterns", start: 0x2800, end: 0x28FF },
+    UnicodeBlock { bit: 83, name: "Yi Syllables", start: 0xA000, end: 0xA48F },
+    UnicodeBlock { bit: 83, name: "Yi Radicals", start: 0xA490, end: 0xA4CF },
+    UnicodeBlock { bit: 84, name: "Tagalog", start: 0x1700, end: 0x171F },
+    UnicodeBlock { bit: 84, name: "Hanunoo", start: 0x1720, end: 0x173F },
+    UnicodeBlock { bit: 84, name: "Buhid", start: 0x1740, end: 0x175F },
+    UnicodeBlock { bit: 84, name: "Tagbanwa", start: 0x1760, end: 0x177F },
+    UnicodeBlock { bit: 85, name: "Old Italic", start: 0x10300, end: 0x1032F },
+    UnicodeBlock { bit: 86, name: "Gothic", start: 0x10330, end: 0x1034F },
+    UnicodeBlock { bit: 87, name: "Deseret", start: 0x10400, end: 0x1044F },
+    UnicodeBlock { bit: 88, name: "Byzantine Musical Symbols", start: 0x1D000, end: 0x1D0FF },
+    UnicodeBlock { bit: 88, name: "Musical Symbols", start: 0x1D100, end: 0x1D1FF },
+    UnicodeBlock { bit: 88, name: "Ancient Greek Musical Notation", start: 0x1D200, end: 0x1D24F },
+    UnicodeBlock { bit: 89, name: "Mathematical Alphanumeric Symbols", start: 0x1D400, end: 0x1D7FF },
+    UnicodeBlock { bit: 90, name: "Private Use (plane 15)", start: 0xF0000, end: 0xFFFFD },
+    UnicodeBlock { bit: 90, name: "Private Use (plane 16)", start: 0x100000, end: 0x10FFFD },
+    UnicodeBlock { bit: 91, name: "Variation Selectors", start: 0xFE00, end: 0xFE0F },
+    UnicodeBlock { bit: 91, name: "Variation Selectors Supplement", start: 0xE0100, end: 0xE01EF },
+    UnicodeBlock { bit: 92, name: "Tags", start: 0xE0000, end: 0xE007F },
+    UnicodeBlock { bit: 93, name: "Limbu", start: 0x1900, end: 0x194F },
+    UnicodeBlock { bit: 94, name: "Tai Le", start: 0x1950, end: 0x197F },
+    UnicodeBlock { bit: 95, name: "New Tai Lue", start: 0x1980, end: 0x19DF },
+    UnicodeBlock { bit: 96, name: "Buginese", start: 0x1A00, end: 0x1A1F },
+    UnicodeBlock { bit: 97, name: "Glagolitic", start: 0x2C00, end: 0x2C5F },
+    UnicodeBlock { bit: 98, name: "Tifinagh", start: 0x2D30, end: 0x2D7F },
+    UnicodeBlock { bit: 99, name: "Yijing Hexagram Symbols", start: 0x4DC0, end: 0x4DFF },
+    UnicodeBlock { bit: 100, name: "Syloti Nagri", start: 0xA800, end: 0xA82F },
+    UnicodeBlock { bit: 101, name: "Linear B Syllabary", start: 0x10000, end: 0x1007F },
+    UnicodeBlock { bit: 101, name: "Linear B Ideograms", start: 0x10080, end: 0x100FF },
+    UnicodeBlock { bit: 101, name: "Aegean Numbers", start: 0x10100, end: 0x1013F },
+    UnicodeBlock { bit: 102, name: "Ancient Greek Numbers", start: 0x10140, end: 0x1018F },
+    UnicodeBlock { bit: 103, name: "Ugaritic", start: 0x10380, end: 0x1039F },
+    UnicodeBlock { bit: 104, name: "Old Persian", start: 0x103A0, end: 0x103DF },
+    UnicodeBlock { bit: 105, name: "Shavian", start: 0x10450, end: 0x1047F },
+    UnicodeBlock { bit: 106, name: "Osmanya", start: 0x10480, end: 0x104AF },
+    UnicodeBlock { bit: 107, name: "Cypriot Syllabary", start: 0x10800, end: 0x1083F },
+    UnicodeBlock { bit: 108, name: "Kharoshthi", start: 0x10A00, end: 0x10A5F },
+    UnicodeBlock { bit: 109, name: "Tai Xuan Jing Symbols", start: 0x1D300, end: 0x1D35F },
+    UnicodeBlock { bit: 110, name: "Cuneiform", start: 0x12000, end: 0x123FF },
+    UnicodeBlock { bit: 110, name: "Cuneiform Numbers and Punctuation", start: 0x12400, end: 0x1247F },
+    UnicodeBlock { bit: 111, name: "Counting Rod Numerals", start: 0x1D360, end: 0x1D37F },
+    UnicodeBlock { bit: 112, name: "Sundanese", start: 0x1B80, end: 0x1BBF },
+    UnicodeBlock { bit: 113, name: "Lepcha", start: 0x1C00, end: 0x1C4F },
+    UnicodeBlock { bit: 114, name: "Ol Chiki", start: 0x1C50, end: 0x1C7F },
+    UnicodeBlock { bit: 115, name: "Saurashtra", start: 0xA880, end: 0xA8DF },
+    UnicodeBlock { bit: 116, name: "Kayah Li", start: 0xA900, end: 0xA92F },
+    UnicodeBlock { bit: 117, name: "Rejang", start: 0xA930, end: 0xA95F },
+    UnicodeBlock { bit: 118, name: "Cham", start: 0xAA00, end: 0xAA5F },
+    UnicodeBlock { bit: 119, name: "Ancient Symbols", start: 0x10190, end: 0x101CF },
+    UnicodeBlock { bit: 120, name: "Phaistos Disc", start: 0x101D0, end: 0x101FF },
+    UnicodeBlock { bit: 121, name: "Carian", start: 0x102A0, end: 0x102DF },
+    UnicodeBlock { bit: 121, name: "Lycian", start: 0x10280, end: 0x1029F },
+    UnicodeBlock { bit: 121, name: "Lydian", start: 0x10920, end: 0x1093F },
+    UnicodeBlock { bit: 122, name: "Domino Tiles", start: 0x1F030, end: 0x1F09F },
+    UnicodeBlock { bit: 122, name: "Mahjong Tiles", start: 0x1F000, end: 0x1F02F },
+];
+
+
+/// An iterator over Unicode blocks marked as supported in the OS/2 table.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct UnicodeBlocks {
+    bits: [u32; 4],
+    index: usize,
+}
+
+impl UnicodeBlocks {
+    pub(crate) fn new(bits: [u32; 4]) -> Self {
+        UnicodeBlocks { bits, index: 0 }
+    }
+
+    #[inline]
+    fn is_set(&self, bit: u8) -> bool {
+        let bit = usize::from(bit);
+        self.bits[bit / 32] & (1 << (bit % 32)) != 0
+    }
+}
+
+impl Iterator for UnicodeBlocks {
+    type Item = UnicodeBlock;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(block) = UNICODE_BLOCKS.get(self.index) {
+            self.index += 1;
+            if self.is_set(block.bit) {
+                return Some(*block);
+            }
+        }
+
+        None
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocks() {
+        let mut blocks = UnicodeBlocks::new([1 << 0 | 1 << 4, 1 << (59 - 32), 0, 1 << (122 - 96)]);
+        assert_eq!(blocks.next().map(|b| b.name), Some("Basic Latin"));
+        assert_eq!(blocks.next().map(|b| b.name), Some("IPA Extensions"));
+        assert_eq!(blocks.next().map(|b| b.name), Some("Phonetic Extensions"));
+        assert_eq!(blocks.next().map(|b| b.name), Some("Phonetic Extensions Supplement"));
+        assert_eq!(blocks.next().map(|b| b.bit), Some(59));
+        assert_eq!(blocks.nth(5).map(|b| b.name), Some("Kanbun"));
+        assert_eq!(blocks.next().map(|b| b.name), Some("Domino Tiles"));
+        assert_eq!(blocks.next().map(|b| b.name), Some("Mahjong Tiles"));
+        assert_eq!(blocks.next(), None);
+    }
+
+    #[test]
+    fn sorted() {
+        for pair in UNICODE_BLOCKS.windows(2) {
+            assert!(pair[0].bit <= pair[1].bit);
+        }
+
+        for block in UNICODE_BLOCKS {
+            assert!(!block.is_empty());
+        }
+    }
+
+    #[test]
+    fn block_len() {
+        let block = UnicodeBlock { bit: 0, name: "Basic Latin", start: 0x0000, end: 0x007F };
+        assert_eq!(block.len(), 128);
+        assert!(!block.is_empty());
+
+        let block = UnicodeBlock { bit: 0, name: "Empty", start: 0x0080, end: 0x007F };
+        assert_eq!(block.len(), 0);
+        assert!(block.is_empty());
+    }
+
+    #[test]
+    fn non_plane_0() {
+        let block = UnicodeBlocks::new([0, 1 << (57 - 32), 0, 0]).next().unwrap();
+        assert!(block.contains(0x10000));
+        assert!(block.contains(0x1F600));
+        assert!(!block.contains(0xD800));
+        assert!(!block.contains(0xFFFF));
+    }
+}
@@ -0,0 +1,93 @@
+use std::string::String;
+use std::vec::Vec;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{GlyphId, OwnedFace, SvgPathOptions};
+
+/// A font face exported to JavaScript via `wasm-bindgen`.
+///
+/// Provides only the most common queries.
+/// The face is validated on creation and re-derived on each call.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct WasmFace(OwnedFace);
+
+#[wasm_bindgen]
+impl WasmFace {
+    /// Parses a face from the font data.
+    ///
+    /// Throws a string error when the data cannot be parsed.
+    #[wasm_bindgen(constructor)]
+    pub fn new(data: Vec<u8>, index: u32) -> Result<WasmFace, JsValue> {
+        match OwnedFace::from_vec(data, index) {
+            Ok(face) => Ok(WasmFace(face)),
+            Err(e) => Err(JsValue::from_str(&format!("{}", e))),
+        }
+    }
+
+    /// Returns the number of glyphs.
+    pub fn number_of_glyphs(&self) -> u16 {
+        self.0.as_face().number_of_glyphs()
+    }
+
+    /// Returns face's units per EM.
+    pub fn units_per_em(&self) -> Option<u16> {
+        self.0.as_face().units_per_em()
+    }
+
+    /// Returns face's ascender.
+    pub fn ascender(&self) -> i16 {
+        self.0.as_face().ascender()
+    }
+
+    /// Returns face's descender.
+    pub fn descender(&self) -> i16 {
+        self.0.as_face().descender()
+    }
+
+    /// Returns face's line gap.
+    pub fn line_gap(&self) -> i16 {
+        self.0.as_face().line_gap()
+    }
+
+    /// Returns a glyph ID for a Unicode code point.
+    pub fn glyph_index(&self, code_point: u32) -> Option<u16> {
+        let c = core::char::from_u32(code_point)?;
+        self.0.as_face().glyph_index(c).map(|id| id.0)
+    }
+
+    /// Returns glyph's horizontal advance.
+    pub fn glyph_hor_advance(&self, glyph_id: u16) -> Option<u16> {
+        self.0.as_face().glyph_hor_advance(GlyphId(glyph_id))
+    }
+
+    /// Returns glyph's horizontal side bearing.
+    pub fn glyph_hor_side_bearing(&self, glyph_id: u16) -> Option<i16> {
+        self.0.as_face().glyph_hor_side_bearing(GlyphId(glyph_id))
+    }
+
+    /// Returns glyph's outline as an SVG path data in font units.
+    ///
+    /// The Y axis is not flipped.
+    pub fn outline_glyph(&self, glyph_id: u16) -> Option<String> {
+        self.0.as_face().glyph_svg_path(GlyphId(glyph_id), SvgPathOptions::default())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path() {
+        let data = std::fs::read("tests/fonts/demo.ttf").unwrap();
+        let face = WasmFace::new(data, 0).unwrap();
+        assert_eq!(face.glyph_index(0x41), Some(1));
+        assert_eq!(face.glyph_index(0xD800), None);
+        assert_eq!(face.outline_glyph(1).unwrap(),
+                   "M 173 267 L 369 267 L 270 587 L 173 267 Z M 6 0 L 224 656 \
+                    L 320 656 L 541 0 L 452 0 L 390 200 L 151 200 L 85 0 L 6 0 Z");
+    }
+}
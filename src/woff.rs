@@ -0,0 +1,215 @@
+// https://www.w3.org/TR/WOFF/
+
+use std::vec::Vec;
+
+use crate::Tag;
+use crate::sfnt::{pad, padded_len, search_params, SFNT_HEADER_SIZE, TABLE_RECORD_SIZE};
+use crate::parser::{Stream, FromData, NumFrom};
+
+const SIGNATURE: u32 = 0x774F4646; // wOFF
+
+#[derive(Clone, Copy)]
+struct TableDirectoryEntry {
+    tag: Tag,
+    offset: u32,
+    comp_length: u32,
+    orig_length: u32,
+    orig_checksum: u32,
+}
+
+impl FromData for TableDirectoryEntry {
+    const SIZE: usize = 20;
+
+    #[inline]
+    fn parse(data: &[u8]) -> Option<Self> {
+        let mut s = Stream::new(data);
+        Some(TableDirectoryEntry {
+            tag: s.read()?,
+            offset: s.read()?,
+            comp_length: s.read()?,
+            orig_length: s.read()?,
+            orig_checksum: s.read()?,
+        })
+    }
+}
+
+/// Checks that the data is a WOFF 1.0 font.
+#[inline]
+pub fn is_woff(data: &[u8]) -> bool {
+    Stream::read_at::<u32>(data, 0) == Some(SIGNATURE)
+}
+
+/// Converts a WOFF 1.0 font into an sfnt one, which can be passed to `Face::from_slice`.
+///
+/// Metadata and private data blocks are ignored.
+///
+/// Returns `None` when the data is not a WOFF font or it's malformed.
+pub fn decompress_woff(data: &[u8]) -> Option<Vec<u8>> {
+    let mut s = Stream::new(data);
+    if s.read::<u32>()? != SIGNATURE {
+        return None;
+    }
+
+    let flavor: u32 = s.read()?;
+    s.skip::<u32>(); // length
+    let num_tables: u16 = s.read()?;
+    s.skip::<u16>(); // reserved
+    let total_sfnt_size: u32 = s.read()?;
+    s.advance(24); // version, metadata and private data blocks
+    let entries = s.read_array16::<TableDirectoryEntry>(num_tables)?;
+
+    // Tables are stored 4-byte aligned after the table directory.
+    let mut sfnt_len = SFNT_HEADER_SIZE + TABLE_RECORD_SIZE * usize::from(num_tables);
+    for entry in entries {
+        let len = usize::num_from(entry.orig_length);
+        sfnt_len = sfnt_len.checked_add(len.checked_add(3)? & !3)?;
+    }
+
+    // Protects from allocating an arbitrary amount of memory.
+    if sfnt_len > usize::num_from(total_sfnt_size) {
+        return None;
+    }
+
+    // `total_sfnt_size` is untrusted as well, so the capacity is also limited
+    // by the maximum size the input can be decompressed to.
+    let max_len = data.len().saturating_mul(crate::zlib::MAX_COMPRESSION_RATIO);
+    let mut sfnt = Vec::with_capacity(core::cmp::min(sfnt_len, max_len));
+    sfnt.extend_from_slice(&flavor.to_be_bytes());
+    sfnt.extend_from_slice(&num_tables.to_be_bytes());
+    let (search_range, entry_selector, range_shift) = search_params(num_tables, TABLE_RECORD_SIZE as u16);
+    sfnt.extend_from_slice(&search_range.to_be_bytes());
+    sfnt.extend_from_slice(&entry_selector.to_be_bytes());
+    sfnt.extend_from_slice(&range_shift.to_be_bytes());
+
+    let mut offset = SFNT_HEADER_SIZE + TABLE_RECORD_SIZE * usize::from(num_tables);
+    for entry in entries {
+        sfnt.extend_from_slice(&entry.tag.0.to_be_bytes());
+        sfnt.extend_from_slice(&entry.orig_checksum.to_be_bytes());
+        // Can't overflow, because the offset is less than `total_sfnt_size`.
+        sfnt.extend_from_slice(&(offset as u32).to_be_bytes());
+        sfnt.extend_from_slice(&entry.orig_length.to_be_bytes());
+        offset += padded_len(usize::num_from(entry.orig_length));
+    }
+
+    for entry in entries {
+        let comp_data = Stream::new_at(data, usize::num_from(entry.offset))?
+            .read_bytes(usize::num_from(entry.comp_length))?;
+
+        let orig_length = usize::num_from(entry.orig_length);
+        if entry.comp_length == entry.orig_length {
+            sfnt.extend_from_slice(comp_data);
+        } else if entry.comp_length < entry.orig_length {
+            sfnt.extend_from_slice(&crate::zlib::decompress(comp_data, orig_length)?);
+        } else {
+            return None;
+        }
+
+        pad(&mut sfnt);
+    }
+
+    Some(sfnt)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer;
+    use writer::TtfType::*;
+
+    #[test]
+    fn tables() {
+        let data = writer::convert(&[
+            Raw(b"wOFF"), // signature
+            TrueTypeMagic, // flavor
+            UInt32(100), // length
+            UInt16(2), // number of tables
+            UInt16(0), // reserved
+            UInt32(68), // total sfnt size
+            UInt16(1), // major version
+            UInt16(0), // minor version
+            UInt32(0), // metadata offset
+            UInt32(0), // metadata length
+            UInt32(0), // metadata original length
+            UInt32(0), // private data offset
+            UInt32(0), // private data length
+            // Table Directory Entry [0]
+            Raw(b"abcd"), // tag
+            UInt32(84), // offset
+            UInt32(3), // compressed length
+            UInt32(3), // original length
+            UInt32(1), // original checksum
+            // Table Directory Entry [1]
+            Raw(b"efgh"), // tag
+            UInt32(88), // offset
+            UInt32(11), // compressed length
+            UInt32(20), // original length
+            UInt32(2), // original checksum
+            // Table [0]
+            UInt8(1), UInt8(2), UInt8(3), UInt8(0), // data and padding
+            // Table [1]
+            UInt16(0x78DA), // zlib header
+            UInt32(0x4B4CC404), UInt8(0x00), // 20 "a" compressed
+            UInt32(0x4FA60795), // adler32
+            UInt8(0), // padding
+        ]);
+
+        assert!(is_woff(&data));
+        let sfnt = decompress_woff(&data).unwrap();
+        assert_eq!(sfnt, writer::convert(&[
+            TrueTypeMagic, // magic
+            UInt16(2), // numTables
+            UInt16(32), // searchRange
+            UInt16(1), // entrySelector
+            UInt16(0), // rangeShift
+            // Table Record [0]
+            Raw(b"abcd"), // tag
+            UInt32(1), // checksum
+            UInt32(44), // offset
+            UInt32(3), // length
+            // Table Record [1]
+            Raw(b"efgh"), // tag
+            UInt32(2), // checksum
+            UInt32(48), // offset
+            UInt32(20), // length
+            // Tables
+            UInt8(1), UInt8(2), UInt8(3), UInt8(0), // table [0]
+            Raw(&[b'a'; 20]), // table [1]
+        ]));
+
+        // Total sfnt size is too small.
+        let mut data = data;
+        data[19] = 67;
+        assert!(decompress_woff(&data).is_none());
+    }
+
+    #[test]
+    fn huge_sizes() {
+        let data = writer::convert(&[
+            Raw(b"wOFF"), // signature
+            TrueTypeMagic, // flavor
+            UInt32(68), // length
+            UInt16(1), // number of tables
+            UInt16(0), // reserved
+            UInt32(0xFFFF_FFFF), // total sfnt size
+            UInt16(1), // major version
+            UInt16(0), // minor version
+            UInt32(0), // metadata offset
+            UInt32(0), // metadata length
+            UInt32(0), // metadata original length
+            UInt32(0), // private data offset
+            UInt32(0), // private data length
+            // Table Directory Entry [0]
+            Raw(b"abcd"), // tag
+            UInt32(64), // offset
+            UInt32(4), // compressed length
+            UInt32(0xFFFF_0000), // original length
+            UInt32(0), // original checksum
+            // Table [0]
+            UInt32(0), // malformed compressed data
+        ]);
+
+        // Must not preallocate 4GiB.
+        assert!(decompress_woff(&data).is_none());
+    }
+}
@@ -16,6 +16,7 @@ pub enum TtfType {
     UInt8(u8),
     Int16(i16),
     UInt16(u16),
+    UInt24(u32),
     Int32(i32),
     UInt32(u32),
     CFFInt(i32),
@@ -56,6 +57,9 @@ pub fn convert_type(value: TtfType, data: &mut Vec<u8>) {
         TtfType::UInt16(n) => {
             data.extend_from_slice(&u16::to_be_bytes(n));
         }
+        TtfType::UInt24(n) => {
+            data.extend_from_slice(&u32::to_be_bytes(n)[1..]);
+        }
         TtfType::Int32(n) => {
             data.extend_from_slice(&i32::to_be_bytes(n));
         }
@@ -108,3 +112,28 @@ impl Writer {
         convert_type(value, &mut self.data);
     }
 }
+
+/// Creates a `name` table with Macintosh English names.
+pub fn mac_name_table(names: &[(u16, &'static str)]) -> Vec<u8> {
+    let mut records = vec![
+        TtfType::UInt16(0), // format
+        TtfType::UInt16(names.len() as u16), // count
+        TtfType::UInt16(6 + 12 * names.len() as u16), // storage offset
+    ];
+    let mut offset = 0;
+    for (id, name) in names {
+        records.extend_from_slice(&[
+            TtfType::UInt16(1), // platform ID: Macintosh
+            TtfType::UInt16(0), // encoding ID: Roman
+            TtfType::UInt16(0), // language ID: English
+            TtfType::UInt16(*id), // name ID
+            TtfType::UInt16(name.len() as u16), // length
+            TtfType::UInt16(offset), // offset
+        ]);
+        offset += name.len() as u16;
+    }
+    for (_, name) in names {
+        records.push(TtfType::Raw(name.as_bytes()));
+    }
+    convert(&records)
+}
@@ -0,0 +1,294 @@
+//! A minimal zlib decompressor.
+//!
+//! Checksums are not validated.
+
+use std::vec::Vec;
+
+use crate::parser::Stream;
+
+/// The maximum number of Huffman code bits.
+const MAX_BITS: usize = 15;
+
+/// The maximum DEFLATE compression ratio.
+///
+/// Used to limit preallocated memory, since the decompressed length is untrusted.
+pub(crate) const MAX_COMPRESSION_RATIO: usize = 1032;
+
+const LENGTH_BASES: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31,
+    35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+
+const LENGTH_EXTRA_BITS: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2,
+    3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+
+const DISTANCE_BASES: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193,
+    257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+
+const DISTANCE_EXTRA_BITS: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6,
+    7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+
+/// The order of code length code lengths in a dynamic block header.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+
+/// Decompresses zlib data of an exactly known length.
+pub(crate) fn decompress(data: &[u8], len: usize) -> Option<Vec<u8>> {
+    let mut s = Stream::new(data);
+    let cmf: u8 = s.read()?;
+    let flg: u8 = s.read()?;
+    let has_dictionary = flg & 0x20 != 0;
+    if cmf & 0x0F != 8 || (u16::from(cmf) << 8 | u16::from(flg)) % 31 != 0 || has_dictionary {
+        return None;
+    }
+
+    // Deflate can't compress better than 1032:1,
+    // so we don't have to trust the length for preallocation.
+    let mut out = Vec::with_capacity(core::cmp::min(len, data.len().saturating_mul(MAX_COMPRESSION_RATIO)));
+    inflate(&mut BitReader::new(s.tail()?), &mut out, len)?;
+    if out.len() == len { Some(out) } else { None }
+}
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+    buffer: u32,
+    count: u32,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        BitReader { data, offset: 0, buffer: 0, count: 0 }
+    }
+
+    fn bits(&mut self, n: u32) -> Option<u32> {
+        while self.count < n {
+            let byte = *self.data.get(self.offset)?;
+            self.offset += 1;
+            self.buffer |= u32::from(byte) << self.count;
+            self.count += 8;
+        }
+
+        let value = self.buffer & ((1u32 << n) - 1);
+        self.buffer >>= n;
+        self.count -= n;
+        Some(value)
+    }
+
+    /// Discards bits till the byte boundary.
+    fn align(&mut self) {
+        self.buffer = 0;
+        self.count = 0;
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.offset..self.offset.checked_add(len)?)?;
+        self.offset += len;
+        Some(bytes)
+    }
+}
+
+/// A canonical Huffman code.
+struct Huffman {
+    counts: [u16; MAX_BITS + 1],
+    symbols: [u16; 288],
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Option<Self> {
+        let mut counts = [0u16; MAX_BITS + 1];
+        for len in lengths {
+            counts[usize::from(*len)] += 1;
+        }
+        counts[0] = 0;
+
+        let mut offsets = [0u16; MAX_BITS + 1];
+        for i in 1..MAX_BITS {
+            offsets[i + 1] = offsets[i] + counts[i];
+        }
+
+        let mut symbols = [0u16; 288];
+        for (symbol, len) in lengths.iter().enumerate() {
+            if *len != 0 {
+                let offset = &mut offsets[usize::from(*len)];
+                *symbols.get_mut(usize::from(*offset))? = symbol as u16;
+                *offset += 1;
+            }
+        }
+
+        Some(Huffman { counts, symbols })
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Option<u16> {
+        let mut code = 0i32;
+        let mut first = 0i32;
+        let mut index = 0i32;
+        for len in 1..=MAX_BITS {
+            code |= reader.bits(1)? as i32;
+            let count = i32::from(self.counts[len]);
+            if code - count < first {
+                return self.symbols.get((index + code - first) as usize).cloned();
+            }
+
+            index += count;
+            first += count;
+            first <<= 1;
+            code <<= 1;
+        }
+
+        None
+    }
+}
+
+fn inflate(reader: &mut BitReader, out: &mut Vec<u8>, max_len: usize) -> Option<()> {
+    loop {
+        let is_final = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => {
+                reader.align();
+                let len = reader.read_bytes(2)?;
+                let len = usize::from(u16::from_le_bytes([len[0], len[1]]));
+                reader.read_bytes(2)?; // One's complement of the length.
+                if out.len() + len > max_len {
+                    return None;
+                }
+
+                out.extend_from_slice(reader.read_bytes(len)?);
+            }
+            1 => {
+                let mut lengths = [0u8; 288 + 30];
+                for (i, len) in lengths.iter_mut().enumerate() {
+                    *len = match i {
+                        0..=143 => 8,
+                        144..=255 => 9,
+                        256..=279 => 7,
+                        280..=287 => 8,
+                        _ => 5,
+                    };
+                }
+
+                let literals = Huffman::new(&lengths[..288])?;
+                let distances = Huffman::new(&lengths[288..])?;
+                inflate_block(reader, out, max_len, &literals, &distances)?;
+            }
+            2 => {
+                let literals_count = reader.bits(5)? as usize + 257;
+                let distances_count = reader.bits(5)? as usize + 1;
+                let code_lengths_count = reader.bits(4)? as usize + 4;
+
+                let mut code_lengths = [0u8; 19];
+                for i in CODE_LENGTH_ORDER.iter().take(code_lengths_count) {
+                    code_lengths[*i] = reader.bits(3)? as u8;
+                }
+                let code_lengths = Huffman::new(&code_lengths)?;
+
+                let mut lengths = [0u8; 288 + 32];
+                let total = literals_count + distances_count;
+                let mut i = 0;
+                while i < total {
+                    let symbol = code_lengths.decode(reader)?;
+                    let (value, repeat) = match symbol {
+                        0..=15 => (symbol as u8, 1),
+                        16 => (*lengths.get(i.checked_sub(1)?)?, 3 + reader.bits(2)?),
+                        17 => (0, 3 + reader.bits(3)?),
+                        _ => (0, 11 + reader.bits(7)?),
+                    };
+
+                    for _ in 0..repeat {
+                        if i >= total {
+                            return None;
+                        }
+
+                        lengths[i] = value;
+                        i += 1;
+                    }
+                }
+
+                let literals = Huffman::new(&lengths[..literals_count])?;
+                let distances = Huffman::new(&lengths[literals_count..total])?;
+                inflate_block(reader, out, max_len, &literals, &distances)?;
+            }
+            _ => return None,
+        }
+
+        if is_final {
+            return Some(());
+        }
+    }
+}
+
+fn inflate_block(
+    reader: &mut BitReader,
+    out: &mut Vec<u8>,
+    max_len: usize,
+    literals: &Huffman,
+    distances: &Huffman,
+) -> Option<()> {
+    loop {
+        let symbol = literals.decode(reader)?;
+        match symbol {
+            0..=255 => {
+                if out.len() == max_len {
+                    return None;
+                }
+
+                out.push(symbol as u8);
+            }
+            256 => return Some(()),
+            _ => {
+                let index = usize::from(symbol - 257);
+                let len = usize::from(*LENGTH_BASES.get(index)?)
+                    + reader.bits(u32::from(LENGTH_EXTRA_BITS[index]))? as usize;
+
+                let index = usize::from(distances.decode(reader)?);
+                let distance = usize::from(*DISTANCE_BASES.get(index)?)
+                    + reader.bits(u32::from(DISTANCE_EXTRA_BITS[index]))? as usize;
+
+                if distance > out.len() || out.len() + len > max_len {
+                    return None;
+                }
+
+                // Ranges can overlap, so bytes are copied one by one.
+                let start = out.len() - distance;
+                for i in 0..len {
+                    let byte = out[start + i];
+                    out.push(byte);
+                }
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stored_block() {
+        let data = [
+            0x78, 0x01, // zlib header
+            0x01, // final stored block
+            0x03, 0x00, 0xFC, 0xFF, // length and its complement
+            1, 2, 3, // data
+        ];
+
+        assert_eq!(decompress(&data, 3), Some(vec![1, 2, 3]));
+        assert_eq!(decompress(&data, 2), None);
+    }
+
+    #[test]
+    fn fixed_huffman_block() {
+        // "aaaaa" compressed with fixed Huffman codes.
+        let data = [0x78, 0x9C, 0x4B, 0x4C, 0x04, 0x02, 0x00];
+        assert_eq!(decompress(&data, 5), Some(b"aaaaa".to_vec()));
+    }
+}